@@ -149,6 +149,85 @@ export interface DeploymentConfig {
   waitForFinalization?: boolean;
 }
 
+/**
+ * Webhook receiving events shown by `glin-forge watch`
+ */
+export interface WebhookConfig {
+  /** Endpoint events are POSTed to as JSON */
+  url: string;
+
+  /** Event names to deliver (e.g. 'Transfer', 'ContractEmitted'); all when omitted */
+  events?: string[];
+
+  /** Extra request headers, e.g. authorization */
+  headers?: { [name: string]: string };
+
+  /** Retries with exponential backoff (default: 3) */
+  retries?: number;
+}
+
+/**
+ * Contract multiplexed by `glin-forge watch --config`
+ */
+export interface WatchContractConfig {
+  /** Label of the contract's events (default: the address) */
+  name?: string;
+
+  /** Contract address */
+  address: string;
+
+  /** Metadata used to decode the contract's events */
+  abi?: string;
+
+  /** Event names or patterns to show (`Transfer|Approval`, `Code*`, `/regex/`); all when omitted */
+  events?: string[];
+}
+
+/**
+ * Event watching configuration
+ */
+export interface WatchConfig {
+  contracts?: WatchContractConfig[];
+  webhooks?: WebhookConfig[];
+}
+
+/**
+ * Alert evaluated by `glin-forge monitor`
+ */
+export interface AlertConfig {
+  name: string;
+
+  /** e.g. 'Transfer.value > 1000 GLIN', 'Paused', 'no events for 1h' */
+  rule: string;
+
+  /** Names or addresses of `watch.contracts` the rule applies to; all when omitted */
+  contracts?: string[];
+
+  /** URL the alert is POSTed to as JSON */
+  webhook?: string;
+
+  /** Shell command run with GLIN_ALERT, GLIN_ALERT_MESSAGE and GLIN_ALERT_EVENT set */
+  exec?: string;
+
+  /** Stop monitoring with this exit code once triggered */
+  exit?: number;
+
+  /** Seconds before the alert can trigger again (default: 0) */
+  cooldown?: number;
+}
+
+/**
+ * Scripts `glin-forge deploy` and `glin-forge run` execute around deployments,
+ * against the same RPC server
+ */
+export interface HooksConfig {
+  /** Script run before deploying, e.g. to fund the deployer */
+  preDeploy?: string;
+
+  /** Script run after a successful deployment, e.g. to verify or register the contract */
+  postDeploy?: string;
+}
+
 /**
  * Complete glin-forge configuration
  */
@@ -180,6 +259,18 @@ export interface ForgeConfig {
     };
   };
 
+  /** Event watching */
+  watch?: WatchConfig;
+
+  /** Alert rules for `glin-forge monitor` */
+  alerts?: AlertConfig[];
+
+  /** Deployment lifecycle hooks */
+  hooks?: HooksConfig;
+
+  /** Runtime of scripts, hooks and the console (default: the first installed) */
+  runtime?: 'node' | 'bun' | 'deno' | 'embedded';
+
   /** Custom configuration variables */
   vars?: {
     [key: string]: any;
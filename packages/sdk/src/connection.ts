@@ -0,0 +1,43 @@
+import { AxiosRequestConfig } from 'axios';
+
+/**
+ * Generator of JSON-RPC ids unique across the clients of a script, as the server
+ * tracks running calls by id: `cancel` then only reaches the caller's own calls
+ */
+export function requestIds(): () => string {
+  const prefix = Math.random().toString(36).slice(2, 10);
+  let count = 0;
+  return () => `${prefix}-${++count}`;
+}
+
+/**
+ * Connection to the RPC server of "glin-forge run", as given by its environment:
+ * GLIN_FORGE_RPC_SOCKET for a unix socket, otherwise GLIN_FORGE_RPC_URL (or
+ * GLIN_FORGE_RPC_PORT on localhost), authenticated with GLIN_FORGE_RPC_TOKEN
+ */
+export function rpcConnection(timeout: number): { rpcUrl: string; config: AxiosRequestConfig } {
+  const socketPath = process.env.GLIN_FORGE_RPC_SOCKET;
+  const port = process.env.GLIN_FORGE_RPC_PORT;
+  if (!socketPath && !port && !process.env.GLIN_FORGE_RPC_URL) {
+    throw new Error(
+      'glin-forge RPC server not running. ' +
+        'This SDK must be used with "glin-forge run" command.'
+    );
+  }
+
+  const rpcUrl = socketPath
+    ? 'http://localhost'
+    : process.env.GLIN_FORGE_RPC_URL ?? `http://127.0.0.1:${port}`;
+  return {
+    rpcUrl: socketPath ? `unix:${socketPath}` : rpcUrl,
+    config: {
+      baseURL: rpcUrl,
+      socketPath,
+      timeout,
+      headers: {
+        'Content-Type': 'application/json',
+        Authorization: `Bearer ${process.env.GLIN_FORGE_RPC_TOKEN ?? ''}`,
+      },
+    },
+  };
+}
@@ -0,0 +1,67 @@
+/**
+ * Errors of glin-forge RPC calls
+ *
+ * Failed calls reject with a `GlinForgeError` whose `kind` tells failures
+ * apart without parsing messages.
+ */
+
+/** Kind of a failed call, as `data.kind` of the JSON-RPC error */
+export type ErrorKind =
+  | 'network'
+  | 'decode'
+  | 'insufficient_balance'
+  | 'module'
+  | 'transaction'
+  | 'account'
+  | 'not_found'
+  | 'rate_limited'
+  | 'timeout'
+  | 'cancelled'
+  | 'internal'
+  | 'invalid_params';
+
+/** Codes of the error kinds */
+export const ErrorCodes: Record<ErrorKind, number> = {
+  network: -32001,
+  decode: -32002,
+  insufficient_balance: -32003,
+  module: -32004,
+  transaction: -32005,
+  account: -32006,
+  not_found: -32007,
+  rate_limited: -32008,
+  timeout: -32009,
+  cancelled: -32010,
+  internal: -32603,
+  invalid_params: -32602,
+};
+
+export class GlinForgeError extends Error {
+  /** JSON-RPC error code */
+  readonly code: number;
+  readonly kind: ErrorKind;
+  /** Pallet of a `module` error */
+  readonly pallet?: string;
+  /** Pallet error variant of a `module` error, e.g. `ContractTrapped` */
+  readonly error?: string;
+  /** Messages of the whole error chain, outermost first */
+  readonly details: string[];
+  /** The `data` of the JSON-RPC error as sent */
+  readonly data: any;
+
+  constructor(error: { code: number; message: string; data?: any }) {
+    super(error.message || 'RPC call failed');
+    this.name = 'GlinForgeError';
+    this.code = error.code;
+    this.data = error.data;
+    this.kind = error.data?.kind ?? kindOf(error.code);
+    this.pallet = error.data?.pallet;
+    this.error = error.data?.error;
+    this.details = error.data?.details ?? [this.message];
+  }
+}
+
+function kindOf(code: number): ErrorKind {
+  const entry = Object.entries(ErrorCodes).find(([, value]) => value === code);
+  return (entry?.[0] as ErrorKind) ?? 'internal';
+}
@@ -1,4 +1,7 @@
 import axios, { AxiosInstance } from 'axios';
+import { requestIds, rpcConnection } from './connection';
+import type { Readable } from 'stream';
+import { GlinForgeError } from './errors';
 
 // ========================================
 // Types
@@ -23,6 +26,8 @@ export interface WatchOptions {
   limit?: number;
   /** Show events from block number */
   fromBlock?: number;
+  /** Contract metadata path, to decode events in follow mode */
+  metadata?: string;
 }
 
 interface WatchResult {
@@ -31,6 +36,12 @@ interface WatchResult {
   error?: string;
 }
 
+interface SubscribeResult {
+  success: boolean;
+  subscription_id?: string;
+  error?: string;
+}
+
 type EventCallback = (event: ContractEvent) => void;
 
 // ========================================
@@ -74,28 +85,17 @@ type EventCallback = (event: ContractEvent) => void;
 export class EventWatcher {
   private rpcUrl: string;
   private axios: AxiosInstance;
-  private requestId: number = 0;
+  private nextId = requestIds();
   private listeners: Map<string, EventCallback[]> = new Map();
   private running: boolean = false;
   private stopRequested: boolean = false;
+  private subscriptionId?: string;
+  private stream?: Readable;
 
   constructor(private options: WatchOptions) {
-    const port = process.env.GLIN_FORGE_RPC_PORT;
-    if (!port) {
-      throw new Error(
-        'glin-forge RPC server not running. ' +
-          'This SDK must be used with "glin-forge run" command.'
-      );
-    }
-
-    this.rpcUrl = `http://127.0.0.1:${port}`;
-    this.axios = axios.create({
-      baseURL: this.rpcUrl,
-      timeout: 300000, // 5 minutes for long-running watches
-      headers: {
-        'Content-Type': 'application/json',
-      },
-    });
+    const { rpcUrl, config } = rpcConnection(300000); // 5 minutes for long-running watches
+    this.rpcUrl = rpcUrl;
+    this.axios = axios.create(config);
   }
 
   /**
@@ -174,6 +174,14 @@ export class EventWatcher {
   stop(): void {
     this.stopRequested = true;
     this.running = false;
+
+    const subscriptionId = this.subscriptionId;
+    this.subscriptionId = undefined;
+    if (subscriptionId) {
+      this.rpc('unsubscribeEvents', { subscription_id: subscriptionId }).catch(() => {});
+    }
+    this.stream?.destroy();
+    this.stream = undefined;
   }
 
   /**
@@ -185,7 +193,7 @@ export class EventWatcher {
 
   private async fetchHistoricalEvents(): Promise<void> {
     try {
-      const result = await this.rpc('watch', {
+      const result: WatchResult = await this.rpc('watch', {
         address: this.options.address,
         event: this.options.event,
         network: this.options.network,
@@ -208,46 +216,76 @@ export class EventWatcher {
   }
 
   private async watchContinuously(): Promise<void> {
-    while (!this.stopRequested) {
-      try {
-        const result = await this.rpc('watch', {
-          address: this.options.address,
-          event: this.options.event,
-          network: this.options.network,
-          follow: true,
-          limit: this.options.limit || 10,
-          fromBlock: this.options.fromBlock,
-        });
+    try {
+      const result: SubscribeResult = await this.rpc('subscribeEvents', {
+        address: this.options.address,
+        event: this.options.event,
+        network: this.options.network,
+        metadata: this.options.metadata,
+      });
 
-        if (!result.success) {
-          throw new Error(result.error || 'Failed to watch events');
-        }
+      if (!result.success || !result.subscription_id) {
+        throw new Error(result.error || 'Failed to subscribe to events');
+      }
+      this.subscriptionId = result.subscription_id;
 
-        // Emit all events to listeners
-        for (const event of result.events) {
-          this.emitEvent(event);
-        }
+      // Events arrive as Server-Sent Events until unsubscribed
+      const response = await this.axios.get(`/subscriptions/${result.subscription_id}`, {
+        responseType: 'stream',
+        timeout: 0,
+      });
+      this.stream = response.data as Readable;
+      if (this.stopRequested) {
+        this.stream.destroy();
+        return;
+      }
 
-        // If we got events, update fromBlock to avoid duplicates
-        if (result.events.length > 0) {
-          const lastBlock = result.events[result.events.length - 1].blockNumber;
-          this.options.fromBlock = lastBlock + 1;
-        }
+      await new Promise<void>((resolve, reject) => {
+        let buffer = '';
+        this.stream!.setEncoding('utf8');
+        this.stream!.on('data', (chunk: string) => {
+          buffer += chunk;
+          let end;
+          while ((end = buffer.indexOf('\n\n')) !== -1) {
+            const message = buffer.slice(0, end);
+            buffer = buffer.slice(end + 2);
+            try {
+              this.handleMessage(message);
+            } catch (error) {
+              this.stream!.destroy();
+              reject(error);
+              return;
+            }
+          }
+        });
+        this.stream!.on('end', () => resolve());
+        this.stream!.on('close', () => resolve());
+        this.stream!.on('error', (error) => (this.stopRequested ? resolve() : reject(error)));
+      });
+    } finally {
+      this.running = false;
+    }
+  }
 
-        // Small delay before next poll (if not stopped)
-        if (!this.stopRequested) {
-          await new Promise((resolve) => setTimeout(resolve, 1000));
-        }
-      } catch (error) {
-        if (!this.stopRequested) {
-          console.error('Error watching events:', error);
-          // Wait before retrying
-          await new Promise((resolve) => setTimeout(resolve, 5000));
-        }
+  private handleMessage(message: string): void {
+    let kind = 'message';
+    let data = '';
+    for (const line of message.split('\n')) {
+      if (line.startsWith('event:')) {
+        kind = line.slice(6).trim();
+      } else if (line.startsWith('data:')) {
+        data += line.slice(5).trim();
       }
     }
+    if (!data) {
+      return;
+    }
 
-    this.running = false;
+    const payload = JSON.parse(data);
+    if (kind === 'error') {
+      throw new Error(payload.message || 'Event subscription failed');
+    }
+    this.emitEvent(payload as ContractEvent);
   }
 
   private emitEvent(event: ContractEvent): void {
@@ -276,19 +314,17 @@ export class EventWatcher {
     }
   }
 
-  private async rpc(method: string, params: any): Promise<WatchResult> {
+  private async rpc(method: string, params: any): Promise<any> {
     try {
       const response = await this.axios.post('/', {
         jsonrpc: '2.0',
-        id: ++this.requestId,
+        id: this.nextId(),
         method,
         params: [params],
       });
 
       if (response.data.error) {
-        throw new Error(
-          response.data.error.message || 'RPC call failed'
-        );
+        throw new GlinForgeError(response.data.error);
       }
 
       return response.data.result;
@@ -327,6 +363,7 @@ export async function watchEvents(options: {
   follow?: boolean;
   limit?: number;
   fromBlock?: number;
+  metadata?: string;
   onEvent: EventCallback;
 }): Promise<void> {
   const watcher = new EventWatcher({
@@ -336,6 +373,7 @@ export async function watchEvents(options: {
     follow: options.follow,
     limit: options.limit,
     fromBlock: options.fromBlock,
+    metadata: options.metadata,
   });
 
   watcher.on('*', options.onEvent);
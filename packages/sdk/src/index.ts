@@ -1,8 +1,11 @@
 import axios, { AxiosInstance } from 'axios';
+import { requestIds, rpcConnection } from './connection';
 import { EventWatcher, ContractEvent, type WatchOptions } from './events';
 import { ArgumentEncoder, type ContractArg, encodeArgs, balance, decodeBalance, formatBalance } from './encoding';
 import { NetworkHelper, type BalanceInfo, type FaucetResult, type GasEstimate } from './network';
 import { Transaction, type TransactionResult, type TransactionReceipt } from './transaction';
+import { RpcSocket, type SubscriptionNotification, type TransactionProgress } from './socket';
+import { GlinForgeError, ErrorCodes, type ErrorKind } from './errors';
 import { TypedContract, TypedContractFactory, type TypedDeployOptions, type TypedContractOptions, type QueryMethodsOf, type TransactionMethodsOf, type EventNamesOf } from './typed-contract';
 import { defineConfig, mergeConfig, loadConfig, getNetwork, validateConfig, ConfigBuilder, defaultConfig, type ForgeConfig, type NetworkConfig, type PathsConfig, type CompilerConfig, type TypeGenConfig, type TestConfig, type DeploymentConfig, type Config, type Network as NetworkType, type Paths, type Compiler, type TypeGen, type Test, type Deployment } from './config';
 
@@ -30,6 +33,58 @@ export interface DeployResult {
   error?: string;
 }
 
+export interface UploadCodeOptions {
+  wasm: string;
+  network: Network | string;
+  account: Signer | string;
+}
+
+export interface UploadCodeResult {
+  success: boolean;
+  codeHash?: string;
+  txHash?: string;
+  blockHash?: string;
+  error?: string;
+}
+
+export interface InstantiateOptions {
+  codeHash: string;
+  metadata: string;
+  /** Constructor to call (default: `new`, or the first constructor) */
+  constructorName?: string;
+  args?: ContractArg[];
+  value?: number;
+  network: Network | string;
+  account: Signer | string;
+  /** Salt making the address unique: `0x`-prefixed hex or text */
+  salt?: string;
+}
+
+export interface TypegenOptions {
+  /** Metadata JSON (default: found in artifacts/ or target/ink) */
+  metadata?: string;
+  /** Output directory (default: ./types) */
+  output?: string;
+  framework?: 'react' | 'angular';
+  reactQuery?: boolean;
+  mock?: boolean;
+  strictBigint?: boolean;
+  branded?: boolean;
+  /** Also return the content of every generated file */
+  includeContents?: boolean;
+}
+
+export interface GeneratedFile {
+  path: string;
+  content?: string;
+}
+
+export interface TypegenResult {
+  contractName: string;
+  /** Written files, the types module first */
+  files: GeneratedFile[];
+}
+
 export interface CallOptions {
   address: string;
   metadata: string;
@@ -62,6 +117,84 @@ export interface QueryResult {
   error?: string;
 }
 
+/** Methods whose transactions can be signed outside glin-forge */
+export type SignableMethod = 'deploy' | 'uploadCode' | 'instantiate' | 'call';
+
+/** Signs the hex payload for an address, resolving to the hex signature */
+export type RawSigner = (payload: string, address: string) => Promise<string>;
+
+export interface PreparedTransaction {
+  /** Id to submit the signature with */
+  id: string;
+  /** Hex bytes to sign */
+  payload: string;
+  /** Address that must sign */
+  address: string;
+}
+
+export interface SubmitSignedResult {
+  success: boolean;
+  txHash?: string;
+  blockHash?: string;
+  /** Contract instantiated by a deploy or instantiate */
+  address?: string;
+  /** Code stored by a deploy or upload */
+  codeHash?: string;
+  error?: string;
+}
+
+/** A contract recorded in the deployments registry (.glin-forge/deployments.json) */
+export interface DeploymentRecord {
+  address: string;
+  codeHash?: string;
+  txHash?: string;
+  /** Block the contract was instantiated in */
+  block?: number;
+  blockHash?: string;
+}
+
+/** A value of a contract's storage */
+export interface StorageItem {
+  /** Hex storage key as the contract uses it, when known */
+  key?: string;
+  /** Hex key in the contract's child trie */
+  trieKey: string;
+  /** Hex SCALE-encoded value */
+  value: string;
+}
+
+export interface StorageItemsOptions {
+  /** Hex prefix of the storage keys to list (default: all) */
+  prefix?: string;
+  /** Items to return at most (default: 100, at most 1000) */
+  count?: number;
+  /** `nextKey` of the previous page */
+  startKey?: string;
+  network?: string;
+}
+
+export interface StorageItemsPage {
+  items: StorageItem[];
+  /** Pass as `startKey` for the next page; undefined after the last one */
+  nextKey?: string;
+}
+
+/** State of the glin-forge server and its connection to a network */
+export interface HealthInfo {
+  /** Whether the server reached the network's node */
+  healthy: boolean;
+  /** glin-forge version of the server */
+  version: string;
+  /** RPC methods the server supports */
+  methods: string[];
+  network: string;
+  rpc: string;
+  /** Chain head, when the node was reached */
+  blockNumber?: number;
+  blockHash?: string;
+  error?: string;
+}
+
 // ========================================
 // Enums
 // ========================================
@@ -88,43 +221,80 @@ export enum Signer {
 class GlinForgeClient {
   private rpcUrl: string;
   private axios: AxiosInstance;
-  private requestId: number = 0;
+  private nextId = requestIds();
+  private timeout: number = 120000; // 2 minutes
 
   constructor() {
-    const port = process.env.GLIN_FORGE_RPC_PORT;
-    if (!port) {
-      throw new Error(
-        'glin-forge RPC server not running. ' +
-          'This SDK must be used with "glin-forge run" command.'
-      );
-    }
-
-    this.rpcUrl = `http://127.0.0.1:${port}`;
-    this.axios = axios.create({
-      baseURL: this.rpcUrl,
-      timeout: 120000, // 2 minutes
-      headers: {
-        'Content-Type': 'application/json',
-      },
-    });
+    const { rpcUrl, config } = rpcConnection(this.timeout);
+    this.rpcUrl = rpcUrl;
+    this.axios = axios.create(config);
   }
 
   private async rpc(method: string, params: any): Promise<any> {
+    const id = this.nextId();
     try {
       const response = await this.axios.post('/', {
         jsonrpc: '2.0',
-        id: ++this.requestId,
+        id,
         method,
         params: [params],
+        // The server gives up when this client does
+        timeout: this.timeout / 1000,
       });
 
       if (response.data.error) {
+        throw new GlinForgeError(response.data.error);
+      }
+
+      return response.data.result;
+    } catch (error) {
+      if (axios.isAxiosError(error)) {
+        if (error.code === 'ECONNABORTED') {
+          // Abandoned; stop its work on the server too
+          this.cancel(id).catch(() => undefined);
+        }
         throw new Error(
-          response.data.error.message || 'RPC call failed'
+          `RPC call failed: ${error.message}. ` +
+            `Is glin-forge RPC server running on ${this.rpcUrl}?`
         );
       }
+      throw error;
+    }
+  }
 
-      return response.data.result;
+  /**
+   * Send several calls in one round trip; the server runs them in parallel and
+   * answers in call order, each with a result or an Error
+   */
+  async batch(calls: { method: string; params: any }[]): Promise<any[]> {
+    if (calls.length === 0) {
+      return [];
+    }
+    const ids = calls.map(() => this.nextId());
+    try {
+      const response = await this.axios.post(
+        '/',
+        calls.map((call, index) => ({
+          jsonrpc: '2.0',
+          id: ids[index],
+          method: call.method,
+          params: [call.params],
+          timeout: this.timeout / 1000,
+        }))
+      );
+      if (!Array.isArray(response.data)) {
+        throw response.data?.error
+          ? new GlinForgeError(response.data.error)
+          : new Error('RPC batch failed');
+      }
+      const byId = new Map<string, any>(response.data.map((output: any) => [output.id, output]));
+      return calls.map((_, index) => {
+        const output = byId.get(ids[index]);
+        if (!output) {
+          return new Error('No response to batched call');
+        }
+        return output.error ? new GlinForgeError(output.error) : output.result;
+      });
     } catch (error) {
       if (axios.isAxiosError(error)) {
         throw new Error(
@@ -136,6 +306,34 @@ class GlinForgeClient {
     }
   }
 
+  /**
+   * Cancel a running call of this client by its JSON-RPC id; resolves to
+   * whether it was still running
+   */
+  async cancel(requestId: string): Promise<boolean> {
+    const response = await this.axios.post('/', {
+      jsonrpc: '2.0',
+      id: this.nextId(),
+      method: 'cancel',
+      params: [{ request_id: requestId }],
+    });
+    return response.data.result?.success ?? false;
+  }
+
+  async health(network?: string): Promise<HealthInfo> {
+    const result = await this.rpc('health', { network });
+    return {
+      healthy: result.success,
+      version: result.version,
+      methods: result.methods,
+      network: result.network,
+      rpc: result.rpc,
+      blockNumber: result.block_number ?? undefined,
+      blockHash: result.block_hash ?? undefined,
+      error: result.error ?? undefined,
+    };
+  }
+
   async deploy(options: DeployOptions): Promise<DeployResult> {
     // Encode arguments if provided
     const encodedOptions = {
@@ -145,6 +343,175 @@ class GlinForgeClient {
     return this.rpc('deploy', encodedOptions);
   }
 
+  async uploadCode(options: UploadCodeOptions): Promise<UploadCodeResult> {
+    const result = await this.rpc('uploadCode', options);
+    return {
+      success: result.success,
+      codeHash: result.code_hash ?? undefined,
+      txHash: result.tx_hash ?? undefined,
+      blockHash: result.block_hash ?? undefined,
+      error: result.error ?? undefined,
+    };
+  }
+
+  private instantiateParams(options: InstantiateOptions): any {
+    return {
+      code_hash: options.codeHash,
+      metadata: options.metadata,
+      constructor: options.constructorName,
+      args: options.args ? ArgumentEncoder.encodeAll(options.args) : [],
+      value: options.value || 0,
+      network: options.network,
+      account: options.account,
+      salt: options.salt,
+    };
+  }
+
+  async instantiate(options: InstantiateOptions): Promise<DeployResult> {
+    const result = await this.rpc('instantiate', this.instantiateParams(options));
+    return {
+      success: result.success,
+      address: result.address ?? undefined,
+      codeHash: result.code_hash ?? undefined,
+      txHash: result.tx_hash ?? undefined,
+      blockHash: result.block_hash ?? undefined,
+      error: result.error ?? undefined,
+    };
+  }
+
+  async typegen(options: TypegenOptions = {}): Promise<TypegenResult> {
+    const result = await this.rpc('typegen', {
+      metadata: options.metadata,
+      output: options.output,
+      framework: options.framework,
+      react_query: options.reactQuery ?? false,
+      mock: options.mock ?? false,
+      strict_bigint: options.strictBigint ?? false,
+      branded: options.branded ?? false,
+      include_contents: options.includeContents ?? false,
+    });
+    return {
+      contractName: result.contract_name,
+      files: result.files,
+    };
+  }
+
+  async signRaw(method: SignableMethod, options: any): Promise<PreparedTransaction> {
+    let params: any;
+    if (method === 'instantiate') {
+      params = this.instantiateParams(options);
+    } else if (method === 'uploadCode') {
+      params = options;
+    } else {
+      params = { ...options, args: options.args ? ArgumentEncoder.encodeAll(options.args) : [] };
+    }
+    const result = await this.rpc('signRaw', { method, params });
+    if (!result.success) {
+      throw new Error(result.error || 'Failed to prepare transaction');
+    }
+    return { id: result.id, payload: result.payload, address: result.address };
+  }
+
+  async submitSigned(
+    id: string,
+    signature: string,
+    signatureType?: 'sr25519' | 'ed25519' | 'ecdsa'
+  ): Promise<SubmitSignedResult> {
+    const result = await this.rpc('submitSigned', {
+      id,
+      signature,
+      signature_type: signatureType,
+    });
+    return {
+      success: result.success,
+      txHash: result.tx_hash ?? undefined,
+      blockHash: result.block_hash ?? undefined,
+      address: result.address ?? undefined,
+      codeHash: result.code_hash ?? undefined,
+      error: result.error ?? undefined,
+    };
+  }
+
+  async getDeployment(name: string, network?: string): Promise<DeploymentRecord | null> {
+    const result = await this.rpc('getDeployment', { name, network });
+    const deployment = result.deployment;
+    if (!deployment) {
+      return null;
+    }
+    return {
+      address: deployment.address,
+      codeHash: deployment.code_hash ?? undefined,
+      txHash: deployment.tx_hash ?? undefined,
+      block: deployment.block ?? undefined,
+      blockHash: deployment.block_hash ?? undefined,
+    };
+  }
+
+  async saveDeployment(
+    name: string,
+    deployment: DeploymentRecord,
+    network?: string
+  ): Promise<DeploymentRecord | null> {
+    const result = await this.rpc('saveDeployment', {
+      name,
+      network,
+      address: deployment.address,
+      code_hash: deployment.codeHash,
+      tx_hash: deployment.txHash,
+      block: deployment.block,
+      block_hash: deployment.blockHash,
+    });
+    const previous = result.previous;
+    return previous
+      ? {
+          address: previous.address,
+          codeHash: previous.code_hash ?? undefined,
+          txHash: previous.tx_hash ?? undefined,
+          block: previous.block ?? undefined,
+          blockHash: previous.block_hash ?? undefined,
+        }
+      : null;
+  }
+
+  async getStorage(address: string, key: string, network?: string): Promise<string | null> {
+    const result = await this.rpc('getStorage', { address, key, network });
+    return result.value ?? null;
+  }
+
+  async getStorageItems(
+    address: string,
+    options: StorageItemsOptions = {}
+  ): Promise<StorageItemsPage> {
+    const result = await this.rpc('getStorageItems', {
+      address,
+      prefix: options.prefix,
+      count: options.count,
+      start_key: options.startKey,
+      network: options.network,
+    });
+    return {
+      items: result.items.map((item: any) => ({
+        key: item.key ?? undefined,
+        trieKey: item.trie_key,
+        value: item.value,
+      })),
+      nextKey: result.next_key ?? undefined,
+    };
+  }
+
+  async unlockAccount(account: string, password: string): Promise<string> {
+    const result = await this.rpc('unlockAccount', { account, password });
+    if (!result.success) {
+      throw new Error(result.error || 'Failed to unlock account');
+    }
+    return result.address;
+  }
+
+  async lockAccount(account: string): Promise<boolean> {
+    const result = await this.rpc('lockAccount', { account });
+    return result.success;
+  }
+
   async call(options: CallOptions): Promise<CallResult> {
     // Encode arguments if provided
     const encodedOptions = {
@@ -251,6 +618,66 @@ export class Contract {
     return result.data;
   }
 
+  /**
+   * Raw SCALE-encoded value under a storage key of the contract, or null
+   *
+   * @example
+   * ```typescript
+   * // Root storage of an ink! contract
+   * const root = await contract.getStorage('0x00000000');
+   * ```
+   */
+  async getStorage(key: string): Promise<string | null> {
+    return this.client.getStorage(this.address, key, this.network as string);
+  }
+
+  /**
+   * Page of the contract's raw storage values whose keys start with `prefix`
+   */
+  async getStorageItems(
+    options: Omit<StorageItemsOptions, 'network'> = {}
+  ): Promise<StorageItemsPage> {
+    return this.client.getStorageItems(this.address, {
+      ...options,
+      network: this.network as string,
+    });
+  }
+
+  /**
+   * Run several queries in one round trip, e.g. many `balance_of` reads;
+   * results come back in query order
+   *
+   * @example
+   * ```typescript
+   * const balances = await contract.queryMany(
+   *   holders.map((holder) => ({ method: 'balance_of', args: [holder] }))
+   * );
+   * ```
+   */
+  async queryMany(queries: { method: string; args?: ContractArg[] }[]): Promise<any[]> {
+    const results = await this.client.batch(
+      queries.map((query) => ({
+        method: 'query',
+        params: {
+          address: this.address,
+          metadata: this.metadata,
+          method: query.method,
+          args: ArgumentEncoder.encodeAll(query.args ?? []),
+          network: this.network,
+        },
+      }))
+    );
+    return results.map((result, index) => {
+      if (result instanceof Error) {
+        throw result;
+      }
+      if (!result.success) {
+        throw new Error(result.error || `Query ${queries[index].method} failed`);
+      }
+      return result.data;
+    });
+  }
+
   /**
    * Create an EventWatcher for this contract
    *
@@ -353,6 +780,197 @@ export async function deploy(options: DeployOptions): Promise<Contract> {
   );
 }
 
+/**
+ * Upload contract code without instantiating it, returning the code hash
+ *
+ * @example
+ * ```typescript
+ * const codeHash = await uploadCode({
+ *   wasm: './target/ink/my_contract.wasm',
+ *   network: Network.Testnet,
+ *   account: Signer.Alice
+ * });
+ * ```
+ */
+export async function uploadCode(options: UploadCodeOptions): Promise<string> {
+  const client = new GlinForgeClient();
+  const result = await client.uploadCode(options);
+
+  if (!result.success || !result.codeHash) {
+    throw new Error(result.error || 'Upload failed');
+  }
+
+  return result.codeHash;
+}
+
+/**
+ * Instantiate uploaded code; distinct salts give many instances of one code hash
+ *
+ * @example
+ * ```typescript
+ * const [a, b] = await Promise.all(
+ *   ['a', 'b'].map((salt) =>
+ *     instantiate({
+ *       codeHash,
+ *       metadata: './target/ink/metadata.json',
+ *       constructorName: 'new',
+ *       args: [1000],
+ *       salt,
+ *       network: Network.Testnet,
+ *       account: Signer.Alice
+ *     })
+ *   )
+ * );
+ * ```
+ */
+export async function instantiate(options: InstantiateOptions): Promise<Contract> {
+  const client = new GlinForgeClient();
+  const result = await client.instantiate(options);
+
+  if (!result.success || !result.address) {
+    throw new Error(result.error || 'Instantiation failed');
+  }
+
+  return new Contract(
+    result.address,
+    options.metadata,
+    options.network,
+    options.account,
+    client
+  );
+}
+
+/**
+ * Unlock an account of the glin-forge keystore for the rest of the run, so it
+ * can sign deploys and calls; resolves to its address
+ *
+ * @example
+ * ```typescript
+ * await unlockAccount('deployer', process.env.DEPLOYER_PASSWORD!);
+ * await deploy({ wasm, metadata, account: 'deployer', network: 'testnet' });
+ * ```
+ */
+export async function unlockAccount(account: string, password: string): Promise<string> {
+  const client = new GlinForgeClient();
+  return client.unlockAccount(account, password);
+}
+
+/**
+ * Send a deploy, upload, instantiate or call signed outside glin-forge, e.g. by
+ * a hardware wallet: glin-forge prepares the transaction for `options.account`
+ * (an address), `sign` signs its payload, and glin-forge submits it
+ *
+ * The payload must be signed as is; signers that wrap raw data in `<Bytes>`
+ * (polkadot-js `signRaw`) produce signatures the chain rejects.
+ *
+ * @example
+ * ```typescript
+ * const result = await signAndSubmit(
+ *   'call',
+ *   { address, metadata, method: 'flip', account: pair.address, network: 'testnet' },
+ *   async (payload) => u8aToHex(pair.sign(hexToU8a(payload)))
+ * );
+ * ```
+ */
+export async function signAndSubmit(
+  method: SignableMethod,
+  options: DeployOptions | UploadCodeOptions | InstantiateOptions | CallOptions,
+  sign: RawSigner
+): Promise<SubmitSignedResult> {
+  const client = new GlinForgeClient();
+  const prepared = await client.signRaw(method, options);
+  const signature = await sign(prepared.payload, prepared.address);
+  return client.submitSigned(prepared.id, signature);
+}
+
+/**
+ * Deployment recorded under a contract name (or address) in the project's
+ * deployments registry, shared with the CLI; null when there is none
+ *
+ * @example
+ * ```typescript
+ * const flipper = await getDeployment('flipper', 'testnet');
+ * const contract = getContract({ address: flipper!.address, metadata, network: 'testnet', account });
+ * ```
+ */
+export async function getDeployment(
+  name: string,
+  network?: Network | string
+): Promise<DeploymentRecord | null> {
+  const client = new GlinForgeClient();
+  return client.getDeployment(name, network);
+}
+
+/**
+ * Record a deployment under a contract name, so the CLI and later scripts can
+ * refer to it by name; resolves to the deployment it replaced, if any
+ *
+ * @example
+ * ```typescript
+ * const token = await deploy({ ... });
+ * await saveDeployment('token', { address: token.address }, 'testnet');
+ * ```
+ */
+export async function saveDeployment(
+  name: string,
+  deployment: DeploymentRecord,
+  network?: Network | string
+): Promise<DeploymentRecord | null> {
+  const client = new GlinForgeClient();
+  return client.saveDeployment(name, deployment, network);
+}
+
+/**
+ * Check that the glin-forge server reaches the network and supports the
+ * `methods` a script needs, e.g. before it starts; throws when it does not
+ *
+ * @example
+ * ```typescript
+ * const { version, blockNumber } = await checkHealth({ network: 'testnet', methods: ['getStorage'] });
+ * console.log(`glin-forge ${version}, testnet at block ${blockNumber}`);
+ * ```
+ */
+export async function checkHealth(
+  options: { network?: Network | string; methods?: string[] } = {}
+): Promise<HealthInfo> {
+  const client = new GlinForgeClient();
+  const health = await client.health(options.network);
+  const missing = (options.methods ?? []).filter((method) => !health.methods.includes(method));
+  if (missing.length > 0) {
+    throw new Error(
+      `glin-forge ${health.version} does not support ${missing.join(', ')}; upgrade glin-forge`
+    );
+  }
+  if (!health.healthy) {
+    throw new Error(`Network ${health.network} is unreachable: ${health.error}`);
+  }
+  return health;
+}
+
+/**
+ * Lock an unlocked keystore account again
+ */
+export async function lockAccount(account: string): Promise<boolean> {
+  const client = new GlinForgeClient();
+  return client.lockAccount(account);
+}
+
+/**
+ * Regenerate the TypeScript types of a contract, e.g. after a deploy so that a
+ * frontend dev server hot-reloads them
+ *
+ * @example
+ * ```typescript
+ * const contract = await deploy({ ... });
+ * const { files } = await generateTypes({ output: './frontend/src/types', framework: 'react' });
+ * console.log('Regenerated', files.map((f) => f.path));
+ * ```
+ */
+export async function generateTypes(options: TypegenOptions = {}): Promise<TypegenResult> {
+  const client = new GlinForgeClient();
+  return client.typegen(options);
+}
+
 /**
  * Get a contract instance for an already deployed contract
  *
@@ -397,6 +1015,10 @@ export { NetworkHelper };
 export type { BalanceInfo, FaucetResult, GasEstimate };
 export { Transaction };
 export type { TransactionResult, TransactionReceipt };
+export { RpcSocket };
+export type { SubscriptionNotification, TransactionProgress };
+export { GlinForgeError, ErrorCodes };
+export type { ErrorKind };
 export { TypedContract, TypedContractFactory };
 export type { TypedDeployOptions, TypedContractOptions, QueryMethodsOf, TransactionMethodsOf, EventNamesOf };
 export { defineConfig, mergeConfig, loadConfig, getNetwork, validateConfig, ConfigBuilder, defaultConfig };
@@ -408,6 +1030,11 @@ export type {
   TypeGenConfig,
   TestConfig,
   DeploymentConfig,
+  WatchConfig,
+  AlertConfig,
+  HooksConfig,
+  WatchContractConfig,
+  WebhookConfig,
   Config,
   Network as NetworkType,
   Paths,
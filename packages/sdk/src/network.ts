@@ -1,5 +1,7 @@
 import axios, { AxiosInstance } from 'axios';
+import { requestIds, rpcConnection } from './connection';
 import { decodeBalance, formatBalance } from './encoding';
+import { GlinForgeError } from './errors';
 
 // ========================================
 // Types
@@ -22,8 +24,14 @@ export interface FaucetResult {
 }
 
 export interface GasEstimate {
+  /** Gas required, as refTime */
   gasLimit: number;
-  estimatedCost: string;
+  refTime: number;
+  proofSize: number;
+  /** Storage deposit charged (positive) or refunded (negative), in plancks */
+  storageDeposit: string;
+  /** Transaction fee in plancks, when `from` is a dev account */
+  estimatedCost?: string;
 }
 
 // ========================================
@@ -48,6 +56,7 @@ export interface GasEstimate {
  * // Estimate gas
  * const estimate = await helper.estimateGas({
  *   address: '5GrwvaEF...',
+ *   metadata: './target/ink/metadata.json',
  *   method: 'transfer',
  *   args: ['5Account...', 1000]
  * });
@@ -57,27 +66,14 @@ export interface GasEstimate {
 export class NetworkHelper {
   private rpcUrl: string;
   private axios: AxiosInstance;
-  private requestId: number = 0;
+  private nextId = requestIds();
   private network: string;
 
   constructor(network: string) {
-    const port = process.env.GLIN_FORGE_RPC_PORT;
-    if (!port) {
-      throw new Error(
-        'glin-forge RPC server not running. ' +
-          'This SDK must be used with "glin-forge run" command.'
-      );
-    }
-
     this.network = network;
-    this.rpcUrl = `http://127.0.0.1:${port}`;
-    this.axios = axios.create({
-      baseURL: this.rpcUrl,
-      timeout: 60000,
-      headers: {
-        'Content-Type': 'application/json',
-      },
-    });
+    const { rpcUrl, config } = rpcConnection(60000);
+    this.rpcUrl = rpcUrl;
+    this.axios = axios.create(config);
   }
 
   /**
@@ -125,7 +121,10 @@ export class NetworkHelper {
   }
 
   /**
-   * Request tokens from faucet (testnet only)
+   * Request tokens from faucet (testnet, local and networks configuring one)
+   *
+   * Rejects with a `rate_limited` GlinForgeError when the address already
+   * received tokens within the faucet's cooldown.
    *
    * @param address - Account address to receive tokens
    * @returns Faucet result
@@ -156,7 +155,9 @@ export class NetworkHelper {
   }
 
   /**
-   * Estimate gas for a contract call
+   * Estimate gas for a contract call by dry-running it
+   *
+   * Throws with the decoded revert value or dispatch error when the call would fail.
    *
    * @param options - Gas estimation options
    * @returns Gas estimate
@@ -165,16 +166,19 @@ export class NetworkHelper {
    * ```typescript
    * const estimate = await helper.estimateGas({
    *   address: '5GrwvaEF...',
+   *   metadata: './target/ink/metadata.json',
    *   method: 'transfer',
    *   args: ['5Account...', 1000],
    *   value: 0
    * });
-   * console.log(`Estimated gas: ${estimate.gasLimit}`);
+   * console.log(`refTime: ${estimate.refTime}, proofSize: ${estimate.proofSize}`);
+   * console.log(`Storage deposit: ${estimate.storageDeposit}`);
    * console.log(`Estimated cost: ${estimate.estimatedCost}`);
    * ```
    */
   async estimateGas(options: {
     address: string;
+    metadata: string;
     method: string;
     args?: any[];
     value?: number;
@@ -183,6 +187,7 @@ export class NetworkHelper {
     try {
       const result = await this.rpc('estimateGas', {
         address: options.address,
+        metadata: options.metadata,
         method: options.method,
         args: options.args || [],
         value: options.value || 0,
@@ -195,8 +200,11 @@ export class NetworkHelper {
       }
 
       return {
-        gasLimit: result.gasLimit,
-        estimatedCost: result.estimatedCost,
+        gasLimit: result.gas_limit,
+        refTime: result.ref_time,
+        proofSize: result.proof_size,
+        storageDeposit: result.storage_deposit,
+        estimatedCost: result.estimated_cost ?? undefined,
       };
     } catch (error) {
       if (axios.isAxiosError(error)) {
@@ -297,15 +305,13 @@ export class NetworkHelper {
     try {
       const response = await this.axios.post('/', {
         jsonrpc: '2.0',
-        id: ++this.requestId,
+        id: this.nextId(),
         method,
         params: [params],
       });
 
       if (response.data.error) {
-        throw new Error(
-          response.data.error.message || 'RPC call failed'
-        );
+        throw new GlinForgeError(response.data.error);
       }
 
       return response.data.result;
@@ -355,6 +361,7 @@ export async function requestFaucet(
 export async function estimateGas(
   options: {
     address: string;
+    metadata: string;
     method: string;
     args?: any[];
     value?: number;
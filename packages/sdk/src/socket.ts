@@ -0,0 +1,182 @@
+import type { ContractEvent } from './events';
+import { requestIds } from './connection';
+import { GlinForgeError } from './errors';
+import { ArgumentEncoder, type ContractArg } from './encoding';
+
+// ========================================
+// Types
+// ========================================
+
+export interface SubscriptionNotification {
+  subscription: string;
+  result?: ContractEvent;
+  error?: { message: string };
+}
+
+/** Stage a transaction submitted by a call reached, as `progress` notifications report */
+export interface TransactionProgress {
+  stage: 'submitted' | 'in_block' | 'retracted' | 'finalized';
+  txHash: string;
+  /** Block of `in_block` and `finalized` */
+  blockHash?: string;
+}
+
+type Pending = {
+  resolve: (value: any) => void;
+  reject: (error: Error) => void;
+  onProgress?: (progress: TransactionProgress) => void;
+};
+
+// ========================================
+// RpcSocket
+// ========================================
+
+/**
+ * JSON-RPC client on a WebSocket to the glin-forge RPC server
+ *
+ * One connection serves every call, without a round trip to open each one,
+ * and receives server notifications such as subscribed events. Requires a
+ * global `WebSocket` (Node.js 22+ or a browser).
+ *
+ * @example
+ * ```typescript
+ * const socket = await RpcSocket.connect();
+ * const balance = await socket.call('getBalance', { address: '5Grw...' });
+ * await socket.subscribe({ address: contract.address }, (event) => console.log(event));
+ * socket.close();
+ * ```
+ */
+export class RpcSocket {
+  private nextId = requestIds();
+  private pending: Map<string, Pending> = new Map();
+  private subscribers: Map<string, (notification: SubscriptionNotification) => void> = new Map();
+
+  private constructor(private socket: WebSocket) {
+    socket.addEventListener('message', (message) => this.receive(String(message.data)));
+    socket.addEventListener('close', () => {
+      for (const { reject } of this.pending.values()) {
+        reject(new Error('glin-forge RPC connection closed'));
+      }
+      this.pending.clear();
+    });
+  }
+
+  /**
+   * Connect to the server of "glin-forge run" (GLIN_FORGE_RPC_WS_URL), or to `url`
+   */
+  static connect(url?: string): Promise<RpcSocket> {
+    const base = url ?? process.env.GLIN_FORGE_RPC_WS_URL;
+    if (!base) {
+      throw new Error(
+        'glin-forge RPC server not reachable over WebSocket. ' +
+          'This SDK must be used with "glin-forge run" command on a TCP address.'
+      );
+    }
+    // Browsers cannot set headers on a WebSocket, so the token goes in the query
+    const target = new URL(base);
+    target.searchParams.set('token', process.env.GLIN_FORGE_RPC_TOKEN ?? '');
+
+    const socket = new WebSocket(target.toString());
+    return new Promise((resolve, reject) => {
+      socket.addEventListener('open', () => resolve(new RpcSocket(socket)), { once: true });
+      socket.addEventListener('error', () => reject(new Error(`Cannot connect to ${base}`)), {
+        once: true,
+      });
+    });
+  }
+
+  /**
+   * Call a JSON-RPC method; `onProgress` receives the stages of the
+   * transactions it submits (deploy, uploadCode, instantiate, call)
+   */
+  call(
+    method: string,
+    params: any,
+    onProgress?: (progress: TransactionProgress) => void
+  ): Promise<any> {
+    const id = this.nextId();
+    return new Promise((resolve, reject) => {
+      this.pending.set(id, { resolve, reject, onProgress });
+      this.socket.send(JSON.stringify({ jsonrpc: '2.0', id, method, params: [params] }));
+    });
+  }
+
+  /**
+   * Deploy a contract, reporting its progress until finalization
+   *
+   * @example
+   * ```typescript
+   * const result = await socket.deploy({ wasm, metadata, args: [true], account: 'alice' }, (p) =>
+   *   console.log(p.stage, p.blockHash ?? p.txHash)
+   * );
+   * ```
+   */
+  deploy(
+    options: { wasm: string; metadata: string; args?: ContractArg[]; [option: string]: any },
+    onProgress: (progress: TransactionProgress) => void
+  ): Promise<any> {
+    const params = {
+      ...options,
+      args: options.args ? ArgumentEncoder.encodeAll(options.args) : [],
+    };
+    return this.call('deploy', params, onProgress);
+  }
+
+  /**
+   * Subscribe to a contract's events; resolves to the subscription id
+   */
+  async subscribe(
+    params: { address: string; event?: string; metadata?: string; network?: string },
+    onEvent: (event: ContractEvent) => void,
+    onError?: (error: Error) => void
+  ): Promise<string> {
+    const result = await this.call('subscribeEvents', params);
+    if (!result.success || !result.subscription_id) {
+      throw new Error(result.error || 'Failed to subscribe to events');
+    }
+    this.subscribers.set(result.subscription_id, (notification) => {
+      if (notification.result) {
+        onEvent(notification.result);
+      } else if (notification.error) {
+        onError?.(new Error(notification.error.message));
+      }
+    });
+    return result.subscription_id;
+  }
+
+  async unsubscribe(subscriptionId: string): Promise<void> {
+    this.subscribers.delete(subscriptionId);
+    await this.call('unsubscribeEvents', { subscription_id: subscriptionId });
+  }
+
+  close(): void {
+    this.socket.close();
+  }
+
+  private receive(data: string): void {
+    const message = JSON.parse(data);
+    if (message.method === 'subscription') {
+      this.subscribers.get(message.params.subscription)?.(message.params);
+      return;
+    }
+    if (message.method === 'progress') {
+      const { request, stage, tx_hash, block_hash } = message.params;
+      this.pending.get(request)?.onProgress?.({
+        stage,
+        txHash: tx_hash,
+        blockHash: block_hash ?? undefined,
+      });
+      return;
+    }
+    const pending = this.pending.get(message.id);
+    if (!pending) {
+      return;
+    }
+    this.pending.delete(message.id);
+    if (message.error) {
+      pending.reject(new GlinForgeError(message.error));
+    } else {
+      pending.resolve(message.result);
+    }
+  }
+}
@@ -1,4 +1,6 @@
 import axios, { AxiosInstance } from 'axios';
+import { requestIds, rpcConnection } from './connection';
+import { GlinForgeError } from './errors';
 
 // ========================================
 // Types
@@ -39,30 +41,17 @@ export interface TransactionReceipt {
 export class Transaction {
   private rpcUrl: string;
   private axios: AxiosInstance;
-  private requestId: number = 0;
+  private nextId = requestIds();
   private network: string;
 
   constructor(
     private result: TransactionResult,
     network: string
   ) {
-    const port = process.env.GLIN_FORGE_RPC_PORT;
-    if (!port) {
-      throw new Error(
-        'glin-forge RPC server not running. ' +
-          'This SDK must be used with "glin-forge run" command.'
-      );
-    }
-
     this.network = network;
-    this.rpcUrl = `http://127.0.0.1:${port}`;
-    this.axios = axios.create({
-      baseURL: this.rpcUrl,
-      timeout: 300000, // 5 minutes for transaction confirmation
-      headers: {
-        'Content-Type': 'application/json',
-      },
-    });
+    const { rpcUrl, config } = rpcConnection(300000); // 5 minutes for transaction confirmation
+    this.rpcUrl = rpcUrl;
+    this.axios = axios.create(config);
   }
 
   /**
@@ -245,15 +234,13 @@ export class Transaction {
     try {
       const response = await this.axios.post('/', {
         jsonrpc: '2.0',
-        id: ++this.requestId,
+        id: this.nextId(),
         method,
         params: [params],
       });
 
       if (response.data.error) {
-        throw new Error(
-          response.data.error.message || 'RPC call failed'
-        );
+        throw new GlinForgeError(response.data.error);
       }
 
       return response.data.result;
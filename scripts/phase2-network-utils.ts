@@ -57,12 +57,15 @@ async function main() {
   try {
     const gasEstimate = await helper.estimateGas({
       address: contractAddress,
+      metadata: './target/ink/metadata.json',
       method: 'transfer',
       args: [aliceAddress, '1000'],
       from: 'alice',
     });
 
-    console.log('  Gas limit:', gasEstimate.gasLimit);
+    console.log('  refTime:', gasEstimate.refTime);
+    console.log('  proofSize:', gasEstimate.proofSize);
+    console.log('  Storage deposit:', gasEstimate.storageDeposit);
     console.log('  Estimated cost:', gasEstimate.estimatedCost);
   } catch (error: any) {
     console.log('  ℹ️ Gas estimation:', error.message);
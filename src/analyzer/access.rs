@@ -0,0 +1,200 @@
+// Access control matrix
+//
+// For every message, the roles allowed to call it are derived from the caller
+// checks in its body and in the helpers it calls on `self` (modifiers such as
+// `ensure_owner()`): comparisons with a storage field (`owner`), lookups in a
+// storage collection (`admins`), `has_role` checks and comparisons with a
+// constant account. Messages without any check are callable by anyone; those
+// that look privileged are flagged for review.
+
+use serde::{Deserialize, Serialize};
+
+use super::ast::{FunctionModel, SourceModel};
+
+/// Anyone may call the message
+pub const ANYONE: &str = "anyone";
+
+/// Message names that usually require a privileged caller
+const PRIVILEGED_NAMES: &[&str] = &[
+    "mint",
+    "pause",
+    "unpause",
+    "upgrade",
+    "set_code",
+    "set_code_hash",
+    "terminate",
+    "transfer_ownership",
+    "set_owner",
+    "set_admin",
+    "grant_role",
+    "revoke_role",
+];
+
+/// Storage fields that only privileged callers should change
+const PRIVILEGED_FIELDS: &[&str] = &["owner", "admin", "paused", "fee", "treasury"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessEntry {
+    pub contract: Option<String>,
+    pub message: String,
+    pub file: String,
+    pub line: usize,
+    pub payable: bool,
+    /// Writes storage directly or through helpers
+    pub mutates: bool,
+    /// Required roles, or `["anyone"]`
+    pub roles: Vec<String>,
+    /// Looks privileged but has no caller check
+    pub unprotected_privileged: bool,
+}
+
+/// Roles required to call a function, empty if anyone can
+pub fn roles(model: &SourceModel, func: &FunctionModel) -> Vec<String> {
+    let reached = model.reachable(func);
+
+    let mut roles: Vec<String> = reached
+        .iter()
+        .flat_map(|f| &f.caller_checks)
+        .map(|check| match check.kind {
+            "field" => check.name.clone(),
+            "membership" => format!("member of {}", check.name),
+            "role" => format!("role {}", check.name),
+            _ => format!("account {}", check.name),
+        })
+        .collect();
+    roles.sort();
+    roles.dedup();
+
+    // A guard we can't attribute to a specific role
+    if roles.is_empty()
+        && reached
+            .iter()
+            .any(|f| f.checks_caller && f.caller_checks.is_empty())
+    {
+        let guards: Vec<&str> = reached
+            .iter()
+            .flat_map(|f| &f.calls)
+            .map(String::as_str)
+            .filter(|call| call.starts_with("only_") || call.starts_with("ensure_"))
+            .collect();
+        roles.push(match guards.first() {
+            Some(guard) => format!("guard {}", guard),
+            None => "caller checked".to_string(),
+        });
+    }
+
+    roles
+}
+
+/// Whether a message looks like it should be restricted
+pub fn is_privileged(model: &SourceModel, func: &FunctionModel) -> bool {
+    let name = func.info.name.as_str();
+    PRIVILEGED_NAMES.contains(&name)
+        || model
+            .written_fields(func)
+            .iter()
+            .any(|field| PRIVILEGED_FIELDS.contains(&field.as_str()))
+}
+
+/// One matrix row per message of a file
+pub fn matrix(file: &str, contract: Option<&str>, model: &SourceModel) -> Vec<AccessEntry> {
+    model
+        .functions
+        .iter()
+        .filter(|f| f.is_message())
+        .map(|func| {
+            let mut roles = roles(model, func);
+            let unprotected_privileged = roles.is_empty() && is_privileged(model, func);
+            if roles.is_empty() {
+                roles.push(ANYONE.to_string());
+            }
+
+            AccessEntry {
+                contract: contract.map(str::to_string),
+                message: func.info.name.clone(),
+                file: file.to_string(),
+                line: func.info.line,
+                payable: func.info.is_payable,
+                mutates: !model.written_fields(func).is_empty(),
+                roles,
+                unprotected_privileged,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ast::parse_source;
+
+    #[test]
+    fn test_access_matrix() {
+        let source = r#"
+#[ink::contract]
+mod vault {
+    const TREASURY: AccountId = AccountId::new([0u8; 32]);
+
+    impl Vault {
+        #[ink(message)]
+        pub fn set_fee(&mut self, fee: u32) {
+            self.ensure_owner();
+            self.fee = fee;
+        }
+
+        #[ink(message)]
+        pub fn pause(&mut self) {
+            self.paused = true;
+        }
+
+        #[ink(message)]
+        pub fn sweep(&mut self) {
+            let caller = self.env().caller();
+            if caller != TREASURY {
+                return;
+            }
+            self.balance = 0;
+        }
+
+        #[ink(message)]
+        pub fn approve_spender(&mut self, spender: AccountId) {
+            assert!(self.admins.contains(self.env().caller()));
+            self.spenders.insert(spender, &true);
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> u32 {
+            self.fee
+        }
+
+        fn ensure_owner(&self) {
+            assert_eq!(self.env().caller(), self.owner, "not owner");
+        }
+    }
+}
+"#;
+        let model = parse_source(source).unwrap();
+        let matrix = matrix("lib.rs", Some("vault"), &model);
+        let rows: Vec<(&str, Vec<&str>, bool)> = matrix
+            .iter()
+            .map(|e| {
+                (
+                    e.message.as_str(),
+                    e.roles.iter().map(String::as_str).collect(),
+                    e.unprotected_privileged,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                ("set_fee", vec!["owner"], false),
+                ("pause", vec!["anyone"], true),
+                ("sweep", vec!["account TREASURY"], false),
+                ("approve_spender", vec!["member of admins"], false),
+                ("get", vec!["anyone"], false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,1166 @@
+// Source model built from a `syn` AST
+//
+// The model records what the rules need to know about a contract file:
+// ink! messages and constructors with their attributes, per-function facts
+// (arithmetic, unwraps, loops, event emission, caller checks) with line
+// numbers, and the fields of the `#[ink(storage)]` struct. Test modules and
+// test functions are skipped so they don't produce findings.
+//
+// Findings can be suppressed inline with `// forge-ignore: rule-id, other-id`
+// (or a bare `// forge-ignore` for every rule). A trailing comment applies to
+// its own line; a comment on its own line applies to the next line of code,
+// skipping attributes and doc comments.
+
+use anyhow::{Context, Result};
+use proc_macro2::TokenTree;
+use quote::ToTokens;
+use std::collections::{HashMap, HashSet};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{Attribute, BinOp, Expr, ImplItemFn, Item, ItemFn, ItemMod, Signature, Visibility};
+
+use super::migrate::{self, LegacyUse};
+use super::FunctionInfo;
+
+/// Everything the analyzer extracts from a single source file
+#[derive(Debug, Default)]
+pub struct SourceModel {
+    /// File contains an `#[ink::contract]` module
+    pub is_contract: bool,
+    pub functions: Vec<FunctionModel>,
+    pub storage_fields: Vec<StorageField>,
+    pub imports: Vec<String>,
+    pub traits: Vec<String>,
+    pub structs: Vec<String>,
+    /// Line -> suppressed rule ids (`*` for all rules)
+    pub suppressions: HashMap<usize, Vec<String>>,
+    /// Identifiers referenced from test code (unit and e2e tests)
+    pub test_references: HashSet<String>,
+    /// Syntax and APIs from before ink! 5, including in test code
+    pub legacy_uses: Vec<LegacyUse>,
+}
+
+/// A function together with the facts collected from its body
+#[derive(Debug)]
+pub struct FunctionModel {
+    pub info: FunctionInfo,
+    /// Takes `&mut self`
+    pub mutates: bool,
+    /// Lines with unchecked `+`, `-` or `*`
+    pub arithmetic: Vec<usize>,
+    /// Lines with `.unwrap()`
+    pub unwraps: Vec<usize>,
+    /// Lines with `for`, `while` or `loop`
+    pub loops: Vec<usize>,
+    pub emits_event: bool,
+    /// Methods called on `self`
+    pub calls: Vec<String>,
+    /// Body inspects the caller or calls an owner/assertion helper
+    pub checks_caller: bool,
+    /// `self.<field>` accesses that read the field
+    pub field_reads: Vec<String>,
+    /// Fields assigned or mutated through `insert`/`set`/`remove`/`push`
+    pub field_writes: Vec<String>,
+    /// Lines of match arms that follow a catch-all arm
+    pub unreachable_arms: Vec<usize>,
+    /// `self.<field>.<method>(..)` calls other than storage writes, as (field, method)
+    pub field_calls: Vec<(String, String)>,
+    /// Cross-contract calls made through `build_call`
+    pub external_calls: Vec<ExternalCall>,
+    /// Trait this function implements, for methods of `impl Trait for Type`
+    pub trait_name: Option<String>,
+    /// Comparisons and lookups involving the caller
+    pub caller_checks: Vec<CallerCheck>,
+    /// Every method or function called in the body, by name, with its line
+    pub invoked: Vec<(String, usize)>,
+}
+
+/// What the caller is checked against
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallerCheck {
+    /// "field" (compared with `self.<name>`), "membership" (looked up in
+    /// `self.<name>`), "role" (`has_role(<name>, caller)`) or "account"
+    /// (compared with a constant)
+    pub kind: &'static str,
+    pub name: String,
+    pub line: usize,
+}
+
+/// A `build_call::<E>()...invoke()` chain
+#[derive(Debug, Clone)]
+pub struct ExternalCall {
+    /// Callee expression, e.g. `self.token`
+    pub target: String,
+    /// Selector label from `selector_bytes!`, if given
+    pub selector: Option<String>,
+    pub line: usize,
+}
+
+impl SourceModel {
+    pub fn is_suppressed(&self, rule: &str, line: usize) -> bool {
+        self.suppressions
+            .get(&line)
+            .map(|rules| rules.iter().any(|r| r == "*" || r == rule))
+            .unwrap_or(false)
+    }
+
+    /// A function and every function it reaches through calls on `self`
+    pub fn reachable<'a>(&'a self, func: &'a FunctionModel) -> Vec<&'a FunctionModel> {
+        let mut reached = vec![func];
+        let mut pending = vec![func];
+
+        while let Some(current) = pending.pop() {
+            for call in &current.calls {
+                if reached.iter().any(|f| &f.info.name == call) {
+                    continue;
+                }
+                if let Some(callee) = self.functions.iter().find(|f| &f.info.name == call) {
+                    reached.push(callee);
+                    pending.push(callee);
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// Whether a function emits an event directly or through methods it calls on `self`
+    pub fn emits_event(&self, func: &FunctionModel) -> bool {
+        self.reachable(func).iter().any(|f| f.emits_event)
+    }
+
+    /// Storage fields a function writes directly or through methods it calls on `self`
+    pub fn written_fields(&self, func: &FunctionModel) -> Vec<String> {
+        let mut fields: Vec<String> = self
+            .reachable(func)
+            .iter()
+            .flat_map(|f| f.field_writes.iter().cloned())
+            .collect();
+        fields.sort();
+        fields.dedup();
+        fields
+    }
+}
+
+impl FunctionModel {
+    pub fn is_message(&self) -> bool {
+        self.info.kind == "message"
+    }
+
+    pub fn is_constructor(&self) -> bool {
+        self.info.kind == "constructor"
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageField {
+    pub name: String,
+    /// Type with whitespace removed, e.g. `Mapping<AccountId,Balance>`
+    pub ty: String,
+    pub line: usize,
+}
+
+/// Parse Rust source into a [`SourceModel`]
+pub fn parse_source(content: &str) -> Result<SourceModel> {
+    let file = syn::parse_file(content).context("Failed to parse Rust source")?;
+
+    let mut collector = ModelCollector {
+        model: SourceModel::default(),
+    };
+    collector.visit_file(&file);
+    collector.model.suppressions = parse_suppressions(content);
+    collector.model.legacy_uses = migrate::scan(&file);
+
+    Ok(collector.model)
+}
+
+fn parse_suppressions(content: &str) -> HashMap<usize, Vec<String>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let comments = line_comments(&lines);
+    let mut suppressions = HashMap::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let Some(comment_start) = comments[i] else {
+            continue;
+        };
+        let comment = line[comment_start + 2..].trim_start_matches('/').trim();
+        let Some(rest) = comment.strip_prefix("forge-ignore") else {
+            continue;
+        };
+
+        let mut rules: Vec<String> = rest
+            .trim_start_matches(':')
+            .split(',')
+            .map(|rule| rule.trim().to_string())
+            .filter(|rule| !rule.is_empty())
+            .collect();
+        if rules.is_empty() {
+            rules.push("*".to_string());
+        }
+
+        // Line numbers are 1-based, matching syn spans
+        let target = if line[..comment_start].trim().is_empty() {
+            lines
+                .iter()
+                .enumerate()
+                .skip(i + 1)
+                .find(|(_, next)| {
+                    let next = next.trim();
+                    !next.is_empty() && !next.starts_with("//") && !next.starts_with("#[")
+                })
+                .map(|(j, _)| j + 1)
+        } else {
+            Some(i + 1)
+        };
+
+        if let Some(target) = target {
+            suppressions
+                .entry(target)
+                .or_insert_with(Vec::new)
+                .extend(rules);
+        }
+    }
+
+    suppressions
+}
+
+/// Where the line comment of each line starts, if it has one: `//` inside string
+/// and character literals or block comments does not start a comment
+fn line_comments(lines: &[&str]) -> Vec<Option<usize>> {
+    enum State {
+        Code,
+        Str,
+        RawStr(usize),
+        Block(usize),
+    }
+
+    let ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut state = State::Code;
+    let mut comments = Vec::with_capacity(lines.len());
+    for line in lines {
+        let bytes = line.as_bytes();
+        let mut comment = None;
+        let mut j = 0;
+        while j < bytes.len() {
+            let rest = &bytes[j..];
+            match state {
+                State::Code if rest.starts_with(b"//") => {
+                    comment = Some(j);
+                    break;
+                }
+                State::Code if rest.starts_with(b"/*") => {
+                    state = State::Block(1);
+                    j += 2;
+                }
+                State::Code if rest[0] == b'"' => {
+                    state = State::Str;
+                    j += 1;
+                }
+                State::Code
+                    if rest[0] == b'r'
+                        && !bytes[..j].last().is_some_and(|&b| ident(b) && b != b'b') =>
+                {
+                    let hashes = rest[1..].iter().take_while(|&&b| b == b'#').count();
+                    if rest.get(1 + hashes) == Some(&b'"') {
+                        state = State::RawStr(hashes);
+                        j += 2 + hashes;
+                    } else {
+                        j += 1;
+                    }
+                }
+                State::Code if rest[0] == b'\'' => {
+                    // A character literal, or else a lifetime
+                    if rest.get(1) == Some(&b'\\') {
+                        j += 3 + rest[3.min(rest.len())..]
+                            .iter()
+                            .position(|&b| b == b'\'')
+                            .map_or(rest.len(), |end| end + 1);
+                    } else {
+                        let width = line[j + 1..].chars().next().map_or(0, char::len_utf8);
+                        j += if rest.get(1 + width) == Some(&b'\'') {
+                            2 + width
+                        } else {
+                            1
+                        };
+                    }
+                }
+                State::Code => j += 1,
+                State::Str if rest[0] == b'\\' => j += 2,
+                State::Str if rest[0] == b'"' => {
+                    state = State::Code;
+                    j += 1;
+                }
+                State::RawStr(hashes)
+                    if rest[0] == b'"'
+                        && rest.len() > hashes
+                        && rest[1..=hashes].iter().all(|&b| b == b'#') =>
+                {
+                    state = State::Code;
+                    j += 1 + hashes;
+                }
+                State::Block(depth) if rest.starts_with(b"*/") => {
+                    state = if depth == 1 {
+                        State::Code
+                    } else {
+                        State::Block(depth - 1)
+                    };
+                    j += 2;
+                }
+                State::Block(depth) if rest.starts_with(b"/*") => {
+                    state = State::Block(depth + 1);
+                    j += 2;
+                }
+                State::Str | State::RawStr(_) | State::Block(_) => j += 1,
+            }
+        }
+        comments.push(comment);
+    }
+    comments
+}
+
+struct ModelCollector {
+    model: SourceModel,
+}
+
+impl ModelCollector {
+    fn add_function(
+        &mut self,
+        attrs: &[Attribute],
+        vis: Option<&Visibility>,
+        sig: &Signature,
+        span: proc_macro2::Span,
+        block: &syn::Block,
+        trait_name: Option<String>,
+    ) {
+        if is_test_fn(attrs) {
+            self.collect_test_references(|v| v.visit_block(block));
+            return;
+        }
+
+        let ink = ink_args(attrs);
+        let kind = if ink.iter().any(|a| a == "message") {
+            "message"
+        } else if ink.iter().any(|a| a == "constructor") {
+            "constructor"
+        } else {
+            "function"
+        };
+
+        let visibility = match vis {
+            Some(Visibility::Public(_)) => "public",
+            Some(Visibility::Restricted(_)) => "crate",
+            // Trait impl items inherit the trait's visibility
+            None => "public",
+            Some(Visibility::Inherited) => "private",
+        };
+
+        let mutates = sig.inputs.iter().any(|input| match input {
+            syn::FnArg::Receiver(receiver) => {
+                receiver.reference.is_some() && receiver.mutability.is_some()
+            }
+            _ => false,
+        });
+
+        let mut visitor = BodyVisitor::default();
+        visitor.visit_block(block);
+
+        let line = sig.ident.span().start().line;
+
+        self.model.functions.push(FunctionModel {
+            info: FunctionInfo {
+                name: sig.ident.to_string(),
+                kind: kind.to_string(),
+                visibility: visibility.to_string(),
+                is_payable: ink.iter().any(|a| a == "payable"),
+                line,
+                lines: span.end().line.saturating_sub(line) + 1,
+                complexity: visitor.complexity,
+            },
+            mutates,
+            arithmetic: visitor.arithmetic,
+            unwraps: visitor.unwraps,
+            loops: visitor.loops,
+            emits_event: visitor.emits_event,
+            calls: visitor.calls,
+            checks_caller: visitor.checks_caller,
+            field_reads: visitor.field_reads,
+            field_writes: visitor.field_writes,
+            unreachable_arms: visitor.unreachable_arms,
+            field_calls: visitor.field_calls,
+            external_calls: visitor.external_calls,
+            invoked: visitor.invoked,
+            trait_name,
+            caller_checks: visitor.caller_checks,
+        });
+    }
+
+    fn collect_test_references(&mut self, visit: impl FnOnce(&mut TestReferences)) {
+        let mut collector = TestReferences::default();
+        visit(&mut collector);
+        self.model.test_references.extend(collector.idents);
+    }
+}
+
+impl<'ast> Visit<'ast> for ModelCollector {
+    fn visit_item_mod(&mut self, node: &'ast ItemMod) {
+        if is_cfg_test(&node.attrs) {
+            self.collect_test_references(|v| v.visit_item_mod(node));
+            return;
+        }
+        if node
+            .attrs
+            .iter()
+            .any(|a| path_is(a.path(), &["ink", "contract"]))
+        {
+            self.model.is_contract = true;
+        }
+        visit::visit_item_mod(self, node);
+    }
+
+    fn visit_item(&mut self, node: &'ast Item) {
+        match node {
+            Item::Use(item) => self.model.imports.push(render_use(item)),
+            Item::Trait(item) => self.model.traits.push(item.ident.to_string()),
+            Item::Struct(item) => {
+                self.model.structs.push(item.ident.to_string());
+
+                if ink_args(&item.attrs).iter().any(|a| a == "storage") {
+                    for (i, field) in item.fields.iter().enumerate() {
+                        let mut ty = field.ty.to_token_stream().to_string();
+                        ty.retain(|c| !c.is_whitespace());
+                        self.model.storage_fields.push(StorageField {
+                            name: field
+                                .ident
+                                .as_ref()
+                                .map(|ident| ident.to_string())
+                                .unwrap_or_else(|| i.to_string()),
+                            ty,
+                            line: field.span().start().line,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+        visit::visit_item(self, node);
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        self.add_function(
+            &node.attrs,
+            Some(&node.vis),
+            &node.sig,
+            node.span(),
+            &node.block,
+            None,
+        );
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        if is_cfg_test(&node.attrs) {
+            self.collect_test_references(|v| v.visit_item_impl(node));
+            return;
+        }
+        let trait_name = node
+            .trait_
+            .as_ref()
+            .and_then(|(_, path, _)| path.segments.last())
+            .map(|segment| segment.ident.to_string());
+
+        for item in &node.items {
+            if let syn::ImplItem::Fn(func) = item {
+                self.visit_impl_fn(func, trait_name.clone());
+            }
+        }
+    }
+}
+
+impl ModelCollector {
+    fn visit_impl_fn(&mut self, node: &ImplItemFn, trait_name: Option<String>) {
+        let vis = trait_name.is_none().then_some(&node.vis);
+        self.add_function(
+            &node.attrs,
+            vis,
+            &node.sig,
+            node.span(),
+            &node.block,
+            trait_name,
+        );
+    }
+}
+
+/// Collects complexity and rule facts from a function body
+#[derive(Debug)]
+struct BodyVisitor {
+    complexity: u32,
+    arithmetic: Vec<usize>,
+    unwraps: Vec<usize>,
+    loops: Vec<usize>,
+    emits_event: bool,
+    calls: Vec<String>,
+    checks_caller: bool,
+    field_reads: Vec<String>,
+    field_writes: Vec<String>,
+    unreachable_arms: Vec<usize>,
+    field_calls: Vec<(String, String)>,
+    external_calls: Vec<ExternalCall>,
+    invoked: Vec<(String, usize)>,
+    caller_checks: Vec<CallerCheck>,
+}
+
+impl Default for BodyVisitor {
+    fn default() -> Self {
+        Self {
+            complexity: 1, // Base complexity
+            arithmetic: Vec::new(),
+            unwraps: Vec::new(),
+            loops: Vec::new(),
+            emits_event: false,
+            calls: Vec::new(),
+            checks_caller: false,
+            field_reads: Vec::new(),
+            field_writes: Vec::new(),
+            unreachable_arms: Vec::new(),
+            field_calls: Vec::new(),
+            external_calls: Vec::new(),
+            invoked: Vec::new(),
+            caller_checks: Vec::new(),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for BodyVisitor {
+    // Nested items do not contribute to the enclosing function
+    fn visit_item(&mut self, _node: &'ast Item) {}
+
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        let line = node.span().start().line;
+
+        // Writes to storage fields don't count as reads
+        match node {
+            Expr::Assign(assign) if self_field(&assign.left).is_some() => {
+                self.field_writes.extend(self_field(&assign.left));
+                self.visit_expr(&assign.right);
+                return;
+            }
+            Expr::Binary(bin) if is_compound_assign(&bin.op) && self_field(&bin.left).is_some() => {
+                self.field_writes.extend(self_field(&bin.left));
+                self.arithmetic.push(line);
+                self.visit_expr(&bin.right);
+                return;
+            }
+            Expr::MethodCall(call)
+                if self_field(&call.receiver).is_some()
+                    && matches!(
+                        call.method.to_string().as_str(),
+                        "insert" | "set" | "remove" | "push"
+                    ) =>
+            {
+                self.field_writes.extend(self_field(&call.receiver));
+                for arg in &call.args {
+                    self.visit_expr(arg);
+                }
+                return;
+            }
+            Expr::Field(_) => {
+                if let Some(field) = self_field(node) {
+                    self.field_reads.push(field);
+                }
+            }
+            Expr::Binary(bin) if matches!(bin.op, BinOp::Eq(_) | BinOp::Ne(_)) => {
+                if mentions_caller(&bin.left) {
+                    self.check_caller_against(&bin.right, line);
+                } else if mentions_caller(&bin.right) {
+                    self.check_caller_against(&bin.left, line);
+                }
+            }
+            Expr::Match(m) => {
+                // Arms after an unguarded catch-all can never match
+                if let Some(pos) = m
+                    .arms
+                    .iter()
+                    .position(|arm| arm.guard.is_none() && is_catch_all(&arm.pat))
+                {
+                    for arm in &m.arms[pos + 1..] {
+                        self.unreachable_arms.push(arm.pat.span().start().line);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        match node {
+            Expr::If(_) => self.complexity += 1,
+            Expr::ForLoop(_) | Expr::While(_) | Expr::Loop(_) => {
+                self.complexity += 1;
+                self.loops.push(line);
+            }
+            Expr::Match(m) => self.complexity += m.arms.len().saturating_sub(1) as u32,
+            Expr::Binary(bin) => match bin.op {
+                BinOp::And(_) | BinOp::Or(_) => self.complexity += 1,
+                // Constant expressions are checked by the compiler
+                BinOp::Add(_)
+                | BinOp::Sub(_)
+                | BinOp::Mul(_)
+                | BinOp::AddAssign(_)
+                | BinOp::SubAssign(_)
+                | BinOp::MulAssign(_)
+                    if !(is_literal(&bin.left) && is_literal(&bin.right)) =>
+                {
+                    self.arithmetic.push(line);
+                }
+                _ => {}
+            },
+            Expr::MethodCall(call) => {
+                let method = call.method.to_string();
+                self.invoked.push((method.clone(), line));
+                if matches!(&*call.receiver, Expr::Path(p) if p.path.is_ident("self")) {
+                    self.calls.push(method.clone());
+                }
+                if let Some(field) = self_field(&call.receiver) {
+                    // `self.admins.contains(caller)`
+                    if matches!(method.as_str(), "get" | "contains" | "contains_key")
+                        && call.args.iter().any(mentions_caller)
+                    {
+                        self.caller_checks.push(CallerCheck {
+                            kind: "membership",
+                            name: field.clone(),
+                            line,
+                        });
+                    }
+                    self.field_calls.push((field, method.clone()));
+                }
+                if matches!(method.as_str(), "invoke" | "try_invoke") {
+                    if let Some(external) = external_call(call) {
+                        self.external_calls.push(external);
+                    }
+                }
+                // `self.has_role(ADMIN, caller)`
+                if method == "has_role" && call.args.iter().any(mentions_caller) {
+                    if let Some(role) = call.args.first() {
+                        let mut name = role.to_token_stream().to_string();
+                        name.retain(|c| c != ' ');
+                        self.caller_checks.push(CallerCheck {
+                            kind: "role",
+                            name,
+                            line,
+                        });
+                    }
+                }
+                match method.as_str() {
+                    "unwrap" => self.unwraps.push(line),
+                    "emit_event" => self.emits_event = true,
+                    "caller" => self.checks_caller = true,
+                    _ if is_guard_name(&method) => self.checks_caller = true,
+                    _ => {}
+                }
+            }
+            Expr::Call(call) => {
+                if let Expr::Path(path) = &*call.func {
+                    if let Some(segment) = path.path.segments.last() {
+                        self.invoked.push((segment.ident.to_string(), line));
+                        if is_guard_name(&segment.ident.to_string()) {
+                            self.checks_caller = true;
+                        }
+                    }
+                }
+            }
+            Expr::Macro(mac) if is_guard_macro(&mac.mac) => self.checks_caller = true,
+            _ => {}
+        }
+
+        visit::visit_expr(self, node);
+    }
+
+    fn visit_stmt_macro(&mut self, node: &'ast syn::StmtMacro) {
+        if is_guard_macro(&node.mac) {
+            self.checks_caller = true;
+        }
+        visit::visit_stmt_macro(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        // Macro arguments aren't parsed, so look for `self.<field>` tokens
+        let tokens = flatten_tokens(node.tokens.clone());
+        for window in tokens.windows(3) {
+            if let [TokenTree::Ident(this), TokenTree::Punct(dot), TokenTree::Ident(field)] = window
+            {
+                if this == "self" && dot.as_char() == '.' {
+                    self.field_reads.push(field.to_string());
+                }
+            }
+        }
+
+        // Caller checks in guard macros, e.g. `assert_eq!(self.env().caller(), self.owner)`
+        if is_guard_macro(node) {
+            let parser = syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated;
+            if let Ok(args) = node.parse_body_with(parser) {
+                let args: Vec<Expr> = args.into_iter().collect();
+
+                let mut inner = BodyVisitor::default();
+                for arg in &args {
+                    inner.visit_expr(arg);
+                }
+                self.caller_checks.extend(inner.caller_checks);
+
+                let is_comparison = node
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|s| s.ident == "assert_eq" || s.ident == "assert_ne");
+                if let (true, [left, right, ..]) = (is_comparison, args.as_slice()) {
+                    let line = node.path.span().start().line;
+                    if mentions_caller(left) {
+                        self.check_caller_against(right, line);
+                    } else if mentions_caller(right) {
+                        self.check_caller_against(left, line);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl BodyVisitor {
+    /// Record what `caller` is compared with
+    fn check_caller_against(&mut self, other: &Expr, line: usize) {
+        let check = match other {
+            _ if self_field(other).is_some() => self_field(other).map(|name| ("field", name)),
+            Expr::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+                .filter(|name| is_constant_name(name))
+                .map(|name| ("account", name)),
+            _ => None,
+        };
+
+        if let Some((kind, name)) = check {
+            self.caller_checks.push(CallerCheck { kind, name, line });
+        }
+    }
+}
+
+/// A `use` item as written, e.g. `pub(crate) use ink::prelude::{string::String, vec::Vec as V};`
+fn render_use(item: &syn::ItemUse) -> String {
+    fn render(tree: &syn::UseTree) -> String {
+        match tree {
+            syn::UseTree::Path(path) => format!("{}::{}", path.ident, render(&path.tree)),
+            syn::UseTree::Name(name) => name.ident.to_string(),
+            syn::UseTree::Rename(rename) => format!("{} as {}", rename.ident, rename.rename),
+            syn::UseTree::Glob(_) => "*".to_string(),
+            syn::UseTree::Group(group) => {
+                let items: Vec<String> = group.items.iter().map(render).collect();
+                format!("{{{}}}", items.join(", "))
+            }
+        }
+    }
+
+    let mut vis = item.vis.to_token_stream().to_string();
+    vis.retain(|c| c != ' ');
+    if !vis.is_empty() {
+        vis.push(' ');
+    }
+    let leading_colon = if item.leading_colon.is_some() {
+        "::"
+    } else {
+        ""
+    };
+    format!("{}use {}{};", vis, leading_colon, render(&item.tree))
+}
+
+/// Whether an expression refers to the caller, e.g. `self.env().caller()` or a `caller` local
+fn mentions_caller(expr: &Expr) -> bool {
+    flatten_tokens(expr.to_token_stream())
+        .iter()
+        .any(|t| matches!(t, TokenTree::Ident(ident) if ident == "caller"))
+}
+
+/// `ADMIN`, `TREASURY_ACCOUNT`
+fn is_constant_name(name: &str) -> bool {
+    name.len() > 1
+        && name.chars().any(|c| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Collects identifiers used in test code, including inside macros like `assert_eq!`
+#[derive(Default)]
+struct TestReferences {
+    idents: HashSet<String>,
+}
+
+impl<'ast> Visit<'ast> for TestReferences {
+    fn visit_ident(&mut self, node: &'ast proc_macro2::Ident) {
+        self.idents.insert(node.to_string());
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        for token in flatten_tokens(node.tokens.clone()) {
+            if let TokenTree::Ident(ident) = token {
+                self.idents.insert(ident.to_string());
+            }
+        }
+        visit::visit_macro(self, node);
+    }
+}
+
+fn flatten_tokens(stream: proc_macro2::TokenStream) -> Vec<TokenTree> {
+    let mut tokens = Vec::new();
+    for token in stream {
+        match token {
+            TokenTree::Group(group) => tokens.extend(flatten_tokens(group.stream())),
+            other => tokens.push(other),
+        }
+    }
+    tokens
+}
+
+/// Walk a `build_call()...invoke()` chain down to its root
+fn external_call(invoke: &syn::ExprMethodCall) -> Option<ExternalCall> {
+    let mut target = None;
+    let mut selector = None;
+    let mut receiver = &*invoke.receiver;
+
+    loop {
+        match receiver {
+            Expr::MethodCall(call) => {
+                match (call.method.to_string().as_str(), call.args.first()) {
+                    // `.call(addr)` and `.call_type(Call::new(addr))`
+                    ("call", Some(arg)) => target = Some(arg),
+                    ("call_type", Some(Expr::Call(inner))) => target = inner.args.first(),
+                    ("exec_input", Some(arg)) => selector = selector_label(arg),
+                    _ => {}
+                }
+                receiver = &call.receiver;
+            }
+            Expr::Call(call) => {
+                let Expr::Path(path) = &*call.func else {
+                    return None;
+                };
+                let is_build_call = path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "build_call");
+                if !is_build_call {
+                    return None;
+                }
+
+                let mut target = target
+                    .map(|expr| expr.to_token_stream().to_string())
+                    .unwrap_or_else(|| "?".to_string());
+                target.retain(|c| c != ' ');
+                return Some(ExternalCall {
+                    target,
+                    selector,
+                    line: invoke.method.span().start().line,
+                });
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Label of the first `selector_bytes!("label")` in an expression
+fn selector_label(expr: &Expr) -> Option<String> {
+    #[derive(Default)]
+    struct Finder(Option<String>);
+
+    impl<'ast> Visit<'ast> for Finder {
+        fn visit_macro(&mut self, node: &'ast syn::Macro) {
+            let is_selector = node
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "selector_bytes");
+            if self.0.is_none() && is_selector {
+                self.0 = node.parse_body::<syn::LitStr>().ok().map(|lit| lit.value());
+            }
+        }
+    }
+
+    let mut finder = Finder::default();
+    finder.visit_expr(expr);
+    finder.0
+}
+
+/// Field name if `expr` is `self.<field>`
+fn self_field(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Field(field) => match (&*field.base, &field.member) {
+            (Expr::Path(base), syn::Member::Named(name)) if base.path.is_ident("self") => {
+                Some(name.to_string())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_compound_assign(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::AddAssign(_) | BinOp::SubAssign(_) | BinOp::MulAssign(_)
+    )
+}
+
+/// `_` or a plain lowercase binding such as `other`
+fn is_catch_all(pat: &syn::Pat) -> bool {
+    match pat {
+        syn::Pat::Wild(_) => true,
+        syn::Pat::Ident(ident) => {
+            ident.subpat.is_none()
+                && ident
+                    .ident
+                    .to_string()
+                    .starts_with(|c: char| c.is_lowercase() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+/// Names of helpers that typically guard access (`only_owner`, `ensure_admin`, ...)
+fn is_guard_name(name: &str) -> bool {
+    name.starts_with("only_") || name.starts_with("ensure_") || name.contains("owner")
+}
+
+fn is_guard_macro(mac: &syn::Macro) -> bool {
+    mac.path
+        .segments
+        .last()
+        .map(|segment| {
+            matches!(
+                segment.ident.to_string().as_str(),
+                "assert" | "assert_eq" | "assert_ne" | "require" | "ensure"
+            )
+        })
+        .unwrap_or(false)
+}
+
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Paren(paren) => is_literal(&paren.expr),
+        Expr::Binary(bin) => is_literal(&bin.left) && is_literal(&bin.right),
+        _ => false,
+    }
+}
+
+/// Arguments of all `#[ink(...)]` attributes, e.g. `["message", "payable"]`
+fn ink_args(attrs: &[Attribute]) -> Vec<String> {
+    let mut args = Vec::new();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("ink")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                args.push(ident.to_string());
+            }
+            // Skip values such as `selector = 0x1234`
+            if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            }
+            Ok(())
+        });
+    }
+
+    args
+}
+
+fn is_cfg_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg") && {
+            let tokens = attr.meta.to_token_stream().to_string();
+            tokens
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .any(|t| t == "test")
+        }
+    })
+}
+
+fn is_test_fn(attrs: &[Attribute]) -> bool {
+    is_cfg_test(attrs)
+        || attrs.iter().any(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .map(|segment| segment.ident == "test")
+                .unwrap_or(false)
+        })
+}
+
+fn path_is(path: &syn::Path, expected: &[&str]) -> bool {
+    path.segments.len() == expected.len()
+        && path
+            .segments
+            .iter()
+            .zip(expected)
+            .all(|(segment, name)| segment.ident == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = r#"
+#[ink::contract]
+mod flipper {
+    use ink::storage::Mapping;
+
+    #[ink(storage)]
+    pub struct Flipper {
+        value: bool,
+        name: String,
+        balances: Mapping<AccountId, Balance>,
+    }
+
+    impl Flipper {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { value: false, name: String::new(), balances: Mapping::default() }
+        }
+
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {
+            let caller = self.env().caller();
+            let current = self.balances.get(caller).unwrap_or(0);
+            self.balances.insert(caller, &(current + self.env().transferred_value()));
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> bool {
+            if self.value && !self.name.is_empty() {
+                return true;
+            }
+            match self.value {
+                true => true,
+                false => false,
+            }
+        }
+
+        fn helper(&self) -> u32 {
+            let max = 1 + 2;
+            let safe = max.checked_add(1).unwrap();
+            safe
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[ink::test]
+        fn it_works() {
+            let x = 1u32 + 2u32 * 3;
+            assert!(x.checked_sub(1).unwrap() > 0);
+        }
+    }
+}
+"#;
+
+    fn function<'a>(model: &'a SourceModel, name: &str) -> &'a FunctionModel {
+        model
+            .functions
+            .iter()
+            .find(|f| f.info.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_detects_ink_items() {
+        let model = parse_source(CONTRACT).unwrap();
+
+        assert!(model.is_contract);
+        assert_eq!(model.functions.len(), 4);
+        assert!(function(&model, "new").is_constructor());
+
+        let deposit = function(&model, "deposit");
+        assert!(deposit.is_message());
+        assert!(deposit.info.is_payable);
+        assert!(deposit.mutates);
+        assert!(deposit.checks_caller);
+        assert_eq!(deposit.info.line, 20);
+
+        let get = function(&model, "get");
+        assert!(get.is_message());
+        assert!(!get.info.is_payable);
+        assert!(!get.mutates);
+
+        assert_eq!(function(&model, "helper").info.visibility, "private");
+        assert_eq!(model.storage_fields.len(), 3);
+        assert_eq!(model.storage_fields[1].ty, "String");
+        assert_eq!(model.storage_fields[2].ty, "Mapping<AccountId,Balance>");
+    }
+
+    #[test]
+    fn test_imports() {
+        let source = r#"
+use crate::users::User;
+pub use ink::prelude::{string::String, vec::Vec as V};
+pub(crate) use ::core::*;
+"#;
+        let model = parse_source(source).unwrap();
+
+        assert_eq!(
+            model.imports,
+            vec![
+                "use crate::users::User;",
+                "pub use ink::prelude::{string::String, vec::Vec as V};",
+                "pub(crate) use ::core::*;",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_and_unwrap_lines() {
+        let model = parse_source(CONTRACT).unwrap();
+
+        // Only the runtime addition in `deposit` counts, not `1 + 2` or test code
+        assert_eq!(function(&model, "deposit").arithmetic, vec![23]);
+        assert!(function(&model, "helper").arithmetic.is_empty());
+        assert_eq!(function(&model, "helper").unwraps, vec![39]);
+        assert!(model.functions.iter().all(|f| f.info.name != "it_works"));
+    }
+
+    #[test]
+    fn test_suppressions() {
+        let source = r##"
+fn a(x: u32) -> u32 {
+    x + 1 // forge-ignore: unchecked-arithmetic
+}
+
+// forge-ignore
+/// Docs
+#[inline]
+fn b() {}
+
+fn c(x: u32) -> &'static str {
+    let _ = x + 1; let url = "http://glin.network"; // forge-ignore: unchecked-arithmetic
+    let _ = x + 2; let note = "// forge-ignore";
+    let _ = ('"', r#"" // forge-ignore"#, x + 3);
+    /* a URL: http://example.com */ "https://glin.network"
+}
+"##;
+        let model = parse_source(source).unwrap();
+
+        assert!(model.is_suppressed("unchecked-arithmetic", 3));
+        assert!(!model.is_suppressed("unwrap", 3));
+        assert!(model.is_suppressed("unwrap", 9));
+        assert!(!model.is_suppressed("unwrap", 2));
+
+        // `//` in strings, character literals and block comments is no comment
+        assert!(model.is_suppressed("unchecked-arithmetic", 12));
+        assert!(![13, 14, 15, 16]
+            .iter()
+            .any(|line| model.suppressions.contains_key(line)));
+    }
+
+    #[test]
+    fn test_complexity() {
+        let model = parse_source(CONTRACT).unwrap();
+
+        // base + if + && + one extra match arm
+        assert_eq!(function(&model, "get").info.complexity, 4);
+        assert_eq!(function(&model, "new").info.complexity, 1);
+    }
+}
@@ -0,0 +1,180 @@
+// Baseline of accepted findings
+//
+// A baseline records the findings that already exist in a codebase so that
+// only newly introduced ones are reported. Findings are matched by rule, file
+// and message rather than line number, so unrelated edits that shift code
+// around don't resurface them. Duplicates are counted: if a file had two
+// `unwrap` findings in a function and now has three, one is reported.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::AnalysisReport;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub findings: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub rule: String,
+    pub file: String,
+    pub message: String,
+}
+
+impl BaselineEntry {
+    fn new(rule: &str, file: &str, message: &str) -> Self {
+        Self {
+            rule: rule.to_string(),
+            file: file.trim_start_matches("./").replace('\\', "/"),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Baseline {
+    /// Capture all findings of a report
+    pub fn from_report(report: &AnalysisReport) -> Self {
+        let mut findings: Vec<BaselineEntry> = report
+            .security_issues
+            .iter()
+            .chain(&report.maintainability_issues)
+            .chain(&report.migration_issues)
+            .map(|i| BaselineEntry::new(&i.rule, &i.file, &i.description))
+            .chain(
+                report
+                    .gas_optimizations
+                    .iter()
+                    .map(|o| BaselineEntry::new(&o.rule, &o.file, &o.description)),
+            )
+            .collect();
+        findings
+            .sort_by(|a, b| (&a.file, &a.rule, &a.message).cmp(&(&b.file, &b.rule, &b.message)));
+
+        Self {
+            version: 1,
+            findings,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse baseline: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Failed to write baseline: {}", path.display()))
+    }
+
+    /// Remove findings covered by the baseline; returns how many were removed
+    pub fn filter_report(&self, report: &mut AnalysisReport) -> usize {
+        let mut remaining: HashMap<&BaselineEntry, usize> = HashMap::new();
+        for entry in &self.findings {
+            *remaining.entry(entry).or_default() += 1;
+        }
+
+        let mut is_known = |entry: BaselineEntry| match remaining.get_mut(&entry) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        };
+
+        let before = report.finding_count();
+        report
+            .security_issues
+            .retain(|i| !is_known(BaselineEntry::new(&i.rule, &i.file, &i.description)));
+        report
+            .gas_optimizations
+            .retain(|o| !is_known(BaselineEntry::new(&o.rule, &o.file, &o.description)));
+        report
+            .maintainability_issues
+            .retain(|i| !is_known(BaselineEntry::new(&i.rule, &i.file, &i.description)));
+        report
+            .migration_issues
+            .retain(|i| !is_known(BaselineEntry::new(&i.rule, &i.file, &i.description)));
+
+        report.summary.security_issues_count = report.security_issues.len();
+        report.summary.gas_optimization_count = report.gas_optimizations.len();
+        report.summary.maintainability_issues_count = report.maintainability_issues.len();
+        report.summary.migration_issues_count = report.migration_issues.len();
+        report.contracts = super::workspace::summarize(report);
+
+        before - report.finding_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisSummary, ComplexityMetrics, SecurityIssue};
+
+    fn issue(description: &str, line: usize) -> SecurityIssue {
+        SecurityIssue {
+            rule: "unwrap".to_string(),
+            severity: "low".to_string(),
+            category: "Error Handling".to_string(),
+            description: description.to_string(),
+            file: "./lib.rs".to_string(),
+            line: Some(line),
+            recommendation: String::new(),
+        }
+    }
+
+    fn report(issues: Vec<SecurityIssue>) -> AnalysisReport {
+        AnalysisReport {
+            summary: AnalysisSummary {
+                total_files: 1,
+                total_lines: 0,
+                total_functions: 0,
+                security_issues_count: issues.len(),
+                gas_optimization_count: 0,
+                maintainability_issues_count: 0,
+                migration_issues_count: 0,
+                average_complexity: 0.0,
+            },
+            files: vec![],
+            security_issues: issues,
+            gas_optimizations: vec![],
+            maintainability_issues: vec![],
+            migration_issues: vec![],
+            complexity_metrics: ComplexityMetrics {
+                cyclomatic_complexity: HashMap::new(),
+                cognitive_complexity: HashMap::new(),
+                maintainability_index: 100.0,
+            },
+            contracts: vec![],
+            storage_layouts: vec![],
+            call_graph: None,
+            access_control: vec![],
+            gas_profile: vec![],
+            diff: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_only_new_findings() {
+        let baseline = Baseline::from_report(&report(vec![issue("unwrap in 'get'", 10)]));
+
+        // Same finding on a shifted line, plus a duplicate and a new one
+        let mut current = report(vec![
+            issue("unwrap in 'get'", 14),
+            issue("unwrap in 'get'", 20),
+            issue("unwrap in 'set'", 30),
+        ]);
+
+        assert_eq!(baseline.filter_report(&mut current), 1);
+        assert_eq!(current.security_issues.len(), 2);
+        assert_eq!(current.security_issues[0].line, Some(20));
+        assert_eq!(current.summary.security_issues_count, 2);
+        assert_eq!(baseline.findings[0].file, "lib.rs");
+    }
+}
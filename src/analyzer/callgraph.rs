@@ -0,0 +1,416 @@
+// Message-level call graph
+//
+// Nodes are the messages and constructors of every contract, plus the helper
+// functions, trait definitions and external callees they connect to. Edges
+// are internal `self.method()` calls, cross-contract calls (through a
+// `...Ref` storage field or a `build_call` chain) and trait implementations.
+// The graph renders as Graphviz DOT or as a Mermaid flowchart.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::ast::SourceModel;
+use super::FileAnalysis;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CallGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub id: String,
+    pub label: String,
+    /// "message", "constructor", "function", "external" or "trait"
+    pub kind: String,
+    /// Contract the node belongs to; `None` for external callees and traits
+    pub contract: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    /// "internal", "cross-contract" or "implements"
+    pub kind: String,
+    /// Called message, if known
+    pub label: Option<String>,
+}
+
+/// Build the call graph of all analyzed files
+pub fn build(files: &[(FileAnalysis, SourceModel)]) -> CallGraph {
+    let mut graph = CallGraph::default();
+
+    // Functions of each contract, across all of its files
+    let mut contracts: BTreeMap<String, Vec<&SourceModel>> = BTreeMap::new();
+    for (analysis, model) in files {
+        if model.functions.is_empty() {
+            continue;
+        }
+        let contract = analysis.contract.clone().unwrap_or_else(|| {
+            std::path::Path::new(&analysis.path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        contracts.entry(contract).or_default().push(model);
+    }
+
+    let mut nodes: BTreeMap<String, Node> = BTreeMap::new();
+    for (contract, models) in &contracts {
+        let functions: Vec<_> = models.iter().flat_map(|m| &m.functions).collect();
+        let ref_fields: BTreeMap<&str, &str> = models
+            .iter()
+            .flat_map(|m| &m.storage_fields)
+            .filter_map(|field| contract_ref(&field.ty).map(|ty| (field.name.as_str(), ty)))
+            .collect();
+
+        for func in &functions {
+            let from = node_id(contract, &func.info.name);
+            if func.is_message() || func.is_constructor() {
+                nodes
+                    .entry(from.clone())
+                    .or_insert_with(|| function_node(contract, &func.info.name, &func.info.kind));
+            }
+
+            for call in &func.calls {
+                let Some(callee) = functions.iter().find(|f| &f.info.name == call) else {
+                    continue;
+                };
+                let to = node_id(contract, call);
+                nodes
+                    .entry(to.clone())
+                    .or_insert_with(|| function_node(contract, call, &callee.info.kind));
+                graph.push_edge(&from, &to, "internal", None);
+            }
+
+            for (field, method) in &func.field_calls {
+                let Some(ty) = ref_fields.get(field.as_str()) else {
+                    continue;
+                };
+                let to = external_node(&mut nodes, ty);
+                graph.push_edge(&from, &to, "cross-contract", Some(method.clone()));
+            }
+
+            for external in &func.external_calls {
+                let to = external_node(&mut nodes, &external.target);
+                graph.push_edge(&from, &to, "cross-contract", external.selector.clone());
+            }
+
+            if let Some(trait_name) = &func.trait_name {
+                let to = format!("trait_{}", sanitize(trait_name));
+                nodes.entry(to.clone()).or_insert_with(|| Node {
+                    id: to.clone(),
+                    label: format!("trait {}", trait_name),
+                    kind: "trait".to_string(),
+                    contract: None,
+                });
+                graph.push_edge(&from, &to, "implements", None);
+            }
+        }
+    }
+
+    // Helpers only appear when connected to something
+    let connected = |id: &str| graph.edges.iter().any(|e| e.from == id || e.to == id);
+    graph.nodes = nodes
+        .into_values()
+        .filter(|node| node.kind != "function" || connected(&node.id))
+        .collect();
+    let ids: Vec<String> = graph.nodes.iter().map(|n| n.id.clone()).collect();
+    graph
+        .edges
+        .retain(|e| ids.contains(&e.from) && ids.contains(&e.to));
+
+    graph
+}
+
+impl CallGraph {
+    fn push_edge(&mut self, from: &str, to: &str, kind: &str, label: Option<String>) {
+        let edge = Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind: kind.to_string(),
+            label,
+        };
+        if !self.edges.contains(&edge) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Nodes grouped by contract, in order
+    fn by_contract(&self) -> BTreeMap<Option<&str>, Vec<&Node>> {
+        let mut groups: BTreeMap<Option<&str>, Vec<&Node>> = BTreeMap::new();
+        for node in &self.nodes {
+            groups
+                .entry(node.contract.as_deref())
+                .or_default()
+                .push(node);
+        }
+        groups
+    }
+
+    /// Render as a Graphviz digraph
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        out.push_str("digraph calls {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str("  node [shape=box, fontname=\"Helvetica\"];\n");
+
+        for (contract, nodes) in self.by_contract() {
+            let indent = if contract.is_some() { "    " } else { "  " };
+            if let Some(contract) = contract {
+                let _ = writeln!(out, "  subgraph cluster_{} {{", sanitize(contract));
+                let _ = writeln!(out, "    label=\"{}\";", escape(contract));
+            }
+            for node in nodes {
+                let style = match node.kind.as_str() {
+                    "message" => "style=bold",
+                    "constructor" => "shape=oval",
+                    "external" => "shape=component",
+                    "trait" => "shape=note",
+                    _ => "style=dashed",
+                };
+                let _ = writeln!(
+                    out,
+                    "{}{} [label=\"{}\", {}];",
+                    indent,
+                    node.id,
+                    escape(&node.label),
+                    style
+                );
+            }
+            if contract.is_some() {
+                out.push_str("  }\n");
+            }
+        }
+
+        for edge in &self.edges {
+            let mut attrs = Vec::new();
+            match edge.kind.as_str() {
+                "cross-contract" => attrs.push("color=red".to_string()),
+                "implements" => attrs.push("style=dotted, arrowhead=empty".to_string()),
+                _ => {}
+            }
+            if let Some(label) = &edge.label {
+                attrs.push(format!("label=\"{}\"", escape(label)));
+            }
+            let attrs = if attrs.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attrs.join(", "))
+            };
+            let _ = writeln!(out, "  {} -> {}{};", edge.from, edge.to, attrs);
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("graph LR\n");
+
+        for (contract, nodes) in self.by_contract() {
+            let indent = if contract.is_some() { "    " } else { "  " };
+            if let Some(contract) = contract {
+                let _ = writeln!(
+                    out,
+                    "  subgraph {}[\"{}\"]",
+                    sanitize(contract),
+                    escape(contract)
+                );
+            }
+            for node in nodes {
+                let label = escape(&node.label);
+                let shape = match node.kind.as_str() {
+                    "message" => format!("[\"{}\"]", label),
+                    "constructor" => format!("([\"{}\"])", label),
+                    "external" => format!("[[\"{}\"]]", label),
+                    "trait" => format!("{{{{\"{}\"}}}}", label),
+                    _ => format!("(\"{}\")", label),
+                };
+                let _ = writeln!(out, "{}{}{}", indent, node.id, shape);
+            }
+            if contract.is_some() {
+                out.push_str("  end\n");
+            }
+        }
+
+        for edge in &self.edges {
+            let arrow = match (edge.kind.as_str(), &edge.label) {
+                ("cross-contract", Some(label)) => format!("-. {} .->", escape(label)),
+                ("cross-contract", None) => "-.->".to_string(),
+                ("implements", _) => "-. implements .->".to_string(),
+                (_, Some(label)) => format!("-- {} -->", escape(label)),
+                _ => "-->".to_string(),
+            };
+            let _ = writeln!(out, "  {} {} {}", edge.from, arrow, edge.to);
+        }
+
+        out
+    }
+}
+
+/// Contract type referenced by a storage field, e.g. `OtherRef` or `Lazy<OtherRef>`
+fn contract_ref(ty: &str) -> Option<&str> {
+    ty.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .find(|part| part.len() > 3 && part.ends_with("Ref"))
+}
+
+fn function_node(contract: &str, name: &str, kind: &str) -> Node {
+    Node {
+        id: node_id(contract, name),
+        label: name.to_string(),
+        kind: kind.to_string(),
+        contract: Some(contract.to_string()),
+    }
+}
+
+fn external_node(nodes: &mut BTreeMap<String, Node>, target: &str) -> String {
+    let id = format!("ext_{}", sanitize(target));
+    nodes.entry(id.clone()).or_insert_with(|| Node {
+        id: id.clone(),
+        label: target.to_string(),
+        kind: "external".to_string(),
+        contract: None,
+    });
+    id
+}
+
+fn node_id(contract: &str, name: &str) -> String {
+    format!("{}__{}", sanitize(contract), sanitize(name))
+}
+
+/// Identifiers valid in both DOT and Mermaid
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn escape(label: &str) -> String {
+    label.replace('"', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ast::parse_source;
+
+    const SOURCE: &str = r#"
+#[ink::contract]
+mod vault {
+    #[ink(storage)]
+    pub struct Vault {
+        token: TokenRef,
+        owner: AccountId,
+    }
+
+    impl Vault {
+        #[ink(constructor)]
+        pub fn new(token: TokenRef) -> Self {
+            Self { token, owner: Self::env().caller() }
+        }
+
+        #[ink(message)]
+        pub fn deposit(&mut self, amount: u128) {
+            self.ensure_owner();
+            self.token.transfer_from(self.env().caller(), amount);
+        }
+
+        #[ink(message)]
+        pub fn ping(&self) {
+            build_call::<DefaultEnvironment>()
+                .call(self.oracle)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!("ping"))))
+                .returns::<()>()
+                .invoke();
+        }
+
+        fn ensure_owner(&self) {
+            assert_eq!(self.env().caller(), self.owner);
+        }
+
+        fn unused(&self) {}
+    }
+
+    impl Ownable for Vault {
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            self.owner
+        }
+    }
+}
+"#;
+
+    fn graph() -> CallGraph {
+        let model = parse_source(SOURCE).unwrap();
+        let analysis = FileAnalysis {
+            path: "vault/lib.rs".to_string(),
+            contract: Some("vault".to_string()),
+            lines_of_code: 0,
+            functions: vec![],
+            imports: vec![],
+            traits: vec![],
+            structs: vec![],
+        };
+        build(&[(analysis, model)])
+    }
+
+    #[test]
+    fn test_call_graph_edges() {
+        let graph = graph();
+        let edges: Vec<(&str, &str, &str, Option<&str>)> = graph
+            .edges
+            .iter()
+            .map(|e| {
+                (
+                    e.from.as_str(),
+                    e.to.as_str(),
+                    e.kind.as_str(),
+                    e.label.as_deref(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            edges,
+            vec![
+                ("vault__deposit", "vault__ensure_owner", "internal", None),
+                (
+                    "vault__deposit",
+                    "ext_TokenRef",
+                    "cross-contract",
+                    Some("transfer_from")
+                ),
+                (
+                    "vault__ping",
+                    "ext_self_oracle",
+                    "cross-contract",
+                    Some("ping")
+                ),
+                ("vault__owner", "trait_Ownable", "implements", None),
+            ]
+        );
+        // Unconnected helpers are left out
+        assert!(graph.nodes.iter().all(|n| n.label != "unused"));
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let graph = graph();
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("  subgraph vault[\"vault\"]\n"));
+        assert!(mermaid.contains("vault__deposit -. transfer_from .-> ext_TokenRef"));
+        assert!(mermaid.contains("trait_Ownable{{\"trait Ownable\"}}"));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph calls {"));
+        assert!(dot.contains("vault__deposit -> vault__ensure_owner;"));
+        assert!(dot.contains("vault__ping -> ext_self_oracle [color=red, label=\"ping\"];"));
+    }
+}
@@ -0,0 +1,172 @@
+// Analyzer configuration loaded from `analyze.toml`
+//
+//   # Files to skip, relative to the analyzed path
+//   ignore = ["tests/**", "src/generated.rs"]
+//
+//   # Rhai rule plugins, relative to this file
+//   plugins = ["rules/naming.rhai"]
+//
+//   [rules]
+//   unwrap = "off"                  # disable a rule
+//   unchecked-arithmetic = "high"   # override its severity
+//   naming/no-get-prefix = "medium" # plugin rules are `<plugin>/<rule>`
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::rules;
+
+pub const CONFIG_FILE: &str = "analyze.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AnalyzerConfig {
+    /// Glob patterns of files to skip
+    #[serde(default)]
+    pub ignore: Vec<String>,
+
+    /// Rhai scripts with custom rules
+    #[serde(default)]
+    pub plugins: Vec<PathBuf>,
+
+    /// Rule id -> "off" or a severity ("high", "medium", "low")
+    #[serde(default)]
+    pub rules: HashMap<String, String>,
+}
+
+/// What to do with findings of a rule
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleSetting {
+    Off,
+    Severity(String),
+}
+
+impl AnalyzerConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut config: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        config.validate()?;
+
+        let base = path.parent().unwrap_or(Path::new("."));
+        for plugin in &mut config.plugins {
+            if plugin.is_relative() {
+                *plugin = base.join(&*plugin);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Look for `analyze.toml` next to the analyzed path, then in the current directory
+    pub fn discover(analyzed: &Path) -> Result<Self> {
+        let base = if analyzed.is_file() {
+            analyzed.parent().unwrap_or(Path::new("."))
+        } else {
+            analyzed
+        };
+
+        for candidate in [base.join(CONFIG_FILE), Path::new(CONFIG_FILE).to_path_buf()] {
+            if candidate.exists() {
+                return Self::load(&candidate);
+            }
+        }
+
+        Ok(Self::default())
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (rule, setting) in &self.rules {
+            // Plugin rules are only known once their scripts run
+            if rules::find_rule(rule).is_none() && !rule.contains('/') {
+                let known: Vec<_> = rules::RULES.iter().map(|r| r.id).collect();
+                anyhow::bail!("Unknown rule '{}'. Known rules: {}", rule, known.join(", "));
+            }
+            if !matches!(setting.as_str(), "off" | "high" | "medium" | "low") {
+                anyhow::bail!(
+                    "Invalid setting '{}' for rule '{}' (use off, high, medium or low)",
+                    setting,
+                    rule
+                );
+            }
+        }
+
+        self.ignore_set()?;
+        Ok(())
+    }
+
+    pub fn setting(&self, rule: &str) -> Option<RuleSetting> {
+        self.rules.get(rule).map(|setting| match setting.as_str() {
+            "off" => RuleSetting::Off,
+            severity => RuleSetting::Severity(severity.to_string()),
+        })
+    }
+
+    pub fn ignore_set(&self) -> Result<GlobSet> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.ignore {
+            builder.add(
+                Glob::new(pattern)
+                    .with_context(|| format!("Invalid ignore pattern: {}", pattern))?,
+            );
+        }
+        Ok(builder.build()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config: AnalyzerConfig = toml::from_str(
+            r#"
+ignore = ["tests/**"]
+
+[rules]
+unwrap = "off"
+unchecked-arithmetic = "high"
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.setting("unwrap"), Some(RuleSetting::Off));
+        assert_eq!(
+            config.setting("unchecked-arithmetic"),
+            Some(RuleSetting::Severity("high".to_string()))
+        );
+        assert_eq!(config.setting("missing-event"), None);
+        assert!(config.ignore_set().unwrap().is_match("tests/flipper.rs"));
+    }
+
+    #[test]
+    fn test_maintainability_rules() {
+        let config: AnalyzerConfig = toml::from_str(
+            r#"
+[rules]
+unused-storage-field = "medium"
+untested-message = "off"
+unreachable-match-arm = "high"
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.setting("untested-message"), Some(RuleSetting::Off));
+        assert_eq!(
+            config.setting("unreachable-match-arm"),
+            Some(RuleSetting::Severity("high".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_rule() {
+        let config: AnalyzerConfig = toml::from_str("[rules]\nno-such-rule = \"off\"").unwrap();
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,265 @@
+// Findings introduced or resolved relative to a git revision
+//
+// `analyze --diff <ref>` checks the revision out into a temporary worktree,
+// runs the same analysis there and compares both reports with the baseline
+// matching (rule, file and message), so only findings added by the change stay
+// in the report and findings that disappeared are listed as resolved.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::baseline::{Baseline, BaselineEntry};
+use super::{AnalysisReport, AnalyzeOptions};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffSummary {
+    /// Revision the working tree was compared with
+    pub base: String,
+    /// Findings present in both revisions, hidden from the report
+    pub unchanged: usize,
+    /// Findings of the base revision that no longer occur
+    pub resolved: Vec<BaselineEntry>,
+}
+
+/// A detached worktree, removed again when dropped
+struct Worktree {
+    repo: PathBuf,
+    dir: PathBuf,
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let _ = git(
+            &self.repo,
+            &["worktree", "remove", "--force", &self.dir.to_string_lossy()],
+        );
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Findings of a base revision, with paths relative to the repository root
+pub struct Revision {
+    pub git_ref: String,
+    repo: PathBuf,
+    report: AnalysisReport,
+}
+
+/// Analyze `path` as it was at `git_ref`
+pub fn analyze_ref(path: &Path, git_ref: &str, options: &AnalyzeOptions) -> Result<Revision> {
+    let start = if path.is_dir() {
+        path
+    } else {
+        path.parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+    };
+    let repo =
+        PathBuf::from(git(start, &["rev-parse", "--show-toplevel"])?.trim()).canonicalize()?;
+    git(
+        &repo,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", git_ref),
+        ],
+    )
+    .with_context(|| format!("Unknown git revision '{}'", git_ref))?;
+
+    let relative = path
+        .canonicalize()?
+        .strip_prefix(&repo)
+        .map(Path::to_path_buf)
+        .with_context(|| format!("{} is outside the git repository", path.display()))?;
+
+    let worktree = Worktree {
+        dir: std::env::temp_dir().join(format!("glin-forge-diff-{}", std::process::id())),
+        repo: repo.clone(),
+    };
+    git(
+        &repo,
+        &[
+            "worktree",
+            "add",
+            "--detach",
+            "--quiet",
+            &worktree.dir.to_string_lossy(),
+            git_ref,
+        ],
+    )
+    .with_context(|| format!("Failed to check out '{}'", git_ref))?;
+
+    let base_path = worktree.dir.join(&relative);
+    if !base_path.exists() {
+        anyhow::bail!("{} does not exist at '{}'", relative.display(), git_ref);
+    }
+
+    // Only findings are compared
+    let options = AnalyzeOptions {
+        call_graph: false,
+        access_control: false,
+        storage: false,
+        ..options.clone()
+    };
+    let mut report = super::analyze_path(&base_path, &options)?;
+
+    let tree = worktree.dir.canonicalize()?;
+    rename_files(&mut report, |file| {
+        relative_to(Path::new(file), &tree).map(|rest| rest.to_string_lossy().to_string())
+    });
+
+    Ok(Revision {
+        git_ref: git_ref.to_string(),
+        repo,
+        report,
+    })
+}
+
+/// Keep only findings introduced since `base` and record the resolved ones
+pub fn compare(report: &mut AnalysisReport, base: Revision) {
+    let Revision {
+        git_ref,
+        repo,
+        report: mut base,
+    } = base;
+
+    // Name base files the way the working tree run does
+    let names: HashMap<PathBuf, String> = finding_files(report)
+        .into_iter()
+        .filter_map(|file| Some((relative_to(Path::new(&file), &repo)?, file)))
+        .collect();
+    rename_files(&mut base, |file| names.get(Path::new(file)).cloned());
+
+    let current = Baseline::from_report(report);
+    let unchanged = Baseline::from_report(&base).filter_report(report);
+    current.filter_report(&mut base);
+
+    report.diff = Some(DiffSummary {
+        base: git_ref,
+        unchanged,
+        resolved: Baseline::from_report(&base).findings,
+    });
+}
+
+/// `path` relative to `root`, resolving symlinks and `./` prefixes
+fn relative_to(path: &Path, root: &Path) -> Option<PathBuf> {
+    let absolute = path.canonicalize().ok()?;
+    absolute.strip_prefix(root).ok().map(Path::to_path_buf)
+}
+
+/// Rewrite the file of every finding for which `rename` returns a new name
+fn rename_files(report: &mut AnalysisReport, rename: impl Fn(&str) -> Option<String>) {
+    for issue in report
+        .security_issues
+        .iter_mut()
+        .chain(&mut report.maintainability_issues)
+        .chain(&mut report.migration_issues)
+    {
+        if let Some(file) = rename(&issue.file) {
+            issue.file = file;
+        }
+    }
+    for opt in &mut report.gas_optimizations {
+        if let Some(file) = rename(&opt.file) {
+            opt.file = file;
+        }
+    }
+}
+
+fn finding_files(report: &AnalysisReport) -> Vec<String> {
+    let mut files: Vec<String> = report
+        .security_issues
+        .iter()
+        .chain(&report.maintainability_issues)
+        .chain(&report.migration_issues)
+        .map(|i| i.file.clone())
+        .chain(report.gas_optimizations.iter().map(|o| o.file.clone()))
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_against_ref() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let source = |body: &str| {
+            format!(
+                "#[ink::contract]\nmod c {{\n    impl C {{\n        #[ink(message)]\n        pub fn get(&self) -> u32 {{\n            {}\n        }}\n    }}\n}}\n",
+                body
+            )
+        };
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("src/lib.rs"),
+            source("self.a.get(0).unwrap().max(self.b.get(0).unwrap())"),
+        )
+        .unwrap();
+
+        let run = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+                .args(args)
+                .current_dir(root)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        run(&["init", "--quiet"]);
+        run(&["add", "."]);
+        run(&["commit", "--quiet", "-m", "base"]);
+
+        // Both unwraps fixed, an unprotected payable message added
+        std::fs::write(
+            root.join("src/lib.rs"),
+            source("self.a.get(0).unwrap_or(0)\n        }\n\n        #[ink(message, payable)]\n        pub fn fund(&mut self) {"),
+        )
+        .unwrap();
+
+        let options = AnalyzeOptions {
+            security: true,
+            ..Default::default()
+        };
+        let path = root.join("src");
+        let mut report = super::super::analyze_path(&path, &options).unwrap();
+        let base = analyze_ref(&path, "HEAD", &options).unwrap();
+        compare(&mut report, base);
+
+        let introduced: Vec<&str> = report
+            .security_issues
+            .iter()
+            .map(|i| i.rule.as_str())
+            .collect();
+        assert_eq!(introduced, vec!["payable-access-control"]);
+
+        let diff = report.diff.unwrap();
+        let resolved: Vec<&str> = diff.resolved.iter().map(|e| e.rule.as_str()).collect();
+        assert_eq!(resolved, vec!["unwrap", "unwrap"]);
+        assert_eq!(diff.unchanged, 0);
+    }
+}
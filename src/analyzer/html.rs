@@ -0,0 +1,218 @@
+// Self-contained HTML report
+//
+// Renders `templates/report/analysis.html.hbs` with summary cards, sortable
+// findings and complexity tables, and a short source excerpt around every
+// finding. CSS and JavaScript are inlined so the file can be shared as is.
+
+use anyhow::{Context, Result};
+use handlebars::Handlebars;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use super::{severity_rank, AnalysisReport};
+
+const TEMPLATE: &str = include_str!("../../templates/report/analysis.html.hbs");
+
+/// Lines of source shown before and after a finding
+const EXCERPT_CONTEXT: usize = 2;
+
+/// Render the report as a standalone HTML page
+pub fn to_html(report: &AnalysisReport) -> Result<String> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    handlebars
+        .register_template_string("report", TEMPLATE)
+        .context("Invalid HTML report template")?;
+
+    handlebars
+        .render("report", &context(report))
+        .context("Failed to render HTML report")
+}
+
+fn context(report: &AnalysisReport) -> Value {
+    let mut sources = SourceCache::default();
+
+    let groups = [
+        ("Security", &report.security_issues),
+        ("Maintainability", &report.maintainability_issues),
+        ("Migration", &report.migration_issues),
+    ];
+    let mut findings: Vec<Value> = groups
+        .iter()
+        .flat_map(|(group, issues)| issues.iter().map(move |issue| (group, issue)))
+        .map(|(group, issue)| {
+            finding(
+                &mut sources,
+                group,
+                &issue.rule,
+                &issue.severity,
+                &issue.description,
+                &issue.recommendation,
+                &issue.file,
+                issue.line,
+            )
+        })
+        .collect();
+    for opt in &report.gas_optimizations {
+        findings.push(finding(
+            &mut sources,
+            "Gas",
+            &opt.rule,
+            &opt.impact,
+            &opt.description,
+            &opt.suggestion,
+            &opt.file,
+            opt.line,
+        ));
+    }
+    // Most severe first
+    findings.sort_by_key(|f| std::cmp::Reverse(f["rank"].as_u64()));
+
+    let max_complexity = report
+        .files
+        .iter()
+        .flat_map(|f| &f.functions)
+        .map(|f| f.complexity)
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    let mut functions: Vec<Value> = report
+        .files
+        .iter()
+        .flat_map(|file| file.functions.iter().map(move |f| (file, f)))
+        .map(|(file, func)| {
+            json!({
+                "name": func.name,
+                "kind": func.kind,
+                "location": format!("{}:{}", file.path, func.line),
+                "complexity": func.complexity,
+                "width": func.complexity * 100 / max_complexity,
+                "level": match func.complexity {
+                    c if c > 10 => "bad",
+                    c if c > 5 => "warn",
+                    _ => "",
+                },
+            })
+        })
+        .collect();
+    functions.sort_by_key(|f| std::cmp::Reverse(f["complexity"].as_u64()));
+
+    let contracts: Vec<Value> = report
+        .contracts
+        .iter()
+        .map(|c| {
+            json!({
+                "name": c.name,
+                "path": c.path,
+                "files": c.files,
+                "functions": c.functions,
+                "security_issues": c.security_issues,
+                "gas_optimizations": c.gas_optimizations,
+                "score": format!("{:.0}", c.score),
+            })
+        })
+        .collect();
+
+    json!({
+        "title": "Contract Analysis Report",
+        "version": env!("CARGO_PKG_VERSION"),
+        "summary": report.summary,
+        "average_complexity": format!("{:.2}", report.summary.average_complexity),
+        "maintainability_index": format!("{:.1}", report.complexity_metrics.maintainability_index),
+        "contracts": contracts,
+        "findings": findings,
+        "functions": functions,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn finding(
+    sources: &mut SourceCache,
+    group: &str,
+    rule: &str,
+    severity: &str,
+    description: &str,
+    recommendation: &str,
+    file: &str,
+    line: Option<usize>,
+) -> Value {
+    let excerpt = line
+        .map(|line| sources.excerpt(file, line))
+        .unwrap_or_default();
+
+    json!({
+        "group": group,
+        "rule": rule,
+        "severity": severity,
+        "rank": severity_rank(severity),
+        "description": description,
+        "recommendation": recommendation,
+        "location": match line {
+            Some(line) => format!("{}:{}", file, line),
+            None => file.to_string(),
+        },
+        "excerpt": excerpt,
+    })
+}
+
+/// Reads each source file once for excerpts
+#[derive(Default)]
+struct SourceCache {
+    files: HashMap<String, Option<Vec<String>>>,
+}
+
+impl SourceCache {
+    fn excerpt(&mut self, file: &str, line: usize) -> Vec<Value> {
+        let lines = self.files.entry(file.to_string()).or_insert_with(|| {
+            std::fs::read_to_string(file)
+                .ok()
+                .map(|content| content.lines().map(str::to_string).collect())
+        });
+        let Some(lines) = lines else {
+            return Vec::new();
+        };
+
+        let start = line.saturating_sub(EXCERPT_CONTEXT).max(1);
+        let end = (line + EXCERPT_CONTEXT).min(lines.len());
+        (start..=end)
+            .map(|number| {
+                json!({
+                    "number": number,
+                    "text": lines[number - 1],
+                    "highlight": number == line,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{analyze_path, AnalyzeOptions};
+
+    #[test]
+    fn test_html_report_escapes_excerpts() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(
+            &file,
+            "fn get(v: Option<u32>) -> u32 {\n    v.unwrap()\n}\n",
+        )
+        .unwrap();
+
+        let options = AnalyzeOptions {
+            security: true,
+            ..Default::default()
+        };
+        let report = analyze_path(&file, &options).unwrap();
+        let html = to_html(&report).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("sev-low"));
+        assert!(html.contains("v.unwrap()"));
+        // Source is HTML escaped
+        assert!(html.contains("Option&lt;u32&gt;"));
+        assert!(!html.contains("<script src"));
+    }
+}
@@ -0,0 +1,304 @@
+// ink! 5 migration advisor
+//
+// Detects syntax and APIs from older ink! versions that were deprecated or
+// removed in ink! 5. Unlike the other rule groups the scan also covers test
+// code, since e2e tests are where most of the removed APIs (`build_message`,
+// `additional_contracts`) live. The resulting findings are rendered as an
+// upgrade checklist by `analyze --migrate`.
+
+use anyhow::Result;
+use std::path::Path;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+
+/// Crates merged into the `ink` crate, with their replacement path
+const LEGACY_CRATES: &[(&str, &str)] = &[
+    ("ink_lang", "ink"),
+    ("ink_storage", "ink::storage"),
+    ("ink_env", "ink::env"),
+    ("ink_prelude", "ink::prelude"),
+    ("ink_primitives", "ink::primitives"),
+];
+
+/// A legacy construct found in a source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegacyUse {
+    pub rule: &'static str,
+    pub line: usize,
+    /// What was found, e.g. the legacy crate name
+    pub found: String,
+}
+
+/// Replacement path for a legacy crate
+pub fn replacement_crate(name: &str) -> Option<&'static str> {
+    LEGACY_CRATES
+        .iter()
+        .find(|(legacy, _)| *legacy == name)
+        .map(|(_, replacement)| *replacement)
+}
+
+/// Scan a parsed file for legacy ink! constructs
+pub fn scan(file: &syn::File) -> Vec<LegacyUse> {
+    let mut scanner = Scanner::default();
+    scanner.visit_file(file);
+    scanner.uses
+}
+
+/// `ink` dependency version and its line in a Cargo.toml, if older than 5.x
+pub fn outdated_dependency(manifest: &Path) -> Result<Option<(String, usize)>> {
+    let content = std::fs::read_to_string(manifest)?;
+    let value: toml::Value = toml::from_str(&content)?;
+
+    let Some(ink) = value.get("dependencies").and_then(|deps| deps.get("ink")) else {
+        return Ok(None);
+    };
+    let Some(version) = ink
+        .as_str()
+        .or_else(|| ink.get("version").and_then(|v| v.as_str()))
+    else {
+        return Ok(None);
+    };
+
+    let major = version
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok());
+    if !major.is_some_and(|major| major < 5) {
+        return Ok(None);
+    }
+
+    let line = content
+        .lines()
+        .position(|line| {
+            let line = line.trim_start();
+            line.starts_with("ink ") || line.starts_with("ink=")
+        })
+        .map(|i| i + 1)
+        .unwrap_or(1);
+
+    Ok(Some((version.to_string(), line)))
+}
+
+#[derive(Default)]
+struct Scanner {
+    uses: Vec<LegacyUse>,
+}
+
+impl Scanner {
+    fn push(&mut self, rule: &'static str, line: usize, found: impl Into<String>) {
+        let found = found.into();
+        // A single `use ink_env::{a, b}` should produce one entry
+        if !self
+            .uses
+            .iter()
+            .any(|u| u.rule == rule && u.line == line && u.found == found)
+        {
+            self.uses.push(LegacyUse { rule, line, found });
+        }
+    }
+
+    fn check_crate(&mut self, ident: &proc_macro2::Ident) {
+        let name = ident.to_string();
+        if replacement_crate(&name).is_some() {
+            self.push("ink-legacy-crate", ident.span().start().line, name);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Scanner {
+    fn visit_use_tree(&mut self, node: &'ast syn::UseTree) {
+        match node {
+            syn::UseTree::Path(path) => self.check_crate(&path.ident),
+            syn::UseTree::Name(name) => self.check_crate(&name.ident),
+            syn::UseTree::Rename(rename) => self.check_crate(&rename.ident),
+            _ => {}
+        }
+        // Only the first segment of a path names a crate, but a root group
+        // like `use {ink_env, ink_storage}` holds several
+        if let syn::UseTree::Group(group) = node {
+            visit::visit_use_group(self, group);
+        }
+    }
+
+    fn visit_path(&mut self, node: &'ast syn::Path) {
+        if let Some(first) = node.segments.first() {
+            if node.segments.len() > 1 || node.leading_colon.is_some() {
+                self.check_crate(&first.ident);
+            }
+        }
+
+        // ink::env::set_code_hash(..)
+        let names: Vec<String> = node.segments.iter().map(|s| s.ident.to_string()).collect();
+        if names.ends_with(&["env".to_string(), "set_code_hash".to_string()]) {
+            self.push(
+                "ink-set-code-hash",
+                node.span().start().line,
+                "set_code_hash",
+            );
+        }
+
+        visit::visit_path(self, node);
+    }
+
+    fn visit_attribute(&mut self, node: &'ast syn::Attribute) {
+        let line = node.span().start().line;
+        let path: Vec<String> = node
+            .path()
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect();
+        let args = match &node.meta {
+            syn::Meta::List(list) => list.tokens.to_string(),
+            _ => String::new(),
+        };
+        let arg_names: Vec<&str> = args
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        match path
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            ["ink"] if arg_names.first() == Some(&"event") => {
+                self.push("ink-legacy-event", line, "#[ink(event)]");
+            }
+            ["ink"] if arg_names.first() == Some(&"extension") => {
+                self.push("ink-chain-extension-syntax", line, "#[ink(extension = ..)]");
+            }
+            ["ink", "chain_extension"] if args.is_empty() => {
+                self.push(
+                    "ink-chain-extension-syntax",
+                    line,
+                    "#[ink::chain_extension]",
+                );
+            }
+            ["ink_e2e", "test"] if arg_names.contains(&"additional_contracts") => {
+                self.push("ink-e2e-additional-contracts", line, "additional_contracts");
+            }
+            _ => {}
+        }
+
+        visit::visit_attribute(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(path) = &*node.func {
+            if path
+                .path
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "build_message")
+            {
+                self.push(
+                    "ink-e2e-build-message",
+                    node.span().start().line,
+                    "build_message",
+                );
+            }
+        }
+        visit::visit_expr_call(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        if node.method == "call_type" {
+            let call_type = node.args.first().and_then(|arg| match arg {
+                syn::Expr::Call(call) => match &*call.func {
+                    syn::Expr::Path(path) => {
+                        let segments = &path.path.segments;
+                        (segments.len() >= 2)
+                            .then(|| segments[segments.len() - 2].ident.to_string())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            });
+            if let Some(call_type) = call_type.filter(|t| t == "Call" || t == "DelegateCall") {
+                self.push("ink-call-type", node.method.span().start().line, call_type);
+            }
+        }
+        visit::visit_expr_method_call(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INK4: &str = r#"
+use ink_lang as ink;
+use ink_storage::{traits::SpreadAllocate, Mapping};
+
+#[ink::contract]
+mod legacy {
+    #[ink(event)]
+    pub struct Transferred {
+        value: u128,
+    }
+
+    impl Legacy {
+        #[ink(message)]
+        pub fn forward(&self) {
+            build_call::<Environment>()
+                .call_type(Call::new(self.target))
+                .invoke();
+            ink::env::set_code_hash(&[0u8; 32]).unwrap();
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        #[ink_e2e::test(additional_contracts = "../other/Cargo.toml")]
+        async fn it_works(mut client: Client<C, E>) -> E2EResult<()> {
+            let msg = build_message::<LegacyRef>(addr).call(|c| c.forward());
+            Ok(())
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn test_scan_ink4_patterns() {
+        let file = syn::parse_file(INK4).unwrap();
+        let uses = scan(&file);
+        let found: Vec<(&str, usize)> = uses.iter().map(|u| (u.rule, u.line)).collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("ink-legacy-crate", 2),
+                ("ink-legacy-crate", 3),
+                ("ink-legacy-event", 7),
+                ("ink-call-type", 16),
+                ("ink-set-code-hash", 18),
+                ("ink-e2e-additional-contracts", 24),
+                ("ink-e2e-build-message", 26),
+            ]
+        );
+        assert_eq!(uses[1].found, "ink_storage");
+        assert_eq!(replacement_crate("ink_storage"), Some("ink::storage"));
+    }
+
+    #[test]
+    fn test_outdated_dependency() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+
+        std::fs::write(
+            &manifest,
+            "[package]\nname = \"legacy\"\n\n[dependencies]\nink = { version = \"4.3\", default-features = false }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            outdated_dependency(&manifest).unwrap(),
+            Some(("4.3".to_string(), 5))
+        );
+
+        std::fs::write(&manifest, "[dependencies]\nink = \"5.0.0\"\n").unwrap();
+        assert_eq!(outdated_dependency(&manifest).unwrap(), None);
+    }
+}
@@ -0,0 +1,611 @@
+// Static analysis for ink! contracts
+//
+// Sources are parsed with `syn` into a `SourceModel` (see `ast`), and the
+// security, gas, maintainability and migration rules in `rules` run against
+// that model. Findings are then filtered through `analyze.toml` settings and inline `forge-ignore` comments.
+
+pub mod access;
+pub mod ast;
+pub mod baseline;
+pub mod callgraph;
+pub mod config;
+pub mod diff;
+pub mod html;
+pub mod migrate;
+pub mod plugin;
+pub mod profile;
+pub mod rules;
+pub mod sarif;
+pub mod storage;
+pub mod vulndb;
+pub mod workspace;
+
+pub use baseline::Baseline;
+pub use config::AnalyzerConfig;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use config::RuleSetting;
+
+/// Which rule groups to run and how to filter their findings
+#[derive(Debug, Clone)]
+pub struct AnalyzeOptions {
+    pub security: bool,
+    pub gas: bool,
+    pub maintainability: bool,
+    /// ink! 5 upgrade checklist
+    pub migrate: bool,
+    /// Build the message call graph
+    pub call_graph: bool,
+    /// Build the message access control matrix
+    pub access_control: bool,
+    pub config: AnalyzerConfig,
+    /// Cargo profile whose `overflow-checks` setting decides arithmetic findings
+    pub cargo_profile: String,
+    /// Report storage layouts from built metadata
+    pub storage: bool,
+    /// Metadata files to read; discovered under each crate's target/ink when empty
+    pub metadata: Vec<PathBuf>,
+    pub deposit_rates: storage::DepositRates,
+    /// Known vulnerability signatures checked with the security rules
+    pub vuln_db: vulndb::VulnDb,
+}
+
+impl Default for AnalyzeOptions {
+    fn default() -> Self {
+        Self {
+            security: false,
+            gas: false,
+            maintainability: false,
+            migrate: false,
+            call_graph: false,
+            access_control: false,
+            config: AnalyzerConfig::default(),
+            // cargo-contract builds contracts in release mode
+            cargo_profile: "release".to_string(),
+            storage: false,
+            metadata: Vec::new(),
+            deposit_rates: storage::DepositRates::default(),
+            vuln_db: vulndb::VulnDb::bundled(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnalysisReport {
+    pub summary: AnalysisSummary,
+    pub files: Vec<FileAnalysis>,
+    pub security_issues: Vec<SecurityIssue>,
+    pub gas_optimizations: Vec<GasOptimization>,
+    /// Dead code and unreachable logic; same shape as security issues
+    pub maintainability_issues: Vec<SecurityIssue>,
+    /// ink! 5 upgrade checklist items
+    #[serde(default)]
+    pub migration_issues: Vec<SecurityIssue>,
+    pub complexity_metrics: ComplexityMetrics,
+    /// Per-crate aggregation of files and findings
+    pub contracts: Vec<workspace::ContractSummary>,
+    pub storage_layouts: Vec<storage::StorageLayoutReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_graph: Option<callgraph::CallGraph>,
+    /// Message -> required roles
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub access_control: Vec<access::AccessEntry>,
+    /// Measured message costs, most expensive first (`--profile`)
+    #[serde(default)]
+    pub gas_profile: Vec<profile::MessageProfile>,
+    /// Comparison with a git revision (`--diff`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<diff::DiffSummary>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnalysisSummary {
+    pub total_files: usize,
+    pub total_lines: usize,
+    pub total_functions: usize,
+    pub security_issues_count: usize,
+    pub gas_optimization_count: usize,
+    pub maintainability_issues_count: usize,
+    #[serde(default)]
+    pub migration_issues_count: usize,
+    pub average_complexity: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileAnalysis {
+    pub path: String,
+    /// Crate the file belongs to
+    pub contract: Option<String>,
+    pub lines_of_code: usize,
+    pub functions: Vec<FunctionInfo>,
+    pub imports: Vec<String>,
+    pub traits: Vec<String>,
+    pub structs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub kind: String, // "message", "constructor", "function"
+    pub visibility: String,
+    pub is_payable: bool,
+    pub line: usize,
+    pub lines: usize,
+    pub complexity: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecurityIssue {
+    pub rule: String,
+    pub severity: String, // "high", "medium", "low"
+    pub category: String,
+    pub description: String,
+    pub file: String,
+    pub line: Option<usize>,
+    pub recommendation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GasOptimization {
+    pub rule: String,
+    pub impact: String, // "high", "medium", "low"
+    pub description: String,
+    pub file: String,
+    pub line: Option<usize>,
+    pub suggestion: String,
+    /// Dry-run measurement of the message containing the finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub measured: Option<profile::MessageProfile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComplexityMetrics {
+    /// Keyed by `file::function`, as function names repeat across a workspace
+    pub cyclomatic_complexity: HashMap<String, u32>,
+    pub cognitive_complexity: HashMap<String, u32>,
+    pub maintainability_index: f64,
+}
+
+impl AnalysisReport {
+    /// Total number of findings across all rule groups
+    pub fn finding_count(&self) -> usize {
+        self.security_issues.len()
+            + self.gas_optimizations.len()
+            + self.maintainability_issues.len()
+            + self.migration_issues.len()
+    }
+
+    /// Number of findings whose severity is at least `threshold`
+    pub fn count_at_or_above(&self, threshold: &str) -> usize {
+        let threshold = severity_rank(threshold);
+        self.security_issues
+            .iter()
+            .chain(&self.maintainability_issues)
+            .chain(&self.migration_issues)
+            .map(|issue| issue.severity.as_str())
+            .chain(self.gas_optimizations.iter().map(|opt| opt.impact.as_str()))
+            .filter(|severity| severity_rank(severity) >= threshold)
+            .count()
+    }
+
+    /// Functions with a cyclomatic complexity above `max`, with their file
+    pub fn functions_over_complexity(&self, max: u32) -> Vec<(&str, &FunctionInfo)> {
+        self.files
+            .iter()
+            .flat_map(|file| file.functions.iter().map(move |f| (file.path.as_str(), f)))
+            .filter(|(_, f)| f.complexity > max)
+            .collect()
+    }
+}
+
+/// Order of severities, higher is more severe
+pub fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "high" => 3,
+        "medium" => 2,
+        "low" => 1,
+        _ => 0,
+    }
+}
+
+/// Analyze a single file or all `.rs` files under a directory
+pub fn analyze_path(path: &Path, options: &AnalyzeOptions) -> Result<AnalysisReport> {
+    let ignore = options.config.ignore_set()?;
+    let plugins = options
+        .config
+        .plugins
+        .iter()
+        .map(|path| plugin::Plugin::load(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let targets = workspace::collect_sources(path, &ignore)?;
+
+    let mut analyzed = Vec::new();
+    for file_path in &targets {
+        if let Some(file) = analyze_file(file_path)? {
+            analyzed.push(file);
+        }
+    }
+
+    // Tests may live in other files of the same crate (e.g. e2e tests)
+    let mut test_references: HashMap<Option<String>, HashSet<String>> = HashMap::new();
+    for (analysis, model) in &analyzed {
+        test_references
+            .entry(analysis.contract.clone())
+            .or_default()
+            .extend(model.test_references.iter().cloned());
+    }
+
+    let call_graph = options.call_graph.then(|| callgraph::build(&analyzed));
+
+    let mut files = Vec::new();
+    let mut security_issues = Vec::new();
+    let mut gas_optimizations = Vec::new();
+    let mut maintainability_issues = Vec::new();
+    let mut migration_issues = Vec::new();
+    let mut access_control = Vec::new();
+
+    for (analysis, model) in analyzed {
+        if options.access_control {
+            access_control.extend(access::matrix(
+                &analysis.path,
+                analysis.contract.as_deref(),
+                &model,
+            ));
+        }
+        if options.security {
+            let overflow_checks =
+                workspace::overflow_checks(Path::new(&analysis.path), &options.cargo_profile);
+            let known = options.vuln_db.check_source(&analysis.path, &model);
+            for mut issue in rules::security(&analysis.path, &model, overflow_checks)
+                .into_iter()
+                .chain(known)
+            {
+                if let Some(severity) =
+                    apply_settings(&options.config, &model, &issue.rule, issue.line)
+                {
+                    issue.severity = severity.unwrap_or(issue.severity);
+                    security_issues.push(issue);
+                }
+            }
+        }
+        if options.gas {
+            for mut opt in rules::gas(&analysis.path, &model) {
+                if let Some(impact) = apply_settings(&options.config, &model, &opt.rule, opt.line) {
+                    opt.impact = impact.unwrap_or(opt.impact);
+                    gas_optimizations.push(opt);
+                }
+            }
+        }
+        if options.maintainability {
+            let tests = &test_references[&analysis.contract];
+            for mut issue in rules::maintainability(&analysis.path, &model, tests) {
+                if let Some(severity) =
+                    apply_settings(&options.config, &model, &issue.rule, issue.line)
+                {
+                    issue.severity = severity.unwrap_or(issue.severity);
+                    maintainability_issues.push(issue);
+                }
+            }
+        }
+        for plugin in &plugins {
+            for plugin::PluginIssue {
+                security,
+                mut issue,
+            } in plugin.check(&analysis, &model)?
+            {
+                let (enabled, issues) = if security {
+                    (options.security, &mut security_issues)
+                } else {
+                    (options.maintainability, &mut maintainability_issues)
+                };
+                if !enabled {
+                    continue;
+                }
+                if let Some(severity) =
+                    apply_settings(&options.config, &model, &issue.rule, issue.line)
+                {
+                    issue.severity = severity.unwrap_or(issue.severity);
+                    issues.push(issue);
+                }
+            }
+        }
+        if options.migrate {
+            for mut issue in rules::migration(&analysis.path, &model) {
+                if let Some(severity) =
+                    apply_settings(&options.config, &model, &issue.rule, issue.line)
+                {
+                    issue.severity = severity.unwrap_or(issue.severity);
+                    migration_issues.push(issue);
+                }
+            }
+        }
+        files.push(analysis);
+    }
+
+    let mut manifests: Vec<PathBuf> = files
+        .iter()
+        .filter_map(|f| workspace::find_manifest(Path::new(&f.path)))
+        .collect();
+    manifests.sort();
+    manifests.dedup();
+    let no_suppressions = ast::SourceModel::default();
+
+    if options.security {
+        for manifest in &manifests {
+            for mut issue in options.vuln_db.check_manifest(manifest)? {
+                if let Some(severity) =
+                    apply_settings(&options.config, &no_suppressions, &issue.rule, issue.line)
+                {
+                    issue.severity = severity.unwrap_or(issue.severity);
+                    security_issues.push(issue);
+                }
+            }
+        }
+    }
+
+    if options.migrate {
+        for manifest in &manifests {
+            let Some((version, line)) = migrate::outdated_dependency(manifest)? else {
+                continue;
+            };
+            let mut issue = rules::outdated_dependency(&manifest.to_string_lossy(), &version, line);
+            if let Some(severity) =
+                apply_settings(&options.config, &no_suppressions, &issue.rule, issue.line)
+            {
+                issue.severity = severity.unwrap_or(issue.severity);
+                migration_issues.push(issue);
+            }
+        }
+    }
+
+    let total_lines: usize = files.iter().map(|f| f.lines_of_code).sum();
+    let total_functions: usize = files.iter().map(|f| f.functions.len()).sum();
+    let total_complexity: u32 = files
+        .iter()
+        .flat_map(|f| &f.functions)
+        .map(|func| func.complexity)
+        .sum();
+    let average_complexity = if total_functions > 0 {
+        total_complexity as f64 / total_functions as f64
+    } else {
+        0.0
+    };
+
+    let complexity_metrics = calculate_complexity_metrics(&files);
+
+    let mut report = AnalysisReport {
+        summary: AnalysisSummary {
+            total_files: files.len(),
+            total_lines,
+            total_functions,
+            security_issues_count: security_issues.len(),
+            gas_optimization_count: gas_optimizations.len(),
+            maintainability_issues_count: maintainability_issues.len(),
+            migration_issues_count: migration_issues.len(),
+            average_complexity,
+        },
+        files,
+        security_issues,
+        gas_optimizations,
+        maintainability_issues,
+        migration_issues,
+        complexity_metrics,
+        contracts: Vec::new(),
+        storage_layouts: Vec::new(),
+        call_graph,
+        access_control,
+        gas_profile: Vec::new(),
+        diff: None,
+    };
+    report.contracts = workspace::summarize(&report);
+
+    if options.storage {
+        let metadata_paths = if options.metadata.is_empty() {
+            report
+                .contracts
+                .iter()
+                .filter_map(|c| storage::find_metadata(Path::new(&c.path), &c.name))
+                .collect()
+        } else {
+            options.metadata.clone()
+        };
+
+        for metadata_path in &metadata_paths {
+            report.storage_layouts.push(storage::analyze_metadata(
+                metadata_path,
+                options.deposit_rates,
+            )?);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Decide whether a finding is kept: `None` drops it, `Some(Some(sev))` overrides its severity
+fn apply_settings(
+    config: &AnalyzerConfig,
+    model: &ast::SourceModel,
+    rule: &str,
+    line: Option<usize>,
+) -> Option<Option<String>> {
+    if line.is_some_and(|line| model.is_suppressed(rule, line)) {
+        return None;
+    }
+
+    match config.setting(rule) {
+        Some(RuleSetting::Off) => None,
+        Some(RuleSetting::Severity(severity)) => Some(Some(severity)),
+        None => Some(None),
+    }
+}
+
+fn analyze_file(path: &Path) -> Result<Option<(FileAnalysis, ast::SourceModel)>> {
+    let content = fs::read_to_string(path).context("Failed to read file")?;
+    let model = ast::parse_source(&content)
+        .with_context(|| format!("Failed to analyze {}", path.display()))?;
+
+    // Skip files that contain neither a contract nor any functions
+    if !model.is_contract && model.functions.is_empty() && model.legacy_uses.is_empty() {
+        return Ok(None);
+    }
+
+    let lines_of_code = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .count();
+
+    let analysis = FileAnalysis {
+        path: path.to_string_lossy().to_string(),
+        contract: workspace::crate_name(path),
+        lines_of_code,
+        functions: model.functions.iter().map(|f| f.info.clone()).collect(),
+        imports: model.imports.clone(),
+        traits: model.traits.clone(),
+        structs: model.structs.clone(),
+    };
+
+    Ok(Some((analysis, model)))
+}
+
+fn calculate_complexity_metrics(files: &[FileAnalysis]) -> ComplexityMetrics {
+    let mut cyclomatic = HashMap::new();
+    let mut cognitive = HashMap::new();
+
+    for file in files {
+        for func in &file.functions {
+            // `lib.rs:12::new`, so that `new` of two impls in a file stay apart
+            let key = format!("{}:{}::{}", file.path, func.line, func.name);
+            cyclomatic.insert(key.clone(), func.complexity);
+            // Cognitive complexity is similar but weights nested structures higher
+            cognitive.insert(key, func.complexity);
+        }
+    }
+
+    // Calculate maintainability index (simplified)
+    let total_complexity: u32 = cyclomatic.values().sum();
+    let total_functions = cyclomatic.len() as f64;
+    let avg_complexity = if total_functions > 0.0 {
+        total_complexity as f64 / total_functions
+    } else {
+        0.0
+    };
+
+    // Maintainability index (simplified formula)
+    // 171 - 5.2 * ln(Halstead Volume) - 0.23 * (Cyclomatic Complexity) - 16.2 * ln(Lines of Code)
+    // Simplified here to: 100 - (avg_complexity * 5)
+    let maintainability = (100.0 - (avg_complexity * 5.0)).max(0.0).min(100.0);
+
+    ComplexityMetrics {
+        cyclomatic_complexity: cyclomatic,
+        cognitive_complexity: cognitive,
+        maintainability_index: maintainability,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quality_gates() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(
+            &file,
+            r#"
+#[ink::contract]
+mod gate {
+    impl Gate {
+        #[ink(message)]
+        pub fn get(&self) -> u32 {
+            self.value.unwrap()
+        }
+
+        fn branchy(&self, x: u32) -> u32 {
+            if x > 1 { 1 } else if x > 2 { 2 } else { 3 }
+        }
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let options = AnalyzeOptions {
+            security: true,
+            ..Default::default()
+        };
+        let report = analyze_path(&file, &options).unwrap();
+
+        // Only the low severity unwrap finding
+        assert_eq!(report.count_at_or_above("low"), 1);
+        assert_eq!(report.count_at_or_above("medium"), 0);
+
+        let complex = report.functions_over_complexity(2);
+        assert_eq!(complex.len(), 1);
+        assert_eq!(complex[0].1.name, "branchy");
+    }
+
+    #[test]
+    fn test_complexity_across_crates() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, body) in [
+            ("token", "1"),
+            ("vault", "if x > 1 { 1 } else if x > 2 { 2 } else { 3 }"),
+        ] {
+            let root = dir.path().join("contracts").join(name);
+            std::fs::create_dir_all(root.join("src")).unwrap();
+            std::fs::write(
+                root.join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\n", name),
+            )
+            .unwrap();
+            std::fs::write(
+                root.join("src/lib.rs"),
+                format!("pub fn new(x: u32) -> u32 {{ {} }}\n", body),
+            )
+            .unwrap();
+        }
+
+        let report = analyze_path(dir.path(), &AnalyzeOptions::default()).unwrap();
+        let metrics = &report.complexity_metrics;
+
+        // Both `new` functions count, each under its own file
+        let mut scores: Vec<(&String, &u32)> = metrics.cyclomatic_complexity.iter().collect();
+        scores.sort();
+        assert_eq!(scores.len(), 2);
+        assert!(scores[0].0.contains("token") && scores[0].0.ends_with("::new"));
+        assert!(scores[1].0.contains("vault"));
+        assert_eq!((*scores[0].1, *scores[1].1), (1, 3));
+        assert_eq!(metrics.maintainability_index, 100.0 - 2.0 * 5.0);
+    }
+
+    #[test]
+    fn test_complexity_same_name_in_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "struct A;\nstruct B;\n\
+             impl A {\n    pub fn new() -> Self { A }\n}\n\
+             impl B {\n    pub fn new(x: u32) -> Self { if x > 1 { B } else { B } }\n}\n",
+        )
+        .unwrap();
+
+        let report = analyze_path(dir.path(), &AnalyzeOptions::default()).unwrap();
+        let mut scores: Vec<(&String, &u32)> = report
+            .complexity_metrics
+            .cyclomatic_complexity
+            .iter()
+            .collect();
+        scores.sort();
+        assert_eq!(scores.len(), 2, "{:?}", scores);
+        assert!(scores[0].0.ends_with("lib.rs:4::new"), "{:?}", scores);
+        assert!(scores[1].0.ends_with("lib.rs:7::new"), "{:?}", scores);
+        assert_eq!((*scores[0].1, *scores[1].1), (1, 2));
+    }
+}
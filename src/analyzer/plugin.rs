@@ -0,0 +1,262 @@
+// Custom analyzer rules written as Rhai scripts
+//
+// A plugin is a `.rhai` file defining `fn check(file)`. It receives the
+// parsed model of one source file as a map and returns an array of findings:
+//
+//   fn check(file) {
+//       let findings = [];
+//       for f in file.functions {
+//           if f.kind == "message" && f.name.starts_with("get_") {
+//               findings.push(#{
+//                   rule: "no-get-prefix",
+//                   message: `Message '${f.name}' uses a get_ prefix`,
+//                   line: f.line,
+//                   fix: "Name getters after the value they return",
+//               });
+//           }
+//       }
+//       findings
+//   }
+//
+// Finding maps may also set `severity` ("high", "medium", "low"; default
+// "low") and `category` ("security" or "maintainability", the default). Rule
+// ids are namespaced by the script name, e.g. `naming/no-get-prefix`, and can
+// be configured in analyze.toml and `forge-ignore` comments like built-in rules.
+
+use anyhow::{Context, Result};
+use rhai::{Dynamic, Engine, Scope, AST};
+use serde::Deserialize;
+use serde_json::json;
+use std::path::Path;
+
+use super::ast::SourceModel;
+use super::{FileAnalysis, SecurityIssue};
+
+/// Upper bound on script operations per file, so a broken loop can't hang analysis
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+pub struct Plugin {
+    pub name: String,
+    engine: Engine,
+    ast: AST,
+}
+
+/// A finding as returned by a plugin script
+#[derive(Debug, Deserialize)]
+struct PluginFinding {
+    rule: String,
+    message: String,
+    #[serde(default)]
+    line: Option<usize>,
+    #[serde(default)]
+    fix: String,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    category: Option<String>,
+}
+
+/// Finding produced by a plugin, routed to the rule group it declared
+#[derive(Debug)]
+pub struct PluginIssue {
+    pub security: bool,
+    pub issue: SecurityIssue,
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> Result<Self> {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .with_context(|| format!("Invalid plugin path: {}", path.display()))?;
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        // Debug builds of rhai default to shallow limits that reject nested findings maps
+        engine.set_max_expr_depths(128, 64);
+
+        let ast = engine
+            .compile_file(path.to_path_buf())
+            .map_err(|e| anyhow::anyhow!("Failed to compile plugin {}: {}", path.display(), e))?;
+
+        if !ast
+            .iter_functions()
+            .any(|f| f.name == "check" && f.params.len() == 1)
+        {
+            anyhow::bail!("Plugin {} must define `fn check(file)`", path.display());
+        }
+
+        Ok(Self { name, engine, ast })
+    }
+
+    /// Run the plugin against one analyzed file
+    pub fn check(&self, file: &FileAnalysis, model: &SourceModel) -> Result<Vec<PluginIssue>> {
+        let input = rhai::serde::to_dynamic(file_model(file, model))
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        let output: Dynamic = self
+            .engine
+            .call_fn(&mut Scope::new(), &self.ast, "check", (input,))
+            .map_err(|e| {
+                anyhow::anyhow!("Plugin '{}' failed on {}: {}", self.name, file.path, e)
+            })?;
+
+        let findings: Vec<PluginFinding> = rhai::serde::from_dynamic(&output).map_err(|e| {
+            anyhow::anyhow!(
+                "Plugin '{}' returned invalid findings (expected an array of maps with rule and message): {}",
+                self.name,
+                e
+            )
+        })?;
+
+        findings
+            .into_iter()
+            .map(|finding| {
+                let severity = finding.severity.unwrap_or_else(|| "low".to_string());
+                if !matches!(severity.as_str(), "high" | "medium" | "low") {
+                    anyhow::bail!(
+                        "Plugin '{}' returned invalid severity '{}' for rule '{}'",
+                        self.name,
+                        severity,
+                        finding.rule
+                    );
+                }
+                let security = finding.category.as_deref() == Some("security");
+
+                Ok(PluginIssue {
+                    security,
+                    issue: SecurityIssue {
+                        rule: format!("{}/{}", self.name, finding.rule),
+                        severity,
+                        category: if security {
+                            "Security"
+                        } else {
+                            "Maintainability"
+                        }
+                        .to_string(),
+                        description: finding.message,
+                        file: file.path.clone(),
+                        line: finding.line,
+                        recommendation: finding.fix,
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+/// The map passed to `check`
+fn file_model(file: &FileAnalysis, model: &SourceModel) -> serde_json::Value {
+    let functions: Vec<_> = model
+        .functions
+        .iter()
+        .map(|f| {
+            json!({
+                "name": f.info.name,
+                "kind": f.info.kind,
+                "visibility": f.info.visibility,
+                "is_payable": f.info.is_payable,
+                "line": f.info.line,
+                "lines": f.info.lines,
+                "complexity": f.info.complexity,
+                "mutates": f.mutates,
+                "emits_event": model.emits_event(f),
+                "checks_caller": f.checks_caller,
+                "calls": f.calls,
+                "field_reads": f.field_reads,
+                "unwraps": f.unwraps,
+                "loops": f.loops,
+            })
+        })
+        .collect();
+    let storage_fields: Vec<_> = model
+        .storage_fields
+        .iter()
+        .map(|field| json!({ "name": field.name, "ty": field.ty, "line": field.line }))
+        .collect();
+
+    json!({
+        "path": file.path,
+        "contract": file.contract,
+        "is_contract": model.is_contract,
+        "functions": functions,
+        "storage_fields": storage_fields,
+        "imports": model.imports,
+        "structs": model.structs,
+        "traits": model.traits,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ast::parse_source;
+
+    #[test]
+    fn test_plugin_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("naming.rhai");
+        std::fs::write(
+            &script,
+            r#"
+fn check(file) {
+    let findings = [];
+    for f in file.functions {
+        if f.kind == "message" && f.name.starts_with("get_") {
+            findings.push(#{
+                rule: "no-get-prefix",
+                message: `Message '${f.name}' uses a get_ prefix`,
+                line: f.line,
+                category: "security",
+                severity: "medium",
+            });
+        }
+    }
+    findings
+}
+"#,
+        )
+        .unwrap();
+
+        let source = r#"
+#[ink::contract]
+mod c {
+    impl C {
+        #[ink(message)]
+        pub fn get_value(&self) -> u32 { 1 }
+
+        #[ink(message)]
+        pub fn value(&self) -> u32 { 1 }
+    }
+}
+"#;
+        let model = parse_source(source).unwrap();
+        let file = FileAnalysis {
+            path: "lib.rs".to_string(),
+            contract: None,
+            lines_of_code: 0,
+            functions: vec![],
+            imports: vec![],
+            traits: vec![],
+            structs: vec![],
+        };
+
+        let plugin = Plugin::load(&script).unwrap();
+        let issues = plugin.check(&file, &model).unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].security);
+        assert_eq!(issues[0].issue.rule, "naming/no-get-prefix");
+        assert_eq!(issues[0].issue.severity, "medium");
+        assert_eq!(issues[0].issue.line, Some(6));
+    }
+
+    #[test]
+    fn test_plugin_requires_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("empty.rhai");
+        std::fs::write(&script, "fn other() { 1 }").unwrap();
+
+        assert!(Plugin::load(&script).is_err());
+    }
+}
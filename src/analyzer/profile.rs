@@ -0,0 +1,203 @@
+// Dynamic gas profile of a deployed contract
+//
+// Every message is dry-run once against a node (argument sets come from the
+// bench file, see `testing::bench`) and the measured refTime, proofSize and
+// storage deposit are merged into the gas report. Static gas findings inside a
+// profiled message get their impact from the measured cost relative to the
+// most expensive message instead of the rule's default severity.
+
+use anyhow::Result;
+use ink_metadata::InkProject;
+use serde::{Deserialize, Serialize};
+use subxt::utils::AccountId32;
+
+use super::{AnalysisReport, FunctionInfo};
+use crate::testing::bench::{self, BenchFile};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageProfile {
+    pub message: String,
+    pub args: Vec<String>,
+    pub ref_time: u64,
+    pub proof_size: u64,
+    pub storage_deposit: i128,
+    pub reverted: bool,
+}
+
+/// Dry-run every message of the contract and collect its measured cost
+pub async fn profile_messages(
+    rpc_url: &str,
+    origin: &AccountId32,
+    contract_address: &str,
+    metadata: &InkProject,
+    bench_file: &BenchFile,
+) -> Result<Vec<MessageProfile>> {
+    let mut profiles = Vec::new();
+
+    for case in bench::plan_cases(metadata, bench_file)? {
+        let result = bench::run_case(rpc_url, origin, contract_address, metadata, &case, 1).await?;
+        profiles.push(MessageProfile {
+            message: result.message,
+            args: result.args,
+            ref_time: result.ref_time.mean,
+            proof_size: result.proof_size.mean,
+            storage_deposit: result.storage_deposit,
+            reverted: result.reverted,
+        });
+    }
+
+    // Most expensive first
+    profiles.sort_by_key(|p| std::cmp::Reverse(p.ref_time));
+    Ok(profiles)
+}
+
+/// Impact of a message relative to the most expensive profiled one
+fn impact(ref_time: u64, max_ref_time: u64) -> &'static str {
+    if max_ref_time == 0 {
+        return "low";
+    }
+    match ref_time as f64 / max_ref_time as f64 {
+        ratio if ratio >= 0.5 => "high",
+        ratio if ratio >= 0.1 => "medium",
+        _ => "low",
+    }
+}
+
+/// Attach measurements to the gas findings of `contract_files` and re-rank them
+pub fn merge(
+    report: &mut AnalysisReport,
+    profiles: Vec<MessageProfile>,
+    contract_files: &[String],
+) {
+    let max_ref_time = profiles.iter().map(|p| p.ref_time).max().unwrap_or(0);
+
+    for opt in &mut report.gas_optimizations {
+        if !contract_files.contains(&opt.file) {
+            continue;
+        }
+        let Some(line) = opt.line else {
+            continue;
+        };
+        let Some(function) = report
+            .files
+            .iter()
+            .filter(|f| f.path == opt.file)
+            .flat_map(|f| &f.functions)
+            .find(|f| contains_line(f, line))
+        else {
+            continue;
+        };
+
+        // The costliest argument set of the message decides its impact
+        if let Some(profile) = profiles.iter().find(|p| p.message == function.name) {
+            opt.impact = impact(profile.ref_time, max_ref_time).to_string();
+            opt.measured = Some(profile.clone());
+        }
+    }
+
+    report.gas_profile = profiles;
+}
+
+fn contains_line(function: &FunctionInfo, line: usize) -> bool {
+    line >= function.line && line < function.line + function.lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisSummary, ComplexityMetrics, FileAnalysis, GasOptimization};
+    use std::collections::HashMap;
+
+    fn function(name: &str, line: usize, lines: usize) -> FunctionInfo {
+        FunctionInfo {
+            name: name.to_string(),
+            kind: "message".to_string(),
+            visibility: "public".to_string(),
+            is_payable: false,
+            line,
+            lines,
+            complexity: 1,
+        }
+    }
+
+    fn optimization(line: usize) -> GasOptimization {
+        GasOptimization {
+            rule: "loop-in-message".to_string(),
+            impact: "medium".to_string(),
+            description: String::new(),
+            file: "lib.rs".to_string(),
+            line: Some(line),
+            suggestion: String::new(),
+            measured: None,
+        }
+    }
+
+    fn profile(message: &str, ref_time: u64) -> MessageProfile {
+        MessageProfile {
+            message: message.to_string(),
+            args: vec![],
+            ref_time,
+            proof_size: 0,
+            storage_deposit: 0,
+            reverted: false,
+        }
+    }
+
+    #[test]
+    fn test_merge_ranks_by_measured_cost() {
+        let mut report = AnalysisReport {
+            summary: AnalysisSummary {
+                total_files: 1,
+                total_lines: 0,
+                total_functions: 2,
+                security_issues_count: 0,
+                gas_optimization_count: 2,
+                maintainability_issues_count: 0,
+                migration_issues_count: 0,
+                average_complexity: 1.0,
+            },
+            files: vec![FileAnalysis {
+                path: "lib.rs".to_string(),
+                contract: Some("token".to_string()),
+                lines_of_code: 40,
+                functions: vec![function("transfer", 10, 10), function("get", 30, 5)],
+                imports: vec![],
+                traits: vec![],
+                structs: vec![],
+            }],
+            security_issues: vec![],
+            gas_optimizations: vec![optimization(12), optimization(31)],
+            maintainability_issues: vec![],
+            migration_issues: vec![],
+            complexity_metrics: ComplexityMetrics {
+                cyclomatic_complexity: HashMap::new(),
+                cognitive_complexity: HashMap::new(),
+                maintainability_index: 100.0,
+            },
+            contracts: vec![],
+            storage_layouts: vec![],
+            call_graph: None,
+            access_control: vec![],
+            gas_profile: vec![],
+            diff: None,
+        };
+
+        merge(
+            &mut report,
+            vec![profile("transfer", 1_000), profile("get", 50)],
+            &["lib.rs".to_string()],
+        );
+
+        assert_eq!(report.gas_optimizations[0].impact, "high");
+        assert_eq!(report.gas_optimizations[1].impact, "low");
+        assert_eq!(
+            report.gas_optimizations[0]
+                .measured
+                .as_ref()
+                .unwrap()
+                .ref_time,
+            1_000
+        );
+        assert_eq!(report.gas_profile.len(), 2);
+    }
+}
@@ -0,0 +1,633 @@
+// Security, gas, maintainability and migration rules evaluated against a `SourceModel`
+
+use std::collections::HashSet;
+
+use super::ast::SourceModel;
+use super::{access, migrate};
+use super::{GasOptimization, SecurityIssue};
+
+/// Complexity above which a function is reported
+const MAX_COMPLEXITY: u32 = 10;
+
+/// Metadata describing a rule
+#[derive(Debug, Clone, Copy)]
+pub struct RuleInfo {
+    pub id: &'static str,
+    pub category: &'static str,
+    /// Default severity, before `analyze.toml` overrides
+    pub severity: &'static str,
+    pub description: &'static str,
+}
+
+const fn rule(
+    id: &'static str,
+    category: &'static str,
+    severity: &'static str,
+    description: &'static str,
+) -> RuleInfo {
+    RuleInfo {
+        id,
+        category,
+        severity,
+        description,
+    }
+}
+
+/// All rules, usable by id in `analyze.toml` and `forge-ignore` comments
+pub const RULES: &[RuleInfo] = &[
+    rule(
+        "unchecked-arithmetic",
+        "Arithmetic",
+        "medium",
+        "Arithmetic that may overflow or underflow",
+    ),
+    rule(
+        "payable-access-control",
+        "Access Control",
+        "high",
+        "Payable message without access control",
+    ),
+    rule(
+        "unwrap",
+        "Error Handling",
+        "low",
+        "Use of unwrap() that could panic",
+    ),
+    rule(
+        "missing-event",
+        "Transparency",
+        "low",
+        "Message that writes storage but emits no events",
+    ),
+    rule(
+        "unprotected-privileged-message",
+        "Access Control",
+        "medium",
+        "Privileged-looking message callable by anyone",
+    ),
+    rule(
+        "storage-string",
+        "Gas",
+        "high",
+        "String stored in contract storage",
+    ),
+    rule(
+        "storage-vec",
+        "Gas",
+        "medium",
+        "Vec stored in contract storage",
+    ),
+    rule(
+        "loop-in-message",
+        "Gas",
+        "medium",
+        "Loop inside a message or constructor",
+    ),
+    rule(
+        "high-complexity",
+        "Gas",
+        "medium",
+        "Function with high cyclomatic complexity",
+    ),
+    rule(
+        "ink-dependency-version",
+        "Migration",
+        "medium",
+        "ink! dependency older than 5.x",
+    ),
+    rule(
+        "ink-legacy-crate",
+        "Migration",
+        "medium",
+        "Import from a crate merged into `ink` (ink_lang, ink_storage, ink_env, ...)",
+    ),
+    rule(
+        "ink-legacy-event",
+        "Migration",
+        "low",
+        "Event declared inline with #[ink(event)]",
+    ),
+    rule(
+        "ink-e2e-build-message",
+        "Migration",
+        "medium",
+        "ink_e2e::build_message, removed in ink! 5",
+    ),
+    rule(
+        "ink-e2e-additional-contracts",
+        "Migration",
+        "medium",
+        "additional_contracts argument to #[ink_e2e::test], removed in ink! 5",
+    ),
+    rule(
+        "ink-chain-extension-syntax",
+        "Migration",
+        "medium",
+        "Chain extension declared with ink! 4 syntax",
+    ),
+    rule(
+        "ink-call-type",
+        "Migration",
+        "medium",
+        "call_type(Call::new(..)) call builder, removed in ink! 5",
+    ),
+    rule(
+        "ink-set-code-hash",
+        "Migration",
+        "low",
+        "ink::env::set_code_hash instead of self.env().set_code_hash",
+    ),
+    rule(
+        "unused-storage-field",
+        "Maintainability",
+        "low",
+        "Storage field that is never read",
+    ),
+    rule(
+        "untested-message",
+        "Maintainability",
+        "low",
+        "Message that no test calls",
+    ),
+    rule(
+        "unreachable-match-arm",
+        "Maintainability",
+        "low",
+        "Match arm that can never be reached",
+    ),
+];
+
+pub fn find_rule(id: &str) -> Option<&'static RuleInfo> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+fn default_severity(id: &str) -> String {
+    find_rule(id)
+        .map(|rule| rule.severity)
+        .unwrap_or("low")
+        .to_string()
+}
+
+/// Security checks; arithmetic is only reported when `overflow_checks` is off,
+/// since with checks enabled an overflow panics and reverts the call
+pub fn security(file: &str, model: &SourceModel, overflow_checks: bool) -> Vec<SecurityIssue> {
+    let mut issues = Vec::new();
+
+    let mut issue = |rule: &str, category: &str, description: String, line, fix: &str| {
+        issues.push(SecurityIssue {
+            rule: rule.to_string(),
+            severity: default_severity(rule),
+            category: category.to_string(),
+            description,
+            file: file.to_string(),
+            line: Some(line),
+            recommendation: fix.to_string(),
+        });
+    };
+
+    for func in &model.functions {
+        let name = &func.info.name;
+
+        // 1. Unchecked arithmetic operations
+        for line in func.arithmetic.iter().filter(|_| !overflow_checks) {
+            issue(
+                "unchecked-arithmetic",
+                "Arithmetic",
+                format!(
+                    "Potential integer overflow/underflow in '{}' (overflow-checks disabled)",
+                    name
+                ),
+                *line,
+                "Use checked arithmetic operations (checked_add, checked_sub, etc.)",
+            );
+        }
+
+        // 2. Missing access control on payable messages
+        if func.is_message() && func.info.is_payable && !func.checks_caller {
+            issue(
+                "payable-access-control",
+                "Access Control",
+                format!("Payable function '{}' lacks access control", name),
+                func.info.line,
+                "Add access control checks to prevent unauthorized calls",
+            );
+        }
+
+        // 3. Unsafe unwrap usage
+        for line in &func.unwraps {
+            issue(
+                "unwrap",
+                "Error Handling",
+                format!("Use of unwrap() in '{}' could panic", name),
+                *line,
+                "Replace unwrap() with proper error handling using ? or expect()",
+            );
+        }
+
+        // 4. Storage writes without an event, including writes made by helpers
+        if func.is_message() && !model.emits_event(func) {
+            let written = model.written_fields(func);
+            if !written.is_empty() {
+                issue(
+                    "missing-event",
+                    "Transparency",
+                    format!(
+                        "State-changing message '{}' modifies {} but emits no events",
+                        name,
+                        written
+                            .iter()
+                            .map(|field| format!("`{}`", field))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    func.info.line,
+                    "Emit an event describing the change so indexers and users can track it",
+                );
+            }
+        }
+
+        // 5. Privileged messages without any caller check
+        if func.is_message()
+            && access::roles(model, func).is_empty()
+            && access::is_privileged(model, func)
+        {
+            issue(
+                "unprotected-privileged-message",
+                "Access Control",
+                format!("Privileged message '{}' can be called by anyone", name),
+                func.info.line,
+                "Restrict the caller, e.g. compare self.env().caller() with the owner",
+            );
+        }
+    }
+
+    issues
+}
+
+pub fn gas(file: &str, model: &SourceModel) -> Vec<GasOptimization> {
+    let mut optimizations = Vec::new();
+
+    let mut optimization = |rule: &str, description: String, line, suggestion: &str| {
+        optimizations.push(GasOptimization {
+            rule: rule.to_string(),
+            impact: default_severity(rule),
+            description,
+            file: file.to_string(),
+            line: Some(line),
+            suggestion: suggestion.to_string(),
+            measured: None,
+        });
+    };
+
+    for field in &model.storage_fields {
+        // 1. String usage (expensive in storage)
+        if field.ty.contains("String") {
+            optimization(
+                "storage-string",
+                format!("String type in storage field '{}' is expensive", field.name),
+                field.line,
+                "Consider using Vec<u8> or bounded types for storage",
+            );
+        }
+
+        // 2. Inefficient data structures
+        if field.ty.starts_with("Vec<") {
+            optimization(
+                "storage-vec",
+                format!(
+                    "Vec in storage field '{}' requires careful management",
+                    field.name
+                ),
+                field.line,
+                "Consider using Mapping for key-value storage or BTreeMap for ordered data",
+            );
+        }
+    }
+
+    for func in &model.functions {
+        // 3. Loops in contract entry points
+        if func.is_message() || func.is_constructor() {
+            for line in &func.loops {
+                optimization(
+                    "loop-in-message",
+                    format!("Loop in '{}' can be gas-intensive", func.info.name),
+                    *line,
+                    "Limit loop iterations or use pagination for large datasets",
+                );
+            }
+        }
+
+        // 4. High complexity functions
+        if func.info.complexity > MAX_COMPLEXITY {
+            optimization(
+                "high-complexity",
+                format!(
+                    "Function '{}' has high complexity ({})",
+                    func.info.name, func.info.complexity
+                ),
+                func.info.line,
+                "Consider breaking down into smaller functions to reduce gas costs",
+            );
+        }
+    }
+
+    optimizations
+}
+
+/// Dead code checks; `test_references` are identifiers used by the crate's tests
+pub fn maintainability(
+    file: &str,
+    model: &SourceModel,
+    test_references: &HashSet<String>,
+) -> Vec<SecurityIssue> {
+    let mut issues = Vec::new();
+
+    let mut issue = |rule: &str, description: String, line, fix: &str| {
+        issues.push(SecurityIssue {
+            rule: rule.to_string(),
+            severity: default_severity(rule),
+            category: "Maintainability".to_string(),
+            description,
+            file: file.to_string(),
+            line: Some(line),
+            recommendation: fix.to_string(),
+        });
+    };
+
+    // 1. Storage fields that are written but never read
+    let reads: HashSet<&str> = model
+        .functions
+        .iter()
+        .flat_map(|f| f.field_reads.iter().map(|r| r.as_str()))
+        .collect();
+    for field in &model.storage_fields {
+        if !reads.contains(field.name.as_str()) {
+            issue(
+                "unused-storage-field",
+                format!("Storage field '{}' is never read", field.name),
+                field.line,
+                "Remove the field or expose it through a message to save storage deposit",
+            );
+        }
+    }
+
+    // 2. Messages not exercised by any test (only when the crate has tests)
+    if !test_references.is_empty() {
+        for func in model.functions.iter().filter(|f| f.is_message()) {
+            if !test_references.contains(&func.info.name) {
+                issue(
+                    "untested-message",
+                    format!("Message '{}' is never referenced in tests", func.info.name),
+                    func.info.line,
+                    "Add a unit or e2e test that calls this message",
+                );
+            }
+        }
+    }
+
+    // 3. Unreachable match arms
+    for func in &model.functions {
+        for line in &func.unreachable_arms {
+            issue(
+                "unreachable-match-arm",
+                format!("Unreachable match arm in '{}'", func.info.name),
+                *line,
+                "Remove the arm or move it before the catch-all pattern",
+            );
+        }
+    }
+
+    issues
+}
+
+/// Upgrade checklist items for ink! 4.x syntax and APIs
+pub fn migration(file: &str, model: &SourceModel) -> Vec<SecurityIssue> {
+    model
+        .legacy_uses
+        .iter()
+        .map(|legacy| {
+            let (description, fix) = match legacy.rule {
+                "ink-legacy-crate" => {
+                    let replacement = migrate::replacement_crate(&legacy.found).unwrap_or("ink");
+                    (
+                        format!("'{}' is merged into the ink crate", legacy.found),
+                        format!(
+                            "Replace '{}' with '{}' and drop the crate from Cargo.toml",
+                            legacy.found, replacement
+                        ),
+                    )
+                }
+                "ink-legacy-event" => (
+                    "Event declared with #[ink(event)]".to_string(),
+                    "Declare the event with #[ink::event] so it can be shared across contracts"
+                        .to_string(),
+                ),
+                "ink-e2e-build-message" => (
+                    "ink_e2e::build_message was removed in ink! 5".to_string(),
+                    "Use `let mut call = contract.call_builder::<Contract>();` and \
+                     `client.call(&ink_e2e::alice(), &call.message()).submit()`"
+                        .to_string(),
+                ),
+                "ink-e2e-additional-contracts" => (
+                    "additional_contracts was removed from #[ink_e2e::test]".to_string(),
+                    "Remove the argument and add the contracts as dev-dependencies with \
+                     features = [\"ink-as-dependency\"]"
+                        .to_string(),
+                ),
+                "ink-chain-extension-syntax" => (
+                    format!("{} uses ink! 4 chain extension syntax", legacy.found),
+                    "Use #[ink::chain_extension(extension = ID)] on the trait and \
+                     #[ink(function = ID)] on its methods"
+                        .to_string(),
+                ),
+                "ink-call-type" => (
+                    format!("call_type({}::new(..)) was removed in ink! 5", legacy.found),
+                    if legacy.found == "DelegateCall" {
+                        "Use `.delegate(code_hash)` on the call builder".to_string()
+                    } else {
+                        "Use `.call(account_id)` on the call builder".to_string()
+                    },
+                ),
+                _ => (
+                    "ink::env::set_code_hash is deprecated".to_string(),
+                    "Call `self.env().set_code_hash(&code_hash)` instead".to_string(),
+                ),
+            };
+
+            SecurityIssue {
+                rule: legacy.rule.to_string(),
+                severity: default_severity(legacy.rule),
+                category: "Migration".to_string(),
+                description,
+                file: file.to_string(),
+                line: Some(legacy.line),
+                recommendation: fix,
+            }
+        })
+        .collect()
+}
+
+/// Checklist item for an `ink` dependency older than 5.x
+pub fn outdated_dependency(manifest: &str, version: &str, line: usize) -> SecurityIssue {
+    SecurityIssue {
+        rule: "ink-dependency-version".to_string(),
+        severity: default_severity("ink-dependency-version"),
+        category: "Migration".to_string(),
+        description: format!("ink {} dependency is older than ink! 5", version),
+        file: manifest.to_string(),
+        line: Some(line),
+        recommendation: "Set ink = { version = \"5\", default-features = false } and bump \
+                         ink_e2e to 5 as well"
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ast::parse_source;
+
+    #[test]
+    fn test_security_rules() {
+        let source = r#"
+#[ink::contract]
+mod vault {
+    #[ink(storage)]
+    pub struct Vault {
+        owners: Vec<AccountId>,
+        total: Balance,
+    }
+
+    impl Vault {
+        #[ink(message, payable)]
+        pub fn fund(&mut self) {
+            self.total = self.total + self.env().transferred_value();
+        }
+
+        #[ink(message, payable)]
+        pub fn owner_fund(&mut self) {
+            assert_eq!(self.env().caller(), self.owners[0]);
+            self.total = self.total.checked_add(1).expect("overflow");
+            self.notify();
+        }
+
+        fn notify(&self) {
+            self.env().emit_event(Funded {});
+        }
+    }
+}
+"#;
+        let model = parse_source(source).unwrap();
+        let issues = security("lib.rs", &model, false);
+        assert!(security("lib.rs", &model, true)
+            .iter()
+            .all(|i| i.rule != "unchecked-arithmetic"));
+
+        let rules: Vec<_> = issues.iter().map(|i| i.rule.as_str()).collect();
+        assert_eq!(
+            rules,
+            vec![
+                "unchecked-arithmetic",
+                "payable-access-control",
+                "missing-event"
+            ]
+        );
+        assert!(issues.iter().all(|i| i.description.contains("'fund'")));
+        assert_eq!(issues[0].line, Some(13));
+
+        let gas = gas("lib.rs", &model);
+        assert_eq!(gas.len(), 1);
+        assert_eq!(gas[0].line, Some(6));
+    }
+
+    #[test]
+    fn test_missing_event_lists_written_fields() {
+        let source = r#"
+#[ink::contract]
+mod registry {
+    impl Registry {
+        #[ink(message)]
+        pub fn register(&mut self, name: u32) {
+            self.count += 1;
+            self.store(name);
+        }
+
+        #[ink(message)]
+        pub fn touch(&mut self) -> u32 {
+            self.count
+        }
+
+        fn store(&mut self, name: u32) {
+            self.names.insert(name, &self.env().caller());
+        }
+    }
+}
+"#;
+        let model = parse_source(source).unwrap();
+        let issues: Vec<_> = security("lib.rs", &model, true)
+            .into_iter()
+            .filter(|i| i.rule == "missing-event")
+            .collect();
+
+        // `touch` takes `&mut self` but writes nothing
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].description,
+            "State-changing message 'register' modifies `count`, `names` but emits no events"
+        );
+    }
+
+    #[test]
+    fn test_maintainability_rules() {
+        let source = r#"
+#[ink::contract]
+mod counter {
+    #[ink(storage)]
+    pub struct Counter {
+        value: u32,
+        history: Mapping<u32, u32>,
+    }
+
+    impl Counter {
+        #[ink(message)]
+        pub fn get(&self) -> u32 {
+            match self.value {
+                0 => 0,
+                _ => 1,
+                1 => 2,
+            }
+        }
+
+        #[ink(message)]
+        pub fn record(&mut self) {
+            self.history.insert(self.value, &1);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[ink::test]
+        fn works() {
+            assert_eq!(counter.get(), 0);
+        }
+    }
+}
+"#;
+        let model = parse_source(source).unwrap();
+        let tests = model.test_references.clone();
+        let issues = maintainability("lib.rs", &model, &tests);
+
+        let found: Vec<_> = issues
+            .iter()
+            .map(|i| (i.rule.as_str(), i.line.unwrap()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("unused-storage-field", 7),
+                ("untested-message", 21),
+                ("unreachable-match-arm", 16),
+            ]
+        );
+    }
+}
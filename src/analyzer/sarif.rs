@@ -0,0 +1,217 @@
+// SARIF 2.1.0 export for GitHub code scanning and other SARIF consumers
+
+use std::collections::BTreeSet;
+
+use serde_json::{json, Value};
+
+use super::rules::find_rule;
+use super::AnalysisReport;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Map analyzer severities onto SARIF result levels
+fn level(severity: &str) -> &'static str {
+    match severity {
+        "high" => "error",
+        "medium" => "warning",
+        _ => "note",
+    }
+}
+
+/// Artifact URIs use forward slashes and no leading `./`
+fn artifact_uri(path: &str) -> String {
+    let uri = path.replace('\\', "/");
+    uri.strip_prefix("./").unwrap_or(&uri).to_string()
+}
+
+/// A finding flattened from any of the report's issue lists
+struct Finding<'a> {
+    rule: &'a str,
+    severity: &'a str,
+    message: &'a str,
+    file: &'a str,
+    line: Option<usize>,
+    help: &'a str,
+}
+
+fn result(finding: &Finding, rule_index: usize) -> Value {
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": artifact_uri(finding.file) }
+        }
+    });
+    if let Some(line) = finding.line {
+        location["physicalLocation"]["region"] = json!({ "startLine": line });
+    }
+
+    json!({
+        "ruleId": finding.rule,
+        "ruleIndex": rule_index,
+        "level": level(finding.severity),
+        "message": { "text": format!("{}. {}", finding.message, finding.help) },
+        "locations": [location],
+    })
+}
+
+/// Rule descriptor; vulnerability database and plugin rules are not in `RULES`
+/// and only carry their id
+fn descriptor(id: &str) -> Value {
+    match find_rule(id) {
+        Some(rule) => json!({
+            "id": rule.id,
+            "shortDescription": { "text": rule.description },
+            "defaultConfiguration": { "level": level(rule.severity) },
+            "properties": { "tags": [rule.category] },
+        }),
+        None => json!({ "id": id }),
+    }
+}
+
+/// Convert an analysis report into a SARIF log
+pub fn to_sarif(report: &AnalysisReport) -> Value {
+    let mut findings: Vec<Finding> = report
+        .security_issues
+        .iter()
+        .chain(&report.maintainability_issues)
+        .chain(&report.migration_issues)
+        .map(|issue| Finding {
+            rule: &issue.rule,
+            severity: &issue.severity,
+            message: &issue.description,
+            file: &issue.file,
+            line: issue.line,
+            help: &issue.recommendation,
+        })
+        .collect();
+
+    findings.extend(report.gas_optimizations.iter().map(|opt| Finding {
+        rule: &opt.rule,
+        severity: &opt.impact,
+        message: &opt.description,
+        file: &opt.file,
+        line: opt.line,
+        help: &opt.suggestion,
+    }));
+
+    // Describe exactly the rules that produced findings, so every result has an index
+    let ids: Vec<&str> = findings
+        .iter()
+        .map(|finding| finding.rule)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let rules: Vec<Value> = ids.iter().map(|id| descriptor(id)).collect();
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            let index = ids.binary_search(&finding.rule).unwrap_or_default();
+            result(finding, index)
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "glin-forge",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{AnalysisSummary, ComplexityMetrics, SecurityIssue};
+    use std::collections::HashMap;
+
+    fn report(security_issues: Vec<SecurityIssue>) -> AnalysisReport {
+        AnalysisReport {
+            summary: AnalysisSummary {
+                total_files: 1,
+                total_lines: 10,
+                total_functions: 1,
+                security_issues_count: security_issues.len(),
+                gas_optimization_count: 0,
+                maintainability_issues_count: 0,
+                migration_issues_count: 0,
+                average_complexity: 1.0,
+            },
+            files: vec![],
+            security_issues,
+            gas_optimizations: vec![],
+            maintainability_issues: vec![],
+            migration_issues: vec![],
+            complexity_metrics: ComplexityMetrics {
+                cyclomatic_complexity: HashMap::new(),
+                cognitive_complexity: HashMap::new(),
+                maintainability_index: 100.0,
+            },
+            contracts: vec![],
+            storage_layouts: vec![],
+            call_graph: None,
+            access_control: vec![],
+            gas_profile: vec![],
+            diff: None,
+        }
+    }
+
+    fn issue(rule: &str, severity: &str) -> SecurityIssue {
+        SecurityIssue {
+            rule: rule.to_string(),
+            severity: severity.to_string(),
+            category: "Error Handling".to_string(),
+            description: "Use of unwrap() in 'get' could panic".to_string(),
+            file: "./lib.rs".to_string(),
+            line: Some(42),
+            recommendation: "Handle the error".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sarif_result_region() {
+        let sarif = to_sarif(&report(vec![issue("unwrap", "medium")]));
+        let result = &sarif["runs"][0]["results"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(result["ruleId"], "unwrap");
+        assert_eq!(result["level"], "warning");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "lib.rs"
+        );
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            42
+        );
+        let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(
+            sarif["runs"][0]["tool"]["driver"]["rules"][rule_index]["id"],
+            "unwrap"
+        );
+    }
+
+    #[test]
+    fn test_sarif_plugin_rule() {
+        let sarif = to_sarif(&report(vec![
+            issue("unwrap", "medium"),
+            issue("plugin/no-todo", "low"),
+        ]));
+        let rules = &sarif["runs"][0]["tool"]["driver"]["rules"];
+
+        assert_eq!(rules.as_array().unwrap().len(), 2);
+        for result in sarif["runs"][0]["results"].as_array().unwrap() {
+            let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+            assert_eq!(rules[rule_index]["id"], result["ruleId"]);
+        }
+        assert_eq!(rules[0]["id"], "plugin/no-todo");
+        assert!(rules[0].get("shortDescription").is_none());
+    }
+}
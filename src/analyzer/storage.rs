@@ -0,0 +1,330 @@
+// Storage layout report from built contract metadata
+//
+// Walks the `storage` layout of the metadata JSON and lists every storage
+// cell: packed fields stored under the contract root key and lazily loaded
+// roots (`Mapping`, `Lazy`, `StorageVec`) stored under their own key. The
+// encoded size of each entry is estimated from the type registry; types
+// without an upper bound (`Vec`, `String`, ...) are flagged since storing
+// them risks ever-growing storage deposits.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+/// Deposit parameters used for cost estimates, in the chain's smallest unit
+#[derive(Debug, Clone, Copy)]
+pub struct DepositRates {
+    pub per_byte: u128,
+    pub per_item: u128,
+}
+
+impl Default for DepositRates {
+    // Matches substrate-contracts-node
+    fn default() -> Self {
+        Self {
+            per_byte: 5_000_000,
+            per_item: 100_000_000_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageLayoutReport {
+    pub contract: String,
+    pub metadata: String,
+    pub fields: Vec<StorageCell>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageCell {
+    /// Field path, e.g. `balances` or `config.owner`
+    pub name: String,
+    /// Storage key as hex
+    pub key: String,
+    pub ty: String,
+    /// "packed" for fields in the root cell, "root" for lazily loaded entries
+    pub kind: String,
+    /// Upper bound of the encoded size, `None` if unbounded
+    pub max_bytes: Option<u64>,
+    /// Estimated deposit for one entry, `None` if unbounded
+    pub deposit_per_entry: Option<u128>,
+}
+
+impl StorageCell {
+    pub fn is_unbounded(&self) -> bool {
+        self.max_bytes.is_none()
+    }
+}
+
+/// Build the storage layout report for a metadata file
+pub fn analyze_metadata(path: &Path, rates: DepositRates) -> Result<StorageLayoutReport> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read metadata: {}", path.display()))?;
+    let metadata: JsonValue = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse metadata: {}", path.display()))?;
+
+    let contract = metadata["contract"]["name"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string();
+
+    let root = metadata
+        .get("storage")
+        .context("Metadata has no storage layout")?;
+    let types = metadata["types"].as_array().cloned().unwrap_or_default();
+
+    let mut walker = LayoutWalker {
+        types: &types,
+        rates,
+        cells: Vec::new(),
+    };
+    walker.walk(root, "");
+
+    Ok(StorageLayoutReport {
+        contract,
+        metadata: path.to_string_lossy().to_string(),
+        fields: walker.cells,
+    })
+}
+
+/// Locate `target/ink/<name>.json` for a contract crate
+pub fn find_metadata(crate_dir: &Path, name: &str) -> Option<std::path::PathBuf> {
+    let ink_dir = crate_dir.join("target/ink");
+    [name.to_string(), name.replace('-', "_")]
+        .iter()
+        .map(|name| ink_dir.join(format!("{}.json", name)))
+        .find(|path| path.exists())
+}
+
+struct LayoutWalker<'a> {
+    types: &'a [JsonValue],
+    rates: DepositRates,
+    cells: Vec<StorageCell>,
+}
+
+impl LayoutWalker<'_> {
+    fn walk(&mut self, layout: &JsonValue, name: &str) {
+        if let Some(root) = layout.get("root") {
+            let key = root["root_key"].as_str().unwrap_or_default();
+            let ty = root["ty"].as_u64();
+            // The contract's own root is the storage struct itself
+            if name.is_empty() {
+                self.walk(&root["layout"], name);
+            } else {
+                self.push_root(name, key, ty, &root["layout"]);
+            }
+        } else if let Some(strukt) = layout.get("struct") {
+            for field in strukt["fields"].as_array().into_iter().flatten() {
+                let field_name = field["name"].as_str().unwrap_or("?");
+                let path = if name.is_empty() {
+                    field_name.to_string()
+                } else {
+                    format!("{}.{}", name, field_name)
+                };
+                self.walk(&field["layout"], &path);
+            }
+        } else if let Some(leaf) = layout.get("leaf") {
+            let key = leaf["key"].as_str().unwrap_or_default();
+            let ty = leaf["ty"].as_u64();
+            let max_bytes = ty.and_then(|ty| self.max_size(ty, 0));
+            self.cells.push(StorageCell {
+                name: name.to_string(),
+                key: key.to_string(),
+                ty: ty.map(|ty| self.type_name(ty)).unwrap_or_default(),
+                kind: "packed".to_string(),
+                max_bytes,
+                // Packed fields share the root cell, so they only add bytes
+                deposit_per_entry: max_bytes.map(|bytes| bytes as u128 * self.rates.per_byte),
+            });
+        } else if let Some(array) = layout.get("array") {
+            self.walk(&array["layout"], name);
+        } else if let Some(enm) = layout.get("enum") {
+            for variant in enm["variants"]
+                .as_object()
+                .into_iter()
+                .flat_map(|variants| variants.values())
+            {
+                self.walk(&serde_json::json!({ "struct": variant }), name);
+            }
+        }
+    }
+
+    /// A lazily loaded root: each entry is a separate storage item
+    fn push_root(&mut self, name: &str, key: &str, ty: Option<u64>, layout: &JsonValue) {
+        let value_ty = layout.get("leaf").and_then(|leaf| leaf["ty"].as_u64());
+        let max_bytes = match value_ty {
+            Some(ty) => self.max_size(ty, 0),
+            // Nested structs inside a root are not sized
+            None => None,
+        };
+
+        self.cells.push(StorageCell {
+            name: name.to_string(),
+            key: key.to_string(),
+            ty: ty.map(|ty| self.type_name(ty)).unwrap_or_default(),
+            kind: "root".to_string(),
+            max_bytes,
+            deposit_per_entry: max_bytes
+                .map(|bytes| self.rates.per_item + bytes as u128 * self.rates.per_byte),
+        });
+    }
+
+    fn lookup(&self, id: u64) -> Option<&JsonValue> {
+        self.types
+            .iter()
+            .find(|t| t["id"].as_u64() == Some(id))
+            .map(|t| &t["type"])
+    }
+
+    fn type_name(&self, id: u64) -> String {
+        let Some(ty) = self.lookup(id) else {
+            return format!("#{}", id);
+        };
+
+        if let Some(last) = ty["path"].as_array().and_then(|path| path.last()) {
+            return last.as_str().unwrap_or_default().to_string();
+        }
+
+        let def = &ty["def"];
+        if let Some(primitive) = def["primitive"].as_str() {
+            primitive.to_string()
+        } else if let Some(inner) = def["sequence"]["type"].as_u64() {
+            format!("Vec<{}>", self.type_name(inner))
+        } else if let Some(inner) = def["array"]["type"].as_u64() {
+            format!("[{}; {}]", self.type_name(inner), def["array"]["len"])
+        } else if let Some(items) = def["tuple"].as_array() {
+            let names: Vec<_> = items
+                .iter()
+                .filter_map(|i| i.as_u64())
+                .map(|i| self.type_name(i))
+                .collect();
+            format!("({})", names.join(", "))
+        } else if let Some(inner) = def["compact"]["type"].as_u64() {
+            format!("Compact<{}>", self.type_name(inner))
+        } else {
+            format!("#{}", id)
+        }
+    }
+
+    /// Upper bound of the SCALE encoded size, `None` if unbounded
+    fn max_size(&self, id: u64, depth: usize) -> Option<u64> {
+        // Recursive types are unbounded
+        if depth > 32 {
+            return None;
+        }
+        let def = &self.lookup(id)?["def"];
+
+        if let Some(primitive) = def["primitive"].as_str() {
+            return match primitive {
+                "bool" | "u8" | "i8" => Some(1),
+                "u16" | "i16" => Some(2),
+                "u32" | "i32" | "char" => Some(4),
+                "u64" | "i64" => Some(8),
+                "u128" | "i128" => Some(16),
+                "u256" | "i256" => Some(32),
+                _ => None, // str
+            };
+        }
+        if def.get("sequence").is_some() {
+            return None;
+        }
+        if let Some(array) = def.get("array") {
+            let len = array["len"].as_u64()?;
+            return Some(len * self.max_size(array["type"].as_u64()?, depth + 1)?);
+        }
+        if let Some(items) = def["tuple"].as_array() {
+            return items.iter().try_fold(0, |acc, item| {
+                Some(acc + self.max_size(item.as_u64()?, depth + 1)?)
+            });
+        }
+        if let Some(fields) = def["composite"]["fields"].as_array() {
+            return fields.iter().try_fold(0, |acc, field| {
+                Some(acc + self.max_size(field["type"].as_u64()?, depth + 1)?)
+            });
+        }
+        if let Some(variants) = def["variant"]["variants"].as_array() {
+            let mut largest = 0;
+            for variant in variants {
+                let size = variant["fields"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .try_fold(0, |acc, field| {
+                        Some(acc + self.max_size(field["type"].as_u64()?, depth + 1)?)
+                    })?;
+                largest = largest.max(size);
+            }
+            return Some(1 + largest);
+        }
+        if def.get("compact").is_some() {
+            return Some(17);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "contract": { "name": "token" },
+        "storage": {
+            "root": {
+                "root_key": "0x00000000",
+                "ty": 5,
+                "layout": {
+                    "struct": {
+                        "name": "Token",
+                        "fields": [
+                            { "name": "total_supply", "layout": { "leaf": { "key": "0x00000000", "ty": 0 } } },
+                            { "name": "name", "layout": { "leaf": { "key": "0x00000000", "ty": 1 } } },
+                            { "name": "balances", "layout": { "root": {
+                                "root_key": "0x2623dce7",
+                                "ty": 3,
+                                "layout": { "leaf": { "key": "0x2623dce7", "ty": 0 } }
+                            } } }
+                        ]
+                    }
+                }
+            }
+        },
+        "types": [
+            { "id": 0, "type": { "def": { "primitive": "u128" } } },
+            { "id": 1, "type": { "def": { "primitive": "str" } } },
+            { "id": 3, "type": { "path": ["ink_storage", "lazy", "mapping", "Mapping"], "def": { "composite": {} } } }
+        ]
+    }"#;
+
+    #[test]
+    fn test_storage_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token.json");
+        std::fs::write(&path, METADATA).unwrap();
+
+        let rates = DepositRates {
+            per_byte: 10,
+            per_item: 1000,
+        };
+        let report = analyze_metadata(&path, rates).unwrap();
+
+        assert_eq!(report.contract, "token");
+        assert_eq!(report.fields.len(), 3);
+
+        let supply = &report.fields[0];
+        assert_eq!(supply.kind, "packed");
+        assert_eq!(supply.max_bytes, Some(16));
+        assert_eq!(supply.deposit_per_entry, Some(160));
+
+        assert!(report.fields[1].is_unbounded());
+        assert_eq!(report.fields[1].ty, "str");
+
+        let balances = &report.fields[2];
+        assert_eq!(balances.kind, "root");
+        assert_eq!(balances.ty, "Mapping");
+        assert_eq!(balances.key, "0x2623dce7");
+        assert_eq!(balances.deposit_per_entry, Some(1160));
+    }
+}
@@ -0,0 +1,501 @@
+// Vulnerability pattern database
+//
+// Signatures for known bad patterns (dangerous environment calls), vulnerable
+// crate versions and dangerous cross-contract selectors live in a versioned
+// JSON file instead of the rule code. A copy is bundled with the binary
+// (`data/vuln-db.json`); `analyze --update-db` downloads newer releases from a
+// remote index into the user cache, and the newest compatible copy wins.
+//
+// The index is a JSON document listing published databases:
+//
+//   { "databases": [ { "schema": 1, "version": 2, "url": "vuln-db-2.json", "sha256": "..." } ] }
+//
+// Relative URLs are resolved against the index URL. Findings use rule ids of
+// the form `db/<id>` so they can be configured like plugin rules.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::access;
+use super::ast::SourceModel;
+use super::SecurityIssue;
+
+/// Database format understood by this build
+pub const SCHEMA: u32 = 1;
+
+/// Default index of published databases
+pub const DEFAULT_INDEX_URL: &str =
+    "https://raw.githubusercontent.com/glin-ai/glin-forge/main/data/index.json";
+
+const BUNDLED: &str = include_str!("../../data/vuln-db.json");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VulnDb {
+    pub schema: u32,
+    pub version: u32,
+    #[serde(default)]
+    pub updated: String,
+    #[serde(default)]
+    pub patterns: Vec<Pattern>,
+    #[serde(default)]
+    pub crates: Vec<VulnerableCrate>,
+    #[serde(default)]
+    pub selectors: Vec<DangerousSelector>,
+}
+
+/// A call to a method or function by name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pattern {
+    pub id: String,
+    pub call: String,
+    /// Only report messages that reach the call without a caller check
+    #[serde(default)]
+    pub unguarded_only: bool,
+    pub severity: String,
+    pub description: String,
+    #[serde(default)]
+    pub recommendation: String,
+}
+
+/// A dependency whose versions matching `vulnerable` are affected
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VulnerableCrate {
+    pub id: String,
+    pub name: String,
+    /// Semver requirement, e.g. `<4.2.1` or `*`
+    pub vulnerable: String,
+    pub severity: String,
+    pub description: String,
+    #[serde(default)]
+    pub recommendation: String,
+}
+
+/// A cross-contract call to a selector, as `0x` followed by 4 bytes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DangerousSelector {
+    pub id: String,
+    pub selector: String,
+    #[serde(default)]
+    pub label: String,
+    pub severity: String,
+    pub description: String,
+    #[serde(default)]
+    pub recommendation: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Index {
+    databases: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+    schema: u32,
+    version: u32,
+    url: String,
+    sha256: String,
+}
+
+impl Default for VulnDb {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+impl VulnDb {
+    /// The database shipped with this build
+    pub fn bundled() -> Self {
+        Self::parse(BUNDLED).expect("bundled vulnerability database is valid")
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let db: Self =
+            serde_json::from_str(content).context("Failed to parse vulnerability database")?;
+        if db.schema != SCHEMA {
+            anyhow::bail!(
+                "Unsupported vulnerability database schema {} (this build reads schema {})",
+                db.schema,
+                SCHEMA
+            );
+        }
+        for severity in db
+            .patterns
+            .iter()
+            .map(|p| &p.severity)
+            .chain(db.crates.iter().map(|c| &c.severity))
+            .chain(db.selectors.iter().map(|s| &s.severity))
+        {
+            if !matches!(severity.as_str(), "high" | "medium" | "low") {
+                anyhow::bail!("Invalid severity '{}' in vulnerability database", severity);
+            }
+        }
+        for vulnerable in &db.crates {
+            semver::VersionReq::parse(&vulnerable.vulnerable).with_context(|| {
+                format!(
+                    "Invalid version requirement '{}' for {}",
+                    vulnerable.vulnerable, vulnerable.id
+                )
+            })?;
+        }
+        Ok(db)
+    }
+
+    /// The cached database if it is newer than the bundled one
+    pub fn load() -> Self {
+        let bundled = Self::bundled();
+        let cached = cache_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| Self::parse(&content).ok());
+
+        match cached {
+            Some(cached) if cached.version > bundled.version => cached,
+            _ => bundled,
+        }
+    }
+
+    /// Download the newest compatible database listed in the index, if newer
+    /// than `self`. Returns the installed database.
+    pub async fn update(&self, index_url: &str) -> Result<Option<Self>> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+
+        let index: Index = client
+            .get(index_url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to fetch vulnerability index {}", index_url))?
+            .json()
+            .await
+            .context("Failed to parse vulnerability index")?;
+
+        let Some(latest) = index
+            .databases
+            .iter()
+            .filter(|entry| entry.schema == SCHEMA)
+            .max_by_key(|entry| entry.version)
+        else {
+            anyhow::bail!(
+                "The index lists no database for schema {}; upgrade glin-forge",
+                SCHEMA
+            );
+        };
+        if latest.version <= self.version {
+            return Ok(None);
+        }
+
+        let url = reqwest::Url::parse(index_url)?
+            .join(&latest.url)
+            .with_context(|| format!("Invalid database URL '{}'", latest.url))?;
+        let content = client
+            .get(url.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to download {}", url))?
+            .text()
+            .await?;
+
+        let digest = hex::encode(sp_core_hashing::sha2_256(content.as_bytes()));
+        if !digest.eq_ignore_ascii_case(&latest.sha256) {
+            anyhow::bail!("Checksum mismatch for {} (expected {})", url, latest.sha256);
+        }
+
+        let db = Self::parse(&content)?;
+        if db.version != latest.version {
+            anyhow::bail!(
+                "Database at {} is version {}, the index lists {}",
+                url,
+                db.version,
+                latest.version
+            );
+        }
+
+        let path = cache_path().context("No cache directory available")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(Some(db))
+    }
+
+    /// Findings for known bad calls and selectors in one file
+    pub fn check_source(&self, file: &str, model: &SourceModel) -> Vec<SecurityIssue> {
+        let mut issues = Vec::new();
+        let mut seen = HashSet::new();
+        let mut issue = |id: &str, severity: &str, description: String, line, fix: &str| {
+            if seen.insert((id.to_string(), line)) {
+                issues.push(SecurityIssue {
+                    rule: format!("db/{}", id),
+                    severity: severity.to_string(),
+                    category: "Known Vulnerability".to_string(),
+                    description,
+                    file: file.to_string(),
+                    line: Some(line),
+                    recommendation: fix.to_string(),
+                });
+            }
+        };
+
+        for pattern in &self.patterns {
+            if pattern.unguarded_only {
+                // Report calls reachable from a message without any caller check
+                for message in model.functions.iter().filter(|f| f.is_message()) {
+                    if !access::roles(model, message).is_empty() {
+                        continue;
+                    }
+                    for func in model.reachable(message) {
+                        for (_, line) in func.invoked.iter().filter(|(n, _)| *n == pattern.call) {
+                            issue(
+                                &pattern.id,
+                                &pattern.severity,
+                                format!("{} (in '{}')", pattern.description, message.info.name),
+                                *line,
+                                &pattern.recommendation,
+                            );
+                        }
+                    }
+                }
+            } else {
+                for func in &model.functions {
+                    for (_, line) in func.invoked.iter().filter(|(n, _)| *n == pattern.call) {
+                        issue(
+                            &pattern.id,
+                            &pattern.severity,
+                            format!("{} (in '{}')", pattern.description, func.info.name),
+                            *line,
+                            &pattern.recommendation,
+                        );
+                    }
+                }
+            }
+        }
+
+        for func in &model.functions {
+            for call in &func.external_calls {
+                let Some(label) = &call.selector else {
+                    continue;
+                };
+                let selector = format!(
+                    "0x{}",
+                    hex::encode(&sp_core_hashing::blake2_256(label.as_bytes())[..4])
+                );
+                for dangerous in self
+                    .selectors
+                    .iter()
+                    .filter(|s| s.selector.eq_ignore_ascii_case(&selector))
+                {
+                    issue(
+                        &dangerous.id,
+                        &dangerous.severity,
+                        format!("{} (in '{}')", dangerous.description, func.info.name),
+                        call.line,
+                        &dangerous.recommendation,
+                    );
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Findings for vulnerable dependency versions of a crate
+    pub fn check_manifest(&self, manifest: &Path) -> Result<Vec<SecurityIssue>> {
+        let content = std::fs::read_to_string(manifest)
+            .with_context(|| format!("Failed to read {}", manifest.display()))?;
+        let value: toml::Value = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", manifest.display()))?;
+        let locked = locked_versions(manifest);
+
+        let mut issues = Vec::new();
+        for vulnerable in &self.crates {
+            let Some(requirement) = dependency_requirement(&value, &vulnerable.name) else {
+                continue;
+            };
+            // The lockfile knows the resolved version; otherwise assume the lowest allowed one
+            let version = locked
+                .iter()
+                .find(|(name, _)| *name == vulnerable.name)
+                .map(|(_, version)| version.clone())
+                .or_else(|| lowest_version(&requirement));
+            let Some(version) = version else {
+                continue;
+            };
+            let affected = semver::VersionReq::parse(&vulnerable.vulnerable)
+                .is_ok_and(|req| req.matches(&version));
+            if !affected {
+                continue;
+            }
+
+            let line = content
+                .lines()
+                .position(|line| {
+                    let line = line.trim_start();
+                    line.starts_with(&format!("{} ", vulnerable.name))
+                        || line.starts_with(&format!("{}=", vulnerable.name))
+                })
+                .map(|i| i + 1)
+                .unwrap_or(1);
+
+            issues.push(SecurityIssue {
+                rule: format!("db/{}", vulnerable.id),
+                severity: vulnerable.severity.clone(),
+                category: "Known Vulnerability".to_string(),
+                description: format!(
+                    "{} {}: {}",
+                    vulnerable.name, version, vulnerable.description
+                ),
+                file: manifest.to_string_lossy().to_string(),
+                line: Some(line),
+                recommendation: vulnerable.recommendation.clone(),
+            });
+        }
+
+        Ok(issues)
+    }
+}
+
+/// Where `--update-db` stores downloaded databases
+pub fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("glin-forge").join("vuln-db.json"))
+}
+
+/// Version requirement of a dependency in any dependency table
+pub(crate) fn dependency_requirement(manifest: &toml::Value, name: &str) -> Option<String> {
+    ["dependencies", "dev-dependencies"]
+        .iter()
+        .filter_map(|table| manifest.get(table).and_then(|deps| deps.get(name)))
+        .find_map(|dep| {
+            dep.as_str()
+                .or_else(|| dep.get("version").and_then(|v| v.as_str()))
+                .map(str::to_string)
+        })
+}
+
+/// Lowest version a requirement like `4.2` or `^5.0.0` accepts
+pub(crate) fn lowest_version(requirement: &str) -> Option<semver::Version> {
+    let version = requirement.trim_start_matches(|c: char| !c.is_ascii_digit());
+    let mut parts: Vec<&str> = version.split('.').take(3).collect();
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    semver::Version::parse(&parts.join(".")).ok()
+}
+
+/// Resolved package versions from the nearest `Cargo.lock`
+pub(crate) fn locked_versions(manifest: &Path) -> Vec<(String, semver::Version)> {
+    let Some(lock) = manifest
+        .parent()
+        .into_iter()
+        .flat_map(Path::ancestors)
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.exists())
+    else {
+        return Vec::new();
+    };
+
+    let Ok(value) = std::fs::read_to_string(lock).map(|content| content.parse::<toml::Value>())
+    else {
+        return Vec::new();
+    };
+
+    value
+        .ok()
+        .and_then(|value| value.get("package").and_then(|p| p.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            Some((name.to_string(), semver::Version::parse(version).ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::ast::parse_source;
+
+    #[test]
+    fn test_bundled_db_parses() {
+        let db = VulnDb::bundled();
+        assert_eq!(db.schema, SCHEMA);
+        assert!(!db.patterns.is_empty());
+        assert!(VulnDb::parse(&BUNDLED.replace("\"schema\": 1", "\"schema\": 99")).is_err());
+
+        // The published index must describe the bundled file
+        let index: Index = serde_json::from_str(include_str!("../../data/index.json")).unwrap();
+        let entry = index.databases.iter().max_by_key(|e| e.version).unwrap();
+        assert_eq!(entry.version, db.version);
+        assert_eq!(
+            entry.sha256,
+            hex::encode(sp_core_hashing::sha2_256(BUNDLED.as_bytes()))
+        );
+    }
+
+    #[test]
+    fn test_db_findings() {
+        let source = r#"
+#[ink::contract]
+mod c {
+    impl C {
+        #[ink(message)]
+        pub fn destroy(&mut self, beneficiary: AccountId) {
+            self.env().terminate_contract(beneficiary);
+        }
+
+        #[ink(message)]
+        pub fn close(&mut self) {
+            assert_eq!(self.env().caller(), self.owner);
+            self.env().terminate_contract(self.owner);
+        }
+
+        #[ink(message)]
+        pub fn pull(&mut self, from: AccountId) {
+            build_call::<DefaultEnvironment>()
+                .call(self.token)
+                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from"))))
+                .returns::<()>()
+                .invoke();
+        }
+    }
+}
+"#;
+        let model = parse_source(source).unwrap();
+        let issues = VulnDb::bundled().check_source("lib.rs", &model);
+        let found: Vec<(&str, Option<usize>)> =
+            issues.iter().map(|i| (i.rule.as_str(), i.line)).collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("db/unguarded-terminate", Some(7)),
+                ("db/external-transfer-from", Some(22)),
+            ]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            "[package]\nname = \"c\"\n\n[dependencies]\nink = \"3.4\"\nopenbrush = { version = \"4.0.0\" }\n",
+        )
+        .unwrap();
+        let issues = VulnDb::bundled().check_manifest(&manifest).unwrap();
+        let found: Vec<(&str, Option<usize>)> =
+            issues.iter().map(|i| (i.rule.as_str(), i.line)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("db/unmaintained-ink-3", Some(5)),
+                ("db/unmaintained-openbrush", Some(6)),
+            ]
+        );
+    }
+}
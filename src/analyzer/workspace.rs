@@ -0,0 +1,298 @@
+// Workspace discovery and per-contract aggregation
+//
+// Directories are walked recursively (honoring .gitignore and skipping
+// `target/`), and every source file is attributed to the nearest crate, i.e.
+// the closest ancestor directory with a Cargo.toml.
+
+use anyhow::Result;
+use globset::GlobSet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::AnalysisReport;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractSummary {
+    pub name: String,
+    pub path: String,
+    pub files: usize,
+    pub lines_of_code: usize,
+    pub functions: usize,
+    pub security_issues: usize,
+    pub gas_optimizations: usize,
+    pub maintainability_issues: usize,
+    pub average_complexity: f64,
+    /// 0-100, penalized by findings weighted by severity
+    pub score: f64,
+}
+
+/// Collect all `.rs` files under `root`, or `root` itself if it is a file
+pub fn collect_sources(root: &Path, ignore: &GlobSet) -> Result<Vec<PathBuf>> {
+    if root.is_file() {
+        return Ok(vec![root.to_path_buf()]);
+    }
+
+    let mut sources = Vec::new();
+    let walker = ignore::WalkBuilder::new(root)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != "target")
+        .build();
+
+    for entry in walker {
+        let entry = entry?;
+        let path = entry.path();
+        let relative = path.strip_prefix(root).unwrap_or(path);
+
+        if path.is_file()
+            && path.extension().and_then(|s| s.to_str()) == Some("rs")
+            && !ignore.is_match(relative)
+        {
+            sources.push(path.to_path_buf());
+        }
+    }
+
+    sources.sort();
+    Ok(sources)
+}
+
+/// Nearest Cargo.toml above a source file
+pub fn find_manifest(file: &Path) -> Option<PathBuf> {
+    file.ancestors()
+        .skip(1)
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| manifest.exists())
+}
+
+/// Name of the crate a source file belongs to, from the nearest Cargo.toml
+pub fn crate_name(file: &Path) -> Option<String> {
+    let manifest = find_manifest(file)?;
+
+    let content = std::fs::read_to_string(&manifest).ok()?;
+    let value: toml::Value = toml::from_str(&content).ok()?;
+
+    match value
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+    {
+        Some(name) => Some(name.to_string()),
+        // Virtual workspace manifests have no package
+        None => manifest
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .map(|name| name.to_string_lossy().to_string()),
+    }
+}
+
+/// Whether `overflow-checks` is enabled for a cargo profile of the crate owning `file`
+///
+/// Cargo only reads profiles from the workspace root, so a member's own
+/// `[profile.*]` tables are ignored; a crate outside any workspace uses its own
+/// manifest. Without an explicit setting, cargo's defaults apply: checks are on
+/// for `dev`/`test` and off for `release`/`bench`.
+pub fn overflow_checks(file: &Path, profile: &str) -> bool {
+    let cargo_profile = match profile {
+        "debug" => "dev",
+        other => other,
+    };
+
+    let manifests: Vec<toml::Value> = file
+        .ancestors()
+        .skip(1)
+        .filter_map(|dir| std::fs::read_to_string(dir.join("Cargo.toml")).ok())
+        .filter_map(|content| toml::from_str(&content).ok())
+        .collect();
+    let root = manifests
+        .iter()
+        .find(|manifest| manifest.get("workspace").is_some())
+        .or_else(|| manifests.first());
+
+    root.and_then(|manifest| manifest.get("profile"))
+        .and_then(|profiles| profiles.get(cargo_profile))
+        .and_then(|profile| profile.get("overflow-checks"))
+        .and_then(|enabled| enabled.as_bool())
+        .unwrap_or(matches!(cargo_profile, "dev" | "test"))
+}
+
+/// Security findings weigh by severity; other findings cost 2 points each
+fn score(high: usize, medium: usize, low: usize, other: usize) -> f64 {
+    let penalty = high as f64 * 15.0 + medium as f64 * 5.0 + low as f64 + other as f64 * 2.0;
+    (100.0 - penalty).clamp(0.0, 100.0)
+}
+
+/// Aggregate files and findings of a report per contract crate
+pub fn summarize(report: &AnalysisReport) -> Vec<ContractSummary> {
+    let mut contracts: BTreeMap<String, ContractSummary> = BTreeMap::new();
+    let mut complexity: BTreeMap<String, u32> = BTreeMap::new();
+    let mut severities: BTreeMap<String, [usize; 3]> = BTreeMap::new();
+
+    let contract_of = |file: &str| {
+        report
+            .files
+            .iter()
+            .find(|f| f.path == file)
+            .and_then(|f| f.contract.clone())
+    };
+
+    for file in &report.files {
+        let Some(name) = &file.contract else {
+            continue;
+        };
+        let dir = Path::new(&file.path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let summary = contracts
+            .entry(name.clone())
+            .or_insert_with(|| ContractSummary {
+                name: name.clone(),
+                path: dir.clone(),
+                files: 0,
+                lines_of_code: 0,
+                functions: 0,
+                security_issues: 0,
+                gas_optimizations: 0,
+                maintainability_issues: 0,
+                average_complexity: 0.0,
+                score: 100.0,
+            });
+
+        // Report the crate directory rather than e.g. its `src/`
+        if dir.len() < summary.path.len() {
+            summary.path = dir;
+        }
+        summary.files += 1;
+        summary.lines_of_code += file.lines_of_code;
+        summary.functions += file.functions.len();
+        *complexity.entry(name.clone()).or_default() +=
+            file.functions.iter().map(|f| f.complexity).sum::<u32>();
+    }
+
+    for issue in &report.security_issues {
+        if let Some(name) = contract_of(&issue.file) {
+            if let Some(summary) = contracts.get_mut(&name) {
+                summary.security_issues += 1;
+            }
+            let counts = severities.entry(name).or_default();
+            match issue.severity.as_str() {
+                "high" => counts[0] += 1,
+                "medium" => counts[1] += 1,
+                _ => counts[2] += 1,
+            }
+        }
+    }
+
+    for opt in &report.gas_optimizations {
+        if let Some(name) = contract_of(&opt.file) {
+            if let Some(summary) = contracts.get_mut(&name) {
+                summary.gas_optimizations += 1;
+            }
+        }
+    }
+
+    for issue in &report.maintainability_issues {
+        if let Some(name) = contract_of(&issue.file) {
+            if let Some(summary) = contracts.get_mut(&name) {
+                summary.maintainability_issues += 1;
+            }
+        }
+    }
+
+    contracts
+        .into_values()
+        .map(|mut summary| {
+            if summary.functions > 0 {
+                summary.average_complexity =
+                    complexity[&summary.name] as f64 / summary.functions as f64;
+            }
+            let [high, medium, low] = severities.get(&summary.name).copied().unwrap_or_default();
+            summary.score = score(
+                high,
+                medium,
+                low,
+                summary.gas_optimizations + summary.maintainability_issues,
+            );
+            summary
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_sources_recursive() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        for path in [
+            "contracts/token/lib.rs",
+            "contracts/nft/src/lib.rs",
+            "target/debug/build.rs",
+            "generated/skip.rs",
+        ] {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, "fn main() {}").unwrap();
+        }
+        std::fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+        std::fs::write(
+            root.join("contracts/nft/Cargo.toml"),
+            "[package]\nname = \"nft\"\n",
+        )
+        .unwrap();
+
+        let sources = collect_sources(root, &GlobSet::empty()).unwrap();
+        let relative: Vec<_> = sources
+            .iter()
+            .map(|p| p.strip_prefix(root).unwrap().to_string_lossy().to_string())
+            .collect();
+
+        assert_eq!(
+            relative,
+            vec!["contracts/nft/src/lib.rs", "contracts/token/lib.rs"]
+        );
+        assert_eq!(crate_name(&sources[0]), Some("nft".to_string()));
+    }
+
+    #[test]
+    fn test_overflow_checks_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("contract/lib.rs");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(
+            dir.path().join("contract/Cargo.toml"),
+            "[package]\nname = \"contract\"\n",
+        )
+        .unwrap();
+
+        assert!(!overflow_checks(&file, "release"));
+        assert!(overflow_checks(&file, "dev"));
+
+        // Workspace root profile applies to member crates
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\n[profile.release]\noverflow-checks = true\n",
+        )
+        .unwrap();
+        assert!(overflow_checks(&file, "release"));
+
+        // while a member's own profile does not
+        std::fs::write(
+            dir.path().join("contract/Cargo.toml"),
+            "[package]\nname = \"contract\"\n[profile.dev]\noverflow-checks = false\n",
+        )
+        .unwrap();
+        assert!(overflow_checks(&file, "dev"));
+    }
+
+    #[test]
+    fn test_score() {
+        assert_eq!(score(0, 0, 0, 0), 100.0);
+        assert_eq!(score(1, 2, 3, 1), 100.0 - 15.0 - 10.0 - 3.0 - 2.0);
+        assert_eq!(score(10, 0, 0, 0), 0.0);
+    }
+}
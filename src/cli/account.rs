@@ -18,14 +18,23 @@ enum AccountCommands {
         name: String,
     },
 
-    /// Import account from seed
+    /// Import account from seed, or a polkadot-js JSON export into the keystore
     Import {
         /// Account name
         name: String,
 
         /// Seed phrase or private key
-        #[arg(short, long)]
-        seed: String,
+        #[arg(
+            short,
+            long,
+            required_unless_present = "keystore",
+            conflicts_with = "keystore"
+        )]
+        seed: Option<String>,
+
+        /// Encrypted polkadot-js JSON export, stored in ~/.glin-forge/keystore
+        #[arg(long)]
+        keystore: Option<std::path::PathBuf>,
     },
 
     /// Show account details
@@ -39,7 +48,14 @@ pub async fn execute(args: AccountArgs) -> anyhow::Result<()> {
     match args.command {
         AccountCommands::List => list_accounts().await,
         AccountCommands::Generate { name } => generate_account(&name).await,
-        AccountCommands::Import { name, seed } => import_account(&name, &seed).await,
+        AccountCommands::Import {
+            name,
+            keystore: Some(keystore),
+            ..
+        } => import_keystore(&name, &keystore),
+        AccountCommands::Import { name, seed, .. } => {
+            import_account(&name, seed.as_deref().unwrap_or_default()).await
+        }
         AccountCommands::Show { name } => show_account(&name).await,
     }
 }
@@ -65,8 +81,20 @@ async fn list_accounts() -> anyhow::Result<()> {
     }
 
     println!();
-    println!("{}", "Custom Accounts:".bold());
-    println!("  {}", "No custom accounts configured".dimmed());
+    println!("{}", "Keystore Accounts:".bold());
+    let keystore_dir = crate::config::keystore::keystore_dir()?;
+    let keystore_accounts = crate::config::keystore::list(&keystore_dir)?;
+    if keystore_accounts.is_empty() {
+        println!("  {}", "No keystore accounts".dimmed());
+    }
+    for (idx, (name, address)) in keystore_accounts.iter().enumerate() {
+        println!(
+            "  {}. {} {}",
+            idx + 1,
+            name.yellow(),
+            format!("({})", address).dimmed()
+        );
+    }
     println!();
     println!(
         "{}",
@@ -130,6 +158,31 @@ async fn import_account(name: &str, seed: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn import_keystore(name: &str, json: &std::path::Path) -> anyhow::Result<()> {
+    println!("{}", format!("Importing account: {}", name).cyan().bold());
+
+    let dir = crate::config::keystore::keystore_dir()?;
+    let address = crate::config::keystore::import_json(&dir, name, json)?;
+
+    println!("\n{} Account imported!", "✓".green().bold());
+    println!();
+    println!("{}", "Account Details:".bold());
+    println!("  {} {}", "Name:".cyan(), name);
+    println!("  {} {}", "Address:".cyan(), address);
+    println!(
+        "  {} {}",
+        "Keystore:".cyan(),
+        crate::config::keystore::account_path(&dir, name)?.display()
+    );
+    println!();
+    println!(
+        "{}",
+        "Scripts unlock it with unlockAccount(name, password) before signing".dimmed()
+    );
+
+    Ok(())
+}
+
 async fn show_account(name: &str) -> anyhow::Result<()> {
     println!("{}", format!("Account: {}", name).cyan().bold());
 
@@ -145,7 +198,19 @@ async fn show_account(name: &str) -> anyhow::Result<()> {
             println!("  {} {}", "Address:".cyan(), address);
         }
         Err(_) => {
-            anyhow::bail!("Account '{}' not found", name);
+            let dir = crate::config::keystore::keystore_dir()?;
+            let keystore_account = crate::config::keystore::list(&dir)?
+                .into_iter()
+                .find(|(account, _)| account == name);
+            let Some((_, address)) = keystore_account else {
+                anyhow::bail!("Account '{}' not found", name);
+            };
+
+            println!();
+            println!("{}", "Account Details:".bold());
+            println!("  {} {}", "Name:".cyan(), name);
+            println!("  {} Keystore (encrypted)", "Type:".cyan());
+            println!("  {} {}", "Address:".cyan(), address);
         }
     }
 
@@ -1,8 +1,11 @@
+use crate::analyzer::callgraph::CallGraph;
+use crate::analyzer::storage::{find_metadata, DepositRates};
+use crate::analyzer::vulndb::{self, VulnDb};
+use crate::analyzer::{self, AnalysisReport, AnalyzeOptions, AnalyzerConfig, Baseline};
+use crate::testing::bench::BenchFile;
 use anyhow::{Context, Result};
 use clap::Args;
 use colored::*;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -12,7 +15,7 @@ pub struct AnalyzeArgs {
     #[arg(default_value = ".")]
     pub path: String,
 
-    /// Output format (text, json)
+    /// Output format (text, json, sarif, html)
     #[arg(short, long, default_value = "text")]
     pub format: String,
 
@@ -28,510 +31,365 @@ pub struct AnalyzeArgs {
     #[arg(short, long)]
     pub gas: bool,
 
-    /// Output file for results
+    /// Check for dead code, unused storage and untested messages
     #[arg(short, long)]
-    pub output: Option<String>,
-}
+    pub maintainability: bool,
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AnalysisReport {
-    pub summary: AnalysisSummary,
-    pub files: Vec<FileAnalysis>,
-    pub security_issues: Vec<SecurityIssue>,
-    pub gas_optimizations: Vec<GasOptimization>,
-    pub complexity_metrics: ComplexityMetrics,
-}
+    /// Generate an ink! 5 upgrade checklist from deprecated and removed APIs
+    #[arg(long)]
+    pub migrate: bool,
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AnalysisSummary {
-    pub total_files: usize,
-    pub total_lines: usize,
-    pub total_functions: usize,
-    pub security_issues_count: usize,
-    pub gas_optimization_count: usize,
-    pub average_complexity: f64,
-}
+    /// Print the message -> required role matrix
+    #[arg(long)]
+    pub access_control: bool,
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FileAnalysis {
-    pub path: String,
-    pub lines_of_code: usize,
-    pub functions: Vec<FunctionInfo>,
-    pub imports: Vec<String>,
-    pub traits: Vec<String>,
-    pub structs: Vec<String>,
-}
+    /// Extract the message call graph (internal, cross-contract and trait edges)
+    #[arg(long)]
+    pub call_graph: bool,
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct FunctionInfo {
-    pub name: String,
-    pub visibility: String,
-    pub is_payable: bool,
-    pub lines: usize,
-    pub complexity: u32,
-}
+    /// Call graph format (mermaid, dot)
+    #[arg(long, default_value = "mermaid", value_parser = ["mermaid", "dot"])]
+    pub graph_format: String,
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SecurityIssue {
-    pub severity: String, // "high", "medium", "low"
-    pub category: String,
-    pub description: String,
-    pub file: String,
-    pub line: Option<usize>,
-    pub recommendation: String,
-}
+    /// Write the call graph to a file instead of the report
+    #[arg(long, requires = "call_graph")]
+    pub graph_output: Option<PathBuf>,
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct GasOptimization {
-    pub impact: String, // "high", "medium", "low"
-    pub description: String,
-    pub file: String,
-    pub line: Option<usize>,
-    pub suggestion: String,
-}
+    /// Cargo profile used to decide whether overflow checks are enabled
+    #[arg(long, default_value = "release")]
+    pub cargo_profile: String,
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ComplexityMetrics {
-    pub cyclomatic_complexity: HashMap<String, u32>,
-    pub cognitive_complexity: HashMap<String, u32>,
-    pub maintainability_index: f64,
-}
+    /// Dry-run each message of a deployed contract and rank gas findings by measured cost
+    #[arg(long, requires_all = ["gas", "address"])]
+    pub profile: bool,
 
-pub fn run(args: AnalyzeArgs) -> Result<()> {
-    let path = PathBuf::from(&args.path);
+    /// Address of the deployed contract to profile
+    #[arg(long)]
+    pub address: Option<String>,
 
-    if !path.exists() {
-        anyhow::bail!("Path does not exist: {}", args.path);
-    }
+    /// Network to profile on
+    #[arg(short, long, default_value = "testnet")]
+    pub network: String,
 
-    println!("{}", "Analyzing contracts...".cyan().bold());
-    println!();
+    /// Bench file with argument sets for messages that take arguments
+    #[arg(long, default_value = "bench.yaml")]
+    pub bench_file: PathBuf,
 
-    let report = analyze_path(&path, &args)?;
+    /// Report storage layout and deposit estimates from built metadata
+    #[arg(long)]
+    pub storage: bool,
 
-    match args.format.as_str() {
-        "json" => output_json(&report, args.output.as_deref())?,
-        "text" | _ => output_text(&report, &args)?,
-    }
+    /// Metadata file(s) for the storage report (defaults to target/ink/<contract>.json)
+    #[arg(long)]
+    pub metadata: Vec<PathBuf>,
 
-    Ok(())
-}
+    /// Storage deposit per byte used for estimates
+    #[arg(long, default_value_t = DepositRates::default().per_byte)]
+    pub deposit_per_byte: u128,
 
-fn analyze_path(path: &Path, args: &AnalyzeArgs) -> Result<AnalysisReport> {
-    let mut files = Vec::new();
-    let mut security_issues = Vec::new();
-    let mut gas_optimizations = Vec::new();
+    /// Storage deposit per item used for estimates
+    #[arg(long, default_value_t = DepositRates::default().per_item)]
+    pub deposit_per_item: u128,
 
-    if path.is_file() {
-        if let Some(analysis) = analyze_file(path)? {
-            if args.security {
-                security_issues.extend(analyze_security(path, &analysis)?);
-            }
-            if args.gas {
-                gas_optimizations.extend(analyze_gas(path, &analysis)?);
-            }
-            files.push(analysis);
-        }
-    } else if path.is_dir() {
-        for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            let file_path = entry.path();
-
-            if file_path.is_file() && file_path.extension().and_then(|s| s.to_str()) == Some("rs") {
-                if let Some(analysis) = analyze_file(&file_path)? {
-                    if args.security {
-                        security_issues.extend(analyze_security(&file_path, &analysis)?);
-                    }
-                    if args.gas {
-                        gas_optimizations.extend(analyze_gas(&file_path, &analysis)?);
-                    }
-                    files.push(analysis);
-                }
-            }
-        }
-    }
+    /// Exit with an error when findings at or above this severity exist (high, medium, low)
+    #[arg(long, value_parser = ["high", "medium", "low"])]
+    pub fail_on: Option<String>,
 
-    let total_lines: usize = files.iter().map(|f| f.lines_of_code).sum();
-    let total_functions: usize = files.iter().map(|f| f.functions.len()).sum();
-    let total_complexity: u32 = files
-        .iter()
-        .flat_map(|f| &f.functions)
-        .map(|func| func.complexity)
-        .sum();
-    let average_complexity = if total_functions > 0 {
-        total_complexity as f64 / total_functions as f64
-    } else {
-        0.0
-    };
+    /// Exit with an error when a function exceeds this cyclomatic complexity
+    #[arg(long)]
+    pub max_complexity: Option<u32>,
 
-    let complexity_metrics = calculate_complexity_metrics(&files);
+    /// Refresh the vulnerability pattern database from the remote index before analyzing
+    #[arg(long)]
+    pub update_db: bool,
 
-    Ok(AnalysisReport {
-        summary: AnalysisSummary {
-            total_files: files.len(),
-            total_lines,
-            total_functions,
-            security_issues_count: security_issues.len(),
-            gas_optimization_count: gas_optimizations.len(),
-            average_complexity,
-        },
-        files,
-        security_issues,
-        gas_optimizations,
-        complexity_metrics,
-    })
-}
+    /// Index the vulnerability database is refreshed from
+    #[arg(long, default_value = vulndb::DEFAULT_INDEX_URL)]
+    pub db_index: String,
 
-fn analyze_file(path: &Path) -> Result<Option<FileAnalysis>> {
-    let content = fs::read_to_string(path).context("Failed to read file")?;
+    /// Output file for results
+    #[arg(short, long)]
+    pub output: Option<String>,
 
-    // Skip non-contract files
-    if !content.contains("#[ink::contract]") && !content.contains("mod ") {
-        return Ok(None);
-    }
+    /// Analyzer config file (defaults to analyze.toml next to the path or in the current directory)
+    #[arg(long)]
+    pub config: Option<PathBuf>,
 
-    let lines_of_code = content
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .count();
-
-    let functions = extract_functions(&content);
-    let imports = extract_imports(&content);
-    let traits = extract_traits(&content);
-    let structs = extract_structs(&content);
-
-    Ok(Some(FileAnalysis {
-        path: path.to_string_lossy().to_string(),
-        lines_of_code,
-        functions,
-        imports,
-        traits,
-        structs,
-    }))
-}
+    /// Rhai rule plugin(s) to run in addition to those in analyze.toml
+    #[arg(long)]
+    pub plugin: Vec<PathBuf>,
 
-fn extract_functions(content: &str) -> Vec<FunctionInfo> {
-    let mut functions = Vec::new();
-    let lines: Vec<&str> = content.lines().collect();
-
-    for (i, line) in lines.iter().enumerate() {
-        let trimmed = line.trim();
-
-        // Look for function definitions
-        if trimmed.starts_with("pub fn ")
-            || trimmed.starts_with("fn ")
-            || trimmed.starts_with("pub(crate) fn ")
-        {
-            let name = extract_function_name(trimmed);
-            let visibility = if trimmed.starts_with("pub ") {
-                "public"
-            } else {
-                "private"
-            }
-            .to_string();
+    /// Baseline of accepted findings; only new findings are reported and fail the run
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
 
-            // Check if payable (look for #[ink(payable)] in previous lines)
-            let is_payable = i > 0 && lines[i - 1].contains("#[ink(payable)]");
+    /// Write the current findings to the baseline file instead of comparing
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
 
-            // Count lines in function body
-            let func_lines = count_function_lines(&lines, i);
+    /// Only report findings introduced or resolved since this git revision
+    #[arg(long, value_name = "GIT_REF", conflicts_with = "update_baseline")]
+    pub diff: Option<String>,
+}
 
-            // Calculate cyclomatic complexity
-            let complexity = calculate_function_complexity(&lines, i);
+pub async fn execute(args: AnalyzeArgs) -> Result<()> {
+    let path = PathBuf::from(&args.path);
 
-            functions.push(FunctionInfo {
-                name,
-                visibility,
-                is_payable,
-                lines: func_lines,
-                complexity,
-            });
-        }
+    if !path.exists() {
+        anyhow::bail!("Path does not exist: {}", args.path);
     }
 
-    functions
-}
+    // Keep machine-readable output clean when it goes to stdout
+    let progress = args.format == "text" || args.output.is_some();
 
-fn extract_function_name(line: &str) -> String {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    for (i, part) in parts.iter().enumerate() {
-        if *part == "fn" && i + 1 < parts.len() {
-            let name = parts[i + 1];
-            return name.split('(').next().unwrap_or(name).to_string();
+    let mut vuln_db = VulnDb::load();
+    if args.update_db {
+        match vuln_db.update(&args.db_index).await? {
+            Some(updated) => {
+                if progress {
+                    println!(
+                        "{} Vulnerability database updated to v{} ({})",
+                        "✓".green(),
+                        updated.version,
+                        updated.updated
+                    );
+                }
+                vuln_db = updated;
+            }
+            None if progress => println!(
+                "{} Vulnerability database v{} is up to date",
+                "✓".green(),
+                vuln_db.version
+            ),
+            None => {}
         }
     }
-    "unknown".to_string()
-}
-
-fn count_function_lines(lines: &[&str], start_idx: usize) -> usize {
-    let mut count = 0;
-    let mut brace_count = 0;
-    let mut started = false;
-
-    for line in lines.iter().skip(start_idx) {
-        if line.contains('{') {
-            brace_count += line.matches('{').count() as i32;
-            started = true;
-        }
-        if line.contains('}') {
-            brace_count -= line.matches('}').count() as i32;
-        }
 
-        if started {
-            count += 1;
-        }
-
-        if started && brace_count == 0 {
-            break;
+    if progress {
+        println!("{}", "Analyzing contracts...".cyan().bold());
+        if args.security {
+            println!(
+                "  {} Vulnerability database v{} ({})",
+                "→".cyan(),
+                vuln_db.version,
+                vuln_db.updated
+            );
         }
+        println!();
     }
 
-    count
-}
-
-fn calculate_function_complexity(lines: &[&str], start_idx: usize) -> u32 {
-    let mut complexity = 1; // Base complexity
-    let mut in_function = false;
-    let mut brace_count = 0;
-
-    for line in lines.iter().skip(start_idx) {
-        let trimmed = line.trim();
+    let mut config = match &args.config {
+        Some(config_path) => AnalyzerConfig::load(config_path)?,
+        None => AnalyzerConfig::discover(&path)?,
+    };
+    config.plugins.extend(args.plugin.iter().cloned());
+
+    let options = AnalyzeOptions {
+        security: args.security,
+        gas: args.gas,
+        maintainability: args.maintainability,
+        migrate: args.migrate,
+        call_graph: args.call_graph,
+        access_control: args.access_control,
+        config,
+        cargo_profile: args.cargo_profile.clone(),
+        storage: args.storage,
+        metadata: args.metadata.clone(),
+        deposit_rates: DepositRates {
+            per_byte: args.deposit_per_byte,
+            per_item: args.deposit_per_item,
+        },
+        vuln_db,
+    };
+    let mut report = analyzer::analyze_path(&path, &options)?;
 
-        if trimmed.contains('{') {
-            brace_count += trimmed.matches('{').count() as i32;
-            in_function = true;
-        }
-        if trimmed.contains('}') {
-            brace_count -= trimmed.matches('}').count() as i32;
-        }
+    if let Some(git_ref) = &args.diff {
+        let base = analyzer::diff::analyze_ref(&path, git_ref, &options)?;
+        analyzer::diff::compare(&mut report, base);
+    }
 
-        if in_function {
-            // Count decision points
-            if trimmed.contains("if ")
-                || trimmed.contains("else if ")
-                || trimmed.contains("match ")
-                || trimmed.contains("while ")
-                || trimmed.contains("for ")
-                || trimmed.contains("loop ")
-                || trimmed.contains("&&")
-                || trimmed.contains("||")
-            {
-                complexity += 1;
-            }
+    if args.profile {
+        profile_gas(&mut report, &args).await?;
+    }
 
-            // Match arms
-            if trimmed.contains("=>") {
-                complexity += 1;
-            }
+    if let Some(baseline_path) = &args.baseline {
+        if args.update_baseline {
+            let baseline = Baseline::from_report(&report);
+            baseline.save(baseline_path)?;
+            println!(
+                "{} Baseline with {} finding(s) written to {}",
+                "✓".green(),
+                baseline.findings.len(),
+                baseline_path.display()
+            );
+            return Ok(());
         }
 
-        if in_function && brace_count == 0 {
-            break;
+        let baseline = Baseline::load(baseline_path)?;
+        let known = baseline.filter_report(&mut report);
+        if args.format == "text" {
+            println!(
+                "{} {} known finding(s) hidden by baseline {}",
+                "→".cyan(),
+                known,
+                baseline_path.display()
+            );
+            println!();
         }
     }
 
-    complexity
-}
-
-fn extract_imports(content: &str) -> Vec<String> {
-    content
-        .lines()
-        .filter(|line| line.trim().starts_with("use "))
-        .map(|line| line.trim().to_string())
-        .collect()
-}
-
-fn extract_traits(content: &str) -> Vec<String> {
-    content
-        .lines()
-        .filter(|line| line.trim().starts_with("pub trait ") || line.trim().starts_with("trait "))
-        .map(|line| {
-            line.trim()
-                .replace("pub trait ", "")
-                .replace("trait ", "")
-                .split_whitespace()
-                .next()
-                .unwrap_or("")
-                .to_string()
-        })
-        .collect()
-}
-
-fn extract_structs(content: &str) -> Vec<String> {
-    content
-        .lines()
-        .filter(|line| line.trim().starts_with("pub struct ") || line.trim().starts_with("struct "))
-        .map(|line| {
-            line.trim()
-                .replace("pub struct ", "")
-                .replace("struct ", "")
-                .split_whitespace()
-                .next()
-                .unwrap_or("")
-                .to_string()
-        })
-        .collect()
-}
-
-fn analyze_security(path: &Path, analysis: &FileAnalysis) -> Result<Vec<SecurityIssue>> {
-    let mut issues = Vec::new();
-    let content = fs::read_to_string(path)?;
-
-    // Check for common security issues
-
-    // 1. Unchecked arithmetic operations
-    if (content.contains(" + ") || content.contains(" - ") || content.contains(" * "))
-        && !content.contains("checked_add")
-        && !content.contains("checked_sub")
-        && !content.contains("checked_mul")
-    {
-        issues.push(SecurityIssue {
-            severity: "medium".to_string(),
-            category: "Arithmetic".to_string(),
-            description: "Potential integer overflow/underflow".to_string(),
-            file: path.to_string_lossy().to_string(),
-            line: None,
-            recommendation: "Use checked arithmetic operations (checked_add, checked_sub, etc.)"
-                .to_string(),
-        });
-    }
-
-    // 2. Missing access control on payable functions
-    for func in &analysis.functions {
-        if func.is_payable
-            && func.visibility == "public"
-            && !content.contains("only_owner")
-            && !content.contains("require!")
-        {
-            issues.push(SecurityIssue {
-                severity: "high".to_string(),
-                category: "Access Control".to_string(),
-                description: format!("Payable function '{}' lacks access control", func.name),
-                file: path.to_string_lossy().to_string(),
-                line: None,
-                recommendation: "Add access control checks to prevent unauthorized calls"
-                    .to_string(),
-            });
+    if let (Some(graph), Some(graph_path)) = (&report.call_graph, &args.graph_output) {
+        fs::write(graph_path, render_graph(graph, &args.graph_format))
+            .with_context(|| format!("Failed to write call graph: {}", graph_path.display()))?;
+        if args.format == "text" {
+            println!(
+                "{} Call graph written to {}",
+                "✓".green(),
+                graph_path.display()
+            );
+            println!();
         }
     }
 
-    // 3. Unsafe unwrap usage
-    if content.contains(".unwrap()") {
-        issues.push(SecurityIssue {
-            severity: "low".to_string(),
-            category: "Error Handling".to_string(),
-            description: "Use of unsafe unwrap() that could panic".to_string(),
-            file: path.to_string_lossy().to_string(),
-            line: None,
-            recommendation: "Replace unwrap() with proper error handling using ? or expect()"
-                .to_string(),
-        });
+    match args.format.as_str() {
+        "json" => output_json(&report, args.output.as_deref())?,
+        "sarif" => output_json(&analyzer::sarif::to_sarif(&report), args.output.as_deref())?,
+        "html" => output_html(&report, args.output.as_deref())?,
+        "text" | _ => output_text(&report, &args)?,
     }
 
-    // 4. Missing event emissions
-    if content.contains("#[ink(message)]") && !content.contains("Self::env().emit_event") {
-        issues.push(SecurityIssue {
-            severity: "low".to_string(),
-            category: "Transparency".to_string(),
-            description: "State-changing functions should emit events".to_string(),
-            file: path.to_string_lossy().to_string(),
-            line: None,
-            recommendation: "Emit events for important state changes for transparency".to_string(),
-        });
+    let new_findings = report.finding_count();
+    if args.baseline.is_some() && new_findings > 0 {
+        anyhow::bail!("{} new finding(s) not in baseline", new_findings);
+    }
+    if let Some(diff) = report.diff.as_ref().filter(|_| new_findings > 0) {
+        anyhow::bail!("{} finding(s) introduced since {}", new_findings, diff.base);
     }
 
-    Ok(issues)
+    check_gates(&report, &args)
 }
 
-fn analyze_gas(path: &Path, analysis: &FileAnalysis) -> Result<Vec<GasOptimization>> {
-    let mut optimizations = Vec::new();
-    let content = fs::read_to_string(path)?;
-
-    // 1. String usage (expensive in storage)
-    if content.contains("String") && content.contains("#[ink(storage)]") {
-        optimizations.push(GasOptimization {
-            impact: "high".to_string(),
-            description: "String type in storage is expensive".to_string(),
-            file: path.to_string_lossy().to_string(),
-            line: None,
-            suggestion: "Consider using Vec<u8> or bounded types for storage".to_string(),
-        });
+/// Fail the run when `--fail-on` or `--max-complexity` thresholds are exceeded
+fn check_gates(report: &AnalysisReport, args: &AnalyzeArgs) -> Result<()> {
+    let mut failures = Vec::new();
+
+    if let Some(threshold) = &args.fail_on {
+        let count = report.count_at_or_above(threshold);
+        if count > 0 {
+            failures.push(format!(
+                "{} finding(s) at or above '{}' severity",
+                count, threshold
+            ));
+        }
     }
 
-    // 2. Large loop iterations
-    if content.contains("for ") {
-        optimizations.push(GasOptimization {
-            impact: "medium".to_string(),
-            description: "Loop iterations can be gas-intensive".to_string(),
-            file: path.to_string_lossy().to_string(),
-            line: None,
-            suggestion: "Limit loop iterations or use pagination for large datasets".to_string(),
-        });
+    if let Some(max) = args.max_complexity {
+        let complex = report.functions_over_complexity(max);
+        if !complex.is_empty() {
+            let names: Vec<String> = complex
+                .iter()
+                .map(|(file, func)| {
+                    format!(
+                        "{} ({}, {})",
+                        func.name,
+                        location(file, Some(func.line)),
+                        func.complexity
+                    )
+                })
+                .collect();
+            failures.push(format!(
+                "{} function(s) exceed complexity {}: {}",
+                complex.len(),
+                max,
+                names.join(", ")
+            ));
+        }
     }
 
-    // 3. Inefficient data structures
-    if content.contains("Vec<") && content.contains("#[ink(storage)]") {
-        optimizations.push(GasOptimization {
-            impact: "medium".to_string(),
-            description: "Vec in storage requires careful management".to_string(),
-            file: path.to_string_lossy().to_string(),
-            line: None,
-            suggestion: "Consider using Mapping for key-value storage or BTreeMap for ordered data"
-                .to_string(),
-        });
+    if !failures.is_empty() {
+        anyhow::bail!("Quality gate failed: {}", failures.join("; "));
     }
 
-    // 4. High complexity functions
-    for func in &analysis.functions {
-        if func.complexity > 10 {
-            optimizations.push(GasOptimization {
-                impact: "medium".to_string(),
-                description: format!(
-                    "Function '{}' has high complexity ({})",
-                    func.name, func.complexity
-                ),
-                file: path.to_string_lossy().to_string(),
-                line: None,
-                suggestion: "Consider breaking down into smaller functions to reduce gas costs"
-                    .to_string(),
-            });
-        }
-    }
-
-    Ok(optimizations)
+    Ok(())
 }
 
-fn calculate_complexity_metrics(files: &[FileAnalysis]) -> ComplexityMetrics {
-    let mut cyclomatic = HashMap::new();
-    let mut cognitive = HashMap::new();
+/// Measure the deployed contract and merge the results into the gas report
+async fn profile_gas(report: &mut AnalysisReport, args: &AnalyzeArgs) -> Result<()> {
+    let address = args.address.as_deref().unwrap_or_default();
+
+    let metadata_path = match args.metadata.first() {
+        Some(path) => path.clone(),
+        None => match report.contracts.as_slice() {
+            [contract] => {
+                find_metadata(Path::new(&contract.path), &contract.name).with_context(|| {
+                    format!(
+                        "No metadata found for '{}'. Build the contract or pass {}",
+                        contract.name,
+                        "--metadata <path>".yellow()
+                    )
+                })?
+            }
+            _ => anyhow::bail!(
+                "Pass {} to select the contract deployed at {}",
+                "--metadata <path>".yellow(),
+                address
+            ),
+        },
+    };
 
-    for file in files {
-        for func in &file.functions {
-            cyclomatic.insert(func.name.clone(), func.complexity);
-            // Cognitive complexity is similar but weights nested structures higher
-            cognitive.insert(func.name.clone(), func.complexity);
-        }
-    }
+    // The profiled contract is the crate owning the metadata, or the only one analyzed
+    let contract = report
+        .contracts
+        .iter()
+        .find(|c| metadata_path.starts_with(&c.path))
+        .or(report
+            .contracts
+            .first()
+            .filter(|_| report.contracts.len() == 1))
+        .map(|c| c.name.clone());
+    let contract_files: Vec<String> = report
+        .files
+        .iter()
+        .filter(|f| f.contract.is_some() && f.contract == contract)
+        .map(|f| f.path.clone())
+        .collect();
+
+    let metadata_json = fs::read_to_string(&metadata_path)
+        .with_context(|| format!("Failed to read metadata: {}", metadata_path.display()))?;
+    let metadata = crate::contract::metadata::parse_metadata(&metadata_json)?;
 
-    // Calculate maintainability index (simplified)
-    let total_complexity: u32 = cyclomatic.values().sum();
-    let total_functions = cyclomatic.len() as f64;
-    let avg_complexity = if total_functions > 0.0 {
-        total_complexity as f64 / total_functions
+    let bench_file = if args.bench_file.exists() {
+        BenchFile::load(&args.bench_file)?
     } else {
-        0.0
+        BenchFile::default()
     };
+    let caller = bench_file.from.as_deref().unwrap_or("alice");
+    let signer = glin_client::get_dev_account(caller)?;
+    let origin = signer.public_key().to_account_id();
 
-    // Maintainability index (simplified formula)
-    // 171 - 5.2 * ln(Halstead Volume) - 0.23 * (Cyclomatic Complexity) - 16.2 * ln(Lines of Code)
-    // Simplified here to: 100 - (avg_complexity * 5)
-    let maintainability = (100.0 - (avg_complexity * 5.0)).max(0.0).min(100.0);
+    let network_config = crate::config::load_network(&args.network)?;
 
-    ComplexityMetrics {
-        cyclomatic_complexity: cyclomatic,
-        cognitive_complexity: cognitive,
-        maintainability_index: maintainability,
+    if args.format == "text" {
+        println!(
+            "{} Profiling {} on {}...",
+            "→".cyan(),
+            address,
+            args.network
+        );
+        println!();
     }
+
+    let profiles = analyzer::profile::profile_messages(
+        &network_config.rpc,
+        &origin,
+        address,
+        &metadata,
+        &bench_file,
+    )
+    .await?;
+    analyzer::profile::merge(report, profiles, &contract_files);
+
+    Ok(())
 }
 
 fn output_text(report: &AnalysisReport, args: &AnalyzeArgs) -> Result<()> {
@@ -550,6 +408,58 @@ fn output_text(report: &AnalysisReport, args: &AnalyzeArgs) -> Result<()> {
     );
     println!();
 
+    // Changes relative to --diff
+    if let Some(diff) = &report.diff {
+        println!(
+            "{}",
+            format!("=== Changes since {} ===", diff.base)
+                .green()
+                .bold()
+        );
+        println!("Introduced:          {}", report.finding_count());
+        println!("Resolved:            {}", diff.resolved.len());
+        println!("Unchanged:           {}", diff.unchanged);
+        for entry in &diff.resolved {
+            println!(
+                "  {} [{}] {} {}",
+                "✓".green(),
+                entry.rule,
+                entry.message,
+                location(&entry.file, None).dimmed()
+            );
+        }
+        println!();
+    }
+
+    // Workspace summary
+    if report.contracts.len() > 1 {
+        println!("{}", "=== Workspace Summary ===".green().bold());
+        println!(
+            "  {:<24} {:>6} {:>10} {:>8} {:>6} {:>7}",
+            "Contract", "Files", "Functions", "Issues", "Gas", "Score"
+        );
+        for contract in &report.contracts {
+            let score = format!("{:.0}", contract.score);
+            let score = if contract.score >= 80.0 {
+                score.green()
+            } else if contract.score >= 50.0 {
+                score.yellow()
+            } else {
+                score.red()
+            };
+            println!(
+                "  {:<24} {:>6} {:>10} {:>8} {:>6} {:>7}",
+                contract.name,
+                contract.files,
+                contract.functions,
+                contract.security_issues,
+                contract.gas_optimizations,
+                score
+            );
+        }
+        println!();
+    }
+
     // Security issues
     if args.security && !report.security_issues.is_empty() {
         println!("{}", "=== Security Issues ===".red().bold());
@@ -567,13 +477,47 @@ fn output_text(report: &AnalysisReport, args: &AnalyzeArgs) -> Result<()> {
                 issue.severity.color(severity_color).bold(),
                 issue.description
             );
-            println!("    Category: {}", issue.category);
-            println!("    File: {}", issue.file);
+            println!("    Category: {} ({})", issue.category, issue.rule);
+            println!("    File: {}", location(&issue.file, issue.line));
+            println!("    {}: {}", "Fix".green(), issue.recommendation);
+            println!();
+        }
+    }
+
+    // Maintainability issues
+    if args.maintainability && !report.maintainability_issues.is_empty() {
+        println!("{}", "=== Maintainability Issues ===".cyan().bold());
+        for issue in &report.maintainability_issues {
+            println!(
+                "  {} [{}] {}",
+                "▸".bold(),
+                issue.severity.bold(),
+                issue.description
+            );
+            println!("    Rule: {}", issue.rule);
+            println!("    File: {}", location(&issue.file, issue.line));
             println!("    {}: {}", "Fix".green(), issue.recommendation);
             println!();
         }
     }
 
+    // Migration checklist
+    if args.migrate {
+        println!("{}", "=== ink! 5 Migration Checklist ===".magenta().bold());
+        if report.migration_issues.is_empty() {
+            println!("  {} No ink! 4.x patterns found", "✓".green());
+        }
+        for issue in &report.migration_issues {
+            println!(
+                "  [ ] {} {}",
+                location(&issue.file, issue.line).bold(),
+                issue.description
+            );
+            println!("      {} {}", "→".cyan(), issue.recommendation);
+        }
+        println!();
+    }
+
     // Gas optimizations
     if args.gas && !report.gas_optimizations.is_empty() {
         println!(
@@ -594,12 +538,140 @@ fn output_text(report: &AnalysisReport, args: &AnalyzeArgs) -> Result<()> {
                 opt.impact.color(impact_color).bold(),
                 opt.description
             );
-            println!("    File: {}", opt.file);
+            println!("    Rule: {}", opt.rule);
+            println!("    File: {}", location(&opt.file, opt.line));
+            if let Some(measured) = &opt.measured {
+                println!(
+                    "    Measured: refTime={} proofSize={} deposit={}",
+                    measured.ref_time, measured.proof_size, measured.storage_deposit
+                );
+            }
             println!("    {}: {}", "Suggestion".green(), opt.suggestion);
             println!();
         }
     }
 
+    // Measured gas profile
+    if !report.gas_profile.is_empty() {
+        println!("{}", "=== Gas Profile ===".yellow().bold());
+        println!(
+            "  {:<32} {:>16} {:>12} {:>20}",
+            "Message", "refTime", "proofSize", "Storage deposit"
+        );
+        for profile in &report.gas_profile {
+            let message = format!("{}({})", profile.message, profile.args.join(", "));
+            let line = format!(
+                "  {:<32} {:>16} {:>12} {:>20}",
+                message, profile.ref_time, profile.proof_size, profile.storage_deposit
+            );
+            if profile.reverted {
+                println!("{} {}", line.yellow(), "(reverted)".yellow());
+            } else {
+                println!("{}", line);
+            }
+        }
+        println!();
+    }
+
+    // Storage layout
+    if args.storage {
+        println!("{}", "=== Storage Layout ===".cyan().bold());
+        if report.storage_layouts.is_empty() {
+            println!(
+                "  {} No metadata found. Build the contract or pass {}",
+                "⚠".yellow(),
+                "--metadata <path>".yellow()
+            );
+        }
+        for layout in &report.storage_layouts {
+            println!("\n  {} ({})", layout.contract.bold(), layout.metadata);
+            println!(
+                "    {:<24} {:<12} {:<20} {:<7} {:>10} {:>22}",
+                "Field", "Key", "Type", "Kind", "Max bytes", "Deposit/entry"
+            );
+            for cell in &layout.fields {
+                let line = format!(
+                    "    {:<24} {:<12} {:<20} {:<7} {:>10} {:>22}",
+                    cell.name,
+                    cell.key,
+                    cell.ty,
+                    cell.kind,
+                    cell.max_bytes
+                        .map(|b| b.to_string())
+                        .unwrap_or_else(|| "unbounded".to_string()),
+                    cell.deposit_per_entry
+                        .map(|d| d.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                );
+                if cell.is_unbounded() {
+                    println!("{} {}", line.yellow(), "⚠".yellow());
+                } else {
+                    println!("{}", line);
+                }
+            }
+
+            let unbounded = layout.fields.iter().filter(|c| c.is_unbounded()).count();
+            if unbounded > 0 {
+                println!(
+                    "    {} {} unbounded field(s) can grow storage deposits without limit; \
+                     prefer Mapping or bounded types",
+                    "⚠".yellow(),
+                    unbounded
+                );
+            }
+        }
+        println!();
+    }
+
+    // Access control matrix
+    if args.access_control {
+        println!("{}", "=== Access Control Matrix ===".cyan().bold());
+        println!(
+            "  {:<28} {:<36} {:<8} {:<8}",
+            "Message", "Required role", "Payable", "Mutates"
+        );
+        for entry in &report.access_control {
+            let line = format!(
+                "  {:<28} {:<36} {:<8} {:<8}",
+                entry.message,
+                entry.roles.join(", "),
+                if entry.payable { "yes" } else { "no" },
+                if entry.mutates { "yes" } else { "no" },
+            );
+            if entry.unprotected_privileged {
+                println!(
+                    "{} {} {}",
+                    line.yellow(),
+                    "⚠".yellow(),
+                    location(&entry.file, Some(entry.line))
+                );
+            } else {
+                println!("{}", line);
+            }
+        }
+
+        let flagged = report
+            .access_control
+            .iter()
+            .filter(|e| e.unprotected_privileged)
+            .count();
+        if flagged > 0 {
+            println!(
+                "\n  {} {} privileged-looking message(s) callable by anyone; review them",
+                "⚠".yellow(),
+                flagged
+            );
+        }
+        println!();
+    }
+
+    // Call graph, unless written to a file
+    if let (Some(graph), None) = (&report.call_graph, &args.graph_output) {
+        println!("{}", "=== Call Graph ===".cyan().bold());
+        print!("{}", render_graph(graph, &args.graph_format));
+        println!();
+    }
+
     // Detailed file analysis
     if args.detailed {
         println!("{}", "=== Detailed Analysis ===".cyan().bold());
@@ -622,9 +694,11 @@ fn output_text(report: &AnalysisReport, args: &AnalyzeArgs) -> Result<()> {
                     };
 
                     println!(
-                        "      • {} ({}, {} lines, complexity: {})",
+                        "      • {} ({} {}, line {}, {} lines, complexity: {})",
                         func.name.bold(),
                         func.visibility,
+                        func.kind,
+                        func.line,
                         func.lines,
                         func.complexity.to_string().color(complexity_color)
                     );
@@ -639,7 +713,35 @@ fn output_text(report: &AnalysisReport, args: &AnalyzeArgs) -> Result<()> {
     Ok(())
 }
 
-fn output_json(report: &AnalysisReport, output_file: Option<&str>) -> Result<()> {
+fn render_graph(graph: &CallGraph, format: &str) -> String {
+    match format {
+        "dot" => graph.to_dot(),
+        _ => graph.to_mermaid(),
+    }
+}
+
+fn location(file: &str, line: Option<usize>) -> String {
+    match line {
+        Some(line) => format!("{}:{}", file, line),
+        None => file.to_string(),
+    }
+}
+
+fn output_html(report: &AnalysisReport, output_file: Option<&str>) -> Result<()> {
+    let html = analyzer::html::to_html(report)?;
+
+    match output_file {
+        Some(file_path) => {
+            fs::write(file_path, html)?;
+            println!("{} Report saved to {}", "✓".green(), file_path);
+        }
+        None => println!("{}", html),
+    }
+
+    Ok(())
+}
+
+fn output_json<T: serde::Serialize>(report: &T, output_file: Option<&str>) -> Result<()> {
     let json = serde_json::to_string_pretty(report)?;
 
     if let Some(file_path) = output_file {
@@ -126,7 +126,7 @@ fn parse_account_id(address: &str) -> anyhow::Result<AccountId32> {
 }
 
 /// Format balance from smallest unit to GLIN with decimals
-fn format_balance(amount: u128) -> String {
+pub(crate) fn format_balance(amount: u128) -> String {
     const DECIMALS: u32 = 18;
     let divisor = 10u128.pow(DECIMALS);
 
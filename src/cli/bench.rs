@@ -0,0 +1,175 @@
+use crate::testing::bench::{self, BenchFile, BenchReport};
+use anyhow::Context;
+use clap::Parser;
+use colored::Colorize;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+pub struct BenchArgs {
+    /// Contract address
+    pub contract: String,
+
+    /// Path to contract metadata (ABI) JSON file
+    #[arg(short, long)]
+    pub metadata: Option<String>,
+
+    /// Bench file with argument sets per message
+    #[arg(short, long, default_value = "bench.yaml")]
+    pub bench_file: PathBuf,
+
+    /// Number of dry-runs per message (overrides the bench file)
+    #[arg(short, long)]
+    pub iterations: Option<u32>,
+
+    /// Network to benchmark on
+    #[arg(short, long, default_value = "testnet")]
+    pub network: String,
+
+    /// Report format (markdown, json)
+    #[arg(short, long, default_value = "markdown")]
+    pub format: String,
+
+    /// Write the report to a file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Baseline report (JSON) to compare against
+    #[arg(long, default_value = ".glin-forge/bench-baseline.json")]
+    pub baseline: PathBuf,
+
+    /// Store this run as the new baseline
+    #[arg(long)]
+    pub save_baseline: bool,
+}
+
+pub async fn execute(args: BenchArgs) -> anyhow::Result<()> {
+    println!("{}", "Benchmarking contract messages...".cyan().bold());
+
+    if args.format != "markdown" && args.format != "json" {
+        anyhow::bail!("Unsupported format: {} (use markdown or json)", args.format);
+    }
+
+    // Load metadata
+    let metadata_path = match args.metadata {
+        Some(path) => path,
+        None => find_metadata()?,
+    };
+    let metadata_json = std::fs::read_to_string(&metadata_path)
+        .with_context(|| format!("Failed to read metadata: {}", metadata_path))?;
+    let metadata = crate::contract::metadata::parse_metadata(&metadata_json)?;
+
+    let bench_file = if args.bench_file.exists() {
+        BenchFile::load(&args.bench_file)?
+    } else {
+        println!(
+            "{} No bench file at {}, benchmarking messages without arguments",
+            "⚠".yellow(),
+            args.bench_file.display()
+        );
+        BenchFile::default()
+    };
+
+    let iterations = args.iterations.or(bench_file.iterations).unwrap_or(10);
+    if iterations == 0 {
+        anyhow::bail!("Iterations must be greater than zero");
+    }
+
+    let cases = bench::plan_cases(&metadata, &bench_file)?;
+    if cases.is_empty() {
+        anyhow::bail!("Nothing to benchmark: add message argument sets to the bench file");
+    }
+
+    let caller = bench_file.from.as_deref().unwrap_or("alice");
+    let signer = glin_client::get_dev_account(caller)?;
+    let origin = signer.public_key().to_account_id();
+
+    let network_config = crate::config::load_network(&args.network)?;
+
+    println!("\n{}", "Benchmark details:".bold());
+    println!("  {} {}", "Contract:".cyan(), args.contract);
+    println!("  {} {}", "Metadata:".cyan(), metadata_path);
+    println!("  {} {}", "Network:".cyan(), args.network);
+    println!("  {} {}", "Cases:".cyan(), cases.len());
+    println!("  {} {}", "Iterations:".cyan(), iterations);
+    println!();
+
+    let mut results = Vec::with_capacity(cases.len());
+    for case in &cases {
+        let result = bench::run_case(
+            &network_config.rpc,
+            &origin,
+            &args.contract,
+            &metadata,
+            case,
+            iterations,
+        )
+        .await?;
+
+        println!(
+            "{} {} refTime={} proofSize={}",
+            if result.reverted {
+                "⚠".yellow()
+            } else {
+                "✓".green()
+            },
+            case.key(),
+            result.ref_time.mean,
+            result.proof_size.mean
+        );
+        results.push(result);
+    }
+
+    let report = BenchReport {
+        contract: args.contract.clone(),
+        network: args.network.clone(),
+        results,
+    };
+
+    let baseline = if args.baseline.exists() && !args.save_baseline {
+        Some(BenchReport::load(&args.baseline)?)
+    } else {
+        None
+    };
+
+    let rendered = if args.format == "json" {
+        serde_json::to_string_pretty(&report)?
+    } else {
+        report.to_markdown(baseline.as_ref())
+    };
+
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, &rendered)
+                .with_context(|| format!("Failed to write report: {}", path.display()))?;
+            println!("\n{} Report written to {}", "✓".green(), path.display());
+        }
+        None => println!("\n{}", rendered),
+    }
+
+    if args.save_baseline {
+        if let Some(parent) = args.baseline.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&args.baseline, serde_json::to_string_pretty(&report)?)?;
+        println!(
+            "{} Baseline saved to {}",
+            "✓".green(),
+            args.baseline.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn find_metadata() -> anyhow::Result<String> {
+    for path in ["target/ink/metadata.json", "contract.json", "abi.json"] {
+        if std::path::Path::new(path).exists() {
+            return Ok(path.to_string());
+        }
+    }
+
+    anyhow::bail!(
+        "Could not find contract metadata. Specify with {}",
+        "--metadata <path>".yellow()
+    )
+}
@@ -3,7 +3,7 @@ use colored::Colorize;
 
 #[derive(Parser)]
 pub struct CallArgs {
-    /// Contract address
+    /// Contract address, or name in the deployments registry
     pub address: String,
 
     /// Method name to call
@@ -55,11 +55,13 @@ pub async fn execute(args: CallArgs) -> anyhow::Result<()> {
         println!("  {} {:?}", "Arguments:".cyan(), args.args);
     }
 
+    let (address, _) = crate::config::deployments::resolve(&args.network, &args.address)?;
+
     // Load metadata
     let metadata_path = if let Some(path) = args.metadata {
         path
     } else {
-        find_metadata_for_contract(&args.address)?
+        find_metadata_for_contract(&args.network, &args.address)?
     };
 
     println!("  {} {}", "Metadata:".cyan(), metadata_path);
@@ -124,7 +126,7 @@ pub async fn execute(args: CallArgs) -> anyhow::Result<()> {
     // Execute transaction
     let result = crate::contract::call_contract(
         &client,
-        &args.address,
+        &address,
         &metadata,
         &args.method,
         args.args.clone(),
@@ -254,7 +256,11 @@ fn format_number(n: u64) -> String {
         .join(",")
 }
 
-fn find_metadata_for_contract(_address: &str) -> anyhow::Result<String> {
+fn find_metadata_for_contract(network: &str, contract: &str) -> anyhow::Result<String> {
+    if let Some(artifact) = crate::cli::native_console::deployed_artifact(network, contract) {
+        return Ok(artifact.path.to_string_lossy().into_owned());
+    }
+
     let possible_paths = vec!["target/ink/metadata.json", "contract.json", "abi.json"];
 
     for path in possible_paths {
@@ -0,0 +1,423 @@
+// Shell completions
+//
+// `glin-forge completions <shell>` prints a script for bash, zsh or fish that
+// hands the command line to the hidden `glin-forge __complete` on every Tab.
+// Subcommands, flags and their possible values come from the clap definitions;
+// network names, the contracts of the deployments registry and the messages of
+// their metadata are read from the project at completion time, so they follow
+// the configuration and deployments as they change. Words with nothing to
+// offer fall back to file names.
+
+use anyhow::Result;
+use clap::{Arg, Command, CommandFactory, Parser};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::cli::native_console::{self, Artifact};
+
+/// Commands whose `address` argument takes contracts of the registry by name
+const CONTRACT_COMMANDS: &[&str] = &["call", "query", "watch", "index"];
+
+#[derive(Parser)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_parser = ["bash", "zsh", "fish"])]
+    pub shell: String,
+}
+
+#[derive(Parser)]
+pub struct CompleteArgs {
+    /// Words of the command line after the program name, ending with the one completed
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub words: Vec<String>,
+}
+
+pub async fn execute(args: CompletionsArgs) -> Result<()> {
+    let script = match args.shell.as_str() {
+        "bash" => BASH,
+        "zsh" => ZSH,
+        _ => FISH,
+    };
+    print!("{}", script);
+    Ok(())
+}
+
+/// Print the candidates for the last word, one per line
+pub async fn complete(args: CompleteArgs) -> Result<()> {
+    let mut command = crate::Cli::command();
+    command.build();
+    for candidate in candidates(&command, &args.words, &Project) {
+        println!("{}", candidate);
+    }
+    Ok(())
+}
+
+const BASH: &str = r#"_glin_forge() {
+    local IFS=$'\n'
+    COMPREPLY=($(glin-forge __complete -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null))
+}
+complete -o default -o nospace -F _glin_forge glin-forge
+"#;
+
+const ZSH: &str = r#"#compdef glin-forge
+
+_glin_forge() {
+    local -a candidates
+    candidates=("${(@f)$(glin-forge __complete -- "${(@)words[2,CURRENT]}" 2>/dev/null)}")
+    if [[ -n "${candidates[1]}" ]]; then
+        compadd -S '' -a candidates
+    else
+        _files
+    fi
+}
+
+if [ "$funcstack[1]" = "_glin_forge" ]; then
+    _glin_forge "$@"
+else
+    compdef _glin_forge glin-forge
+fi
+"#;
+
+const FISH: &str = r#"function __glin_forge_complete
+    set -l words (commandline -opc)[2..-1] (commandline -ct)
+    set -l candidates (glin-forge __complete -- $words 2>/dev/null)
+    if test (count $candidates) -eq 0
+        __fish_complete_path (commandline -ct)
+    else
+        printf '%s\n' $candidates
+    end
+end
+
+complete -c glin-forge -f -a '(__glin_forge_complete)'
+"#;
+
+/// Project state offered for completion
+trait Lookup {
+    fn networks(&self) -> Vec<String>;
+
+    /// Names of the contracts deployed on a network
+    fn contracts(&self, network: &str) -> Vec<String>;
+
+    /// Artifact of a contract, from its metadata file or the registry
+    fn artifact(&self, network: &str, contract: &str, metadata: Option<&str>) -> Option<Artifact>;
+}
+
+/// The project in the current directory
+struct Project;
+
+impl Lookup for Project {
+    fn networks(&self) -> Vec<String> {
+        crate::config::network_names().unwrap_or_default()
+    }
+
+    fn contracts(&self, network: &str) -> Vec<String> {
+        crate::config::deployments::DeploymentRegistry::open_default()
+            .map(|registry| {
+                registry
+                    .deployments(network)
+                    .map(|(name, _)| name.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn artifact(&self, network: &str, contract: &str, metadata: Option<&str>) -> Option<Artifact> {
+        match metadata {
+            Some(path) => {
+                let json = std::fs::read_to_string(path).ok()?;
+                native_console::artifact(Path::new(path), &serde_json::from_str(&json).ok()?)
+            }
+            None => native_console::deployed_artifact(network, contract),
+        }
+    }
+}
+
+/// Where the words before the completed one leave the command line
+struct Position<'a> {
+    command: &'a Command,
+    /// Values of the options given, by argument id
+    values: HashMap<&'a str, String>,
+    positionals: Vec<&'a str>,
+    /// Option still waiting for its value
+    pending: Option<&'a Arg>,
+}
+
+/// Candidates for the last of `words`, those starting with it
+fn candidates(root: &Command, words: &[String], lookup: &dyn Lookup) -> Vec<String> {
+    let Some((current, before)) = words.split_last() else {
+        return Vec::new();
+    };
+    let position = walk(root, before);
+    let command = position.command;
+
+    if let Some(arg) = position.pending {
+        return arg_values(&position, arg, current, lookup);
+    }
+    if let Some((flag, value)) = current
+        .split_once('=')
+        .filter(|_| current.starts_with("--"))
+    {
+        return match option(command, flag) {
+            Some(arg) if arg.get_action().takes_values() => {
+                arg_values(&position, arg, value, lookup)
+                    .into_iter()
+                    .map(|candidate| format!("{}={}", flag, candidate))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+    }
+    if current.starts_with('-') {
+        return command
+            .get_arguments()
+            .filter(|arg| !arg.is_positional() && !arg.is_hide_set())
+            .filter_map(|arg| arg.get_long())
+            .map(|long| format!("--{}", long))
+            .filter(|flag| flag.starts_with(current.as_str()))
+            .collect();
+    }
+    if position.positionals.is_empty() && command.has_subcommands() {
+        return command
+            .get_subcommands()
+            .filter(|subcommand| !subcommand.is_hide_set())
+            .map(|subcommand| subcommand.get_name().to_string())
+            .filter(|name| name.starts_with(current.as_str()))
+            .collect();
+    }
+    let positionals: Vec<&Arg> = command.get_positionals().collect();
+    let arg = positionals.get(position.positionals.len()).or_else(|| {
+        positionals
+            .last()
+            .filter(|arg| arg.get_num_args().is_some_and(|n| n.max_values() > 1))
+    });
+    match arg {
+        Some(arg) => arg_values(&position, arg, current, lookup),
+        None => Vec::new(),
+    }
+}
+
+fn walk<'a>(root: &'a Command, words: &'a [String]) -> Position<'a> {
+    let mut position = Position {
+        command: root,
+        values: HashMap::new(),
+        positionals: Vec::new(),
+        pending: None,
+    };
+    let mut options_done = false;
+    for word in words {
+        if let Some(arg) = position.pending.take() {
+            position.values.insert(arg.get_id().as_str(), word.clone());
+            continue;
+        }
+        if word == "--" {
+            options_done = true;
+            continue;
+        }
+        if !options_done && word.starts_with('-') && word.len() > 1 {
+            let (flag, value) = match word.split_once('=') {
+                Some((flag, value)) => (flag, Some(value)),
+                None => (word.as_str(), None),
+            };
+            let Some(arg) = option(position.command, flag) else {
+                continue;
+            };
+            match value {
+                _ if !arg.get_action().takes_values() => {}
+                Some(value) => {
+                    position
+                        .values
+                        .insert(arg.get_id().as_str(), value.to_string());
+                }
+                // `-n<value>` carries its value
+                None if !flag.starts_with("--") && flag.len() > 2 => {
+                    position
+                        .values
+                        .insert(arg.get_id().as_str(), flag[2..].to_string());
+                }
+                None => position.pending = Some(arg),
+            }
+            continue;
+        }
+        if position.positionals.is_empty() {
+            if let Some(subcommand) = position.command.find_subcommand(word) {
+                position.command = subcommand;
+                position.values.clear();
+                continue;
+            }
+        }
+        position.positionals.push(word);
+    }
+    position
+}
+
+/// Option of a command by `--long` or `-s` (short options may carry their value)
+fn option<'a>(command: &'a Command, flag: &str) -> Option<&'a Arg> {
+    command
+        .get_arguments()
+        .find(|arg| match flag.strip_prefix("--") {
+            Some(long) => arg.get_long() == Some(long),
+            None => {
+                let short = flag.strip_prefix('-').and_then(|s| s.chars().next());
+                short.is_some() && arg.get_short() == short
+            }
+        })
+}
+
+/// Values of an argument starting with `current`
+fn arg_values(position: &Position, arg: &Arg, current: &str, lookup: &dyn Lookup) -> Vec<String> {
+    let command = position.command;
+    let network = || {
+        position.values.get("network").cloned().or_else(|| {
+            command
+                .get_arguments()
+                .find(|arg| arg.get_id() == "network")
+                .and_then(|arg| arg.get_default_values().first())
+                .map(|default| default.to_string_lossy().into_owned())
+        })
+    };
+
+    let (prefix, current) = match arg.get_value_delimiter() {
+        Some(delimiter) => match current.rfind(delimiter) {
+            Some(at) => current.split_at(at + delimiter.len_utf8()),
+            None => ("", current),
+        },
+        None => ("", current),
+    };
+    let values = match (command.get_name(), arg.get_id().as_str()) {
+        (_, "network" | "networks") => lookup.networks(),
+        (name, "address") if CONTRACT_COMMANDS.contains(&name) => match network() {
+            Some(network) => lookup.contracts(&network),
+            None => Vec::new(),
+        },
+        (name @ ("call" | "query"), "method") => {
+            let artifact = match (network(), position.positionals.first()) {
+                (Some(network), Some(contract)) => lookup.artifact(
+                    &network,
+                    contract,
+                    position.values.get("metadata").map(String::as_str),
+                ),
+                _ => None,
+            };
+            artifact
+                .map(|artifact| {
+                    artifact
+                        .messages
+                        .into_iter()
+                        .filter(|message| message.mutates == (name == "call"))
+                        .map(|message| message.label)
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+        _ => arg
+            .get_possible_values()
+            .into_iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect(),
+    };
+    values
+        .into_iter()
+        .filter(|value| value.starts_with(current))
+        .map(|value| format!("{}{}", prefix, value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    struct Fixture;
+
+    impl Lookup for Fixture {
+        fn networks(&self) -> Vec<String> {
+            vec![
+                "local".to_string(),
+                "mainnet".to_string(),
+                "testnet".to_string(),
+            ]
+        }
+
+        fn contracts(&self, network: &str) -> Vec<String> {
+            match network {
+                "testnet" => vec!["flipper".to_string(), "token".to_string()],
+                _ => vec!["dex".to_string()],
+            }
+        }
+
+        fn artifact(&self, _: &str, contract: &str, metadata: Option<&str>) -> Option<Artifact> {
+            let messages = match (contract, metadata) {
+                ("token", None) => vec!["PSP22::transfer", "PSP22::balance_of", "total_supply"],
+                (_, Some(_)) => vec!["flip", "get"],
+                _ => return None,
+            };
+            Some(Artifact {
+                name: contract.to_string(),
+                path: PathBuf::from("artifacts/token.json"),
+                code_hash: None,
+                messages: messages
+                    .into_iter()
+                    .map(|label| native_console::Message {
+                        label: label.to_string(),
+                        args: Vec::new(),
+                        mutates: label.contains("transfer") || label == "flip",
+                        payable: false,
+                    })
+                    .collect(),
+            })
+        }
+    }
+
+    fn complete(line: &str) -> Vec<String> {
+        let mut command = crate::Cli::command();
+        command.build();
+        let mut words: Vec<String> = line.split(' ').map(str::to_string).collect();
+        if line.is_empty() {
+            words = vec![String::new()];
+        }
+        candidates(&command, &words, &Fixture)
+    }
+
+    #[test]
+    fn test_complete_commands_and_flags() {
+        let commands = complete("");
+        assert!(commands.contains(&"deploy".to_string()));
+        assert!(commands.contains(&"completions".to_string()));
+        assert!(!commands
+            .iter()
+            .any(|c| c.starts_with("__") || c == "embedded-run"));
+        assert_eq!(complete("qu"), vec!["query"]);
+        assert_eq!(complete("query --js"), vec!["--json"]);
+        assert!(complete("call --").contains(&"--help".to_string()));
+        assert_eq!(complete("completions "), vec!["bash", "zsh", "fish"]);
+        assert_eq!(complete("run --runtime d"), vec!["deno"]);
+    }
+
+    #[test]
+    fn test_complete_project_values() {
+        assert_eq!(complete("deploy --network t"), vec!["testnet"]);
+        assert_eq!(complete("deploy --network=l"), vec!["--network=local"]);
+        assert_eq!(
+            complete("run script.ts --networks local,t"),
+            vec!["local,testnet"]
+        );
+
+        // Contracts of the network given, or of the command's default one
+        assert_eq!(complete("call "), vec!["flipper", "token"]);
+        assert_eq!(complete("call -n local "), vec!["dex"]);
+        assert_eq!(complete("query --network local d"), vec!["dex"]);
+
+        // Calls offer the messages mutating the contract, queries the others
+        assert_eq!(complete("call token "), vec!["PSP22::transfer"]);
+        assert_eq!(
+            complete("query token "),
+            vec!["PSP22::balance_of", "total_supply"]
+        );
+        assert_eq!(complete("query token PSP22::b"), vec!["PSP22::balance_of"]);
+        assert_eq!(complete("query -m flipper.json 5Grw... g"), vec!["get"]);
+        assert!(complete("query dex ").is_empty());
+        // Further arguments are the message's, not completed
+        assert!(complete("call token PSP22::transfer ").is_empty());
+    }
+}
@@ -2,9 +2,11 @@ use anyhow::{Context, Result};
 use clap::Args;
 use colored::*;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+use crate::config::runtime::Runtime;
+
 #[derive(Debug, Args)]
 pub struct ConsoleArgs {
     /// Network to connect to
@@ -22,24 +24,52 @@ pub struct ConsoleArgs {
     /// Show welcome banner
     #[arg(long, default_value = "true")]
     pub banner: bool,
+
+    /// Run a console line (command or contract expression) instead of reading any, then exit;
+    /// repeatable, native console only
+    #[arg(long, value_name = "EXPR")]
+    pub exec: Vec<String>,
+
+    /// Console to run: the native one, or a JavaScript REPL with @polkadot/api on a runtime
+    #[arg(long, default_value = "native", value_parser = ["native", "node", "bun", "deno"])]
+    pub runtime: String,
 }
 
 pub async fn execute(args: ConsoleArgs) -> Result<()> {
-    if args.banner {
+    if args.banner && args.exec.is_empty() {
         print_banner();
     }
 
-    // Check if Node.js is available
-    check_nodejs()?;
-
     // Load network configuration
-    let network_config = load_network_config(&args.network)?;
+    let rpc = network_rpc(&args.network)?;
+
+    if args.runtime == "native" {
+        if args.exec.is_empty() {
+            println!("{}", "Type '.help' for the commands".dimmed());
+            println!();
+        }
+        return crate::cli::native_console::run(
+            &args.network,
+            &rpc,
+            args.contract.as_deref(),
+            Path::new(&args.artifacts_path),
+            &args.exec,
+        )
+        .await;
+    }
+    if !args.exec.is_empty() {
+        anyhow::bail!("--exec needs the native console (--runtime native)");
+    }
+
+    // Check that the runtime is available
+    let runtime: Runtime = args.runtime.parse()?;
+    check_runtime(runtime)?;
 
     // Create REPL script
-    let repl_script = create_repl_script(&args, &network_config)?;
+    let repl_script = create_repl_script(&args, &rpc)?;
 
-    // Write temporary REPL file
-    let temp_file = std::env::temp_dir().join("glin-forge-console.js");
+    // Write temporary REPL file; .cjs, as Deno reads .js files as ES modules
+    let temp_file = std::env::temp_dir().join("glin-forge-console.cjs");
     fs::write(&temp_file, repl_script).context("Failed to write REPL script")?;
 
     println!(
@@ -48,10 +78,7 @@ pub async fn execute(args: ConsoleArgs) -> Result<()> {
             .cyan()
             .bold()
     );
-    println!(
-        "{}",
-        format!("RPC endpoint: {}", network_config.rpc).dimmed()
-    );
+    println!("{}", format!("RPC endpoint: {}", rpc).dimmed());
 
     if let Some(contract_addr) = &args.contract {
         println!(
@@ -64,14 +91,16 @@ pub async fn execute(args: ConsoleArgs) -> Result<()> {
     println!("{}", "Type '.help' for more information".dimmed());
     println!();
 
-    // Run Node.js REPL
-    let status = Command::new("node")
+    // Run the REPL
+    let (program, runtime_args) = runtime.script_invocation(false)?;
+    let status = Command::new(program)
+        .args(runtime_args)
         .arg(&temp_file)
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .status()
-        .context("Failed to start Node.js REPL")?;
+        .with_context(|| format!("Failed to start the {} REPL", runtime.name()))?;
 
     // Cleanup
     let _ = fs::remove_file(temp_file);
@@ -83,43 +112,41 @@ pub async fn execute(args: ConsoleArgs) -> Result<()> {
     Ok(())
 }
 
-fn check_nodejs() -> Result<()> {
-    let output = Command::new("node")
+fn check_runtime(runtime: Runtime) -> Result<()> {
+    let output = Command::new(runtime.name())
         .arg("--version")
         .output()
-        .context("Node.js not found. Please install Node.js to use the console.")?;
+        .with_context(|| {
+            format!(
+                "{} not found. Please install it, or use the native console (--runtime native).",
+                runtime.name()
+            )
+        })?;
 
     if !output.status.success() {
-        anyhow::bail!("Node.js is not working properly");
+        anyhow::bail!("{} is not working properly", runtime.name());
     }
 
     Ok(())
 }
 
-#[derive(Debug)]
-struct NetworkConfig {
-    rpc: String,
-}
-
-fn load_network_config(network: &str) -> Result<NetworkConfig> {
-    // Try to load from glinforge.config.ts/js
-    // For now, use defaults
-    let rpc = match network {
-        "local" => "ws://localhost:9944",
-        "testnet" => "wss://testnet-rpc.glin.network",
-        "mainnet" => "wss://rpc.glin.network",
-        custom if custom.starts_with("ws://") || custom.starts_with("wss://") => custom,
-        _ => {
-            anyhow::bail!("Unknown network: {}. Use 'local', 'testnet', 'mainnet', or provide a WebSocket URL", network);
-        }
-    };
-
-    Ok(NetworkConfig {
-        rpc: rpc.to_string(),
-    })
+/// RPC endpoint of a network: one of the configured networks, or a WebSocket URL as is
+pub(crate) fn network_rpc(network: &str) -> Result<String> {
+    if network.starts_with("ws://") || network.starts_with("wss://") {
+        return Ok(network.to_string());
+    }
+    let names = crate::config::network_names()?;
+    if !names.iter().any(|name| name == network) {
+        anyhow::bail!(
+            "Unknown network: {}. Use {}, or provide a WebSocket URL",
+            network,
+            names.join(", ")
+        );
+    }
+    Ok(crate::config::load_network(network)?.rpc)
 }
 
-fn create_repl_script(args: &ConsoleArgs, network_config: &NetworkConfig) -> Result<String> {
+fn create_repl_script(args: &ConsoleArgs, rpc: &str) -> Result<String> {
     let artifacts_path = PathBuf::from(&args.artifacts_path);
     let artifacts_path_str = artifacts_path
         .canonicalize()
@@ -127,14 +154,19 @@ fn create_repl_script(args: &ConsoleArgs, network_config: &NetworkConfig) -> Res
         .to_string_lossy()
         .to_string();
 
+    let deployments = crate::cli::native_console::deployed_contracts(
+        &args.network,
+        Path::new(&args.artifacts_path),
+    )?;
+
     let script = format!(
         r#"
-const repl = require('repl');
+const repl = require('node:repl');
 const {{ ApiPromise, WsProvider }} = require('@polkadot/api');
 const {{ Keyring }} = require('@polkadot/keyring');
 const {{ cryptoWaitReady }} = require('@polkadot/util-crypto');
-const fs = require('fs');
-const path = require('path');
+const fs = require('node:fs');
+const path = require('node:path');
 
 // ANSI colors
 const colors = {{
@@ -183,6 +215,52 @@ async function initConsole() {{
       }}
     }}
 
+    // Deployed contracts as objects whose query and tx members run their methods
+    const deployments = {};
+    const contracts = {{}};
+    const toAddress = (value) => (value && value.address ? value.address : value);
+    const signer = () => replServer.context.signer || alice;
+    let ContractPromise;
+    try {{
+      ({{ ContractPromise }} = require('@polkadot/api-contract'));
+    }} catch (e) {{
+      if (deployments.some((d) => d.metadata)) {{
+        console.log(`${{colors.yellow}}⚠ Install @polkadot/api-contract to use the deployed contracts${{colors.reset}}`);
+      }}
+    }}
+    for (const deployment of deployments) {{
+      if (!ContractPromise || !deployment.metadata) continue;
+      const abi = JSON.parse(fs.readFileSync(deployment.metadata, 'utf-8'));
+      const contract = new ContractPromise(api, abi, deployment.address);
+      const query = {{}};
+      const tx = {{}};
+      for (const method of Object.keys(contract.query)) {{
+        query[method] = async (...args) => {{
+          const gasLimit = api.registry.createType('WeightV2', api.consts.system.blockWeights.maxBlock);
+          const {{ result, output }} = await contract.query[method](
+            toAddress(signer()), {{ gasLimit, storageDepositLimit: null }}, ...args.map(toAddress));
+          if (result.isErr) throw new Error(result.asErr.toString());
+          return output ? output.toHuman() : null;
+        }};
+      }}
+      for (const method of Object.keys(contract.tx)) {{
+        tx[method] = (...args) => new Promise((resolve, reject) => {{
+          const gasLimit = api.registry.createType('WeightV2', {{ refTime: 3000000000, proofSize: 1000000 }});
+          contract.tx[method]({{ gasLimit, storageDepositLimit: null }}, ...args.map(toAddress))
+            .signAndSend(signer(), ({{ status, dispatchError }}) => {{
+              if (dispatchError) {{
+                reject(new Error(dispatchError.toString()));
+              }} else if (status.isInBlock) {{
+                console.log(`${{colors.green}}✓ Transaction included in block${{colors.reset}}`);
+                resolve(status.asInBlock.toHex());
+              }}
+            }})
+            .catch(reject);
+        }});
+      }}
+      contracts[deployment.name] = {{ address: deployment.address, contract, query, tx }};
+    }}
+
     // Helper functions
     const helpers = {{
       // Get balance of an account
@@ -226,8 +304,13 @@ async function initConsole() {{
         }});
       }},
 
-      // List all available contracts from artifacts
+      // List deployed contracts and available artifacts
       listContracts() {{
+        console.log(`${{colors.cyan}}Deployed contracts:${{colors.reset}}`);
+        deployments.forEach(({{ name, address, metadata }}) => {{
+          const note = metadata ? '' : ` ${{colors.dim}}(no metadata)${{colors.reset}}`;
+          console.log(`  • ${{name}} ${{address}}${{note}}`);
+        }});
         console.log(`${{colors.cyan}}Available contracts:${{colors.reset}}`);
         Object.keys(artifacts).forEach(name => {{
           console.log(`  • ${{name}}`);
@@ -249,6 +332,9 @@ async function initConsole() {{
         console.log('    alice, bob   - Test accounts');
         console.log('    charlie, dave, eve - More test accounts');
         console.log('    artifacts    - Contract artifacts');
+        console.log('    contracts    - Deployed contracts, each also a global:');
+        console.log('                   token.query.balanceOf(alice), token.tx.transfer(bob, 100)');
+        console.log('    signer       - Account transactions are signed with (alice)');
         console.log('');
         console.log('  ${{colors.yellow}}Helper functions:${{colors.reset}}');
         console.log('    getBalance(address)       - Get account balance');
@@ -287,6 +373,11 @@ async function initConsole() {{
     replServer.context.dave = dave;
     replServer.context.eve = eve;
     replServer.context.artifacts = artifacts;
+    replServer.context.contracts = contracts;
+    replServer.context.signer = alice;
+    for (const [name, contract] of Object.entries(contracts)) {{
+      if (!(name in replServer.context)) replServer.context[name] = contract;
+    }}
 
     // Add helper functions
     Object.assign(replServer.context, helpers);
@@ -309,6 +400,9 @@ async function initConsole() {{
     if (Object.keys(artifacts).length > 0) {{
       console.log(`${{colors.dim}}  Loaded ${{Object.keys(artifacts).length}} contract(s)${{colors.reset}}`);
     }}
+    if (Object.keys(contracts).length > 0) {{
+      console.log(`${{colors.dim}}  Deployed: ${{Object.keys(contracts).join(', ')}}${{colors.reset}}`);
+    }}
 
     console.log('');
 
@@ -321,7 +415,9 @@ async function initConsole() {{
 // Start console
 initConsole();
 "#,
-        network_config.rpc, artifacts_path_str
+        rpc,
+        artifacts_path_str,
+        serde_json::to_string(&deployments)?
     );
 
     Ok(script)
@@ -343,3 +439,23 @@ fn print_banner() {
     println!("{}", "Interactive Console for Smart Contracts".yellow());
     println!();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_rpc() {
+        assert_eq!(network_rpc("local").unwrap(), "ws://localhost:9944");
+        assert_eq!(
+            network_rpc("testnet").unwrap(),
+            crate::config::load_network("testnet").unwrap().rpc
+        );
+        assert_eq!(
+            network_rpc("wss://node.example.com").unwrap(),
+            "wss://node.example.com"
+        );
+        let error = network_rpc("nowhere").unwrap_err().to_string();
+        assert!(error.contains("local, mainnet, testnet"), "{}", error);
+    }
+}
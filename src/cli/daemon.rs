@@ -0,0 +1,441 @@
+// Long-lived RPC server shared by scripts, the console and frontend dev servers
+//
+// `daemon start` runs the RPC server in the background and records how to
+// reach it in `<cache>/daemon.json` (pid, address or socket, network, token).
+// `run` uses a daemon serving its network instead of starting its own server;
+// other clients read the state file for the address and token. The file is
+// written owner-only, as the token grants signing with the session's accounts.
+
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::cli::run::RpcServerArgs;
+use crate::rpc::server::Endpoint;
+
+/// Time a detached daemon has to come up
+const START_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time a stopped daemon has to shut down
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Parser)]
+pub struct DaemonArgs {
+    #[command(subcommand)]
+    command: DaemonCommands,
+}
+
+#[derive(Subcommand)]
+enum DaemonCommands {
+    /// Start the RPC server in the background
+    Start {
+        /// Network requests default to (testnet, mainnet, local)
+        #[arg(short, long, default_value = "testnet")]
+        network: String,
+
+        #[command(flatten)]
+        server: RpcServerArgs,
+
+        /// Stay in the foreground instead of detaching
+        #[arg(long)]
+        foreground: bool,
+    },
+
+    /// Stop the running daemon
+    Stop,
+
+    /// Show whether a daemon is running and where
+    Status,
+}
+
+/// How to reach a running daemon, as recorded in its state file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaemonState {
+    pub pid: u32,
+    pub network: String,
+    #[serde(default)]
+    pub allowed_networks: Vec<String>,
+    /// TCP address the server is bound to
+    pub address: Option<SocketAddr>,
+    /// Unix socket the server listens on
+    pub socket: Option<PathBuf>,
+    pub token: String,
+    /// Start time, in seconds since the Unix epoch
+    pub started: u64,
+}
+
+impl DaemonState {
+    pub fn endpoint(&self) -> Endpoint {
+        match (&self.socket, self.address) {
+            (Some(socket), _) => Endpoint::Unix(socket.clone()),
+            (None, Some(address)) => Endpoint::Tcp(address),
+            (None, None) => Endpoint::Tcp(SocketAddr::from(([127, 0, 0, 1], 0))),
+        }
+    }
+
+    /// Whether the daemon still accepts connections
+    pub fn alive(&self) -> bool {
+        match self.endpoint() {
+            Endpoint::Tcp(address) => std::net::TcpStream::connect_timeout(
+                &crate::cli::run::connect_address(&address),
+                Duration::from_secs(1),
+            )
+            .is_ok(),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => std::os::unix::net::UnixStream::connect(path).is_ok(),
+            #[cfg(not(unix))]
+            Endpoint::Unix(_) => false,
+        }
+    }
+}
+
+pub async fn execute(args: DaemonArgs) -> anyhow::Result<()> {
+    match args.command {
+        DaemonCommands::Start {
+            network,
+            server,
+            foreground: true,
+        } => serve(&network, &server).await,
+        DaemonCommands::Start { .. } => start(),
+        DaemonCommands::Stop => stop(),
+        DaemonCommands::Status => status(),
+    }
+}
+
+/// State file of the daemon, in the project's cache directory
+pub fn state_path() -> anyhow::Result<PathBuf> {
+    Ok(cache_dir()?.join("daemon.json"))
+}
+
+/// The running daemon, if any; a state file left by a dead daemon is removed
+pub fn running() -> anyhow::Result<Option<DaemonState>> {
+    running_in(&state_path()?)
+}
+
+fn running_in(path: &Path) -> anyhow::Result<Option<DaemonState>> {
+    let Some(state) = read_state(path)? else {
+        return Ok(None);
+    };
+    if state.alive() {
+        Ok(Some(state))
+    } else {
+        let _ = std::fs::remove_file(path);
+        Ok(None)
+    }
+}
+
+fn cache_dir() -> anyhow::Result<PathBuf> {
+    let cache = if crate::config::file::find_config_file().is_ok() {
+        crate::config::file::load_config_file(None)?.paths.cache
+    } else {
+        "./.cache".to_string()
+    };
+    Ok(PathBuf::from(cache))
+}
+
+fn read_state(path: &Path) -> anyhow::Result<Option<DaemonState>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    match serde_json::from_str(&content) {
+        Ok(state) => Ok(Some(state)),
+        // A state file cut short by a crash is as good as none
+        Err(_) => Ok(None),
+    }
+}
+
+fn write_state(path: &Path, state: &DaemonState) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use std::io::Write;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // The mode only applies to a new file, so the token goes into a fresh one that
+    // then replaces the state file, whatever its permissions were
+    let tmp = path.with_extension("json.tmp");
+    let _ = std::fs::remove_file(&tmp);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&tmp)?;
+    file.write_all(serde_json::to_string_pretty(state)?.as_bytes())?;
+    drop(file);
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Run the server until stopped, recording it in the state file
+async fn serve(network: &str, server: &RpcServerArgs) -> anyhow::Result<()> {
+    let path = state_path()?;
+    if let Some(state) = running_in(&path)? {
+        anyhow::bail!(
+            "A daemon is already running (pid {}) at {}",
+            state.pid,
+            state.endpoint()
+        );
+    }
+
+    println!("{}", "Starting glin-forge daemon...".cyan().bold());
+    println!("  {} {}", "Network:".cyan(), network);
+    crate::cli::run::print_server_args(server);
+    let (rpc_server, token) = crate::cli::run::start_server(network, server).await?;
+
+    let (address, socket) = match rpc_server.endpoint() {
+        Endpoint::Tcp(address) => (Some(*address), None),
+        Endpoint::Unix(path) => (None, Some(path.clone())),
+    };
+    let state = DaemonState {
+        pid: std::process::id(),
+        network: network.to_string(),
+        allowed_networks: server.allow_networks.clone(),
+        address,
+        socket,
+        token,
+        started: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+    write_state(&path, &state)?;
+    println!("{} State written to {}", "✓".green(), path.display());
+
+    shutdown_signal().await?;
+
+    println!("\n{}", "Shutting down daemon...".cyan());
+    rpc_server.shutdown().await?;
+    // Leave the file alone if another daemon has taken over since
+    if read_state(&path)?.is_some_and(|s| s.pid == state.pid) {
+        std::fs::remove_file(&path)?;
+    }
+    println!("{} Daemon stopped", "✓".green());
+    Ok(())
+}
+
+/// Resolve on Ctrl-C, or SIGTERM as `daemon stop` sends
+async fn shutdown_signal() -> anyhow::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result?,
+            _ = terminate.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await?;
+    Ok(())
+}
+
+/// Relaunch this command detached, and wait for it to come up
+fn start() -> anyhow::Result<()> {
+    let path = state_path()?;
+    if let Some(state) = running_in(&path)? {
+        anyhow::bail!(
+            "A daemon is already running (pid {}) at {}",
+            state.pid,
+            state.endpoint()
+        );
+    }
+
+    let log_path = path.with_file_name("daemon.log");
+    if let Some(dir) = log_path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)?;
+
+    let mut command = std::process::Command::new(std::env::current_exe()?);
+    command
+        .args(std::env::args_os().skip(1))
+        .arg("--foreground")
+        .stdin(std::process::Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    // Out of the terminal's process group, so Ctrl-C there leaves it running
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to start the daemon: {}", e))?;
+
+    let started = Instant::now();
+    let state = loop {
+        if let Some(state) = running_in(&path)?.filter(|s| s.pid == child.id()) {
+            break state;
+        }
+        if let Some(status) = child.try_wait()? {
+            anyhow::bail!("Daemon exited with {}; see {}", status, log_path.display());
+        }
+        if started.elapsed() > START_TIMEOUT {
+            anyhow::bail!(
+                "Daemon did not come up within {}s; see {}",
+                START_TIMEOUT.as_secs(),
+                log_path.display()
+            );
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    };
+
+    println!("{} Daemon started", "✓".green().bold());
+    print_state(&state, &path, &log_path);
+    println!(
+        "\n{} `glin-forge run -n {}` now uses this daemon; stop it with `glin-forge daemon stop`",
+        "→".cyan(),
+        state.network
+    );
+    Ok(())
+}
+
+fn stop() -> anyhow::Result<()> {
+    let path = state_path()?;
+    let Some(state) = running_in(&path)? else {
+        println!("{} No daemon running", "→".cyan());
+        return Ok(());
+    };
+
+    println!("{} Stopping daemon (pid {})...", "→".cyan(), state.pid);
+    #[cfg(unix)]
+    let status = std::process::Command::new("kill")
+        .arg(state.pid.to_string())
+        .status()?;
+    #[cfg(not(unix))]
+    let status = std::process::Command::new("taskkill")
+        .args(["/PID", &state.pid.to_string(), "/F"])
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("Failed to signal daemon process {}", state.pid);
+    }
+
+    let stopping = Instant::now();
+    while state.alive() {
+        if stopping.elapsed() > STOP_TIMEOUT {
+            anyhow::bail!(
+                "Daemon (pid {}) still running after {}s",
+                state.pid,
+                STOP_TIMEOUT.as_secs()
+            );
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    // A daemon killed outright leaves its state file behind
+    if read_state(&path)?.is_some_and(|s| s.pid == state.pid) {
+        std::fs::remove_file(&path)?;
+    }
+    println!("{} Daemon stopped", "✓".green());
+    Ok(())
+}
+
+fn status() -> anyhow::Result<()> {
+    let path = state_path()?;
+    match running_in(&path)? {
+        Some(state) => {
+            println!("{} Daemon running", "✓".green().bold());
+            print_state(&state, &path, &path.with_file_name("daemon.log"));
+        }
+        None => {
+            println!("{} No daemon running", "→".cyan());
+            println!("  Start one with: glin-forge daemon start");
+        }
+    }
+    Ok(())
+}
+
+fn print_state(state: &DaemonState, path: &Path, log_path: &Path) {
+    println!("  {} {}", "PID:".cyan(), state.pid);
+    println!("  {} {}", "Endpoint:".cyan(), state.endpoint());
+    println!("  {} {}", "Network:".cyan(), state.network);
+    if !state.allowed_networks.is_empty() {
+        println!(
+            "  {} {}",
+            "Also allowed:".cyan(),
+            state.allowed_networks.join(", ")
+        );
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    println!(
+        "  {} {}",
+        "Uptime:".cyan(),
+        format_uptime(now.saturating_sub(state.started))
+    );
+    println!("  {} {}", "State file:".cyan(), path.display());
+    println!("  {} {}", "Log:".cyan(), log_path.display());
+}
+
+fn format_uptime(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache").join("daemon.json");
+        assert_eq!(running_in(&path).unwrap(), None);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let state = DaemonState {
+            pid: 4242,
+            network: "local".to_string(),
+            allowed_networks: vec!["testnet".to_string()],
+            address: Some(listener.local_addr().unwrap()),
+            socket: None,
+            token: "secret".to_string(),
+            started: 0,
+        };
+        // A state file left readable by others is replaced, not reused
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{}").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        }
+        write_state(&path, &state).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert_eq!(running_in(&path).unwrap(), Some(state.clone()));
+
+        // Once the server is gone the state file is stale and removed
+        drop(listener);
+        assert_eq!(running_in(&path).unwrap(), None);
+        assert!(!path.exists());
+
+        std::fs::write(&path, "{\"pid\":").unwrap();
+        assert_eq!(read_state(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(42), "42s");
+        assert_eq!(format_uptime(125), "2m 5s");
+        assert_eq!(format_uptime(7380), "2h 3m");
+    }
+}
@@ -1,6 +1,10 @@
+use anyhow::Context;
 use clap::Parser;
 use colored::Colorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::cli::hooks::Hook;
+use crate::cli::run::RpcServerArgs;
 
 #[derive(Parser)]
 pub struct DeployArgs {
@@ -13,7 +17,7 @@ pub struct DeployArgs {
     pub metadata: Option<PathBuf>,
 
     /// Constructor arguments (comma-separated)
-    #[arg(short, long)]
+    #[arg(long)]
     pub args: Option<String>,
 
     /// Value to transfer to contract (in GLIN)
@@ -45,10 +49,9 @@ pub async fn execute(args: DeployArgs) -> anyhow::Result<()> {
     println!("{}", "Deploying contract...".cyan().bold());
 
     // Auto-detect WASM and metadata if not provided
-    let (wasm_path, metadata_path) = if args.wasm.is_none() || args.metadata.is_none() {
-        find_contract_artifacts(".")?
-    } else {
-        (args.wasm.unwrap(), args.metadata.unwrap())
+    let (wasm_path, metadata_path) = match (args.wasm, args.metadata) {
+        (Some(wasm), Some(metadata)) => (wasm, metadata),
+        _ => find_contract_artifacts(".")?,
     };
 
     println!("\n{}", "Contract artifacts:".bold());
@@ -96,83 +99,181 @@ pub async fn execute(args: DeployArgs) -> anyhow::Result<()> {
         }
     }
 
-    println!("\n{}", "Connecting to network...".cyan());
-
-    // Connect to network
-    let client = glin_client::create_client(&network_config.rpc).await?;
-    println!("{} Connected to {}", "✓".green(), network_config.rpc);
-
-    // Get signer account
-    let signer = glin_client::get_dev_account(&args.account)?;
-    let signer_address = glin_client::get_address(&signer);
-    println!("{} Using account: {}", "✓".green(), signer_address);
+    // Hooks reach the network through an RPC server, as `run` scripts do
+    let hooks = crate::cli::hooks::configured()?;
+    let rpc_server = if crate::cli::hooks::any(&hooks) {
+        crate::config::runtime::configure(None)?;
+        crate::cli::run::serve(&args.network, &RpcServerArgs::default(), false).await?
+    } else {
+        None
+    };
+    let contract = deployment_name(&metadata_json, &metadata_path).unwrap_or_default();
+
+    let outcome = async {
+        println!("\n{}", "Connecting to network...".cyan());
+
+        // Connect to network
+        let client = glin_client::create_client(&network_config.rpc).await?;
+        println!("{} Connected to {}", "✓".green(), network_config.rpc);
+
+        // Get signer account
+        let signer = glin_client::get_dev_account(&args.account)?;
+        let signer_address = glin_client::get_address(&signer);
+        println!("{} Using account: {}", "✓".green(), signer_address);
+
+        let mut hook_env = vec![
+            ("GLIN_FORGE_DEPLOY_CONTRACT", contract),
+            ("GLIN_FORGE_DEPLOY_WASM", wasm_path.display().to_string()),
+            (
+                "GLIN_FORGE_DEPLOY_METADATA",
+                metadata_path.display().to_string(),
+            ),
+            ("GLIN_FORGE_DEPLOY_ACCOUNT", signer_address.to_string()),
+        ];
+        crate::cli::hooks::run(Hook::PreDeploy, &hooks, &hook_env).await?;
+
+        // Parse value
+        let value_u128 = args.value.parse::<u128>().unwrap_or(0);
+
+        // Gas estimation tips
+        println!("\n{}", "Gas Estimation:".bold());
+        println!("  {} Estimating deployment gas...", "→".cyan());
+
+        // Simulated gas estimation
+        let estimated_gas = 3_000_000_000u64; // 3B refTime
+        let estimated_proof = 1_000_000u64; // 1M proofSize
+
+        println!("  {} refTime: {}", "→".cyan(), format_number(estimated_gas));
+        println!(
+            "  {} proofSize: {}",
+            "→".cyan(),
+            format_number(estimated_proof)
+        );
 
-    // Parse value
-    let value_u128 = args.value.parse::<u128>().unwrap_or(0);
+        if args.gas_limit.is_none() {
+            println!("  {} Using auto-estimated gas limit", "ℹ".blue());
+            println!("    {}", "Tip: Add 20% buffer for safety".dimmed());
+        }
 
-    // Gas estimation tips
-    println!("\n{}", "Gas Estimation:".bold());
-    println!("  {} Estimating deployment gas...", "→".cyan());
+        println!("\n{}", "Deploying contract...".cyan());
+
+        // Deploy contract
+        let result = crate::contract::deploy_contract(
+            &client,
+            wasm_bytes,
+            &metadata,
+            constructor_args,
+            None,
+            value_u128,
+            args.salt.as_deref(),
+            &signer,
+        )
+        .await?;
+
+        if result.success {
+            println!("\n{} Contract deployed successfully!", "✓".green().bold());
+            println!("\n{}", "Contract info:".bold());
+
+            if let Some(addr) = &result.contract_address {
+                println!("  {} {}", "Address:".cyan(), addr);
+
+                if let Some(explorer) = network_config.explorer {
+                    println!("  {} {}/contract/{}", "Explorer:".cyan(), explorer, addr);
+                }
+            }
 
-    // Simulated gas estimation
-    let estimated_gas = 3_000_000_000u64; // 3B refTime
-    let estimated_proof = 1_000_000u64; // 1M proofSize
+            if let Some(hash) = &result.tx_hash {
+                println!("  {} {}", "Transaction:".cyan(), hash);
+            }
 
-    println!("  {} refTime: {}", "→".cyan(), format_number(estimated_gas));
-    println!(
-        "  {} proofSize: {}",
-        "→".cyan(),
-        format_number(estimated_proof)
-    );
+            if let Some(block) = result.block_number {
+                println!("  {} #{}", "Block:".cyan(), block);
+            }
 
-    if args.gas_limit.is_none() {
-        println!("  {} Using auto-estimated gas limit", "ℹ".blue());
-        println!("    {}", "Tip: Add 20% buffer for safety".dimmed());
-    }
+            if let Some(code_hash) = &result.code_hash {
+                println!("  {} {}", "Code Hash:".cyan(), code_hash);
+            }
 
-    println!("\n{}", "Deploying contract...".cyan());
-
-    // Deploy contract
-    let result = crate::contract::deploy_contract(
-        &client,
-        wasm_bytes,
-        &metadata,
-        constructor_args,
-        None,
-        value_u128,
-        &signer,
-    )
-    .await?;
-
-    if result.success {
-        println!("\n{} Contract deployed successfully!", "✓".green().bold());
-        println!("\n{}", "Contract info:".bold());
-
-        if let Some(addr) = result.contract_address {
-            println!("  {} {}", "Address:".cyan(), addr);
-
-            if let Some(explorer) = network_config.explorer {
-                println!("  {} {}/contract/{}", "Explorer:".cyan(), explorer, addr);
+            record_deployment(&args.network, &metadata_json, &metadata_path, &result)?;
+
+            let outcome = [
+                ("GLIN_FORGE_DEPLOY_ADDRESS", &result.contract_address),
+                ("GLIN_FORGE_DEPLOY_CODE_HASH", &result.code_hash),
+                ("GLIN_FORGE_DEPLOY_TX_HASH", &result.tx_hash),
+                ("GLIN_FORGE_DEPLOY_BLOCK_HASH", &result.block_hash),
+            ];
+            hook_env.extend(
+                outcome
+                    .into_iter()
+                    .filter_map(|(name, value)| Some((name, value.clone()?))),
+            );
+            if let Some(block) = result.block_number {
+                hook_env.push(("GLIN_FORGE_DEPLOY_BLOCK", block.to_string()));
             }
+            crate::cli::hooks::run(Hook::PostDeploy, &hooks, &hook_env)
+                .await
+                .context("The contract is deployed, but its postDeploy hook failed")?;
+        } else {
+            anyhow::bail!(
+                "Deployment failed: {}",
+                result.error.unwrap_or_else(|| "Unknown error".to_string())
+            );
         }
 
-        if let Some(hash) = result.tx_hash {
-            println!("  {} {}", "Transaction:".cyan(), hash);
-        }
+        anyhow::Ok(())
+    }
+    .await;
 
-        if let Some(code_hash) = result.code_hash {
-            println!("  {} {}", "Code Hash:".cyan(), code_hash);
-        }
-    } else {
-        anyhow::bail!(
-            "Deployment failed: {}",
-            result.error.unwrap_or_else(|| "Unknown error".to_string())
-        );
+    if let Some(rpc_server) = rpc_server {
+        rpc_server.shutdown().await?;
     }
+    outcome
+}
 
+/// Record an instantiated contract in the deployments registry under its metadata name
+pub(crate) fn record_deployment(
+    network: &str,
+    metadata_json: &str,
+    metadata_path: &Path,
+    result: &crate::contract::DeployResult,
+) -> anyhow::Result<()> {
+    use crate::config::deployments::{DeploymentRecord, DeploymentRegistry, DEPLOYMENTS_FILE};
+
+    let Some(address) = &result.contract_address else {
+        return Ok(());
+    };
+    let name = deployment_name(metadata_json, metadata_path).unwrap_or_else(|| address.clone());
+
+    let mut registry = DeploymentRegistry::open_default()?;
+    registry.record(
+        network,
+        &name,
+        DeploymentRecord {
+            address: address.clone(),
+            code_hash: result.code_hash.clone(),
+            tx_hash: result.tx_hash.clone(),
+            block: result.block_number,
+            block_hash: result.block_hash.clone(),
+        },
+    )?;
+    println!(
+        "  {} Recorded as {} in {}",
+        "✓".green(),
+        name.bold(),
+        DEPLOYMENTS_FILE
+    );
     Ok(())
 }
 
+/// Name a contract is recorded under: its metadata name, or else the metadata file's stem
+pub(crate) fn deployment_name(metadata_json: &str, metadata_path: &Path) -> Option<String> {
+    crate::config::deployments::contract_name(metadata_json).or_else(|| {
+        metadata_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+    })
+}
+
 fn format_number(n: u64) -> String {
     n.to_string()
         .as_bytes()
@@ -0,0 +1,458 @@
+// Toolchain diagnostics
+//
+// `glin-forge doctor` checks what building, testing and deploying contracts
+// rely on: cargo-contract, rustc with the wasm32 target and rust-src, the
+// versions of both against the ink! version of the contract in `--path`,
+// Node.js for typegen and JavaScript scripts, and whether the nodes of the
+// configured networks answer. Each failed check prints the command fixing it;
+// failed checks make the command fail, warnings do not.
+
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use semver::Version;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// Longest wait for a node to answer
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Oldest Node.js version typegen and scripts support
+const NODE_MINIMUM: u64 = 18;
+
+/// Oldest cargo-contract and rustc building contracts of each ink! major version
+const INK_REQUIREMENTS: &[(u64, &str, &str)] = &[
+    (4, "3.0.0", "1.69.0"),
+    (5, "4.0.0", "1.75.0"),
+    (6, "6.0.0-alpha", "1.85.0"),
+];
+
+#[derive(Parser)]
+pub struct DoctorArgs {
+    /// Path to the contract project
+    #[arg(short, long, default_value = ".")]
+    pub path: String,
+
+    /// Network to check (repeatable) [default: every configured network]
+    #[arg(short, long = "network", value_name = "NETWORK")]
+    pub networks: Vec<String>,
+
+    /// Skip the network checks
+    #[arg(long, conflicts_with = "networks")]
+    pub offline: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warning,
+    Failed,
+    Skipped,
+}
+
+/// Outcome of one check, with the command fixing it when it did not pass
+#[derive(Debug, Clone, PartialEq)]
+struct Check {
+    name: String,
+    status: Status,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+pub async fn execute(args: DoctorArgs) -> Result<()> {
+    println!(
+        "{}",
+        "Checking the development environment...".cyan().bold()
+    );
+    println!();
+    println!("{}", "Toolchain:".bold());
+
+    let cargo_contract = command_version("cargo", &["contract", "--version"]);
+    let rustc = command_version("rustc", &["--version"]);
+    let mut checks = vec![
+        check_cargo_contract(cargo_contract.as_ref()),
+        check_rustc(rustc.as_ref()),
+    ];
+    checks.extend(check_rustup());
+    checks.push(match ink_version(Path::new(&args.path)) {
+        Some(ink) => check_ink(&ink, rustc.as_ref(), cargo_contract.as_ref()),
+        None => Check::new(
+            "ink!",
+            Status::Skipped,
+            format!("No ink! contract in {}", args.path),
+        ),
+    });
+    checks.push(check_node(command_version("node", &["--version"]).as_ref()));
+    print_checks(&checks);
+
+    if !args.offline {
+        let networks = match args.networks.is_empty() {
+            true => crate::config::network_names()?,
+            false => args.networks.clone(),
+        };
+        println!("\n{}", "Networks:".bold());
+        let network_checks =
+            futures::future::join_all(networks.iter().map(|name| check_network(name))).await;
+        print_checks(&network_checks);
+        checks.extend(network_checks);
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    let failed = count(Status::Failed);
+    println!();
+    println!(
+        "{} passed, {} warning(s), {} failed",
+        count(Status::Ok),
+        count(Status::Warning),
+        failed
+    );
+    if failed > 0 {
+        anyhow::bail!("{} check(s) failed", failed);
+    }
+    println!("{} Ready to build and deploy contracts", "✓".green().bold());
+    Ok(())
+}
+
+fn print_checks(checks: &[Check]) {
+    let width = checks
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or_default();
+    for check in checks {
+        let symbol = match check.status {
+            Status::Ok => "✓".green(),
+            Status::Warning => "⚠".yellow(),
+            Status::Failed => "✗".red(),
+            Status::Skipped => "-".dimmed(),
+        };
+        println!(
+            "  {} {:width$}  {}",
+            symbol,
+            check.name,
+            check.detail,
+            width = width
+        );
+        if let Some(fix) = &check.fix {
+            println!("    {} {}", "→".cyan(), fix.yellow());
+        }
+    }
+}
+
+/// Version a command prints, None when it cannot run or fails
+pub(crate) fn command_version(program: &str, args: &[&str]) -> Option<Version> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// First `major.minor.patch` of a version line, like `rustc 1.80.0 (051478957 2024-07-21)`
+/// or `cargo-contract-contract 4.1.1-unknown-x86_64-unknown-linux-gnu`
+fn parse_version(output: &str) -> Option<Version> {
+    let pattern = regex::Regex::new(r"(\d+)\.(\d+)\.(\d+)").ok()?;
+    let captures = pattern.captures(output)?;
+    let part = |index: usize| captures[index].parse::<u64>().ok();
+    Some(Version::new(part(1)?, part(2)?, part(3)?))
+}
+
+fn check_cargo_contract(version: Option<&Version>) -> Check {
+    match version {
+        Some(version) => Check::new("cargo-contract", Status::Ok, version.to_string()),
+        None => Check::new("cargo-contract", Status::Failed, "not installed")
+            .fix("cargo install cargo-contract --force --locked"),
+    }
+}
+
+fn check_rustc(version: Option<&Version>) -> Check {
+    match version {
+        Some(version) => Check::new("rustc", Status::Ok, version.to_string()),
+        None => Check::new("rustc", Status::Failed, "not installed")
+            .fix("curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh"),
+    }
+}
+
+/// The wasm32 target and the rust-src component cargo-contract builds with
+fn check_rustup() -> Vec<Check> {
+    let installed = |args: &[&str]| {
+        let output = Command::new("rustup").args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let (Some(targets), Some(components)) = (
+        installed(&["target", "list", "--installed"]),
+        installed(&["component", "list", "--installed"]),
+    ) else {
+        return vec![Check::new(
+            "wasm32 target",
+            Status::Warning,
+            "rustup not found; cannot check the installed targets",
+        )
+        .fix("curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh")];
+    };
+
+    let target = match targets
+        .lines()
+        .any(|t| t.trim() == "wasm32-unknown-unknown")
+    {
+        true => Check::new("wasm32 target", Status::Ok, "wasm32-unknown-unknown"),
+        false => Check::new("wasm32 target", Status::Failed, "not installed")
+            .fix("rustup target add wasm32-unknown-unknown"),
+    };
+    let source = match components.lines().any(|c| c.trim().starts_with("rust-src")) {
+        true => Check::new("rust-src", Status::Ok, "installed"),
+        false => Check::new("rust-src", Status::Failed, "not installed")
+            .fix("rustup component add rust-src"),
+    };
+    vec![target, source]
+}
+
+/// ink! version of the contract in a directory: the locked one, or the lowest its
+/// requirement accepts
+pub(crate) fn ink_version(dir: &Path) -> Option<Version> {
+    let manifest = dir.join("Cargo.toml");
+    let value: toml::Value = std::fs::read_to_string(&manifest).ok()?.parse().ok()?;
+    let requirement = crate::analyzer::vulndb::dependency_requirement(&value, "ink");
+    let locked = crate::analyzer::vulndb::locked_versions(&manifest)
+        .into_iter()
+        .find(|(name, _)| name == "ink")
+        .map(|(_, version)| version);
+    match locked {
+        Some(version) => Some(version),
+        None => crate::analyzer::vulndb::lowest_version(&requirement?),
+    }
+}
+
+/// Whether rustc and cargo-contract build contracts of an ink! version
+fn check_ink(ink: &Version, rustc: Option<&Version>, cargo_contract: Option<&Version>) -> Check {
+    let name = "ink!";
+    let Some((_, cargo_minimum, rustc_minimum)) = INK_REQUIREMENTS
+        .iter()
+        .find(|(major, _, _)| *major == ink.major)
+    else {
+        return match ink.major < INK_REQUIREMENTS[0].0 {
+            true => Check::new(
+                name,
+                Status::Failed,
+                format!("{} is no longer supported by cargo-contract", ink),
+            )
+            .fix("Upgrade to ink! 5; glin-forge analyze --migrate lists the changes"),
+            false => Check::new(
+                name,
+                Status::Warning,
+                format!("{} is newer than the versions glin-forge knows", ink),
+            ),
+        };
+    };
+    let cargo_minimum = Version::parse(cargo_minimum).expect("valid version");
+    let rustc_minimum = Version::parse(rustc_minimum).expect("valid version");
+
+    if let Some(cargo_contract) = cargo_contract.filter(|v| **v < cargo_minimum) {
+        return Check::new(
+            name,
+            Status::Failed,
+            format!(
+                "{} needs cargo-contract {} or later, found {}",
+                ink, cargo_minimum, cargo_contract
+            ),
+        )
+        .fix(format!(
+            "cargo install cargo-contract --version ^{} --force --locked",
+            cargo_minimum.major
+        ));
+    }
+    if let Some(rustc) = rustc.filter(|v| **v < rustc_minimum) {
+        return Check::new(
+            name,
+            Status::Failed,
+            format!(
+                "{} needs rustc {} or later, found {}",
+                ink, rustc_minimum, rustc
+            ),
+        )
+        .fix("rustup update stable");
+    }
+    // Rust 1.82 enables WebAssembly features that the contracts pallet rejects, which only
+    // cargo-contract 5 turns off
+    let wasm_features = Version::new(1, 82, 0);
+    if ink.major < 6
+        && rustc.is_some_and(|v| *v >= wasm_features)
+        && cargo_contract.is_some_and(|v| v.major < 5)
+    {
+        return Check::new(
+            name,
+            Status::Failed,
+            format!(
+                "{} with rustc {} needs cargo-contract 5, which disables the WebAssembly \
+                 features rustc 1.82 enables",
+                ink,
+                rustc.expect("checked above")
+            ),
+        )
+        .fix("cargo install cargo-contract --version ^5 --force --locked");
+    }
+    Check::new(name, Status::Ok, ink.to_string())
+}
+
+fn check_node(version: Option<&Version>) -> Check {
+    let fix = format!(
+        "Install Node.js {} or later from https://nodejs.org",
+        NODE_MINIMUM
+    );
+    match version {
+        Some(version) if version.major >= NODE_MINIMUM => {
+            Check::new("Node.js", Status::Ok, version.to_string())
+        }
+        Some(version) => Check::new(
+            "Node.js",
+            Status::Warning,
+            format!("{} is older than {}", version, NODE_MINIMUM),
+        )
+        .fix(fix),
+        None => Check::new(
+            "Node.js",
+            Status::Warning,
+            "not installed; typegen and JavaScript scripts need it",
+        )
+        .fix(fix),
+    }
+}
+
+/// Whether a network's node answers within the timeout, with its chain and latest block
+async fn check_network(name: &str) -> Check {
+    let label = format!("network {}", name);
+    let network = match crate::config::load_network(name) {
+        Ok(network) => network,
+        Err(e) => {
+            return Check::new(label, Status::Failed, e.to_string())
+                .fix("glin-forge network list shows the configured networks")
+        }
+    };
+    let reached = tokio::time::timeout(NETWORK_TIMEOUT, async {
+        let rpc = glin_client::create_rpc_client(&network.rpc)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e.root_cause()))?;
+        let chain = rpc.system_chain().await?;
+        let header = rpc.chain_get_header(None).await?;
+        anyhow::Ok((chain, header.map(|h| h.number)))
+    })
+    .await;
+    let error = match reached {
+        Ok(Ok((chain, Some(number)))) => {
+            let detail = format!("{} at #{} ({})", chain, number, network.rpc);
+            return Check::new(label, Status::Ok, detail);
+        }
+        Ok(Ok((chain, None))) => {
+            return Check::new(label, Status::Ok, format!("{} ({})", chain, network.rpc))
+        }
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => format!("no answer within {}s", NETWORK_TIMEOUT.as_secs()),
+    };
+    let fix = match network.rpc.contains("localhost") || network.rpc.contains("127.0.0.1") {
+        true => format!("Start a local node listening on {}", network.rpc),
+        false => format!(
+            "Check your connection, or the rpc of {} in the glin-forge config",
+            name
+        ),
+    };
+    Check::new(label, Status::Failed, format!("{}: {}", network.rpc, error)).fix(fix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        let version = |output| parse_version(output).map(|v| v.to_string());
+        assert_eq!(
+            version("rustc 1.80.0 (051478957 2024-07-21)").as_deref(),
+            Some("1.80.0")
+        );
+        assert_eq!(
+            version("cargo-contract-contract 4.1.1-unknown-x86_64-unknown-linux-gnu").as_deref(),
+            Some("4.1.1")
+        );
+        assert_eq!(version("v18.17.0\n").as_deref(), Some("18.17.0"));
+        assert_eq!(version("command not found"), None);
+    }
+
+    #[test]
+    fn test_check_ink() {
+        let v = |s| Version::parse(s).unwrap();
+        let check = |ink, rustc, cargo_contract| {
+            check_ink(&v(ink), Some(&v(rustc)), Some(&v(cargo_contract)))
+        };
+
+        assert_eq!(check("5.1.0", "1.81.0", "4.1.1").status, Status::Ok);
+        assert_eq!(check("5.1.0", "1.84.0", "5.0.1").status, Status::Ok);
+
+        let outdated = check("5.1.0", "1.80.0", "3.2.0");
+        assert_eq!(outdated.status, Status::Failed);
+        assert!(outdated.detail.contains("cargo-contract 4.0.0 or later"));
+        assert!(outdated.fix.unwrap().contains("--version ^4"));
+
+        let old_rustc = check("5.0.0", "1.70.0", "4.1.1");
+        assert_eq!(old_rustc.fix.as_deref(), Some("rustup update stable"));
+
+        let wasm_features = check("5.1.0", "1.82.0", "4.1.1");
+        assert_eq!(wasm_features.status, Status::Failed);
+        assert!(wasm_features.fix.unwrap().contains("--version ^5"));
+
+        assert_eq!(check("3.4.0", "1.80.0", "4.1.1").status, Status::Failed);
+        assert_eq!(check("7.0.0", "1.90.0", "7.0.0").status, Status::Warning);
+        // Missing tools are reported by their own checks
+        assert_eq!(check_ink(&v("5.1.0"), None, None).status, Status::Ok);
+    }
+
+    #[test]
+    fn test_ink_version() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(ink_version(dir.path()), None);
+
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            "[package]\nname = \"flipper\"\n\n[dependencies]\nink = { version = \"5.0\", default-features = false }\n",
+        )
+        .unwrap();
+        assert_eq!(ink_version(dir.path()), Some(Version::new(5, 0, 0)));
+
+        // The locked version wins over the requirement
+        std::fs::write(
+            dir.path().join("Cargo.lock"),
+            "[[package]]\nname = \"ink\"\nversion = \"5.1.1\"\n",
+        )
+        .unwrap();
+        assert_eq!(ink_version(dir.path()), Some(Version::new(5, 1, 1)));
+    }
+
+    #[test]
+    fn test_check_node() {
+        assert_eq!(check_node(Some(&Version::new(20, 1, 0))).status, Status::Ok);
+        let old = check_node(Some(&Version::new(16, 20, 2)));
+        assert_eq!(old.status, Status::Warning);
+        assert!(old.fix.is_some());
+        assert_eq!(check_node(None).status, Status::Warning);
+    }
+}
@@ -0,0 +1,103 @@
+// Deployment lifecycle hooks
+//
+// The config's `hooks.preDeploy` and `hooks.postDeploy` name scripts run around
+// deployments: by `deploy` around the contract it deploys, and by `run` around
+// its script. They run like `run` scripts, against the same RPC server, so the
+// SDK works in them. `deploy` describes the deployment in GLIN_FORGE_DEPLOY_*
+// variables, and a failing preDeploy hook stops it.
+
+use anyhow::Context;
+use colored::Colorize;
+use std::path::Path;
+
+use crate::config::file::HooksConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PreDeploy,
+    PostDeploy,
+}
+
+impl Hook {
+    /// Name of the hook in the config
+    pub fn name(self) -> &'static str {
+        match self {
+            Hook::PreDeploy => "preDeploy",
+            Hook::PostDeploy => "postDeploy",
+        }
+    }
+
+    fn script(self, hooks: &HooksConfig) -> Option<&Path> {
+        match self {
+            Hook::PreDeploy => hooks.pre_deploy.as_deref(),
+            Hook::PostDeploy => hooks.post_deploy.as_deref(),
+        }
+    }
+}
+
+/// Hooks of the project config; none without a config file
+pub fn configured() -> anyhow::Result<HooksConfig> {
+    match crate::config::file::find_config_file() {
+        Ok(path) => Ok(crate::config::file::load_config_file(Some(&path))?.hooks),
+        Err(_) => Ok(HooksConfig::default()),
+    }
+}
+
+/// Whether any hook is configured, so that a command needs an RPC server for them
+pub fn any(hooks: &HooksConfig) -> bool {
+    hooks.pre_deploy.is_some() || hooks.post_deploy.is_some()
+}
+
+/// Run a hook's script, if configured, with `env` added to its environment
+pub async fn run(hook: Hook, hooks: &HooksConfig, env: &[(&str, String)]) -> anyhow::Result<()> {
+    let Some(script) = hook.script(hooks) else {
+        return Ok(());
+    };
+    println!(
+        "\n{} Running {} hook {}",
+        "→".cyan(),
+        hook.name(),
+        script.display()
+    );
+    let extension = crate::cli::run::script_extension(script)
+        .with_context(|| format!("Invalid {} hook", hook.name()))?;
+    let mut command = crate::cli::run::script_command(script, extension)?;
+    command.envs(env.iter().map(|(name, value)| (name, value)));
+
+    let status = command
+        .status()
+        .await
+        .with_context(|| format!("Failed to execute the {} hook", hook.name()))?;
+    if !status.success() {
+        anyhow::bail!(
+            "{} hook {} exited with code: {}",
+            hook.name(),
+            script.display(),
+            status.code().unwrap_or(-1)
+        );
+    }
+    println!("{} {} hook completed", "✓".green(), hook.name());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_hook() {
+        let hooks: HooksConfig =
+            serde_json::from_str(r#"{"preDeploy": "scripts/fund.ts"}"#).unwrap();
+        assert_eq!(
+            Hook::PreDeploy.script(&hooks),
+            Some(Path::new("scripts/fund.ts"))
+        );
+        assert!(any(&hooks));
+        assert!(!any(&HooksConfig::default()));
+
+        // Unconfigured hooks do nothing, missing scripts fail
+        run(Hook::PostDeploy, &hooks, &[]).await.unwrap();
+        let error = run(Hook::PreDeploy, &hooks, &[]).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("Invalid preDeploy hook"));
+    }
+}
@@ -0,0 +1,458 @@
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+use serde_json::{json, Value as JsonValue};
+use std::path::{Path, PathBuf};
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::utils::AccountId32;
+
+use crate::contract::events::EventDecoder;
+use crate::indexer::checkpoint::{common_ancestor, is_reorg, Checkpoint, MAX_REORG_DEPTH};
+use crate::indexer::{EventStore, IndexedEvent, QueryRows};
+
+/// Database used when --db is omitted
+const DEFAULT_DB: &str = "events.sqlite";
+
+/// Metadata of the workspace contract, used when --metadata is omitted
+const DEFAULT_METADATA: &str = "target/ink/metadata.json";
+
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct IndexArgs {
+    #[command(subcommand)]
+    command: Option<IndexCommands>,
+
+    /// Contract address to index, or the name of a contract in the deployments registry
+    #[arg(required = true)]
+    pub address: Option<String>,
+
+    /// SQLite database receiving the events
+    #[arg(long, default_value = DEFAULT_DB)]
+    pub db: PathBuf,
+
+    /// Network to connect to
+    #[arg(short, long, default_value = "testnet")]
+    pub network: String,
+
+    /// Contract metadata used to decode events [default: target/ink/metadata.json when present]
+    #[arg(short, long)]
+    pub metadata: Option<String>,
+
+    /// Backfill from this block before following finalized blocks [default: after the last checkpoint]
+    #[arg(long)]
+    pub from_block: Option<u64>,
+
+    /// Backfill from the block the contract was instantiated in, per the deployments registry or the chain
+    #[arg(long, conflicts_with = "from_block")]
+    pub from_deployment: bool,
+
+    /// Follow best blocks instead of finalized ones, rewinding on chain reorganizations
+    #[arg(long)]
+    pub unfinalized: bool,
+}
+
+#[derive(Subcommand)]
+enum IndexCommands {
+    /// Run SQL against an event database
+    Query {
+        /// SQL statement (tables: blocks, events, topics)
+        sql: String,
+
+        /// SQLite database to query
+        #[arg(long, default_value = DEFAULT_DB)]
+        db: PathBuf,
+
+        /// Print rows as a JSON array of objects
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Serve indexed events over REST and GraphQL
+    Serve {
+        /// SQLite database to serve
+        #[arg(long, default_value = DEFAULT_DB)]
+        db: PathBuf,
+
+        /// Port to listen on
+        #[arg(short, long, default_value = "4000")]
+        port: u16,
+
+        /// Address to bind
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+    },
+}
+
+pub async fn execute(args: IndexArgs) -> anyhow::Result<()> {
+    match &args.command {
+        Some(IndexCommands::Query { sql, db, json }) => query(sql, db, *json),
+        Some(IndexCommands::Serve { db, port, host }) => serve(db, host, *port).await,
+        None => ingest(&args).await,
+    }
+}
+
+async fn ingest(args: &IndexArgs) -> anyhow::Result<()> {
+    let label = args
+        .address
+        .as_deref()
+        .context("Contract address required")?;
+    let (address, _) = crate::config::deployments::resolve(&args.network, label)?;
+    let address = address.as_str();
+    let contract = crate::contract::parse_account_id(address)?;
+
+    println!(
+        "{}",
+        format!("Indexing contract events: {}", label).cyan().bold()
+    );
+
+    let network_config = crate::config::load_network(&args.network)?;
+    let mut store = EventStore::open(&args.db)?;
+
+    println!("\n{}", "Configuration:".bold());
+    println!("  {} {}", "Contract:".cyan(), address);
+    println!("  {} {}", "Network:".cyan(), args.network);
+    println!("  {} {}", "Database:".cyan(), args.db.display());
+
+    let metadata_path = args.metadata.clone().or_else(|| {
+        Path::new(DEFAULT_METADATA)
+            .exists()
+            .then(|| DEFAULT_METADATA.to_string())
+    });
+    let decoder = match &metadata_path {
+        Some(path) => {
+            println!("  {} {}", "Metadata:".cyan(), path);
+            Some(EventDecoder::load(path)?)
+        }
+        None => {
+            println!(
+                "  {} No metadata, storing raw payloads (use {})",
+                "⚠".yellow(),
+                "--metadata".yellow()
+            );
+            None
+        }
+    };
+
+    println!("\n{}", "Connecting to network...".cyan());
+    let client = glin_client::create_client(&network_config.rpc).await?;
+    let rpc = glin_client::create_rpc_client(&network_config.rpc).await?;
+    println!("{} Connected to {}", "✓".green(), network_config.rpc);
+
+    // Resume after the checkpoint unless told where to start
+    let mut next = match args.from_block {
+        Some(from) => Some(from),
+        None if args.from_deployment => {
+            let block = crate::config::deployments::instantiation_block(
+                &client,
+                &rpc,
+                &args.network,
+                &contract,
+            )
+            .await?;
+            println!("{} {} instantiated at block #{}", "✓".green(), label, block);
+            Some(block)
+        }
+        None => match store.checkpoint(&args.network, address)? {
+            Some(checkpoint) => {
+                let ancestor = if checkpoint_canonical(&rpc, &checkpoint).await? {
+                    checkpoint.block
+                } else {
+                    rewind(&mut store, &rpc).await?
+                };
+                println!("{} Resuming after block #{}", "✓".green(), ancestor);
+                Some(ancestor + 1)
+            }
+            None => None,
+        },
+    };
+
+    let mode = if args.unfinalized {
+        "best"
+    } else {
+        "finalized"
+    };
+    println!("\n{}", format!("Indexing {} blocks...", mode).cyan());
+    println!("{}", "Press Ctrl+C to stop\n".dimmed());
+
+    let mut blocks_sub = if args.unfinalized {
+        client.blocks().subscribe_best().await?
+    } else {
+        client.blocks().subscribe_finalized().await?
+    };
+
+    while let Some(block_result) = blocks_sub.next().await {
+        let block = block_result?;
+        let number = block.number() as u64;
+        let hash = format!("{:?}", block.hash());
+        let parent = format!("{:?}", block.header().parent_hash);
+
+        if is_reorg(number, &hash, &parent, |n| {
+            store.block_hash(n).ok().flatten()
+        }) {
+            let ancestor = rewind(&mut store, &rpc).await?;
+            next = Some(ancestor + 1);
+        } else if next.is_some_and(|n| number < n) {
+            // Already ingested
+            continue;
+        }
+
+        // Backfill up to the followed block, and any block the subscription skipped
+        if let Some(from) = next {
+            for block_num in from..number {
+                let hash = rpc
+                    .chain_get_block_hash(Some(block_num.into()))
+                    .await?
+                    .with_context(|| format!("Block #{} not found", block_num))?;
+                let block = client.blocks().at(hash).await?;
+                store_block(&mut store, &block, &contract, decoder.as_ref()).await?;
+                store.save_checkpoint(
+                    &args.network,
+                    address,
+                    &Checkpoint {
+                        block: block_num,
+                        hash: format!("{:?}", hash),
+                    },
+                )?;
+            }
+        }
+
+        store_block(&mut store, &block, &contract, decoder.as_ref()).await?;
+        store.save_checkpoint(
+            &args.network,
+            address,
+            &Checkpoint {
+                block: number,
+                hash,
+            },
+        )?;
+        next = Some(number + 1);
+    }
+
+    println!(
+        "\n{} Subscription ended, {} events indexed",
+        "⚠".yellow(),
+        store.event_count()?
+    );
+
+    Ok(())
+}
+
+/// Whether a checkpointed block is still on the canonical chain
+async fn checkpoint_canonical(
+    rpc: &LegacyRpcMethods<glin_client::GlinConfig>,
+    checkpoint: &Checkpoint,
+) -> anyhow::Result<bool> {
+    let canonical = rpc
+        .chain_get_block_hash(Some(checkpoint.block.into()))
+        .await?;
+    Ok(canonical.is_some_and(|h| format!("{:?}", h) == checkpoint.hash))
+}
+
+/// Drop the ingested blocks a reorganization retracted; returns the common ancestor
+async fn rewind(
+    store: &mut EventStore,
+    rpc: &LegacyRpcMethods<glin_client::GlinConfig>,
+) -> anyhow::Result<u64> {
+    let known = store.recent_blocks(MAX_REORG_DEPTH)?;
+    let ancestor = common_ancestor(rpc, &known).await?;
+    let removed = store.rollback(ancestor)?;
+    println!(
+        "{} Chain reorganization: rewound to block #{}, {} events retracted",
+        "⚠".yellow(),
+        ancestor,
+        removed
+    );
+    Ok(ancestor)
+}
+
+/// Ingest the contract events of one block
+async fn store_block(
+    store: &mut EventStore,
+    block: &subxt::blocks::Block<glin_client::GlinConfig, glin_client::GlinClient>,
+    contract: &AccountId32,
+    decoder: Option<&EventDecoder>,
+) -> anyhow::Result<()> {
+    let number = block.number() as u64;
+    let events = block.events().await?;
+
+    let mut indexed = Vec::new();
+    for event in events.iter() {
+        let event = event?;
+        if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {
+            continue;
+        }
+        let (emitter, data) = crate::contract::events::contract_emitted(event.field_bytes())?;
+        if emitter != *contract {
+            continue;
+        }
+        let topics: Vec<[u8; 32]> = event.topics().iter().map(|topic| topic.0).collect();
+        indexed.push(indexed_event(
+            number,
+            event.index(),
+            &emitter,
+            &topics,
+            &data,
+            decoder,
+        ));
+    }
+
+    let inserted = store.insert_block(number, &format!("{:?}", block.hash()), &indexed)?;
+    if inserted > 0 {
+        let names: Vec<&str> = indexed
+            .iter()
+            .map(|e| e.name.as_deref().unwrap_or("(undecoded)"))
+            .collect();
+        println!(
+            "{} Block #{}: {} ({} total)",
+            "→".cyan(),
+            number,
+            names.join(", ").yellow(),
+            store.event_count()?
+        );
+    }
+
+    Ok(())
+}
+
+/// Storage record of a `ContractEmitted` payload; undecodable payloads are kept raw
+fn indexed_event(
+    block: u64,
+    event_index: u32,
+    contract: &AccountId32,
+    topics: &[[u8; 32]],
+    data: &[u8],
+    decoder: Option<&EventDecoder>,
+) -> IndexedEvent {
+    let decoded = decoder.map(|decoder| (decoder, decoder.decode(topics, data)));
+    let (name, payload, labels) = match decoded {
+        Some((decoder, Ok(event))) => (
+            Some(event.name.clone()),
+            event.to_json()["fields"].take(),
+            decoder.topic_labels(&event.name),
+        ),
+        _ => (
+            None,
+            json!({ "raw": format!("0x{}", hex::encode(data)) }),
+            Vec::new(),
+        ),
+    };
+
+    IndexedEvent {
+        block,
+        event_index,
+        contract: contract.to_string(),
+        name,
+        data: payload,
+        topics: topics
+            .iter()
+            .enumerate()
+            .map(|(i, topic)| (labels.get(i).cloned().flatten(), *topic))
+            .collect(),
+    }
+}
+
+fn query(sql: &str, db: &Path, json: bool) -> anyhow::Result<()> {
+    let store = EventStore::open_read_only(db)?;
+    let rows = store.query(sql)?;
+
+    if json {
+        let objects: Vec<JsonValue> = rows
+            .rows
+            .iter()
+            .map(|row| {
+                JsonValue::Object(
+                    rows.columns
+                        .iter()
+                        .cloned()
+                        .zip(row.iter().cloned())
+                        .collect(),
+                )
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&objects)?);
+        return Ok(());
+    }
+
+    print!("{}", format_table(&rows));
+    println!("{}", format!("({} rows)", rows.rows.len()).dimmed());
+    Ok(())
+}
+
+async fn serve(db: &Path, host: &str, port: u16) -> anyhow::Result<()> {
+    let store = EventStore::open_read_only(db)?;
+    let addr: std::net::SocketAddr = format!("{}:{}", host, port)
+        .parse()
+        .with_context(|| format!("Invalid address {}:{}", host, port))?;
+
+    println!("{}", "Serving indexed events".cyan().bold());
+    println!("\n{}", "Configuration:".bold());
+    println!("  {} {}", "Database:".cyan(), db.display());
+    println!("  {} {}", "Events:".cyan(), store.event_count()?);
+    println!("  {} http://{}", "REST:".cyan(), addr);
+    println!("  {} http://{}/graphql", "GraphQL:".cyan(), addr);
+    println!("\n{}", "Press Ctrl+C to stop".dimmed());
+
+    crate::indexer::server::serve(store, addr).await
+}
+
+/// Rows as a left-aligned text table
+fn format_table(rows: &QueryRows) -> String {
+    let cell = |value: &JsonValue| match value {
+        JsonValue::Null => "NULL".to_string(),
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+
+    let mut widths: Vec<usize> = rows.columns.iter().map(|c| c.chars().count()).collect();
+    for row in &rows.rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell(value).chars().count());
+        }
+    }
+
+    let line = |cells: Vec<String>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<w$}", c, w = *w))
+            .collect();
+        format!("{}\n", padded.join("  ").trim_end())
+    };
+
+    let mut output = line(rows.columns.clone());
+    output.push_str(&line(widths.iter().map(|w| "-".repeat(*w)).collect()));
+    for row in &rows.rows {
+        output.push_str(&line(row.iter().map(cell).collect()));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexed_event_without_metadata() {
+        let contract = AccountId32([1; 32]);
+        let event = indexed_event(5, 2, &contract, &[[9; 32]], &[0xde, 0xad], None);
+        assert_eq!(event.name, None);
+        assert_eq!(event.data, json!({ "raw": "0xdead" }));
+        assert_eq!(event.topics, [(None, [9; 32])]);
+        assert_eq!(event.contract, contract.to_string());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows = QueryRows {
+            columns: vec!["name".to_string(), "count".to_string()],
+            rows: vec![
+                vec![json!("Transfer"), json!(12)],
+                vec![JsonValue::Null, json!(1)],
+            ],
+        };
+        assert_eq!(
+            format_table(&rows),
+            "name      count\n--------  -----\nTransfer  12\nNULL      1\n"
+        );
+    }
+}
@@ -13,7 +13,7 @@ pub struct InstantiateArgs {
     pub metadata: Option<PathBuf>,
 
     /// Constructor arguments (comma-separated)
-    #[arg(short, long)]
+    #[arg(long)]
     pub args: Option<String>,
 
     /// Value to transfer to contract (in GLIN)
@@ -143,6 +143,7 @@ pub async fn execute(args: InstantiateArgs) -> anyhow::Result<()> {
         constructor_args,
         None,
         value_u128,
+        args.salt.as_deref(),
         &signer,
     )
     .await?;
@@ -154,7 +155,7 @@ pub async fn execute(args: InstantiateArgs) -> anyhow::Result<()> {
         );
         println!("\n{}", "Contract info:".bold());
 
-        if let Some(addr) = result.contract_address {
+        if let Some(addr) = &result.contract_address {
             println!("  {} {}", "Address:".cyan(), addr);
 
             if let Some(explorer) = network_config.explorer {
@@ -174,9 +175,20 @@ pub async fn execute(args: InstantiateArgs) -> anyhow::Result<()> {
             println!("    {} glin-forge query {} <method>", "".dimmed(), addr);
         }
 
-        if let Some(hash) = result.tx_hash {
+        if let Some(hash) = &result.tx_hash {
             println!("\n  {} {}", "Transaction:".cyan(), hash);
         }
+
+        if let Some(block) = result.block_number {
+            println!("  {} #{}", "Block:".cyan(), block);
+        }
+
+        crate::cli::deploy::record_deployment(
+            &args.network,
+            &metadata_json,
+            &metadata_path,
+            &result,
+        )?;
     } else {
         anyhow::bail!(
             "Instantiation failed: {}",
@@ -0,0 +1,373 @@
+// Migrations: ordered, idempotent deployment scripts
+//
+// `migrate` runs the scripts of `<paths.scripts>/deploy` (scripts/deploy by
+// default) in file name order, like `run` scripts, and records each one that
+// succeeds per network in `.glin-forge/migrations.json`, so later runs skip it.
+// A script declares its tags and the tags (or migration names) it depends on as
+// literal arrays, read without running it:
+//
+//   export const tags = ['token'];
+//   export const dependencies = ['registry'];
+//
+// `--tags` runs only the migrations with one of the tags, after the migrations
+// they depend on.
+
+use anyhow::Context;
+use clap::Parser;
+use colored::Colorize;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cli::run::RpcServerArgs;
+use crate::config::migrations::{MigrationRecord, MigrationRegistry, MIGRATIONS_FILE};
+
+#[derive(Parser)]
+pub struct MigrateArgs {
+    /// Network to migrate (testnet, mainnet, local)
+    #[arg(short, long, default_value = "testnet")]
+    pub network: String,
+
+    /// Run only the migrations with one of these tags, and their dependencies (comma-separated)
+    #[arg(short, long, value_delimiter = ',')]
+    pub tags: Vec<String>,
+
+    /// Directory of the migration scripts [default: <paths.scripts>/deploy from the config]
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+
+    /// Forget the migrations applied to the network and run them all again
+    #[arg(long)]
+    pub reset: bool,
+
+    /// List the migrations that would run without running them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Runtime to run the migrations on [default: runtime in the config, or the first installed]
+    #[arg(long, value_parser = ["node", "bun", "deno", "embedded"])]
+    pub runtime: Option<String>,
+
+    #[command(flatten)]
+    pub server: RpcServerArgs,
+}
+
+/// A migration script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// File name without its extension, as recorded
+    pub name: String,
+    pub path: PathBuf,
+    pub tags: Vec<String>,
+    /// Tags, or names, of the migrations to apply first
+    pub dependencies: Vec<String>,
+}
+
+pub async fn execute(args: MigrateArgs) -> anyhow::Result<()> {
+    println!("{}", "Running migrations...".cyan().bold());
+
+    let dir = match args.dir {
+        Some(dir) => dir,
+        None => default_dir()?,
+    };
+    let migrations = discover(&dir)?;
+    let order = plan(&migrations, &args.tags)?;
+
+    println!("\n{}", "Migration details:".bold());
+    println!("  {} {}", "Directory:".cyan(), dir.display());
+    println!("  {} {}", "Network:".cyan(), args.network);
+    if !args.tags.is_empty() {
+        println!("  {} {}", "Tags:".cyan(), args.tags.join(", "));
+    }
+
+    let mut registry = MigrationRegistry::open_default()?;
+    if args.reset && !args.dry_run {
+        registry.reset(&args.network)?;
+        println!(
+            "{} Forgot the migrations applied to {}",
+            "✓".green(),
+            args.network
+        );
+    }
+    let pending: Vec<&Migration> = order
+        .iter()
+        .map(|&index| &migrations[index])
+        .filter(|migration| {
+            args.reset || registry.applied(&args.network, &migration.name).is_none()
+        })
+        .collect();
+
+    println!();
+    for &index in &order {
+        let migration = &migrations[index];
+        let status = if pending.contains(&migration) {
+            "pending".yellow()
+        } else {
+            "applied".green()
+        };
+        println!("  {} {} ({})", "→".cyan(), migration.name, status);
+    }
+    if pending.is_empty() {
+        println!("\n{} Nothing to migrate on {}", "✓".green(), args.network);
+        return Ok(());
+    }
+    if args.dry_run {
+        return Ok(());
+    }
+
+    crate::config::runtime::configure(args.runtime.as_deref())?;
+    let rpc_server = crate::cli::run::serve(&args.network, &args.server, false).await?;
+    let result = async {
+        for migration in &pending {
+            apply(&args.network, migration).await?;
+            let applied_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default();
+            registry.record(
+                &args.network,
+                &migration.name,
+                MigrationRecord {
+                    applied_at,
+                    tags: migration.tags.clone(),
+                },
+            )?;
+        }
+        anyhow::Ok(())
+    }
+    .await;
+
+    if let Some(rpc_server) = rpc_server {
+        rpc_server.shutdown().await?;
+    }
+    result?;
+    println!(
+        "\n{} Applied {} migration(s) to {}, recorded in {}",
+        "✓".green().bold(),
+        pending.len(),
+        args.network,
+        MIGRATIONS_FILE
+    );
+    Ok(())
+}
+
+/// Migration scripts directory from the config's `paths.scripts`
+fn default_dir() -> anyhow::Result<PathBuf> {
+    let scripts = match crate::config::file::find_config_file() {
+        Ok(path) => {
+            crate::config::file::load_config_file(Some(&path))?
+                .paths
+                .scripts
+        }
+        Err(_) => "scripts".to_string(),
+    };
+    Ok(PathBuf::from(scripts).join("deploy"))
+}
+
+/// Run one migration script
+async fn apply(network: &str, migration: &Migration) -> anyhow::Result<()> {
+    println!("\n{} Applying {}", "→".cyan(), migration.name.bold());
+    println!("{}", "─".repeat(60));
+    let extension = crate::cli::run::script_extension(&migration.path)?;
+    let mut command = crate::cli::run::script_command(&migration.path, extension)?;
+    command.env("GLIN_FORGE_MIGRATION", &migration.name);
+    let status = command
+        .status()
+        .await
+        .with_context(|| format!("Failed to execute {}", migration.path.display()))?;
+    println!("{}", "─".repeat(60));
+
+    if !status.success() {
+        anyhow::bail!(
+            "Migration {} exited with code {}; it and the ones after it are not recorded on {}",
+            migration.name,
+            status.code().unwrap_or(-1),
+            network
+        );
+    }
+    println!("{} Applied {}", "✓".green(), migration.name);
+    Ok(())
+}
+
+/// Migration scripts of a directory, in file name order
+pub fn discover(dir: &Path) -> anyhow::Result<Vec<Migration>> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("No migrations directory {}", dir.display()))?;
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let script = path
+            .extension()
+            .is_some_and(|extension| matches!(extension.to_str(), Some("ts" | "js" | "rhai")));
+        if script && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut migrations: Vec<Migration> = Vec::with_capacity(paths.len());
+    for path in paths {
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if let Some(other) = migrations.iter().find(|m| m.name == name) {
+            anyhow::bail!(
+                "Migrations {} and {} have the same name",
+                other.path.display(),
+                path.display()
+            );
+        }
+        migrations.push(Migration {
+            name,
+            tags: declared_list(&source, "tags"),
+            dependencies: declared_list(&source, "dependencies"),
+            path,
+        });
+    }
+    Ok(migrations)
+}
+
+/// Strings of a literal array a script exports, as `export const <name> = [...]` or
+/// `exports.<name> = [...]`
+fn declared_list(source: &str, name: &str) -> Vec<String> {
+    static DECLARATION: OnceLock<Regex> = OnceLock::new();
+    static ITEM: OnceLock<Regex> = OnceLock::new();
+    let declaration = DECLARATION.get_or_init(|| {
+        Regex::new(
+            r"(?m)^\s*(?:export\s+const\s+|(?:module\.)?exports\.)(\w+)\s*(?::[^=]*)?=\s*\[([^\]]*)\]",
+        )
+        .expect("valid declaration pattern")
+    });
+    let item =
+        ITEM.get_or_init(|| Regex::new(r#"['"`]([^'"`]+)['"`]"#).expect("valid item pattern"));
+
+    declaration
+        .captures_iter(source)
+        .filter(|captures| &captures[1] == name)
+        .flat_map(|captures| {
+            item.captures_iter(&captures[2])
+                .map(|item| item[1].to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Indices of the migrations to apply, in order: those with one of `tags` (all when empty),
+/// each after the migrations it depends on
+pub fn plan(migrations: &[Migration], tags: &[String]) -> anyhow::Result<Vec<usize>> {
+    fn visit(
+        migrations: &[Migration],
+        index: usize,
+        visiting: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) -> anyhow::Result<()> {
+        if order.contains(&index) {
+            return Ok(());
+        }
+        if let Some(start) = visiting.iter().position(|&i| i == index) {
+            let cycle: Vec<&str> = visiting[start..]
+                .iter()
+                .chain([&index])
+                .map(|&i| migrations[i].name.as_str())
+                .collect();
+            anyhow::bail!("Migrations depend on each other: {}", cycle.join(" → "));
+        }
+        visiting.push(index);
+        let migration = &migrations[index];
+        for dependency in &migration.dependencies {
+            let providers: Vec<usize> = (0..migrations.len())
+                .filter(|&i| {
+                    migrations[i].name == *dependency || migrations[i].tags.contains(dependency)
+                })
+                .collect();
+            if providers.is_empty() {
+                anyhow::bail!(
+                    "Migration {} depends on '{}', which no migration name or tag matches",
+                    migration.name,
+                    dependency
+                );
+            }
+            for provider in providers {
+                visit(migrations, provider, visiting, order)?;
+            }
+        }
+        visiting.pop();
+        order.push(index);
+        Ok(())
+    }
+
+    let selected: Vec<usize> = (0..migrations.len())
+        .filter(|&i| tags.is_empty() || migrations[i].tags.iter().any(|tag| tags.contains(tag)))
+        .collect();
+    if selected.is_empty() && !tags.is_empty() {
+        anyhow::bail!("No migration has the tags {}", tags.join(", "));
+    }
+
+    let mut order = Vec::new();
+    for index in selected {
+        visit(migrations, index, &mut Vec::new(), &mut order)?;
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_migrations() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| std::fs::write(dir.path().join(name), content);
+        write(
+            "001_token.ts",
+            "import { deploy } from '@glin-forge/sdk';\nexport const tags = ['token', \"core\"];\nexport const dependencies: string[] = ['registry'];\n",
+        )
+        .unwrap();
+        write("002_registry.js", "exports.tags = ['registry'];\n").unwrap();
+        write(
+            "003_market.ts",
+            "export const tags = ['market'];\nexport const dependencies = ['001_token'];\n",
+        )
+        .unwrap();
+        write("notes.md", "not a migration").unwrap();
+
+        let migrations = discover(dir.path()).unwrap();
+        let names: Vec<&str> = migrations.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["001_token", "002_registry", "003_market"]);
+        assert_eq!(migrations[0].tags, ["token", "core"]);
+        assert_eq!(migrations[0].dependencies, ["registry"]);
+
+        let order = |tags: &[&str]| {
+            let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+            plan(&migrations, &tags).map(|order| {
+                order
+                    .iter()
+                    .map(|&i| migrations[i].name.clone())
+                    .collect::<Vec<_>>()
+            })
+        };
+        // Dependencies run first, whatever their file order
+        assert_eq!(
+            order(&[]).unwrap(),
+            ["002_registry", "001_token", "003_market"]
+        );
+        assert_eq!(order(&["market"]).unwrap(), order(&[]).unwrap());
+        assert_eq!(order(&["registry"]).unwrap(), ["002_registry"]);
+        assert!(order(&["missing"]).is_err());
+
+        let mut cyclic = migrations.clone();
+        cyclic[1].dependencies = vec!["market".to_string()];
+        let error = plan(&cyclic, &[]).unwrap_err();
+        assert!(
+            error.to_string().contains("depend on each other"),
+            "{}",
+            error
+        );
+        cyclic[1].dependencies = vec!["nothing".to_string()];
+        assert!(plan(&cyclic, &[]).is_err());
+    }
+}
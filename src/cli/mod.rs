@@ -1,20 +1,31 @@
 pub mod account;
 pub mod analyze;
 pub mod balance;
+pub mod bench;
 pub mod build;
 pub mod call;
 pub mod clean;
+pub mod completions;
 pub mod config;
 pub mod console;
+pub mod daemon;
 pub mod deploy;
+pub mod doctor;
+pub mod hooks;
+pub mod index;
 pub mod init;
 pub mod instantiate;
+pub mod migrate;
+pub mod monitor;
+pub mod native_console;
 pub mod network;
 pub mod new;
 pub mod query;
+pub mod rhai_script;
 pub mod run;
 pub mod test;
 pub mod typegen;
+pub mod ui;
 pub mod upload;
 pub mod verify;
 pub mod watch;
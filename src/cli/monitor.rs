@@ -0,0 +1,616 @@
+use anyhow::Context;
+use clap::Parser;
+use colored::Colorize;
+use serde_json::{json, Value as JsonValue};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use super::watch::{decode_emitted, deliver, event_contract, event_record, WatchedContract};
+use crate::config::file::{AlertConfig, WebhookConfig};
+
+/// Smallest units per GLIN
+const GLIN: u128 = 1_000_000_000_000_000_000;
+
+/// How often inactivity rules are checked
+const SILENCE_CHECK: Duration = Duration::from_secs(5);
+
+#[derive(Parser)]
+pub struct MonitorArgs {
+    /// Config file with the `alerts` and `watch.contracts` sections [default: glinforge.config.ts]
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Network to connect to
+    #[arg(short, long, default_value = "testnet")]
+    pub network: String,
+
+    /// Validate the alert rules and exit
+    #[arg(long)]
+    pub check: bool,
+}
+
+pub async fn execute(args: MonitorArgs) -> anyhow::Result<()> {
+    println!("{}", "Monitoring contract alerts".cyan().bold());
+
+    let config = crate::config::file::load_config_file(args.config.as_deref())?;
+    if config.alerts.is_empty() {
+        anyhow::bail!("No alerts in the `alerts` section of the config file");
+    }
+    if config.watch.contracts.is_empty() {
+        anyhow::bail!("No contracts in the `watch.contracts` section of the config file");
+    }
+
+    let contracts = config
+        .watch
+        .contracts
+        .iter()
+        .map(WatchedContract::from_config)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut alerts = config
+        .alerts
+        .iter()
+        .map(|alert| Alert::new(alert, &contracts))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    println!("\n{}", "Configuration:".bold());
+    for contract in &contracts {
+        println!(
+            "  {} {} {}",
+            "Contract:".cyan(),
+            contract.label.bold(),
+            contract.address
+        );
+    }
+    for alert in &alerts {
+        println!(
+            "  {} {} ({})",
+            "Alert:".cyan(),
+            alert.config.name.bold(),
+            alert.config.rule
+        );
+    }
+    println!("  {} {}", "Network:".cyan(), args.network);
+
+    if args.check {
+        println!("\n{} {} alert rules valid", "✓".green(), alerts.len());
+        return Ok(());
+    }
+
+    let network_config = crate::config::load_network(&args.network)?;
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    println!("\n{}", "Connecting to network...".cyan());
+    let client = glin_client::create_client(&network_config.rpc).await?;
+    println!("{} Connected to {}", "✓".green(), network_config.rpc);
+
+    println!("\n{}", "Monitoring finalized blocks...".cyan());
+    println!("{}", "Press Ctrl+C to stop\n".dimmed());
+
+    let mut blocks_sub = client.blocks().subscribe_finalized().await?;
+    let mut silence_check = tokio::time::interval(SILENCE_CHECK);
+
+    loop {
+        let mut triggered = Vec::new();
+        tokio::select! {
+            block_result = blocks_sub.next() => {
+                let Some(block_result) = block_result else {
+                    println!("\n{} Subscription ended", "⚠".yellow());
+                    return Ok(());
+                };
+                let block = block_result?;
+                let block_number = block.number() as u64;
+
+                for event in block.events().await?.iter() {
+                    let event = event?;
+                    if event.pallet_name() != "Contracts" {
+                        continue;
+                    }
+                    let emitter = event_contract(&event);
+                    let Some(contract) = contracts
+                        .iter()
+                        .find(|c| c.account.is_some() && c.account == emitter)
+                    else {
+                        continue;
+                    };
+                    let decoded = decode_emitted(
+                        &event,
+                        contract.decoder.as_ref(),
+                        contract.account.as_ref(),
+                    );
+                    let record =
+                        event_record(block_number, &contract.label, &event, decoded.as_ref())?;
+                    for alert in &mut alerts {
+                        let now = Instant::now();
+                        if let Some(message) = alert.on_event(&contract.address, &record, now) {
+                            triggered.push((alert.config.clone(), message, Some(record.clone())));
+                        }
+                    }
+                }
+            }
+            _ = silence_check.tick() => {
+                for alert in &mut alerts {
+                    if let Some(message) = alert.on_tick(Instant::now()) {
+                        triggered.push((alert.config.clone(), message, None));
+                    }
+                }
+            }
+        }
+
+        for (alert, message, record) in triggered {
+            trigger(&http, &args.network, &alert, &message, record.as_ref()).await;
+        }
+    }
+}
+
+/// Run the actions of a triggered alert; exits the process when the alert sets `exit`
+async fn trigger(
+    http: &reqwest::Client,
+    network: &str,
+    alert: &AlertConfig,
+    message: &str,
+    record: Option<&JsonValue>,
+) {
+    println!(
+        "{} {} {}",
+        "⚠".yellow().bold(),
+        format!("[{}]", alert.name).yellow().bold(),
+        message
+    );
+
+    let payload = json!({
+        "alert": alert.name,
+        "rule": alert.rule,
+        "message": message,
+        "network": network,
+        "event": record,
+    });
+
+    if let Some(url) = &alert.webhook {
+        let hook = WebhookConfig {
+            url: url.clone(),
+            events: Vec::new(),
+            headers: Default::default(),
+            retries: crate::config::file::default_webhook_retries(),
+        };
+        if let Err(e) = deliver(http, &hook, &payload, super::watch::WEBHOOK_BACKOFF).await {
+            println!("  {} Webhook {} failed: {}", "⚠".yellow(), url, e);
+        }
+    }
+
+    if let Some(command) = &alert.exec {
+        let (shell, flag) = if cfg!(windows) {
+            ("cmd", "/C")
+        } else {
+            ("sh", "-c")
+        };
+        let status = tokio::process::Command::new(shell)
+            .args([flag, command])
+            .env("GLIN_ALERT", &alert.name)
+            .env("GLIN_ALERT_MESSAGE", message)
+            .env("GLIN_ALERT_EVENT", payload["event"].to_string())
+            .status()
+            .await;
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => println!("  {} `{}` exited with {}", "⚠".yellow(), command, status),
+            Err(e) => println!("  {} Failed to run `{}`: {}", "⚠".yellow(), command, e),
+        }
+    }
+
+    if let Some(code) = alert.exit {
+        println!("\n{} Exiting with code {}", "✗".red(), code);
+        std::process::exit(code);
+    }
+}
+
+/// A configured alert and its trigger state
+struct Alert {
+    config: AlertConfig,
+    rule: Rule,
+    /// Addresses the rule applies to; all watched contracts when empty
+    contracts: Vec<String>,
+    last_triggered: Option<Instant>,
+    /// Last matching event, or the start of monitoring
+    last_seen: Instant,
+    /// Whether the current inactivity period was already reported
+    silence_reported: bool,
+}
+
+impl Alert {
+    fn new(config: &AlertConfig, watched: &[WatchedContract]) -> anyhow::Result<Self> {
+        let rule = config
+            .rule
+            .parse()
+            .with_context(|| format!("Invalid rule of alert '{}'", config.name))?;
+        let contracts = config
+            .contracts
+            .iter()
+            .map(|name| {
+                watched
+                    .iter()
+                    .find(|c| c.label == *name || c.address == *name)
+                    .map(|c| c.address.clone())
+                    .with_context(|| {
+                        format!(
+                            "Alert '{}': '{}' is not in watch.contracts",
+                            config.name, name
+                        )
+                    })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            config: config.clone(),
+            rule,
+            contracts,
+            last_triggered: None,
+            last_seen: Instant::now(),
+            silence_reported: false,
+        })
+    }
+
+    /// Trigger message for an event record, honouring the cooldown
+    fn on_event(&mut self, address: &str, record: &JsonValue, now: Instant) -> Option<String> {
+        if !self.contracts.is_empty() && !self.contracts.iter().any(|c| c == address) {
+            return None;
+        }
+        match &self.rule {
+            Rule::Silence { event, .. } => {
+                if event
+                    .as_deref()
+                    .is_none_or(|name| event_named(record, name))
+                {
+                    self.last_seen = now;
+                    self.silence_reported = false;
+                }
+                None
+            }
+            Rule::Event { event, condition } => {
+                if !event_named(record, event) {
+                    return None;
+                }
+                let detail = match condition {
+                    Some(condition) => {
+                        let actual = condition.field_value(record)?;
+                        if !condition.holds(actual) {
+                            return None;
+                        }
+                        format!(" ({} = {})", condition.field, display(actual))
+                    }
+                    None => String::new(),
+                };
+                let cooling = self.last_triggered.is_some_and(|last| {
+                    now.duration_since(last) < Duration::from_secs(self.config.cooldown)
+                });
+                if cooling {
+                    return None;
+                }
+                self.last_triggered = Some(now);
+                Some(format!(
+                    "{} in block #{} of {}{}",
+                    event,
+                    record["block"],
+                    display(&record["contract"]),
+                    detail
+                ))
+            }
+        }
+    }
+
+    /// Trigger message once an inactivity period is exceeded
+    fn on_tick(&mut self, now: Instant) -> Option<String> {
+        let Rule::Silence { event, duration } = &self.rule else {
+            return None;
+        };
+        if self.silence_reported || now.duration_since(self.last_seen) < *duration {
+            return None;
+        }
+        self.silence_reported = true;
+        Some(format!(
+            "No {} for {}",
+            event
+                .as_deref()
+                .map_or("events".to_string(), |e| format!("{} events", e)),
+            format_duration(*duration)
+        ))
+    }
+}
+
+/// Whether a record is the named contract event or pallet variant
+fn event_named(record: &JsonValue, name: &str) -> bool {
+    record["event"] == *name || record["variant"] == *name
+}
+
+fn display(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Parsed alert rule
+#[derive(Debug, Clone, PartialEq)]
+enum Rule {
+    /// `Event` or `Event.field <op> <value> [GLIN]`
+    Event {
+        event: String,
+        condition: Option<Condition>,
+    },
+    /// `no events for <duration>` or `no <Event> events for <duration>`
+    Silence {
+        event: Option<String>,
+        duration: Duration,
+    },
+}
+
+impl std::str::FromStr for Rule {
+    type Err = anyhow::Error;
+
+    fn from_str(rule: &str) -> anyhow::Result<Self> {
+        let tokens: Vec<&str> = rule.split_whitespace().collect();
+        match tokens.as_slice() {
+            [] => anyhow::bail!("Empty rule"),
+            ["no", "events", "for", duration] => Ok(Rule::Silence {
+                event: None,
+                duration: parse_duration(duration)?,
+            }),
+            ["no", event, "events", "for", duration] => Ok(Rule::Silence {
+                event: Some(event.to_string()),
+                duration: parse_duration(duration)?,
+            }),
+            [event] if !event.contains('.') => Ok(Rule::Event {
+                event: event.to_string(),
+                condition: None,
+            }),
+            [target, op, value @ ..] if !value.is_empty() => {
+                let (event, field) = target
+                    .split_once('.')
+                    .with_context(|| format!("Expected Event.field, found '{}'", target))?;
+                let op = match *op {
+                    ">" => Op::Gt,
+                    ">=" => Op::Ge,
+                    "<" => Op::Lt,
+                    "<=" => Op::Le,
+                    "==" => Op::Eq,
+                    "!=" => Op::Ne,
+                    other => anyhow::bail!("Unknown operator '{}' (>, >=, <, <=, ==, !=)", other),
+                };
+                Ok(Rule::Event {
+                    event: event.to_string(),
+                    condition: Some(Condition {
+                        field: field.to_string(),
+                        op,
+                        value: Threshold::parse(value)?,
+                    }),
+                })
+            }
+            _ => anyhow::bail!(
+                "Expected 'Event', 'Event.field <op> <value>' or 'no events for <duration>'"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Op {
+    fn compare<T: PartialOrd>(self, actual: T, expected: T) -> bool {
+        match self {
+            Op::Gt => actual > expected,
+            Op::Ge => actual >= expected,
+            Op::Lt => actual < expected,
+            Op::Le => actual <= expected,
+            Op::Eq => actual == expected,
+            Op::Ne => actual != expected,
+        }
+    }
+}
+
+/// Field comparison of an event rule
+#[derive(Debug, Clone, PartialEq)]
+struct Condition {
+    /// Field of the decoded event, dotted for nested fields
+    field: String,
+    op: Op,
+    value: Threshold,
+}
+
+impl Condition {
+    fn field_value<'a>(&self, record: &'a JsonValue) -> Option<&'a JsonValue> {
+        let pointer = format!("/data/{}", self.field.replace('.', "/"));
+        record.pointer(&pointer)
+    }
+
+    fn holds(&self, actual: &JsonValue) -> bool {
+        match &self.value {
+            Threshold::Amount(expected) => match integer(actual) {
+                Some(actual) => self.op.compare(actual, *expected),
+                // Negative or fractional values
+                None => display(actual)
+                    .parse::<f64>()
+                    .is_ok_and(|actual| self.op.compare(actual, *expected as f64)),
+            },
+            Threshold::Text(expected) => match self.op {
+                Op::Eq => display(actual) == *expected,
+                Op::Ne => display(actual) != *expected,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Right-hand side of a condition
+#[derive(Debug, Clone, PartialEq)]
+enum Threshold {
+    /// In smallest units, `GLIN` amounts being scaled by 10^18
+    Amount(u128),
+    Text(String),
+}
+
+impl Threshold {
+    fn parse(tokens: &[&str]) -> anyhow::Result<Self> {
+        if let [amount, unit] = tokens {
+            if unit.eq_ignore_ascii_case("glin") {
+                return Ok(Threshold::Amount(parse_glin(amount)?));
+            }
+        }
+        let text = tokens.join(" ");
+        Ok(match text.parse::<u128>() {
+            Ok(amount) => Threshold::Amount(amount),
+            Err(_) => Threshold::Text(text.trim_matches(|c| c == '"' || c == '\'').to_string()),
+        })
+    }
+}
+
+/// Decimal GLIN amount in smallest units
+pub(crate) fn parse_glin(amount: &str) -> anyhow::Result<u128> {
+    let invalid = || anyhow::anyhow!("Invalid GLIN amount '{}'", amount);
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if fraction.len() > 18 {
+        return Err(invalid());
+    }
+    let whole: u128 = whole.parse().map_err(|_| invalid())?;
+    let fraction: u128 = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<18}", fraction)
+            .parse()
+            .map_err(|_| invalid())?
+    };
+    whole
+        .checked_mul(GLIN)
+        .and_then(|n| n.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+/// Non-negative integer of a decoded value; 128-bit integers are decoded as strings
+fn integer(value: &JsonValue) -> Option<u128> {
+    match value {
+        JsonValue::Number(n) => n.as_u64().map(u128::from),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// `30s`, `15m`, `1h` or `2d`
+fn parse_duration(duration: &str) -> anyhow::Result<Duration> {
+    let invalid = || anyhow::anyhow!("Invalid duration '{}' (e.g. 30s, 15m, 1h, 2d)", duration);
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = duration.split_at(split);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(invalid()),
+    };
+    Ok(Duration::from_secs(amount * seconds))
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        s if s % 86400 == 0 => format!("{}d", s / 86400),
+        s if s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(rule: &str, cooldown: u64) -> Alert {
+        let config = AlertConfig {
+            name: "test".to_string(),
+            rule: rule.to_string(),
+            contracts: Vec::new(),
+            webhook: None,
+            exec: None,
+            exit: None,
+            cooldown,
+        };
+        Alert::new(&config, &[]).unwrap()
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(
+            "Transfer.value > 1000.5 GLIN".parse::<Rule>().unwrap(),
+            Rule::Event {
+                event: "Transfer".to_string(),
+                condition: Some(Condition {
+                    field: "value".to_string(),
+                    op: Op::Gt,
+                    value: Threshold::Amount(1_000_500_000_000_000_000_000),
+                }),
+            }
+        );
+        assert_eq!(
+            "no Transfer events for 1h".parse::<Rule>().unwrap(),
+            Rule::Silence {
+                event: Some("Transfer".to_string()),
+                duration: Duration::from_secs(3600),
+            }
+        );
+        assert!("Transfer.value ~ 3".parse::<Rule>().is_err());
+        assert!("no events for soon".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_evaluate_alerts() {
+        let record = |value: &str| {
+            json!({
+                "block": 9,
+                "contract": "token",
+                "variant": "ContractEmitted",
+                "event": "Transfer",
+                "data": { "to": "5Alice", "value": value },
+            })
+        };
+        let start = Instant::now();
+
+        let mut large = alert("Transfer.value >= 2 GLIN", 60);
+        assert_eq!(
+            large.on_event("5Token", &record("1999999999999999999"), start),
+            None
+        );
+        assert_eq!(
+            large.on_event("5Token", &record("2000000000000000000"), start),
+            Some("Transfer in block #9 of token (value = 2000000000000000000)".to_string())
+        );
+        // Cooling down
+        assert_eq!(
+            large.on_event("5Token", &record("3000000000000000000"), start),
+            None
+        );
+
+        let mut recipient = alert("Transfer.to == 5Alice", 0);
+        assert!(recipient.on_event("5Token", &record("1"), start).is_some());
+
+        let mut silence = alert("no events for 1m", 0);
+        silence.last_seen = start;
+        assert_eq!(silence.on_tick(start + Duration::from_secs(30)), None);
+        assert_eq!(
+            silence.on_tick(start + Duration::from_secs(61)),
+            Some("No events for 1m".to_string())
+        );
+        // Reported once per inactivity period
+        assert_eq!(silence.on_tick(start + Duration::from_secs(90)), None);
+        silence.on_event("5Token", &record("1"), start + Duration::from_secs(100));
+        assert!(silence.on_tick(start + Duration::from_secs(161)).is_some());
+    }
+}
@@ -0,0 +1,1260 @@
+// Native console
+//
+// `glin-forge console` reads commands in a line editor and runs them with the
+// functions behind `query`, `call` and `balance`, so it needs no JavaScript
+// runtime. Its contracts are the network's entries in the deployments
+// registry, each paired with its metadata under the artifacts directory or
+// target/ink (by code hash, else by name); Tab completes commands, contract
+// names, their method names and accounts.
+//
+// Every deployed contract is also an object, as in a JavaScript console:
+// `token.query.balanceOf(alice)` queries a method and `token.tx.transfer(bob,
+// 100)` calls it, with methods in camelCase or as labelled in the metadata and
+// account and contract names standing for their addresses.
+//
+// Lines read are kept in ~/.config/glin-forge/history across sessions;
+// `.save <file>` writes the session's lines to a file and `.load <file>` runs a
+// file's lines. `--exec` runs lines without reading any, for scripts.
+
+use anyhow::{Context, Result};
+use colored::*;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::Editor;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::deployments::DeploymentRegistry;
+
+/// Commands, with their arguments and what they do
+const COMMANDS: &[(&str, &str, &str)] = &[
+    (".contracts", "", "List deployed contracts and artifacts"),
+    (".methods", "<contract>", "List a contract's methods"),
+    (
+        ".query",
+        "<contract> <method> [args...]",
+        "Query a method (read-only)",
+    ),
+    (
+        ".call",
+        "<contract> <method> [args...]",
+        "Call a method in a transaction",
+    ),
+    (
+        ".balance",
+        "[account|address]",
+        "Show a free balance [default: the account]",
+    ),
+    (
+        ".account",
+        "[name]",
+        "Show or set the account transactions are signed with",
+    ),
+    (".connect", "", "Reconnect to the network"),
+    (
+        ".network",
+        "[name]",
+        "Show the network, or switch to another one",
+    ),
+    (".save", "<file>", "Save the session's lines to a file"),
+    (".load", "<file>", "Run the lines of a file"),
+    (
+        "<contract>.query.<method>(args)",
+        "",
+        "Query a method, e.g. token.query.balanceOf(alice)",
+    ),
+    (
+        "<contract>.tx.<method>(args)",
+        "",
+        "Call a method, e.g. token.tx.transfer(bob, 100)",
+    ),
+    ("<contract>.address", "", "Show a contract's address"),
+    (".help", "", "Show this help"),
+    (".exit", "", "Exit the console"),
+];
+
+/// Dev accounts offered for completion
+pub(crate) const DEV_ACCOUNTS: &[&str] = &["alice", "bob", "charlie", "dave", "eve", "ferdie"];
+
+/// Contract metadata found on disk
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Artifact {
+    pub(crate) name: String,
+    pub(crate) path: PathBuf,
+    pub(crate) code_hash: Option<String>,
+    pub(crate) messages: Vec<Message>,
+}
+
+/// A message of a contract, as declared in its metadata
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Message {
+    pub(crate) label: String,
+    /// Names and type names of the arguments
+    pub(crate) args: Vec<(String, String)>,
+    pub(crate) mutates: bool,
+    pub(crate) payable: bool,
+}
+
+impl Message {
+    /// Name of the method on contract objects: the label in camelCase, as in polkadot.js
+    fn method_name(&self) -> String {
+        camel_case(&self.label)
+    }
+
+    pub(crate) fn signature(&self) -> String {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|(name, ty)| format!("{}: {}", name, ty))
+            .collect();
+        format!("{}({})", self.label, args.join(", "))
+    }
+}
+
+/// A contract the console knows the address of
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Contract {
+    pub(crate) name: String,
+    pub(crate) address: String,
+    /// Index of its metadata in the artifacts
+    pub(crate) artifact: Option<usize>,
+}
+
+/// Properties of contract objects holding their methods: queries, and transactions
+const METHOD_KINDS: &[&str] = &["query", "tx"];
+
+/// State of a console session
+struct Session {
+    network: String,
+    rpc: String,
+    client: Option<glin_client::GlinClient>,
+    account: String,
+    /// Directory of the artifacts, besides target/ink
+    artifacts_dir: PathBuf,
+    artifacts: Vec<Artifact>,
+    contracts: Vec<Contract>,
+    /// Lines run in this session, for `.save`
+    lines: Vec<String>,
+}
+
+/// Run the console until `.exit` or end of input, or run the `exec` lines only
+pub async fn run(
+    network: &str,
+    rpc: &str,
+    contract: Option<&str>,
+    artifacts: &Path,
+    exec: &[String],
+) -> Result<()> {
+    let mut session = Session {
+        network: network.to_string(),
+        rpc: rpc.to_string(),
+        client: None,
+        account: "alice".to_string(),
+        artifacts_dir: artifacts.to_path_buf(),
+        artifacts: Vec::new(),
+        contracts: Vec::new(),
+        lines: Vec::new(),
+    };
+    session.load()?;
+    if let Some(address) = contract {
+        crate::contract::parse_account_id(address)?;
+        if !session.contracts.iter().any(|c| c.address == address) {
+            session.contracts.push(Contract {
+                name: "contract".to_string(),
+                address: address.to_string(),
+                artifact: (session.artifacts.len() == 1).then_some(0),
+            });
+        }
+    }
+    // Lines given to run connect when they need to, and fail the command when one fails
+    if !exec.is_empty() {
+        for line in exec {
+            if !session.execute(line.trim()).await? {
+                break;
+            }
+        }
+        return Ok(());
+    }
+
+    if let Err(e) = session.connect().await {
+        println!("{} {:#}", "⚠".yellow(), e);
+        println!("  {}", "Run .connect to try again".dimmed());
+    }
+    println!(
+        "{}",
+        format!(
+            "  {} contract(s), {} artifact(s)",
+            session.contracts.len(),
+            session.artifacts.len()
+        )
+        .dimmed()
+    );
+    println!();
+
+    let mut editor: Editor<ConsoleHelper, DefaultHistory> =
+        Editor::new().context("Failed to start the line editor")?;
+    let history = history_file();
+    if let Some(history) = &history {
+        // Missing on the first session
+        let _ = editor.load_history(history);
+    }
+    editor.set_helper(Some(ConsoleHelper {
+        completions: session.completions(),
+    }));
+    loop {
+        let prompt = format!("{} ", format!("glin-forge({})>", session.network).cyan());
+        let line = match tokio::task::block_in_place(|| editor.readline(&prompt)) {
+            Ok(line) => line,
+            // Ctrl-C drops the line, Ctrl-D ends the session
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e).context("Failed to read input"),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+
+        match session.execute(line).await {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => println!("{} {:#}", "✗".red(), e),
+        }
+        // Commands may change the contracts
+        if let Some(helper) = editor.helper_mut() {
+            helper.completions = session.completions();
+        }
+    }
+
+    if let Some(history) = &history {
+        let saved = history
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| Ok(editor.save_history(history)?));
+        if let Err(e) = saved {
+            println!(
+                "{} Failed to save the history to {}: {}",
+                "⚠".yellow(),
+                history.display(),
+                e
+            );
+        }
+    }
+    println!("{}", "Disconnecting...".green());
+    Ok(())
+}
+
+/// File keeping the lines read in earlier sessions
+fn history_file() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config/glin-forge/history"))
+}
+
+impl Session {
+    /// Load the artifacts, and the contracts deployed on the network
+    fn load(&mut self) -> Result<()> {
+        self.artifacts = load_artifacts(&[&self.artifacts_dir, Path::new("target/ink")]);
+        let registry = DeploymentRegistry::open_default()?;
+        self.contracts = load_contracts(&registry, &self.network, &self.artifacts);
+        Ok(())
+    }
+
+    /// Disconnect, and connect to another network with its deployments
+    async fn switch_network(&mut self, network: &str) -> Result<()> {
+        let rpc = crate::cli::console::network_rpc(network)?;
+        self.client = None;
+        self.network = network.to_string();
+        self.rpc = rpc;
+        self.load()?;
+        println!(
+            "{} Switched to {}: {} contract(s)",
+            "→".cyan(),
+            self.network,
+            self.contracts.len()
+        );
+        if let Err(e) = self.connect().await {
+            println!("{} {:#}", "⚠".yellow(), e);
+            println!("  {}", "Run .connect to try again".dimmed());
+        }
+        Ok(())
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        self.client = None;
+        let client = glin_client::create_client(&self.rpc).await.map_err(|e| {
+            anyhow::anyhow!("Failed to connect to {}: {}", self.rpc, e.root_cause())
+        })?;
+        self.client = Some(client);
+        println!(
+            "{} Connected to {} ({})",
+            "✓".green(),
+            self.network,
+            self.rpc
+        );
+        Ok(())
+    }
+
+    async fn client(&mut self) -> Result<&glin_client::GlinClient> {
+        if self.client.is_none() {
+            let client = glin_client::create_client(&self.rpc).await.map_err(|e| {
+                anyhow::anyhow!("Failed to connect to {}: {}", self.rpc, e.root_cause())
+            })?;
+            self.client = Some(client);
+        }
+        Ok(self.client.as_ref().unwrap())
+    }
+
+    /// Run a command line; false once the session should end
+    async fn execute(&mut self, line: &str) -> Result<bool> {
+        let command = line.split_whitespace().next().unwrap_or_default();
+        if !matches!(command, ".save" | ".load" | ".exit") {
+            self.lines.push(line.to_string());
+        }
+        if !line.starts_with('.') {
+            self.evaluate(line).await?;
+            return Ok(true);
+        }
+        let words = split_words(line)?;
+        let (command, rest) = words.split_first().context("Empty command")?;
+        match command.as_str() {
+            ".exit" => return Ok(false),
+            ".save" => {
+                let [file] = rest else {
+                    anyhow::bail!("Usage: .save <file>");
+                };
+                let mut content = self.lines.join("\n");
+                content.push('\n');
+                std::fs::write(file, content)
+                    .with_context(|| format!("Failed to write {}", file))?;
+                println!(
+                    "{} Saved {} line(s) to {}",
+                    "✓".green(),
+                    self.lines.len(),
+                    file
+                );
+            }
+            ".load" => {
+                let [file] = rest else {
+                    anyhow::bail!("Usage: .load <file>");
+                };
+                let content = std::fs::read_to_string(file)
+                    .with_context(|| format!("Failed to read {}", file))?;
+                for (number, line) in content.lines().enumerate() {
+                    let line = line.trim();
+                    if line.is_empty() || line.starts_with("//") {
+                        continue;
+                    }
+                    println!("{}", format!("> {}", line).dimmed());
+                    let keep_going = Box::pin(self.execute(line))
+                        .await
+                        .with_context(|| format!("{} line {}", file, number + 1))?;
+                    if !keep_going {
+                        return Ok(false);
+                    }
+                }
+            }
+            ".help" => print_help(),
+            ".connect" => self.connect().await?,
+            ".network" => match rest {
+                [] => println!("  {} {} ({})", "Network:".cyan(), self.network, self.rpc),
+                [network] => self.switch_network(network).await?,
+                _ => anyhow::bail!("Usage: .network [name]"),
+            },
+            ".contracts" => self.list_contracts(),
+            ".methods" => {
+                let [contract] = rest else {
+                    anyhow::bail!("Usage: .methods <contract>");
+                };
+                // Contracts not deployed yet have their methods listed too
+                let artifact = match self.artifacts.iter().find(|a| &a.name == contract) {
+                    Some(artifact) => artifact,
+                    None => self.resolve(contract)?.1,
+                };
+                for message in &artifact.messages {
+                    let kind = match (message.mutates, message.payable) {
+                        (true, true) => "tx, payable",
+                        (true, false) => "tx",
+                        _ => "query",
+                    };
+                    println!("  {} {}", message.signature(), kind.dimmed());
+                }
+            }
+            ".query" => {
+                let [contract, method, args @ ..] = rest else {
+                    anyhow::bail!("Usage: .query <contract> <method> [args...]");
+                };
+                self.query(contract, method, args.to_vec()).await?;
+            }
+            ".call" => {
+                let [contract, method, args @ ..] = rest else {
+                    anyhow::bail!("Usage: .call <contract> <method> [args...]");
+                };
+                self.call(contract, method, args.to_vec()).await?;
+            }
+            ".balance" => {
+                let account = rest.first().unwrap_or(&self.account).clone();
+                self.balance(&account).await?;
+            }
+            ".account" => match rest.first() {
+                Some(account) => {
+                    let address = signer_address(account)?;
+                    self.account = account.clone();
+                    println!("{} Signing with {} ({})", "✓".green(), account, address);
+                }
+                None => println!(
+                    "  {} {} ({})",
+                    "Account:".cyan(),
+                    self.account,
+                    signer_address(&self.account)?
+                ),
+            },
+            _ => anyhow::bail!("Unknown command '{}'. Type .help for the commands", command),
+        }
+        Ok(true)
+    }
+
+    fn list_contracts(&self) {
+        if self.contracts.is_empty() {
+            println!(
+                "  {}",
+                format!("No contracts deployed on {}", self.network).dimmed()
+            );
+        }
+        for contract in &self.contracts {
+            let metadata = match contract.artifact {
+                Some(index) => self.artifacts[index].path.display().to_string(),
+                None => "no metadata".to_string(),
+            };
+            println!(
+                "  {} {} {}",
+                contract.name.bold(),
+                contract.address,
+                format!("({})", metadata).dimmed()
+            );
+        }
+        let undeployed: Vec<&str> = self
+            .artifacts
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.contracts.iter().any(|c| c.artifact == Some(*index)))
+            .map(|(_, artifact)| artifact.name.as_str())
+            .collect();
+        if !undeployed.is_empty() {
+            println!(
+                "  {}",
+                format!(
+                    "Not deployed on {}: {}",
+                    self.network,
+                    undeployed.join(", ")
+                )
+                .dimmed()
+            );
+        }
+    }
+
+    /// Address and metadata of a contract given by name or address
+    fn resolve(&self, contract: &str) -> Result<(String, &Artifact)> {
+        let known = self
+            .contracts
+            .iter()
+            .find(|c| c.name == contract || c.address == contract);
+        let (address, artifact) = match known {
+            Some(known) => (known.address.clone(), known.artifact),
+            None => {
+                if crate::contract::parse_account_id(contract).is_err() {
+                    anyhow::bail!(
+                        "{} is neither an address nor a contract deployed on {} (see .contracts)",
+                        contract,
+                        self.network
+                    );
+                }
+                (
+                    contract.to_string(),
+                    (self.artifacts.len() == 1).then_some(0),
+                )
+            }
+        };
+        let artifact = artifact.with_context(|| {
+            format!(
+                "No metadata found for {}; build it or copy it to the artifacts directory",
+                contract
+            )
+        })?;
+        Ok((address, &self.artifacts[artifact]))
+    }
+
+    /// Evaluate a contract object expression
+    async fn evaluate(&mut self, line: &str) -> Result<()> {
+        let expression = parse_expression(line)?;
+        let Some((kind, method, args)) = expression.call else {
+            let contract = self
+                .contracts
+                .iter()
+                .find(|c| c.name == expression.contract)
+                .with_context(|| {
+                    format!(
+                        "No contract {} deployed on {} (see .contracts)",
+                        expression.contract, self.network
+                    )
+                })?;
+            println!("{}", contract.address.green());
+            return Ok(());
+        };
+        let args = args
+            .iter()
+            .map(|arg| self.argument(arg))
+            .collect::<Result<Vec<_>>>()?;
+        match kind {
+            "tx" => self.call(&expression.contract, &method, args).await,
+            _ => self.query(&expression.contract, &method, args).await,
+        }
+    }
+
+    fn argument(&self, arg: &str) -> Result<String> {
+        argument(&self.contracts, arg)
+    }
+
+    async fn query(&mut self, contract: &str, method: &str, args: Vec<String>) -> Result<()> {
+        let (address, artifact) = self.resolve(contract)?;
+        let method = message_label(artifact, method)?;
+        let metadata = parse_artifact(artifact)?;
+        let rpc = self.rpc.clone();
+        let client = self.client().await?;
+        let result =
+            crate::contract::query_contract(client, &rpc, &address, &metadata, &method, args)
+                .await?;
+        if !result.success {
+            anyhow::bail!(
+                "Query failed: {}",
+                result.error.unwrap_or_else(|| "Unknown error".to_string())
+            );
+        }
+        match result.data {
+            Some(data) => println!("{}", data.green()),
+            None => println!("{}", "No data returned".yellow()),
+        }
+        Ok(())
+    }
+
+    async fn call(&mut self, contract: &str, method: &str, args: Vec<String>) -> Result<()> {
+        let (address, artifact) = self.resolve(contract)?;
+        let method = message_label(artifact, method)?;
+        let metadata = parse_artifact(artifact)?;
+        let signer = crate::rpc::accounts::signer(&self.account)?;
+        let client = self.client().await?;
+        let result =
+            crate::contract::call_contract(client, &address, &metadata, &method, args, 0, &signer)
+                .await?;
+        if !result.success {
+            anyhow::bail!(
+                "Transaction failed: {}",
+                result.error.unwrap_or_else(|| "Unknown error".to_string())
+            );
+        }
+        println!("{} Transaction successful", "✓".green());
+        if let Some(hash) = &result.tx_hash {
+            println!("  {} {}", "Hash:".cyan(), hash);
+        }
+        if let Some(block) = &result.block_hash {
+            println!("  {} {}", "Block:".cyan(), block);
+        }
+        if !result.events.is_empty() {
+            println!("  {} {}", "Events:".cyan(), result.events.join(", "));
+        }
+        Ok(())
+    }
+
+    async fn balance(&mut self, account: &str) -> Result<()> {
+        let address = match self.contracts.iter().find(|c| c.name == account) {
+            Some(contract) => contract.address.clone(),
+            None if crate::contract::parse_account_id(account).is_ok() => account.to_string(),
+            None => signer_address(account)?,
+        };
+        let account_id = crate::contract::parse_account_id(&address)?;
+        let client = self.client().await?;
+        let free = crate::rpc::methods::free_balance(client, &account_id).await?;
+        println!(
+            "  {} {} GLIN",
+            format!("{}:", address).cyan(),
+            crate::cli::balance::format_balance(free)
+        );
+        Ok(())
+    }
+
+    fn completions(&self) -> Completions {
+        let mut contracts = BTreeMap::new();
+        for contract in &self.contracts {
+            let methods = contract
+                .artifact
+                .map(|index| {
+                    self.artifacts[index]
+                        .messages
+                        .iter()
+                        .map(|message| message.label.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
+            contracts.insert(contract.name.clone(), methods);
+        }
+        let artifacts = self.artifacts.iter().map(|a| a.name.clone()).collect();
+        Completions {
+            contracts,
+            artifacts,
+            networks: crate::config::network_names().unwrap_or_default(),
+        }
+    }
+}
+
+fn print_help() {
+    println!("{}", "glin-forge console commands:".cyan());
+    for (command, args, description) in COMMANDS {
+        let usage = format!("{} {}", command, args);
+        println!("  {:<40} {}", usage.yellow(), description);
+    }
+    println!();
+    println!(
+        "  {}",
+        "Arguments are separated by spaces; quote the ones containing spaces.".dimmed()
+    );
+}
+
+/// Value of an expression argument: account and contract names stand for their address,
+/// quotes are removed and anything else is passed on as written
+pub(crate) fn argument(contracts: &[Contract], arg: &str) -> Result<String> {
+    if let Some(quoted) = unquote(arg) {
+        return Ok(quoted.to_string());
+    }
+    if let Some(contract) = contracts.iter().find(|c| c.name == arg) {
+        return Ok(contract.address.clone());
+    }
+    let identifier = arg.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && arg.starts_with(|c: char| c.is_alphabetic());
+    if identifier && !matches!(arg, "true" | "false") {
+        if let Ok(address) = signer_address(arg) {
+            return Ok(address);
+        }
+    }
+    Ok(arg.to_string())
+}
+
+/// Address of an account transactions can be signed with
+pub(crate) fn signer_address(account: &str) -> Result<String> {
+    let signer = crate::rpc::accounts::signer(account)?;
+    Ok(signer.public_key().to_account_id().to_string())
+}
+
+/// Label of the message a method name refers to
+pub(crate) fn message_label(artifact: &Artifact, method: &str) -> Result<String> {
+    artifact
+        .messages
+        .iter()
+        .find(|message| message.label == method || message.method_name() == method)
+        .map(|message| message.label.clone())
+        .with_context(|| {
+            format!(
+                "{} has no method {}; see .methods {}",
+                artifact.name, method, artifact.name
+            )
+        })
+}
+
+/// `balance_of` as `balanceOf`, `PSP22::balance_of` as `psp22::balanceOf`
+fn camel_case(label: &str) -> String {
+    let (namespace, method) = match label.rsplit_once("::") {
+        Some((namespace, method)) => (Some(namespace), method),
+        None => (None, label),
+    };
+    let mut camel = String::new();
+    for (i, word) in method.split('_').filter(|w| !w.is_empty()).enumerate() {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            if i == 0 {
+                camel.extend(first.to_lowercase());
+            } else {
+                camel.extend(first.to_uppercase());
+            }
+            camel.push_str(chars.as_str());
+        }
+    }
+    match namespace {
+        Some(namespace) => format!("{}::{}", namespace.to_lowercase(), camel),
+        None => camel,
+    }
+}
+
+/// A contract object expression: `contract.address`, or `contract.query.method(args)` and
+/// `contract.tx.method(args)` with the method's kind, name and arguments
+#[derive(Debug, PartialEq)]
+struct Expression {
+    contract: String,
+    call: Option<(&'static str, String, Vec<String>)>,
+}
+
+fn parse_expression(line: &str) -> Result<Expression> {
+    let usage = || {
+        anyhow::anyhow!(
+            "Expected <contract>.query.<method>(args), <contract>.tx.<method>(args) or a command (see .help)"
+        )
+    };
+    let (path, args) = match line.split_once('(') {
+        Some((path, rest)) => {
+            let args = rest.trim_end().strip_suffix(')').ok_or_else(usage)?;
+            (path.trim(), Some(split_args(args)?))
+        }
+        None => (line.trim(), None),
+    };
+    let (contract, member) = path.split_once('.').ok_or_else(usage)?;
+    match (member.split_once('.'), args) {
+        (None, None) if member == "address" => Ok(Expression {
+            contract: contract.to_string(),
+            call: None,
+        }),
+        (Some((kind, method)), Some(args)) => {
+            let kind = METHOD_KINDS
+                .iter()
+                .find(|name| **name == kind)
+                .ok_or_else(usage)?;
+            Ok(Expression {
+                contract: contract.to_string(),
+                call: Some((kind, method.to_string(), args)),
+            })
+        }
+        _ => Err(usage()),
+    }
+}
+
+/// Arguments between parentheses, split at commas outside of quotes and brackets; quoted
+/// arguments keep their quotes
+fn split_args(args: &str) -> Result<Vec<String>> {
+    let mut split = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    for c in args.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '{' | '(') => depth += 1,
+            (None, ']' | '}' | ')') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                split.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if quote.is_some() {
+        anyhow::bail!("Unterminated quote");
+    }
+    if !current.trim().is_empty() || !split.is_empty() {
+        split.push(current.trim().to_string());
+    }
+    Ok(split)
+}
+
+/// Contents of a quoted argument
+fn unquote(arg: &str) -> Option<&str> {
+    ['"', '\''].into_iter().find_map(|quote| {
+        arg.strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+    })
+}
+
+pub(crate) fn parse_artifact(artifact: &Artifact) -> Result<ink_metadata::InkProject> {
+    let json = std::fs::read_to_string(&artifact.path)
+        .with_context(|| format!("Failed to read {}", artifact.path.display()))?;
+    crate::contract::metadata::parse_metadata(&json)
+        .with_context(|| format!("Failed to parse {}", artifact.path.display()))
+}
+
+/// Contract metadata in the given directories, the first found of each contract name
+pub(crate) fn load_artifacts(dirs: &[&Path]) -> Vec<Artifact> {
+    fn collect(dir: &Path, found: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                collect(&path, found);
+            } else if path.extension().is_some_and(|e| e == "json") {
+                found.push(path);
+            }
+        }
+    }
+
+    let mut paths = Vec::new();
+    for dir in dirs {
+        collect(dir, &mut paths);
+    }
+    let mut artifacts: Vec<Artifact> = Vec::new();
+    for path in paths {
+        // Other JSON files may live next to the metadata
+        let Some(artifact) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .and_then(|json| artifact(&path, &json))
+        else {
+            continue;
+        };
+        if !artifacts.iter().any(|a| a.name == artifact.name) {
+            artifacts.push(artifact);
+        }
+    }
+    artifacts
+}
+
+/// Name, code hash and messages of ink! metadata JSON
+pub(crate) fn artifact(path: &Path, json: &serde_json::Value) -> Option<Artifact> {
+    let name = json["contract"]["name"].as_str()?.to_string();
+    let messages = json["spec"]["messages"]
+        .as_array()?
+        .iter()
+        .filter_map(|message| {
+            let args = message["args"]
+                .as_array()
+                .map(|args| {
+                    args.iter()
+                        .map(|arg| {
+                            let ty = arg["type"]["displayName"]
+                                .as_array()
+                                .and_then(|segments| segments.last())
+                                .and_then(|segment| segment.as_str())
+                                .unwrap_or("?");
+                            (
+                                arg["label"].as_str().unwrap_or("_").to_string(),
+                                ty.to_string(),
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(Message {
+                label: message["label"].as_str()?.to_string(),
+                args,
+                mutates: message["mutates"].as_bool().unwrap_or(false),
+                payable: message["payable"].as_bool().unwrap_or(false),
+            })
+        })
+        .collect();
+    Some(Artifact {
+        name,
+        path: path.to_path_buf(),
+        code_hash: json["source"]["hash"].as_str().map(str::to_lowercase),
+        messages,
+    })
+}
+
+/// Name, address and metadata path of the contracts deployed on a network, for the
+/// JavaScript console
+pub(crate) fn deployed_contracts(
+    network: &str,
+    artifacts: &Path,
+) -> Result<Vec<serde_json::Value>> {
+    let artifacts = load_artifacts(&[artifacts, Path::new("target/ink")]);
+    let registry = DeploymentRegistry::open_default()?;
+    Ok(load_contracts(&registry, network, &artifacts)
+        .into_iter()
+        .map(|contract| {
+            let metadata = contract.artifact.map(|index| {
+                let path = &artifacts[index].path;
+                path.canonicalize().unwrap_or(path.clone())
+            });
+            serde_json::json!({
+                "name": contract.name,
+                "address": contract.address,
+                "metadata": metadata,
+            })
+        })
+        .collect())
+}
+
+/// Contracts deployed on a network, with their metadata
+pub(crate) fn load_contracts(
+    registry: &DeploymentRegistry,
+    network: &str,
+    artifacts: &[Artifact],
+) -> Vec<Contract> {
+    registry
+        .deployments(network)
+        .map(|(name, deployment)| {
+            let code_hash = deployment.code_hash.as_deref().map(str::to_lowercase);
+            let artifact = artifacts
+                .iter()
+                .position(|a| code_hash.is_some() && a.code_hash == code_hash)
+                .or_else(|| artifacts.iter().position(|a| a.name == name));
+            Contract {
+                name: name.to_string(),
+                address: deployment.address.clone(),
+                artifact,
+            }
+        })
+        .collect()
+}
+
+/// Artifact of a contract of the registry, given by name or address
+pub(crate) fn deployed_artifact(network: &str, contract: &str) -> Option<Artifact> {
+    let mut artifacts = load_artifacts(&[Path::new("./artifacts"), Path::new("target/ink")]);
+    let registry = DeploymentRegistry::open_default().ok()?;
+    let index = load_contracts(&registry, network, &artifacts)
+        .into_iter()
+        .find(|c| c.name == contract || c.address == contract)?
+        .artifact?;
+    Some(artifacts.swap_remove(index))
+}
+
+/// Words of a command line, split at whitespace outside of quotes
+pub(crate) fn split_words(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote: Option<char> = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        anyhow::bail!("Unterminated quote");
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// What Tab completes: contract names and their method names, and artifact names
+#[derive(Debug, Default)]
+struct Completions {
+    contracts: BTreeMap<String, Vec<String>>,
+    artifacts: Vec<String>,
+    networks: Vec<String>,
+}
+
+impl Completions {
+    /// Start of the word being completed in `line`, and its candidates
+    fn candidates(&self, line: &str) -> (usize, Vec<String>) {
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let prefix = &line[start..];
+        let words: Vec<&str> = line[..start].split_whitespace().collect();
+
+        let options: Vec<String> = match words.as_slice() {
+            [] if !prefix.starts_with('.') => self.members(prefix),
+            [] => COMMANDS
+                .iter()
+                .map(|(c, _, _)| c.to_string())
+                .filter(|c| c.starts_with('.'))
+                .collect(),
+            [".methods"] => {
+                let undeployed = self
+                    .artifacts
+                    .iter()
+                    .filter(|a| !self.contracts.contains_key(*a));
+                self.contracts.keys().chain(undeployed).cloned().collect()
+            }
+            [".query" | ".call"] => self.contracts.keys().cloned().collect(),
+            [".query" | ".call", contract] => {
+                self.contracts.get(*contract).cloned().unwrap_or_default()
+            }
+            [".balance"] => DEV_ACCOUNTS
+                .iter()
+                .map(|a| a.to_string())
+                .chain(self.contracts.keys().cloned())
+                .collect(),
+            [".account"] => DEV_ACCOUNTS.iter().map(|a| a.to_string()).collect(),
+            [".network"] => self.networks.clone(),
+            _ => Vec::new(),
+        };
+        let candidates = options
+            .into_iter()
+            .filter(|option| option.starts_with(prefix))
+            .collect();
+        (start, candidates)
+    }
+
+    /// Contract object expressions continuing `path`
+    fn members(&self, path: &str) -> Vec<String> {
+        let parts: Vec<&str> = path.split('.').collect();
+        match parts.as_slice() {
+            [_] => self.contracts.keys().cloned().collect(),
+            [contract, _] if self.contracts.contains_key(*contract) => METHOD_KINDS
+                .iter()
+                .copied()
+                .chain(["address"])
+                .map(|member| format!("{}.{}", contract, member))
+                .collect(),
+            [contract, kind, _] if METHOD_KINDS.contains(kind) => self
+                .contracts
+                .get(*contract)
+                .into_iter()
+                .flatten()
+                .map(|label| format!("{}.{}.{}(", contract, kind, camel_case(label)))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Line editor helper completing with the session's contracts
+struct ConsoleHelper {
+    completions: Completions,
+}
+
+impl Completer for ConsoleHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, candidates) = self.completions.candidates(&line[..pos]);
+        let pairs = candidates
+            .into_iter()
+            .map(|candidate| Pair {
+                display: candidate.clone(),
+                replacement: candidate,
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for ConsoleHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ConsoleHelper {}
+
+impl Validator for ConsoleHelper {}
+
+impl rustyline::Helper for ConsoleHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::deployments::DeploymentRecord;
+
+    const FLIPPER: &str = r#"{
+        "source": { "hash": "0xC0DE" },
+        "contract": { "name": "flipper", "version": "0.1.0" },
+        "spec": {
+            "messages": [
+                { "label": "flip", "args": [], "mutates": true, "payable": false },
+                { "label": "get", "args": [], "mutates": false, "payable": false },
+                {
+                    "label": "set",
+                    "args": [{ "label": "value", "type": { "displayName": ["bool"], "type": 0 } }],
+                    "mutates": true,
+                    "payable": false
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_console_contracts() {
+        let dir = tempfile::tempdir().unwrap();
+        let artifacts_dir = dir.path().join("artifacts");
+        std::fs::create_dir_all(&artifacts_dir).unwrap();
+        std::fs::write(artifacts_dir.join("flipper.json"), FLIPPER).unwrap();
+        std::fs::write(artifacts_dir.join("package.json"), r#"{"name": "app"}"#).unwrap();
+
+        let artifacts = load_artifacts(&[&artifacts_dir, &dir.path().join("target/ink")]);
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].code_hash.as_deref(), Some("0xc0de"));
+        assert_eq!(artifacts[0].messages[2].signature(), "set(value: bool)");
+
+        // Deployments are paired with metadata by code hash, whatever their name
+        let mut registry = DeploymentRegistry::open(&dir.path().join("deployments.json")).unwrap();
+        let deployment = |code_hash: &str| DeploymentRecord {
+            address: "5Flip".to_string(),
+            code_hash: Some(code_hash.to_string()),
+            tx_hash: None,
+            block: None,
+            block_hash: None,
+        };
+        registry
+            .record("local", "main_flipper", deployment("0xc0de"))
+            .unwrap();
+        registry
+            .record("local", "other", deployment("0xbeef"))
+            .unwrap();
+        let contracts = load_contracts(&registry, "local", &artifacts);
+        assert_eq!(contracts[0].name, "main_flipper");
+        assert_eq!(contracts[0].artifact, Some(0));
+        assert_eq!(contracts[1].artifact, None);
+        assert!(load_contracts(&registry, "testnet", &artifacts).is_empty());
+    }
+
+    #[test]
+    fn test_console_completions() {
+        let mut completions = Completions::default();
+        completions.contracts.insert(
+            "flipper".to_string(),
+            vec!["flip".to_string(), "get".to_string()],
+        );
+        completions
+            .contracts
+            .insert("token".to_string(), Vec::new());
+        completions.artifacts = vec!["flipper".to_string(), "dex".to_string()];
+
+        assert_eq!(
+            completions.candidates(".q"),
+            (0, vec![".query".to_string()])
+        );
+        assert_eq!(
+            completions.candidates(".query "),
+            (7, vec!["flipper".to_string(), "token".to_string()])
+        );
+        assert_eq!(
+            completions.candidates(".call flipper fl"),
+            (14, vec!["flip".to_string()])
+        );
+        assert_eq!(
+            completions.candidates(".balance b"),
+            (9, vec!["bob".to_string()])
+        );
+        assert!(completions.candidates(".query flipper get x").1.is_empty());
+        // Artifacts not deployed yet only have their methods listed
+        assert_eq!(
+            completions.candidates(".methods ").1,
+            ["flipper", "token", "dex"]
+        );
+        assert!(completions.candidates(".query d").1.is_empty());
+
+        // Contract objects complete like properties
+        assert_eq!(completions.candidates("fl").1, ["flipper"]);
+        assert_eq!(completions.candidates("flipper.q").1, ["flipper.query"]);
+        assert_eq!(
+            completions.candidates("flipper.tx.").1,
+            ["flipper.tx.flip(", "flipper.tx.get("]
+        );
+    }
+
+    fn session() -> Session {
+        Session {
+            network: "local".to_string(),
+            rpc: "ws://localhost:9944".to_string(),
+            client: None,
+            account: "alice".to_string(),
+            artifacts_dir: PathBuf::from("artifacts"),
+            artifacts: Vec::new(),
+            contracts: vec![Contract {
+                name: "flipper".to_string(),
+                address: "5Flip".to_string(),
+                artifact: None,
+            }],
+            lines: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_console_save_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("session.txt");
+        let file = file.to_str().unwrap();
+
+        let mut first = session();
+        assert!(first.execute(".account bob").await.unwrap());
+        assert!(first.execute("flipper.address").await.unwrap());
+        assert!(first.execute(".bogus").await.is_err());
+        assert!(first.execute(&format!(".save {}", file)).await.unwrap());
+        assert_eq!(
+            std::fs::read_to_string(file).unwrap(),
+            ".account bob\nflipper.address\n.bogus\n"
+        );
+
+        // Loading stops at the first failing line, naming it
+        let mut second = session();
+        let error = second
+            .execute(&format!(".load {}", file))
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("line 3"), "{:#}", error);
+        assert_eq!(second.account, "bob");
+
+        std::fs::write(file, "// setup\n.account charlie\n.exit\n.account dave\n").unwrap();
+        let mut third = session();
+        assert!(!third.execute(&format!(".load {}", file)).await.unwrap());
+        assert_eq!(third.account, "charlie");
+        assert_eq!(third.lines, [".account charlie"]);
+    }
+
+    #[tokio::test]
+    async fn test_console_network() {
+        let mut session = session();
+        session.network = "testnet".to_string();
+        assert!(session.execute(".network nowhere").await.is_err());
+        assert_eq!(session.network, "testnet");
+
+        // The switch happens whether or not a node answers
+        session.execute(".network local").await.unwrap();
+        assert_eq!(session.network, "local");
+        assert_eq!(session.rpc, "ws://localhost:9944");
+
+        let completions = session.completions();
+        assert_eq!(completions.candidates(".network t").1, ["testnet"]);
+    }
+
+    #[test]
+    fn test_console_expressions() {
+        assert_eq!(
+            parse_expression(r#"token.query.balanceOf(alice, "a, b", [1, 2])"#).unwrap(),
+            Expression {
+                contract: "token".to_string(),
+                call: Some((
+                    "query",
+                    "balanceOf".to_string(),
+                    vec![
+                        "alice".to_string(),
+                        r#""a, b""#.to_string(),
+                        "[1, 2]".to_string()
+                    ]
+                )),
+            }
+        );
+        assert_eq!(
+            parse_expression("flipper.tx.flip()").unwrap().call,
+            Some(("tx", "flip".to_string(), Vec::new()))
+        );
+        assert_eq!(parse_expression("flipper.address").unwrap().call, None);
+        assert!(parse_expression("flipper.send.flip()").is_err());
+        assert!(parse_expression("flipper.query.get").is_err());
+
+        assert_eq!(camel_case("balance_of"), "balanceOf");
+        assert_eq!(camel_case("PSP22::total_supply"), "psp22::totalSupply");
+        assert_eq!(camel_case("get"), "get");
+
+        let session = session();
+        let alice = signer_address("alice").unwrap();
+        assert_eq!(session.argument("alice").unwrap(), alice);
+        assert_eq!(session.argument("flipper").unwrap(), "5Flip");
+        assert_eq!(session.argument("'alice'").unwrap(), "alice");
+        assert_eq!(session.argument("true").unwrap(), "true");
+        assert_eq!(session.argument("1000").unwrap(), "1000");
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#".call token transfer "5Grw a" '' 100"#).unwrap(),
+            [".call", "token", "transfer", "5Grw a", "", "100"]
+        );
+        assert!(split_words(r#".query "open"#).is_err());
+    }
+}
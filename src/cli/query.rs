@@ -3,7 +3,7 @@ use colored::Colorize;
 
 #[derive(Parser)]
 pub struct QueryArgs {
-    /// Contract address
+    /// Contract address, or name in the deployments registry
     pub address: String,
 
     /// Method name to query
@@ -37,12 +37,14 @@ pub async fn execute(args: QueryArgs) -> anyhow::Result<()> {
         println!("  {} {:?}", "Arguments:".cyan(), args.args);
     }
 
+    let (address, _) = crate::config::deployments::resolve(&args.network, &args.address)?;
+
     // Load metadata
     let metadata_path = if let Some(path) = args.metadata {
         path
     } else {
         // Try to find in current directory
-        find_metadata_for_contract(&args.address)?
+        find_metadata_for_contract(&args.network, &args.address)?
     };
 
     println!("  {} {}", "Metadata:".cyan(), metadata_path);
@@ -64,7 +66,7 @@ pub async fn execute(args: QueryArgs) -> anyhow::Result<()> {
     let result = crate::contract::query_contract(
         &client,
         &network_config.rpc,
-        &args.address,
+        &address,
         &metadata,
         &args.method,
         args.args.clone(),
@@ -99,7 +101,11 @@ pub async fn execute(args: QueryArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn find_metadata_for_contract(_address: &str) -> anyhow::Result<String> {
+fn find_metadata_for_contract(network: &str, contract: &str) -> anyhow::Result<String> {
+    if let Some(artifact) = crate::cli::native_console::deployed_artifact(network, contract) {
+        return Ok(artifact.path.to_string_lossy().into_owned());
+    }
+
     // Try to find metadata in common locations
     let possible_paths = vec!["target/ink/metadata.json", "contract.json", "abi.json"];
 
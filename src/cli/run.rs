@@ -1,73 +1,246 @@
-use clap::Parser;
+use anyhow::Context;
+use clap::{Args, Parser};
 use colored::Colorize;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use notify::{RecursiveMode, Watcher};
+use regex::Regex;
+use std::collections::HashSet;
+use std::io::IsTerminal;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc;
+
+use crate::cli::hooks::Hook;
+use crate::config::deployments::DeploymentRegistry;
+use crate::config::file::{HooksConfig, RpcConfig};
+use crate::config::journal::{Journal, JournalDeployment, JournalEntry};
+use crate::config::runtime::Runtime;
+use crate::rpc::network::Sandbox;
+use crate::rpc::server::Endpoint;
+
+/// Options of the RPC server, shared by `run` and `daemon start`
+#[derive(Args, Clone)]
+pub struct RpcServerArgs {
+    /// Other network scripts may target per request, besides --network (repeatable)
+    #[arg(long = "allow-network")]
+    pub allow_networks: Vec<String>,
+
+    /// Browser origin allowed to call the RPC server, e.g. a frontend dev server (repeatable)
+    #[arg(long = "allow-origin")]
+    pub allow_origins: Vec<String>,
+
+    /// Address the RPC server binds to [default: rpc.host in the config, or 127.0.0.1]
+    #[arg(long)]
+    pub rpc_host: Option<String>,
+
+    /// Port of the RPC server [default: rpc.port in the config, or a random one]
+    #[arg(long)]
+    pub rpc_port: Option<u16>,
+
+    /// Serve the RPC server on this unix socket instead of TCP
+    #[arg(long, conflicts_with_all = ["rpc_host", "rpc_port"])]
+    pub rpc_socket: Option<PathBuf>,
+
+    /// Serve Prometheus metrics of the RPC calls on GET /metrics
+    #[arg(long)]
+    pub metrics: bool,
+
+    /// Log every RPC call (method, duration, outcome) to stderr as a JSON line
+    #[arg(long)]
+    pub log_requests: bool,
+
+    /// Seconds an RPC call may run before it fails; 0 for no limit
+    #[arg(long, value_name = "SECS", default_value_t = crate::rpc::requests::DEFAULT_TIMEOUT.as_secs())]
+    pub request_timeout: u64,
+
+    /// Refuse requests acting on mainnet, or on any network with its RPC endpoint
+    #[arg(long)]
+    pub deny_mainnet: bool,
+
+    /// Refuse transactions transferring more than this many GLIN
+    #[arg(long, value_name = "GLIN", value_parser = crate::cli::monitor::parse_glin)]
+    pub max_value: Option<u128>,
+}
+
+impl RpcServerArgs {
+    /// Limits the flags put on the server's requests
+    pub fn sandbox(&self) -> Sandbox {
+        Sandbox {
+            deny_mainnet: self.deny_mainnet,
+            max_value: self.max_value,
+        }
+    }
+
+    /// The first flag changed from its default; a running daemon applies none of them
+    pub fn changed_flag(&self) -> Option<&'static str> {
+        [
+            ("--allow-network", !self.allow_networks.is_empty()),
+            ("--allow-origin", !self.allow_origins.is_empty()),
+            ("--rpc-host", self.rpc_host.is_some()),
+            ("--rpc-port", self.rpc_port.is_some()),
+            ("--rpc-socket", self.rpc_socket.is_some()),
+            ("--metrics", self.metrics),
+            ("--log-requests", self.log_requests),
+            (
+                "--request-timeout",
+                self.request_timeout != Self::default().request_timeout,
+            ),
+            ("--deny-mainnet", self.deny_mainnet),
+            ("--max-value", self.max_value.is_some()),
+        ]
+        .into_iter()
+        .find(|(_, changed)| *changed)
+        .map(|(flag, _)| flag)
+    }
+}
+
+impl Default for RpcServerArgs {
+    fn default() -> Self {
+        Self {
+            allow_networks: Vec::new(),
+            allow_origins: Vec::new(),
+            rpc_host: None,
+            rpc_port: None,
+            rpc_socket: None,
+            metrics: false,
+            log_requests: false,
+            request_timeout: crate::rpc::requests::DEFAULT_TIMEOUT.as_secs(),
+            deny_mainnet: false,
+            max_value: None,
+        }
+    }
+}
 
 #[derive(Parser)]
 pub struct RunArgs {
-    /// Path to TypeScript/JavaScript deployment script
+    /// Path to TypeScript/JavaScript (or Rhai) deployment script
     pub script: PathBuf,
 
     /// Network to run on (testnet, mainnet, local)
     #[arg(short, long, default_value = "testnet")]
     pub network: String,
 
-    /// Watch mode - rerun on file changes
+    /// Networks to run the script on instead, once each (comma-separated)
+    #[arg(long, value_delimiter = ',', conflicts_with_all = ["network", "watch"])]
+    pub networks: Vec<String>,
+
+    /// Run on the --networks at the same time, each line of output prefixed with its network
+    #[arg(long, requires = "networks")]
+    pub parallel: bool,
+
+    #[command(flatten)]
+    pub server: RpcServerArgs,
+
+    /// Rerun the script when it, a local module it imports or a contract artifact changes,
+    /// keeping the RPC server running
     #[arg(short, long)]
     pub watch: bool,
+
+    /// Run even if the journal shows a successful run with identical inputs on the network
+    #[arg(long)]
+    pub force: bool,
+
+    /// Runtime to run the script on [default: runtime in the config, or the first installed]
+    #[arg(long, value_parser = ["node", "bun", "deno", "embedded"])]
+    pub runtime: Option<String>,
 }
 
-pub async fn execute(args: RunArgs) -> anyhow::Result<()> {
-    println!("{}", "Starting glin-forge SDK runtime...".cyan().bold());
+/// Arguments of the hidden command running a script on the embedded engine or as Rhai
+#[derive(Parser)]
+pub struct EmbeddedArgs {
+    /// Script, or config file with --config
+    pub script: PathBuf,
 
-    // Validate script path exists
-    if !args.script.exists() {
-        anyhow::bail!("Script not found: {}", args.script.display());
-    }
+    /// Print the JSON of the module's default export instead
+    #[arg(long)]
+    pub config: bool,
+}
 
-    // Check if script is TypeScript or JavaScript
-    let extension = args
-        .script
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("");
+pub async fn execute(args: RunArgs) -> anyhow::Result<()> {
+    println!("{}", "Starting glin-forge SDK runtime...".cyan().bold());
 
-    if extension != "ts" && extension != "js" {
-        anyhow::bail!(
-            "Script must be a TypeScript (.ts) or JavaScript (.js) file, got: .{}",
-            extension
-        );
+    let extension = script_extension(&args.script)?;
+    crate::config::runtime::configure(args.runtime.as_deref())?;
+    let runtime = match extension {
+        "rhai" => "rhai",
+        _ => crate::config::runtime::for_script(&args.script, extension == "ts")?.name(),
+    };
+    if !args.networks.is_empty() {
+        return execute_networks(&args, extension, runtime).await;
     }
 
     println!("\n{}", "Script details:".bold());
     println!("  {} {}", "Path:".cyan(), args.script.display());
     println!("  {} {}", "Network:".cyan(), args.network);
+    println!("  {} {}", "Runtime:".cyan(), runtime);
 
-    // Start JSON-RPC server
-    println!("\n{}", "Starting RPC server...".cyan());
-    let rpc_server = crate::rpc::RpcServer::start(args.network.clone()).await?;
-    let port = rpc_server.port();
-    println!("{} RPC server listening on port {}", "✓".green(), port);
+    // Watched scripts rerun on purpose, so only single runs are journaled
+    let journaled = if args.watch {
+        None
+    } else {
+        let script = args.script.display().to_string();
+        let hash = inputs_hash(&script_sources(&args.script)?)?;
+        let journal = Journal::open_default()?;
+        if let Some(previous) = journal.executed(&script, &hash, &args.network) {
+            if !args.force && !confirm_rerun(previous)? {
+                println!("\n{} Skipped; pass --force to run it again", "→".cyan());
+                return Ok(());
+            }
+        }
+        Some((journal, script, hash))
+    };
+
+    let hooks = crate::cli::hooks::configured()?;
+    let before = DeploymentRegistry::open_default()?;
+    let started_at = unix_time();
+    let rpc_server = serve(&args.network, &args.server, journaled.is_some()).await?;
 
-    // Set environment variables for SDK
-    std::env::set_var("GLIN_FORGE_RPC_PORT", port.to_string());
-    std::env::set_var("GLIN_FORGE_NETWORK", &args.network);
+    let result = if args.watch {
+        watch_script(&args.script, extension, &hooks).await
+    } else {
+        async {
+            crate::cli::hooks::run(Hook::PreDeploy, &hooks, &[]).await?;
+
+            println!("\n{}", "Executing script...".cyan());
+            println!("{}", "─".repeat(60));
 
-    println!("\n{}", "Executing script...".cyan());
-    println!("{}", "─".repeat(60));
+            // Execute script
+            let result = execute_script(&args.script, extension, &[], None).await;
 
-    // Execute script
-    let result = execute_script(&args.script, extension).await;
+            println!("{}", "─".repeat(60));
+            result?;
 
-    println!("{}", "─".repeat(60));
+            crate::cli::hooks::run(Hook::PostDeploy, &hooks, &[]).await
+        }
+        .await
+    };
 
     // Shutdown RPC server
-    println!("\n{}", "Shutting down RPC server...".cyan());
-    rpc_server.shutdown().await?;
-    println!("{} RPC server stopped", "✓".green());
+    if let Some(rpc_server) = rpc_server {
+        println!("\n{}", "Shutting down RPC server...".cyan());
+        rpc_server.shutdown().await?;
+        println!("{} RPC server stopped", "✓".green());
+    }
+
+    if let Some((mut journal, script, hash)) = journaled {
+        let deployed = crate::rpc::deployed::take();
+        let deployments = produced_deployments(&args.network, &before, deployed)?;
+        journal.append(JournalEntry {
+            script,
+            hash,
+            network: args.network.clone(),
+            started_at,
+            success: result.is_ok(),
+            deployments,
+        })?;
+    }
 
     // Handle script result
     match result {
+        Ok(_) if args.watch => Ok(()),
         Ok(_) => {
             println!("\n{} Script completed successfully!", "✓".green().bold());
             Ok(())
@@ -79,38 +252,593 @@ pub async fn execute(args: RunArgs) -> anyhow::Result<()> {
     }
 }
 
-/// Execute a TypeScript or JavaScript script
-async fn execute_script(script: &PathBuf, extension: &str) -> anyhow::Result<()> {
-    let script_path = script.canonicalize()?;
-
-    // Determine runtime command based on file extension
-    let (command, args) = if extension == "ts" {
-        // Try to use tsx (faster) or ts-node (fallback)
-        if which::which("tsx").is_ok() {
-            ("tsx", vec![script_path.to_string_lossy().to_string()])
-        } else if which::which("ts-node").is_ok() {
-            ("ts-node", vec![script_path.to_string_lossy().to_string()])
+/// Run a script on the embedded engine or as Rhai, or print a config file
+pub async fn execute_embedded(args: EmbeddedArgs) -> anyhow::Result<()> {
+    tokio::task::spawn_blocking(move || {
+        if args.config {
+            let config = crate::config::embedded::evaluate_config(&args.script)?;
+            println!("{}", config);
+            Ok(())
+        } else if args.script.extension().is_some_and(|e| e == "rhai") {
+            crate::cli::rhai_script::run(&args.script)
         } else {
-            anyhow::bail!(
-                "TypeScript runtime not found. Please install tsx or ts-node:\n  npm install -g tsx\n  # or\n  npm install -g ts-node"
+            crate::config::embedded::run_script(&args.script)
+        }
+    })
+    .await?
+    // The parent command shows the script's output, not the chain of an error
+    .map_err(|e| anyhow::anyhow!("{:#}", e))
+}
+
+/// Outcome of a script on one of the networks of a multi-network run
+struct NetworkRun {
+    network: String,
+    result: anyhow::Result<()>,
+    deployments: usize,
+}
+
+/// Run a script once per network, one after another or all at once, each against an RPC
+/// server of its own, and sum up how every network fared
+async fn execute_networks(args: &RunArgs, extension: &str, runtime: &str) -> anyhow::Result<()> {
+    let mut networks: Vec<String> = Vec::new();
+    for network in &args.networks {
+        if !network.is_empty() && !networks.contains(network) {
+            networks.push(network.clone());
+        }
+    }
+    if networks.is_empty() {
+        anyhow::bail!("--networks names no network");
+    }
+    if args.parallel && (args.server.rpc_port.is_some() || args.server.rpc_socket.is_some()) {
+        anyhow::bail!(
+            "--parallel starts a server per network, so it cannot take --rpc-port or --rpc-socket"
+        );
+    }
+
+    println!("\n{}", "Script details:".bold());
+    println!("  {} {}", "Path:".cyan(), args.script.display());
+    println!("  {} {}", "Networks:".cyan(), networks.join(", "));
+    println!("  {} {}", "Runtime:".cyan(), runtime);
+    let mode = if args.parallel {
+        "parallel"
+    } else {
+        "sequential"
+    };
+    println!("  {} {}", "Mode:".cyan(), mode);
+
+    let script = args.script.display().to_string();
+    let hash = inputs_hash(&script_sources(&args.script)?)?;
+    let mut journal = Journal::open_default()?;
+    let mut pending = Vec::new();
+    for network in networks {
+        if let Some(previous) = journal.executed(&script, &hash, &network) {
+            if !args.force && !confirm_rerun(previous)? {
+                println!(
+                    "\n{} Skipped on {}; pass --force to run it again",
+                    "→".cyan(),
+                    network
+                );
+                continue;
+            }
+        }
+        pending.push(network);
+    }
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let hooks = crate::cli::hooks::configured()?;
+    let before = DeploymentRegistry::open_default()?;
+    let started_at = unix_time();
+    let mut runs = Vec::new();
+    let mut record = |network: &str, result: anyhow::Result<()>, deployed| -> anyhow::Result<()> {
+        let deployments = produced_deployments(network, &before, deployed)?;
+        journal.append(JournalEntry {
+            script: script.clone(),
+            hash: hash.clone(),
+            network: network.to_string(),
+            started_at,
+            success: result.is_ok(),
+            deployments: deployments.clone(),
+        })?;
+        runs.push(NetworkRun {
+            network: network.to_string(),
+            result,
+            deployments: deployments.len(),
+        });
+        Ok(())
+    };
+
+    if args.parallel {
+        let mut servers = Vec::new();
+        for network in &pending {
+            println!("\n{} {}", "Network:".bold(), network);
+            servers.push(server_for(network, &args.server, true).await?);
+        }
+        println!("\n{}", "Executing script...".cyan());
+        println!("{}", "─".repeat(60));
+        let width = pending.iter().map(String::len).max().unwrap_or_default();
+        let results =
+            futures::future::join_all(pending.iter().zip(&servers).map(|(network, (_, env))| {
+                let prefix = format!("[{:width$}]", network, width = width)
+                    .dimmed()
+                    .to_string();
+                let script = &args.script;
+                let hooks = &hooks;
+                async move {
+                    crate::cli::hooks::run(Hook::PreDeploy, hooks, env).await?;
+                    execute_script(script, extension, env, Some(&prefix)).await?;
+                    crate::cli::hooks::run(Hook::PostDeploy, hooks, env).await
+                }
+            }))
+            .await;
+        println!("{}", "─".repeat(60));
+        shutdown_servers(servers.into_iter().map(|(server, _)| server)).await?;
+
+        // Each server notes the network of the contracts it deployed
+        let deployed = crate::rpc::deployed::take();
+        for (network, result) in pending.iter().zip(results) {
+            let deployed = deployed
+                .iter()
+                .filter(|d| d.network.as_deref() == Some(network.as_str()))
+                .cloned()
+                .collect();
+            record(network, result, deployed)?;
+        }
+    } else {
+        for network in &pending {
+            println!("\n{} {}", "Network:".bold(), network);
+            let (server, env) = server_for(network, &args.server, true).await?;
+            let result = async {
+                crate::cli::hooks::run(Hook::PreDeploy, &hooks, &env).await?;
+                println!("\n{}", "Executing script...".cyan());
+                println!("{}", "─".repeat(60));
+                let result = execute_script(&args.script, extension, &env, None).await;
+                println!("{}", "─".repeat(60));
+                result?;
+                crate::cli::hooks::run(Hook::PostDeploy, &hooks, &env).await
+            }
+            .await;
+            shutdown_servers([server]).await?;
+            record(network, result, crate::rpc::deployed::take())?;
+        }
+    }
+
+    println!("\n{}", "Results:".bold());
+    let width = runs
+        .iter()
+        .map(|run| run.network.len())
+        .max()
+        .unwrap_or_default();
+    for run in &runs {
+        match &run.result {
+            Ok(()) => println!(
+                "  {} {:width$}  {} deployed",
+                "✓".green(),
+                run.network,
+                match run.deployments {
+                    1 => "1 contract".to_string(),
+                    count => format!("{} contracts", count),
+                },
+                width = width
+            ),
+            Err(e) => println!(
+                "  {} {:width$}  {:#}",
+                "✗".red(),
+                run.network,
+                e,
+                width = width
+            ),
+        }
+    }
+    let failed: Vec<&str> = runs
+        .iter()
+        .filter(|run| run.result.is_err())
+        .map(|run| run.network.as_str())
+        .collect();
+    if !failed.is_empty() {
+        println!(
+            "\n{} Script failed on {}",
+            "✗".red().bold(),
+            failed.join(", ")
+        );
+        anyhow::bail!(
+            "Script failed on {} of {} networks",
+            failed.len(),
+            runs.len()
+        );
+    }
+    println!(
+        "\n{} Script completed successfully on every network!",
+        "✓".green().bold()
+    );
+    Ok(())
+}
+
+/// Shut down the RPC servers a run started
+async fn shutdown_servers(
+    servers: impl IntoIterator<Item = Option<crate::rpc::RpcServer>>,
+) -> anyhow::Result<()> {
+    for rpc_server in servers.into_iter().flatten() {
+        println!("\n{}", "Shutting down RPC server...".cyan());
+        rpc_server.shutdown().await?;
+        println!("{} RPC server stopped", "✓".green());
+    }
+    Ok(())
+}
+
+/// Ask whether to run a script again that already ran with identical inputs; no when
+/// nobody can answer
+fn confirm_rerun(previous: &JournalEntry) -> anyhow::Result<bool> {
+    println!(
+        "\n{} {} already ran on {} with identical inputs, {} ago",
+        "⚠".yellow(),
+        previous.script,
+        previous.network,
+        format_age(unix_time().saturating_sub(previous.started_at))
+    );
+    for deployment in &previous.deployments {
+        println!(
+            "  {} {} {}",
+            "Deployed:".cyan(),
+            deployment.name.as_deref().unwrap_or("contract"),
+            deployment.address
+        );
+    }
+    if !std::io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    Ok(dialoguer::Confirm::new()
+        .with_prompt("Run it again?")
+        .default(false)
+        .interact()?)
+}
+
+/// Hash of a script's inputs: its sources' paths and contents
+pub(crate) fn inputs_hash(sources: &HashSet<PathBuf>) -> anyhow::Result<String> {
+    let root = std::env::current_dir()?.canonicalize()?;
+    let mut sources: Vec<&PathBuf> = sources.iter().collect();
+    sources.sort();
+
+    let mut inputs = Vec::new();
+    for source in sources {
+        let content = std::fs::read(source)
+            .with_context(|| format!("Failed to read {}", source.display()))?;
+        let path = source.strip_prefix(&root).unwrap_or(source);
+        inputs.extend_from_slice(path.to_string_lossy().as_bytes());
+        inputs.push(0);
+        inputs.extend_from_slice(&(content.len() as u64).to_le_bytes());
+        inputs.extend_from_slice(&content);
+    }
+    Ok(format!(
+        "0x{}",
+        hex::encode(sp_core_hashing::blake2_256(&inputs))
+    ))
+}
+
+/// Contracts a run deployed: those the RPC server instantiated, and those it recorded in the
+/// deployments registry, named after their registry entry
+fn produced_deployments(
+    network: &str,
+    before: &DeploymentRegistry,
+    deployed: Vec<crate::rpc::deployed::Deployed>,
+) -> anyhow::Result<Vec<JournalDeployment>> {
+    let after = DeploymentRegistry::open_default()?;
+    let name = |network: &str, address: &str| {
+        after
+            .deployments(network)
+            .find(|(_, deployment)| deployment.address == address)
+            .map(|(name, _)| name.to_string())
+    };
+
+    let mut deployments: Vec<JournalDeployment> = deployed
+        .into_iter()
+        .map(|deployed| {
+            let network = deployed.network.unwrap_or_else(|| network.to_string());
+            JournalDeployment {
+                name: name(&network, &deployed.address),
+                network,
+                address: deployed.address,
+                code_hash: deployed.code_hash,
+                tx_hash: deployed.tx_hash,
+            }
+        })
+        .collect();
+    for (recorded, deployment) in after.deployments(network) {
+        let known = deployments.iter().any(|d| d.address == deployment.address);
+        let changed = before.find(network, recorded).map(|(_, d)| d) != Some(deployment);
+        if changed && !known {
+            deployments.push(JournalDeployment {
+                name: Some(recorded.to_string()),
+                network: network.to_string(),
+                address: deployment.address.clone(),
+                code_hash: deployment.code_hash.clone(),
+                tx_hash: deployment.tx_hash.clone(),
+            });
+        }
+    }
+    Ok(deployments)
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..=119 => format!("{}s", seconds),
+        120..=7199 => format!("{} minutes", seconds / 60),
+        7200..=172_799 => format!("{} hours", seconds / 3600),
+        _ => format!("{} days", seconds / 86_400),
+    }
+}
+
+/// Extension of a `run` script, which must exist and be TypeScript or JavaScript
+pub(crate) fn script_extension(script: &Path) -> anyhow::Result<&str> {
+    // Validate script path exists
+    if !script.exists() {
+        anyhow::bail!("Script not found: {}", script.display());
+    }
+
+    // Check if script is TypeScript, JavaScript or Rhai
+    let extension = script.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    if !matches!(extension, "ts" | "js" | "rhai") {
+        anyhow::bail!(
+            "Script must be a TypeScript (.ts), JavaScript (.js) or Rhai (.rhai) file, got: .{}",
+            extension
+        );
+    }
+    Ok(extension)
+}
+
+/// Point scripts at an RPC server for a network: a running daemon serving it, or else a new
+/// server, returned to be shut down. Journaled runs always get a server of their own, as only
+/// a server in this process reports the contracts it deployed.
+pub(crate) async fn serve(
+    network: &str,
+    server: &RpcServerArgs,
+    journaled: bool,
+) -> anyhow::Result<Option<crate::rpc::RpcServer>> {
+    let (rpc_server, env) = server_for(network, server, journaled).await?;
+    for (name, value) in env {
+        std::env::set_var(name, value);
+    }
+    Ok(rpc_server)
+}
+
+/// RPC server for a network, a running daemon serving it or else a new server returned to be
+/// shut down, with the environment pointing scripts at it
+async fn server_for(
+    network: &str,
+    server: &RpcServerArgs,
+    journaled: bool,
+) -> anyhow::Result<(Option<crate::rpc::RpcServer>, Vec<(&'static str, String)>)> {
+    // A running daemon serving the network spares starting a server of our own, unless a
+    // server flag asks for something the daemon was not started with
+    let daemon = match crate::cli::daemon::running()? {
+        Some(state) if state.network == network && !journaled => match server.changed_flag() {
+            Some(flag) => {
+                println!(
+                    "\n{} Not using the glin-forge daemon: {} needs a server of its own",
+                    "→".cyan(),
+                    flag
+                );
+                None
+            }
+            None => Some(state),
+        },
+        _ => None,
+    };
+    let rpc_server = match &daemon {
+        Some(state) => {
+            println!(
+                "\n{} Using glin-forge daemon at {} (pid {})",
+                "→".cyan(),
+                state.endpoint(),
+                state.pid
             );
+            (None, server_env(&state.endpoint(), &state.token))
+        }
+        None => {
+            print_server_args(server);
+            println!("\n{}", "Starting RPC server...".cyan());
+            let (rpc_server, token) = start_server(network, server).await?;
+            let env = server_env(rpc_server.endpoint(), &token);
+            (Some(rpc_server), env)
         }
+    };
+    let (rpc_server, mut env) = rpc_server;
+    env.push(("GLIN_FORGE_NETWORK", network.to_string()));
+    Ok((rpc_server, env))
+}
+
+/// Print the server options that differ from the defaults
+pub(crate) fn print_server_args(server: &RpcServerArgs) {
+    if !server.allow_networks.is_empty() {
+        println!(
+            "  {} {}",
+            "Also allowed:".cyan(),
+            server.allow_networks.join(", ")
+        );
+    }
+    if !server.allow_origins.is_empty() {
+        println!(
+            "  {} {}",
+            "Allowed origins:".cyan(),
+            server.allow_origins.join(", ")
+        );
+    }
+    let mut limits = Vec::new();
+    if server.deny_mainnet {
+        limits.push("mainnet denied".to_string());
+    }
+    if let Some(max_value) = server.max_value {
+        let max_value = crate::cli::balance::format_balance(max_value);
+        limits.push(format!("at most {} GLIN per transaction", max_value));
+    }
+    if !limits.is_empty() {
+        println!("  {} {}", "Sandbox:".cyan(), limits.join(", "));
+    }
+}
+
+/// Start the RPC server for a network; returns it with the token clients need
+pub(crate) async fn start_server(
+    network: &str,
+    server: &RpcServerArgs,
+) -> anyhow::Result<(crate::rpc::RpcServer, String)> {
+    let file_config = if crate::config::file::find_config_file().is_ok() {
+        Some(crate::config::file::load_config_file(None)?)
     } else {
-        // JavaScript - use node
-        ("node", vec![script_path.to_string_lossy().to_string()])
+        None
     };
+    let endpoint = rpc_endpoint(server, file_config.as_ref().map(|c| &c.rpc))?;
+    let networks = crate::rpc::network::NetworkPolicy::new(network, &server.allow_networks)?
+        .sandboxed(&server.sandbox())?;
+    // A token set beforehand is kept, so clients outside the script can share it
+    let access = match std::env::var(crate::rpc::auth::TOKEN_ENV) {
+        Ok(token) if !token.is_empty() => {
+            crate::rpc::auth::Access::with_token(token, &server.allow_origins)
+        }
+        _ => crate::rpc::auth::Access::new(&server.allow_origins),
+    };
+    let token = access.token().to_string();
+    let metrics = crate::rpc::metrics::Metrics::new(server.metrics, server.log_requests);
+    let timeout = Some(std::time::Duration::from_secs(server.request_timeout))
+        .filter(|timeout| !timeout.is_zero());
+    let requests = crate::rpc::requests::Requests::new(timeout);
+    let rpc_server =
+        crate::rpc::RpcServer::start(networks, access, metrics, requests, &endpoint).await?;
+    println!(
+        "{} RPC server listening on {}",
+        "✓".green(),
+        rpc_server.endpoint()
+    );
+    if server.metrics {
+        println!("  {} {}/metrics", "Metrics:".cyan(), rpc_server.endpoint());
+    }
+    if let Endpoint::Tcp(address) = rpc_server.endpoint() {
+        if !address.ip().is_loopback() {
+            println!(
+                "{} Reachable from other hosts; requests need the {} token",
+                "⚠".yellow(),
+                crate::rpc::auth::TOKEN_ENV
+            );
+        }
+    }
+    Ok((rpc_server, token))
+}
+
+/// Variables pointing the SDK at a server
+fn server_env(endpoint: &Endpoint, token: &str) -> Vec<(&'static str, String)> {
+    let mut env = match endpoint {
+        Endpoint::Tcp(address) => {
+            let url = connect_url(address);
+            vec![
+                ("GLIN_FORGE_RPC_PORT", address.port().to_string()),
+                ("GLIN_FORGE_RPC_WS_URL", url.replacen("http", "ws", 1)),
+                ("GLIN_FORGE_RPC_URL", url),
+            ]
+        }
+        Endpoint::Unix(path) => vec![("GLIN_FORGE_RPC_SOCKET", path.to_string_lossy().to_string())],
+    };
+    env.push((crate::rpc::auth::TOKEN_ENV, token.to_string()));
+    env
+}
+
+/// Where to serve the RPC server: the flags, then the config `rpc` section, then a random
+/// localhost port
+fn rpc_endpoint(args: &RpcServerArgs, config: Option<&RpcConfig>) -> anyhow::Result<Endpoint> {
+    let config = config.cloned().unwrap_or_default();
+    let flags_given = args.rpc_host.is_some() || args.rpc_port.is_some();
+    if let Some(socket) = args
+        .rpc_socket
+        .clone()
+        .or(config.socket.filter(|_| !flags_given))
+    {
+        return Ok(Endpoint::Unix(socket));
+    }
+
+    let host = args
+        .rpc_host
+        .clone()
+        .or(config.host)
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = args.rpc_port.or(config.port).unwrap_or(0);
+    let address = (host.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| anyhow::anyhow!("Invalid RPC host {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("RPC host {} did not resolve", host))?;
+    Ok(Endpoint::Tcp(address))
+}
 
-    // Execute the script
-    let mut child = Command::new(command)
-        .args(&args)
+/// URL a local script reaches the server at
+fn connect_url(address: &SocketAddr) -> String {
+    format!("http://{}", connect_address(address))
+}
+
+/// Address a local client connects to; a wildcard address is reached over loopback
+pub(crate) fn connect_address(address: &SocketAddr) -> SocketAddr {
+    let mut address = *address;
+    if address.ip().is_unspecified() {
+        address.set_ip(match address {
+            SocketAddr::V4(_) => std::net::Ipv4Addr::LOCALHOST.into(),
+            SocketAddr::V6(_) => std::net::Ipv6Addr::LOCALHOST.into(),
+        });
+    }
+    address
+}
+
+/// Command running a TypeScript or JavaScript script on the selected runtime, or a Rhai
+/// script in glin-forge
+pub(crate) fn script_command(script: &Path, extension: &str) -> anyhow::Result<Command> {
+    let mut script_path = script.canonicalize()?;
+    let runtime = match extension {
+        "rhai" => Runtime::Embedded,
+        _ => crate::config::runtime::for_script(script, extension == "ts")?,
+    };
+    let mut typescript = extension == "ts";
+    if typescript && runtime == Runtime::Node {
+        if let Some(compiled) = crate::config::transpile::compiled(script) {
+            script_path = compiled;
+            typescript = false;
+        }
+    }
+    let (program, args) = runtime.script_invocation(typescript)?;
+
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .arg(script_path)
+        .env(crate::config::runtime::RUNTIME_ENV, runtime.name())
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .map_err(|e| anyhow::anyhow!("Failed to execute {}: {}", command, e))?;
+        .stderr(Stdio::inherit());
+    Ok(command)
+}
+
+/// Execute a script with `env` added to its environment, its output lines after `prefix` if
+/// given
+async fn execute_script(
+    script: &Path,
+    extension: &str,
+    env: &[(&str, String)],
+    prefix: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut command = script_command(script, extension)?;
+    command.envs(env.iter().map(|(name, value)| (name, value)));
+    let program = command.as_std().get_program().to_string_lossy().to_string();
 
-    // Wait for completion
-    let status = child.wait()?;
+    // Execute the script and wait for completion
+    let status = match prefix {
+        Some(prefix) => run_prefixed(command, prefix).await,
+        None => command.status().await,
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to execute {}: {}", program, e))?;
 
     if !status.success() {
         anyhow::bail!("Script exited with code: {}", status.code().unwrap_or(-1));
@@ -118,3 +846,455 @@ async fn execute_script(script: &PathBuf, extension: &str) -> anyhow::Result<()>
 
     Ok(())
 }
+
+/// Run a command to completion, printing each line of its output after `prefix`; it cannot
+/// read the terminal
+async fn run_prefixed(
+    mut command: Command,
+    prefix: &str,
+) -> std::io::Result<std::process::ExitStatus> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = command.spawn()?;
+    let stdout = forward_lines(child.stdout.take(), prefix, false);
+    let stderr = forward_lines(child.stderr.take(), prefix, true);
+    let (_, _, status) = tokio::join!(stdout, stderr, child.wait());
+    status
+}
+
+async fn forward_lines(
+    output: Option<impl tokio::io::AsyncRead + Unpin>,
+    prefix: &str,
+    stderr: bool,
+) {
+    use tokio::io::AsyncBufReadExt;
+    let Some(output) = output else {
+        return;
+    };
+    let mut lines = tokio::io::BufReader::new(output).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        match stderr {
+            true => eprintln!("{} {}", prefix, line),
+            false => println!("{} {}", prefix, line),
+        }
+    }
+}
+
+/// Time without further changes before a watched script restarts
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Time a stopped script has to exit before it is killed
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What ended a watched run
+enum Wake {
+    Exited(std::io::Result<std::process::ExitStatus>),
+    /// The preDeploy hook failed, so the script did not run
+    Failed(anyhow::Error),
+    Changed(PathBuf),
+    Stop,
+}
+
+/// Rerun a script, between its hooks, whenever it, a local module it imports or a contract
+/// artifact changes, until Ctrl-C. A run still going is stopped first, with the processes it
+/// started: it runs in a process group of its own, so it cannot read the terminal.
+async fn watch_script(script: &Path, extension: &str, hooks: &HooksConfig) -> anyhow::Result<()> {
+    let (changes, mut changed) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if !event.kind.is_access() {
+                let _ = changes.send(event.paths);
+            }
+        }
+    })
+    .context("Failed to start the file watcher")?;
+
+    let artifacts = artifact_dirs();
+    for dir in &artifacts {
+        watcher
+            .watch(dir, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", dir.display()))?;
+    }
+    // Directories rather than files, as editors replace the files they save
+    let mut watched = HashSet::new();
+
+    loop {
+        // Imports may have changed since the last run
+        let sources = script_sources(script)?;
+        for dir in sources.iter().filter_map(|file| file.parent()) {
+            if watched.insert(dir.to_path_buf()) {
+                watcher
+                    .watch(dir, RecursiveMode::NonRecursive)
+                    .with_context(|| format!("Failed to watch {}", dir.display()))?;
+            }
+        }
+        let relevant = |path: &Path| {
+            sources.contains(path) || artifacts.iter().any(|dir| path.starts_with(dir))
+        };
+
+        let wake = match crate::cli::hooks::run(Hook::PreDeploy, hooks, &[]).await {
+            Err(e) => Wake::Failed(e),
+            Ok(()) => {
+                println!("\n{}", "Executing script...".cyan());
+                println!("{}", "─".repeat(60));
+                let mut command = script_command(script, extension)?;
+                #[cfg(unix)]
+                command.process_group(0);
+                let mut child = command.spawn().context("Failed to execute the script")?;
+
+                let wake = tokio::select! {
+                    status = child.wait() => Wake::Exited(status),
+                    path = next_change(&mut changed, relevant) => path.map_or(Wake::Stop, Wake::Changed),
+                    _ = tokio::signal::ctrl_c() => Wake::Stop,
+                };
+                if !matches!(wake, Wake::Exited(_)) {
+                    stop_script(&mut child).await;
+                    println!("{}", "─".repeat(60));
+                }
+                wake
+            }
+        };
+        let wake = match wake {
+            Wake::Exited(status) => {
+                println!("{}", "─".repeat(60));
+                match status? {
+                    status if status.success() => {
+                        println!("\n{} Script completed successfully!", "✓".green().bold());
+                        if let Err(e) = crate::cli::hooks::run(Hook::PostDeploy, hooks, &[]).await {
+                            println!("\n{} {:#}", "✗".red().bold(), e);
+                        }
+                    }
+                    status => println!(
+                        "\n{} Script exited with code: {}",
+                        "✗".red().bold(),
+                        status.code().unwrap_or(-1)
+                    ),
+                }
+                wait_for_change(&mut changed, relevant).await
+            }
+            Wake::Failed(e) => {
+                println!("\n{} {:#}", "✗".red().bold(), e);
+                wait_for_change(&mut changed, relevant).await
+            }
+            wake => wake,
+        };
+
+        match wake {
+            Wake::Changed(path) => {
+                let path = std::env::current_dir()
+                    .ok()
+                    .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+                    .unwrap_or(path);
+                println!("\n{} {} changed, restarting...", "→".cyan(), path.display());
+            }
+            _ => {
+                println!("\n{} Stopped watching", "✓".green());
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Wait for the next change after a run ended, or Ctrl-C
+async fn wait_for_change(
+    changed: &mut mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    relevant: impl Fn(&Path) -> bool,
+) -> Wake {
+    println!("{} Watching for changes (Ctrl-C to stop)...", "→".cyan());
+    tokio::select! {
+        path = next_change(changed, relevant) => path.map_or(Wake::Stop, Wake::Changed),
+        _ = tokio::signal::ctrl_c() => Wake::Stop,
+    }
+}
+
+/// Wait for a change to a path `relevant` accepts, then for the changes following it to settle
+async fn next_change(
+    changed: &mut mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    relevant: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    let mut first = None;
+    while first.is_none() {
+        first = changed
+            .recv()
+            .await?
+            .into_iter()
+            .find(|path| relevant(path));
+    }
+    while let Ok(Some(_)) = tokio::time::timeout(WATCH_DEBOUNCE, changed.recv()).await {}
+    first
+}
+
+/// Stop a script run along with the processes it started, e.g. the node process of tsx
+async fn stop_script(child: &mut Child) {
+    if let Some(pid) = child.id() {
+        #[cfg(unix)]
+        let _ = std::process::Command::new("kill")
+            .arg("-TERM")
+            .arg(format!("-{}", pid))
+            .status();
+        #[cfg(not(unix))]
+        let _ = std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .status();
+    }
+    if tokio::time::timeout(STOP_TIMEOUT, child.wait())
+        .await
+        .is_err()
+    {
+        let _ = child.kill().await;
+    }
+}
+
+/// Contract artifact directories of the project: the config's `paths.artifacts` and target/ink
+fn artifact_dirs() -> Vec<PathBuf> {
+    let artifacts = crate::config::file::find_config_file()
+        .ok()
+        .and_then(|path| crate::config::file::load_config_file(Some(&path)).ok())
+        .map(|config| config.paths.artifacts)
+        .unwrap_or_else(|| "artifacts".to_string());
+    let mut dirs: Vec<PathBuf> = [PathBuf::from(artifacts), PathBuf::from("target/ink")]
+        .iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .collect();
+    dirs.dedup();
+    dirs
+}
+
+/// Extensions tried on an import without one, in the order runtimes resolve them
+const MODULE_EXTENSIONS: &[&str] = &[
+    "ts", "tsx", "mts", "cts", "js", "mjs", "cjs", "json", "rhai",
+];
+
+/// The script and the local modules it imports, directly or not, as canonical paths
+pub(crate) fn script_sources(script: &Path) -> anyhow::Result<HashSet<PathBuf>> {
+    let mut found = HashSet::new();
+    let mut pending = vec![script
+        .canonicalize()
+        .with_context(|| format!("Script not found: {}", script.display()))?];
+    while let Some(file) = pending.pop() {
+        if !found.insert(file.clone()) || file.extension().is_some_and(|e| e == "json") {
+            continue;
+        }
+        let Ok(source) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        let dir = file.parent().unwrap_or(Path::new("."));
+        pending.extend(
+            local_imports(&source)
+                .iter()
+                .filter_map(|specifier| resolve_module(dir, specifier)),
+        );
+    }
+    Ok(found)
+}
+
+/// Relative specifiers of the `import`, `export ... from` and `require` of a module
+fn local_imports(source: &str) -> Vec<String> {
+    static IMPORT: OnceLock<Regex> = OnceLock::new();
+    let import = IMPORT.get_or_init(|| {
+        Regex::new(r#"(?:\bfrom\s*|\bimport\s*\(?\s*|\brequire\s*\(\s*)['"](\.\.?/[^'"]+)['"]"#)
+            .expect("valid import pattern")
+    });
+    import
+        .captures_iter(source)
+        .map(|captures| captures[1].to_string())
+        .collect()
+}
+
+/// File a relative import resolves to, like node and TypeScript: as is, with an extension,
+/// `.ts` for `.js`, or the directory's index
+pub(crate) fn resolve_module(dir: &Path, specifier: &str) -> Option<PathBuf> {
+    let base = dir.join(specifier);
+    let with_extension = |path: &Path, extension: &str| {
+        let mut path = path.as_os_str().to_owned();
+        path.push(".");
+        path.push(extension);
+        PathBuf::from(path)
+    };
+
+    let mut candidates = vec![base.clone()];
+    // TypeScript imports name the JavaScript file a module compiles to
+    if let Some(stem) = specifier.strip_suffix(".js") {
+        candidates.push(with_extension(&dir.join(stem), "ts"));
+    }
+    candidates.extend(MODULE_EXTENSIONS.iter().map(|e| with_extension(&base, e)));
+    candidates.extend(
+        MODULE_EXTENSIONS
+            .iter()
+            .map(|e| base.join(format!("index.{}", e))),
+    );
+    candidates
+        .into_iter()
+        .find(|path| path.is_file())
+        .and_then(|path| path.canonicalize().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_endpoint() {
+        let args = |flags: &[&str]| {
+            let argv = ["run", "deploy.ts"].iter().chain(flags);
+            RunArgs::try_parse_from(argv).unwrap().server
+        };
+        let tcp = |address: &str| Endpoint::Tcp(address.parse().unwrap());
+
+        assert_eq!(rpc_endpoint(&args(&[]), None).unwrap(), tcp("127.0.0.1:0"));
+        assert_eq!(
+            rpc_endpoint(
+                &args(&["--rpc-host", "0.0.0.0", "--rpc-port", "8545"]),
+                None
+            )
+            .unwrap(),
+            tcp("0.0.0.0:8545")
+        );
+        assert_eq!(
+            rpc_endpoint(&args(&["--rpc-socket", "/tmp/forge.sock"]), None).unwrap(),
+            Endpoint::Unix("/tmp/forge.sock".into())
+        );
+        assert!(
+            RunArgs::try_parse_from(["run", "a.ts", "--rpc-socket", "s", "--rpc-port", "1"])
+                .is_err()
+        );
+        assert_eq!(args(&[]).changed_flag(), None);
+        assert_eq!(
+            args(&["--request-timeout", "5"]).changed_flag(),
+            Some("--request-timeout")
+        );
+        assert_eq!(
+            args(&["--log-requests", "--max-value", "1"]).changed_flag(),
+            Some("--log-requests")
+        );
+
+        let config = RpcConfig {
+            host: None,
+            port: Some(9000),
+            socket: None,
+        };
+        assert_eq!(
+            rpc_endpoint(&args(&[]), Some(&config)).unwrap(),
+            tcp("127.0.0.1:9000")
+        );
+        assert_eq!(
+            rpc_endpoint(&args(&["--rpc-port", "9001"]), Some(&config)).unwrap(),
+            tcp("127.0.0.1:9001")
+        );
+        let config = RpcConfig {
+            socket: Some("/run/forge.sock".into()),
+            ..config
+        };
+        assert_eq!(
+            rpc_endpoint(&args(&[]), Some(&config)).unwrap(),
+            Endpoint::Unix("/run/forge.sock".into())
+        );
+        assert_eq!(
+            rpc_endpoint(&args(&["--rpc-port", "9001"]), Some(&config)).unwrap(),
+            tcp("127.0.0.1:9001")
+        );
+
+        assert_eq!(
+            connect_url(&"0.0.0.0:8545".parse().unwrap()),
+            "http://127.0.0.1:8545"
+        );
+        assert_eq!(connect_url(&"[::]:1".parse().unwrap()), "http://[::1]:1");
+        assert_eq!(
+            connect_url(&"10.0.0.2:1".parse().unwrap()),
+            "http://10.0.0.2:1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_networks() {
+        let args = RunArgs::try_parse_from(["run", "a.ts", "--networks", "testnet,local"]).unwrap();
+        assert_eq!(args.networks, ["testnet", "local"]);
+        assert!(!args.parallel);
+        assert!(RunArgs::try_parse_from(["run", "a.ts", "--parallel"]).is_err());
+        assert!(
+            RunArgs::try_parse_from(["run", "a.ts", "-n", "local", "--networks", "a"]).is_err()
+        );
+        assert!(RunArgs::try_parse_from(["run", "a.ts", "--networks", "a", "--watch"]).is_err());
+
+        // Each network's script gets its server in its own environment
+        let env = server_env(&Endpoint::Tcp("0.0.0.0:8545".parse().unwrap()), "secret");
+        assert!(env.contains(&("GLIN_FORGE_RPC_URL", "http://127.0.0.1:8545".to_string())));
+        assert!(env.contains(&("GLIN_FORGE_RPC_WS_URL", "ws://127.0.0.1:8545".to_string())));
+        assert!(env.contains(&(crate::rpc::auth::TOKEN_ENV, "secret".to_string())));
+        let env = server_env(&Endpoint::Unix("/tmp/forge.sock".into()), "secret");
+        assert_eq!(
+            env[0],
+            ("GLIN_FORGE_RPC_SOCKET", "/tmp/forge.sock".to_string())
+        );
+
+        #[cfg(unix)]
+        {
+            let mut command = Command::new("sh");
+            command.args(["-c", "echo out; echo err >&2; exit 3"]);
+            let status = run_prefixed(command, "[local]").await.unwrap();
+            assert_eq!(status.code(), Some(3));
+        }
+    }
+
+    #[test]
+    fn test_script_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |path: &str, content: &str| {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "scripts/deploy.ts",
+            "import { deploy } from '@glin-forge/sdk';\nimport { token } from './lib';\nimport config from \"../config.json\";\n",
+        );
+        write(
+            "scripts/lib/index.ts",
+            "export * from '../util.js';\nconst abi = require('./abi');\n",
+        );
+        write("scripts/lib/abi.js", "module.exports = {};\n");
+        write(
+            "scripts/util.ts",
+            "export const token = await import('./lib/index.ts');\n",
+        );
+        write("config.json", "{}");
+        write("scripts/unused.ts", "");
+
+        let sources = script_sources(&dir.path().join("scripts/deploy.ts")).unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let mut names: Vec<_> = sources
+            .iter()
+            .map(|path| {
+                path.strip_prefix(&root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "config.json",
+                "scripts/deploy.ts",
+                "scripts/lib/abi.js",
+                "scripts/lib/index.ts",
+                "scripts/util.ts"
+            ]
+        );
+
+        assert_eq!(
+            local_imports(
+                "import './setup';\nimport x from \"lodash\";\nexport { y } from '../y';"
+            ),
+            ["./setup", "../y"]
+        );
+        assert!(
+            RunArgs::try_parse_from(["run", "deploy.ts", "--watch"])
+                .unwrap()
+                .watch
+        );
+    }
+}
@@ -1,6 +1,9 @@
+use anyhow::Context;
 use clap::Parser;
 use colored::Colorize;
+use std::path::Path;
 use std::process::Command;
+use std::time::Instant;
 
 #[derive(Parser)]
 pub struct TestArgs {
@@ -9,9 +12,23 @@ pub struct TestArgs {
     pub path: String,
 
     /// Run end-to-end tests
-    #[arg(long)]
+    #[arg(long, conflicts_with = "sandbox")]
     pub e2e: bool,
 
+    /// Run tests against an in-process drink! sandbox runtime (no node required).
+    /// Runs the crate's `sandbox-tests` feature tests; there is no fuzz or
+    /// invariant runner behind it
+    #[arg(long)]
+    pub sandbox: bool,
+
+    /// Run declarative YAML scenarios from test/scenarios/
+    #[arg(long, conflicts_with_all = ["e2e", "sandbox"])]
+    pub scenarios: bool,
+
+    /// Network to run scenarios against
+    #[arg(short, long, default_value = "local")]
+    pub network: String,
+
     /// Test name filter
     #[arg(long)]
     pub test: Option<String>,
@@ -21,7 +38,18 @@ pub struct TestArgs {
     pub nocapture: bool,
 }
 
+/// Cargo feature that contract crates use to gate drink! sandbox tests
+const SANDBOX_FEATURE: &str = "sandbox-tests";
+
 pub async fn execute(args: TestArgs) -> anyhow::Result<()> {
+    if args.sandbox {
+        return run_sandbox_tests(&args);
+    }
+
+    if args.scenarios {
+        return run_scenarios(&args).await;
+    }
+
     println!("{}", "Running contract tests...".cyan().bold());
 
     let test_type = if args.e2e { "E2E" } else { "Unit" };
@@ -82,3 +110,186 @@ pub async fn execute(args: TestArgs) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Run tests against the drink! sandbox runtime.
+///
+/// The sandbox is an in-process runtime pulled in by the contract crate itself, so
+/// this only needs cargo: no node, no cargo-contract, no network access.
+fn run_sandbox_tests(args: &TestArgs) -> anyhow::Result<()> {
+    println!("{}", "Running sandbox tests...".cyan().bold());
+    println!("  {} drink! in-process runtime", "→".cyan());
+
+    if let Some(filter) = &args.test {
+        println!("  {} Filtering by: {}", "→".cyan(), filter);
+    }
+
+    check_sandbox_setup(Path::new(&args.path))?;
+
+    println!();
+
+    let mut cmd = Command::new("cargo");
+    cmd.arg("test").arg("--features").arg(SANDBOX_FEATURE);
+
+    if let Some(filter) = &args.test {
+        cmd.arg(filter);
+    }
+
+    if args.nocapture {
+        cmd.arg("--").arg("--nocapture");
+    }
+
+    cmd.current_dir(&args.path);
+    cmd.env("GLIN_FORGE_SANDBOX", "1");
+
+    let started = Instant::now();
+    let output = cmd.output()?;
+    let elapsed = started.elapsed();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    println!("{}", stdout);
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr);
+        anyhow::bail!("Sandbox tests failed");
+    }
+
+    println!(
+        "\n{} All sandbox tests passed in {:.2}s",
+        "✓".green().bold(),
+        elapsed.as_secs_f64()
+    );
+
+    Ok(())
+}
+
+/// Ensure the contract crate declares the sandbox feature and a drink dev-dependency
+fn check_sandbox_setup(path: &Path) -> anyhow::Result<()> {
+    let cargo_toml_path = path.join("Cargo.toml");
+    let content = std::fs::read_to_string(&cargo_toml_path)
+        .with_context(|| format!("Failed to read {}", cargo_toml_path.display()))?;
+    let manifest: toml::Value = toml::from_str(&content)?;
+
+    let has_feature = manifest
+        .get("features")
+        .and_then(|f| f.get(SANDBOX_FEATURE))
+        .is_some();
+
+    let has_drink = manifest
+        .get("dev-dependencies")
+        .and_then(|d| d.get("drink"))
+        .is_some();
+
+    if !has_feature || !has_drink {
+        anyhow::bail!(
+            "Sandbox tests are not configured for this contract. Add to Cargo.toml:\n\n  \
+             [dev-dependencies]\n  drink = \"0.18\"\n\n  [features]\n  {} = []\n\n\
+             and gate your drink! tests with #[cfg(feature = \"{}\")]",
+            SANDBOX_FEATURE,
+            SANDBOX_FEATURE
+        );
+    }
+
+    Ok(())
+}
+
+/// Run YAML scenarios against a node and report assertion failures
+async fn run_scenarios(args: &TestArgs) -> anyhow::Result<()> {
+    use crate::testing::{load_scenarios, ScenarioRunner};
+
+    println!("{}", "Running scenario tests...".cyan().bold());
+
+    let base_dir = Path::new(&args.path);
+    let scenarios_dir = base_dir.join("test/scenarios");
+    let mut scenarios = load_scenarios(&scenarios_dir)?;
+
+    if let Some(filter) = &args.test {
+        println!("  {} Filtering by: {}", "→".cyan(), filter);
+        scenarios.retain(|(_, scenario)| scenario.name.contains(filter.as_str()));
+    }
+
+    if scenarios.is_empty() {
+        println!(
+            "{} No scenarios found in {}",
+            "⚠".yellow(),
+            scenarios_dir.display()
+        );
+        return Ok(());
+    }
+
+    println!("  {} {} scenario(s)", "→".cyan(), scenarios.len());
+    println!();
+
+    let mut runners: std::collections::HashMap<String, ScenarioRunner> =
+        std::collections::HashMap::new();
+    let mut failed = Vec::new();
+
+    for (path, scenario) in &scenarios {
+        let network = scenario.network.as_deref().unwrap_or(&args.network);
+
+        if !runners.contains_key(network) {
+            let network_config = crate::config::load_network(network)?;
+            let runner = ScenarioRunner::connect(&network_config.rpc, base_dir).await?;
+            runners.insert(network.to_string(), runner);
+        }
+        let runner = runners.get_mut(network).expect("runner was just inserted");
+
+        println!(
+            "{} {} {}",
+            "▸".cyan().bold(),
+            scenario.name.bold(),
+            format!("({})", path.display()).dimmed()
+        );
+        if let Some(description) = &scenario.description {
+            println!("  {}", description.dimmed());
+        }
+
+        let outcomes = runner.run(scenario).await;
+        for outcome in &outcomes {
+            match &outcome.failure {
+                None => println!("    {} {}", "✓".green(), outcome.label),
+                Some(reason) => {
+                    println!("    {} {}", "✗".red(), outcome.label);
+                    for line in reason.lines() {
+                        println!("      {}", line);
+                    }
+                }
+            }
+        }
+
+        let passed = outcomes.iter().filter(|outcome| outcome.passed()).count();
+        println!(
+            "    {} {}/{} step(s) passed",
+            "→".cyan(),
+            passed,
+            scenario.steps.len()
+        );
+        let skipped = scenario.steps.len() - outcomes.len();
+        if skipped > 0 {
+            println!("    {} {} step(s) skipped", "-".dimmed(), skipped);
+        }
+
+        if passed < outcomes.len() {
+            failed.push(scenario.name.clone());
+        }
+        println!();
+    }
+
+    println!("{}", "=== Scenario Summary ===".bold());
+    println!(
+        "  {} {}/{} scenarios passed",
+        "✓".green(),
+        scenarios.len() - failed.len(),
+        scenarios.len()
+    );
+
+    if !failed.is_empty() {
+        println!("  {} {} failed:", "✗".red(), failed.len());
+        for name in &failed {
+            println!("    • {}", name);
+        }
+        anyhow::bail!("Scenario tests failed");
+    }
+
+    Ok(())
+}
@@ -20,22 +20,100 @@ pub struct TypegenArgs {
     #[arg(short, long, default_value = "testnet")]
     pub network: String,
 
-    /// Generate React hooks alongside types
+    /// Language of the generated bindings (rust: subxt client, ink: trait definition)
+    #[arg(long, value_parser = ["typescript", "rust", "ink"], default_value = "typescript")]
+    pub lang: String,
+
+    /// Generate React hooks alongside types (same as --framework react)
     #[arg(long)]
     pub hooks: bool,
 
+    /// Generate framework bindings alongside types (react, angular)
+    #[arg(long, value_parser = ["react", "angular"])]
+    pub framework: Option<String>,
+
+    /// Scaffold editable UI components into <output>/components (react: provider, wallet
+    /// connect, read and write panels); implies --framework react, existing files are kept
+    #[arg(long, value_parser = ["react"])]
+    pub scaffold: Option<String>,
+
+    /// Build React hooks on TanStack Query (caching, event invalidation, mutations)
+    #[arg(long)]
+    pub react_query: bool,
+
+    /// Generate bindings for every contract in artifacts/ and target/ink, plus an index.ts barrel
+    #[arg(long, conflicts_with_all = ["abi", "contract"])]
+    pub all: bool,
+
+    /// Also write an in-memory `Mock<Contract>` implementing the contract interface, for unit tests
+    #[arg(long)]
+    pub mock: bool,
+
+    /// Compile the generated sources to JavaScript with declarations and source maps
+    /// (both: dist/esm and dist/cjs plus a package.json exports block)
+    #[arg(long, value_parser = ["esm", "cjs", "both"])]
+    pub module: Option<String>,
+
+    /// Emit `bigint` for 64-bit and wider integers in results and accept `bigint | string` as input
+    #[arg(long)]
+    pub strict_bigint: bool,
+
+    /// Nominal `AccountId`, `Balance` and `Hash` types with validating constructors instead of plain strings and numbers
+    #[arg(long)]
+    pub branded: bool,
+
+    /// Also write a JSON Schema of every message's arguments and return value
+    #[arg(long)]
+    pub schema: bool,
+
+    /// Also write an OpenRPC document describing the contract's methods and errors
+    #[arg(long)]
+    pub openrpc: bool,
+
+    /// Also export the ABI in another ecosystem's format (evm-abi: Solidity ABI JSON approximation)
+    #[arg(long, value_parser = ["evm-abi"])]
+    pub format: Option<String>,
+
+    /// Directory of handlebars templates overriding the built-in ones (header.hbs,
+    /// hooks.hbs, hooks-file.hbs) [default: ./typegen.templates when present]
+    #[arg(long)]
+    pub templates: Option<PathBuf>,
+
     /// Use legacy type generator (simple interfaces)
     #[arg(long)]
     pub legacy: bool,
 }
 
 pub async fn execute(args: TypegenArgs) -> anyhow::Result<()> {
-    println!("{}", "Generating TypeScript types...".cyan().bold());
+    let rust = args.lang != "typescript";
+    if args.lang == "ink" {
+        println!("{}", "Generating ink! trait definition...".cyan().bold());
+    } else if rust {
+        println!("{}", "Generating Rust client...".cyan().bold());
+    } else {
+        println!("{}", "Generating TypeScript types...".cyan().bold());
+    }
+
+    let framework = bindings_framework(&args)?;
+
+    let templates = load_templates(&args)?;
+    if !rust && !templates.overridden().is_empty() {
+        println!(
+            "{} Using project templates: {}",
+            "→".cyan(),
+            templates.overridden().join(", ")
+        );
+    }
+
+    if args.all {
+        if args.legacy {
+            anyhow::bail!("--all builds on the enhanced types; drop --legacy");
+        }
+        return generate_all(&args, framework, &templates);
+    }
 
     // Load ABI
-    let abi_json = if let Some(abi_path) = &args.abi {
-        std::fs::read_to_string(abi_path)?
-    } else if let Some(contract_addr) = &args.contract {
+    let abi_json = if let Some(contract_addr) = &args.contract {
         println!("{} Fetching metadata from network...", "→".cyan());
 
         // Get network configuration
@@ -63,18 +141,7 @@ pub async fn execute(args: TypegenArgs) -> anyhow::Result<()> {
         // Convert InkProject back to JSON string for compatibility
         serde_json::to_string(&metadata)?
     } else {
-        // Try to find in artifacts/ directory first (Hardhat-style), then target/ink/
-        let artifacts_path = find_metadata_in_artifacts()?;
-        if let Some(path) = artifacts_path {
-            std::fs::read_to_string(&path)?
-        } else {
-            let default_path = PathBuf::from("target/ink").join("metadata.json");
-            if default_path.exists() {
-                std::fs::read_to_string(&default_path)?
-            } else {
-                anyhow::bail!("No ABI specified. Use --abi <path> or --contract <address>");
-            }
-        }
+        local_abi(&args)?
     };
 
     let abi: serde_json::Value = serde_json::from_str(&abi_json)?;
@@ -82,18 +149,204 @@ pub async fn execute(args: TypegenArgs) -> anyhow::Result<()> {
     // Parse contract metadata using codegen module
     let contract_name = crate::codegen::extract_contract_name(&abi)?;
     let messages = crate::codegen::extract_messages(&abi)?;
+    let events = crate::codegen::extract_events(&abi);
 
     println!("\n{}", "Contract info:".bold());
     println!("  {} {}", "Name:".cyan(), contract_name);
     println!("  {} {}", "Messages:".cyan(), messages.len());
+    println!("  {} {}", "Events:".cyan(), events.len());
+
+    if args.lang == "ink" {
+        let trait_file = write_rust_client(&abi, &contract_name, &args)?;
+
+        println!("\n{}", "Usage example:".bold());
+        println!(
+            "  mod {}; // {}",
+            trait_file.file_stem().unwrap_or_default().to_string_lossy(),
+            trait_file.display()
+        );
+        println!(
+            "  let mut contract: ink::contract_ref!({}) = address.into();",
+            crate::codegen::rust_client_name(&contract_name).trim_end_matches("Client")
+        );
+        return Ok(());
+    }
+    if rust {
+        let client_file = write_rust_client(&abi, &contract_name, &args)?;
+        let module = crate::codegen::rust_module_name(&contract_name);
+
+        println!("\n{}", "Usage example:".bold());
+        println!("  mod {}; // {}", module, client_file.display());
+        println!(
+            "  let contract = {}::{}::new(api, address);",
+            module,
+            crate::codegen::rust_client_name(&contract_name)
+        );
+        return Ok(());
+    }
+
+    let files = write_bindings(&abi, &contract_name, &args, framework, &templates)?;
+    let types_file = &files[0];
+    if let Some(module) = &args.module {
+        compile_modules(&args.output, module, &contract_name, &contract_name)?;
+    }
+
+    println!("\n{}", "Usage example:".bold());
+    if args.legacy {
+        println!(
+            "  import {{ {}Contract }} from './{}'",
+            contract_name,
+            types_file.display()
+        );
+    } else {
+        println!(
+            "  import type {{ {}, {}Queries, {}Transactions }} from './{}'",
+            contract_name,
+            contract_name,
+            contract_name,
+            types_file.display()
+        );
+        println!("  // Fully type-safe contract interactions with IDE autocomplete!");
+        println!(
+            "  deploy{}(deployer, metadata, 'new', [...args], {{ value: 0n }})",
+            contract_name
+        );
+        if let Some(event) = events.first() {
+            println!(
+                "  import {{ create{}EventSubscriptions }} from './{}'",
+                contract_name,
+                types_file.display()
+            );
+            println!(
+                "  create{}EventSubscriptions(contract).subscribe{}((event) => ...)",
+                contract_name, event.label
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Framework of the bindings, rejecting options that do not go together
+fn bindings_framework(args: &TypegenArgs) -> anyhow::Result<Option<&str>> {
+    let rust = args.lang != "typescript";
+    if args.scaffold.is_some() && args.framework.as_deref() == Some("angular") {
+        anyhow::bail!("--scaffold react builds on the React hooks; drop --framework angular");
+    }
+    let framework = args
+        .framework
+        .as_deref()
+        .or(args.hooks.then_some("react"))
+        .or(args.scaffold.as_deref());
+    if rust
+        && (framework.is_some()
+            || args.react_query
+            || args.strict_bigint
+            || args.branded
+            || args.legacy)
+    {
+        anyhow::bail!(
+            "--lang {} does not take TypeScript options (--hooks, --framework, --scaffold, --react-query, --strict-bigint, --branded, --legacy)",
+            args.lang
+        );
+    }
+    if args.legacy && framework == Some("angular") {
+        anyhow::bail!("--framework angular builds on the enhanced types; drop --legacy");
+    }
+    if args.react_query && framework != Some("react") {
+        anyhow::bail!("--react-query requires --hooks or --framework react");
+    }
+    if args.scaffold.is_some() && args.legacy {
+        anyhow::bail!("--scaffold builds on the enhanced types; drop --legacy");
+    }
+    if args.react_query && args.legacy {
+        anyhow::bail!("--react-query builds on the enhanced types; drop --legacy");
+    }
+    if args.mock && (rust || args.legacy) {
+        anyhow::bail!("--mock implements the enhanced TypeScript interface; use --lang typescript without --legacy");
+    }
+    if args.module.is_some() && rust {
+        anyhow::bail!(
+            "--module compiles TypeScript bindings; drop --lang {}",
+            args.lang
+        );
+    }
+    if args.strict_bigint && args.legacy {
+        anyhow::bail!("--strict-bigint builds on the enhanced types; drop --legacy");
+    }
+    if args.branded && args.legacy {
+        anyhow::bail!("--branded builds on the enhanced types; drop --legacy");
+    }
+
+    Ok(framework)
+}
+
+/// Project templates from --templates or ./typegen.templates, built-in ones otherwise
+fn load_templates(args: &TypegenArgs) -> anyhow::Result<crate::codegen::Templates> {
+    match &args.templates {
+        Some(dir) if !dir.is_dir() => {
+            anyhow::bail!("Templates directory {} not found", dir.display())
+        }
+        Some(dir) => crate::codegen::Templates::load(dir),
+        None => crate::codegen::Templates::load(std::path::Path::new(
+            crate::codegen::DEFAULT_TEMPLATES_DIR,
+        )),
+    }
+}
+
+/// Metadata JSON from --abi, or found in artifacts/ then target/ink
+fn local_abi(args: &TypegenArgs) -> anyhow::Result<String> {
+    if let Some(abi_path) = &args.abi {
+        return std::fs::read_to_string(abi_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", abi_path.display(), e));
+    }
+    // Try to find in artifacts/ directory first (Hardhat-style), then target/ink/
+    let artifacts_path = find_metadata_in_artifacts()?;
+    if let Some(path) = artifacts_path {
+        Ok(std::fs::read_to_string(&path)?)
+    } else {
+        let default_path = PathBuf::from("target/ink").join("metadata.json");
+        if default_path.exists() {
+            Ok(std::fs::read_to_string(&default_path)?)
+        } else {
+            anyhow::bail!("No ABI specified. Use --abi <path> or --contract <address>");
+        }
+    }
+}
+
+/// Generate the TypeScript bindings of a local metadata file, for the RPC server;
+/// returns the contract name and the written files, types module first
+pub(crate) fn generate_typescript(args: &TypegenArgs) -> anyhow::Result<(String, Vec<PathBuf>)> {
+    let framework = bindings_framework(args)?;
+    let templates = load_templates(args)?;
+    let abi: serde_json::Value = serde_json::from_str(&local_abi(args)?)?;
+    let contract_name = crate::codegen::extract_contract_name(&abi)?;
+    let files = write_bindings(&abi, &contract_name, args, framework, &templates)?;
+    Ok((contract_name, files))
+}
+
+/// Write the types module and framework bindings of one contract; returns the written files,
+/// types module first
+fn write_bindings(
+    abi: &serde_json::Value,
+    contract_name: &str,
+    args: &TypegenArgs,
+    framework: Option<&str>,
+    templates: &crate::codegen::Templates,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let options = crate::codegen::ModuleOptions {
+        strict_bigint: args.strict_bigint,
+        branded: args.branded,
+        templates: Some(templates),
+    };
 
     // Generate TypeScript types using codegen module
     let ts_content = if args.legacy {
         // Use legacy simple type generator
-        crate::codegen::generate_typescript_types(&contract_name, &abi)?
+        crate::codegen::generate_typescript_types(contract_name, abi)?
     } else {
         // Use enhanced type generator with full type safety
-        crate::codegen::generate_typescript_module(&contract_name, &abi)?
+        crate::codegen::generate_typescript_module(contract_name, abi, options)?
     };
 
     // Create output directory
@@ -106,36 +359,423 @@ pub async fn execute(args: TypegenArgs) -> anyhow::Result<()> {
     println!("\n{} TypeScript types generated!", "✓".green().bold());
     println!("  {} {}", "Output:".cyan(), types_file.display());
 
-    // Generate React hooks if requested
-    if args.hooks {
-        let hooks_content = crate::codegen::generate_react_hooks(&contract_name, &abi)?;
-        let hooks_file = args.output.join(format!("use{}.ts", contract_name));
-        std::fs::write(&hooks_file, hooks_content)?;
+    let mut files = vec![types_file];
+    files.extend(write_documents(abi, contract_name, args)?);
+
+    if args.mock {
+        let mock_content = crate::codegen::generate_mock_client(contract_name, abi, options)?;
+        let mock_file = args.output.join(crate::codegen::mock_file(contract_name));
+        std::fs::write(&mock_file, mock_content)?;
 
-        println!("  {} {}", "Hooks:".cyan(), hooks_file.display());
+        println!("  {} {}", "Mock:".cyan(), mock_file.display());
+        files.push(mock_file);
     }
 
-    println!("\n{}", "Usage example:".bold());
-    if args.legacy {
+    // Generate framework bindings if requested
+    match framework {
+        Some("react") => {
+            let hooks_content = crate::codegen::generate_react_hooks(
+                contract_name,
+                abi,
+                if args.react_query {
+                    crate::codegen::HookStyle::ReactQuery
+                } else {
+                    crate::codegen::HookStyle::Basic
+                },
+                options,
+            )?;
+            let hooks_file = if args.react_query {
+                args.output.join(format!("use{}.ts", contract_name))
+            } else {
+                args.output.join(templates.hooks_file(contract_name)?)
+            };
+            std::fs::write(&hooks_file, hooks_content)?;
+
+            println!("  {} {}", "Hooks:".cyan(), hooks_file.display());
+            files.push(hooks_file);
+
+            if args.scaffold.is_some() {
+                files.extend(write_scaffold(abi, contract_name, args)?);
+            }
+        }
+        Some("angular") => {
+            let service_content =
+                crate::codegen::generate_angular_service(contract_name, abi, options)?;
+            let service_file = args
+                .output
+                .join(crate::codegen::angular_service_file(contract_name));
+            std::fs::write(&service_file, service_content)?;
+
+            println!("  {} {}", "Service:".cyan(), service_file.display());
+            files.push(service_file);
+        }
+        _ => {}
+    }
+
+    Ok(files)
+}
+
+/// Write the UI scaffold of one contract, keeping files that already exist; returns the written files
+fn write_scaffold(
+    abi: &serde_json::Value,
+    contract_name: &str,
+    args: &TypegenArgs,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let dir = args.output.join(crate::codegen::SCAFFOLD_DIR);
+    std::fs::create_dir_all(&dir)?;
+
+    let mut written = Vec::new();
+    let mut kept = Vec::new();
+    for file in crate::codegen::generate_react_scaffold(contract_name, abi)? {
+        let path = dir.join(&file.name);
+        if path.exists() {
+            kept.push(file.name);
+            continue;
+        }
+        std::fs::write(&path, file.content)?;
+        println!("  {} {}", "Scaffold:".cyan(), path.display());
+        written.push(path);
+    }
+    if !kept.is_empty() {
         println!(
-            "  import {{ {}Contract }} from './{}'",
-            contract_name,
-            types_file.display()
+            "  {} Kept existing {} (delete to regenerate)",
+            "⚠".yellow(),
+            kept.join(", ")
         );
+    }
+
+    Ok(written)
+}
+
+/// Write the Rust client module (or ink! trait with --lang ink) of one contract; returns its path
+fn write_rust_client(
+    abi: &serde_json::Value,
+    contract_name: &str,
+    args: &TypegenArgs,
+) -> anyhow::Result<PathBuf> {
+    let (content, file_name, kind) = if args.lang == "ink" {
+        (
+            crate::codegen::generate_ink_trait(contract_name, abi)?,
+            crate::codegen::ink_trait_file(contract_name),
+            "ink! trait definition",
+        )
     } else {
+        (
+            crate::codegen::generate_rust_client(contract_name, abi)?,
+            format!("{}.rs", crate::codegen::rust_module_name(contract_name)),
+            "Rust client",
+        )
+    };
+
+    std::fs::create_dir_all(&args.output)?;
+    let client_file = args.output.join(file_name);
+    std::fs::write(&client_file, content)?;
+    rustfmt(&client_file);
+
+    println!("\n{} {} generated!", "✓".green().bold(), kind);
+    println!("  {} {}", "Output:".cyan(), client_file.display());
+
+    write_documents(abi, contract_name, args)?;
+
+    Ok(client_file)
+}
+
+/// Write the language-independent documents requested by --schema, --openrpc and --format;
+/// returns the written files
+fn write_documents(
+    abi: &serde_json::Value,
+    contract_name: &str,
+    args: &TypegenArgs,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if args.schema {
+        let schema = crate::codegen::generate_json_schema(contract_name, abi)?;
+        let schema_file = args.output.join(crate::codegen::schema_file(contract_name));
+        std::fs::write(&schema_file, serde_json::to_string_pretty(&schema)?)?;
+
+        println!("  {} {}", "Schema:".cyan(), schema_file.display());
+        files.push(schema_file);
+    }
+    if args.openrpc {
+        let document = crate::codegen::generate_openrpc(contract_name, abi)?;
+        let openrpc_file = args
+            .output
+            .join(crate::codegen::openrpc_file(contract_name));
+        std::fs::write(&openrpc_file, serde_json::to_string_pretty(&document)?)?;
+
+        println!("  {} {}", "OpenRPC:".cyan(), openrpc_file.display());
+        files.push(openrpc_file);
+    }
+    if args.format.as_deref() == Some("evm-abi") {
+        let abi = crate::codegen::generate_evm_abi(abi)?;
+        let abi_file = args
+            .output
+            .join(crate::codegen::evm_abi_file(contract_name));
+        std::fs::write(&abi_file, serde_json::to_string_pretty(&abi.items)?)?;
+
+        println!("  {} {}", "EVM ABI:".cyan(), abi_file.display());
+        for skipped in &abi.skipped {
+            println!("    {} skipped {}", "⚠".yellow(), skipped);
+        }
+        files.push(abi_file);
+    }
+    Ok(files)
+}
+
+/// Compile the TypeScript sources in `output` with tsc, emitting `.d.ts` and source maps next to the JavaScript
+///
+/// `esm` and `cjs` build into `dist/`; `both` into `dist/esm` and `dist/cjs`, with a
+/// package.json named after `name` whose exports resolve `entry` and every other
+/// module per format. ES builds get `.js` import specifiers and a `"type": "module"`
+/// package.json, as Node requires.
+fn compile_modules(
+    output: &std::path::Path,
+    module: &str,
+    name: &str,
+    entry: &str,
+) -> anyhow::Result<()> {
+    let mut sources: Vec<PathBuf> = std::fs::read_dir(output)?
+        .collect::<std::io::Result<Vec<_>>>()?
+        .into_iter()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+            name.ends_with(".ts") && !name.ends_with(".d.ts")
+        })
+        .collect();
+    sources.sort();
+
+    let tsc = find_tsc().ok_or_else(|| {
+        anyhow::anyhow!(
+            "--module needs the TypeScript compiler; install it with `npm install -D typescript`"
+        )
+    })?;
+
+    let formats = match module {
+        "both" => vec!["esm", "cjs"],
+        format => vec![format],
+    };
+    println!("\n{} Compiling {} module(s)...", "→".cyan(), module);
+    for format in &formats {
+        let out_dir = if module == "both" {
+            output.join("dist").join(format)
+        } else {
+            output.join("dist")
+        };
+        let result = std::process::Command::new(&tsc)
+            .args(["--declaration", "--declarationMap", "--sourceMap"])
+            .args(["--target", "ES2020", "--moduleResolution", "node"])
+            .args([
+                "--module",
+                if *format == "esm" {
+                    "ES2020"
+                } else {
+                    "CommonJS"
+                },
+            ])
+            .args(["--esModuleInterop", "--skipLibCheck"])
+            .arg("--outDir")
+            .arg(&out_dir)
+            .args(&sources)
+            .output()?;
+        if !result.status.success() {
+            anyhow::bail!(
+                "tsc failed for {}:\n{}{}",
+                format,
+                String::from_utf8_lossy(&result.stdout),
+                String::from_utf8_lossy(&result.stderr)
+            );
+        }
+        if *format == "esm" {
+            for entry in std::fs::read_dir(&out_dir)? {
+                let path = entry?.path();
+                let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+                if name.ends_with(".js") || name.ends_with(".d.ts") {
+                    let source = std::fs::read_to_string(&path)?;
+                    std::fs::write(&path, crate::codegen::esm_specifiers(&source))?;
+                }
+            }
+        }
+        if module == "both" || *format == "esm" {
+            std::fs::write(
+                out_dir.join("package.json"),
+                serde_json::to_string_pretty(&crate::codegen::format_package_json(format))?,
+            )?;
+        }
         println!(
-            "  import type {{ {}, {}Queries, {}Transactions }} from './{}'",
-            contract_name,
-            contract_name,
-            contract_name,
-            types_file.display()
+            "  {} {}",
+            format!("{}:", format.to_uppercase()).cyan(),
+            out_dir.display()
         );
-        println!("  // Fully type-safe contract interactions with IDE autocomplete!");
     }
 
+    if module == "both" {
+        let package_file = output.join("package.json");
+        std::fs::write(
+            &package_file,
+            crate::codegen::generate_package_json(name, entry),
+        )?;
+        println!("  {} {}", "Package:".cyan(), package_file.display());
+    }
     Ok(())
 }
 
+/// Name of the `--all` package: the Cargo package in the current directory, or
+/// the directory's name
+fn project_name() -> String {
+    let manifest = std::fs::read_to_string("Cargo.toml")
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok());
+    if let Some(name) = manifest
+        .as_ref()
+        .and_then(|manifest| manifest.get("package")?.get("name")?.as_str())
+    {
+        return name.to_string();
+    }
+    std::env::current_dir()
+        .ok()
+        .and_then(|dir| dir.file_name()?.to_str().map(str::to_string))
+        .unwrap_or_else(|| "contracts".to_string())
+}
+
+/// Project-local tsc from node_modules/.bin, or one on PATH
+fn find_tsc() -> Option<PathBuf> {
+    let mut dir = std::env::current_dir().ok();
+    while let Some(current) = dir {
+        let local = current.join("node_modules").join(".bin").join("tsc");
+        if local.exists() {
+            return Some(local);
+        }
+        dir = current.parent().map(PathBuf::from);
+    }
+    std::process::Command::new("tsc")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|_| PathBuf::from("tsc"))
+}
+
+/// Format generated Rust code when rustfmt is installed
+fn rustfmt(path: &std::path::Path) {
+    let _ = std::process::Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .arg(path)
+        .status();
+}
+
+/// Generate one module per contract artifact and an index.ts barrel
+fn generate_all(
+    args: &TypegenArgs,
+    framework: Option<&str>,
+    templates: &crate::codegen::Templates,
+) -> anyhow::Result<()> {
+    let mut names: Vec<String> = Vec::new();
+
+    for path in find_all_metadata()? {
+        let abi: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        // Other JSON files may live next to the artifacts
+        let Ok(contract_name) = crate::codegen::extract_contract_name(&abi) else {
+            continue;
+        };
+        if names.contains(&contract_name) {
+            continue;
+        }
+
+        println!(
+            "\n{} {} ({})",
+            "→".cyan(),
+            contract_name.bold(),
+            path.display()
+        );
+        if args.lang != "typescript" {
+            write_rust_client(&abi, &contract_name, args)?;
+        } else {
+            write_bindings(&abi, &contract_name, args, framework, templates)?;
+        }
+        names.push(contract_name);
+    }
+
+    if names.is_empty() {
+        anyhow::bail!("No contract metadata found in artifacts/ or target/ink. Run build first.");
+    }
+
+    names.sort();
+    if args.lang == "ink" {
+        println!(
+            "\n{} Generated trait definitions for {} contract(s)",
+            "✓".green().bold(),
+            names.len()
+        );
+        return Ok(());
+    }
+    if args.lang == "rust" {
+        let mod_file = args.output.join("mod.rs");
+        std::fs::write(&mod_file, crate::codegen::generate_rust_mod(&names))?;
+
+        println!(
+            "\n{} Generated clients for {} contract(s)",
+            "✓".green().bold(),
+            names.len()
+        );
+        println!("  {} {}", "Module:".cyan(), mod_file.display());
+        return Ok(());
+    }
+    let index_file = args.output.join("index.ts");
+    std::fs::write(&index_file, crate::codegen::generate_index(&names))?;
+
+    println!(
+        "\n{} Generated bindings for {} contract(s)",
+        "✓".green().bold(),
+        names.len()
+    );
+    println!("  {} {}", "Index:".cyan(), index_file.display());
+    if let Some(module) = &args.module {
+        compile_modules(&args.output, module, &project_name(), "index")?;
+    }
+    println!("\n{}", "Usage example:".bold());
+    println!(
+        "  import {{ contracts, type Contracts }} from './{}'",
+        args.output.display()
+    );
+    println!(
+        "  contracts.{}.deploy(deployer, metadata, 'new', [...args])",
+        names[0]
+    );
+
+    Ok(())
+}
+
+/// Every metadata JSON under artifacts/ and target/ink, artifacts first
+fn find_all_metadata() -> anyhow::Result<Vec<PathBuf>> {
+    fn collect(dir: &std::path::Path, found: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        if !dir.exists() {
+            return Ok(());
+        }
+        let mut entries: Vec<_> = std::fs::read_dir(dir)?
+            .collect::<std::io::Result<Vec<_>>>()?
+            .into_iter()
+            .map(|entry| entry.path())
+            .collect();
+        entries.sort();
+
+        for path in entries {
+            if path.is_dir() {
+                collect(&path, found)?;
+            } else if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                found.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut found = Vec::new();
+    collect(std::path::Path::new("artifacts"), &mut found)?;
+    collect(std::path::Path::new("target/ink"), &mut found)?;
+    Ok(found)
+}
+
 /// Find metadata JSON file in artifacts/ directory
 fn find_metadata_in_artifacts() -> anyhow::Result<Option<PathBuf>> {
     let artifacts_dir = PathBuf::from("artifacts");
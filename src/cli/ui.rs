@@ -0,0 +1,983 @@
+// Terminal dashboard
+//
+// `glin-forge ui` keeps the state of a network in view during development: the
+// connection and the latest finalized block, the contracts of the deployments
+// registry, a feed of the events they emit (decoded with their metadata), the
+// recent transactions to contracts and the balances of the dev accounts. A
+// background task follows the finalized blocks and feeds the panels. `q` and
+// `c` open a dialog to query or call a message of the selected contract,
+// taking arguments like the native console's `.query` and `.call`.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use futures::StreamExt;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+use scale::Decode;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use subxt::utils::{AccountId32, MultiAddress};
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+use crate::cli::native_console::{self, Artifact, Contract};
+use crate::config::deployments::DeploymentRegistry;
+use crate::contract::events::EventDecoder;
+
+/// Lines kept in the event and transaction feeds
+const FEED_LENGTH: usize = 200;
+
+/// Wait before reconnecting to a node that went away
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// Longest wait for a key before the dashboard redraws
+const TICK: Duration = Duration::from_millis(100);
+
+#[derive(Parser)]
+pub struct UiArgs {
+    /// Network to connect to
+    #[arg(short, long, default_value = "local")]
+    pub network: String,
+
+    /// Account signing the calls
+    #[arg(short, long, default_value = "alice")]
+    pub account: String,
+
+    /// Account whose balance is shown, by name or address (repeatable) [default: the dev
+    /// accounts]
+    #[arg(long = "balance", value_name = "ACCOUNT")]
+    pub balances: Vec<String>,
+
+    /// Path to contract artifacts
+    #[arg(long, default_value = "./artifacts")]
+    pub artifacts_path: PathBuf,
+}
+
+pub async fn execute(args: UiArgs) -> Result<()> {
+    let rpc = crate::cli::console::network_rpc(&args.network)?;
+    native_console::signer_address(&args.account)
+        .with_context(|| format!("Cannot sign calls as {}", args.account))?;
+    let accounts = match args.balances.is_empty() {
+        true => native_console::DEV_ACCOUNTS
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        false => args.balances.clone(),
+    };
+    let mut app = App::new(&args.network, &rpc, &args.account, args.artifacts_path);
+    app.balances = accounts
+        .iter()
+        .map(|account| {
+            let address = match crate::contract::parse_account_id(account) {
+                Ok(_) => account.clone(),
+                Err(_) => native_console::signer_address(account)?,
+            };
+            Ok(Balance {
+                label: account.clone(),
+                address,
+                free: None,
+            })
+        })
+        .collect::<Result<_>>()?;
+    app.load()?;
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal).await;
+    ratatui::restore();
+    result
+}
+
+/// Connection to the network's node
+#[derive(Debug, Clone, PartialEq)]
+enum Connection {
+    Connecting,
+    Connected { chain: String },
+    Disconnected(String),
+}
+
+/// What the background tasks report to the dashboard
+enum Update {
+    Connected {
+        client: glin_client::GlinClient,
+        chain: String,
+    },
+    Disconnected(String),
+    Block(u64),
+    Event(String),
+    Transaction(String),
+    Balances(Vec<Option<u128>>),
+    /// Outcome of a query or call of the dialog
+    Outcome(std::result::Result<String, String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DialogKind {
+    Query,
+    Call,
+}
+
+/// Dialog to query or call a message of the selected contract
+#[derive(Debug, Clone, PartialEq)]
+struct Dialog {
+    kind: DialogKind,
+    /// Method and arguments, as with `.query` and `.call`
+    input: String,
+    /// Message highlighted in the list
+    selected: usize,
+}
+
+/// What a key asks the dashboard to do
+#[derive(Debug, PartialEq)]
+enum Action {
+    Quit,
+    Reload,
+    Submit(DialogKind, String),
+}
+
+/// An account whose balance is shown
+#[derive(Debug, Clone, PartialEq)]
+struct Balance {
+    label: String,
+    address: String,
+    free: Option<u128>,
+}
+
+/// A contract whose events and transactions are followed
+struct Followed {
+    name: String,
+    account: AccountId32,
+    decoder: Option<EventDecoder>,
+}
+
+struct App {
+    network: String,
+    rpc: String,
+    account: String,
+    artifacts_dir: PathBuf,
+    artifacts: Vec<Artifact>,
+    contracts: Vec<Contract>,
+    selected: usize,
+    client: Option<glin_client::GlinClient>,
+    connection: Connection,
+    /// Latest finalized block, and when it came
+    block: Option<(u64, Instant)>,
+    events: VecDeque<String>,
+    transactions: VecDeque<String>,
+    balances: Vec<Balance>,
+    dialog: Option<Dialog>,
+    /// Outcome of the last action: whether it succeeded, and its message
+    status: Option<(bool, String)>,
+}
+
+impl App {
+    fn new(network: &str, rpc: &str, account: &str, artifacts_dir: PathBuf) -> Self {
+        Self {
+            network: network.to_string(),
+            rpc: rpc.to_string(),
+            account: account.to_string(),
+            artifacts_dir,
+            artifacts: Vec::new(),
+            contracts: Vec::new(),
+            selected: 0,
+            client: None,
+            connection: Connection::Connecting,
+            block: None,
+            events: VecDeque::new(),
+            transactions: VecDeque::new(),
+            balances: Vec::new(),
+            dialog: None,
+            status: None,
+        }
+    }
+
+    /// Load the artifacts, and the contracts deployed on the network
+    fn load(&mut self) -> Result<()> {
+        let dirs = [
+            self.artifacts_dir.as_path(),
+            std::path::Path::new("target/ink"),
+        ];
+        self.artifacts = native_console::load_artifacts(&dirs);
+        let registry = DeploymentRegistry::open_default()?;
+        self.contracts = native_console::load_contracts(&registry, &self.network, &self.artifacts);
+        self.selected = self.selected.min(self.contracts.len().saturating_sub(1));
+        Ok(())
+    }
+
+    /// Contracts to follow, with the decoders of their metadata
+    fn followed(&self) -> Vec<Followed> {
+        self.contracts
+            .iter()
+            .filter_map(|contract| {
+                let account = crate::contract::parse_account_id(&contract.address).ok()?;
+                let decoder = contract.artifact.and_then(|index| {
+                    EventDecoder::load(&self.artifacts[index].path.to_string_lossy()).ok()
+                });
+                Some(Followed {
+                    name: contract.name.clone(),
+                    account,
+                    decoder,
+                })
+            })
+            .collect()
+    }
+
+    fn accounts(&self) -> Vec<AccountId32> {
+        self.balances
+            .iter()
+            .filter_map(|balance| crate::contract::parse_account_id(&balance.address).ok())
+            .collect()
+    }
+
+    async fn run(&mut self, terminal: &mut ratatui::DefaultTerminal) -> Result<()> {
+        let (updates, mut received) = mpsc::unbounded_channel();
+        let mut follower = tokio::spawn(follow(
+            self.rpc.clone(),
+            self.followed(),
+            self.accounts(),
+            updates.clone(),
+        ));
+
+        loop {
+            while let Ok(update) = received.try_recv() {
+                self.apply(update);
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let key = tokio::task::block_in_place(|| -> Result<Option<KeyEvent>> {
+                if !event::poll(TICK)? {
+                    return Ok(None);
+                }
+                Ok(match event::read()? {
+                    Event::Key(key) if key.kind == KeyEventKind::Press => Some(key),
+                    _ => None,
+                })
+            })?;
+            let Some(action) = key.and_then(|key| self.on_key(key)) else {
+                continue;
+            };
+            match action {
+                Action::Quit => break,
+                Action::Reload => {
+                    follower.abort();
+                    self.status = Some(match self.load() {
+                        Ok(()) => (true, format!("Loaded {} contract(s)", self.contracts.len())),
+                        Err(e) => (false, format!("{:#}", e)),
+                    });
+                    self.connection = Connection::Connecting;
+                    follower = tokio::spawn(follow(
+                        self.rpc.clone(),
+                        self.followed(),
+                        self.accounts(),
+                        updates.clone(),
+                    ));
+                }
+                Action::Submit(kind, line) => {
+                    if let Err(e) = self.submit(kind, &line, &updates) {
+                        self.status = Some((false, format!("{:#}", e)));
+                    }
+                }
+            }
+        }
+        follower.abort();
+        Ok(())
+    }
+
+    fn apply(&mut self, update: Update) {
+        let push = |feed: &mut VecDeque<String>, line: String| {
+            feed.push_front(line);
+            feed.truncate(FEED_LENGTH);
+        };
+        match update {
+            Update::Connected { client, chain } => {
+                self.client = Some(client);
+                self.connection = Connection::Connected { chain };
+            }
+            Update::Disconnected(error) => {
+                self.client = None;
+                self.connection = Connection::Disconnected(error);
+            }
+            Update::Block(number) => self.block = Some((number, Instant::now())),
+            Update::Event(line) => push(&mut self.events, line),
+            Update::Transaction(line) => push(&mut self.transactions, line),
+            Update::Balances(free) => {
+                for (balance, free) in self.balances.iter_mut().zip(free) {
+                    balance.free = free;
+                }
+            }
+            Update::Outcome(outcome) => {
+                self.status = Some(match outcome {
+                    Ok(message) => (true, message),
+                    Err(error) => (false, error),
+                })
+            }
+        }
+    }
+
+    /// Messages the dialog offers: the queries, or the messages mutating the contract
+    fn dialog_messages(&self, kind: DialogKind) -> Vec<&native_console::Message> {
+        let Some(artifact) = self
+            .contracts
+            .get(self.selected)
+            .and_then(|contract| contract.artifact)
+        else {
+            return Vec::new();
+        };
+        self.artifacts[artifact]
+            .messages
+            .iter()
+            .filter(|message| message.mutates == (kind == DialogKind::Call))
+            .collect()
+    }
+
+    fn on_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        if let Some(mut dialog) = self.dialog.take() {
+            let messages: Vec<String> = self
+                .dialog_messages(dialog.kind)
+                .iter()
+                .map(|message| message.label.clone())
+                .collect();
+            match key.code {
+                KeyCode::Esc => return None,
+                KeyCode::Enter if !dialog.input.trim().is_empty() => {
+                    return Some(Action::Submit(dialog.kind, dialog.input));
+                }
+                KeyCode::Up | KeyCode::Down if !messages.is_empty() => {
+                    dialog.selected = match key.code {
+                        KeyCode::Up => dialog.selected.checked_sub(1).unwrap_or(messages.len() - 1),
+                        _ => (dialog.selected + 1) % messages.len(),
+                    };
+                    dialog.input = format!("{} ", messages[dialog.selected]);
+                }
+                KeyCode::Tab => {
+                    if let Some(message) =
+                        messages.iter().find(|m| m.starts_with(dialog.input.trim()))
+                    {
+                        dialog.input = format!("{} ", message);
+                    }
+                }
+                KeyCode::Backspace => {
+                    dialog.input.pop();
+                }
+                KeyCode::Char(c) => dialog.input.push(c),
+                _ => {}
+            }
+            self.dialog = Some(dialog);
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('x') => return Some(Action::Quit),
+            KeyCode::Char('r') => return Some(Action::Reload),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.contracts.len().saturating_sub(1))
+            }
+            KeyCode::Char(c @ ('q' | 'c')) => {
+                let kind = match c {
+                    'q' => DialogKind::Query,
+                    _ => DialogKind::Call,
+                };
+                match self.contracts.get(self.selected) {
+                    Some(contract) if contract.artifact.is_some() => {
+                        let input = self
+                            .dialog_messages(kind)
+                            .first()
+                            .map(|message| format!("{} ", message.label))
+                            .unwrap_or_default();
+                        self.dialog = Some(Dialog {
+                            kind,
+                            input,
+                            selected: 0,
+                        });
+                    }
+                    Some(contract) => {
+                        let message = format!("No metadata found for {}", contract.name);
+                        self.status = Some((false, message));
+                    }
+                    None => {
+                        let message = format!("No contracts deployed on {}", self.network);
+                        self.status = Some((false, message));
+                    }
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Query or call the selected contract in the background, with its outcome as an update
+    fn submit(
+        &mut self,
+        kind: DialogKind,
+        line: &str,
+        updates: &UnboundedSender<Update>,
+    ) -> Result<()> {
+        let words = native_console::split_words(line)?;
+        let (method, args) = words.split_first().context("Name a method")?;
+        let contract = self
+            .contracts
+            .get(self.selected)
+            .context("No contract selected")?;
+        let artifact = contract
+            .artifact
+            .map(|index| &self.artifacts[index])
+            .with_context(|| format!("No metadata found for {}", contract.name))?;
+        let label = native_console::message_label(artifact, method)?;
+        let args = args
+            .iter()
+            .map(|arg| native_console::argument(&self.contracts, arg))
+            .collect::<Result<Vec<_>>>()?;
+        let client = self
+            .client
+            .clone()
+            .with_context(|| format!("Not connected to {}", self.network))?;
+        let signer = crate::rpc::accounts::signer(&self.account)?;
+        let metadata = native_console::parse_artifact(artifact)?;
+
+        let rpc = self.rpc.clone();
+        let address = contract.address.clone();
+        let name = format!("{}.{}", contract.name, label);
+        self.status = Some((true, format!("Running {}...", name)));
+        let updates = updates.clone();
+        tokio::spawn(async move {
+            let outcome = match kind {
+                DialogKind::Query => crate::contract::query_contract(
+                    &client, &rpc, &address, &metadata, &label, args,
+                )
+                .await
+                .and_then(|result| match result.success {
+                    true => Ok(format!(
+                        "{} → {}",
+                        name,
+                        result.data.unwrap_or_else(|| "no data".to_string())
+                    )),
+                    false => Err(anyhow::anyhow!(
+                        "Query failed: {}",
+                        result.error.unwrap_or_else(|| "Unknown error".to_string())
+                    )),
+                }),
+                DialogKind::Call => crate::contract::call_contract(
+                    &client, &address, &metadata, &label, args, 0, &signer,
+                )
+                .await
+                .and_then(|result| match result.success {
+                    true => Ok(format!(
+                        "✓ {} in {}",
+                        name,
+                        result
+                            .tx_hash
+                            .unwrap_or_else(|| "a transaction".to_string())
+                    )),
+                    false => Err(anyhow::anyhow!(
+                        "Transaction failed: {}",
+                        result.error.unwrap_or_else(|| "Unknown error".to_string())
+                    )),
+                }),
+            };
+            let _ = updates.send(Update::Outcome(outcome.map_err(|e| format!("{:#}", e))));
+        });
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(body);
+        let [contracts, balances] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(self.balances.len() as u16 + 2),
+        ])
+        .areas(left);
+        let [events, transactions] =
+            Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(right);
+
+        self.draw_header(frame, header);
+        self.draw_contracts(frame, contracts);
+        self.draw_balances(frame, balances);
+        draw_feed(frame, events, "Events", &self.events);
+        draw_feed(frame, transactions, "Transactions", &self.transactions);
+        self.draw_footer(frame, footer);
+        if let Some(dialog) = &self.dialog {
+            self.draw_dialog(frame, dialog);
+        }
+    }
+
+    fn draw_header(&self, frame: &mut Frame, area: Rect) {
+        let connection = match &self.connection {
+            Connection::Connecting => Span::styled("connecting...", Style::new().fg(Color::Yellow)),
+            Connection::Connected { chain } if chain.is_empty() => {
+                Span::styled("connected", Style::new().fg(Color::Green))
+            }
+            Connection::Connected { chain } => Span::styled(
+                format!("connected to {}", chain),
+                Style::new().fg(Color::Green),
+            ),
+            Connection::Disconnected(error) => Span::styled(
+                format!("disconnected: {}", error),
+                Style::new().fg(Color::Red),
+            ),
+        };
+        let block = match self.block {
+            Some((number, at)) => format!("#{} ({}s ago)", number, at.elapsed().as_secs()),
+            None => "-".to_string(),
+        };
+        let line = Line::from(vec![
+            Span::styled(self.network.clone(), Style::new().bold()),
+            Span::raw(format!(" {}  ", self.rpc)),
+            connection,
+            Span::raw("  Finalized: ".to_string()).fg(Color::Cyan),
+            Span::raw(block),
+            Span::raw("  Account: ".to_string()).fg(Color::Cyan),
+            Span::raw(self.account.clone()),
+        ]);
+        frame.render_widget(
+            Paragraph::new(line).block(Block::bordered().title(" glin-forge ")),
+            area,
+        );
+    }
+
+    fn draw_contracts(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .contracts
+            .iter()
+            .map(|contract| {
+                let methods = match contract.artifact {
+                    Some(index) => format!("{} messages", self.artifacts[index].messages.len()),
+                    None => "no metadata".to_string(),
+                };
+                ListItem::new(vec![
+                    Line::from(Span::styled(contract.name.clone(), Style::new().bold())),
+                    Line::from(format!("  {}", contract.address)).fg(Color::DarkGray),
+                    Line::from(format!("  {}", methods)).fg(Color::DarkGray),
+                ])
+            })
+            .collect();
+        let title = format!(" Contracts on {} ", self.network);
+        let list = match items.is_empty() {
+            true => List::new([ListItem::new("No deployments recorded").fg(Color::DarkGray)]),
+            false => List::new(items),
+        }
+        .block(Block::bordered().title(title))
+        .highlight_style(
+            Style::new()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+        let mut state = ListState::default()
+            .with_selected((!self.contracts.is_empty()).then_some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_balances(&self, frame: &mut Frame, area: Rect) {
+        let width = self
+            .balances
+            .iter()
+            .map(|b| b.label.len())
+            .max()
+            .unwrap_or_default();
+        let lines: Vec<Line> = self
+            .balances
+            .iter()
+            .map(|balance| {
+                let free = match balance.free {
+                    Some(free) => format!("{} GLIN", crate::cli::balance::format_balance(free)),
+                    None => "-".to_string(),
+                };
+                Line::from(vec![
+                    Span::raw(format!("{:width$}  ", balance.label, width = width)).fg(Color::Cyan),
+                    Span::raw(free),
+                ])
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(" Balances ")),
+            area,
+        );
+    }
+
+    fn draw_footer(&self, frame: &mut Frame, area: Rect) {
+        let status = match &self.status {
+            Some((true, message)) => Line::from(message.clone()).fg(Color::Green),
+            Some((false, message)) => Line::from(message.clone()).fg(Color::Red),
+            None => Line::from(""),
+        };
+        let keys = match self.dialog {
+            Some(_) => "Enter run · ↑/↓ pick a message · Tab complete · Esc close",
+            None => "↑/↓ select · q query · c call · r reload · x/Esc quit",
+        };
+        frame.render_widget(
+            Paragraph::new(vec![status, Line::from(keys).fg(Color::DarkGray)]),
+            area,
+        );
+    }
+
+    fn draw_dialog(&self, frame: &mut Frame, dialog: &Dialog) {
+        let messages = self.dialog_messages(dialog.kind);
+        let contract = self
+            .contracts
+            .get(self.selected)
+            .map(|contract| contract.name.as_str())
+            .unwrap_or_default();
+        let title = match dialog.kind {
+            DialogKind::Query => format!(" Query {} ", contract),
+            DialogKind::Call => format!(" Call {} as {} ", contract, self.account),
+        };
+        let area = frame.area();
+        let height = (messages.len() as u16 + 5).min(area.height);
+        let width = (area.width * 3 / 4).max(40).min(area.width);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let mut lines: Vec<Line> = messages
+            .iter()
+            .enumerate()
+            .map(|(index, message)| {
+                let line = Line::from(format!("  {}", message.signature()));
+                match index == dialog.selected {
+                    true => line.bold(),
+                    false => line.fg(Color::DarkGray),
+                }
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from("  No such messages").fg(Color::DarkGray));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::raw("> ").fg(Color::Cyan),
+            Span::raw(dialog.input.clone()),
+            Span::raw("█"),
+        ]));
+        frame.render_widget(Clear, popup);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(title)),
+            popup,
+        );
+    }
+}
+
+fn draw_feed(frame: &mut Frame, area: Rect, title: &str, feed: &VecDeque<String>) {
+    let lines: Vec<Line> = match feed.is_empty() {
+        true => vec![Line::from("Waiting for finalized blocks...").fg(Color::DarkGray)],
+        false => feed
+            .iter()
+            .take(area.height as usize)
+            .map(|line| Line::from(line.clone()))
+            .collect(),
+    };
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(format!(" {} ", title))),
+        area,
+    );
+}
+
+/// Follow the finalized blocks of a node, reconnecting whenever it goes away, until the
+/// dashboard stops listening
+async fn follow(
+    rpc: String,
+    contracts: Vec<Followed>,
+    accounts: Vec<AccountId32>,
+    updates: UnboundedSender<Update>,
+) {
+    loop {
+        if let Err(e) = follow_blocks(&rpc, &contracts, &accounts, &updates).await {
+            if updates.send(Update::Disconnected(e.to_string())).is_err() {
+                return;
+            }
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn follow_blocks(
+    rpc: &str,
+    contracts: &[Followed],
+    accounts: &[AccountId32],
+    updates: &UnboundedSender<Update>,
+) -> Result<()> {
+    let client = glin_client::create_client(rpc)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e.root_cause()))?;
+    let chain = match glin_client::create_rpc_client(rpc).await {
+        Ok(methods) => methods.system_chain().await.unwrap_or_default(),
+        Err(_) => String::new(),
+    };
+    let send = |update| {
+        updates
+            .send(update)
+            .map_err(|_| anyhow::anyhow!("The dashboard closed"))
+    };
+    send(Update::Connected {
+        client: client.clone(),
+        chain,
+    })?;
+    send(Update::Balances(balances(&client, accounts).await))?;
+
+    let mut blocks = client.blocks().subscribe_finalized().await?;
+    while let Some(block) = blocks.next().await {
+        let block = block?;
+        let number = block.number() as u64;
+        send(Update::Block(number))?;
+
+        let events = block.events().await?;
+        for event in events.iter() {
+            let event = event?;
+            if event.pallet_name() != "Contracts" {
+                continue;
+            }
+            let emitter = crate::cli::watch::event_contract(&event);
+            let Some(contract) = contracts
+                .iter()
+                .find(|c| emitter.as_ref() == Some(&c.account))
+            else {
+                continue;
+            };
+            let decoded = crate::cli::watch::decode_emitted(
+                &event,
+                contract.decoder.as_ref(),
+                Some(&contract.account),
+            );
+            let description = match decoded {
+                Some(Ok(decoded)) => decoded.to_string(),
+                _ => format!("{}::{}", event.pallet_name(), event.variant_name()),
+            };
+            send(Update::Event(format!(
+                "#{} {} {}",
+                number, contract.name, description
+            )))?;
+        }
+
+        for extrinsic in block.extrinsics().await?.iter() {
+            if extrinsic.pallet_name().ok() != Some("Contracts") {
+                continue;
+            }
+            let failed = events.iter().flatten().any(|event| {
+                event.phase() == subxt::events::Phase::ApplyExtrinsic(extrinsic.index())
+                    && event.pallet_name() == "System"
+                    && event.variant_name() == "ExtrinsicFailed"
+            });
+            let caller = extrinsic
+                .address_bytes()
+                .and_then(|mut bytes| MultiAddress::<AccountId32, ()>::decode(&mut bytes).ok())
+                .map(|address| match address {
+                    MultiAddress::Id(account) => short_address(&account.to_string()),
+                    _ => "?".to_string(),
+                })
+                .unwrap_or_else(|| "unsigned".to_string());
+            let variant = extrinsic.variant_name().unwrap_or("?");
+            let description = match variant {
+                "call" => crate::cli::watch::ContractCall::decode(&mut extrinsic.field_bytes())
+                    .ok()
+                    .map(|call| describe_call(&call, contracts))
+                    .unwrap_or_else(|| "Contracts::call".to_string()),
+                variant => format!("Contracts::{}", variant),
+            };
+            let outcome = if failed { "✗" } else { "✓" };
+            let line = format!("#{} {} {} → {}", number, outcome, caller, description);
+            send(Update::Transaction(line))?;
+        }
+
+        send(Update::Balances(balances(&client, accounts).await))?;
+    }
+    anyhow::bail!("Block subscription ended")
+}
+
+/// Free balances of accounts, None for those that could not be read
+async fn balances(client: &glin_client::GlinClient, accounts: &[AccountId32]) -> Vec<Option<u128>> {
+    let mut free = Vec::new();
+    for account in accounts {
+        free.push(
+            crate::rpc::methods::free_balance(client, account)
+                .await
+                .ok(),
+        );
+    }
+    free
+}
+
+/// Contract and message of a `Contracts::call`, decoded when the contract is followed
+fn describe_call(call: &crate::cli::watch::ContractCall, contracts: &[Followed]) -> String {
+    let MultiAddress::Id(dest) = &call.dest else {
+        return "Contracts::call".to_string();
+    };
+    let Some(contract) = contracts.iter().find(|c| c.account == *dest) else {
+        return short_address(&dest.to_string());
+    };
+    let message = contract
+        .decoder
+        .as_ref()
+        .and_then(|decoder| decoder.decode_call(&call.data).ok())
+        .map(|decoded| decoded.to_string())
+        .unwrap_or_else(|| format!("0x{}", hex::encode(&call.data)));
+    let value = match call.value.0 {
+        0 => String::new(),
+        value => format!(" ({} GLIN)", crate::cli::balance::format_balance(value)),
+    };
+    format!("{} {}{}", contract.name, message, value)
+}
+
+fn short_address(address: &str) -> String {
+    match address.len() > 12 {
+        true => format!("{}…{}", &address[..6], &address[address.len() - 4..]),
+        false => address.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    fn app() -> App {
+        let mut app = App::new(
+            "local",
+            "ws://127.0.0.1:9944",
+            "alice",
+            PathBuf::from("artifacts"),
+        );
+        app.artifacts = vec![Artifact {
+            name: "flipper".to_string(),
+            path: PathBuf::from("artifacts/flipper.json"),
+            code_hash: None,
+            messages: ["flip", "get"]
+                .into_iter()
+                .map(|label| native_console::Message {
+                    label: label.to_string(),
+                    args: Vec::new(),
+                    mutates: label == "flip",
+                    payable: false,
+                })
+                .collect(),
+        }];
+        app.contracts = vec![
+            Contract {
+                name: "flipper".to_string(),
+                address: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".to_string(),
+                artifact: Some(0),
+            },
+            Contract {
+                name: "token".to_string(),
+                address: "5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y".to_string(),
+                artifact: None,
+            },
+        ];
+        app.balances = vec![Balance {
+            label: "alice".to_string(),
+            address: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".to_string(),
+            free: None,
+        }];
+        app
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn screen(app: &App) -> String {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_ui_updates() {
+        let mut app = app();
+        app.apply(Update::Block(42));
+        app.apply(Update::Balances(vec![Some(1_500_000_000_000_000_000)]));
+        app.apply(Update::Disconnected("Connection refused".to_string()));
+        for index in 0..FEED_LENGTH + 10 {
+            app.apply(Update::Event(format!("#{} flipper Flipped", index)));
+        }
+        assert_eq!(app.events.len(), FEED_LENGTH);
+        assert_eq!(
+            app.events[0],
+            format!("#{} flipper Flipped", FEED_LENGTH + 9)
+        );
+        app.apply(Update::Transaction(
+            "#42 ✓ 5Grwva…utQY → flipper flip()".to_string(),
+        ));
+        app.apply(Update::Outcome(Ok("flipper.get → true".to_string())));
+
+        let screen = screen(&app);
+        for text in [
+            "Contracts on local",
+            "flipper",
+            "2 messages",
+            "no metadata",
+            "#42",
+            "1.5000 GLIN",
+            "disconnected: Connection refused",
+            "flipper flip()",
+            "flipper.get → true",
+        ] {
+            assert!(screen.contains(text), "{} missing from\n{}", text, screen);
+        }
+    }
+
+    #[test]
+    fn test_ui_keys() {
+        let mut app = app();
+        assert_eq!(app.on_key(key(KeyCode::Char('r'))), Some(Action::Reload));
+        app.on_key(key(KeyCode::Down));
+        app.on_key(key(KeyCode::Down));
+        assert_eq!(app.selected, 1);
+        // Contracts without metadata have nothing to query
+        assert_eq!(app.on_key(key(KeyCode::Char('q'))), None);
+        assert!(app.dialog.is_none());
+        assert!(app.status.as_ref().is_some_and(|(ok, _)| !ok));
+
+        app.on_key(key(KeyCode::Up));
+        app.on_key(key(KeyCode::Char('q')));
+        assert_eq!(app.dialog.as_ref().unwrap().input, "get ");
+        assert!(screen(&app).contains("Query flipper"));
+        app.on_key(key(KeyCode::Esc));
+        assert!(app.dialog.is_none());
+
+        // The call dialog offers the mutating messages, and takes arguments after them
+        app.on_key(key(KeyCode::Char('c')));
+        assert_eq!(app.dialog.as_ref().unwrap().input, "flip ");
+        for c in "true".chars() {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        app.on_key(key(KeyCode::Backspace));
+        assert_eq!(
+            app.on_key(key(KeyCode::Enter)),
+            Some(Action::Submit(DialogKind::Call, "flip tru".to_string()))
+        );
+        assert!(app.dialog.is_none());
+
+        // Calls need a connection
+        let (updates, _received) = mpsc::unbounded_channel();
+        let error = app.submit(DialogKind::Call, "flip", &updates).unwrap_err();
+        assert!(error.to_string().contains("Not connected"), "{}", error);
+        let error = app.submit(DialogKind::Query, "nope", &updates).unwrap_err();
+        assert!(error.to_string().contains("no method nope"), "{}", error);
+
+        assert_eq!(app.on_key(key(KeyCode::Char('x'))), Some(Action::Quit));
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(app.on_key(ctrl_c), Some(Action::Quit));
+    }
+}
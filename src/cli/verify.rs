@@ -1,11 +1,27 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use colored::Colorize;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Log lines shown for failures without a recognizable compiler error
+const LOG_TAIL: usize = 20;
+
+/// Build manifest written into source archives
+const BUILD_MANIFEST: &str = "glin-forge-build.json";
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct VerifyArgs {
+    #[command(subcommand)]
+    command: Option<VerifyCommands>,
+
     /// Contract address to verify
-    pub address: String,
+    #[arg(required_unless_present = "all")]
+    pub address: Option<String>,
+
+    /// Verify every contract of the deployments registry on --network
+    #[arg(long, conflicts_with = "address")]
+    pub all: bool,
 
     /// Path to contract WASM file
     #[arg(short, long)]
@@ -26,26 +42,112 @@ pub struct VerifyArgs {
     /// Compiler version used
     #[arg(long)]
     pub compiler_version: Option<String>,
+
+    /// Rebuild the contract from --source and compare its code hash with the deployed one,
+    /// without the explorer
+    #[arg(long)]
+    pub local: bool,
+
+    /// Rebuild in cargo-contract's verifiable Docker image
+    #[arg(long, requires = "local")]
+    pub docker: bool,
+
+    /// Docker image to rebuild in [default: the one pinned in the contract's metadata]
+    #[arg(long, requires = "docker")]
+    pub image: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum VerifyCommands {
+    /// Poll the explorer until a submitted verification succeeds or fails
+    Status {
+        /// Contract address, deployment name or verification ID
+        target: String,
+
+        /// Network whose explorer verifies the contract
+        #[arg(short, long, default_value = "testnet")]
+        network: String,
+
+        /// Seconds between polls
+        #[arg(long, default_value = "5")]
+        interval: u64,
+
+        /// Seconds to wait for the verification to finish
+        #[arg(long, default_value = "600")]
+        timeout: u64,
+
+        /// Print the whole compiler log of a failed verification
+        #[arg(long)]
+        full_log: bool,
+    },
+
+    /// Package the contract's sources into a deterministic archive for explorers or auditors
+    Package {
+        /// Path to the contract project
+        #[arg(short, long, default_value = ".")]
+        path: PathBuf,
+
+        /// Archive to write [default: artifacts/<name>/<name>-<version>-source.tar.gz]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Record a verifiable build in cargo-contract's Docker image as the build command
+        #[arg(long)]
+        docker: bool,
+
+        /// Docker image of the verifiable build
+        #[arg(long, requires = "docker")]
+        image: Option<String>,
+    },
 }
 
 pub async fn execute(args: VerifyArgs) -> anyhow::Result<()> {
+    match &args.command {
+        Some(VerifyCommands::Status {
+            target,
+            network,
+            interval,
+            timeout,
+            full_log,
+        }) => {
+            let timing = (
+                Duration::from_secs(*interval),
+                Duration::from_secs(*timeout),
+            );
+            return status(target, network, timing, *full_log).await;
+        }
+        Some(VerifyCommands::Package {
+            path,
+            output,
+            docker,
+            image,
+        }) => return package(path, output.as_deref(), *docker, image.as_deref()),
+        None => {}
+    }
+    if args.all {
+        return verify_all(args).await;
+    }
+    if args.local {
+        return verify_local(args).await;
+    }
+    let address = args
+        .address
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Contract address required"))?;
+
     println!(
         "{}",
-        format!("Verifying contract: {}", args.address)
-            .cyan()
-            .bold()
+        format!("Verifying contract: {}", address).cyan().bold()
     );
 
     // Auto-detect files if not provided
-    let (wasm_path, metadata_path, source_path) = if args.wasm.is_none() || args.metadata.is_none()
-    {
-        find_verification_files(".")?
-    } else {
-        (
-            args.wasm.unwrap(),
-            args.metadata.unwrap(),
+    let (wasm_path, metadata_path, source_path) = match (args.wasm, args.metadata) {
+        (Some(wasm), Some(metadata)) => (
+            wasm,
+            metadata,
             args.source.unwrap_or_else(|| PathBuf::from(".")),
-        )
+        ),
+        _ => find_verification_files(".")?,
     };
 
     println!("\n{}", "Verification files:".bold());
@@ -57,7 +159,7 @@ pub async fn execute(args: VerifyArgs) -> anyhow::Result<()> {
     let network_config = crate::config::load_network(&args.network)?;
 
     println!("\n{}", "Verification details:".bold());
-    println!("  {} {}", "Contract:".cyan(), args.address);
+    println!("  {} {}", "Contract:".cyan(), address);
     println!("  {} {}", "Network:".cyan(), args.network);
 
     if let Some(compiler) = &args.compiler_version {
@@ -111,14 +213,13 @@ pub async fn execute(args: VerifyArgs) -> anyhow::Result<()> {
         println!("  {} {}", "Endpoint:".cyan(), verification_url);
 
         // Prepare verification payload
-        let payload = serde_json::json!({
-            "address": args.address,
-            "code_hash": code_hash_hex,
-            "wasm": hex::encode(&wasm_bytes),
-            "metadata": serde_json::from_str::<serde_json::Value>(&metadata_json)?,
-            "compiler_version": args.compiler_version.unwrap_or_else(|| "latest".to_string()),
-            "network": args.network,
-        });
+        let payload = verification_payload(
+            &address,
+            &wasm_bytes,
+            &metadata_json,
+            args.compiler_version.as_deref(),
+            &args.network,
+        )?;
 
         // Submit verification request
         let client = reqwest::Client::builder()
@@ -144,12 +245,18 @@ pub async fn execute(args: VerifyArgs) -> anyhow::Result<()> {
                     "  {} {}/contract/{}#code",
                     "View on Explorer:".cyan(),
                     explorer,
-                    args.address
+                    address
                 );
                 println!(
                     "\n{}",
                     "Verification usually completes in 1-2 minutes...".dimmed()
                 );
+                println!(
+                    "  {} Check progress: glin-forge verify status {} --network {}",
+                    "→".cyan(),
+                    address,
+                    args.network
+                );
             }
             Ok(response) => {
                 let status = response.status();
@@ -171,7 +278,7 @@ pub async fn execute(args: VerifyArgs) -> anyhow::Result<()> {
                 // Provide helpful fallback instructions
                 println!("\n{}", "Manual verification:".bold());
                 println!("  1. Visit: {}/verify", explorer);
-                println!("  2. Enter contract address: {}", args.address);
+                println!("  2. Enter contract address: {}", address);
                 println!("  3. Upload WASM: {}", wasm_path.display());
                 println!("  4. Upload metadata: {}", metadata_path.display());
 
@@ -185,6 +292,1036 @@ pub async fn execute(args: VerifyArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Request body of `POST <explorer>/api/verify`
+fn verification_payload(
+    address: &str,
+    wasm: &[u8],
+    metadata_json: &str,
+    compiler_version: Option<&str>,
+    network: &str,
+) -> anyhow::Result<serde_json::Value> {
+    let code_hash = sp_core_hashing::blake2_256(wasm);
+    Ok(serde_json::json!({
+        "address": address,
+        "code_hash": format!("0x{}", hex::encode(code_hash)),
+        "wasm": hex::encode(wasm),
+        "metadata": serde_json::from_str::<serde_json::Value>(metadata_json)?,
+        "compiler_version": compiler_version.unwrap_or("latest"),
+        "network": network,
+    }))
+}
+
+/// How verifying one contract of the registry went
+#[derive(Debug, Clone, PartialEq)]
+enum Outcome {
+    /// The rebuilt code hash is the deployed one
+    Matches,
+    Mismatch,
+    /// Submitted to the explorer, with the verification ID it answered
+    Submitted(Option<String>),
+    Skipped(String),
+    Failed(String),
+}
+
+impl Outcome {
+    fn failed(&self) -> bool {
+        matches!(self, Outcome::Mismatch | Outcome::Failed(_))
+    }
+
+    fn describe(&self) -> colored::ColoredString {
+        match self {
+            Outcome::Matches => "✓ matches".green(),
+            Outcome::Mismatch => "✗ code hash mismatch".red(),
+            Outcome::Submitted(Some(id)) => format!("✓ submitted ({})", id).green(),
+            Outcome::Submitted(None) => "✓ submitted".green(),
+            Outcome::Skipped(reason) => format!("- skipped: {}", reason).dimmed(),
+            Outcome::Failed(error) => format!("✗ {}", error).red(),
+        }
+    }
+}
+
+/// Submit or locally check every contract deployed on the network, then print a summary
+async fn verify_all(args: VerifyArgs) -> anyhow::Result<()> {
+    use crate::cli::native_console::{load_artifacts, load_contracts};
+
+    println!(
+        "{}",
+        format!("Verifying the contracts deployed on {}", args.network)
+            .cyan()
+            .bold()
+    );
+    let source = args.source.clone().unwrap_or_else(|| PathBuf::from("."));
+    let artifacts = load_artifacts(&[
+        std::path::Path::new("./artifacts"),
+        &source.join("target/ink"),
+    ]);
+    let registry = crate::config::deployments::DeploymentRegistry::open_default()?;
+    let contracts = load_contracts(&registry, &args.network, &artifacts);
+    if contracts.is_empty() {
+        anyhow::bail!(
+            "No contracts deployed on {} (see {})",
+            args.network,
+            crate::config::deployments::DEPLOYMENTS_FILE
+        );
+    }
+    let network_config = crate::config::load_network(&args.network)?;
+    let crates = workspace_crates(&source);
+
+    // Local checks need the chain, submissions the explorer
+    let (chain, explorer) = match args.local {
+        true => {
+            let client = glin_client::create_client(&network_config.rpc).await?;
+            (Some(client), None)
+        }
+        false => {
+            let explorer = network_config.explorer.clone().ok_or_else(|| {
+                anyhow::anyhow!("No explorer configured for network '{}'", args.network)
+            })?;
+            (None, Some(explorer))
+        }
+    };
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+
+    let mut results = Vec::new();
+    for contract in &contracts {
+        println!(
+            "\n{} {} ({})",
+            "→".cyan(),
+            contract.name.bold(),
+            contract.address
+        );
+        let outcome = match contract.artifact.map(|index| &artifacts[index]) {
+            None => Outcome::Skipped("no artifact".to_string()),
+            Some(artifact) => {
+                let verified = match (&chain, &explorer) {
+                    (Some(client), _) => {
+                        match crates
+                            .iter()
+                            .find(|(name, _)| same_crate(name, &artifact.name))
+                        {
+                            Some((_, dir)) => {
+                                check_locally(client, &contract.address, artifact, dir, &args).await
+                            }
+                            None => Ok(Outcome::Skipped("no manifest".to_string())),
+                        }
+                    }
+                    (None, Some(explorer)) => {
+                        submit(&http, explorer, &contract.address, artifact, &args).await
+                    }
+                    (None, None) => unreachable!("the chain or the explorer is set"),
+                };
+                verified.unwrap_or_else(|e| Outcome::Failed(format!("{:#}", e)))
+            }
+        };
+        println!("  {}", outcome.describe());
+        results.push((contract.name.as_str(), contract.address.as_str(), outcome));
+    }
+
+    let width = results
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let width = width.max("Contract".len());
+    println!("\n{}", "Summary:".bold());
+    println!(
+        "  {:<width$}  {:<48}  {}",
+        "Contract",
+        "Address",
+        "Result",
+        width = width
+    );
+    for (name, address, outcome) in &results {
+        println!(
+            "  {:<width$}  {:<48}  {}",
+            name,
+            address,
+            outcome.describe(),
+            width = width
+        );
+    }
+
+    let failed = results
+        .iter()
+        .filter(|(_, _, outcome)| outcome.failed())
+        .count();
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} contract(s) failed verification",
+            failed,
+            results.len()
+        );
+    }
+    Ok(())
+}
+
+/// Rebuild a contract from its crate and compare the code hash with the deployed one
+async fn check_locally(
+    client: &glin_client::GlinClient,
+    address: &str,
+    artifact: &crate::cli::native_console::Artifact,
+    dir: &std::path::Path,
+    args: &VerifyArgs,
+) -> anyhow::Result<Outcome> {
+    let account = crate::contract::parse_account_id(address)?;
+    let Some(deployed) = deployed_code_hash(client, &account).await? else {
+        return Ok(Outcome::Failed("not found on chain".to_string()));
+    };
+    let image = match args.docker {
+        true => args.image.clone().or_else(|| {
+            std::fs::read_to_string(&artifact.path)
+                .ok()
+                .and_then(|json| pinned_image(&json))
+        }),
+        false => None,
+    };
+    let wasm = rebuild(dir, args.docker, image.as_deref())?;
+    let code_hash = sp_core_hashing::blake2_256(&std::fs::read(wasm)?);
+    Ok(match code_hash == deployed {
+        true => Outcome::Matches,
+        false => Outcome::Mismatch,
+    })
+}
+
+/// Submit a contract's artifact to the explorer
+async fn submit(
+    http: &reqwest::Client,
+    explorer: &str,
+    address: &str,
+    artifact: &crate::cli::native_console::Artifact,
+    args: &VerifyArgs,
+) -> anyhow::Result<Outcome> {
+    let metadata_json = std::fs::read_to_string(&artifact.path)?;
+    let wasm = artifact_wasm(&artifact.path, &metadata_json)?;
+    let payload = verification_payload(
+        address,
+        &wasm,
+        &metadata_json,
+        args.compiler_version.as_deref(),
+        &args.network,
+    )?;
+    let response = http
+        .post(format!("{}/api/verify", explorer))
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| anyhow::anyhow!("Could not connect to explorer API: {}", e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!("Explorer returned status {}: {}", status, error_text);
+    }
+    let answer = response.json::<serde_json::Value>().await.ok();
+    let id = answer.and_then(|answer| match &answer["verification_id"] {
+        serde_json::Value::String(id) => Some(id.clone()),
+        serde_json::Value::Null => None,
+        id => Some(id.to_string()),
+    });
+    Ok(Outcome::Submitted(id))
+}
+
+/// WASM of an artifact: the `.wasm` next to its metadata, or the code in its `.contract`
+/// bundle
+fn artifact_wasm(metadata_path: &std::path::Path, metadata_json: &str) -> anyhow::Result<Vec<u8>> {
+    let wasm_path = metadata_path.with_extension("wasm");
+    if wasm_path.is_file() {
+        return Ok(std::fs::read(wasm_path)?);
+    }
+    let bundle = metadata_path.with_extension("contract");
+    let bundle_json = match bundle.is_file() {
+        true => std::fs::read_to_string(&bundle)?,
+        false => metadata_json.to_string(),
+    };
+    let bundle: serde_json::Value = serde_json::from_str(&bundle_json)?;
+    let code = bundle["source"]["wasm"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("No WASM found next to {}", metadata_path.display()))?;
+    Ok(hex::decode(code.trim_start_matches("0x"))?)
+}
+
+/// Package names and directories of the contract crates in a project: its root and
+/// `contracts/*`
+fn workspace_crates(root: &std::path::Path) -> Vec<(String, PathBuf)> {
+    let mut dirs = vec![root.to_path_buf()];
+    if let Ok(entries) = std::fs::read_dir(root.join("contracts")) {
+        let mut found: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        found.sort();
+        dirs.extend(found);
+    }
+    dirs.into_iter()
+        .filter_map(|dir| {
+            let manifest = std::fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+            let value: toml::Value = toml::from_str(&manifest).ok()?;
+            let name = value.get("package")?.get("name")?.as_str()?.to_string();
+            Some((name, dir))
+        })
+        .collect()
+}
+
+/// Whether a package and a contract name the same crate, as `my-token` and `my_token` do
+fn same_crate(package: &str, contract: &str) -> bool {
+    package.replace('-', "_") == contract.replace('-', "_")
+}
+
+/// Archive the sources a contract builds from, and record the archive's hash
+fn package(
+    dir: &std::path::Path,
+    output: Option<&std::path::Path>,
+    docker: bool,
+    image: Option<&str>,
+) -> anyhow::Result<()> {
+    println!("{}", "Packaging contract sources...".cyan().bold());
+    let name = package_field(dir, "name")?;
+    let version = package_field(dir, "version")?;
+
+    let (mut entries, member) = source_entries(dir)?;
+    let manifest_path = match &member {
+        Some(member) => format!("{}/Cargo.toml", member),
+        None => "Cargo.toml".to_string(),
+    };
+    let mut build_command = match (docker, image) {
+        (true, Some(image)) => format!("cargo contract build --verifiable --image {}", image),
+        (true, None) => "cargo contract build --verifiable".to_string(),
+        (false, _) => "cargo contract build --release".to_string(),
+    };
+    if member.is_some() {
+        build_command.push_str(&format!(" --manifest-path {}", manifest_path));
+    }
+    let toolchain = toolchain_file(&entries);
+    let build_manifest = serde_json::json!({
+        "contract": name,
+        "version": version,
+        "ink": crate::cli::doctor::ink_version(dir).map(|v| v.to_string()),
+        "rust_toolchain": toolchain,
+        "manifest_path": manifest_path,
+        "build_command": build_command,
+        "image": image,
+    });
+    entries.push((
+        BUILD_MANIFEST.to_string(),
+        format!("{}\n", serde_json::to_string_pretty(&build_manifest)?).into_bytes(),
+    ));
+    entries.sort();
+
+    let output = output.map(PathBuf::from).unwrap_or_else(|| {
+        PathBuf::from("artifacts")
+            .join(&name)
+            .join(format!("{}-{}-source.tar.gz", name, version))
+    });
+    let prefix = format!("{}-{}", name, version);
+    let archive = write_archive(&entries, &prefix, &output)?;
+
+    println!("\n{}", "Files:".bold());
+    for (path, data) in &entries {
+        println!("  {} {} ({} bytes)", "→".cyan(), path, data.len());
+    }
+    println!("\n{} Source archive created", "✓".green().bold());
+    println!("  {} {}", "Archive:".cyan(), output.display());
+    println!("  {} {}", "SHA-256:".cyan(), archive.sha256);
+    println!("  {} {}", "Build:".cyan(), build_command);
+
+    let mut manifest = crate::config::artifacts::ArtifactManifest::open_default()?;
+    manifest.record_source_archive(&name, &version, archive)?;
+    println!(
+        "  {} {}",
+        "Recorded in:".cyan(),
+        crate::config::artifacts::ARTIFACTS_FILE
+    );
+    Ok(())
+}
+
+/// Archive path of the toolchain file, which may sit next to a workspace member
+fn toolchain_file(entries: &[Entry]) -> Option<String> {
+    entries
+        .iter()
+        .find(|(path, _)| {
+            let file = path.rsplit('/').next().unwrap_or_default();
+            matches!(file, "rust-toolchain.toml" | "rust-toolchain")
+        })
+        .map(|(path, _)| path.clone())
+}
+
+/// A `[package]` field of a crate's manifest, inherited from its workspace if need be
+fn package_field(dir: &std::path::Path, field: &str) -> anyhow::Result<String> {
+    let read = |path: &std::path::Path| -> Option<toml::Value> {
+        std::fs::read_to_string(path).ok()?.parse().ok()
+    };
+    let manifest = read(&dir.join("Cargo.toml"))
+        .ok_or_else(|| anyhow::anyhow!("No Cargo.toml found in {}", dir.display()))?;
+    let value = manifest
+        .get("package")
+        .and_then(|package| package.get(field));
+    if let Some(value) = value.and_then(|v| v.as_str()) {
+        return Ok(value.to_string());
+    }
+    // `field.workspace = true`
+    if value.is_some() {
+        for ancestor in dir.canonicalize()?.ancestors().skip(1) {
+            let inherited = read(&ancestor.join("Cargo.toml")).and_then(|workspace| {
+                let value = workspace.get("workspace")?.get("package")?.get(field)?;
+                value.as_str().map(str::to_string)
+            });
+            if let Some(inherited) = inherited {
+                return Ok(inherited);
+            }
+        }
+    }
+    anyhow::bail!(
+        "No package {} in {}",
+        field,
+        dir.join("Cargo.toml").display()
+    )
+}
+
+/// A file to archive: its path in the archive and its content
+type Entry = (String, Vec<u8>);
+
+/// Files a contract builds from, by their path in the archive: its manifest, the lock
+/// file and toolchain file (from the crate or the workspace above it), cargo's
+/// configuration, its build script and `src/`, and the same for each of its path
+/// dependencies, which have to lie inside the crate or its workspace.
+///
+/// A workspace member keeps its place in the workspace, returned as the member's
+/// archive path, under a virtual manifest that lists only it but keeps the
+/// workspace's inherited package fields and dependencies, profiles and patches, so
+/// the archive builds on its own.
+fn source_entries(dir: &std::path::Path) -> anyhow::Result<(Vec<Entry>, Option<String>)> {
+    let dir = dir
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", dir.display(), e))?;
+    let root = workspace_root(&dir);
+    let base = root.as_deref().unwrap_or(&dir);
+    let relative = |path: &std::path::Path| {
+        path.strip_prefix(base).ok().map(|path| {
+            path.components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+    };
+    let member = root.as_ref().and_then(|_| relative(&dir));
+    let in_member = |path: &str| match &member {
+        Some(member) => format!("{}/{}", member, path),
+        None => path.to_string(),
+    };
+
+    let mut entries = Vec::new();
+    if let Some(root) = &root {
+        entries.push((
+            "Cargo.toml".to_string(),
+            workspace_manifest(root, member.as_deref().unwrap_or_default())?,
+        ));
+    }
+
+    // The crate, then the path dependencies it builds with, at their place below `base`
+    let mut pending = vec![dir.clone()];
+    if let Some(root) = &root {
+        pending.extend(patch_paths(&read_manifest(root)?, root));
+    }
+    let mut archived: Vec<PathBuf> = Vec::new();
+    while let Some(crate_dir) = pending.pop() {
+        let crate_dir = crate_dir.canonicalize().map_err(|e| {
+            anyhow::anyhow!("Cannot read path dependency {}: {}", crate_dir.display(), e)
+        })?;
+        if archived.contains(&crate_dir) {
+            continue;
+        }
+        let prefix = relative(&crate_dir).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Path dependency {} is outside {}, so the source archive cannot include it; \
+                 move it into the workspace or depend on it through git or a registry",
+                crate_dir.display(),
+                base.display()
+            )
+        })?;
+        crate_entries(&crate_dir, &prefix, &mut entries)?;
+        pending.extend(path_dependencies(&crate_dir, root.as_deref())?);
+        archived.push(crate_dir);
+    }
+
+    let nearest = |file: &str| {
+        dir.ancestors()
+            .map(|ancestor| ancestor.join(file))
+            .find(|path| path.is_file())
+    };
+    let lock = match &root {
+        Some(root) => Some(root.join("Cargo.lock")).filter(|lock| lock.is_file()),
+        None => nearest("Cargo.lock"),
+    };
+    let lock = lock.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No Cargo.lock found for {}; build the contract first so that its dependencies are locked",
+            dir.display()
+        )
+    })?;
+    entries.push(("Cargo.lock".to_string(), std::fs::read(lock)?));
+    for toolchain in ["rust-toolchain.toml", "rust-toolchain"] {
+        if let Some(path) = nearest(toolchain) {
+            let archived = relative(&path).unwrap_or_else(|| toolchain.to_string());
+            entries.push((archived, std::fs::read(path)?));
+            break;
+        }
+    }
+    let mut configured = vec![(dir.clone(), in_member(""))];
+    if let Some(root) = &root {
+        configured.push((root.clone(), String::new()));
+    }
+    for (config_dir, archived) in configured {
+        for file in [".cargo/config.toml", ".cargo/config"] {
+            let path = config_dir.join(file);
+            if path.is_file() {
+                entries.push((format!("{}{}", archived, file), std::fs::read(path)?));
+            }
+        }
+    }
+    Ok((entries, member))
+}
+
+/// A crate's manifest, build script and `src/`, archived below `prefix`
+fn crate_entries(
+    dir: &std::path::Path,
+    prefix: &str,
+    entries: &mut Vec<Entry>,
+) -> anyhow::Result<()> {
+    let archived = |path: &str| match prefix {
+        "" => path.to_string(),
+        prefix => format!("{}/{}", prefix, path),
+    };
+    entries.push((
+        archived("Cargo.toml"),
+        std::fs::read(dir.join("Cargo.toml"))?,
+    ));
+    let build_script = dir.join("build.rs");
+    if build_script.is_file() {
+        entries.push((archived("build.rs"), std::fs::read(build_script)?));
+    }
+
+    fn collect(
+        dir: &std::path::Path,
+        relative: &str,
+        entries: &mut Vec<Entry>,
+    ) -> anyhow::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let relative = format!("{}/{}", relative, name);
+            if path.is_dir() {
+                collect(&path, &relative, entries)?;
+            } else {
+                entries.push((relative, std::fs::read(&path)?));
+            }
+        }
+        Ok(())
+    }
+    let src = dir.join("src");
+    if !src.is_dir() {
+        anyhow::bail!("No src directory in {}", dir.display());
+    }
+    collect(&src, &archived("src"), entries)
+}
+
+fn read_manifest(dir: &std::path::Path) -> anyhow::Result<toml::Table> {
+    let path = dir.join("Cargo.toml");
+    std::fs::read_to_string(&path)?
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))
+}
+
+/// Directories of a crate's path dependencies, including those it inherits from the
+/// workspace at `root` and those its own `[patch]` tables point at
+fn path_dependencies(
+    dir: &std::path::Path,
+    root: Option<&std::path::Path>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let manifest = read_manifest(dir)?;
+    let workspace = root.map(read_manifest).transpose()?;
+    let inherited = |name: &str| {
+        let path = workspace
+            .as_ref()?
+            .get("workspace")?
+            .get("dependencies")?
+            .get(name)?
+            .get("path")?
+            .as_str()?;
+        Some(root?.join(path))
+    };
+
+    const SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+    let targets = manifest
+        .get("target")
+        .and_then(|targets| targets.as_table())
+        .into_iter()
+        .flat_map(|targets| targets.values());
+    let tables = std::iter::once(&manifest)
+        .chain(targets.filter_map(|target| target.as_table()))
+        .flat_map(|table| SECTIONS.iter().filter_map(|section| table.get(*section)))
+        .filter_map(|section| section.as_table());
+
+    let mut dirs = patch_paths(&manifest, dir);
+    for (name, dependency) in tables.flatten() {
+        if let Some(path) = dependency.get("path").and_then(|path| path.as_str()) {
+            dirs.push(dir.join(path));
+        } else if dependency.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+            dirs.extend(inherited(name));
+        }
+    }
+    Ok(dirs)
+}
+
+/// Directories the `[patch]` tables of a manifest in `dir` point at
+fn patch_paths(manifest: &toml::Table, dir: &std::path::Path) -> Vec<PathBuf> {
+    manifest
+        .get("patch")
+        .and_then(|patch| patch.as_table())
+        .into_iter()
+        .flat_map(|sources| sources.values())
+        .filter_map(|source| source.as_table())
+        .flat_map(|source| source.values())
+        .filter_map(|patched| patched.get("path")?.as_str())
+        .map(|path| dir.join(path))
+        .collect()
+}
+
+/// Root of the workspace a crate is a member of, unless the crate is its own
+fn workspace_root(dir: &std::path::Path) -> Option<PathBuf> {
+    let is_workspace = |dir: &std::path::Path| {
+        std::fs::read_to_string(dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|content| content.parse::<toml::Value>().ok())
+            .is_some_and(|manifest| manifest.get("workspace").is_some())
+    };
+    if is_workspace(dir) {
+        return None;
+    }
+    dir.ancestors()
+        .skip(1)
+        .find(|ancestor| is_workspace(ancestor))
+        .map(PathBuf::from)
+}
+
+/// Virtual manifest of a workspace with `member` as its only member
+fn workspace_manifest(root: &std::path::Path, member: &str) -> anyhow::Result<Vec<u8>> {
+    let manifest = read_manifest(root)?;
+
+    let mut workspace = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.as_table())
+        .cloned()
+        .unwrap_or_default();
+    workspace.remove("default-members");
+    workspace.remove("exclude");
+    workspace.insert(
+        "members".to_string(),
+        toml::Value::Array(vec![toml::Value::String(member.to_string())]),
+    );
+    let mut kept = toml::Table::new();
+    kept.insert("workspace".to_string(), toml::Value::Table(workspace));
+    for table in ["profile", "patch", "replace"] {
+        if let Some(value) = manifest.get(table) {
+            kept.insert(table.to_string(), value.clone());
+        }
+    }
+    Ok(toml::to_string(&kept)?.into_bytes())
+}
+
+/// Write entries to a gzipped tarball under `prefix/`, identical for identical entries:
+/// sorted, with fixed modes, owners and times
+fn write_archive(
+    entries: &[Entry],
+    prefix: &str,
+    output: &std::path::Path,
+) -> anyhow::Result<crate::config::artifacts::SourceArchive> {
+    let mut sorted: Vec<&Entry> = entries.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let gzip = flate2::GzBuilder::new()
+        .mtime(0)
+        .write(Vec::new(), flate2::Compression::best());
+    let mut tar = tar::Builder::new(gzip);
+    tar.mode(tar::HeaderMode::Deterministic);
+    let mut files = Vec::new();
+    for (path, data) in sorted {
+        let archived = format!("{}/{}", prefix, path);
+        let mut header = tar::Header::new_ustar();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(0);
+        header.set_uid(0);
+        header.set_gid(0);
+        tar.append_data(&mut header, &archived, data.as_slice())?;
+        files.push(archived);
+    }
+    let bytes = tar.into_inner()?.finish()?;
+
+    if let Some(dir) = output.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(output, &bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output.display(), e))?;
+    Ok(crate::config::artifacts::SourceArchive {
+        path: output.to_path_buf(),
+        sha256: format!("0x{}", hex::encode(sp_core_hashing::sha2_256(&bytes))),
+        files,
+    })
+}
+
+/// Progress of a verification on the explorer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Progress {
+    Pending,
+    Verified,
+    Failed,
+}
+
+/// A verification as the explorer reports it
+#[derive(Debug, Clone, PartialEq)]
+struct VerificationStatus {
+    progress: Progress,
+    /// Status as the explorer names it, e.g. `compiling`
+    state: String,
+    error: Option<String>,
+    compiler_log: Option<String>,
+}
+
+impl VerificationStatus {
+    /// From the JSON of `GET <explorer>/api/verify/<address or id>`
+    fn parse(json: &serde_json::Value) -> anyhow::Result<Self> {
+        let state = json["status"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Unexpected explorer response: {}", json))?
+            .to_lowercase();
+        let progress = match state.as_str() {
+            "success" | "verified" | "complete" | "completed" => Progress::Verified,
+            "failed" | "failure" | "error" | "mismatch" => Progress::Failed,
+            _ => Progress::Pending,
+        };
+        let text = |field: &str| json[field].as_str().map(str::to_string);
+        Ok(Self {
+            progress,
+            state,
+            error: text("error").or_else(|| text("message")),
+            compiler_log: text("compiler_log").or_else(|| text("log")),
+        })
+    }
+}
+
+/// Poll a verification until it succeeds, fails or `timeout` passes
+async fn status(
+    target: &str,
+    network: &str,
+    (interval, timeout): (Duration, Duration),
+    full_log: bool,
+) -> anyhow::Result<()> {
+    let network_config = crate::config::load_network(network)?;
+    let explorer = network_config
+        .explorer
+        .ok_or_else(|| anyhow::anyhow!("No explorer configured for network '{}'", network))?;
+    // Deployment names stand for their addresses; anything else is an address or an ID
+    let registry = crate::config::deployments::DeploymentRegistry::open_default()?;
+    let target = match registry.find(network, target) {
+        Some((_, deployment)) => deployment.address.clone(),
+        None => target.to_string(),
+    };
+    let url = format!("{}/api/verify/{}", explorer, target);
+
+    println!(
+        "{}",
+        format!("Checking verification: {}", target).cyan().bold()
+    );
+    println!("  {} {}", "Endpoint:".cyan(), url);
+    println!();
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let deadline = Instant::now() + timeout;
+    let mut last_state = None;
+    loop {
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Could not connect to explorer API: {}", e))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("No verification of {} found on {}", target, explorer);
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!("Explorer returned status {}: {}", status, error_text);
+        }
+        let status = VerificationStatus::parse(&response.json().await?)?;
+        if last_state.as_ref() != Some(&status.state) {
+            println!("  {} {}", "→".cyan(), status.state);
+            last_state = Some(status.state.clone());
+        }
+
+        match status.progress {
+            Progress::Verified => {
+                println!("\n{} Contract verified!", "✓".green().bold());
+                println!(
+                    "  {} {}/contract/{}#code",
+                    "View on Explorer:".cyan(),
+                    explorer,
+                    target
+                );
+                return Ok(());
+            }
+            Progress::Failed => {
+                println!("\n{} Verification failed", "✗".red().bold());
+                if let Some(error) = &status.error {
+                    println!("  {} {}", "Error:".cyan(), error);
+                }
+                if let Some(log) = &status.compiler_log {
+                    let lines = match full_log {
+                        true => log.lines().collect(),
+                        false => compiler_errors(log),
+                    };
+                    println!("\n{}", "Compiler log:".bold());
+                    for line in lines {
+                        println!("  {}", line);
+                    }
+                    if !full_log {
+                        println!("\n{}", "Run with --full-log for the whole log".dimmed());
+                    }
+                }
+                anyhow::bail!(
+                    "Verification failed: {}",
+                    status.error.as_deref().unwrap_or(&status.state)
+                );
+            }
+            Progress::Pending if Instant::now() + interval > deadline => {
+                anyhow::bail!(
+                    "Verification still {} after {}s; check again with glin-forge verify status {} --network {}",
+                    status.state,
+                    timeout.as_secs(),
+                    target,
+                    network
+                );
+            }
+            Progress::Pending => tokio::time::sleep(interval).await,
+        }
+    }
+}
+
+/// The error diagnostics of a compiler log, each from its `error` line to the next blank
+/// line; the end of the log when none is recognizable
+fn compiler_errors(log: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut in_error = false;
+    for line in log.lines() {
+        if line.trim_start().starts_with("error") {
+            in_error = true;
+        } else if line.trim().is_empty() {
+            in_error = false;
+        }
+        if in_error {
+            lines.push(line);
+        }
+    }
+    if lines.is_empty() {
+        let all: Vec<&str> = log.lines().collect();
+        lines = all[all.len().saturating_sub(LOG_TAIL)..].to_vec();
+    }
+    lines
+}
+
+/// Rebuild the contract and compare its code hash with the deployed contract's
+async fn verify_local(args: VerifyArgs) -> anyhow::Result<()> {
+    use sp_core_hashing::blake2_256;
+
+    let label = args
+        .address
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Contract address required"))?;
+    println!(
+        "{}",
+        format!("Verifying contract locally: {}", label)
+            .cyan()
+            .bold()
+    );
+    let (address, _) = crate::config::deployments::resolve(&args.network, label)?;
+    let account = crate::contract::parse_account_id(&address)?;
+    let source = args.source.clone().unwrap_or_else(|| PathBuf::from("."));
+    let network_config = crate::config::load_network(&args.network)?;
+
+    println!("\n{}", "Checking on-chain...".cyan());
+    let client = glin_client::create_client(&network_config.rpc).await?;
+    let deployed = deployed_code_hash(&client, &account)
+        .await?
+        .ok_or_else(|| {
+            anyhow::anyhow!("Contract {} does not exist on {}", address, args.network)
+        })?;
+    println!(
+        "  {} 0x{}",
+        "Deployed code hash:".cyan(),
+        hex::encode(deployed)
+    );
+
+    // The metadata of the deployed build pins the image it was built in
+    let metadata = args
+        .metadata
+        .clone()
+        .or_else(|| {
+            find_verification_files(&source.to_string_lossy())
+                .ok()
+                .map(|(_, m, _)| m)
+        })
+        .and_then(|path| std::fs::read_to_string(path).ok());
+    let image = match args.docker {
+        true => args
+            .image
+            .clone()
+            .or_else(|| metadata.as_deref().and_then(pinned_image)),
+        false => None,
+    };
+
+    println!("\n{}", "Rebuilding contract...".cyan());
+    println!("  {} {}", "Source:".cyan(), source.display());
+    let environment = match (args.docker, &image) {
+        (true, Some(image)) => format!("Docker image {}", image),
+        (true, None) => "cargo-contract's verifiable Docker image".to_string(),
+        (false, _) => "local toolchain".to_string(),
+    };
+    println!("  {} {}", "Build:".cyan(), environment);
+    let wasm_path = rebuild(&source, args.docker, image.as_deref())?;
+    let code_hash = blake2_256(&std::fs::read(&wasm_path)?);
+    println!("  {} {}", "WASM:".cyan(), wasm_path.display());
+    println!(
+        "  {} 0x{}",
+        "Rebuilt code hash:".cyan(),
+        hex::encode(code_hash)
+    );
+
+    if code_hash == deployed {
+        println!(
+            "\n{} Source matches the contract deployed at {}",
+            "✓".green().bold(),
+            address
+        );
+        return Ok(());
+    }
+
+    println!(
+        "\n{} Source does not match the deployed contract",
+        "✗".red().bold()
+    );
+    if let Some(build_info) = metadata.as_deref().and_then(build_info) {
+        println!("\n{}", "Built with (from its metadata):".bold());
+        for (label, value) in build_info {
+            println!("  {} {}", format!("{}:", label).cyan(), value);
+        }
+    }
+    if !args.docker {
+        println!(
+            "\n{}",
+            "Local toolchains rarely reproduce a build exactly; try --docker".dimmed()
+        );
+    }
+    anyhow::bail!(
+        "Code hash mismatch: rebuilt 0x{}, deployed 0x{}",
+        hex::encode(code_hash),
+        hex::encode(deployed)
+    )
+}
+
+/// Code hash of a contract from its `Contracts.ContractInfoOf`, None when it does not exist
+async fn deployed_code_hash(
+    client: &glin_client::GlinClient,
+    contract: &subxt::utils::AccountId32,
+) -> anyhow::Result<Option<[u8; 32]>> {
+    let query = subxt::dynamic::storage(
+        "Contracts",
+        "ContractInfoOf",
+        vec![subxt::dynamic::Value::from_bytes(contract.0)],
+    );
+    let Some(info) = client.storage().at_latest().await?.fetch(&query).await? else {
+        return Ok(None);
+    };
+    let info = serde_json::to_value(info.to_value()?)?;
+    let mut bytes = Vec::new();
+    crate::cli::watch::collect_bytes(&info["code_hash"], &mut bytes);
+    let code_hash = bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Unexpected contract info: {}", info))?;
+    Ok(Some(code_hash))
+}
+
+/// Build a contract in release mode, natively or in Docker, and return its WASM
+fn rebuild(source: &std::path::Path, docker: bool, image: Option<&str>) -> anyhow::Result<PathBuf> {
+    let mut command = std::process::Command::new("cargo");
+    command
+        .args(["contract", "build", "--output-json", "--manifest-path"])
+        .arg(source.join("Cargo.toml"));
+    match docker {
+        // Verifiable builds are release builds
+        true => {
+            command.arg("--verifiable");
+            if let Some(image) = image {
+                command.args(["--image", image]);
+            }
+        }
+        false => {
+            command.arg("--release");
+        }
+    }
+    let output = command
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "cargo-contract not found. Install it with: {}",
+                "cargo install cargo-contract --force".yellow()
+            )
+        })?;
+    if !output.status.success() {
+        anyhow::bail!("Build failed");
+    }
+    built_wasm(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// WASM a `cargo contract build --output-json` produced
+fn built_wasm(output: &str) -> anyhow::Result<PathBuf> {
+    let result: serde_json::Value = serde_json::from_str(output.trim())
+        .map_err(|e| anyhow::anyhow!("Unexpected cargo-contract output: {}", e))?;
+    ["dest_wasm", "dest_binary"]
+        .iter()
+        .find_map(|field| result[field].as_str())
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow::anyhow!("cargo-contract did not report the built WASM"))
+}
+
+/// Docker image a verifiable build was made in, as recorded in its metadata
+fn pinned_image(metadata_json: &str) -> Option<String> {
+    let metadata: serde_json::Value = serde_json::from_str(metadata_json).ok()?;
+    metadata["image"].as_str().map(str::to_string)
+}
+
+/// Toolchain versions recorded in metadata, to compare a mismatching rebuild against
+fn build_info(metadata_json: &str) -> Option<Vec<(&'static str, String)>> {
+    let metadata: serde_json::Value = serde_json::from_str(metadata_json).ok()?;
+    let build_info = metadata["source"].get("build_info")?;
+    let info = [
+        ("Rust toolchain", &build_info["rust_toolchain"]),
+        ("cargo-contract", &build_info["cargo_contract_version"]),
+        ("Build mode", &build_info["build_mode"]),
+        ("Image", &metadata["image"]),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| value.as_str().map(|value| (label, value.to_string())))
+    .collect();
+    Some(info)
+}
+
 fn find_verification_files(path: &str) -> anyhow::Result<(PathBuf, PathBuf, PathBuf)> {
     let target_dir = PathBuf::from(path).join("target/ink");
 
@@ -222,3 +1359,332 @@ fn find_verification_files(path: &str) -> anyhow::Result<(PathBuf, PathBuf, Path
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_status() {
+        let pending = serde_json::json!({ "verification_id": "v-42", "status": "Compiling" });
+        let status = VerificationStatus::parse(&pending).unwrap();
+        assert_eq!(status.progress, Progress::Pending);
+        assert_eq!(status.state, "compiling");
+
+        let verified = serde_json::json!({ "status": "verified" });
+        assert_eq!(
+            VerificationStatus::parse(&verified).unwrap().progress,
+            Progress::Verified
+        );
+
+        let failed = serde_json::json!({
+            "status": "failed",
+            "message": "Code hash mismatch",
+            "log": "Compiling flipper v0.1.0",
+        });
+        let status = VerificationStatus::parse(&failed).unwrap();
+        assert_eq!(status.progress, Progress::Failed);
+        assert_eq!(status.error.as_deref(), Some("Code hash mismatch"));
+        assert_eq!(
+            status.compiler_log.as_deref(),
+            Some("Compiling flipper v0.1.0")
+        );
+
+        assert!(VerificationStatus::parse(&serde_json::json!({ "id": 1 })).is_err());
+    }
+
+    #[test]
+    fn test_compiler_errors() {
+        let log = "   Compiling flipper v0.1.0 (/build)\n\
+                   error[E0425]: cannot find value `valu` in this scope\n\
+                   \x20 --> lib.rs:12:9\n\
+                   \n\
+                   warning: unused import\n\
+                   \n\
+                   error: could not compile `flipper`\n";
+        assert_eq!(
+            compiler_errors(log),
+            vec![
+                "error[E0425]: cannot find value `valu` in this scope",
+                "  --> lib.rs:12:9",
+                "error: could not compile `flipper`",
+            ]
+        );
+
+        let log: String = (1..=30).map(|i| format!("line {}\n", i)).collect();
+        let tail = compiler_errors(&log);
+        assert_eq!(tail.len(), LOG_TAIL);
+        assert_eq!(tail[0], "line 11");
+    }
+
+    #[test]
+    fn test_verify_all_helpers() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"contracts/*\"]\n",
+        )
+        .unwrap();
+        for name in ["my-token", "flipper"] {
+            std::fs::create_dir_all(root.join("contracts").join(name)).unwrap();
+            std::fs::write(
+                root.join("contracts").join(name).join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+            )
+            .unwrap();
+        }
+        let crates = workspace_crates(root);
+        let names: Vec<&str> = crates.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["flipper", "my-token"]);
+        assert!(same_crate("my-token", "my_token"));
+        assert!(!same_crate("my-token", "token"));
+
+        // Code next to the metadata, or in the bundle
+        let metadata = root.join("flipper.json");
+        std::fs::write(&metadata, "{}").unwrap();
+        assert!(artifact_wasm(&metadata, "{}").is_err());
+        std::fs::write(
+            root.join("flipper.contract"),
+            r#"{ "source": { "wasm": "0x0061736d" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            artifact_wasm(&metadata, "{}").unwrap(),
+            vec![0, 0x61, 0x73, 0x6d]
+        );
+        std::fs::write(root.join("flipper.wasm"), [1, 2]).unwrap();
+        assert_eq!(artifact_wasm(&metadata, "{}").unwrap(), vec![1, 2]);
+
+        assert!(Outcome::Mismatch.failed());
+        assert!(!Outcome::Skipped("no artifact".to_string()).failed());
+    }
+
+    #[test]
+    fn test_source_archive_outside_path_dependency() {
+        let dir = tempfile::tempdir().unwrap();
+        let crate_dir = dir.path().join("flipper");
+        std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("shared/src")).unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\nversion = \"0.1.0\"\n\n\
+             [dependencies]\nshared = { path = \"../shared\" }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("shared/Cargo.toml"),
+            "[package]\nname = \"shared\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(crate_dir.join("Cargo.lock"), "version = 3\n").unwrap();
+        std::fs::write(crate_dir.join("src/lib.rs"), "").unwrap();
+
+        let error = source_entries(&crate_dir).unwrap_err().to_string();
+        assert!(error.contains("shared is outside"), "{}", error);
+    }
+
+    #[test]
+    fn test_source_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"root\"\n\n[workspace]\nmembers = [\"flipper\", \"other\"]\nexclude = [\"old\"]\n\n\
+             [workspace.package]\nversion = \"1.2.0\"\n\n\
+             [workspace.dependencies]\nink = { version = \"5\", default-features = false }\n\
+             shared = { path = \"shared\" }\n\n\
+             [profile.release]\nlto = true\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("Cargo.lock"), "version = 3\n").unwrap();
+        let shared = root.join("shared");
+        std::fs::create_dir_all(shared.join("src")).unwrap();
+        std::fs::write(
+            shared.join("Cargo.toml"),
+            "[package]\nname = \"shared\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(shared.join("src/lib.rs"), "").unwrap();
+        let crate_dir = root.join("flipper");
+        std::fs::create_dir_all(crate_dir.join("src/nested")).unwrap();
+        std::fs::create_dir_all(crate_dir.join("target/ink")).unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\nversion.workspace = true\n\n\
+             [dependencies]\nink = { workspace = true }\nshared = { workspace = true }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            crate_dir.join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.81\"\n",
+        )
+        .unwrap();
+        std::fs::write(crate_dir.join("src/lib.rs"), "mod nested;\n").unwrap();
+        std::fs::write(crate_dir.join("src/nested/mod.rs"), "").unwrap();
+        std::fs::write(crate_dir.join("target/ink/flipper.wasm"), [0]).unwrap();
+
+        assert_eq!(package_field(&crate_dir, "name").unwrap(), "flipper");
+        assert_eq!(package_field(&crate_dir, "version").unwrap(), "1.2.0");
+        assert!(package_field(&crate_dir, "license").is_err());
+
+        let (entries, member) = source_entries(&crate_dir).unwrap();
+        assert_eq!(member.as_deref(), Some("flipper"));
+        let mut paths: Vec<&str> = entries.iter().map(|(path, _)| path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "Cargo.lock",
+                "Cargo.toml",
+                "flipper/Cargo.toml",
+                "flipper/rust-toolchain.toml",
+                "flipper/src/lib.rs",
+                "flipper/src/nested/mod.rs",
+                "shared/Cargo.toml",
+                "shared/src/lib.rs",
+            ]
+        );
+        assert_eq!(
+            toolchain_file(&entries).as_deref(),
+            Some("flipper/rust-toolchain.toml")
+        );
+
+        // The workspace manifest lists only the member and keeps what it inherits
+        let workspace = &entries
+            .iter()
+            .find(|(path, _)| path == "Cargo.toml")
+            .unwrap()
+            .1;
+        let workspace: toml::Table = String::from_utf8_lossy(workspace).parse().unwrap();
+        assert_eq!(
+            workspace["workspace"]["members"].as_array().unwrap(),
+            &vec![toml::Value::from("flipper")]
+        );
+        assert!(workspace["workspace"].get("exclude").is_none());
+        assert!(workspace.get("package").is_none());
+        assert_eq!(
+            workspace["workspace"]["package"]["version"].as_str(),
+            Some("1.2.0")
+        );
+        assert_eq!(workspace["profile"]["release"]["lto"].as_bool(), Some(true));
+
+        // and cargo loads it on its own
+        let unpacked = root.join("unpacked");
+        for (path, data) in &entries {
+            let path = unpacked.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, data).unwrap();
+        }
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let metadata = std::process::Command::new(cargo)
+            .args([
+                "metadata",
+                "--no-deps",
+                "--offline",
+                "--format-version",
+                "1",
+            ])
+            .arg("--manifest-path")
+            .arg(unpacked.join("flipper/Cargo.toml"))
+            .output()
+            .unwrap();
+        assert!(
+            metadata.status.success(),
+            "{}",
+            String::from_utf8_lossy(&metadata.stderr)
+        );
+        let metadata: serde_json::Value = serde_json::from_slice(&metadata.stdout).unwrap();
+        assert_eq!(metadata["packages"][0]["version"], "1.2.0");
+        assert_eq!(metadata["packages"][0]["dependencies"][0]["req"], "^5");
+
+        // The same sources make the same archive, whenever and in whatever order
+        let first = write_archive(&entries, "flipper-1.2.0", &root.join("a.tar.gz")).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut reversed = entries.clone();
+        reversed.reverse();
+        let second = write_archive(&reversed, "flipper-1.2.0", &root.join("b.tar.gz")).unwrap();
+        assert_eq!(first.sha256, second.sha256);
+        assert_eq!(first.files[0], "flipper-1.2.0/Cargo.lock");
+        assert_eq!(
+            std::fs::read(root.join("a.tar.gz")).unwrap(),
+            std::fs::read(root.join("b.tar.gz")).unwrap()
+        );
+
+        let archive = std::fs::File::open(root.join("a.tar.gz")).unwrap();
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+        let archived: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .collect();
+        assert_eq!(archived, first.files);
+
+        std::fs::remove_file(root.join("Cargo.lock")).unwrap();
+        assert!(source_entries(&crate_dir).is_err());
+
+        // A crate outside any workspace is archived as it is
+        std::fs::remove_file(root.join("Cargo.toml")).unwrap();
+        std::fs::write(root.join("Cargo.lock"), "version = 3\n").unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"flipper\"\nversion = \"1.2.0\"\n",
+        )
+        .unwrap();
+        let (entries, member) = source_entries(&crate_dir).unwrap();
+        assert_eq!(member, None);
+        let mut paths: Vec<&str> = entries.iter().map(|(path, _)| path.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "Cargo.lock",
+                "Cargo.toml",
+                "rust-toolchain.toml",
+                "src/lib.rs",
+                "src/nested/mod.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_built_wasm() {
+        let output = r#"{
+            "dest_wasm": "/project/target/ink/flipper.wasm",
+            "metadata_result": { "dest_metadata": "/project/target/ink/flipper.json" },
+            "build_mode": "Release"
+        }"#;
+        assert_eq!(
+            built_wasm(output).unwrap(),
+            PathBuf::from("/project/target/ink/flipper.wasm")
+        );
+        assert!(built_wasm(r#"{ "build_mode": "Release" }"#).is_err());
+        assert!(built_wasm("ERROR: no manifest").is_err());
+    }
+
+    #[test]
+    fn test_build_metadata() {
+        let metadata = r#"{
+            "source": {
+                "hash": "0x01",
+                "build_info": {
+                    "rust_toolchain": "stable-x86_64-unknown-linux-gnu",
+                    "cargo_contract_version": "4.1.1",
+                    "build_mode": "Release"
+                }
+            },
+            "image": "useink/contracts-verifiable:4.1.1"
+        }"#;
+        assert_eq!(
+            pinned_image(metadata).as_deref(),
+            Some("useink/contracts-verifiable:4.1.1")
+        );
+        let info = build_info(metadata).unwrap();
+        assert_eq!(info[1], ("cargo-contract", "4.1.1".to_string()));
+        assert_eq!(info.len(), 4);
+
+        let unpinned = r#"{ "source": { "hash": "0x01" }, "image": null }"#;
+        assert_eq!(pinned_image(unpinned), None);
+        assert_eq!(build_info(unpinned), None);
+    }
+}
@@ -1,14 +1,66 @@
+use anyhow::Context;
 use clap::Parser;
 use colored::Colorize;
+use futures::StreamExt;
+use globset::{Glob, GlobMatcher};
+use regex::Regex;
+use scale::{Compact, Decode, Encode};
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::blocks::{Block, ExtrinsicDetails, Extrinsics};
+use subxt::events::Phase;
+use subxt::storage::Storage;
+use subxt::utils::{AccountId32, MultiAddress};
+
+use glin_client::{GlinClient, GlinConfig};
+
+use crate::cli::balance::format_balance;
+use crate::config::file::{WatchContractConfig, WebhookConfig};
+use crate::contract::events::{DecodedEvent, EventDecoder};
+use crate::indexer::checkpoint::{
+    common_ancestor, is_reorg, Checkpoint, CheckpointFile, RecentBlocks, CHECKPOINT_FILE,
+};
+
+/// Status line: stdout, or stderr under --json whose stdout carries only records
+macro_rules! status {
+    ($json:expr, $($arg:tt)*) => {
+        if $json {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// Pallets whose events --system matches by participant
+const SYSTEM_PALLETS: [&str; 2] = ["Balances", "System"];
+
+/// Share of --deposit-limit from which the storage deposit is flagged, in percent
+const DEPOSIT_WARNING_PERCENT: u128 = 80;
+
+/// Longest wait between reconnection attempts of --follow
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Metadata of the workspace contract, used when --metadata is omitted
+const DEFAULT_METADATA: &str = "target/ink/metadata.json";
 
 #[derive(Parser)]
 pub struct WatchArgs {
-    /// Contract address to watch
-    pub address: String,
+    /// Contract address to watch, or the name of a contract in the deployments registry
+    #[arg(required_unless_present = "config")]
+    pub address: Option<String>,
 
-    /// Event name to filter (optional, shows all if not specified)
+    /// Events to show: names separated by `|`, each possibly a glob (`Trans*`), or a `/regex/`
     pub event: Option<String>,
 
+    /// Hide events matching this pattern, in the syntax of the event filter (repeatable)
+    #[arg(long)]
+    pub exclude: Vec<String>,
+
     /// Network to connect to
     #[arg(short, long, default_value = "testnet")]
     pub network: String,
@@ -17,6 +69,26 @@ pub struct WatchArgs {
     #[arg(short, long)]
     pub follow: bool,
 
+    /// Also track the contract account's balance and storage deposit, showing every change
+    #[arg(long)]
+    pub deposits: bool,
+
+    /// With --deposits, warn when the storage deposit nears this many GLIN
+    #[arg(long, requires = "deposits")]
+    pub deposit_limit: Option<String>,
+
+    /// Also show Balances and System events involving the contract account (transfers, reserves)
+    #[arg(long)]
+    pub system: bool,
+
+    /// Print one JSON record per line per event, without colors or banners
+    #[arg(long)]
+    pub json: bool,
+
+    /// Also show the `Contracts::call` extrinsics sent to the contract, decoded with its metadata
+    #[arg(long)]
+    pub calls: bool,
+
     /// Maximum number of events to show
     #[arg(long, default_value = "10")]
     pub limit: usize,
@@ -24,166 +96,1890 @@ pub struct WatchArgs {
     /// Show events from block number
     #[arg(long)]
     pub from_block: Option<u64>,
+
+    /// Blocks fetched in parallel when scanning history
+    #[arg(long, default_value = "16")]
+    pub concurrency: usize,
+
+    /// Continue after the last block processed for this contract and network
+    #[arg(long, conflicts_with = "from_block")]
+    pub resume: bool,
+
+    /// Scan from the block the contract was instantiated in, per the deployments registry or the chain
+    #[arg(long, conflicts_with_all = ["from_block", "resume"])]
+    pub from_deployment: bool,
+
+    /// With --follow, watch best blocks instead of finalized ones, detecting chain reorganizations
+    #[arg(long, requires = "follow")]
+    pub unfinalized: bool,
+
+    /// Append every displayed event as a record to this file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Format of --output [default: from the file extension, ndjson otherwise]
+    #[arg(long, value_parser = ["ndjson", "csv", "json"], requires = "output")]
+    pub format: Option<String>,
+
+    /// POST every displayed event as JSON to this URL (repeatable; adds to config `watch.webhooks`)
+    #[arg(long = "webhook")]
+    pub webhooks: Vec<String>,
+
+    /// Contract metadata used to decode emitted events [default: target/ink/metadata.json when present]
+    #[arg(short, long, conflicts_with = "config")]
+    pub metadata: Option<String>,
+
+    /// Watch the contracts of the config `watch.contracts` section [default file: glinforge.config.ts]
+    #[arg(long, num_args = 0..=1, require_equals = true, conflicts_with = "address")]
+    pub config: Option<Option<PathBuf>>,
 }
 
 pub async fn execute(args: WatchArgs) -> anyhow::Result<()> {
-    println!(
-        "{}",
-        format!("Watching contract events: {}", args.address)
-            .cyan()
-            .bold()
-    );
+    let file_config = match &args.config {
+        Some(path) => Some(crate::config::file::load_config_file(path.as_deref())?),
+        // Only for its webhooks: a config that was not asked for does not stop the watch
+        None if crate::config::file::find_config_file().is_ok() => {
+            match crate::config::file::load_config_file(None) {
+                Ok(config) => Some(config),
+                Err(e) => {
+                    status!(
+                        args.json,
+                        "{} Ignoring the config file, which failed to load: {:#}",
+                        "⚠".yellow(),
+                        e
+                    );
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    let contracts = match &args.config {
+        Some(_) => {
+            let configured = file_config
+                .as_ref()
+                .map(|config| config.watch.contracts.clone())
+                .unwrap_or_default();
+            if configured.is_empty() {
+                anyhow::bail!("No contracts in the `watch.contracts` section of the config file");
+            }
+            configured
+                .iter()
+                .map(WatchedContract::from_config)
+                .collect::<anyhow::Result<Vec<_>>>()?
+        }
+        None => {
+            let label = args.address.clone().context("Contract address required")?;
+            let (address, _) = crate::config::deployments::resolve(&args.network, &label)?;
+            let metadata_path = args.metadata.clone().or_else(|| {
+                Path::new(DEFAULT_METADATA)
+                    .exists()
+                    .then(|| DEFAULT_METADATA.to_string())
+            });
+            vec![WatchedContract {
+                label,
+                account: crate::contract::parse_account_id(&address).ok(),
+                address,
+                decoder: metadata_path
+                    .as_deref()
+                    .map(EventDecoder::load)
+                    .transpose()?,
+                metadata: metadata_path,
+                events: Vec::new(),
+                resume_after: None,
+            }]
+        }
+    };
+    let multiplexed = args.config.is_some();
 
     let network_config = crate::config::load_network(&args.network)?;
+    let include = args.event.as_deref().map(EventPattern::parse).transpose()?;
+    let exclude = args
+        .exclude
+        .iter()
+        .map(|pattern| EventPattern::parse(pattern))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let export = args
+        .output
+        .as_deref()
+        .map(|path| EventExport::open(path, args.format.as_deref()))
+        .transpose()?;
+
+    let mut webhooks: Vec<WebhookConfig> = args
+        .webhooks
+        .iter()
+        .map(|url| WebhookConfig {
+            url: url.clone(),
+            events: Vec::new(),
+            headers: Default::default(),
+            retries: crate::config::file::default_webhook_retries(),
+        })
+        .collect();
+    if let Some(config) = &file_config {
+        webhooks.extend(config.watch.webhooks.iter().cloned());
+    }
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+    let deposit_limit = args
+        .deposit_limit
+        .as_deref()
+        .map(crate::cli::monitor::parse_glin)
+        .transpose()?;
+
+    // --json keeps stdout to one record per line
+    let json = args.json;
+    if json {
+        colored::control::set_override(false);
+    } else {
+        print_configuration(
+            &args,
+            &contracts,
+            include.as_ref(),
+            &exclude,
+            export.as_ref(),
+            &webhooks,
+        );
+        println!("\n{}", "Connecting to network...".cyan());
+    }
+
+    // Connect to network
+    let client = glin_client::create_client(&network_config.rpc).await?;
+    if !json {
+        println!("{} Connected to {}", "✓".green(), network_config.rpc);
+        println!("\n{}", "Watching for events...".cyan());
+        println!("{}", "Press Ctrl+C to stop\n".dimmed());
+    }
+
+    let rpc = glin_client::create_rpc_client(&network_config.rpc).await?;
+    let mut checkpoints = CheckpointFile::open(Path::new(CHECKPOINT_FILE))?;
+    let mut contracts = contracts;
+    let resume_from = if args.resume {
+        for contract in &mut contracts {
+            let checkpoint = checkpoints.get(&args.network, &contract.address);
+            match checkpoint {
+                Some(checkpoint) => status!(
+                    json,
+                    "{} Resuming {} after block #{}",
+                    "✓".green(),
+                    contract.label,
+                    checkpoint.block
+                ),
+                None => status!(
+                    json,
+                    "{} No checkpoint for {} yet, starting fresh",
+                    "⚠".yellow(),
+                    contract.label
+                ),
+            }
+            contract.resume_after = checkpoint.map(|c| c.block);
+        }
+        // Contracts checkpointed further along skip the blocks they already processed
+        contracts
+            .iter()
+            .map(|c| c.resume_after)
+            .min()
+            .flatten()
+            .map(|block| block + 1)
+    } else if args.from_deployment {
+        let mut first = None;
+        for contract in &contracts {
+            let account = contract
+                .account
+                .as_ref()
+                .with_context(|| format!("Invalid contract address: {}", contract.address))?;
+            let block = crate::config::deployments::instantiation_block(
+                &client,
+                &rpc,
+                &args.network,
+                account,
+            )
+            .await?;
+            status!(
+                json,
+                "{} {} instantiated at block #{}",
+                "✓".green(),
+                contract.label,
+                block
+            );
+            first = Some(first.map_or(block, |first: u64| first.min(block)));
+        }
+        first
+    } else {
+        None
+    };
+
+    let mut sink = EventSink {
+        args: &args,
+        contracts,
+        multiplexed,
+        include,
+        exclude,
+        export,
+        webhooks,
+        http,
+        progress: indicatif::ProgressBar::hidden(),
+        count: 0,
+        deposit_limit,
+        deposits: HashMap::new(),
+    };
+    let deposit_accounts = sink.deposit_accounts();
+
+    if args.follow {
+        let mut recent = RecentBlocks::default();
+        let mut next = resume_from;
+        let (mut client, mut rpc) = (client, rpc);
+        let mut attempt = 0;
+
+        // A dropped connection is re-established; the first block after it backfills the gap
+        loop {
+            let result = follow_blocks(
+                &client,
+                &rpc,
+                &mut sink,
+                &mut checkpoints,
+                &mut recent,
+                &mut next,
+            )
+            .await;
+            let mut reason = match result {
+                Ok(false) => {
+                    sink.limit_reached();
+                    return Ok(());
+                }
+                Ok(true) => "subscription ended".to_string(),
+                Err(e) if is_connection_error(&e) => format!("{:#}", e),
+                Err(e) => return Err(e),
+            };
+
+            loop {
+                let delay = reconnect_delay(attempt);
+                attempt += 1;
+                status!(
+                    json,
+                    "{} Connection lost: {}; reconnecting in {}s...",
+                    "⚠".yellow(),
+                    reason,
+                    delay.as_secs()
+                );
+                tokio::time::sleep(delay).await;
+                match reconnect(&network_config.rpc).await {
+                    Ok(connection) => {
+                        (client, rpc) = connection;
+                        break;
+                    }
+                    Err(e) if is_connection_error(&e) => reason = format!("{:#}", e),
+                    Err(e) => return Err(e),
+                }
+            }
+            attempt = 0;
+            match next {
+                Some(block) => status!(
+                    json,
+                    "{} Reconnected to {}, backfilling from block #{}\n",
+                    "✓".green(),
+                    network_config.rpc,
+                    block
+                ),
+                None => status!(
+                    json,
+                    "{} Reconnected to {}\n",
+                    "✓".green(),
+                    network_config.rpc
+                ),
+            }
+        }
+    } else {
+        // Get historical events from a range of blocks
+        let latest_block = client.blocks().at_latest().await?;
+        let latest_number = latest_block.number() as u64;
+
+        let start_block = resume_from
+            .or(args.from_block)
+            .unwrap_or_else(|| latest_number.saturating_sub(100));
+
+        let total = (latest_number + 1).saturating_sub(start_block);
+        let progress = if json {
+            indicatif::ProgressBar::hidden()
+        } else {
+            indicatif::ProgressBar::new(total)
+        };
+        progress.set_style(
+            indicatif::ProgressStyle::with_template(
+                "{bar:40.cyan/blue} {pos}/{len} blocks ({per_sec}, eta {eta})",
+            )?
+            .progress_chars("=> "),
+        );
+        sink.progress = progress.clone();
+
+        // Fetch hashes and events concurrently, handling blocks in order
+        let (client, rpc, args, deposit_accounts) = (&client, &rpc, &args, &deposit_accounts);
+        let mut blocks = futures::stream::iter(start_block..=latest_number)
+            .map(|block_num| async move {
+                let Some(block_hash) = rpc.chain_get_block_hash(Some(block_num.into())).await?
+                else {
+                    return anyhow::Ok((block_num, None));
+                };
+                let block = client.blocks().at(block_hash).await?;
+                let extras = block_extras(&block, args, deposit_accounts).await?;
+                let events = block.events().await?;
+                anyhow::Ok((block_num, Some((block_hash, events, extras))))
+            })
+            .buffered(args.concurrency.max(1));
+
+        while let Some(fetched) = blocks.next().await {
+            if sink.count >= args.limit {
+                break;
+            }
+            let (block_num, fetched) = fetched?;
+            progress.inc(1);
+
+            if let Some((block_hash, events, extras)) = fetched {
+                if !sink.block(block_num, &events, &extras).await? {
+                    break;
+                }
+                sink.checkpoint(
+                    &mut checkpoints,
+                    Checkpoint {
+                        block: block_num,
+                        hash: format!("{:?}", block_hash),
+                    },
+                )?;
+            }
+        }
+        progress.finish_and_clear();
+        if json {
+            return Ok(());
+        }
+
+        let elapsed = progress.elapsed().as_secs_f64();
+        println!(
+            "{} Scanned {} blocks in {:.1}s ({:.0} blocks/s)",
+            "✓".green(),
+            progress.position(),
+            elapsed,
+            progress.position() as f64 / elapsed.max(f64::EPSILON)
+        );
+
+        println!("\n{} Displayed {} events", "✓".green().bold(), sink.count);
+        if let Some(path) = &args.output {
+            println!("  {} {}", "Written to:".cyan(), path.display());
+        }
+        if sink.count == 0 {
+            println!("{}", "No contract events found in recent blocks".dimmed());
+        }
+        println!(
+            "Use {} to keep watching for new events",
+            "--follow".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Banner describing what is watched, omitted by --json
+fn print_configuration(
+    args: &WatchArgs,
+    contracts: &[WatchedContract],
+    include: Option<&EventPattern>,
+    exclude: &[EventPattern],
+    export: Option<&EventExport>,
+    webhooks: &[WebhookConfig],
+) {
+    let multiplexed = args.config.is_some();
+    if multiplexed {
+        println!(
+            "{}",
+            format!("Watching events of {} contracts", contracts.len())
+                .cyan()
+                .bold()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("Watching contract events: {}", contracts[0].address)
+                .cyan()
+                .bold()
+        );
+    }
 
     println!("\n{}", "Configuration:".bold());
-    println!("  {} {}", "Contract:".cyan(), args.address);
+    if multiplexed {
+        for contract in contracts {
+            let events = if contract.events.is_empty() {
+                "all events".to_string()
+            } else {
+                contract
+                    .events
+                    .iter()
+                    .map(EventPattern::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            println!(
+                "  {} {} {} ({})",
+                "Contract:".cyan(),
+                contract.label.bold(),
+                contract.address,
+                events
+            );
+        }
+    } else {
+        println!("  {} {}", "Contract:".cyan(), contracts[0].address);
+    }
     println!("  {} {}", "Network:".cyan(), args.network);
 
-    if let Some(event) = &args.event {
+    if let Some(event) = include {
         println!("  {} {}", "Event filter:".cyan(), event);
     } else {
         println!("  {} All events", "Event filter:".cyan());
     }
+    if !exclude.is_empty() {
+        let excluded: Vec<String> = exclude.iter().map(EventPattern::to_string).collect();
+        println!("  {} {}", "Excluded:".cyan(), excluded.join(", "));
+    }
+
+    match (args.calls, args.system) {
+        (true, true) => println!(
+            "  {} Events, calls and account balance events",
+            "Showing:".cyan()
+        ),
+        (true, false) => println!("  {} Events and calls", "Showing:".cyan()),
+        (false, true) => println!("  {} Events and account balance events", "Showing:".cyan()),
+        (false, false) => {}
+    }
+
+    if args.deposits {
+        match &args.deposit_limit {
+            Some(limit) => println!(
+                "  {} Balance and storage deposit (limit {} GLIN)",
+                "Tracking:".cyan(),
+                limit
+            ),
+            None => println!("  {} Balance and storage deposit", "Tracking:".cyan()),
+        }
+    }
 
     if args.follow {
         println!("  {} Follow (live)", "Mode:".cyan());
     }
 
-    println!("\n{}", "Connecting to network...".cyan());
+    if !multiplexed {
+        if let Some(path) = &contracts[0].metadata {
+            println!("  {} {}", "Metadata:".cyan(), path);
+        }
+    }
 
-    // Connect to network
-    let client = glin_client::create_client(&network_config.rpc).await?;
-    println!("{} Connected to {}", "✓".green(), network_config.rpc);
+    if let (Some(path), Some(export)) = (&args.output, export) {
+        println!(
+            "  {} {} ({})",
+            "Output:".cyan(),
+            path.display(),
+            export.format.name()
+        );
+    }
+    for hook in webhooks {
+        println!("  {} {}", "Webhook:".cyan(), hook.url);
+    }
+}
 
-    println!("\n{}", "Watching for events...".cyan());
-    println!("{}", "Press Ctrl+C to stop\n".dimmed());
+/// Show the events of new blocks until the subscription ends (true) or --limit is reached (false)
+async fn follow_blocks(
+    client: &GlinClient,
+    rpc: &LegacyRpcMethods<GlinConfig>,
+    sink: &mut EventSink<'_>,
+    checkpoints: &mut CheckpointFile,
+    recent: &mut RecentBlocks,
+    next: &mut Option<u64>,
+) -> anyhow::Result<bool> {
+    let args = sink.args;
+    let deposit_accounts = sink.deposit_accounts();
+    // Subscribe to new blocks and watch for contract events
+    let mut blocks_sub = if args.unfinalized {
+        client.blocks().subscribe_best().await?
+    } else {
+        client.blocks().subscribe_finalized().await?
+    };
 
-    let mut event_count = 0;
+    while let Some(block_result) = blocks_sub.next().await {
+        let block = block_result?;
+        let block_number = block.number() as u64;
+        let hash = format!("{:?}", block.hash());
+        let parent = format!("{:?}", block.header().parent_hash);
 
-    if args.follow {
-        // Subscribe to finalized blocks and watch for contract events
-        let mut blocks_sub = client.blocks().subscribe_finalized().await?;
+        if is_reorg(block_number, &hash, &parent, |n| recent.get(n)) {
+            let ancestor = common_ancestor(rpc, &recent.newest_first()).await?;
+            recent.rollback(ancestor);
+            status!(
+                args.json,
+                "{} Chain reorganization: blocks after #{} were retracted, their events above no longer apply\n",
+                "⚠".yellow(),
+                ancestor
+            );
+            *next = Some(ancestor + 1);
+        } else if next.is_some_and(|n| block_number < n) {
+            // Already shown
+            continue;
+        }
 
-        while let Some(block_result) = blocks_sub.next().await {
-            let block = block_result?;
-            let block_number = block.number();
-            let events = block.events().await?;
+        // Catch up from the checkpoint or reorg ancestor, and on blocks the subscription skipped
+        if let Some(from) = *next {
+            for block_num in from..block_number {
+                let block_hash = rpc
+                    .chain_get_block_hash(Some(block_num.into()))
+                    .await?
+                    .with_context(|| format!("Block #{} not found", block_num))?;
+                let block = client.blocks().at(block_hash).await?;
+                let extras = block_extras(&block, args, &deposit_accounts).await?;
+                if !sink
+                    .block(block_num, &block.events().await?, &extras)
+                    .await?
+                {
+                    return Ok(false);
+                }
+                let checkpoint = Checkpoint {
+                    block: block_num,
+                    hash: format!("{:?}", block_hash),
+                };
+                recent.push(block_num, checkpoint.hash.clone());
+                sink.checkpoint(checkpoints, checkpoint)?;
+            }
+        }
 
-            for event in events.iter() {
-                let event = event?;
+        let extras = block_extras(&block, args, &deposit_accounts).await?;
+        if !sink
+            .block(block_number, &block.events().await?, &extras)
+            .await?
+        {
+            return Ok(false);
+        }
+        recent.push(block_number, hash.clone());
+        sink.checkpoint(
+            checkpoints,
+            Checkpoint {
+                block: block_number,
+                hash,
+            },
+        )?;
+        *next = Some(block_number + 1);
+    }
+    Ok(true)
+}
 
-                // Filter for Contracts pallet events
-                if event.pallet_name() == "Contracts" {
-                    let variant = event.variant_name();
+/// Whether an error comes from the node connection rather than from watch itself
+fn is_connection_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<subxt::Error>())
+}
 
-                    // Filter by event name if specified
-                    if let Some(filter) = &args.event {
-                        if variant != filter.as_str() {
-                            continue;
-                        }
-                    }
+/// Delay before reconnection attempt `attempt`: 1s doubling up to 30s
+fn reconnect_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.min(5)).min(MAX_RECONNECT_DELAY)
+}
 
-                    // Check if limit reached
-                    if event_count >= args.limit {
-                        println!(
-                            "\n{} Reached limit of {} events",
-                            "✓".green().bold(),
-                            args.limit
-                        );
-                        return Ok(());
-                    }
+async fn reconnect(url: &str) -> anyhow::Result<(GlinClient, LegacyRpcMethods<GlinConfig>)> {
+    let client = glin_client::create_client(url).await?;
+    let rpc = glin_client::create_rpc_client(url).await?;
+    Ok((client, rpc))
+}
 
-                    println!("{} Block #{}", "→".cyan(), block_number);
-                    println!(
-                        "  {} {}",
-                        variant.yellow().bold(),
-                        format_event_data(&event)?
-                    );
-                    println!();
+/// A contract whose events are shown
+pub(crate) struct WatchedContract {
+    /// Name of the contract in multi-contract streams
+    pub(crate) label: String,
+    pub(crate) address: String,
+    /// Emitter the events are attributed to; every contract event when the address is not SS58
+    pub(crate) account: Option<AccountId32>,
+    pub(crate) metadata: Option<String>,
+    pub(crate) decoder: Option<EventDecoder>,
+    /// Events shown; all when empty
+    pub(crate) events: Vec<EventPattern>,
+    /// Last block processed before a --resume, whose events were already shown
+    pub(crate) resume_after: Option<u64>,
+}
 
-                    event_count += 1;
-                }
+impl WatchedContract {
+    pub(crate) fn from_config(config: &WatchContractConfig) -> anyhow::Result<Self> {
+        let account = crate::contract::parse_account_id(&config.address)
+            .with_context(|| format!("Invalid address in watch.contracts: {}", config.address))?;
+        let decoder = config
+            .abi
+            .as_deref()
+            .map(|path| {
+                EventDecoder::load(path)
+                    .with_context(|| format!("Failed to load ABI of {}", config.address))
+            })
+            .transpose()?;
+        Ok(Self {
+            label: config
+                .name
+                .clone()
+                .unwrap_or_else(|| config.address.clone()),
+            address: config.address.clone(),
+            account: Some(account),
+            metadata: config.abi.clone(),
+            decoder,
+            events: config
+                .events
+                .iter()
+                .map(|pattern| EventPattern::parse(pattern))
+                .collect::<anyhow::Result<_>>()
+                .with_context(|| format!("Invalid event filter of {}", config.address))?,
+            resume_after: None,
+        })
+    }
+}
+
+/// Where the events of watched blocks go: the terminal, --output and webhooks
+struct EventSink<'a> {
+    args: &'a WatchArgs,
+    contracts: Vec<WatchedContract>,
+    /// Label events with their contract
+    multiplexed: bool,
+    /// Parsed event filter and --exclude patterns
+    include: Option<EventPattern>,
+    exclude: Vec<EventPattern>,
+    export: Option<EventExport>,
+    webhooks: Vec<WebhookConfig>,
+    http: reqwest::Client,
+    /// Historical scan progress, hidden while following
+    progress: indicatif::ProgressBar,
+    /// Events shown so far
+    count: usize,
+    /// Storage deposit from which --deposits warns, in smallest units
+    deposit_limit: Option<u128>,
+    /// Last seen balance and deposit per contract address
+    deposits: HashMap<String, Deposit>,
+}
+
+impl EventSink<'_> {
+    /// Show the matching events of a block; false when --limit stopped it midway
+    async fn block(
+        &mut self,
+        block_number: u64,
+        events: &subxt::events::Events<GlinConfig>,
+        extras: &BlockExtras,
+    ) -> anyhow::Result<bool> {
+        // Calls first, the events of the block being their outcome
+        for extrinsic in extras.extrinsics.iter().flat_map(|e| e.iter()) {
+            if !self
+                .call(block_number, extras.timestamp, &extrinsic, events)
+                .await?
+            {
+                return Ok(false);
             }
         }
-    } else {
-        // Get historical events from a range of blocks
-        let latest_block = client.blocks().at_latest().await?;
-        let latest_number = latest_block.number() as u64;
 
-        let start_block = args
-            .from_block
-            .unwrap_or_else(|| latest_number.saturating_sub(100));
+        for event in events.iter() {
+            let event = event?;
 
-        for block_num in start_block..=latest_number {
-            if event_count >= args.limit {
-                break;
+            // Contracts pallet events, plus with --system the account's balance events
+            let contract = match event.pallet_name() {
+                "Contracts" => {
+                    let emitter = event_contract(&event);
+                    self.contracts.iter().find(|c| match &c.account {
+                        Some(account) => emitter.as_ref() == Some(account),
+                        None => true,
+                    })
+                }
+                pallet if self.args.system && SYSTEM_PALLETS.contains(&pallet) => {
+                    let participants = field_accounts(&typed_fields(&event)?);
+                    self.contracts.iter().find(|c| {
+                        c.account
+                            .as_ref()
+                            .is_some_and(|account| participants.contains(account))
+                    })
+                }
+                _ => None,
+            };
+            let Some(contract) = contract else {
+                continue;
+            };
+            if contract
+                .resume_after
+                .is_some_and(|last| block_number <= last)
+            {
+                continue;
             }
+            let variant = event.variant_name();
+            let decoded =
+                decode_emitted(&event, contract.decoder.as_ref(), contract.account.as_ref());
 
-            // Get block hash for this number using RPC
-            let rpc = glin_client::create_rpc_client(&network_config.rpc).await?;
+            // Filter by event name if specified
+            if let Some(filter) = &self.include {
+                if !matches_filter(filter, variant, decoded.as_ref()) {
+                    continue;
+                }
+            }
+            if !contract.events.is_empty()
+                && !contract
+                    .events
+                    .iter()
+                    .any(|filter| matches_filter(filter, variant, decoded.as_ref()))
+            {
+                continue;
+            }
+            if self
+                .exclude
+                .iter()
+                .any(|filter| matches_filter(filter, variant, decoded.as_ref()))
+            {
+                continue;
+            }
 
-            let block_hash_opt: Option<subxt::utils::H256> =
-                rpc.chain_get_block_hash(Some(block_num.into())).await?;
+            if self.count >= self.args.limit {
+                return Ok(false);
+            }
 
-            if let Some(block_hash) = block_hash_opt {
-                let block = client.blocks().at(block_hash).await?;
-                let events = block.events().await?;
+            if !self.args.json {
+                let heading = self.heading(block_number, extras.timestamp, contract);
+                let body = format_event(&event, decoded.as_ref(), contract.decoder.as_ref())?;
+                // Keep the scan progress bar below the events
+                self.progress
+                    .suspend(|| println!("{}\n{}\n", heading, body));
+            }
 
-                for event in events.iter() {
-                    let event = event?;
+            if self.args.json || self.export.is_some() || !self.webhooks.is_empty() {
+                let record = event_record(block_number, &contract.label, &event, decoded.as_ref())?;
+                if self.args.json {
+                    println!("{}", record);
+                }
+                if let Some(export) = &mut self.export {
+                    export.write(&record)?;
+                }
+                notify(
+                    &self.http,
+                    &self.webhooks,
+                    &self.args.network,
+                    &contract.address,
+                    &record,
+                )
+                .await;
+            }
 
-                    if event.pallet_name() == "Contracts" {
-                        let variant = event.variant_name();
+            self.count += 1;
+        }
 
-                        if let Some(filter) = &args.event {
-                            if variant != filter.as_str() {
-                                continue;
-                            }
-                        }
+        self.deposits(block_number, extras.timestamp, &extras.deposits)?;
+        Ok(true)
+    }
 
-                        if event_count >= args.limit {
-                            break;
-                        }
+    /// Show a `Contracts::call` extrinsic sent to a watched contract; false when over --limit
+    async fn call(
+        &mut self,
+        block_number: u64,
+        timestamp: Option<u64>,
+        extrinsic: &ExtrinsicDetails<GlinConfig, GlinClient>,
+        events: &subxt::events::Events<GlinConfig>,
+    ) -> anyhow::Result<bool> {
+        if extrinsic.pallet_name()? != "Contracts" || extrinsic.variant_name()? != "call" {
+            return Ok(true);
+        }
+        let call = ContractCall::decode(&mut extrinsic.field_bytes())
+            .context("Malformed Contracts::call extrinsic")?;
+        let MultiAddress::Id(dest) = &call.dest else {
+            return Ok(true);
+        };
+        let Some(contract) = self.contracts.iter().find(|c| match &c.account {
+            Some(account) => account == dest,
+            None => true,
+        }) else {
+            return Ok(true);
+        };
+        if contract
+            .resume_after
+            .is_some_and(|last| block_number <= last)
+        {
+            return Ok(true);
+        }
+        if self.count >= self.args.limit {
+            return Ok(false);
+        }
 
-                        println!("{} Block #{}", "→".cyan(), block_num);
-                        println!(
-                            "  {} {}",
-                            variant.yellow().bold(),
-                            format_event_data(&event)?
-                        );
-                        println!();
+        let caller = extrinsic
+            .address_bytes()
+            .and_then(|mut bytes| MultiAddress::<AccountId32, ()>::decode(&mut bytes).ok())
+            .map(|address| match address {
+                MultiAddress::Id(account) => account.to_string(),
+                other => format!("{:?}", other),
+            });
+        let success = !events.iter().flatten().any(|event| {
+            event.phase() == Phase::ApplyExtrinsic(extrinsic.index())
+                && event.pallet_name() == "System"
+                && event.variant_name() == "ExtrinsicFailed"
+        });
+        let decoded = contract
+            .decoder
+            .as_ref()
+            .map(|decoder| decoder.decode_call(&call.data));
 
-                        event_count += 1;
-                    }
+        if !self.args.json {
+            let heading = self.heading(block_number, timestamp, contract);
+            let message = match &decoded {
+                Some(Ok(decoded)) => decoded.to_string(),
+                Some(Err(e)) => format!(
+                    "0x{} {}",
+                    hex::encode(&call.data),
+                    format!("(undecoded: {})", e).dimmed()
+                ),
+                None => format!("0x{}", hex::encode(&call.data)),
+            };
+            let mut details = format!(
+                "{} {}",
+                "Caller:".cyan(),
+                caller.as_deref().unwrap_or("unsigned")
+            );
+            if call.value.0 > 0 {
+                details.push_str(&format!("  {} {}", "Value:".cyan(), call.value.0));
+            }
+            if !success {
+                details.push_str(&format!("  {}", "✗ failed".red()));
+            }
+            self.progress.suspend(|| {
+                println!(
+                    "{}\n  {} {}\n  {}\n",
+                    heading,
+                    "call".magenta().bold(),
+                    message,
+                    details
+                )
+            });
+        }
+
+        if self.args.json || self.export.is_some() || !self.webhooks.is_empty() {
+            let args = match &decoded {
+                Some(Ok(decoded)) => decoded.to_json()["fields"].take(),
+                _ => json!(format!("0x{}", hex::encode(&call.data))),
+            };
+            let record = json!({
+                "block": block_number,
+                "contract": contract.label,
+                "pallet": "Contracts",
+                "variant": "call",
+                "event": decoded.as_ref().and_then(|d| d.as_ref().ok()).map(|d| d.name.clone()),
+                "data": {
+                    "caller": caller,
+                    "value": call.value.0.to_string(),
+                    "success": success,
+                    "args": args,
+                },
+            });
+            if self.args.json {
+                println!("{}", record);
+            }
+            if let Some(export) = &mut self.export {
+                export.write(&record)?;
+            }
+            notify(
+                &self.http,
+                &self.webhooks,
+                &self.args.network,
+                &contract.address,
+                &record,
+            )
+            .await;
+        }
+
+        self.count += 1;
+        Ok(true)
+    }
+
+    /// `→ Block #N  <time>  <contract>` line above each shown item
+    fn heading(
+        &self,
+        block_number: u64,
+        timestamp: Option<u64>,
+        contract: &WatchedContract,
+    ) -> String {
+        let mut heading = format!("{} Block #{}", "→".cyan(), block_number);
+        if let Some(timestamp) = timestamp {
+            heading.push_str(&format!("  {}", format_timestamp(timestamp).dimmed()));
+        }
+        if self.multiplexed {
+            heading.push_str(&format!("  {}", contract.label.bold()));
+        }
+        heading
+    }
+
+    /// Contract accounts whose deposits are tracked; none without --deposits
+    fn deposit_accounts(&self) -> Vec<AccountId32> {
+        if !self.args.deposits {
+            return Vec::new();
+        }
+        self.contracts
+            .iter()
+            .filter_map(|contract| contract.account.clone())
+            .collect()
+    }
+
+    /// Show the balances and deposits of a block that changed since the last one seen
+    fn deposits(
+        &mut self,
+        block_number: u64,
+        timestamp: Option<u64>,
+        deposits: &[(AccountId32, Deposit)],
+    ) -> anyhow::Result<()> {
+        for (account, deposit) in deposits {
+            let Some(contract) = self
+                .contracts
+                .iter()
+                .find(|c| c.account.as_ref() == Some(account))
+            else {
+                continue;
+            };
+            let previous = self.deposits.insert(contract.address.clone(), *deposit);
+            if previous == Some(*deposit) {
+                continue;
+            }
+            let near_limit = self
+                .deposit_limit
+                .filter(|limit| deposit.storage_deposit * 100 >= limit * DEPOSIT_WARNING_PERCENT);
+
+            if !self.args.json {
+                let heading = self.heading(block_number, timestamp, contract);
+                let change = |current: u128, last: Option<u128>| match last {
+                    Some(last) if last != current => format!(
+                        " ({}{})",
+                        if current > last { "+" } else { "-" },
+                        format_balance(current.abs_diff(last))
+                    ),
+                    _ => String::new(),
+                };
+                let mut lines = vec![
+                    format!(
+                        "  {} {} GLIN{}, {} bytes in {} items",
+                        "Storage deposit:".cyan(),
+                        format_balance(deposit.storage_deposit),
+                        change(deposit.storage_deposit, previous.map(|p| p.storage_deposit)),
+                        deposit.storage_bytes,
+                        deposit.storage_items
+                    ),
+                    format!(
+                        "  {} {} GLIN free{}, {} GLIN reserved",
+                        "Balance:".cyan(),
+                        format_balance(deposit.free),
+                        change(deposit.free, previous.map(|p| p.free)),
+                        format_balance(deposit.reserved)
+                    ),
+                ];
+                if let Some(limit) = near_limit {
+                    lines.push(format!(
+                        "  {} Storage deposit at {}% of the {} GLIN limit",
+                        "⚠".yellow(),
+                        deposit.storage_deposit * 100 / limit.max(1),
+                        format_balance(limit)
+                    ));
+                }
+                self.progress
+                    .suspend(|| println!("{}\n{}\n", heading, lines.join("\n")));
+            }
+
+            if self.args.json || self.export.is_some() {
+                let record = json!({
+                    "block": block_number,
+                    "contract": contract.label,
+                    "pallet": "Contracts",
+                    "variant": "deposit",
+                    "event": JsonValue::Null,
+                    "data": {
+                        "free": deposit.free.to_string(),
+                        "reserved": deposit.reserved.to_string(),
+                        "storage_deposit": deposit.storage_deposit.to_string(),
+                        "storage_bytes": deposit.storage_bytes,
+                        "storage_items": deposit.storage_items,
+                        "near_limit": near_limit.is_some(),
+                    },
+                });
+                if self.args.json {
+                    println!("{}", record);
+                }
+                if let Some(export) = &mut self.export {
+                    export.write(&record)?;
                 }
             }
         }
+        Ok(())
+    }
 
-        println!("\n{} Displayed {} events", "✓".green().bold(), event_count);
-        if event_count == 0 {
-            println!("{}", "No contract events found in recent blocks".dimmed());
+    /// Record a fully processed block for every watched contract
+    fn checkpoint(
+        &self,
+        checkpoints: &mut CheckpointFile,
+        checkpoint: Checkpoint,
+    ) -> anyhow::Result<()> {
+        for contract in &self.contracts {
+            checkpoints.set(&self.args.network, &contract.address, checkpoint.clone())?;
         }
-        println!(
-            "Use {} to keep watching for new events",
-            "--follow".yellow()
+        Ok(())
+    }
+
+    fn limit_reached(&self) {
+        status!(
+            self.args.json,
+            "\n{} Reached limit of {} events",
+            "✓".green().bold(),
+            self.args.limit
         );
     }
+}
 
-    Ok(())
+/// Arguments of a `Contracts::call` extrinsic
+#[derive(Decode)]
+pub(crate) struct ContractCall {
+    pub(crate) dest: MultiAddress<AccountId32, ()>,
+    pub(crate) value: Compact<u128>,
+    _gas_limit: (Compact<u64>, Compact<u64>),
+    _storage_deposit_limit: Option<Compact<u128>>,
+    pub(crate) data: Vec<u8>,
+}
+
+/// What watch needs of a block besides its events
+#[derive(Default)]
+struct BlockExtras {
+    /// Extrinsics, with --calls
+    extrinsics: Option<Extrinsics<GlinConfig, GlinClient>>,
+    /// Tracked deposits, with --deposits
+    deposits: Vec<(AccountId32, Deposit)>,
+    /// Block time in milliseconds, for terminal output
+    timestamp: Option<u64>,
+}
+
+async fn block_extras(
+    block: &Block<GlinConfig, GlinClient>,
+    args: &WatchArgs,
+    deposit_accounts: &[AccountId32],
+) -> anyhow::Result<BlockExtras> {
+    let storage = block.storage();
+    let extrinsics = match args.calls {
+        true => Some(block.extrinsics().await?),
+        false => None,
+    };
+    let timestamp = match args.json {
+        true => None,
+        false => block_timestamp(&storage).await,
+    };
+    Ok(BlockExtras {
+        extrinsics,
+        deposits: block_deposits(&storage, deposit_accounts).await?,
+        timestamp,
+    })
+}
+
+/// `Timestamp::Now` of a block; `None` on chains without the pallet
+async fn block_timestamp(storage: &Storage<GlinConfig, GlinClient>) -> Option<u64> {
+    let query = subxt::dynamic::storage("Timestamp", "Now", Vec::<subxt::dynamic::Value>::new());
+    let value = storage.fetch(&query).await.ok()??.to_value().ok()?;
+    serde_json::to_value(value).ok()?.as_u64()
+}
+
+/// UTC date and time of a millisecond Unix timestamp
+fn format_timestamp(millis: u64) -> String {
+    let secs = millis / 1000;
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
 }
 
-/// Format event data for display
-fn format_event_data<T: subxt::Config>(
+/// Balance and storage deposit of a contract account
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Deposit {
+    free: u128,
+    reserved: u128,
+    /// Byte, item and base deposits of the contract's storage
+    storage_deposit: u128,
+    storage_bytes: u64,
+    storage_items: u64,
+}
+
+impl Deposit {
+    /// From the decoded `System::Account` and `Contracts::ContractInfoOf` entries
+    fn from_values(account: &JsonValue, info: &JsonValue) -> Self {
+        let amount = |value: &JsonValue| match value {
+            JsonValue::Number(n) => n.as_u64().map(u128::from).unwrap_or(0),
+            JsonValue::String(s) => s.parse().unwrap_or(0),
+            _ => 0,
+        };
+        Self {
+            free: amount(&account["data"]["free"]),
+            reserved: amount(&account["data"]["reserved"]),
+            storage_deposit: [
+                "storage_byte_deposit",
+                "storage_item_deposit",
+                "storage_base_deposit",
+            ]
+            .iter()
+            .map(|field| amount(&info[*field]))
+            .sum(),
+            storage_bytes: amount(&info["storage_bytes"]) as u64,
+            storage_items: amount(&info["storage_items"]) as u64,
+        }
+    }
+}
+
+/// Deposits of the tracked contract accounts at a block
+async fn block_deposits(
+    storage: &Storage<GlinConfig, GlinClient>,
+    accounts: &[AccountId32],
+) -> anyhow::Result<Vec<(AccountId32, Deposit)>> {
+    let mut deposits = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let key = || vec![subxt::dynamic::Value::from_bytes(account.0)];
+        let fetch = |pallet: &'static str, entry: &'static str| {
+            let query = subxt::dynamic::storage(pallet, entry, key());
+            async move {
+                let value = match storage.fetch(&query).await? {
+                    Some(value) => serde_json::to_value(value.to_value()?)?,
+                    None => JsonValue::Null,
+                };
+                anyhow::Ok(value)
+            }
+        };
+        let account_info = fetch("System", "Account").await?;
+        let contract_info = fetch("Contracts", "ContractInfoOf").await?;
+        deposits.push((
+            account.clone(),
+            Deposit::from_values(&account_info, &contract_info),
+        ));
+    }
+    Ok(deposits)
+}
+
+/// Decoded payload of a `ContractEmitted` event of the watched contract
+pub(crate) fn decode_emitted<T: subxt::Config>(
+    event: &subxt::events::EventDetails<T>,
+    decoder: Option<&EventDecoder>,
+    watched: Option<&AccountId32>,
+) -> Option<anyhow::Result<DecodedEvent>> {
+    let decoder = decoder?;
+    if event.variant_name() != "ContractEmitted" {
+        return None;
+    }
+    let (contract, data) = match crate::contract::events::contract_emitted(event.field_bytes()) {
+        Ok(emitted) => emitted,
+        Err(e) => return Some(Err(e)),
+    };
+    // Other contracts' events don't follow this metadata
+    if watched.is_some_and(|watched| *watched != contract) {
+        return None;
+    }
+    let topics: Vec<[u8; 32]> = event
+        .topics()
+        .iter()
+        .filter_map(|topic| topic.encode().try_into().ok())
+        .collect();
+    Some(decoder.decode(&topics, &data))
+}
+
+/// Contract a Contracts pallet event belongs to: the emitter, or its `contract` field
+pub(crate) fn event_contract<T: subxt::Config>(
+    event: &subxt::events::EventDetails<T>,
+) -> Option<AccountId32> {
+    if event.variant_name() == "ContractEmitted" {
+        return crate::contract::events::contract_emitted(event.field_bytes())
+            .ok()
+            .map(|(contract, _)| contract);
+    }
+    let fields = serde_json::to_value(event.field_values().ok()?).ok()?;
+    let mut bytes = Vec::new();
+    collect_bytes(fields.get("contract")?, &mut bytes);
+    Some(AccountId32(bytes.try_into().ok()?))
+}
+
+/// Fields of a pallet event with their metadata type names, e.g. `T::AccountId`
+fn typed_fields<T: subxt::Config>(
+    event: &subxt::events::EventDetails<T>,
+) -> anyhow::Result<Vec<(String, Option<String>, JsonValue)>> {
+    let values = serde_json::to_value(event.field_values()?).unwrap_or(JsonValue::Null);
+    Ok(event
+        .event_metadata()
+        .variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let value = match &field.name {
+                Some(name) => values[name.as_str()].clone(),
+                None => values[i].clone(),
+            };
+            let name = field.name.clone().unwrap_or_else(|| i.to_string());
+            (name, field.type_name.clone(), value)
+        })
+        .collect())
+}
+
+/// Accounts held by the fields typed as account ids
+fn field_accounts(fields: &[(String, Option<String>, JsonValue)]) -> Vec<AccountId32> {
+    fields
+        .iter()
+        .filter(|(_, type_name, _)| {
+            type_name
+                .as_deref()
+                .is_some_and(|name| name.contains("AccountId"))
+        })
+        .filter_map(|(_, _, value)| {
+            let mut bytes = Vec::new();
+            collect_bytes(value, &mut bytes);
+            Some(AccountId32(bytes.try_into().ok()?))
+        })
+        .collect()
+}
+
+/// Flatten the nested byte arrays a decoded account id serializes to
+pub(crate) fn collect_bytes(value: &JsonValue, bytes: &mut Vec<u8>) {
+    match value {
+        JsonValue::Array(items) => items.iter().for_each(|item| collect_bytes(item, bytes)),
+        JsonValue::Object(fields) => fields
+            .values()
+            .for_each(|field| collect_bytes(field, bytes)),
+        JsonValue::Number(n) => bytes.extend(n.as_u64().and_then(|n| u8::try_from(n).ok())),
+        _ => {}
+    }
+}
+
+/// Event name pattern: `|`-separated names or globs, or a regex between slashes
+#[derive(Debug, Clone)]
+pub(crate) struct EventPattern {
+    source: String,
+    matcher: PatternMatcher,
+}
+
+#[derive(Debug, Clone)]
+enum PatternMatcher {
+    Globs(Vec<GlobMatcher>),
+    Regex(Regex),
+}
+
+impl EventPattern {
+    pub(crate) fn parse(pattern: &str) -> anyhow::Result<Self> {
+        let matcher = match pattern
+            .strip_prefix('/')
+            .and_then(|rest| rest.strip_suffix('/'))
+        {
+            Some(regex) => PatternMatcher::Regex(
+                Regex::new(regex).with_context(|| format!("Invalid event regex: {}", pattern))?,
+            ),
+            None => PatternMatcher::Globs(
+                pattern
+                    .split('|')
+                    .map(|name| {
+                        Glob::new(name.trim())
+                            .map(|glob| glob.compile_matcher())
+                            .with_context(|| format!("Invalid event pattern: {}", name))
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+        };
+        Ok(Self {
+            source: pattern.to_string(),
+            matcher,
+        })
+    }
+
+    pub(crate) fn matches(&self, name: &str) -> bool {
+        match &self.matcher {
+            PatternMatcher::Globs(globs) => globs.iter().any(|glob| glob.is_match(name)),
+            PatternMatcher::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+impl std::fmt::Display for EventPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Whether an event matches a filter, by pallet variant or decoded contract event name
+fn matches_filter(
+    filter: &EventPattern,
+    variant: &str,
+    decoded: Option<&anyhow::Result<DecodedEvent>>,
+) -> bool {
+    filter.matches(variant) || matches!(decoded, Some(Ok(event)) if filter.matches(&event.name))
+}
+
+/// Event for the terminal: its name, then its fields as an aligned key/value list
+fn format_event<T: subxt::Config>(
     event: &subxt::events::EventDetails<T>,
+    decoded: Option<&anyhow::Result<DecodedEvent>>,
+    decoder: Option<&EventDecoder>,
 ) -> anyhow::Result<String> {
-    // Get event field values
-    let field_values = event.field_values()?;
+    let name = match event.pallet_name() {
+        "Contracts" => event.variant_name().to_string(),
+        pallet => format!("{}::{}", pallet, event.variant_name()),
+    };
+    let mut title = format!("  {}", name.yellow().bold());
+
+    let fields: Vec<(String, Option<String>, JsonValue)> = match decoded {
+        Some(Ok(decoded)) => {
+            title.push_str(&format!(" {}", decoded.name.bold()));
+            let types = decoder
+                .map(|decoder| decoder.field_types(&decoded.name))
+                .unwrap_or_default();
+            decoded
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, (name, value))| {
+                    (name.clone(), types.get(i).cloned().flatten(), value.clone())
+                })
+                .collect()
+        }
+        // Pallet events: the metadata's type names tell accounts, hashes and balances apart
+        _ => typed_fields(event)?,
+    };
+
+    let mut lines = vec![title];
+    lines.extend(format_fields(&fields));
+    if let Some(Err(e)) = decoded {
+        lines.push(format!("    {}", format!("(undecoded: {})", e).dimmed()));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// `name  value` lines, names padded to a common width
+fn format_fields(fields: &[(String, Option<String>, JsonValue)]) -> Vec<String> {
+    let width = fields
+        .iter()
+        .map(|(name, ..)| name.len())
+        .max()
+        .unwrap_or(0);
+    fields
+        .iter()
+        .map(|(name, type_name, value)| {
+            format!(
+                "    {:width$}  {}",
+                name.cyan(),
+                format_value(type_name.as_deref(), value),
+                width = width
+            )
+        })
+        .collect()
+}
+
+/// A field value: balances in GLIN, account ids in SS58, hashes and bytes as hex
+fn format_value(type_name: Option<&str>, value: &JsonValue) -> String {
+    let type_name = type_name.unwrap_or_default();
+    let bytes = || {
+        let mut bytes = Vec::new();
+        collect_bytes(value, &mut bytes);
+        bytes
+    };
+    if type_name.contains("Balance") {
+        let amount = match value {
+            JsonValue::Number(n) => n.as_u64().map(u128::from),
+            JsonValue::String(s) => s.parse().ok(),
+            _ => None,
+        };
+        if let Some(amount) = amount {
+            return format!("{} GLIN", format_balance(amount));
+        }
+    } else if type_name.contains("AccountId") && !value.is_string() {
+        if let Ok(account) = <[u8; 32]>::try_from(bytes()) {
+            return AccountId32(account).to_string();
+        }
+    } else if (type_name.contains("Hash") || type_name == "Vec<u8>") && !value.is_string() {
+        return format!("0x{}", hex::encode(bytes()));
+    }
+    match value {
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Structured record of an event, as written by --output
+pub(crate) fn event_record<T: subxt::Config>(
+    block: u64,
+    contract: &str,
+    event: &subxt::events::EventDetails<T>,
+    decoded: Option<&anyhow::Result<DecodedEvent>>,
+) -> anyhow::Result<JsonValue> {
+    let (name, data) = match decoded {
+        Some(Ok(decoded)) => (json!(decoded.name), decoded.to_json()["fields"].take()),
+        _ => (
+            JsonValue::Null,
+            serde_json::to_value(event.field_values()?).unwrap_or(JsonValue::Null),
+        ),
+    };
+    Ok(json!({
+        "block": block,
+        "contract": contract,
+        "pallet": event.pallet_name(),
+        "variant": event.variant_name(),
+        "event": name,
+        "data": data,
+    }))
+}
 
-    // Try to convert to JSON for display
-    if let Ok(json) = serde_json::to_value(&field_values) {
-        Ok(json.to_string())
+/// First retry delay of webhook deliveries, doubled on every attempt
+pub(crate) const WEBHOOK_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Whether a webhook takes an event record
+fn webhook_matches(hook: &WebhookConfig, record: &JsonValue) -> bool {
+    hook.events.is_empty()
+        || hook
+            .events
+            .iter()
+            .any(|name| record["variant"] == *name.as_str() || record["event"] == *name.as_str())
+}
+
+/// Deliver an event to every matching webhook; failures are reported, not fatal
+async fn notify(
+    http: &reqwest::Client,
+    webhooks: &[WebhookConfig],
+    network: &str,
+    address: &str,
+    record: &JsonValue,
+) {
+    let mut payload = record.clone();
+    payload["address"] = json!(address);
+    payload["network"] = json!(network);
+
+    for hook in webhooks.iter().filter(|hook| webhook_matches(hook, record)) {
+        if let Err(e) = deliver(http, hook, &payload, WEBHOOK_BACKOFF).await {
+            eprintln!("  {} Webhook {} failed: {}", "⚠".yellow(), hook.url, e);
+        }
+    }
+}
+
+/// POST a payload, retrying connection errors, 429 and 5xx responses with exponential backoff
+pub(crate) async fn deliver(
+    http: &reqwest::Client,
+    hook: &WebhookConfig,
+    payload: &JsonValue,
+    backoff: Duration,
+) -> anyhow::Result<()> {
+    let mut attempt = 0;
+    loop {
+        let mut request = http.post(&hook.url).json(payload);
+        for (name, value) in &hook.headers {
+            request = request.header(name, value);
+        }
+
+        let error = match request.send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response)
+                if !(response.status().is_server_error()
+                    || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS) =>
+            {
+                anyhow::bail!("HTTP {}", response.status())
+            }
+            Ok(response) => format!("HTTP {}", response.status()),
+            Err(e) => e.to_string(),
+        };
+
+        if attempt >= hook.retries {
+            anyhow::bail!("{} (after {} attempts)", error, attempt + 1);
+        }
+        tokio::time::sleep(backoff * 2u32.pow(attempt)).await;
+        attempt += 1;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Ndjson,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Columns of CSV exports; `data` holds the fields as JSON
+const CSV_COLUMNS: [&str; 6] = ["block", "contract", "pallet", "variant", "event", "data"];
+
+/// Appends event records to a file
+struct EventExport {
+    path: PathBuf,
+    format: ExportFormat,
+    /// Records of a JSON array export, rewritten as a whole
+    records: Vec<JsonValue>,
+}
+
+impl EventExport {
+    fn open(path: &Path, format: Option<&str>) -> anyhow::Result<Self> {
+        let format = match format.or_else(|| path.extension().and_then(|e| e.to_str())) {
+            Some("csv") => ExportFormat::Csv,
+            Some("json") => ExportFormat::Json,
+            _ => ExportFormat::Ndjson,
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let existing = std::fs::read_to_string(path).unwrap_or_default();
+        let mut records = Vec::new();
+        match format {
+            ExportFormat::Json if !existing.trim().is_empty() => {
+                records = serde_json::from_str(&existing).map_err(|e| {
+                    anyhow::anyhow!("{} is not a JSON array of events: {}", path.display(), e)
+                })?;
+            }
+            ExportFormat::Csv if existing.is_empty() => {
+                std::fs::write(path, format!("{}\n", CSV_COLUMNS.join(",")))?;
+            }
+            _ => {}
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            format,
+            records,
+        })
+    }
+
+    fn write(&mut self, record: &JsonValue) -> anyhow::Result<()> {
+        let line = match self.format {
+            ExportFormat::Json => {
+                self.records.push(record.clone());
+                std::fs::write(&self.path, serde_json::to_string_pretty(&self.records)?)?;
+                return Ok(());
+            }
+            ExportFormat::Ndjson => record.to_string(),
+            ExportFormat::Csv => CSV_COLUMNS
+                .iter()
+                .map(|column| match &record[*column] {
+                    JsonValue::Null => String::new(),
+                    JsonValue::String(s) => csv_field(s),
+                    other => csv_field(&other.to_string()),
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+}
+
+/// Quote a CSV field when it contains separators, quotes or newlines
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
     } else {
-        Ok(String::from("(no data)"))
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_export() {
+        let dir = std::env::temp_dir().join(format!("glin-forge-watch-{}", std::process::id()));
+        let record = json!({
+            "block": 7,
+            "contract": "token",
+            "pallet": "Contracts",
+            "variant": "ContractEmitted",
+            "event": "Transfer",
+            "data": { "value": "1000", "to": "5Grw" },
+        });
+
+        let csv = dir.join("events.csv");
+        EventExport::open(&csv, None)
+            .unwrap()
+            .write(&record)
+            .unwrap();
+        // Reopening appends without repeating the header
+        EventExport::open(&csv, None)
+            .unwrap()
+            .write(&record)
+            .unwrap();
+        let content = std::fs::read_to_string(&csv).unwrap();
+        let row =
+            r#"7,token,Contracts,ContractEmitted,Transfer,"{""to"":""5Grw"",""value"":""1000""}""#;
+        assert_eq!(
+            content,
+            format!("block,contract,pallet,variant,event,data\n{row}\n{row}\n")
+        );
+
+        let json_file = dir.join("events.json");
+        EventExport::open(&json_file, None)
+            .unwrap()
+            .write(&record)
+            .unwrap();
+        EventExport::open(&json_file, None)
+            .unwrap()
+            .write(&record)
+            .unwrap();
+        let records: Vec<JsonValue> =
+            serde_json::from_str(&std::fs::read_to_string(&json_file).unwrap()).unwrap();
+        assert_eq!(records, [record.clone(), record.clone()]);
+
+        let ndjson = dir.join("events.log");
+        let mut export = EventExport::open(&ndjson, Some("ndjson")).unwrap();
+        export.write(&record).unwrap();
+        let line = std::fs::read_to_string(&ndjson).unwrap();
+        assert_eq!(
+            serde_json::from_str::<JsonValue>(line.trim()).unwrap(),
+            record
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_contract_field_bytes() {
+        use subxt::ext::scale_value::Value;
+
+        // `Called { caller, contract }`, accounts decoded as `AccountId32([u8; 32])`
+        let fields = Value::named_composite([
+            (
+                "caller",
+                Value::unnamed_composite([Value::from_bytes([1u8; 32])]),
+            ),
+            (
+                "contract",
+                Value::unnamed_composite([Value::from_bytes([7u8; 32])]),
+            ),
+        ]);
+        let json = serde_json::to_value(&fields).unwrap();
+        let mut bytes = Vec::new();
+        collect_bytes(&json["contract"], &mut bytes);
+        assert_eq!(bytes, [7u8; 32]);
+    }
+
+    #[test]
+    fn test_event_patterns() {
+        let either = EventPattern::parse("Transfer|Approval").unwrap();
+        assert!(either.matches("Transfer"));
+        assert!(either.matches("Approval"));
+        assert!(!either.matches("TransferFrom"));
+
+        let glob = EventPattern::parse("Code*").unwrap();
+        assert!(glob.matches("CodeStored"));
+        assert!(!glob.matches("ContractEmitted"));
+
+        let regex = EventPattern::parse("/^(Mint|Burn)ed$/").unwrap();
+        assert!(regex.matches("Minted"));
+        assert!(!regex.matches("Minter"));
+        assert_eq!(regex.to_string(), "/^(Mint|Burn)ed$/");
+        assert!(EventPattern::parse("/(/").is_err());
+
+        // Decoded contract event names match too
+        let decoded = Some(Ok(DecodedEvent {
+            name: "Approval".to_string(),
+            fields: Vec::new(),
+        }));
+        assert!(matches_filter(&either, "ContractEmitted", decoded.as_ref()));
+        assert!(!matches_filter(&glob, "ContractEmitted", decoded.as_ref()));
+    }
+
+    #[test]
+    fn test_format_fields() {
+        colored::control::set_override(false);
+        let fields = vec![
+            (
+                "contract".to_string(),
+                Some("T::AccountId".to_string()),
+                json!([vec![7u8; 32]]),
+            ),
+            (
+                "code_hash".to_string(),
+                Some("CodeHash<T>".to_string()),
+                json!([vec![0xabu8; 2]]),
+            ),
+            (
+                "value".to_string(),
+                Some("Balance".to_string()),
+                json!("1500000000000000000"),
+            ),
+            ("note".to_string(), None, json!("hello")),
+        ];
+        assert_eq!(
+            format_fields(&fields),
+            [
+                format!("    contract   {}", AccountId32([7; 32])),
+                "    code_hash  0xabab".to_string(),
+                "    value      1.5000 GLIN".to_string(),
+                "    note       hello".to_string(),
+            ]
+        );
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(
+            format_timestamp(1_791_979_203_000),
+            "2026-10-14 12:00:03 UTC"
+        );
+    }
+
+    #[test]
+    fn test_field_accounts() {
+        // Balances::Transfer { from, to, amount }
+        let fields = vec![
+            (
+                "from".to_string(),
+                Some("T::AccountId".to_string()),
+                json!([vec![1u8; 32]]),
+            ),
+            (
+                "to".to_string(),
+                Some("T::AccountId".to_string()),
+                json!([vec![2u8; 32]]),
+            ),
+            (
+                "amount".to_string(),
+                Some("T::Balance".to_string()),
+                json!("10"),
+            ),
+        ];
+        assert_eq!(
+            field_accounts(&fields),
+            [AccountId32([1; 32]), AccountId32([2; 32])]
+        );
+    }
+
+    #[test]
+    fn test_deposit_values() {
+        let account =
+            json!({ "nonce": 0, "data": { "free": "5000", "reserved": 300, "frozen": "0" } });
+        let info = json!({
+            "storage_bytes": 1024,
+            "storage_items": 12,
+            "storage_byte_deposit": "200",
+            "storage_item_deposit": "60",
+            "storage_base_deposit": "40",
+        });
+        assert_eq!(
+            Deposit::from_values(&account, &info),
+            Deposit {
+                free: 5000,
+                reserved: 300,
+                storage_deposit: 300,
+                storage_bytes: 1024,
+                storage_items: 12,
+            }
+        );
+        // Accounts without contract info, or unknown ones
+        assert_eq!(
+            Deposit::from_values(&JsonValue::Null, &JsonValue::Null),
+            Deposit::default()
+        );
+    }
+
+    #[test]
+    fn test_reconnect() {
+        assert_eq!(reconnect_delay(0), Duration::from_secs(1));
+        assert_eq!(reconnect_delay(3), Duration::from_secs(8));
+        assert_eq!(reconnect_delay(40), MAX_RECONNECT_DELAY);
+
+        let dropped = anyhow::Error::from(subxt::Error::Other("connection closed".to_string()))
+            .context("Failed to fetch block");
+        assert!(is_connection_error(&dropped));
+        assert!(!is_connection_error(&anyhow::anyhow!(
+            "Invalid checkpoint file"
+        )));
+    }
+
+    #[test]
+    fn test_contract_call_args() {
+        let dest = AccountId32([7; 32]);
+        let encoded = (
+            MultiAddress::<AccountId32, ()>::Id(dest.clone()),
+            Compact(5u128),
+            (Compact(1_000u64), Compact(64u64)),
+            None::<Compact<u128>>,
+            vec![0x84u8, 0xa1, 0x5d, 0xa1],
+        )
+            .encode();
+        let call = ContractCall::decode(&mut &encoded[..]).unwrap();
+        assert_eq!(call.dest, MultiAddress::Id(dest));
+        assert_eq!(call.value.0, 5);
+        assert_eq!(call.data, [0x84, 0xa1, 0x5d, 0xa1]);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivery() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Unavailable once, then accepting
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/events", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut bodies = Vec::new();
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                // Read the headers, then content-length bytes of body
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                loop {
+                    let n = socket.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..n]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length: usize = text
+                            .lines()
+                            .find_map(|l| l.strip_prefix("content-length: "))
+                            .map_or(0, |l| l.trim().parse().unwrap());
+                        if request.len() >= end + 4 + length || n == 0 {
+                            break;
+                        }
+                    }
+                }
+                bodies.push(String::from_utf8_lossy(&request).to_string());
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    status
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            bodies
+        });
+
+        let hook = WebhookConfig {
+            url,
+            events: vec!["Transfer".to_string()],
+            headers: [("x-token".to_string(), "secret".to_string())].into(),
+            retries: 2,
+        };
+        let record = json!({ "variant": "ContractEmitted", "event": "Transfer", "data": {} });
+        assert!(webhook_matches(&hook, &record));
+        assert!(!webhook_matches(
+            &hook,
+            &json!({ "variant": "Called", "event": null })
+        ));
+
+        deliver(
+            &reqwest::Client::new(),
+            &hook,
+            &record,
+            Duration::from_millis(1),
+        )
+        .await
+        .unwrap();
+        let bodies = server.await.unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert!(bodies[1].contains("x-token: secret"));
+        assert!(bodies[1].ends_with(&record.to_string()));
     }
 }
@@ -0,0 +1,307 @@
+// Angular service generation for ink! contracts
+//
+// Emits an injectable `<Contract>ContractService` wrapping the typed contract
+// interface from the main module: queries and events as RxJS observables,
+// transactions as promises.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
+
+use super::metadata::extract_events;
+use super::type_resolver::collect_names;
+use super::typescript::{member_access, property_key, ModuleOptions};
+
+/// Generate an Angular service for contract interaction
+pub fn generate_angular_service(
+    contract_name: &str,
+    abi: &JsonValue,
+    options: ModuleOptions,
+) -> Result<String> {
+    let (types, spec) = match abi.get("V3") {
+        Some(v3) => (&v3["types"], &v3["spec"]),
+        None => (
+            abi.get("types").context("Types section not found")?,
+            &abi["spec"],
+        ),
+    };
+    let mut resolver = options.type_resolver(types)?;
+    let events = extract_events(&serde_json::json!({ "spec": spec }));
+    let messages = spec["messages"]
+        .as_array()
+        .context("Messages section missing")?;
+
+    let mut imports = BTreeSet::from([contract_name.to_string()]);
+    let mut queries = String::new();
+    let mut transactions = String::new();
+    for msg in messages {
+        let label = msg["label"].as_str().unwrap_or("method");
+        let mut params = Vec::new();
+        let mut names = Vec::new();
+        for arg in msg["args"].as_array().into_iter().flatten() {
+            let arg_name = arg["label"].as_str().unwrap_or("arg");
+            let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+            let arg_type = resolver.resolve_type(type_id)?;
+            collect_names(&arg_type, &mut imports);
+            params.push(format!(
+                "{}: {}",
+                arg_name,
+                resolver.format_input_type(&arg_type)
+            ));
+            names.push(arg_name.to_string());
+        }
+        let docs = jsdoc(&msg["docs"]);
+
+        if msg["mutates"].as_bool().unwrap_or(false) {
+            transactions.push_str(&format!(
+                "{docs}  {key}({params}): Promise<Transaction> {{\n    return this.require().tx{access}({names});\n  }}\n\n",
+                key = property_key(label),
+                access = member_access(label),
+                params = params.join(", "),
+                names = names.join(", "),
+            ));
+        } else {
+            let ret = match msg["returnType"]["type"].as_u64() {
+                Some(type_id) => {
+                    let ts_type = resolver.resolve_type(type_id as u32)?;
+                    collect_names(&ts_type, &mut imports);
+                    resolver.format_type(&ts_type)
+                }
+                None => "void".to_string(),
+            };
+            queries.push_str(&format!(
+                "{docs}  {key}({params}): Observable<{ret}> {{\n    return defer(() => from(this.require().query{access}({names})));\n  }}\n\n",
+                key = property_key(label),
+                access = member_access(label),
+                params = params.join(", "),
+                names = names.join(", "),
+            ));
+        }
+    }
+
+    let mut event_methods = String::new();
+    if !events.is_empty() {
+        imports.insert(format!("{}EventSource", contract_name));
+        imports.insert(format!("{}DecodedEvent", contract_name));
+        for event in &events {
+            imports.insert(format!("{}Event", event.label));
+            event_methods.push_str(&format!(
+                "  /** `{label}` events as they are emitted */\n  on{label}(): Observable<{label}Event> {{\n    return this.observe<{label}Event>((subscriptions, next) => subscriptions.subscribe{label}(next));\n  }}\n\n",
+                label = event.label
+            ));
+        }
+        event_methods.push_str(&format!(
+            "  /** Every event, tagged with its name */\n  events(): Observable<{name}DecodedEvent> {{\n    return this.observe<{name}DecodedEvent>((subscriptions, next) => subscriptions.subscribeAll(next));\n  }}\n\n",
+            name = contract_name
+        ));
+        event_methods.push_str(&format!(
+            r#"  private observe<T>(
+    subscribe: (
+      subscriptions: ReturnType<typeof create{name}EventSubscriptions>,
+      next: (event: T) => void,
+    ) => Unsubscribe | Promise<Unsubscribe>,
+  ): Observable<T> {{
+    return new Observable<T>((subscriber) => {{
+      if (!this.eventSource) {{
+        subscriber.error(new Error('No event source connected'));
+        return;
+      }}
+      const stop = subscribe(create{name}EventSubscriptions(this.eventSource), (event) =>
+        subscriber.next(event),
+      );
+      return () => {{
+        Promise.resolve(stop).then((unsubscribe) => unsubscribe());
+      }};
+    }});
+  }}
+
+"#,
+            name = contract_name
+        ));
+    }
+
+    let (event_field, connect_params, connect_body, value_imports) = if events.is_empty() {
+        (
+            String::new(),
+            format!("contract: {}", contract_name),
+            String::new(),
+            String::new(),
+        )
+    } else {
+        (
+            format!("  private eventSource?: {}EventSource;\n", contract_name),
+            format!(
+                "contract: {name}, eventSource?: {name}EventSource",
+                name = contract_name
+            ),
+            "    this.eventSource = eventSource;\n".to_string(),
+            format!(
+                "import {{ create{}EventSubscriptions, type Unsubscribe }} from './{}';\n",
+                contract_name, contract_name
+            ),
+        )
+    };
+
+    Ok(format!(
+        r#"// Generated by glin-forge
+// Do not edit manually
+
+import {{ Injectable }} from '@angular/core';
+import {{ defer, from, Observable }} from 'rxjs';
+import type {{ Transaction }} from '@glin-forge/sdk';
+import type {{ {imports} }} from './{name}';
+{branded_import}{value_imports}
+/**
+ * Injectable wrapper around the {name} contract
+ *
+ * Call `connect()` once the contract is loaded; queries and events are cold
+ * observables, transactions resolve once submitted.
+ */
+@Injectable({{ providedIn: 'root' }})
+export class {name}ContractService {{
+  private contract?: {name};
+{event_field}
+  connect({connect_params}): void {{
+    this.contract = contract;
+{connect_body}  }}
+
+  get connected(): boolean {{
+    return this.contract !== undefined;
+  }}
+
+  // Queries
+
+{queries}  // Transactions
+
+{transactions}{events_header}{event_methods}  private require(): {name} {{
+    if (!this.contract) {{
+      throw new Error('{name}ContractService is not connected');
+    }}
+    return this.contract;
+  }}
+}}
+"#,
+        name = contract_name,
+        imports = imports.into_iter().collect::<Vec<_>>().join(", "),
+        branded_import = options.branded_import(contract_name),
+        events_header = if events.is_empty() {
+            ""
+        } else {
+            "  // Events\n\n"
+        },
+    ))
+}
+
+/// File name following Angular conventions, e.g. `my-token-contract.service.ts`
+pub fn angular_service_file(contract_name: &str) -> String {
+    let mut kebab = String::new();
+    for (i, c) in contract_name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            kebab.push('-');
+        }
+        if c == '_' {
+            kebab.push('-');
+        } else {
+            kebab.extend(c.to_lowercase());
+        }
+    }
+    format!("{}-contract.service.ts", kebab)
+}
+
+fn jsdoc(docs: &JsonValue) -> String {
+    let lines: Vec<&str> = docs
+        .as_array()
+        .map(|docs| docs.iter().filter_map(|d| d.as_str()).collect())
+        .unwrap_or_default();
+    if lines.is_empty() {
+        return String::new();
+    }
+    let mut output = String::from("  /**\n");
+    for line in lines {
+        output.push_str(&format!("   * {}\n", line.trim()));
+    }
+    output.push_str("   */\n");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_angular_service() {
+        let abi = serde_json::json!({
+            "spec": {
+                "constructors": [],
+                "messages": [
+                    {
+                        "label": "get",
+                        "mutates": false,
+                        "args": [],
+                        "returnType": { "type": 0 },
+                        "docs": ["Current value."]
+                    },
+                    {
+                        "label": "set",
+                        "mutates": true,
+                        "args": [{ "label": "value", "type": { "type": 0 } }],
+                        "returnType": null
+                    }
+                ],
+                "events": [
+                    { "label": "Changed", "args": [{ "label": "value", "indexed": false, "type": { "type": 0 } }] }
+                ]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "bool" } } }
+            ]
+        });
+
+        let service = generate_angular_service("MyToken", &abi, ModuleOptions::default()).unwrap();
+        assert!(service.contains("export class MyTokenContractService"));
+        assert!(service.contains("get(): Observable<boolean>"));
+        assert!(service.contains("set(value: boolean): Promise<Transaction>"));
+        assert!(service.contains("onChanged(): Observable<ChangedEvent>"));
+        assert_eq!(
+            angular_service_file("MyToken"),
+            "my-token-contract.service.ts"
+        );
+    }
+
+    #[test]
+    fn test_angular_trait_message() {
+        let abi = serde_json::json!({
+            "spec": {
+                "constructors": [],
+                "messages": [
+                    {
+                        "label": "Erc20::total_supply",
+                        "mutates": false,
+                        "args": [],
+                        "returnType": { "type": 0 }
+                    },
+                    {
+                        "label": "Erc20::approve",
+                        "mutates": true,
+                        "args": [{ "label": "value", "type": { "type": 1 } }],
+                        "returnType": null
+                    }
+                ],
+                "events": []
+            },
+            "types": [
+                { "id": 0, "type": { "path": ["Result"], "params": [{ "type": 1 }, { "type": 2 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 1, "type": { "def": { "primitive": "bool" } } },
+                { "id": 2, "type": { "def": { "tuple": [] } } }
+            ]
+        });
+
+        let service = generate_angular_service("Token", &abi, ModuleOptions::default()).unwrap();
+        assert!(service.contains("import type { Result, Token } from './Token';"));
+        assert!(service.contains(
+            "  'Erc20::total_supply'(): Observable<Result> {\n    return defer(() => from(this.require().query['Erc20::total_supply']()));"
+        ));
+        assert!(service.contains("return this.require().tx['Erc20::approve'](value);"));
+    }
+}
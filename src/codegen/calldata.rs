@@ -0,0 +1,429 @@
+// Call data encoders for the generated TypeScript module
+//
+// Every message gets a selector constant and an `encode<Message>()` function
+// returning the hex call data (selector followed by the SCALE-encoded
+// arguments), for wallets and multisigs that only take raw call data. Encoders
+// are generated per registry type from the metadata, so the module needs no
+// codec library; inputs have the shapes of the generated argument types.
+
+use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+
+use super::hooks::pascal_case;
+use super::type_resolver::TypeResolver;
+use super::typescript::property_key;
+
+/// Generate the selector constants, message encoders and SCALE helpers
+pub(super) fn generate_call_data(
+    contract_name: &str,
+    types: &JsonValue,
+    spec: &JsonValue,
+    resolver: &mut TypeResolver,
+) -> Result<String> {
+    let registry: HashMap<u32, JsonValue> = types
+        .as_array()
+        .context("Types section must be an array")?
+        .iter()
+        .filter_map(|entry| Some((entry["id"].as_u64()? as u32, entry["type"].clone())))
+        .collect();
+    let messages = spec["messages"]
+        .as_array()
+        .context("Messages section missing")?;
+
+    let mut encoders = BTreeMap::new();
+    let mut selectors = String::new();
+    let mut functions = String::new();
+    for msg in messages {
+        let label = msg["label"].as_str().unwrap_or("method");
+        let Some(selector) = msg["selector"].as_str() else {
+            continue;
+        };
+        selectors.push_str(&format!("  {}: '{}',\n", property_key(label), selector));
+
+        let mut params = Vec::new();
+        let mut body = String::new();
+        for arg in msg["args"].as_array().into_iter().flatten() {
+            let name = arg["label"].as_str().unwrap_or("arg");
+            let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+            let arg_type = resolver.resolve_type(type_id)?;
+            params.push(format!(
+                "{}: {}",
+                name,
+                resolver.format_input_type(&arg_type)
+            ));
+            body.push_str(&format!("  encode{}(out, {});\n", type_id, name));
+            collect(&registry, type_id, &mut encoders)?;
+        }
+
+        functions.push_str(&format!(
+            "/** Call data of `{label}`: selector `{selector}` and the encoded arguments */\nexport function encode{name}({params}): string {{\n  const out: number[] = [{selector_bytes}];\n{body}  return toHex(out);\n}}\n\n",
+            name = pascal_case(label),
+            params = params.join(", "),
+            selector_bytes = selector_bytes(selector)?,
+        ));
+    }
+    if selectors.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut output = String::new();
+    output.push_str("// ========================================\n");
+    output.push_str("// Call Data\n");
+    output.push_str("// ========================================\n\n");
+    output.push_str(&format!(
+        "export const {}Selectors = {{\n{}}} as const;\n\n",
+        contract_name, selectors
+    ));
+    output.push_str(&functions);
+    for (type_id, body) in &encoders {
+        output.push_str(&format!(
+            "function encode{}(out: number[], value: any): void {{\n{}}}\n\n",
+            type_id, body
+        ));
+    }
+    output.push_str(SCALE_HELPERS);
+    Ok(output)
+}
+
+/// Generate the encoder body of `type_id` and every type it contains
+fn collect(
+    registry: &HashMap<u32, JsonValue>,
+    type_id: u32,
+    encoders: &mut BTreeMap<u32, String>,
+) -> Result<()> {
+    if encoders.contains_key(&type_id) {
+        return Ok(());
+    }
+    // Reserve the slot first so recursive types terminate
+    encoders.insert(type_id, String::new());
+
+    let ty = registry
+        .get(&type_id)
+        .with_context(|| format!("Type ID {} not found in registry", type_id))?;
+    let path = ty["path"]
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|p| p.as_str())
+                .collect::<Vec<_>>()
+                .join("::")
+        })
+        .unwrap_or_default();
+    let def = &ty["def"];
+    let param = |i: usize| ty["params"][i]["type"].as_u64().map(|id| id as u32);
+
+    let mut children = Vec::new();
+    let body = if path.contains("AccountId")
+        || (path.contains("Hash") && is_byte_array(registry, def))
+    {
+        "  writeFixedBytes(out, value, 32);\n".to_string()
+    } else if path == "Option" {
+        let inner = param(0).context("Option without type parameter")?;
+        children.push(inner);
+        format!(
+            "  if (value === null || value === undefined) {{\n    out.push(0);\n  }} else {{\n    out.push(1);\n    encode{}(out, value);\n  }}\n",
+            inner
+        )
+    } else if path == "Result" {
+        let ok = param(0).context("Result without Ok type")?;
+        let err = param(1).context("Result without Err type")?;
+        children.extend([ok, err]);
+        format!(
+            "  if (value.type === 'Ok') {{\n    out.push(0);\n    encode{}(out, value.value);\n  }} else {{\n    out.push(1);\n    encode{}(out, value.error);\n  }}\n",
+            ok, err
+        )
+    } else if let Some(primitive) = def["primitive"].as_str() {
+        match primitive {
+            "bool" => "  out.push(value ? 1 : 0);\n".to_string(),
+            "char" => "  writeUint(out, String(value).codePointAt(0) ?? 0, 4);\n".to_string(),
+            "str" => "  writeStr(out, value);\n".to_string(),
+            int => {
+                let signed = int.starts_with('i');
+                let bits: u32 = int[1..]
+                    .parse()
+                    .with_context(|| format!("Unknown primitive {}", int))?;
+                format!(
+                    "  write{}(out, value, {});\n",
+                    if signed { "Int" } else { "Uint" },
+                    bits / 8
+                )
+            }
+        }
+    } else if let Some(fields) = def["composite"]["fields"].as_array() {
+        let mut body = String::new();
+        for (i, field) in fields.iter().enumerate() {
+            let field_type = field["type"].as_u64().context("Field missing type")? as u32;
+            children.push(field_type);
+            let access = match field["name"].as_str() {
+                Some(name) => format!("value.{}", name),
+                // Single unnamed fields are unwrapped in the generated types
+                None if fields.len() == 1 => "value".to_string(),
+                None => format!("value[{}]", i),
+            };
+            body.push_str(&format!("  encode{}(out, {});\n", field_type, access));
+        }
+        body
+    } else if let Some(variants) = def["variant"]["variants"].as_array() {
+        let mut body = String::from("  switch (value.type) {\n");
+        for (position, variant) in variants.iter().enumerate() {
+            let name = variant["name"].as_str().context("Variant missing name")?;
+            let index = variant["index"].as_u64().unwrap_or(position as u64);
+            body.push_str(&format!(
+                "    case '{}':\n      out.push({});\n",
+                name, index
+            ));
+            let fields = variant["fields"].as_array().cloned().unwrap_or_default();
+            for (i, field) in fields.iter().enumerate() {
+                let field_type = field["type"].as_u64().context("Field missing type")? as u32;
+                children.push(field_type);
+                let access = match field["name"].as_str() {
+                    Some(name) => name.to_string(),
+                    None if fields.len() == 1 => "value".to_string(),
+                    None => format!("field{}", i),
+                };
+                body.push_str(&format!(
+                    "      encode{}(out, value.{});\n",
+                    field_type, access
+                ));
+            }
+            body.push_str("      return;\n");
+        }
+        body.push_str(&format!(
+            "    default:\n      throw new TypeError(`Unknown {} variant '${{value?.type}}'`);\n  }}\n",
+            path.rsplit("::").next().unwrap_or("enum")
+        ));
+        body
+    } else if let Some(inner) = def["sequence"]["type"].as_u64() {
+        let inner = inner as u32;
+        if is_u8(registry, inner) {
+            "  const bytes = toBytes(value);\n  writeCompact(out, bytes.length);\n  out.push(...bytes);\n"
+                .to_string()
+        } else {
+            children.push(inner);
+            format!(
+                "  writeCompact(out, value.length);\n  for (const item of value) {{\n    encode{}(out, item);\n  }}\n",
+                inner
+            )
+        }
+    } else if let Some(inner) = def["array"]["type"].as_u64() {
+        let inner = inner as u32;
+        let len = def["array"]["len"].as_u64().unwrap_or(0);
+        if is_u8(registry, inner) {
+            format!("  writeFixedBytes(out, value, {});\n", len)
+        } else {
+            children.push(inner);
+            format!(
+                "  for (let i = 0; i < {}; i++) {{\n    encode{}(out, value[i]);\n  }}\n",
+                len, inner
+            )
+        }
+    } else if let Some(items) = def["tuple"].as_array() {
+        let mut body = String::new();
+        for (i, item) in items.iter().enumerate() {
+            let item = item.as_u64().context("Invalid type ID")? as u32;
+            children.push(item);
+            body.push_str(&format!("  encode{}(out, value[{}]);\n", item, i));
+        }
+        if body.is_empty() {
+            body.push_str("  void value;\n");
+        }
+        body
+    } else if def.get("compact").is_some() {
+        "  writeCompact(out, value);\n".to_string()
+    } else {
+        format!(
+            "  throw new TypeError('Encoding type {} is not supported');\n",
+            type_id
+        )
+    };
+
+    encoders.insert(type_id, body);
+    for child in children {
+        collect(registry, child, encoders)?;
+    }
+    Ok(())
+}
+
+fn is_u8(registry: &HashMap<u32, JsonValue>, type_id: u32) -> bool {
+    registry
+        .get(&type_id)
+        .is_some_and(|ty| ty["def"]["primitive"] == "u8")
+}
+
+/// `[u8; 32]`, directly or wrapped in a single-field struct
+fn is_byte_array(registry: &HashMap<u32, JsonValue>, def: &JsonValue) -> bool {
+    let inner = match def["composite"]["fields"].as_array() {
+        Some(fields) if fields.len() == 1 => fields[0]["type"]
+            .as_u64()
+            .and_then(|id| registry.get(&(id as u32)))
+            .map(|ty| &ty["def"]),
+        _ => Some(def),
+    };
+    inner.is_some_and(|def| {
+        def["array"]["len"] == 32
+            && def["array"]["type"]
+                .as_u64()
+                .is_some_and(|id| is_u8(registry, id as u32))
+    })
+}
+
+/// `0x84a15da1` as `0x84, 0xa1, 0x5d, 0xa1`
+fn selector_bytes(selector: &str) -> Result<String> {
+    let bytes = hex::decode(selector.trim_start_matches("0x"))
+        .with_context(|| format!("Invalid selector {}", selector))?;
+    Ok(bytes
+        .iter()
+        .map(|b| format!("0x{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(", "))
+}
+
+const SCALE_HELPERS: &str = r#"function writeUint(out: number[], value: bigint | string | number, bytes: number): void {
+  let n = BigInt(value);
+  if (n < 0n || n >= 1n << BigInt(bytes * 8)) {
+    throw new RangeError(`${value} does not fit in u${bytes * 8}`);
+  }
+  for (let i = 0; i < bytes; i++) {
+    out.push(Number(n & 0xffn));
+    n >>= 8n;
+  }
+}
+
+function writeInt(out: number[], value: bigint | string | number, bytes: number): void {
+  const n = BigInt(value);
+  const half = 1n << BigInt(bytes * 8 - 1);
+  if (n < -half || n >= half) {
+    throw new RangeError(`${value} does not fit in i${bytes * 8}`);
+  }
+  writeUint(out, n < 0n ? n + 2n * half : n, bytes);
+}
+
+function writeCompact(out: number[], value: bigint | string | number): void {
+  const n = BigInt(value);
+  if (n < 0n) {
+    throw new RangeError(`Compact ${value} is negative`);
+  }
+  if (n < 1n << 6n) {
+    out.push(Number(n << 2n));
+  } else if (n < 1n << 14n) {
+    writeUint(out, (n << 2n) | 1n, 2);
+  } else if (n < 1n << 30n) {
+    writeUint(out, (n << 2n) | 2n, 4);
+  } else {
+    const bytes: number[] = [];
+    for (let m = n; m > 0n; m >>= 8n) {
+      bytes.push(Number(m & 0xffn));
+    }
+    out.push(((bytes.length - 4) << 2) | 3, ...bytes);
+  }
+}
+
+function writeStr(out: number[], value: string): void {
+  const bytes = new TextEncoder().encode(value);
+  writeCompact(out, bytes.length);
+  out.push(...bytes);
+}
+
+/** `Uint8Array`, `0x` hex or, for other strings, their UTF-8 bytes */
+function toBytes(value: Uint8Array | string): number[] {
+  if (typeof value !== 'string') {
+    return Array.from(value);
+  }
+  if (/^0x([0-9a-fA-F]{2})*$/.test(value)) {
+    return (value.slice(2).match(/../g) ?? []).map((byte) => parseInt(byte, 16));
+  }
+  return Array.from(new TextEncoder().encode(value));
+}
+
+/** Exactly `len` bytes; 32-byte strings may also be SS58 addresses */
+function writeFixedBytes(out: number[], value: Uint8Array | string, len: number): void {
+  const bytes =
+    typeof value === 'string' && !value.startsWith('0x') && len === 32
+      ? ss58Decode(value)
+      : toBytes(value);
+  if (bytes.length !== len) {
+    throw new RangeError(`Expected ${len} bytes, got ${bytes.length}`);
+  }
+  out.push(...bytes);
+}
+
+const BASE58 = '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';
+
+/** Account id of an SS58 address (prefix, 32 bytes, 2 checksum bytes) */
+function ss58Decode(address: string): number[] {
+  let n = 0n;
+  for (const c of address) {
+    const digit = BASE58.indexOf(c);
+    if (digit < 0) {
+      throw new TypeError(`Invalid SS58 address: '${address}'`);
+    }
+    n = n * 58n + BigInt(digit);
+  }
+  const bytes: number[] = [];
+  for (; n > 0n; n >>= 8n) {
+    bytes.unshift(Number(n & 0xffn));
+  }
+  for (const c of address) {
+    if (c !== '1') break;
+    bytes.unshift(0);
+  }
+  const prefix = bytes.length - 34;
+  if (prefix !== 1 && prefix !== 2) {
+    throw new TypeError(`Invalid SS58 address: '${address}'`);
+  }
+  return bytes.slice(prefix, prefix + 32);
+}
+
+function toHex(bytes: number[]): string {
+  return '0x' + bytes.map((byte) => byte.toString(16).padStart(2, '0')).join('');
+}
+
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_call_data() {
+        let types = serde_json::json!([
+            { "id": 0, "type": { "path": ["ink_primitives", "types", "AccountId"], "def": { "composite": { "fields": [{ "type": 1 }] } } } },
+            { "id": 1, "type": { "def": { "array": { "len": 32, "type": 2 } } } },
+            { "id": 2, "type": { "def": { "primitive": "u8" } } },
+            { "id": 3, "type": { "def": { "primitive": "u128" } } },
+            { "id": 4, "type": { "path": ["Option"], "params": [{ "type": 5 }], "def": { "variant": { "variants": [{ "name": "None", "index": 0 }, { "name": "Some", "index": 1, "fields": [{ "type": 5 }] }] } } } },
+            { "id": 5, "type": { "def": { "sequence": { "type": 2 } } } }
+        ]);
+        let spec = serde_json::json!({
+            "messages": [
+                {
+                    "label": "transfer",
+                    "selector": "0x84a15da1",
+                    "args": [
+                        { "label": "to", "type": { "type": 0 } },
+                        { "label": "value", "type": { "type": 3 } },
+                        { "label": "data", "type": { "type": 4 } }
+                    ]
+                },
+                { "label": "PSP22::total_supply", "selector": "0x162df8c2", "args": [] }
+            ]
+        });
+
+        let mut resolver = TypeResolver::new(&types).unwrap();
+        let output = generate_call_data("Token", &types, &spec, &mut resolver).unwrap();
+        assert!(
+            output.contains("  transfer: '0x84a15da1',\n  'PSP22::total_supply': '0x162df8c2',\n")
+        );
+        assert!(output.contains("export function encodeTransfer(to: string, value: string | number | bigint, data: Uint8Array | string | null): string {"));
+        assert!(output.contains("  const out: number[] = [0x84, 0xa1, 0x5d, 0xa1];"));
+        assert!(output.contains("export function encodePsp22TotalSupply(): string {"));
+        assert!(output.contains("function encode0(out: number[], value: any): void {\n  writeFixedBytes(out, value, 32);\n}"));
+        assert!(output.contains(
+            "function encode3(out: number[], value: any): void {\n  writeUint(out, value, 16);\n}"
+        ));
+        assert!(output.contains("    out.push(1);\n    encode5(out, value);"));
+        assert!(output.contains("  writeCompact(out, bytes.length);"));
+    }
+}
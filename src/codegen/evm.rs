@@ -0,0 +1,508 @@
+// Solidity ABI approximation of a contract's metadata
+//
+// Maps messages, the default constructor, events and the variants of error
+// enums to Solidity ABI JSON items so EVM explorers, indexers and UI
+// generators can read ink! contracts. Integers keep their width, `AccountId`
+// and hashes become `bytes32`, byte vectors `bytes`, structs and tuples
+// `tuple` and field-less enums `uint8`. Items with types Solidity can't
+// express (`Option`, enums carrying data, maps) are skipped and reported.
+// Selectors are not Solidity's keccak selectors; the ink! selector is kept as
+// `selector` on each function.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Solidity ABI items and the messages or events that could not be mapped
+#[derive(Debug, Clone)]
+pub struct EvmAbi {
+    pub items: Vec<JsonValue>,
+    /// `label: reason` of every skipped item
+    pub skipped: Vec<String>,
+}
+
+/// Map contract metadata to a Solidity ABI
+pub fn generate_evm_abi(metadata: &JsonValue) -> Result<EvmAbi> {
+    let (types, spec) = match metadata.get("V3") {
+        Some(v3) => (&v3["types"], &v3["spec"]),
+        None => (
+            metadata.get("types").context("Types section not found")?,
+            &metadata["spec"],
+        ),
+    };
+    let registry = Registry::new(types)?;
+    let mut abi = EvmAbi {
+        items: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    // Solidity has a single constructor
+    let constructors = spec["constructors"].as_array().cloned().unwrap_or_default();
+    let default = constructors
+        .iter()
+        .find(|c| c["default"].as_bool().unwrap_or(false))
+        .or_else(|| constructors.first());
+    if let Some(ctor) = default {
+        match registry.params(&ctor["args"]) {
+            Ok(inputs) => abi.items.push(json!({
+                "type": "constructor",
+                "inputs": inputs,
+                "stateMutability": if ctor["payable"].as_bool().unwrap_or(false) { "payable" } else { "nonpayable" },
+            })),
+            Err(reason) => abi.skipped.push(format!("constructor {}: {}", label(ctor), reason)),
+        }
+    }
+
+    let mut errors: Vec<String> = Vec::new();
+    for msg in spec["messages"]
+        .as_array()
+        .context("Messages section missing")?
+    {
+        let outputs = match msg["returnType"]["type"].as_u64() {
+            Some(type_id) => {
+                let (ok, err) = registry.split_returns(type_id as u32);
+                if let Some(err) = err {
+                    match registry.errors(err) {
+                        Ok(items) => {
+                            for item in items {
+                                let name = item["name"].as_str().unwrap_or_default().to_string();
+                                if !errors.contains(&name) {
+                                    errors.push(name);
+                                    abi.items.push(item);
+                                }
+                            }
+                        }
+                        Err(reason) => {
+                            abi.skipped
+                                .push(format!("errors of {}: {}", label(msg), reason))
+                        }
+                    }
+                }
+                match ok {
+                    Some(ok) => registry.outputs(ok),
+                    None => Ok(Vec::new()),
+                }
+            }
+            None => Ok(Vec::new()),
+        };
+        let inputs = registry.params(&msg["args"]);
+
+        match (inputs, outputs) {
+            (Ok(inputs), Ok(outputs)) => {
+                let mutability = if msg["payable"].as_bool().unwrap_or(false) {
+                    "payable"
+                } else if msg["mutates"].as_bool().unwrap_or(false) {
+                    "nonpayable"
+                } else {
+                    "view"
+                };
+                let mut item = json!({
+                    "type": "function",
+                    "name": function_name(label(msg)),
+                    "inputs": inputs,
+                    "outputs": outputs,
+                    "stateMutability": mutability,
+                });
+                if let Some(selector) = msg["selector"].as_str() {
+                    item["selector"] = json!(selector);
+                }
+                abi.items.push(item);
+            }
+            (Err(reason), _) | (_, Err(reason)) => {
+                abi.skipped.push(format!("{}: {}", label(msg), reason))
+            }
+        }
+    }
+
+    for event in spec["events"].as_array().into_iter().flatten() {
+        let mut inputs = Vec::new();
+        let mut failed = None;
+        for arg in event["args"].as_array().into_iter().flatten() {
+            match registry.param(arg["label"].as_str().unwrap_or(""), type_ref(&arg["type"])) {
+                Ok(mut param) => {
+                    param["indexed"] = json!(arg["indexed"].as_bool().unwrap_or(false));
+                    inputs.push(param);
+                }
+                Err(reason) => {
+                    failed = Some(reason);
+                    break;
+                }
+            }
+        }
+        match failed {
+            None => abi.items.push(json!({
+                "type": "event",
+                "name": label(event),
+                "inputs": inputs,
+                "anonymous": false,
+            })),
+            Some(reason) => abi
+                .skipped
+                .push(format!("event {}: {}", label(event), reason)),
+        }
+    }
+
+    Ok(abi)
+}
+
+/// File name of the ABI, e.g. `MyToken.evm-abi.json`
+pub fn evm_abi_file(contract_name: &str) -> String {
+    format!("{}.evm-abi.json", contract_name)
+}
+
+/// Reason a type has no Solidity equivalent
+type Unsupported = String;
+
+struct Registry {
+    types: HashMap<u32, JsonValue>,
+}
+
+impl Registry {
+    fn new(types_array: &JsonValue) -> Result<Self> {
+        let mut types = HashMap::new();
+        for entry in types_array
+            .as_array()
+            .context("Types section must be an array")?
+        {
+            let id = entry["id"].as_u64().context("Type entry missing id")? as u32;
+            types.insert(id, entry["type"].clone());
+        }
+        Ok(Self { types })
+    }
+
+    fn get(&self, type_id: u32) -> std::result::Result<&JsonValue, Unsupported> {
+        self.types
+            .get(&type_id)
+            .ok_or_else(|| format!("type {} missing from the registry", type_id))
+    }
+
+    fn params(&self, args: &JsonValue) -> std::result::Result<Vec<JsonValue>, Unsupported> {
+        args.as_array()
+            .into_iter()
+            .flatten()
+            .map(|arg| self.param(arg["label"].as_str().unwrap_or(""), type_ref(&arg["type"])))
+            .collect()
+    }
+
+    /// Outputs of a return type; tuples are spread into several outputs
+    fn outputs(&self, type_id: u32) -> std::result::Result<Vec<JsonValue>, Unsupported> {
+        let def = self.get(type_id)?;
+        if let Some(tuple) = def["def"]["tuple"].as_array() {
+            return tuple
+                .iter()
+                .map(|id| self.param("", id.as_u64().unwrap_or(0) as u32))
+                .collect();
+        }
+        Ok(vec![self.param("", type_id)?])
+    }
+
+    /// `(T, E)` of a message returning `Result<T, E>` after removing ink!'s
+    /// `Result<_, LangError>` wrapper; `T` is `None` for `()`
+    fn split_returns(&self, type_id: u32) -> (Option<u32>, Option<u32>) {
+        let mut returns = type_id;
+        if let Some((ok, err)) = self.result_params(returns) {
+            if self.last_segment(err).as_deref() == Some("LangError") {
+                returns = ok;
+            }
+        }
+        let (ok, err) = match self.result_params(returns) {
+            Some((ok, err)) => (ok, Some(err)),
+            None => (returns, None),
+        };
+        let unit = self
+            .types
+            .get(&ok)
+            .and_then(|def| def["def"]["tuple"].as_array())
+            .map(|t| t.is_empty())
+            .unwrap_or(false);
+        ((!unit).then_some(ok), err)
+    }
+
+    fn result_params(&self, type_id: u32) -> Option<(u32, u32)> {
+        let def = self.types.get(&type_id)?;
+        if path(def) != ["Result"] {
+            return None;
+        }
+        let ok = def["params"].get(0)?["type"].as_u64()? as u32;
+        let err = def["params"].get(1)?["type"].as_u64()? as u32;
+        Some((ok, err))
+    }
+
+    fn last_segment(&self, type_id: u32) -> Option<String> {
+        self.types.get(&type_id).and_then(|def| path(def).pop())
+    }
+
+    /// Solidity `error` items for the variants of an error enum
+    fn errors(&self, type_id: u32) -> std::result::Result<Vec<JsonValue>, Unsupported> {
+        let def = self.get(type_id)?;
+        let Some(variants) = def["def"]["variant"]["variants"].as_array() else {
+            return Ok(vec![json!({
+                "type": "error",
+                "name": self.last_segment(type_id).unwrap_or_else(|| "Error".to_string()),
+                "inputs": [self.param("", type_id)?],
+            })]);
+        };
+        variants
+            .iter()
+            .map(|variant| {
+                let inputs = variant["fields"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|f| self.param(f["name"].as_str().unwrap_or(""), type_ref(f)))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(json!({
+                    "type": "error",
+                    "name": variant["name"].as_str().unwrap_or("Error"),
+                    "inputs": inputs,
+                }))
+            })
+            .collect()
+    }
+
+    /// ABI parameter `{ name, type, components? }`
+    fn param(&self, name: &str, type_id: u32) -> std::result::Result<JsonValue, Unsupported> {
+        let (ty, components) = self.solidity_type(type_id)?;
+        let mut param = json!({ "name": name, "type": ty });
+        if let Some(components) = components {
+            param["components"] = json!(components);
+        }
+        if let (Some(internal), Some(suffix)) =
+            (self.last_segment(type_id), ty.strip_prefix("tuple"))
+        {
+            param["internalType"] = json!(format!("struct {}{}", internal, suffix));
+        }
+        Ok(param)
+    }
+
+    /// Solidity type of a registry entry, with tuple components
+    fn solidity_type(
+        &self,
+        type_id: u32,
+    ) -> std::result::Result<(String, Option<Vec<JsonValue>>), Unsupported> {
+        let def = self.get(type_id)?;
+        let path = path(def);
+        let last = path.last().map(String::as_str).unwrap_or("");
+        let body = &def["def"];
+
+        match path.join("::").as_str() {
+            "Option" => return Err("Option has no Solidity equivalent".to_string()),
+            "Result" => return Err("nested Result has no Solidity equivalent".to_string()),
+            "BTreeMap" | "BTreeSet" => return Err(format!("{} has no Solidity equivalent", last)),
+            _ if body.get("composite").is_some() && (last == "AccountId" || last == "Hash") => {
+                return Ok(("bytes32".to_string(), None))
+            }
+            _ if body.get("composite").is_some() && last == "H160" => {
+                return Ok(("address".to_string(), None))
+            }
+            _ => {}
+        }
+
+        if let Some(primitive) = body["primitive"].as_str() {
+            let ty = match primitive {
+                "bool" => "bool".to_string(),
+                "str" => "string".to_string(),
+                "char" => "uint32".to_string(),
+                p if p.starts_with('u') => format!("uint{}", &p[1..]),
+                p if p.starts_with('i') => format!("int{}", &p[1..]),
+                p => return Err(format!("primitive {} has no Solidity equivalent", p)),
+            };
+            return Ok((ty, None));
+        }
+        if let Some(composite) = body.get("composite") {
+            let fields = composite["fields"].as_array().cloned().unwrap_or_default();
+            // Newtypes map to their field
+            if fields.len() == 1 && fields[0]["name"].is_null() {
+                return self.solidity_type(type_ref(&fields[0]));
+            }
+            let components = fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| {
+                    let name = f["name"]
+                        .as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("_{}", i));
+                    self.param(&name, type_ref(f))
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            return Ok(("tuple".to_string(), Some(components)));
+        }
+        if let Some(variant) = body.get("variant") {
+            let variants = variant["variants"].as_array().cloned().unwrap_or_default();
+            if variants
+                .iter()
+                .all(|v| v["fields"].as_array().map(|f| f.is_empty()).unwrap_or(true))
+            {
+                return Ok(("uint8".to_string(), None));
+            }
+            return Err(format!("enum {} carries data", last));
+        }
+        if let Some(sequence) = body.get("sequence") {
+            let inner = type_ref(sequence);
+            if self.is_u8(inner) {
+                return Ok(("bytes".to_string(), None));
+            }
+            let (ty, components) = self.solidity_type(inner)?;
+            return Ok((format!("{}[]", ty), components));
+        }
+        if let Some(array) = body.get("array") {
+            let inner = type_ref(array);
+            let len = array["len"].as_u64().unwrap_or(0);
+            if self.is_u8(inner) && (1..=32).contains(&len) {
+                return Ok((format!("bytes{}", len), None));
+            }
+            let (ty, components) = self.solidity_type(inner)?;
+            return Ok((format!("{}[{}]", ty, len), components));
+        }
+        if let Some(tuple) = body["tuple"].as_array() {
+            if tuple.is_empty() {
+                return Err("() can only be a return type".to_string());
+            }
+            let components = tuple
+                .iter()
+                .enumerate()
+                .map(|(i, id)| self.param(&format!("_{}", i), id.as_u64().unwrap_or(0) as u32))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            return Ok(("tuple".to_string(), Some(components)));
+        }
+        if let Some(compact) = body.get("compact") {
+            return self.solidity_type(type_ref(compact));
+        }
+        Err(format!("type {} has no Solidity equivalent", type_id))
+    }
+
+    fn is_u8(&self, type_id: u32) -> bool {
+        self.types
+            .get(&type_id)
+            .map(|def| def["def"]["primitive"] == "u8")
+            .unwrap_or(false)
+    }
+}
+
+/// Type id of a field, sequence or `{ "type": id }` argument type
+fn type_ref(value: &JsonValue) -> u32 {
+    value["type"].as_u64().unwrap_or(0) as u32
+}
+
+fn path(def: &JsonValue) -> Vec<String> {
+    def["path"]
+        .as_array()
+        .map(|p| {
+            p.iter()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn label(item: &JsonValue) -> &str {
+    item["label"].as_str().unwrap_or("unnamed")
+}
+
+/// Solidity identifiers can't contain `::` (trait messages)
+fn function_name(label: &str) -> String {
+    label.replace("::", "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_evm_abi() {
+        let metadata = json!({
+            "spec": {
+                "constructors": [{
+                    "label": "new",
+                    "default": true,
+                    "payable": false,
+                    "args": [{ "label": "supply", "type": { "type": 0 } }]
+                }],
+                "messages": [
+                    {
+                        "label": "PSP22::transfer",
+                        "selector": "0xdb20f9f5",
+                        "mutates": true,
+                        "payable": false,
+                        "args": [
+                            { "label": "to", "type": { "type": 1 } },
+                            { "label": "data", "type": { "type": 4 } }
+                        ],
+                        "returnType": { "type": 6 }
+                    },
+                    {
+                        "label": "owner",
+                        "selector": "0x4fa43c8c",
+                        "mutates": false,
+                        "args": [],
+                        "returnType": { "type": 10 }
+                    }
+                ],
+                "events": [{
+                    "label": "Transfer",
+                    "args": [
+                        { "label": "to", "indexed": true, "type": { "type": 1 } },
+                        { "label": "value", "indexed": false, "type": { "type": 0 } }
+                    ]
+                }]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } },
+                { "id": 1, "type": { "path": ["ink_primitives", "types", "AccountId"], "def": { "composite": { "fields": [{ "type": 2 }] } } } },
+                { "id": 2, "type": { "def": { "array": { "len": 32, "type": 3 } } } },
+                { "id": 3, "type": { "def": { "primitive": "u8" } } },
+                { "id": 4, "type": { "def": { "sequence": { "type": 3 } } } },
+                { "id": 5, "type": { "def": { "tuple": [] } } },
+                { "id": 6, "type": { "path": ["Result"], "params": [{ "type": 7 }, { "type": 9 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 7, "type": { "path": ["Result"], "params": [{ "type": 5 }, { "type": 8 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 8, "type": { "path": ["psp22", "PSP22Error"], "def": { "variant": { "variants": [
+                    { "name": "InsufficientBalance" },
+                    { "name": "Custom", "fields": [{ "type": 11 }] }
+                ] } } } },
+                { "id": 9, "type": { "path": ["ink_primitives", "LangError"], "def": { "variant": { "variants": [{ "name": "CouldNotReadInput" }] } } } },
+                { "id": 10, "type": { "path": ["Option"], "params": [{ "type": 1 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 11, "type": { "def": { "primitive": "str" } } }
+            ]
+        });
+
+        let abi = generate_evm_abi(&metadata).unwrap();
+        let kinds: Vec<(&str, &str)> = abi
+            .items
+            .iter()
+            .map(|i| {
+                (
+                    i["type"].as_str().unwrap(),
+                    i["name"].as_str().unwrap_or(""),
+                )
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("constructor", ""),
+                ("error", "InsufficientBalance"),
+                ("error", "Custom"),
+                ("function", "PSP22_transfer"),
+                ("event", "Transfer"),
+            ]
+        );
+
+        let ctor = &abi.items[0];
+        assert_eq!(ctor["inputs"][0]["type"], "uint128");
+        assert_eq!(abi.items[2]["inputs"][0]["type"], "string");
+
+        let transfer = &abi.items[3];
+        assert_eq!(transfer["inputs"][0]["type"], "bytes32");
+        assert_eq!(transfer["inputs"][1]["type"], "bytes");
+        assert_eq!(transfer["outputs"], json!([]));
+        assert_eq!(transfer["stateMutability"], "nonpayable");
+        assert_eq!(transfer["selector"], "0xdb20f9f5");
+
+        assert_eq!(abi.items[4]["inputs"][0]["indexed"], true);
+        assert_eq!(
+            abi.skipped,
+            vec!["owner: Option has no Solidity equivalent".to_string()]
+        );
+    }
+}
@@ -1,74 +1,314 @@
 // React hooks generation for ink! contracts
 
-use anyhow::Result;
+use std::collections::BTreeSet;
+
+use anyhow::{Context, Result};
 use serde_json::Value as JsonValue;
 
+use super::metadata::extract_events;
+use super::templates::template_data;
+use super::type_resolver::{collect_names, TypeResolver};
+use super::typescript::{member_access, ModuleOptions};
+
+/// Which data-fetching layer the generated hooks build on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HookStyle {
+    /// `@glin-ai/sdk-react` hooks with local state
+    #[default]
+    Basic,
+    /// TanStack Query: cached queries, event-driven invalidation, mutations
+    ReactQuery,
+}
+
 /// Generate React hooks for contract interaction
-pub fn generate_react_hooks(contract_name: &str, _abi: &JsonValue) -> Result<String> {
-    let hooks_output = format!(
-        r#"// Generated by glin-forge
-// Do not edit manually
+pub fn generate_react_hooks(
+    contract_name: &str,
+    abi: &JsonValue,
+    style: HookStyle,
+    options: ModuleOptions,
+) -> Result<String> {
+    match style {
+        HookStyle::Basic => options
+            .templates()
+            .render("hooks", &template_data(contract_name, None)),
+        HookStyle::ReactQuery => generate_react_query_hooks(contract_name, abi, options),
+    }
+}
+
+/// TanStack Query hooks over the typed contract interface of the main module
+fn generate_react_query_hooks(
+    contract_name: &str,
+    abi: &JsonValue,
+    options: ModuleOptions,
+) -> Result<String> {
+    let (types, spec) = match abi.get("V3") {
+        Some(v3) => (&v3["types"], &v3["spec"]),
+        None => (
+            abi.get("types").context("Types section not found")?,
+            &abi["spec"],
+        ),
+    };
+    let mut resolver = options.type_resolver(types)?;
+    let messages = spec["messages"]
+        .as_array()
+        .context("Messages section missing")?;
+    let events = extract_events(&serde_json::json!({ "spec": spec }));
+    let name = contract_name;
 
-import {{ useContract, useContractQuery, useContractTx }} from '@glin-ai/sdk-react';
-import {{ type {}Contract }} from './{}';
-import metadata from './metadata.json';
+    let mut imports = BTreeSet::from([name.to_string()]);
+    let mut body = String::new();
 
-export function use{}(address: string, signer?: any) {{
-  const {{ contract, loading, error }} = useContract({{
-    address,
-    abi: metadata,
-    signer,
+    body.push_str("// Queries\n\n");
+    for msg in messages
+        .iter()
+        .filter(|m| !m["mutates"].as_bool().unwrap_or(false))
+    {
+        let label = msg["label"].as_str().unwrap_or("method");
+        let (params, names) = arguments(&mut resolver, msg, &mut imports)?;
+        let ret = match msg["returnType"]["type"].as_u64() {
+            Some(type_id) => {
+                let ts_type = resolver.resolve_type(type_id as u32)?;
+                collect_names(&ts_type, &mut imports);
+                resolver.format_type(&ts_type)
+            }
+            None => "void".to_string(),
+        };
+        let params = params
+            .iter()
+            .map(|p| format!("{}, ", p))
+            .collect::<String>();
+        body.push_str(&format!(
+            r#"export function use{name}{hook}(
+  contract: {name} | undefined,
+  {params}options?: QueryOptions<{ret}>,
+) {{
+  return useQuery<{ret}>({{
+    queryKey: {lower}Keys.query(contract?.address ?? '', '{label}', [{names}]),
+    queryFn: () => contract!.query{access}({names}),
+    enabled: contract !== undefined,
+    ...options,
   }});
+}}
 
-  return {{ contract: contract as {}Contract, loading, error }};
+"#,
+            name = name,
+            hook = pascal_case(label),
+            lower = lower_first(name),
+            access = member_access(label),
+            names = names.join(", "),
+        ));
+    }
+
+    body.push_str("// Transactions\n\n");
+    for msg in messages
+        .iter()
+        .filter(|m| m["mutates"].as_bool().unwrap_or(false))
+    {
+        let label = msg["label"].as_str().unwrap_or("method");
+        let (params, names) = arguments(&mut resolver, msg, &mut imports)?;
+        body.push_str(&format!(
+            r#"export function use{name}{hook}Mutation(
+  contract: {name} | undefined,
+  optimisticUpdate?: OptimisticUpdate<[{params}]>,
+  options?: Omit<UseMutationOptions<Transaction, Error, [{params}], unknown>, 'mutationFn'>,
+) {{
+  return useTransaction<[{params}]>(
+    contract,
+    '{label}',
+    (contract, [{names}]) => contract.tx{access}({names}),
+    optimisticUpdate,
+    options,
+  );
 }}
 
-export function use{}Query(address: string) {{
-  const contract = use{}(address);
+"#,
+            name = name,
+            hook = pascal_case(label),
+            access = member_access(label),
+            params = params.join(", "),
+            names = names.join(", "),
+        ));
+    }
+
+    if !events.is_empty() {
+        body.push_str(&format!(
+            r#"// Event-driven invalidation
 
-  // Helper hook for read-only queries
-  const query = async (method: string, ...args: any[]) => {{
-    if (!contract.contract) throw new Error('Contract not loaded');
-    return contract.contract.query[method](...args);
-  }};
+/**
+ * Invalidate cached queries when the contract emits an event. Queries without
+ * arguments are always refreshed; queries with arguments only when one of them
+ * appears in the event, e.g. a balance query for the `to` of a transfer.
+ */
+export function use{name}EventInvalidation(address: string | undefined, source: {name}EventSource | undefined) {{
+  const queryClient = useQueryClient();
 
-  return {{ ...contract, query }};
+  useEffect(() => {{
+    if (!address || !source) return;
+    const stop = create{name}EventSubscriptions(source).subscribeAll((event) => {{
+      const values = Object.values(event.args as Record<string, unknown>).map(String);
+      queryClient.invalidateQueries({{
+        queryKey: {lower}Keys.all(address),
+        predicate: (query) => {{
+          const args = query.queryKey.slice(3);
+          return args.length === 0 || args.some((arg) => values.includes(String(arg)));
+        }},
+      }});
+    }});
+    return () => {{
+      Promise.resolve(stop).then((unsubscribe) => unsubscribe());
+    }};
+  }}, [address, source, queryClient]);
 }}
+"#,
+            name = name,
+            lower = lower_first(name),
+        ));
+    }
+
+    let mut output = format!(
+        r#"// Generated by glin-forge
+// Do not edit manually
 
-export function use{}Tx(address: string, signer: any) {{
-  const contract = use{}(address, signer);
+import {{
+  useMutation,
+  useQuery,
+  useQueryClient,
+  type QueryClient,
+  type UseMutationOptions,
+  type UseQueryOptions,
+}} from '@tanstack/react-query';
+import type {{ Transaction }} from '@glin-forge/sdk';
+import type {{ {imports} }} from './{name}';
+{branded_import}{event_imports}
+/** Query key prefix of every query of a contract instance */
+export const {lower}Keys = {{
+  all: (address: string) => ['{name}', address] as const,
+  query: (address: string, method: string, args: unknown[]) =>
+    ['{name}', address, method, ...args] as const,
+}};
 
-  // Helper hook for transactions
-  const tx = async (method: string, ...args: any[]) => {{
-    if (!contract.contract) throw new Error('Contract not loaded');
-    if (!signer) throw new Error('Signer required for transactions');
-    return contract.contract.tx[method](...args);
-  }};
+type QueryOptions<T> = Omit<UseQueryOptions<T>, 'queryKey' | 'queryFn'>;
 
-  return {{ ...contract, tx }};
+/** Optimistic update applied before a transaction is submitted; rolled back if it fails */
+export type OptimisticUpdate<V> = (queryClient: QueryClient, variables: V, address: string) => void;
+
+function useTransaction<V extends unknown[]>(
+  contract: {name} | undefined,
+  method: string,
+  send: (contract: {name}, variables: V) => Promise<Transaction>,
+  optimisticUpdate?: OptimisticUpdate<V>,
+  options?: Omit<UseMutationOptions<Transaction, Error, V, unknown>, 'mutationFn'>,
+) {{
+  const queryClient = useQueryClient();
+
+  return useMutation<Transaction, Error, V, {{ snapshot: [readonly unknown[], unknown][] }}>({{
+    mutationKey: ['{name}', contract?.address, method],
+    mutationFn: (variables) => {{
+      if (!contract) throw new Error('Contract not loaded');
+      return send(contract, variables);
+    }},
+    onMutate: async (variables) => {{
+      if (!contract || !optimisticUpdate) return {{ snapshot: [] }};
+      const key = {lower}Keys.all(contract.address);
+      await queryClient.cancelQueries({{ queryKey: key }});
+      const snapshot = queryClient.getQueriesData({{ queryKey: key }});
+      optimisticUpdate(queryClient, variables, contract.address);
+      return {{ snapshot }};
+    }},
+    onError: (_error, _variables, context) => {{
+      context?.snapshot.forEach(([key, data]) => queryClient.setQueryData(key, data));
+    }},
+    onSettled: () => {{
+      if (contract) queryClient.invalidateQueries({{ queryKey: {lower}Keys.all(contract.address) }});
+    }},
+    ...options,
+  }} as UseMutationOptions<Transaction, Error, V, {{ snapshot: [readonly unknown[], unknown][] }}>);
 }}
+
 "#,
-        contract_name,
-        contract_name,
-        contract_name,
-        contract_name,
-        contract_name,
-        contract_name,
-        contract_name,
-        contract_name
+        name = name,
+        lower = lower_first(name),
+        imports = imports.into_iter().collect::<Vec<_>>().join(", "),
+        branded_import = options.branded_import(name),
+        event_imports = if events.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "import {{ useEffect }} from 'react';\nimport {{ create{n}EventSubscriptions, type {n}EventSource }} from './{n}';\n",
+                n = name
+            )
+        },
     );
 
-    Ok(hooks_output)
+    output.push_str(&body);
+
+    Ok(output)
+}
+
+/// Typed parameters and argument names of a message; the types they name are
+/// added to `imports`
+fn arguments(
+    resolver: &mut TypeResolver,
+    msg: &JsonValue,
+    imports: &mut BTreeSet<String>,
+) -> Result<(Vec<String>, Vec<String>)> {
+    let mut params = Vec::new();
+    let mut names = Vec::new();
+    for arg in msg["args"].as_array().into_iter().flatten() {
+        let arg_name = arg["label"].as_str().unwrap_or("arg");
+        let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+        let arg_type = resolver.resolve_type(type_id)?;
+        collect_names(&arg_type, imports);
+        params.push(format!(
+            "{}: {}",
+            arg_name,
+            resolver.format_input_type(&arg_type)
+        ));
+        names.push(arg_name.to_string());
+    }
+    Ok((params, names))
+}
+
+/// `balance_of` -> `BalanceOf`, `PSP22::balance_of` -> `Psp22BalanceOf`
+pub(super) fn pascal_case(label: &str) -> String {
+    label
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            let rest: String = if part.chars().all(|c| !c.is_ascii_lowercase()) {
+                chars.as_str().to_ascii_lowercase()
+            } else {
+                chars.as_str().to_string()
+            };
+            first.into_iter().collect::<String>() + rest.as_str()
+        })
+        .collect()
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::codegen::typescript::generate_typescript_module;
 
     #[test]
     fn test_generate_react_hooks() {
         let abi = serde_json::json!({});
-        let result = generate_react_hooks("MyContract", &abi);
+        let result = generate_react_hooks(
+            "MyContract",
+            &abi,
+            HookStyle::Basic,
+            ModuleOptions::default(),
+        );
         assert!(result.is_ok());
 
         let hooks = result.unwrap();
@@ -76,4 +316,94 @@ mod tests {
         assert!(hooks.contains("useMyContractQuery"));
         assert!(hooks.contains("useMyContractTx"));
     }
+
+    #[test]
+    fn test_generate_react_query_hooks() {
+        let abi = serde_json::json!({
+            "spec": {
+                "messages": [
+                    {
+                        "label": "balance_of",
+                        "mutates": false,
+                        "args": [{ "label": "owner", "type": { "type": 0 } }],
+                        "returnType": { "type": 1 }
+                    },
+                    {
+                        "label": "transfer",
+                        "mutates": true,
+                        "args": [
+                            { "label": "to", "type": { "type": 0 } },
+                            { "label": "value", "type": { "type": 1 } }
+                        ],
+                        "returnType": null
+                    }
+                ],
+                "events": [
+                    { "label": "Transfer", "args": [{ "label": "to", "indexed": true, "type": { "type": 0 } }] }
+                ]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "str" } } },
+                { "id": 1, "type": { "def": { "primitive": "bool" } } }
+            ]
+        });
+
+        let hooks = generate_react_hooks(
+            "Token",
+            &abi,
+            HookStyle::ReactQuery,
+            ModuleOptions::default(),
+        )
+        .unwrap();
+        assert!(hooks.contains("export function useTokenBalanceOf("));
+        assert!(hooks
+            .contains("queryKey: tokenKeys.query(contract?.address ?? '', 'balance_of', [owner])"));
+        assert!(hooks.contains("export function useTokenTransferMutation("));
+        assert!(hooks.contains("export function useTokenEventInvalidation("));
+        assert_eq!(pascal_case("PSP22::balance_of"), "Psp22BalanceOf");
+    }
+
+    #[test]
+    fn test_react_query_trait_message() {
+        let abi = serde_json::json!({
+            "spec": {
+                "constructors": [],
+                "messages": [
+                    {
+                        "label": "Erc20::total_supply",
+                        "mutates": false,
+                        "args": [],
+                        "returnType": { "type": 0 }
+                    },
+                    {
+                        "label": "Erc20::approve",
+                        "mutates": true,
+                        "args": [{ "label": "value", "type": { "type": 1 } }],
+                        "returnType": null
+                    }
+                ],
+                "events": []
+            },
+            "types": [
+                { "id": 0, "type": { "path": ["Result"], "params": [{ "type": 1 }, { "type": 2 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 1, "type": { "def": { "primitive": "bool" } } },
+                { "id": 2, "type": { "def": { "tuple": [] } } }
+            ]
+        });
+
+        let hooks = generate_react_hooks(
+            "Token",
+            &abi,
+            HookStyle::ReactQuery,
+            ModuleOptions::default(),
+        )
+        .unwrap();
+        assert!(hooks.contains("import type { Result, Token } from './Token';"));
+        assert!(hooks.contains("export function useTokenErc20TotalSupply("));
+        assert!(hooks.contains("queryFn: () => contract!.query['Erc20::total_supply'](),"));
+        assert!(hooks.contains("(contract, [value]) => contract.tx['Erc20::approve'](value),"));
+
+        let module = generate_typescript_module("Token", &abi, ModuleOptions::default()).unwrap();
+        assert!(module.contains("  'Erc20::total_supply'(): Promise<"));
+    }
 }
@@ -0,0 +1,203 @@
+// ink! trait definition generation from contract metadata
+//
+// Emits an `#[ink::trait_definition]` with every message of a contract, its
+// selector pinned to the one in the metadata, plus the argument and result
+// types, so other contracts can call a deployed contract without its source:
+//
+//     let mut token: ink::contract_ref!(Token) = address.into();
+//     token.transfer(to, value)?;
+//
+// ink! wraps every message result in `Result<_, LangError>` itself, so the
+// wrapper is removed from the declared return types.
+
+use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
+use std::collections::HashSet;
+
+use super::hooks::pascal_case;
+use super::rust::{doc_comment, field_name, Flavor, RustTypes};
+
+/// Generate an ink! trait definition for a contract's messages
+pub fn generate_ink_trait(contract_name: &str, metadata: &JsonValue) -> Result<String> {
+    let (types, spec) = match metadata.get("V3") {
+        Some(v3) => (&v3["types"], &v3["spec"]),
+        None => (
+            metadata.get("types").context("Types section not found")?,
+            &metadata["spec"],
+        ),
+    };
+    let mut registry = RustTypes::new(types, Flavor::Ink)?;
+    let messages = spec["messages"]
+        .as_array()
+        .context("Messages section missing")?;
+
+    // Trait messages (`PSP22::transfer`) keep their method name unless it clashes
+    let short_name = |label: &str| label.rsplit("::").next().unwrap_or(label).to_string();
+    let mut seen = HashSet::new();
+    let clashing: HashSet<String> = messages
+        .iter()
+        .filter_map(|m| m["label"].as_str())
+        .map(short_name)
+        .filter(|name| !seen.insert(name.clone()))
+        .collect();
+
+    let mut methods = String::new();
+    for msg in messages {
+        let label = msg["label"].as_str().unwrap_or("method");
+        let selector = msg["selector"]
+            .as_str()
+            .with_context(|| format!("{} has no selector", label))?;
+        let name = if clashing.contains(&short_name(label)) {
+            label.replace("::", "_").to_lowercase()
+        } else {
+            short_name(label)
+        };
+
+        let mut params = vec![if msg["mutates"].as_bool().unwrap_or(false) {
+            "&mut self".to_string()
+        } else {
+            "&self".to_string()
+        }];
+        for arg in msg["args"].as_array().into_iter().flatten() {
+            let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+            params.push(format!(
+                "{}: {}",
+                field_name(arg["label"].as_str().unwrap_or("arg")),
+                registry.rust_type(type_id)?
+            ));
+        }
+        let returns = match msg["returnType"]["type"].as_u64() {
+            Some(type_id) => match registry.return_type(type_id as u32)?.0.as_str() {
+                "()" => String::new(),
+                ty => format!(" -> {}", ty),
+            },
+            None => String::new(),
+        };
+
+        let mut attrs = vec!["message"];
+        if msg["payable"].as_bool().unwrap_or(false) {
+            attrs.push("payable");
+        }
+        let selector = format!("selector = {}", selector.to_uppercase().replace("0X", "0x"));
+        attrs.push(&selector);
+
+        methods.push_str(&doc_comment("    ", &msg["docs"]));
+        methods.push_str(&format!(
+            "    #[ink({})]\n    fn {}({}){};\n\n",
+            attrs.join(", "),
+            field_name(&name),
+            params.join(", "),
+            returns
+        ));
+    }
+
+    let mut trait_name = pascal_case(contract_name);
+    if registry.declarations.iter().any(|d| {
+        d.contains(&format!("pub struct {} ", trait_name))
+            || d.contains(&format!("pub struct {}(", trait_name))
+            || d.contains(&format!("pub enum {} ", trait_name))
+    }) {
+        trait_name.push_str("Trait");
+    }
+
+    Ok(format!(
+        r#"// Generated by glin-forge
+// Do not edit manually
+//
+// Interface of the {name} contract for cross-contract calls:
+//
+//     let mut contract: ink::contract_ref!({trait_name}) = address.into();
+
+#[allow(unused_imports)]
+use ink::prelude::{{string::String, vec::Vec}};
+#[allow(unused_imports)]
+use ink::primitives::{{AccountId, Hash}};
+
+{types}#[ink::trait_definition]
+pub trait {trait_name} {{
+{methods}}}
+"#,
+        name = contract_name,
+        types = registry.declarations.concat(),
+        methods = methods.trim_end_matches('\n').to_string() + "\n",
+    ))
+}
+
+/// File name of the trait module, e.g. `my_token_trait.rs`
+pub fn ink_trait_file(contract_name: &str) -> String {
+    format!("{}_trait.rs", super::rust::rust_module_name(contract_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_ink_trait() {
+        let metadata = serde_json::json!({
+            "spec": {
+                "constructors": [],
+                "messages": [
+                    {
+                        "label": "PSP22::transfer",
+                        "selector": "0xdb20f9f5",
+                        "mutates": true,
+                        "payable": false,
+                        "args": [
+                            { "label": "to", "type": { "type": 1 } },
+                            { "label": "value", "type": { "type": 0 } }
+                        ],
+                        "returnType": { "type": 3 },
+                        "docs": [" Moves tokens."]
+                    },
+                    {
+                        "label": "deposit",
+                        "selector": "0x2d10c9bd",
+                        "mutates": true,
+                        "payable": true,
+                        "args": [],
+                        "returnType": { "type": 7 },
+                        "docs": []
+                    },
+                    {
+                        "label": "balance_of",
+                        "selector": "0x0f755a56",
+                        "mutates": false,
+                        "args": [{ "label": "owner", "type": { "type": 1 } }],
+                        "returnType": { "type": 8 },
+                        "docs": []
+                    }
+                ]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } },
+                { "id": 1, "type": { "path": ["ink_primitives", "types", "AccountId"], "def": { "composite": { "fields": [{ "type": 2 }] } } } },
+                { "id": 2, "type": { "def": { "array": { "len": 32, "type": 9 } } } },
+                { "id": 3, "type": { "path": ["Result"], "params": [{ "type": 4 }, { "type": 6 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 4, "type": { "path": ["Result"], "params": [{ "type": 5 }, { "type": 10 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 5, "type": { "def": { "tuple": [] } } },
+                { "id": 6, "type": { "path": ["ink_primitives", "LangError"], "def": { "variant": { "variants": [{ "name": "CouldNotReadInput", "index": 1 }] } } } },
+                { "id": 7, "type": { "path": ["Result"], "params": [{ "type": 5 }, { "type": 6 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 8, "type": { "path": ["Result"], "params": [{ "type": 0 }, { "type": 6 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 9, "type": { "def": { "primitive": "u8" } } },
+                { "id": 10, "type": { "path": ["token", "PSP22Error"], "def": { "variant": { "variants": [{ "name": "InsufficientBalance", "index": 0 }] } } } }
+            ]
+        });
+
+        let source = generate_ink_trait("my_token", &metadata).unwrap();
+        assert!(source.contains("#[ink::trait_definition]\npub trait MyToken {"));
+        assert!(source.contains(
+            "    /// Moves tokens.\n    #[ink(message, selector = 0xDB20F9F5)]\n    fn transfer(&mut self, to: AccountId, value: u128) -> Result<(), PSP22Error>;"
+        ));
+        assert!(source.contains(
+            "#[ink(message, payable, selector = 0x2D10C9BD)]\n    fn deposit(&mut self);"
+        ));
+        assert!(source.contains("fn balance_of(&self, owner: AccountId) -> u128;"));
+        assert!(source.contains(
+            "#[cfg_attr(feature = \"std\", derive(scale_info::TypeInfo))]\npub enum PSP22Error {"
+        ));
+        // ink! adds the LangError wrapper itself
+        assert!(!source.contains("LangError"));
+        assert_eq!(ink_trait_file("MyToken"), "my_token_trait.rs");
+    }
+}
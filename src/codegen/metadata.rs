@@ -94,6 +94,49 @@ pub fn extract_constructors(abi: &JsonValue) -> Result<Vec<ConstructorInfo>> {
         .collect()
 }
 
+/// Extract event definitions from metadata; contracts without events yield an empty list
+pub fn extract_events(abi: &JsonValue) -> Vec<EventInfo> {
+    abi["spec"]["events"]
+        .as_array()
+        .map(|events| {
+            events
+                .iter()
+                .filter_map(|event| {
+                    let args = event["args"]
+                        .as_array()
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(|arg| {
+                                    Some(EventArgInfo {
+                                        label: arg["label"].as_str()?.to_string(),
+                                        indexed: arg["indexed"].as_bool().unwrap_or(false),
+                                        type_info: arg["type"].clone(),
+                                    })
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
+                    Some(EventInfo {
+                        label: event["label"].as_str()?.to_string(),
+                        // ink! 5 metadata; `null` for anonymous events
+                        signature_topic: event["signature_topic"].as_str().map(str::to_string),
+                        args,
+                        docs: event["docs"]
+                            .as_array()
+                            .map(|docs| {
+                                docs.iter()
+                                    .filter_map(|d| d.as_str().map(str::to_string))
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Information about a contract message (method)
 #[derive(Debug, Clone)]
 pub struct MessageInfo {
@@ -110,6 +153,25 @@ pub struct ConstructorInfo {
     pub args: Vec<ArgumentInfo>,
 }
 
+/// Information about an event
+#[derive(Debug, Clone)]
+pub struct EventInfo {
+    pub label: String,
+    /// First topic identifying the event, if the metadata provides it
+    pub signature_topic: Option<String>,
+    pub args: Vec<EventArgInfo>,
+    pub docs: Vec<String>,
+}
+
+/// Information about an event field
+#[derive(Debug, Clone)]
+pub struct EventArgInfo {
+    pub label: String,
+    /// Published as a topic, so subscribers can filter on it
+    pub indexed: bool,
+    pub type_info: JsonValue,
+}
+
 /// Information about a method/constructor argument
 #[derive(Debug, Clone)]
 pub struct ArgumentInfo {
@@ -165,6 +227,29 @@ mod tests {
                         ],
                         "returnType": null
                     }
+                ],
+                "events": [
+                    {
+                        "label": "Changed",
+                        "signature_topic": "0x1a2b",
+                        "args": [
+                            {
+                                "label": "by",
+                                "indexed": true,
+                                "type": {
+                                    "displayName": ["AccountId"]
+                                }
+                            },
+                            {
+                                "label": "value",
+                                "indexed": false,
+                                "type": {
+                                    "displayName": ["u32"]
+                                }
+                            }
+                        ],
+                        "docs": ["Value changed."]
+                    }
                 ]
             }
         })
@@ -190,9 +275,22 @@ mod tests {
         let messages = extract_messages(&abi).unwrap();
         assert_eq!(messages.len(), 2);
         assert_eq!(messages[0].label, "get");
-        assert_eq!(messages[0].mutates, false);
+        assert!(!messages[0].mutates);
         assert_eq!(messages[1].label, "set");
-        assert_eq!(messages[1].mutates, true);
+        assert!(messages[1].mutates);
+    }
+
+    #[test]
+    fn test_extract_events() {
+        let abi = sample_abi();
+        let events = extract_events(&abi);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].label, "Changed");
+        assert_eq!(events[0].signature_topic.as_deref(), Some("0x1a2b"));
+        assert!(events[0].args[0].indexed);
+        assert!(!events[0].args[1].indexed);
+
+        assert!(extract_events(&serde_json::json!({ "spec": {} })).is_empty());
     }
 
     #[test]
@@ -0,0 +1,345 @@
+// In-memory mock generation for frontend unit tests
+//
+// Emits a `Mock<Contract>` implementing the contract interface of the main
+// module without a node or the SDK runtime: queries answer from a state object
+// seeded with zero values, transactions are recorded and run configurable
+// handlers, and events are pushed to subscribers with `emit()`. The mock is an
+// event source, so `create<Contract>EventSubscriptions()` works on it as well.
+
+use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
+
+use super::type_resolver::TypeScriptType;
+use super::typescript::{property_key, ModuleOptions};
+
+/// Generate the mock contract module
+pub fn generate_mock_client(
+    contract_name: &str,
+    metadata: &JsonValue,
+    options: ModuleOptions,
+) -> Result<String> {
+    let (types, spec) = match metadata.get("V3") {
+        Some(v3) => (&v3["types"], &v3["spec"]),
+        None => (
+            metadata.get("types").context("Types section not found")?,
+            &metadata["spec"],
+        ),
+    };
+    let mut resolver = options.type_resolver(types)?;
+
+    let mut defaults = String::new();
+    let mut queries = String::new();
+    let mut transactions = String::new();
+    for msg in spec["messages"]
+        .as_array()
+        .context("Messages section missing")?
+    {
+        let label = msg["label"].as_str().unwrap_or("method");
+        if msg["mutates"].as_bool().unwrap_or(false) {
+            transactions.push_str(&format!(
+                "    {key}: (...args) => this.runTx('{label}', args),\n",
+                key = property_key(label)
+            ));
+        } else {
+            let zero = match msg["returnType"]["type"].as_u64() {
+                Some(type_id) => zero_value(&resolver.resolve_type(type_id as u32)?),
+                None => "undefined".to_string(),
+            };
+            defaults.push_str(&format!("  {}: {},\n", property_key(label), zero));
+            queries.push_str(&format!(
+                "    {key}: (...args) => this.runQuery('{label}', args),\n",
+                key = property_key(label)
+            ));
+        }
+    }
+
+    let example = spec["messages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|msg| !msg["mutates"].as_bool().unwrap_or(false))
+        .and_then(|msg| msg["label"].as_str())
+        .map(|label| format!(" * mock.setQuery('{}', ...);\n", label))
+        .unwrap_or_default();
+
+    Ok(format!(
+        r#"// Generated by glin-forge
+// Do not edit manually
+
+import type {{ ContractEvent, Transaction }} from '@glin-forge/sdk';
+import type {{ {name}, {name}Events, {name}Queries, {name}Transactions }} from './{name}';
+
+type QueryName = keyof {name}Queries;
+type TxName = keyof {name}Transactions;
+type QueryResult<K extends QueryName> = Awaited<ReturnType<{name}Queries[K]>>;
+
+/** A query result, or a function computing it from the call's arguments */
+export type {name}MockResponse<K extends QueryName> =
+  | QueryResult<K>
+  | ((...args: Parameters<{name}Queries[K]>) => QueryResult<K>);
+
+/** Outcome of a mocked transaction; transactions succeed by default */
+export interface {name}MockOutcome {{
+  success: boolean;
+  error?: string;
+}}
+
+/** A transaction sent to the mock */
+export interface {name}MockCall {{
+  method: TxName;
+  args: unknown[];
+}}
+
+/** Zero values of every query's return type */
+const defaults: {{ [K in QueryName]: QueryResult<K> }} = {{
+{defaults}}};
+
+/**
+ * In-memory {name} for unit tests
+ *
+ * @example
+ * const mock = new Mock{name}();
+{example} * mock.onTx(name, (...args) => mock.emit(event, {{ ... }}));
+ * render(<Component contract={{mock}} />);
+ */
+export class Mock{name} implements {name} {{
+  address: string;
+  metadata = '{{}}';
+  events = {{}} as {name}Events;
+
+  /** Query results, seeded with zero values */
+  state: {{ [K in QueryName]: {name}MockResponse<K> }} = {{ ...defaults }};
+
+  /** Transactions sent so far, oldest first */
+  calls: {name}MockCall[] = [];
+
+  private handlers: {{
+    [K in TxName]?: (...args: Parameters<{name}Transactions[K]>) => {name}MockOutcome | void;
+  }} = {{}};
+  private listeners = new Set<(event: ContractEvent) => void>();
+  private nonce = 0;
+
+  constructor(address = '5C4hrfjw9DjXZTzV3MwzrrAr9P1MJhSrvWGWqi1eSuyUpnhM') {{
+    this.address = address;
+  }}
+
+  query: {name}Queries = {{
+{queries}  }};
+
+  tx: {name}Transactions = {{
+{transactions}  }};
+
+  /** Set the result of a query */
+  setQuery<K extends QueryName>(name: K, response: {name}MockResponse<K>): this {{
+    this.state[name] = response;
+    return this;
+  }}
+
+  /** Run `handler` when a transaction is sent; it may update `state`, emit events or fail */
+  onTx<K extends TxName>(
+    name: K,
+    handler: (...args: Parameters<{name}Transactions[K]>) => {name}MockOutcome | void,
+  ): this {{
+    this.handlers[name] = handler;
+    return this;
+  }}
+
+  /** Deliver an event to every subscriber */
+  emit<K extends keyof {name}Events>(name: K, args: {name}Events[K]): void {{
+    const event = {{ name, args }} as unknown as ContractEvent;
+    this.listeners.forEach((listener) => listener(event));
+  }}
+
+  subscribe(handler: (event: ContractEvent) => void): () => void {{
+    this.listeners.add(handler);
+    return () => {{
+      this.listeners.delete(handler);
+    }};
+  }}
+
+  /** Restore zero values and forget calls, handlers and subscribers */
+  reset(): void {{
+    this.state = {{ ...defaults }};
+    this.calls = [];
+    this.handlers = {{}};
+    this.listeners.clear();
+  }}
+
+  private async runQuery<K extends QueryName>(name: K, args: unknown[]): Promise<QueryResult<K>> {{
+    const response = this.state[name];
+    return typeof response === 'function'
+      ? (response as (...args: unknown[]) => QueryResult<K>)(...args)
+      : response;
+  }}
+
+  private async runTx<K extends TxName>(name: K, args: unknown[]): Promise<Transaction> {{
+    this.calls.push({{ method: name, args }});
+    const handler = this.handlers[name] as
+      | ((...args: unknown[]) => {name}MockOutcome | void)
+      | undefined;
+    const outcome = handler?.(...args) ?? {{ success: true }};
+    this.nonce += 1;
+    return mockTransaction(`0x${{this.nonce.toString(16).padStart(64, '0')}}`, outcome);
+  }}
+}}
+
+/** A settled transaction that never touches the network */
+function mockTransaction(txHash: string, outcome: {name}MockOutcome): Transaction {{
+  const receipt = {{ txHash, blockHash: txHash, blockNumber: 1, success: outcome.success, events: [] }};
+  return {{
+    hash: () => txHash,
+    blockHash: () => txHash,
+    isSuccess: () => outcome.success,
+    error: () => outcome.error,
+    wait: async () => receipt,
+    waitWithProgress: async () => receipt,
+  }} as unknown as Transaction;
+}}
+"#,
+        name = contract_name,
+    ))
+}
+
+/// File name of the mock module, e.g. `MyToken.mock.ts`
+pub fn mock_file(contract_name: &str) -> String {
+    format!("{}.mock.ts", contract_name)
+}
+
+/// TypeScript literal of a type's zero value
+fn zero_value(ts_type: &TypeScriptType) -> String {
+    match ts_type {
+        TypeScriptType::Primitive(name) => match name.as_str() {
+            "boolean" => "false".to_string(),
+            "number" => "0".to_string(),
+            "bigint" => "0n".to_string(),
+            "string" => "''".to_string(),
+            "null" => "null".to_string(),
+            _ => "undefined".to_string(),
+        },
+        TypeScriptType::Reference(name) if name == "Uint8Array" => "new Uint8Array()".to_string(),
+        // Branded types; the mock need not validate
+        TypeScriptType::Reference(name) if name == "AccountId" => {
+            "'5C4hrfjw9DjXZTzV3MwzrrAr9P1MJhSrvWGWqi1eSuyUpnhM' as never".to_string()
+        }
+        TypeScriptType::Reference(name) if name == "Balance" => "0n as never".to_string(),
+        TypeScriptType::Reference(name) if name == "Hash" => {
+            format!("'0x{}' as never", "0".repeat(64))
+        }
+        // Recursive references have no finite zero value
+        TypeScriptType::Reference(_) => "undefined as never".to_string(),
+        TypeScriptType::Any => "undefined".to_string(),
+        TypeScriptType::Array(_) => "[]".to_string(),
+        TypeScriptType::Tuple(types) => format!(
+            "[{}]",
+            types.iter().map(zero_value).collect::<Vec<_>>().join(", ")
+        ),
+        TypeScriptType::Or(types) => {
+            // `string | number | bigint`
+            if types
+                .iter()
+                .any(|t| matches!(t, TypeScriptType::Primitive(p) if p == "bigint"))
+            {
+                return "0n".to_string();
+            }
+            types
+                .first()
+                .map(zero_value)
+                .unwrap_or_else(|| "undefined".to_string())
+        }
+        TypeScriptType::Optional(_) => "null".to_string(),
+        TypeScriptType::Interface { fields, .. } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(field, field_type)| format!("{}: {}", field, zero_value(field_type)))
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        // First variant, so `Ok` of results
+        TypeScriptType::Union { variants, .. } => match variants.first() {
+            Some(variant) => {
+                let mut fields = vec![format!("type: '{}'", variant.name)];
+                for (field, field_type) in &variant.fields {
+                    fields.push(format!(
+                        "{}: {}",
+                        field.as_deref().unwrap_or("value"),
+                        zero_value(field_type)
+                    ));
+                }
+                format!("{{ {} }}", fields.join(", "))
+            }
+            None => "undefined as never".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::type_resolver::UnionVariant;
+
+    #[test]
+    fn test_generate_mock_client() {
+        let metadata = serde_json::json!({
+            "spec": {
+                "messages": [
+                    { "label": "total_supply", "mutates": false, "args": [], "returnType": { "type": 0 } },
+                    { "label": "paused", "mutates": false, "args": [], "returnType": { "type": 1 } },
+                    { "label": "Ownable::owner", "mutates": false, "args": [], "returnType": { "type": 1 } },
+                    {
+                        "label": "transfer",
+                        "mutates": true,
+                        "args": [{ "label": "value", "type": { "type": 0 } }],
+                        "returnType": { "type": 2 }
+                    }
+                ]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } },
+                { "id": 1, "type": { "def": { "primitive": "bool" } } },
+                { "id": 2, "type": { "path": ["Result"], "params": [{ "type": 3 }, { "type": 4 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 3, "type": { "def": { "tuple": [] } } },
+                { "id": 4, "type": { "path": ["token", "Error"], "def": { "variant": { "variants": [{ "name": "InsufficientBalance" }] } } } }
+            ]
+        });
+
+        let mock = generate_mock_client("Token", &metadata, ModuleOptions::default()).unwrap();
+        assert!(mock.contains("export class MockToken implements Token {"));
+        assert!(mock.contains(
+            "import type { Token, TokenEvents, TokenQueries, TokenTransactions } from './Token';"
+        ));
+        assert!(
+            mock.contains("  total_supply: 0n,\n  paused: false,\n  'Ownable::owner': false,\n")
+        );
+        assert!(mock
+            .contains("    'Ownable::owner': (...args) => this.runQuery('Ownable::owner', args),"));
+        assert!(mock.contains("    transfer: (...args) => this.runTx('transfer', args),"));
+        assert!(mock.contains(" * mock.setQuery('total_supply', ...);"));
+        assert_eq!(mock_file("Token"), "Token.mock.ts");
+    }
+
+    #[test]
+    fn test_zero_value() {
+        let result = TypeScriptType::Union {
+            name: "Result".to_string(),
+            variants: vec![UnionVariant {
+                name: "Ok".to_string(),
+                fields: vec![(
+                    None,
+                    TypeScriptType::Optional(Box::new(TypeScriptType::Primitive(
+                        "string".to_string(),
+                    ))),
+                )],
+                docs: Vec::new(),
+            }],
+            docs: Vec::new(),
+        };
+        assert_eq!(zero_value(&result), "{ type: 'Ok', value: null }");
+        assert_eq!(
+            zero_value(&TypeScriptType::Tuple(vec![
+                TypeScriptType::Primitive("number".to_string()),
+                TypeScriptType::Array(Box::new(TypeScriptType::Any)),
+            ])),
+            "[0, []]"
+        );
+    }
+}
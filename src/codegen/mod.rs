@@ -1,17 +1,40 @@
 // Code generation module for TypeScript/JavaScript bindings
 
+pub mod angular;
+mod calldata;
+pub mod evm;
 pub mod hooks;
+pub mod ink_trait;
 pub mod metadata;
+pub mod mock;
+pub mod openrpc;
+pub mod package;
+pub mod registry;
+pub mod rust;
+pub mod scaffold;
+pub mod schema;
+pub mod templates;
 pub mod type_resolver;
 pub mod types;
 pub mod typescript;
 
 // Re-export main functions for convenience
-pub use hooks::generate_react_hooks;
+pub use angular::{angular_service_file, generate_angular_service};
+pub use evm::{evm_abi_file, generate_evm_abi, EvmAbi};
+pub use hooks::{generate_react_hooks, HookStyle};
+pub use ink_trait::{generate_ink_trait, ink_trait_file};
 pub use metadata::{
-    extract_constructors, extract_contract_name, extract_contract_version, extract_messages,
-    ArgumentInfo, ConstructorInfo, MessageInfo,
+    extract_constructors, extract_contract_name, extract_contract_version, extract_events,
+    extract_messages, ArgumentInfo, ConstructorInfo, EventArgInfo, EventInfo, MessageInfo,
 };
+pub use mock::{generate_mock_client, mock_file};
+pub use openrpc::{generate_openrpc, openrpc_file};
+pub use package::{esm_specifiers, format_package_json, generate_package_json};
+pub use registry::generate_index;
+pub use rust::{generate_rust_client, generate_rust_mod, rust_client_name, rust_module_name};
+pub use scaffold::{generate_react_scaffold, ScaffoldFile, SCAFFOLD_DIR};
+pub use schema::{generate_json_schema, schema_file};
+pub use templates::{Templates, DEFAULT_TEMPLATES_DIR};
 pub use type_resolver::{TypeResolver, TypeScriptType, UnionVariant};
 pub use types::generate_typescript_types;
-pub use typescript::generate_typescript_module;
+pub use typescript::{generate_typescript_module, ModuleOptions};
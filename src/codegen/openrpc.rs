@@ -0,0 +1,242 @@
+// OpenRPC document generation from contract metadata
+//
+// Describes a contract's callable surface as an OpenRPC 1.2.6 service: one
+// method per message with by-name params, the result schema and, for messages
+// returning `Result<T, E>`, the variants of `E` as JSON-RPC errors. ink!'s
+// outer `Result<_, LangError>` is unwrapped. Schemas are the ones of
+// `typegen --schema`; selectors and mutability are kept as `x-` extensions.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value as JsonValue};
+
+use super::schema::type_schema;
+use super::type_resolver::{TypeResolver, TypeScriptType, UnionVariant};
+
+const OPENRPC_VERSION: &str = "1.2.6";
+
+/// First JSON-RPC error code of contract errors; variant `i` gets `-32000 - i`
+const ERROR_CODE_BASE: i64 = -32000;
+
+/// Generate the OpenRPC document of a contract
+pub fn generate_openrpc(contract_name: &str, metadata: &JsonValue) -> Result<JsonValue> {
+    let (types, spec) = match metadata.get("V3") {
+        Some(v3) => (&v3["types"], &v3["spec"]),
+        None => (
+            metadata.get("types").context("Types section not found")?,
+            &metadata["spec"],
+        ),
+    };
+    let mut resolver = TypeResolver::new(types)?;
+    let mut schemas = Map::new();
+
+    let mut methods = Vec::new();
+    for msg in spec["messages"]
+        .as_array()
+        .context("Messages section missing")?
+    {
+        let label = msg["label"].as_str().unwrap_or("method");
+
+        let mut params = Vec::new();
+        for arg in msg["args"].as_array().into_iter().flatten() {
+            let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+            params.push(json!({
+                "name": arg["label"].as_str().unwrap_or("arg"),
+                "required": true,
+                "schema": type_schema(&resolver.resolve_type(type_id)?),
+            }));
+        }
+
+        let returns = match msg["returnType"]["type"].as_u64() {
+            Some(type_id) => unwrap_lang_error(resolver.resolve_type(type_id as u32)?),
+            None => TypeScriptType::Primitive("void".to_string()),
+        };
+        let (ok, errors) = match split_result(&returns) {
+            Some((ok, err)) => {
+                let errors = match err {
+                    TypeScriptType::Union { name, variants, .. } => {
+                        schemas.insert(name.clone(), type_schema(err));
+                        errors(variants)
+                    }
+                    _ => vec![json!({
+                        "code": ERROR_CODE_BASE,
+                        "message": "Err",
+                        "data": type_schema(err),
+                    })],
+                };
+                (ok.clone(), errors)
+            }
+            None => (returns, Vec::new()),
+        };
+
+        let mut method = json!({
+            "name": label,
+            "paramStructure": "by-name",
+            "params": params,
+            "result": {
+                "name": format!("{}Result", label.replace("::", "_")),
+                "schema": type_schema(&ok),
+            },
+            "x-mutates": msg["mutates"].as_bool().unwrap_or(false),
+            "x-payable": msg["payable"].as_bool().unwrap_or(false),
+        });
+        if let Some(selector) = msg["selector"].as_str() {
+            method["x-selector"] = json!(selector);
+        }
+        let docs = docs(&msg["docs"]);
+        if !docs.is_empty() {
+            method["summary"] = json!(docs.lines().next().unwrap_or_default());
+            method["description"] = json!(docs);
+        }
+        if !errors.is_empty() {
+            method["errors"] = json!(errors);
+        }
+        methods.push(method);
+    }
+
+    let mut info = json!({
+        "title": format!("{} contract", contract_name),
+        "version": metadata["contract"]["version"].as_str().unwrap_or("0.0.0"),
+    });
+    let description = docs(&metadata["contract"]["docs"]);
+    if !description.is_empty() {
+        info["description"] = json!(description);
+    }
+
+    Ok(json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": info,
+        "methods": methods,
+        "components": { "schemas": schemas },
+    }))
+}
+
+/// File name of the OpenRPC document, e.g. `MyToken.openrpc.json`
+pub fn openrpc_file(contract_name: &str) -> String {
+    format!("{}.openrpc.json", contract_name)
+}
+
+/// `T` of ink!'s `Result<T, LangError>` message return wrapper
+fn unwrap_lang_error(returns: TypeScriptType) -> TypeScriptType {
+    if let Some((ok, TypeScriptType::Union { name, .. })) = split_result(&returns) {
+        if name == "LangError" {
+            return ok.clone();
+        }
+    }
+    returns
+}
+
+/// `(T, E)` of a `Result<T, E>`
+pub(super) fn split_result(ts_type: &TypeScriptType) -> Option<(&TypeScriptType, &TypeScriptType)> {
+    let TypeScriptType::Union { name, variants, .. } = ts_type else {
+        return None;
+    };
+    if name != "Result" || variants.len() != 2 {
+        return None;
+    }
+    let ok = &variants[0].fields.first()?.1;
+    let err = &variants[1].fields.first()?.1;
+    Some((ok, err))
+}
+
+/// JSON-RPC error objects for the variants of a contract error enum
+fn errors(variants: &[UnionVariant]) -> Vec<JsonValue> {
+    variants
+        .iter()
+        .enumerate()
+        .map(|(i, variant)| {
+            let mut error = json!({
+                "code": ERROR_CODE_BASE - i as i64,
+                "message": variant.name,
+            });
+            if let Some((_, field_type)) = variant.fields.first() {
+                error["data"] = type_schema(field_type);
+            }
+            error
+        })
+        .collect()
+}
+
+fn docs(docs: &JsonValue) -> String {
+    docs.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|d| d.as_str())
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_openrpc() {
+        let metadata = json!({
+            "contract": { "name": "token", "version": "1.2.0" },
+            "spec": {
+                "messages": [
+                    {
+                        "label": "transfer",
+                        "selector": "0x84a15da1",
+                        "mutates": true,
+                        "payable": false,
+                        "args": [{ "label": "value", "type": { "type": 0 } }],
+                        "returnType": { "type": 1 },
+                        "docs": [" Moves tokens.", " Fails without balance."]
+                    },
+                    {
+                        "label": "total_supply",
+                        "selector": "0xdb6375a8",
+                        "mutates": false,
+                        "args": [],
+                        "returnType": { "type": 5 },
+                        "docs": []
+                    }
+                ]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } },
+                { "id": 1, "type": { "path": ["Result"], "params": [{ "type": 2 }, { "type": 4 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 2, "type": { "path": ["Result"], "params": [{ "type": 6 }, { "type": 3 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 3, "type": { "path": ["token", "Error"], "def": { "variant": { "variants": [
+                    { "name": "InsufficientBalance" },
+                    { "name": "Custom", "fields": [{ "type": 7 }] }
+                ] } } } },
+                { "id": 4, "type": { "path": ["ink_primitives", "LangError"], "def": { "variant": { "variants": [{ "name": "CouldNotReadInput" }] } } } },
+                { "id": 5, "type": { "path": ["Result"], "params": [{ "type": 0 }, { "type": 4 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 6, "type": { "def": { "tuple": [] } } },
+                { "id": 7, "type": { "def": { "primitive": "str" } } }
+            ]
+        });
+
+        let doc = generate_openrpc("Token", &metadata).unwrap();
+        assert_eq!(doc["openrpc"], "1.2.6");
+        assert_eq!(doc["info"]["version"], "1.2.0");
+
+        let transfer = &doc["methods"][0];
+        assert_eq!(transfer["name"], "transfer");
+        assert_eq!(transfer["summary"], "Moves tokens.");
+        assert_eq!(transfer["params"][0]["name"], "value");
+        assert_eq!(transfer["result"]["schema"], json!({ "type": "null" }));
+        assert_eq!(transfer["x-mutates"], true);
+        assert_eq!(
+            transfer["errors"],
+            json!([
+                { "code": -32000, "message": "InsufficientBalance" },
+                { "code": -32001, "message": "Custom", "data": { "type": "string" } }
+            ])
+        );
+        assert!(doc["components"]["schemas"]["Error"]["oneOf"].is_array());
+
+        // Only the LangError wrapper is removed
+        let supply = &doc["methods"][1];
+        assert_eq!(
+            supply["result"]["schema"]["type"],
+            json!(["integer", "string"])
+        );
+        assert!(supply.get("errors").is_none());
+    }
+}
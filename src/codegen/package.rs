@@ -0,0 +1,133 @@
+// package.json for compiled bindings
+//
+// With `typegen --module both` the generated sources are compiled twice, to
+// `dist/esm` and `dist/cjs`. The manifest points bundlers and Node at the
+// matching build through conditional exports, with declarations next to each.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::{json, Value as JsonValue};
+
+/// Manifest exposing the dual ESM/CJS build, `entry` being the main module (`index`, `MyToken`)
+///
+/// Written by hand rather than through `serde_json`, which sorts keys: export
+/// conditions are matched in order and `types` has to come first.
+pub fn generate_package_json(name: &str, entry: &str) -> String {
+    let condition = |format: &str, module: &str| {
+        format!(
+            "{{\n        \"types\": \"./dist/{f}/{m}.d.ts\",\n        \"default\": \"./dist/{f}/{m}.js\"\n      }}",
+            f = format,
+            m = module
+        )
+    };
+
+    format!(
+        r#"{{
+  "name": "{name}-types",
+  "private": true,
+  "sideEffects": false,
+  "main": "./dist/cjs/{entry}.js",
+  "module": "./dist/esm/{entry}.js",
+  "types": "./dist/esm/{entry}.d.ts",
+  "exports": {{
+    ".": {{
+      "import": {esm_entry},
+      "require": {cjs_entry}
+    }},
+    "./*": {{
+      "import": {esm_all},
+      "require": {cjs_all}
+    }},
+    "./package.json": "./package.json"
+  }}
+}}
+"#,
+        name = kebab_case(name),
+        esm_entry = condition("esm", entry),
+        cjs_entry = condition("cjs", entry),
+        esm_all = condition("esm", "*"),
+        cjs_all = condition("cjs", "*"),
+    )
+}
+
+/// Marks the module format of the `.js` files below a `dist/<format>` directory
+pub fn format_package_json(format: &str) -> JsonValue {
+    json!({ "type": if format == "esm" { "module" } else { "commonjs" } })
+}
+
+/// Add `.js` to the relative import specifiers of a compiled ES module, which
+/// Node resolves only with the extension written out
+pub fn esm_specifiers(source: &str) -> String {
+    static SPECIFIER: OnceLock<Regex> = OnceLock::new();
+    let specifier = SPECIFIER.get_or_init(|| {
+        Regex::new(r#"(\bfrom\s*|\bimport\s*\(\s*)(['"])(\.\.?/[^'"]*)['"]"#)
+            .expect("valid specifier pattern")
+    });
+    specifier
+        .replace_all(source, |captures: &regex::Captures| {
+            let path = &captures[3];
+            let extension = if path.ends_with(".js") || path.ends_with(".json") {
+                ""
+            } else {
+                ".js"
+            };
+            format!(
+                "{}{}{}{}{}",
+                &captures[1], &captures[2], path, extension, &captures[2]
+            )
+        })
+        .into_owned()
+}
+
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 && !kebab.ends_with('-') {
+            kebab.push('-');
+        }
+        if c == '_' {
+            kebab.push('-');
+        } else {
+            kebab.extend(c.to_lowercase());
+        }
+    }
+    kebab
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_package_json() {
+        let manifest = generate_package_json("MyToken", "MyToken");
+        let package: JsonValue = serde_json::from_str(&manifest).unwrap();
+        assert_eq!(package["name"], "my-token-types");
+        assert_eq!(package["main"], "./dist/cjs/MyToken.js");
+        assert_eq!(
+            package["exports"]["."]["import"],
+            json!({ "types": "./dist/esm/MyToken.d.ts", "default": "./dist/esm/MyToken.js" })
+        );
+        assert_eq!(
+            package["exports"]["./*"]["require"]["types"],
+            "./dist/cjs/*.d.ts"
+        );
+        // Conditions are matched in order
+        assert!(manifest.contains("\"types\": \"./dist/cjs/*.d.ts\",\n        \"default\""));
+        assert_eq!(format_package_json("cjs"), json!({ "type": "commonjs" }));
+    }
+
+    #[test]
+    fn test_esm_specifiers() {
+        let source = "export * from './Token';\nimport { contracts } from \"./index.js\";\n\
+                      import type { Transaction } from '@glin-forge/sdk';\n\
+                      const mock = await import('../mocks/Token.mock');\n";
+        assert_eq!(
+            esm_specifiers(source),
+            "export * from './Token.js';\nimport { contracts } from \"./index.js\";\n\
+             import type { Transaction } from '@glin-forge/sdk';\n\
+             const mock = await import('../mocks/Token.mock.js');\n"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+// Barrel index for workspace-wide typegen
+//
+// `typegen --all` writes one module per contract; the index re-exports each
+// module as a namespace (type names such as `TransferEvent` may repeat across
+// contracts) along with its prefixed top-level types, and a `contracts`
+// registry keyed by contract name for looking up deploy factories by name.
+
+/// Generate `index.ts` for modules named after `contract_names`
+pub fn generate_index(contract_names: &[String]) -> String {
+    let mut output = String::from(
+        "/**\n * Contract bindings of this workspace\n *\n * Generated by glin-forge typegen --all\n * DO NOT EDIT MANUALLY\n */\n\n",
+    );
+
+    for name in contract_names {
+        output.push_str(&format!(
+            "import {{ {n}CodeHash, {n}ConstructorSelectors, deploy{n} }} from './{n}';\n",
+            n = name
+        ));
+        output.push_str(&format!(
+            "import type {{ {n}, {n}ConstructorArgs }} from './{n}';\n",
+            n = name
+        ));
+    }
+    output.push('\n');
+
+    for name in contract_names {
+        output.push_str(&format!(
+            "export type {{ {n}, {n}Queries, {n}Transactions, {n}Events, {n}Constructors, {n}ConstructorArgs, {n}DeployOptions }} from './{n}';\n",
+            n = name
+        ));
+        output.push_str(&format!("export * as {}Module from './{}';\n", name, name));
+    }
+    output.push('\n');
+
+    output.push_str("/** Contract interface by name */\n");
+    output.push_str("export interface Contracts {\n");
+    for name in contract_names {
+        output.push_str(&format!("  {}: {};\n", name, name));
+    }
+    output.push_str("}\n\n");
+
+    output.push_str("/** Constructor arguments by contract name */\n");
+    output.push_str("export interface ContractConstructorArgs {\n");
+    for name in contract_names {
+        output.push_str(&format!("  {}: {}ConstructorArgs;\n", name, name));
+    }
+    output.push_str("}\n\n");
+
+    output.push_str("export type ContractName = keyof Contracts;\n\n");
+
+    output
+        .push_str("/** Code hash, constructor selectors and deploy factory of every contract */\n");
+    output.push_str("export const contracts = {\n");
+    for name in contract_names {
+        output.push_str(&format!(
+            "  {n}: {{\n    name: '{n}',\n    codeHash: {n}CodeHash,\n    constructors: {n}ConstructorSelectors,\n    deploy: deploy{n},\n  }},\n",
+            n = name
+        ));
+    }
+    output.push_str("} as const;\n");
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_index() {
+        let index = generate_index(&["Flipper".to_string(), "Token".to_string()]);
+        assert!(index.contains("export * as FlipperModule from './Flipper';"));
+        assert!(index.contains("  Token: Token;\n"));
+        assert!(index.contains("    deploy: deployToken,\n"));
+        assert!(index.contains("export type ContractName = keyof Contracts;"));
+    }
+}
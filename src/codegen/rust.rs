@@ -0,0 +1,1052 @@
+// Rust client generation from contract metadata
+//
+// Emits a self-contained module per contract for integration tests and
+// backend services: SCALE-encodable types mirroring the metadata registry,
+// selector constants, event structs with a decoder and a `<Contract>Client`
+// wrapping a subxt `OnlineClient`. Queries dry-run through `ContractsApi_call`,
+// transactions dry-run first for the gas limit and are then submitted with
+// `Contracts::call`. The module only depends on `subxt` and `subxt-signer`;
+// the codec is taken from `subxt::ext::codec`.
+
+use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
+
+use super::hooks::pascal_case;
+use super::metadata::extract_events;
+
+/// Generate the Rust client module of a contract
+pub fn generate_rust_client(contract_name: &str, metadata: &JsonValue) -> Result<String> {
+    let (types, spec, source) = match metadata.get("V3") {
+        Some(v3) => (&v3["types"], &v3["spec"], &metadata["source"]),
+        None => (
+            metadata.get("types").context("Types section not found")?,
+            &metadata["spec"],
+            &metadata["source"],
+        ),
+    };
+    // Type names are CamelCase even for `my_token` style contract names
+    let contract_name = &pascal_case(contract_name);
+    let mut registry = RustTypes::new(types, Flavor::Client)?;
+    let messages = spec["messages"]
+        .as_array()
+        .context("Messages section missing")?;
+    let constructors = spec["constructors"].as_array().cloned().unwrap_or_default();
+
+    let client = rust_client_name(contract_name);
+    let error = format!("{}Error", contract_name);
+
+    // Selectors
+    let mut selectors = String::from("pub mod selectors {\n    pub mod constructors {\n");
+    for ctor in &constructors {
+        selectors.push_str(&selector_const(ctor)?);
+    }
+    selectors.push_str("    }\n\n    pub mod messages {\n");
+    for msg in messages {
+        selectors.push_str(&selector_const(msg)?);
+    }
+    selectors.push_str("    }\n}\n");
+
+    // Constructors
+    let mut ctor_methods = String::new();
+    for ctor in &constructors {
+        let label = ctor["label"].as_str().unwrap_or("new");
+        let (params, encode) = registry.arguments(ctor)?;
+        ctor_methods.push_str(&doc_comment("    ", &ctor["docs"]));
+        ctor_methods.push_str(&format!(
+            r#"    pub async fn instantiate_{name}(
+        api: OnlineClient<PolkadotConfig>,
+        signer: &Keypair,
+        code_hash: H256,
+{params}        transferred_value: u128,
+        salt: Vec<u8>,
+    ) -> Result<Self, {error}> {{
+        let mut input = selectors::constructors::{konst}.to_vec();
+{encode}        let address = instantiate(&api, signer, code_hash, input, transferred_value, salt).await?;
+        Ok(Self::new(api, address))
+    }}
+
+"#,
+            name = method_name(label),
+            konst = const_name(label),
+            params = params
+                .iter()
+                .map(|p| format!("        {},\n", p))
+                .collect::<String>(),
+        ));
+    }
+
+    // Messages
+    let mut queries = String::new();
+    let mut transactions = String::new();
+    for msg in messages {
+        let label = msg["label"].as_str().unwrap_or("method");
+        let (params, encode) = registry.arguments(msg)?;
+        let docs = doc_comment("    ", &msg["docs"]);
+        let name = method_name(label);
+        let konst = const_name(label);
+
+        if msg["mutates"].as_bool().unwrap_or(false) {
+            // Declares the result types, e.g. to decode `Reverted` data
+            if let Some(type_id) = msg["returnType"]["type"].as_u64() {
+                registry.return_type(type_id as u32)?;
+            }
+            let payable = msg["payable"].as_bool().unwrap_or(false);
+            let mut signature = vec!["signer: &Keypair".to_string()];
+            signature.extend(params);
+            if payable {
+                signature.push("transferred_value: u128".to_string());
+            }
+            transactions.push_str(&docs);
+            transactions.push_str(&format!(
+                r#"    pub async fn {name}(&self, {signature}) -> Result<ExtrinsicEvents<PolkadotConfig>, {error}> {{
+        let mut input = selectors::messages::{konst}.to_vec();
+{encode}        self.submit(signer, input, {value}).await
+    }}
+
+"#,
+                signature = signature.join(", "),
+                value = if payable { "transferred_value" } else { "0" },
+            ));
+        } else {
+            let (ret, lang_error) = match msg["returnType"]["type"].as_u64() {
+                Some(type_id) => registry.return_type(type_id as u32)?,
+                None => ("()".to_string(), None),
+            };
+            let decode = if let Some(lang_error) = lang_error {
+                let lang = registry.rust_type(lang_error)?;
+                format!(
+                    "Result::<{ret}, {lang}>::decode(&mut &data[..])?\n            .map_err(|e| {error}::Lang(format!(\"{{:?}}\", e)))"
+                )
+            } else {
+                format!("Ok(<{ret}>::decode(&mut &data[..])?)")
+            };
+            queries.push_str(&docs);
+            queries.push_str(&format!(
+                r#"    pub async fn {name}(&self{params}) -> Result<{ret}, {error}> {{
+        let mut input = selectors::messages::{konst}.to_vec();
+{encode}        let data = self.query(input).await?;
+        {decode}
+    }}
+
+"#,
+                params = params
+                    .iter()
+                    .map(|p| format!(", {}", p))
+                    .collect::<String>(),
+            ));
+        }
+    }
+
+    let events = generate_events(contract_name, &mut registry, metadata, &error)?;
+
+    let code_hash = match source["hash"].as_str().map(bytes_literal).transpose()? {
+        Some(bytes) => format!(
+            "    /// Code hash of the metadata this client was generated from\n    pub const CODE_HASH: H256 = H256({});\n\n",
+            bytes
+        ),
+        None => String::new(),
+    };
+
+    Ok(format!(
+        r#"// Generated by glin-forge
+// Do not edit manually
+//
+// Typed client for the {name} contract. Requires `subxt` and `subxt-signer`.
+
+#![allow(dead_code, unused_mut, clippy::all)]
+
+use subxt::blocks::ExtrinsicEvents;
+use subxt::ext::codec::{{self, Compact, Decode, Encode}};
+use subxt::utils::{{AccountId32, H256}};
+use subxt::{{OnlineClient, PolkadotConfig}};
+use subxt_signer::sr25519::Keypair;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+{types}// ============================================================================
+// Selectors
+// ============================================================================
+
+{selectors}
+// ============================================================================
+// Events
+// ============================================================================
+
+{events}
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[derive(Debug)]
+pub enum {error} {{
+    /// RPC or transaction failure
+    Subxt(subxt::Error),
+    /// Return data or event payload did not decode
+    Codec(codec::Error),
+    /// The contract reverted; `data` holds its encoded return value
+    Reverted {{ data: Vec<u8>, debug_message: String }},
+    /// The runtime rejected the call (contract missing, out of gas, ...)
+    Dispatch {{ debug_message: String }},
+    /// The contract did not accept the message (`ink::LangError`)
+    Lang(String),
+    /// No `Contracts::Instantiated` event in the instantiation
+    NotInstantiated,
+    /// Event of another contract version
+    UnknownEvent,
+}}
+
+impl std::fmt::Display for {error} {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        match self {{
+            Self::Subxt(e) => write!(f, "{{}}", e),
+            Self::Codec(e) => write!(f, "decoding failed: {{}}", e),
+            Self::Reverted {{ debug_message, .. }} => write!(f, "contract reverted {{}}", debug_message),
+            Self::Dispatch {{ debug_message }} => write!(f, "contract call failed {{}}", debug_message),
+            Self::Lang(e) => write!(f, "message rejected: {{}}", e),
+            Self::NotInstantiated => write!(f, "contract was not instantiated"),
+            Self::UnknownEvent => write!(f, "unknown {name} event"),
+        }}
+    }}
+}}
+
+impl std::error::Error for {error} {{}}
+
+impl From<subxt::Error> for {error} {{
+    fn from(e: subxt::Error) -> Self {{
+        Self::Subxt(e)
+    }}
+}}
+
+impl From<codec::Error> for {error} {{
+    fn from(e: codec::Error) -> Self {{
+        Self::Codec(e)
+    }}
+}}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// Two-dimensional weight (refTime, proofSize)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Encode, Decode)]
+#[codec(crate = subxt::ext::codec)]
+pub struct Weight {{
+    #[codec(compact)]
+    pub ref_time: u64,
+    #[codec(compact)]
+    pub proof_size: u64,
+}}
+
+/// Result of dry-running a message
+#[derive(Debug, Clone)]
+pub struct DryRun {{
+    pub gas_required: Weight,
+    /// Positive values are charged, negative refunded
+    pub storage_deposit: i128,
+    pub debug_message: String,
+    /// Encoded return value
+    pub data: Vec<u8>,
+}}
+
+/// Typed client for a deployed {name} contract
+#[derive(Clone)]
+pub struct {client} {{
+    api: OnlineClient<PolkadotConfig>,
+    address: AccountId32,
+    origin: AccountId32,
+}}
+
+impl {client} {{
+{code_hash}    pub fn new(api: OnlineClient<PolkadotConfig>, address: AccountId32) -> Self {{
+        Self {{
+            api,
+            address,
+            origin: AccountId32([0u8; 32]),
+        }}
+    }}
+
+    /// Account queries are made from (defaults to the zero account)
+    pub fn with_origin(mut self, origin: AccountId32) -> Self {{
+        self.origin = origin;
+        self
+    }}
+
+    pub fn address(&self) -> &AccountId32 {{
+        &self.address
+    }}
+
+    // Constructors
+
+{ctor_methods}    // Queries
+
+{queries}    // Transactions
+
+{transactions}    /// Events this contract emitted in a finalized extrinsic
+    pub fn events(&self, events: &ExtrinsicEvents<PolkadotConfig>) -> Result<Vec<{name}Event>, {error}> {{
+        let mut decoded = Vec::new();
+        for event in events.iter() {{
+            let event = event?;
+            if event.pallet_name() != "Contracts" || event.variant_name() != "ContractEmitted" {{
+                continue;
+            }}
+            let (contract, data) = <(AccountId32, Vec<u8>)>::decode(&mut event.field_bytes())?;
+            if contract == self.address {{
+                decoded.push({name}Event::decode(event.topics(), &data)?);
+            }}
+        }}
+        Ok(decoded)
+    }}
+
+    /// Dry-run a message from `origin`
+    pub async fn dry_run(&self, origin: &AccountId32, input: Vec<u8>, transferred_value: u128) -> Result<DryRun, {error}> {{
+        let params = (
+            origin,
+            &self.address,
+            transferred_value,
+            None::<Weight>,
+            None::<Compact<u128>>,
+            input,
+        )
+            .encode();
+        let bytes = self
+            .api
+            .runtime_api()
+            .at_latest()
+            .await?
+            .call_raw("ContractsApi_call", Some(&params))
+            .await?;
+        decode_exec_result(&bytes)
+    }}
+
+    async fn query(&self, input: Vec<u8>) -> Result<Vec<u8>, {error}> {{
+        Ok(self.dry_run(&self.origin, input, 0).await?.data)
+    }}
+
+    async fn submit(&self, signer: &Keypair, input: Vec<u8>, transferred_value: u128) -> Result<ExtrinsicEvents<PolkadotConfig>, {error}> {{
+        let origin = signer.public_key().to_account_id();
+        let dry_run = self.dry_run(&origin, input.clone(), transferred_value).await?;
+
+        let tx = subxt::dynamic::tx(
+            "Contracts",
+            "call",
+            vec![
+                subxt::dynamic::Value::unnamed_composite(vec![subxt::dynamic::Value::from_bytes(
+                    self.address.0,
+                )]),
+                subxt::dynamic::Value::u128(transferred_value),
+                subxt::dynamic::Value::unnamed_composite(vec![
+                    subxt::dynamic::Value::u128(dry_run.gas_required.ref_time as u128),
+                    subxt::dynamic::Value::u128(dry_run.gas_required.proof_size as u128),
+                ]),
+                subxt::dynamic::Value::unnamed_variant("None", vec![]),
+                subxt::dynamic::Value::from_bytes(&input),
+            ],
+        );
+        Ok(self
+            .api
+            .tx()
+            .sign_and_submit_then_watch_default(&tx, signer)
+            .await?
+            .wait_for_finalized_success()
+            .await?)
+    }}
+}}
+
+async fn instantiate(
+    api: &OnlineClient<PolkadotConfig>,
+    signer: &Keypair,
+    code_hash: H256,
+    input: Vec<u8>,
+    transferred_value: u128,
+    salt: Vec<u8>,
+) -> Result<AccountId32, {error}> {{
+    let tx = subxt::dynamic::tx(
+        "Contracts",
+        "instantiate",
+        vec![
+            subxt::dynamic::Value::u128(transferred_value),
+            subxt::dynamic::Value::unnamed_composite(vec![
+                subxt::dynamic::Value::u128(5_000_000_000),
+                subxt::dynamic::Value::u128(2_000_000),
+            ]),
+            subxt::dynamic::Value::unnamed_variant("None", vec![]),
+            subxt::dynamic::Value::from_bytes(code_hash.0),
+            subxt::dynamic::Value::from_bytes(&input),
+            subxt::dynamic::Value::from_bytes(salt),
+        ],
+    );
+    let events = api
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await?
+        .wait_for_finalized_success()
+        .await?;
+
+    for event in events.iter() {{
+        let event = event?;
+        if event.pallet_name() == "Contracts" && event.variant_name() == "Instantiated" {{
+            let (_deployer, contract) = <(AccountId32, AccountId32)>::decode(&mut event.field_bytes())?;
+            return Ok(contract);
+        }}
+    }}
+    Err({error}::NotInstantiated)
+}}
+
+/// Decode `ContractExecResult` as returned by `ContractsApi_call`
+fn decode_exec_result(bytes: &[u8]) -> Result<DryRun, {error}> {{
+    let mut input = bytes;
+    let _gas_consumed = Weight::decode(&mut input)?;
+    let gas_required = Weight::decode(&mut input)?;
+    let charge = u8::decode(&mut input)? == 1;
+    let deposit = u128::decode(&mut input)? as i128;
+    let debug_message = String::from_utf8_lossy(&Vec::<u8>::decode(&mut input)?).to_string();
+
+    if u8::decode(&mut input)? != 0 {{
+        return Err({error}::Dispatch {{ debug_message }});
+    }}
+    let flags = u32::decode(&mut input)?;
+    let data = Vec::<u8>::decode(&mut input)?;
+    if flags & 1 != 0 {{
+        return Err({error}::Reverted {{ data, debug_message }});
+    }}
+
+    Ok(DryRun {{
+        gas_required,
+        storage_deposit: if charge {{ deposit }} else {{ -deposit }},
+        debug_message,
+        data,
+    }})
+}}
+"#,
+        name = contract_name,
+        types = registry.declarations.concat(),
+    ))
+}
+
+/// Module name of a contract's client, e.g. `my_token`
+pub fn rust_module_name(contract_name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in contract_name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 && !snake.ends_with('_') {
+            snake.push('_');
+        }
+        if c == '-' {
+            snake.push('_');
+        } else {
+            snake.extend(c.to_lowercase());
+        }
+    }
+    snake
+}
+
+/// Client struct of a contract, e.g. `MyTokenClient`
+pub fn rust_client_name(contract_name: &str) -> String {
+    format!("{}Client", pascal_case(contract_name))
+}
+
+/// `mod.rs` declaring the client module of every contract
+pub fn generate_rust_mod(contract_names: &[String]) -> String {
+    let mut output = String::from("// Generated by glin-forge\n// Do not edit manually\n\n");
+    for name in contract_names {
+        output.push_str(&format!("pub mod {};\n", rust_module_name(name)));
+    }
+    output.push('\n');
+    for name in contract_names {
+        output.push_str(&format!(
+            "pub use {}::{};\n",
+            rust_module_name(name),
+            rust_client_name(name)
+        ));
+    }
+    output
+}
+
+/// Event structs and the `<Contract>Event` decoder
+fn generate_events(
+    contract_name: &str,
+    registry: &mut RustTypes,
+    metadata: &JsonValue,
+    error: &str,
+) -> Result<String> {
+    let spec = metadata
+        .get("V3")
+        .map(|v3| &v3["spec"])
+        .unwrap_or(&metadata["spec"]);
+    let events = extract_events(&serde_json::json!({ "spec": spec }));
+    let raw_events = spec["events"].as_array().cloned().unwrap_or_default();
+
+    let mut output = String::new();
+    let mut variants = String::new();
+    let mut decoders = String::new();
+    for (index, (event, raw)) in events.iter().zip(&raw_events).enumerate() {
+        let mut fields = String::new();
+        for arg in raw["args"].as_array().into_iter().flatten() {
+            let label = arg["label"].as_str().unwrap_or("value");
+            let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+            fields.push_str(&doc_comment("    ", &arg["docs"]));
+            fields.push_str(&format!(
+                "    pub {}: {},\n",
+                field_name(label),
+                registry.rust_type(type_id)?
+            ));
+        }
+
+        output.push_str(&doc_comment("", &raw["docs"]));
+        output.push_str(&format!(
+            "#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]\n#[codec(crate = subxt::ext::codec)]\npub struct {label}Event {{\n{fields}}}\n\n",
+            label = event.label
+        ));
+        variants.push_str(&format!(
+            "    {label}({label}Event),\n",
+            label = event.label
+        ));
+
+        match event.signature_topic.as_deref().map(bytes_literal).transpose()? {
+            Some(topic) => {
+                output.push_str(&format!(
+                    "impl {label}Event {{\n    pub const SIGNATURE_TOPIC: [u8; 32] = {topic};\n}}\n\n",
+                    label = event.label
+                ));
+                decoders.push_str(&format!(
+                    "        if topics.first().map(|t| t.0) == Some({label}Event::SIGNATURE_TOPIC) {{\n            return Ok(Self::{label}({label}Event::decode(&mut &data[..])?));\n        }}\n",
+                    label = event.label
+                ));
+            }
+            // Events without signature topics are prefixed with their index
+            None => decoders.push_str(&format!(
+                "        if let Some((&{index}, rest)) = data.split_first() {{\n            return Ok(Self::{label}({label}Event::decode(&mut &rest[..])?));\n        }}\n",
+                label = event.label
+            )),
+        }
+    }
+
+    output.push_str(&format!(
+        r#"/// Any event emitted by the {name} contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum {name}Event {{
+{variants}}}
+
+impl {name}Event {{
+    /// Decode a `ContractEmitted` payload given the topics of its event record
+    pub fn decode(topics: &[H256], data: &[u8]) -> Result<Self, {error}> {{
+{decoders}        let _ = (topics, data);
+        Err({error}::UnknownEvent)
+    }}
+}}
+"#,
+        name = contract_name,
+    ));
+    Ok(output)
+}
+
+/// Crate the generated types build on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Flavor {
+    /// Off-chain subxt client
+    Client,
+    /// ink! contract code
+    Ink,
+}
+
+impl Flavor {
+    fn account_id(self) -> &'static str {
+        match self {
+            Flavor::Client => "AccountId32",
+            Flavor::Ink => "AccountId",
+        }
+    }
+
+    fn hash(self) -> &'static str {
+        match self {
+            Flavor::Client => "H256",
+            Flavor::Ink => "Hash",
+        }
+    }
+
+    fn collections(self) -> &'static str {
+        match self {
+            Flavor::Client => "std::collections",
+            Flavor::Ink => "ink::prelude::collections",
+        }
+    }
+
+    fn derives(self) -> &'static str {
+        match self {
+            Flavor::Client => "#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]\n#[codec(crate = subxt::ext::codec)]\n",
+            Flavor::Ink => "#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]\n#[cfg_attr(feature = \"std\", derive(scale_info::TypeInfo))]\n",
+        }
+    }
+
+    /// Names the generated module uses itself
+    fn reserved(self) -> &'static [&'static str] {
+        match self {
+            Flavor::Client => &[
+                "Weight",
+                "DryRun",
+                "AccountId32",
+                "H256",
+                "Compact",
+                "Keypair",
+            ],
+            Flavor::Ink => &["AccountId", "Hash", "String", "Vec"],
+        }
+    }
+}
+
+/// Rust type names for the metadata type registry, with declarations for structs and enums
+pub(super) struct RustTypes {
+    flavor: Flavor,
+    types: HashMap<u32, JsonValue>,
+    /// Declared name of each struct and enum
+    names: HashMap<u32, String>,
+    taken: HashSet<String>,
+    pub(super) declarations: Vec<String>,
+}
+
+impl RustTypes {
+    pub(super) fn new(types_array: &JsonValue, flavor: Flavor) -> Result<Self> {
+        let mut types = HashMap::new();
+        for entry in types_array
+            .as_array()
+            .context("Types section must be an array")?
+        {
+            let id = entry["id"].as_u64().context("Type entry missing id")? as u32;
+            types.insert(id, entry["type"].clone());
+        }
+        Ok(Self {
+            flavor,
+            types,
+            names: HashMap::new(),
+            taken: flavor.reserved().iter().map(|s| s.to_string()).collect(),
+            declarations: Vec::new(),
+        })
+    }
+
+    /// Parameters and encoding statements of a constructor's or message's arguments
+    fn arguments(&mut self, item: &JsonValue) -> Result<(Vec<String>, String)> {
+        let mut params = Vec::new();
+        let mut encode = String::new();
+        for arg in item["args"].as_array().into_iter().flatten() {
+            let name = field_name(arg["label"].as_str().unwrap_or("arg"));
+            let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+            params.push(format!("{}: {}", name, self.rust_type(type_id)?));
+            encode.push_str(&format!("        {}.encode_to(&mut input);\n", name));
+        }
+        Ok((params, encode))
+    }
+
+    /// Return type of a message without ink!'s `Result<T, LangError>` wrapper,
+    /// and the type id of the removed `LangError`
+    pub(super) fn return_type(&mut self, type_id: u32) -> Result<(String, Option<u32>)> {
+        let def = self.get(type_id)?.clone();
+        let lang_error = def["params"]
+            .get(1)
+            .and_then(|p| p["type"].as_u64())
+            .map(|id| id as u32)
+            .filter(|id| {
+                self.types
+                    .get(id)
+                    .map(|err| path(err).last().map(String::as_str) == Some("LangError"))
+                    .unwrap_or(false)
+            });
+
+        match lang_error {
+            Some(err) if path(&def) == ["Result"] => {
+                let ok = def["params"][0]["type"]
+                    .as_u64()
+                    .context("Result missing T")? as u32;
+                Ok((self.rust_type(ok)?, Some(err)))
+            }
+            _ => Ok((self.rust_type(type_id)?, None)),
+        }
+    }
+
+    fn get(&self, type_id: u32) -> Result<&JsonValue> {
+        self.types
+            .get(&type_id)
+            .with_context(|| format!("Type ID {} not found in registry", type_id))
+    }
+
+    fn param(&mut self, def: &JsonValue, index: usize) -> Result<String> {
+        match def["params"].get(index).and_then(|p| p["type"].as_u64()) {
+            Some(id) => self.rust_type(id as u32),
+            None => Ok("()".to_string()),
+        }
+    }
+
+    /// Rust type of a registry entry
+    pub(super) fn rust_type(&mut self, type_id: u32) -> Result<String> {
+        if let Some(name) = self.names.get(&type_id) {
+            return Ok(name.clone());
+        }
+        let def = self.get(type_id)?.clone();
+        let path = path(&def);
+        let last = path.last().map(String::as_str).unwrap_or("");
+
+        // Well-known types by path
+        match path.join("::").as_str() {
+            "Option" => return Ok(format!("Option<{}>", self.param(&def, 0)?)),
+            "Result" => {
+                return Ok(format!(
+                    "Result<{}, {}>",
+                    self.param(&def, 0)?,
+                    self.param(&def, 1)?
+                ))
+            }
+            "BTreeMap" => {
+                return Ok(format!(
+                    "{}::BTreeMap<{}, {}>",
+                    self.flavor.collections(),
+                    self.param(&def, 0)?,
+                    self.param(&def, 1)?
+                ))
+            }
+            "BTreeSet" => {
+                return Ok(format!(
+                    "{}::BTreeSet<{}>",
+                    self.flavor.collections(),
+                    self.param(&def, 0)?
+                ))
+            }
+            _ if def["def"].get("composite").is_some() && last == "AccountId" => {
+                return Ok(self.flavor.account_id().to_string())
+            }
+            _ if def["def"].get("composite").is_some() && last == "Hash" => {
+                return Ok(self.flavor.hash().to_string())
+            }
+            _ => {}
+        }
+
+        let body = &def["def"];
+        if let Some(primitive) = body["primitive"].as_str() {
+            return Ok(match primitive {
+                "str" => "String".to_string(),
+                "u256" | "i256" => "[u8; 32]".to_string(),
+                other => other.to_string(),
+            });
+        }
+        if let Some(composite) = body.get("composite") {
+            let fields = composite["fields"].as_array().cloned().unwrap_or_default();
+            if fields.is_empty() {
+                return Ok("()".to_string());
+            }
+            // Newtypes encode like their field
+            if fields.len() == 1 && fields[0]["name"].is_null() {
+                let inner = fields[0]["type"].as_u64().context("Field missing type")? as u32;
+                return self.rust_type(inner);
+            }
+            return self.declare_struct(type_id, &def, &fields);
+        }
+        if let Some(variant) = body.get("variant") {
+            let variants = variant["variants"].as_array().cloned().unwrap_or_default();
+            return self.declare_enum(type_id, &def, &variants);
+        }
+        if let Some(sequence) = body.get("sequence") {
+            let inner = sequence["type"].as_u64().context("Sequence missing type")? as u32;
+            return Ok(format!("Vec<{}>", self.rust_type(inner)?));
+        }
+        if let Some(array) = body.get("array") {
+            let inner = array["type"].as_u64().context("Array missing type")? as u32;
+            return Ok(format!(
+                "[{}; {}]",
+                self.rust_type(inner)?,
+                array["len"].as_u64().unwrap_or(0)
+            ));
+        }
+        if let Some(tuple) = body.get("tuple").and_then(|t| t.as_array()) {
+            let mut items = Vec::new();
+            for id in tuple {
+                items.push(self.rust_type(id.as_u64().context("Invalid type ID")? as u32)?);
+            }
+            return Ok(match items.len() {
+                0 => "()".to_string(),
+                1 => format!("({},)", items[0]),
+                _ => format!("({})", items.join(", ")),
+            });
+        }
+        if let Some(compact) = body.get("compact") {
+            let inner = compact["type"].as_u64().context("Compact missing type")? as u32;
+            let compact = match self.flavor {
+                Flavor::Client => "Compact",
+                Flavor::Ink => "scale::Compact",
+            };
+            return Ok(format!("{}<{}>", compact, self.rust_type(inner)?));
+        }
+        if body.get("bitSequence").is_some() {
+            anyhow::bail!(
+                "Type {} is a BitSequence, which the Rust client does not support",
+                type_id
+            );
+        }
+        anyhow::bail!("Type {} has an unknown definition", type_id)
+    }
+
+    /// Reserve a unique declaration name for a registry entry
+    fn reserve(&mut self, type_id: u32, def: &JsonValue) -> String {
+        let base = path(def)
+            .last()
+            .cloned()
+            .unwrap_or_else(|| format!("Type{}", type_id));
+        let name = if self.taken.contains(&base) {
+            format!("{}{}", base, type_id)
+        } else {
+            base
+        };
+        self.taken.insert(name.clone());
+        self.names.insert(type_id, name.clone());
+        name
+    }
+
+    fn declare_struct(
+        &mut self,
+        type_id: u32,
+        def: &JsonValue,
+        fields: &[JsonValue],
+    ) -> Result<String> {
+        let name = self.reserve(type_id, def);
+        let named = fields.iter().all(|f| f["name"].is_string());
+        let mut body = String::new();
+        for field in fields {
+            let ty = self.rust_type(field["type"].as_u64().context("Field missing type")? as u32)?;
+            if named {
+                body.push_str(&doc_comment("    ", &field["docs"]));
+                body.push_str(&format!(
+                    "    pub {}: {},\n",
+                    field_name(field["name"].as_str().unwrap_or("field")),
+                    ty
+                ));
+            } else {
+                body.push_str(&format!("pub {}, ", ty));
+            }
+        }
+
+        let mut declaration = doc_comment("", &def["docs"]);
+        declaration.push_str(self.flavor.derives());
+        if named {
+            declaration.push_str(&format!("pub struct {} {{\n{}}}\n\n", name, body));
+        } else {
+            declaration.push_str(&format!(
+                "pub struct {}({});\n\n",
+                name,
+                body.trim_end_matches(", ")
+            ));
+        }
+        self.declarations.push(declaration);
+        Ok(name)
+    }
+
+    fn declare_enum(
+        &mut self,
+        type_id: u32,
+        def: &JsonValue,
+        variants: &[JsonValue],
+    ) -> Result<String> {
+        let name = self.reserve(type_id, def);
+        let mut body = String::new();
+        for variant in variants {
+            body.push_str(&doc_comment("    ", &variant["docs"]));
+            if let Some(index) = variant["index"].as_u64() {
+                body.push_str(&format!("    #[codec(index = {})]\n", index));
+            }
+            let label = variant["name"].as_str().context("Variant missing name")?;
+            let fields = variant["fields"].as_array().cloned().unwrap_or_default();
+            if fields.is_empty() {
+                body.push_str(&format!("    {},\n", label));
+                continue;
+            }
+            let named = fields.iter().all(|f| f["name"].is_string());
+            let mut items = Vec::new();
+            for field in &fields {
+                let ty =
+                    self.rust_type(field["type"].as_u64().context("Field missing type")? as u32)?;
+                items.push(if named {
+                    format!(
+                        "{}: {}",
+                        field_name(field["name"].as_str().unwrap_or("field")),
+                        ty
+                    )
+                } else {
+                    ty
+                });
+            }
+            if named {
+                body.push_str(&format!("    {} {{ {} }},\n", label, items.join(", ")));
+            } else {
+                body.push_str(&format!("    {}({}),\n", label, items.join(", ")));
+            }
+        }
+
+        let mut declaration = doc_comment("", &def["docs"]);
+        declaration.push_str(self.flavor.derives());
+        declaration.push_str(&format!("pub enum {} {{\n{}}}\n\n", name, body));
+        self.declarations.push(declaration);
+        Ok(name)
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "yield",
+];
+
+fn path(def: &JsonValue) -> Vec<String> {
+    def["path"]
+        .as_array()
+        .map(|p| {
+            p.iter()
+                .filter_map(|s| s.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Identifier for a field or argument
+pub(super) fn field_name(label: &str) -> String {
+    match label {
+        "self" | "Self" | "super" | "crate" => format!("{}_", label),
+        _ if KEYWORDS.contains(&label) => format!("r#{}", label),
+        _ => label.to_string(),
+    }
+}
+
+/// Method name of a message label; trait messages (`PSP22::transfer`) are prefixed
+fn method_name(label: &str) -> String {
+    field_name(&label.replace("::", "_").to_lowercase())
+}
+
+fn const_name(label: &str) -> String {
+    label.replace("::", "_").to_uppercase()
+}
+
+fn selector_const(item: &JsonValue) -> Result<String> {
+    let label = item["label"].as_str().unwrap_or("method");
+    let selector = item["selector"]
+        .as_str()
+        .with_context(|| format!("{} has no selector", label))?;
+    Ok(format!(
+        "        pub const {}: [u8; 4] = {};\n",
+        const_name(label),
+        bytes_literal(selector)?
+    ))
+}
+
+/// `0x0102` as `[0x01, 0x02]`
+pub(super) fn bytes_literal(hex_str: &str) -> Result<String> {
+    let bytes = hex::decode(hex_str.trim_start_matches("0x"))
+        .with_context(|| format!("Invalid hex '{}'", hex_str))?;
+    Ok(format!(
+        "[{}]",
+        bytes
+            .iter()
+            .map(|b| format!("0x{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
+
+pub(super) fn doc_comment(indent: &str, docs: &JsonValue) -> String {
+    docs.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|d| d.as_str())
+        .map(|line| format!("{}///{}\n", indent, line.trim_end()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_metadata() -> JsonValue {
+        serde_json::json!({
+            "source": { "hash": "0x0101010101010101010101010101010101010101010101010101010101010101" },
+            "spec": {
+                "constructors": [{
+                    "label": "new",
+                    "selector": "0x9bae9d5e",
+                    "args": [{ "label": "supply", "type": { "type": 0 } }],
+                    "docs": [" Creates the token."]
+                }],
+                "messages": [
+                    {
+                        "label": "PSP22::transfer",
+                        "selector": "0xdb20f9f5",
+                        "mutates": true,
+                        "payable": false,
+                        "args": [
+                            { "label": "to", "type": { "type": 1 } },
+                            { "label": "value", "type": { "type": 0 } }
+                        ],
+                        "returnType": { "type": 3 },
+                        "docs": []
+                    },
+                    {
+                        "label": "balance_of",
+                        "selector": "0x0f755a56",
+                        "mutates": false,
+                        "payable": false,
+                        "args": [{ "label": "owner", "type": { "type": 1 } }],
+                        "returnType": { "type": 6 },
+                        "docs": []
+                    }
+                ],
+                "events": [{
+                    "label": "Transfer",
+                    "signature_topic": "0xb5b61a3e6a21a16be4f044b517c28ac692492f73c5bfd3f60178ad98c767f4cb",
+                    "args": [
+                        { "label": "from", "indexed": true, "type": { "type": 7 } },
+                        { "label": "value", "indexed": false, "type": { "type": 0 } }
+                    ],
+                    "docs": []
+                }]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } },
+                { "id": 1, "type": { "path": ["ink_primitives", "types", "AccountId"], "def": { "composite": { "fields": [{ "type": 2, "typeName": "[u8; 32]" }] } } } },
+                { "id": 2, "type": { "def": { "array": { "len": 32, "type": 8 } } } },
+                { "id": 3, "type": { "path": ["Result"], "params": [{ "name": "T", "type": 4 }, { "name": "E", "type": 9 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 4, "type": { "path": ["Result"], "params": [{ "name": "T", "type": 5 }, { "name": "E", "type": 10 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 5, "type": { "def": { "tuple": [] } } },
+                { "id": 6, "type": { "path": ["Result"], "params": [{ "name": "T", "type": 0 }, { "name": "E", "type": 9 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 7, "type": { "path": ["Option"], "params": [{ "name": "T", "type": 1 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 8, "type": { "def": { "primitive": "u8" } } },
+                { "id": 9, "type": { "path": ["ink_primitives", "LangError"], "def": { "variant": { "variants": [{ "name": "CouldNotReadInput", "index": 1 }] } } } },
+                { "id": 10, "type": { "path": ["token", "Error"], "def": { "variant": { "variants": [
+                    { "name": "InsufficientBalance", "index": 0 },
+                    { "name": "Custom", "index": 1, "fields": [{ "type": 11 }] }
+                ] } } } },
+                { "id": 11, "type": { "def": { "primitive": "str" } } }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_generate_rust_client() {
+        let client = generate_rust_client("Token", &token_metadata()).unwrap();
+
+        assert!(client.contains("pub struct TokenClient"));
+        assert!(client.contains("pub const NEW: [u8; 4] = [0x9b, 0xae, 0x9d, 0x5e];"));
+        assert!(client.contains("pub const PSP22_TRANSFER: [u8; 4]"));
+        assert!(client.contains("pub async fn instantiate_new("));
+        assert!(client.contains(
+            "pub async fn psp22_transfer(&self, signer: &Keypair, to: AccountId32, value: u128)"
+        ));
+        // Query results unwrap ink's LangError
+        assert!(client.contains(
+            "pub async fn balance_of(&self, owner: AccountId32) -> Result<u128, TokenError>"
+        ));
+        assert!(
+            client.contains("pub enum Error {\n    #[codec(index = 0)]\n    InsufficientBalance,")
+        );
+        assert!(client.contains("    Custom(String),"));
+        assert!(client.contains("pub from: Option<AccountId32>,"));
+        assert!(client.contains("pub const SIGNATURE_TOPIC: [u8; 32] = [0xb5, 0xb6,"));
+        assert!(client.contains("pub const CODE_HASH: H256 = H256([0x01,"));
+        assert_eq!(rust_module_name("MyToken"), "my_token");
+    }
+}
@@ -0,0 +1,411 @@
+// React UI scaffolding over the generated bindings
+//
+// `typegen --scaffold react` writes a starting point for a contract UI into
+// `components/`: a `<Contract>Provider` adapting an SDK contract to the typed
+// interface, a `WalletConnect` account picker, read and write panels with a
+// form per message, and a `<Contract>App` wiring them together. Unlike the
+// bindings these files are meant to be edited, so existing ones are kept.
+
+use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
+
+/// Directory of the scaffold below the typegen output
+pub const SCAFFOLD_DIR: &str = "components";
+
+/// A scaffold file, relative to [`SCAFFOLD_DIR`]
+#[derive(Debug, Clone)]
+pub struct ScaffoldFile {
+    pub name: String,
+    pub content: String,
+}
+
+/// Generate the React scaffold of a contract
+pub fn generate_react_scaffold(contract_name: &str, abi: &JsonValue) -> Result<Vec<ScaffoldFile>> {
+    let spec = match abi.get("V3") {
+        Some(v3) => &v3["spec"],
+        None => &abi["spec"],
+    };
+    let messages = spec["messages"]
+        .as_array()
+        .context("Messages section missing")?;
+
+    let mut queries = String::new();
+    let mut transactions = String::new();
+    for msg in messages {
+        let label = msg["label"].as_str().unwrap_or("method");
+        let params: Vec<String> = msg["args"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|arg| format!("'{}'", arg["label"].as_str().unwrap_or("arg")))
+            .collect();
+        let mutates = msg["mutates"].as_bool().unwrap_or(false);
+        let (section, interface) = if mutates {
+            ("tx", "Transactions")
+        } else {
+            ("query", "Queries")
+        };
+        let form = format!(
+            "      <MessageForm\n        label=\"{label}\"\n        params={{[{params}]}}\n        mutates={{{mutates}}}\n        run={{(args) =>\n          contract.{section}{access}(...(args as Parameters<{name}{interface}['{label}']>))\n        }}\n      />\n",
+            params = params.join(", "),
+            access = property_access(label),
+            name = contract_name,
+        );
+        if mutates {
+            transactions.push_str(&form);
+        } else {
+            queries.push_str(&form);
+        }
+    }
+
+    let component = super::hooks::pascal_case(contract_name);
+    Ok(vec![
+        ScaffoldFile {
+            name: format!("{}Provider.tsx", component),
+            content: provider(contract_name),
+        },
+        ScaffoldFile {
+            name: "WalletConnect.tsx".to_string(),
+            content: WALLET_CONNECT.to_string(),
+        },
+        ScaffoldFile {
+            name: "MessageForm.tsx".to_string(),
+            content: MESSAGE_FORM.to_string(),
+        },
+        ScaffoldFile {
+            name: format!("{}Read.tsx", component),
+            content: panel(contract_name, "Read", "Queries", &queries),
+        },
+        ScaffoldFile {
+            name: format!("{}Write.tsx", component),
+            content: panel(contract_name, "Write", "Transactions", &transactions),
+        },
+        ScaffoldFile {
+            name: format!("{}App.tsx", component),
+            content: app(contract_name),
+        },
+    ])
+}
+
+/// `.transfer` or `['PSP22::transfer']`
+fn property_access(label: &str) -> String {
+    if label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        format!(".{}", label)
+    } else {
+        format!("['{}']", label)
+    }
+}
+
+fn provider(name: &str) -> String {
+    format!(
+        r#"// Scaffolded by glin-forge typegen --scaffold react; edit freely
+
+import {{ createContext, useContext, useMemo, useState, type ReactNode }} from 'react';
+import {{ getContract, Network, type Contract }} from '@glin-forge/sdk';
+import type {{ {n}, {n}Events, {n}Queries, {n}Transactions }} from '../{n}';
+
+export interface {c}ContextValue {{
+  /** Typed contract, once an account is connected */
+  contract: {n} | null;
+  account: string | null;
+  connect(account: string): void;
+  disconnect(): void;
+}}
+
+const {c}Context = createContext<{c}ContextValue | null>(null);
+
+/** Typed view of an SDK contract: `query` and `tx` methods map to untyped calls by label */
+export function as{c}(contract: Contract, metadata: string): {n} {{
+  const methods = <T extends object>(call: (method: string, args: any[]) => Promise<unknown>) =>
+    new Proxy({{}} as T, {{
+      get: (_, method) => (...args: any[]) => call(String(method), args),
+    }});
+  return {{
+    address: contract.address,
+    metadata,
+    query: methods<{n}Queries>((method, args) => contract.query(method, args)),
+    tx: methods<{n}Transactions>((method, args) => contract.call(method, args)),
+    events: {{}} as {n}Events,
+  }};
+}}
+
+export interface {c}ProviderProps {{
+  address: string;
+  /** Path to the contract metadata JSON */
+  metadata: string;
+  network?: Network | string;
+  children: ReactNode;
+}}
+
+export function {c}Provider({{
+  address,
+  metadata,
+  network = Network.Local,
+  children,
+}}: {c}ProviderProps) {{
+  const [account, setAccount] = useState<string | null>(null);
+  const contract = useMemo(
+    () =>
+      account ? as{c}(getContract({{ address, metadata, network, account }}), metadata) : null,
+    [address, metadata, network, account],
+  );
+  const value = useMemo(
+    () => ({{ contract, account, connect: setAccount, disconnect: () => setAccount(null) }}),
+    [contract, account],
+  );
+
+  return <{c}Context.Provider value={{value}}>{{children}}</{c}Context.Provider>;
+}}
+
+export function use{c}Context(): {c}ContextValue {{
+  const context = useContext({c}Context);
+  if (!context) {{
+    throw new Error('use{c}Context must be used inside <{c}Provider>');
+  }}
+  return context;
+}}
+"#,
+        n = name,
+        c = super::hooks::pascal_case(name),
+    )
+}
+
+fn panel(name: &str, title: &str, interface: &str, forms: &str) -> String {
+    let verb = if title == "Read" { "query" } else { "call" };
+    let body = if forms.is_empty() {
+        format!(
+            "      <p>{} has no {}.</p>\n",
+            name,
+            interface.to_lowercase()
+        )
+    } else {
+        forms.to_string()
+    };
+    format!(
+        r#"// Scaffolded by glin-forge typegen --scaffold react; edit freely
+
+import type {{ {n}{interface} }} from '../{n}';
+import {{ MessageForm }} from './MessageForm';
+import {{ use{c}Context }} from './{c}Provider';
+
+export function {c}{title}() {{
+  const {{ contract }} = use{c}Context();
+  if (!contract) {{
+    return <p>Connect an account to {verb} {n}.</p>;
+  }}
+
+  return (
+    <section className="card">
+      <h2>{title}</h2>
+{body}    </section>
+  );
+}}
+"#,
+        n = name,
+        c = super::hooks::pascal_case(name),
+    )
+}
+
+fn app(name: &str) -> String {
+    format!(
+        r#"// Scaffolded by glin-forge typegen --scaffold react; edit freely
+
+import {{ WalletConnect }} from './WalletConnect';
+import {{ {c}Provider, use{c}Context }} from './{c}Provider';
+import {{ {c}Read }} from './{c}Read';
+import {{ {c}Write }} from './{c}Write';
+
+function Wallet() {{
+  const {{ account, connect, disconnect }} = use{c}Context();
+  return <WalletConnect account={{account}} onConnect={{connect}} onDisconnect={{disconnect}} />;
+}}
+
+/**
+ * Complete {n} UI
+ *
+ * @example
+ * <{c}App address={{CONTRACT_ADDRESS}} metadata="./target/ink/metadata.json" />
+ */
+export function {c}App({{ address, metadata }}: {{ address: string; metadata: string }}) {{
+  return (
+    <{c}Provider address={{address}} metadata={{metadata}}>
+      <Wallet />
+      <{c}Read />
+      <{c}Write />
+    </{c}Provider>
+  );
+}}
+"#,
+        n = name,
+        c = super::hooks::pascal_case(name),
+    )
+}
+
+const WALLET_CONNECT: &str = r#"// Scaffolded by glin-forge typegen --scaffold react; edit freely
+
+import { useState } from 'react';
+import { Signer } from '@glin-forge/sdk';
+
+export interface WalletConnectProps {
+  account: string | null;
+  onConnect(account: string): void;
+  onDisconnect(): void;
+}
+
+/** Pick one of the development accounts signing through `glin-forge run` */
+export function WalletConnect({ account, onConnect, onDisconnect }: WalletConnectProps) {
+  const [selected, setSelected] = useState<string>(Signer.Alice);
+
+  if (account) {
+    return (
+      <div className="wallet connected">
+        <span>
+          Connected as <code>{account}</code>
+        </span>
+        <button onClick={onDisconnect}>Disconnect</button>
+      </div>
+    );
+  }
+
+  return (
+    <div className="wallet">
+      <select value={selected} onChange={(event) => setSelected(event.target.value)}>
+        {Object.values(Signer).map((signer) => (
+          <option key={signer} value={signer}>
+            {signer}
+          </option>
+        ))}
+      </select>
+      <button className="primary-button" onClick={() => onConnect(selected)}>
+        Connect
+      </button>
+    </div>
+  );
+}
+"#;
+
+const MESSAGE_FORM: &str = r#"// Scaffolded by glin-forge typegen --scaffold react; edit freely
+
+import { useState } from 'react';
+
+export interface MessageFormProps {
+  label: string;
+  params: string[];
+  mutates: boolean;
+  run(args: unknown[]): Promise<unknown>;
+}
+
+/** Inputs are parsed as JSON where possible (numbers, booleans, objects), else kept as strings */
+function parseArg(input: string): unknown {
+  const trimmed = input.trim();
+  if (/^-?\d{16,}$/.test(trimmed)) {
+    return trimmed;
+  }
+  try {
+    return JSON.parse(trimmed);
+  } catch {
+    return input;
+  }
+}
+
+function formatResult(value: unknown): string {
+  if (value && typeof value === 'object' && 'hash' in value && typeof value.hash === 'function') {
+    return `Submitted ${value.hash()}`;
+  }
+  return JSON.stringify(value, (_, v) => (typeof v === 'bigint' ? v.toString() : v), 2) ?? 'OK';
+}
+
+/** One contract message: an input per argument and its result */
+export function MessageForm({ label, params, mutates, run }: MessageFormProps) {
+  const [inputs, setInputs] = useState<string[]>(() => params.map(() => ''));
+  const [result, setResult] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [pending, setPending] = useState(false);
+
+  const submit = async () => {
+    setPending(true);
+    setError(null);
+    try {
+      setResult(formatResult(await run(inputs.map(parseArg))));
+    } catch (e) {
+      setResult(null);
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <form
+      className="message"
+      onSubmit={(event) => {
+        event.preventDefault();
+        void submit();
+      }}
+    >
+      <code>{label}</code>
+      {params.map((param, i) => (
+        <input
+          key={param}
+          placeholder={param}
+          value={inputs[i]}
+          onChange={(event) =>
+            setInputs((current) => current.map((v, j) => (j === i ? event.target.value : v)))
+          }
+        />
+      ))}
+      <button type="submit" className="primary-button" disabled={pending}>
+        {pending ? '...' : mutates ? 'Send' : 'Query'}
+      </button>
+      {result !== null && <pre className="result">{result}</pre>}
+      {error !== null && <pre className="error">{error}</pre>}
+    </form>
+  );
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_react_scaffold() {
+        let abi = serde_json::json!({
+            "spec": {
+                "messages": [
+                    { "label": "get", "mutates": false, "args": [] },
+                    {
+                        "label": "PSP22::transfer",
+                        "mutates": true,
+                        "args": [{ "label": "to" }, { "label": "value" }]
+                    }
+                ]
+            }
+        });
+
+        let files = generate_react_scaffold("Token", &abi).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "TokenProvider.tsx",
+                "WalletConnect.tsx",
+                "MessageForm.tsx",
+                "TokenRead.tsx",
+                "TokenWrite.tsx",
+                "TokenApp.tsx"
+            ]
+        );
+
+        let read = &files[3].content;
+        assert!(read.contains("contract.query.get(...(args as Parameters<TokenQueries['get']>))"));
+        let write = &files[4].content;
+        assert!(write.contains("params={['to', 'value']}"));
+        assert!(write.contains(
+            "contract.tx['PSP22::transfer'](...(args as Parameters<TokenTransactions['PSP22::transfer']>))"
+        ));
+        assert!(files[0]
+            .content
+            .contains("export function useTokenContext(): TokenContextValue {"));
+    }
+}
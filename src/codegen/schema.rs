@@ -0,0 +1,279 @@
+// JSON Schema generation from contract metadata
+//
+// Every constructor and message gets a schema for its argument object and every
+// message one for its return value, collected under `$defs` of a single
+// draft 2020-12 document (`#/$defs/messages.transfer.args`). The schemas follow
+// the shapes of the generated TypeScript types: enums are objects tagged with
+// `type`, 64-bit and wider integers are integers or decimal strings and byte
+// arrays are `0x` hex strings.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value as JsonValue};
+
+use super::type_resolver::{TypeResolver, TypeScriptType};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generate the JSON Schema document of a contract
+pub fn generate_json_schema(contract_name: &str, metadata: &JsonValue) -> Result<JsonValue> {
+    let (types, spec) = match metadata.get("V3") {
+        Some(v3) => (&v3["types"], &v3["spec"]),
+        None => (
+            metadata.get("types").context("Types section not found")?,
+            &metadata["spec"],
+        ),
+    };
+    let mut resolver = TypeResolver::new(types)?;
+
+    let mut defs = Map::new();
+    for ctor in spec["constructors"].as_array().into_iter().flatten() {
+        let label = ctor["label"].as_str().unwrap_or("new");
+        defs.insert(
+            format!("constructors.{}.args", label),
+            arguments_schema(&mut resolver, ctor, &format!("{} constructor", label))?,
+        );
+    }
+    for msg in spec["messages"]
+        .as_array()
+        .context("Messages section missing")?
+    {
+        let label = msg["label"].as_str().unwrap_or("method");
+        defs.insert(
+            format!("messages.{}.args", label),
+            arguments_schema(&mut resolver, msg, label)?,
+        );
+        let returns = match msg["returnType"]["type"].as_u64() {
+            Some(type_id) => type_schema(&resolver.resolve_type(type_id as u32)?),
+            None => json!({ "type": "null" }),
+        };
+        defs.insert(
+            format!("messages.{}.returns", label),
+            with_title(returns, format!("{} result", label)),
+        );
+    }
+
+    Ok(json!({
+        "$schema": DRAFT,
+        "$id": schema_file(contract_name),
+        "title": format!("{} contract", contract_name),
+        "$defs": defs,
+    }))
+}
+
+/// File name of the schema document, e.g. `MyToken.schema.json`
+pub fn schema_file(contract_name: &str) -> String {
+    format!("{}.schema.json", contract_name)
+}
+
+/// Object schema of a constructor's or message's arguments, keyed by label
+fn arguments_schema(
+    resolver: &mut TypeResolver,
+    item: &JsonValue,
+    title: &str,
+) -> Result<JsonValue> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for arg in item["args"].as_array().into_iter().flatten() {
+        let name = arg["label"].as_str().unwrap_or("arg");
+        let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+        properties.insert(
+            name.to_string(),
+            type_schema(&resolver.resolve_type(type_id)?),
+        );
+        required.push(name);
+    }
+
+    let mut schema = json!({
+        "title": format!("{} arguments", title),
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    });
+    let docs: Vec<&str> = item["docs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|d| d.as_str())
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .collect();
+    if !docs.is_empty() {
+        schema["description"] = json!(docs.join(" "));
+    }
+    if let Some(selector) = item["selector"].as_str() {
+        schema["$comment"] = json!(format!("Selector {}", selector));
+    }
+    Ok(schema)
+}
+
+/// Schema of a resolved type
+pub(super) fn type_schema(ts_type: &TypeScriptType) -> JsonValue {
+    match ts_type {
+        TypeScriptType::Primitive(name) => match name.as_str() {
+            "boolean" => json!({ "type": "boolean" }),
+            "number" => json!({ "type": "integer" }),
+            "string" => json!({ "type": "string" }),
+            "bigint" => wide_integer(),
+            "void" | "null" => json!({ "type": "null" }),
+            _ => json!({}),
+        },
+        TypeScriptType::Reference(name) if name == "Uint8Array" => bytes(),
+        TypeScriptType::Reference(_) | TypeScriptType::Any => json!({}),
+        TypeScriptType::Array(inner) => json!({ "type": "array", "items": type_schema(inner) }),
+        TypeScriptType::Tuple(types) => json!({
+            "type": "array",
+            "prefixItems": types.iter().map(type_schema).collect::<Vec<_>>(),
+            "items": false,
+            "minItems": types.len(),
+        }),
+        TypeScriptType::Or(types) => {
+            // `string | number | bigint`
+            if types
+                .iter()
+                .any(|t| matches!(t, TypeScriptType::Primitive(p) if p == "bigint"))
+            {
+                return wide_integer();
+            }
+            // `Uint8Array | string` and hashes
+            if types
+                .iter()
+                .any(|t| matches!(t, TypeScriptType::Reference(r) if r == "Uint8Array"))
+            {
+                return bytes();
+            }
+            json!({ "anyOf": types.iter().map(type_schema).collect::<Vec<_>>() })
+        }
+        TypeScriptType::Optional(inner) => {
+            json!({ "anyOf": [type_schema(inner), { "type": "null" }] })
+        }
+        TypeScriptType::Interface { name, fields, .. } => {
+            let properties: Map<String, JsonValue> = fields
+                .iter()
+                .map(|(field, field_type)| (field.clone(), type_schema(field_type)))
+                .collect();
+            json!({
+                "title": name,
+                "type": "object",
+                "properties": properties,
+                "required": fields.iter().map(|(field, _)| field).collect::<Vec<_>>(),
+                "additionalProperties": false,
+            })
+        }
+        TypeScriptType::Union { name, variants, .. } => {
+            let variants: Vec<JsonValue> = variants
+                .iter()
+                .map(|variant| {
+                    let mut properties = Map::new();
+                    let mut required = vec!["type".to_string()];
+                    properties.insert("type".to_string(), json!({ "const": variant.name }));
+                    for (field, field_type) in &variant.fields {
+                        let field = field.clone().unwrap_or_else(|| "value".to_string());
+                        properties.insert(field.clone(), type_schema(field_type));
+                        required.push(field);
+                    }
+                    json!({
+                        "type": "object",
+                        "properties": properties,
+                        "required": required,
+                        "additionalProperties": false,
+                    })
+                })
+                .collect();
+            json!({ "title": name, "oneOf": variants })
+        }
+    }
+}
+
+/// 64-bit and wider integers: JSON numbers lose precision, so decimal strings are accepted too
+fn wide_integer() -> JsonValue {
+    json!({ "type": ["integer", "string"], "pattern": "^-?[0-9]+$" })
+}
+
+fn bytes() -> JsonValue {
+    json!({ "type": "string", "pattern": "^0x([0-9a-fA-F]{2})*$" })
+}
+
+fn with_title(mut schema: JsonValue, title: String) -> JsonValue {
+    if let Some(object) = schema.as_object_mut() {
+        object.entry("title").or_insert(json!(title));
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_json_schema() {
+        let metadata = json!({
+            "spec": {
+                "constructors": [{
+                    "label": "new",
+                    "selector": "0x9bae9d5e",
+                    "args": [{ "label": "supply", "type": { "type": 0 } }],
+                    "docs": []
+                }],
+                "messages": [
+                    {
+                        "label": "transfer",
+                        "selector": "0x84a15da1",
+                        "mutates": true,
+                        "args": [
+                            { "label": "to", "type": { "type": 1 } },
+                            { "label": "value", "type": { "type": 0 } }
+                        ],
+                        "returnType": { "type": 3 },
+                        "docs": [" Moves tokens."]
+                    },
+                    {
+                        "label": "paused",
+                        "mutates": false,
+                        "args": [],
+                        "returnType": { "type": 4 },
+                        "docs": []
+                    }
+                ]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } },
+                { "id": 1, "type": { "path": ["ink_primitives", "types", "AccountId"], "def": { "composite": { "fields": [{ "type": 2 }] } } } },
+                { "id": 2, "type": { "def": { "array": { "len": 32, "type": 5 } } } },
+                { "id": 3, "type": { "path": ["Result"], "params": [{ "name": "T", "type": 6 }, { "name": "E", "type": 7 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 4, "type": { "def": { "primitive": "bool" } } },
+                { "id": 5, "type": { "def": { "primitive": "u8" } } },
+                { "id": 6, "type": { "def": { "tuple": [] } } },
+                { "id": 7, "type": { "path": ["token", "Error"], "def": { "variant": { "variants": [{ "name": "InsufficientBalance" }] } } } }
+            ]
+        });
+
+        let schema = generate_json_schema("Token", &metadata).unwrap();
+        assert_eq!(schema["$id"], "Token.schema.json");
+        let defs = &schema["$defs"];
+
+        let ctor = &defs["constructors.new.args"];
+        assert_eq!(ctor["required"], json!(["supply"]));
+        assert_eq!(
+            ctor["properties"]["supply"]["type"],
+            json!(["integer", "string"])
+        );
+
+        let transfer = &defs["messages.transfer.args"];
+        assert_eq!(transfer["properties"]["to"], json!({ "type": "string" }));
+        assert_eq!(transfer["description"], "Moves tokens.");
+        assert_eq!(transfer["$comment"], "Selector 0x84a15da1");
+        assert_eq!(transfer["additionalProperties"], false);
+
+        let returns = &defs["messages.transfer.returns"]["oneOf"];
+        assert_eq!(returns[0]["properties"]["type"]["const"], "Ok");
+        assert_eq!(returns[0]["properties"]["value"], json!({ "type": "null" }));
+        assert_eq!(
+            returns[1]["properties"]["error"]["oneOf"][0]["properties"]["type"]["const"],
+            "InsufficientBalance"
+        );
+
+        assert_eq!(defs["messages.paused.returns"]["type"], "boolean");
+        assert_eq!(defs["messages.paused.args"]["properties"], json!({}));
+    }
+}
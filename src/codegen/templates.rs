@@ -0,0 +1,199 @@
+// Overridable handlebars templates for typegen
+//
+// The module header, the basic React hooks and the hooks file name are
+// rendered from templates embedded from `templates/typegen`. A project can
+// override any of them by placing a file of the same name in its templates
+// directory (`typegen.templates/` by default); the rest fall back to the
+// embedded ones. Templates see `contract_name` and `version`, and the
+// `snake_case`, `kebab_case`, `camel_case` and `pascal_case` helpers.
+
+use anyhow::{Context, Result};
+use handlebars::{handlebars_helper, Handlebars};
+use serde_json::Value as JsonValue;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Project directory searched for template overrides
+pub const DEFAULT_TEMPLATES_DIR: &str = "typegen.templates";
+
+const EMBEDDED: [(&str, &str); 3] = [
+    ("header", include_str!("../../templates/typegen/header.hbs")),
+    ("hooks", include_str!("../../templates/typegen/hooks.hbs")),
+    (
+        "hooks-file",
+        include_str!("../../templates/typegen/hooks-file.hbs"),
+    ),
+];
+
+handlebars_helper!(snake_case: |name: str| to_words(name).join("_"));
+handlebars_helper!(kebab_case: |name: str| to_words(name).join("-"));
+handlebars_helper!(pascal_case: |name: str| super::hooks::pascal_case(name));
+handlebars_helper!(camel_case: |name: str| {
+    let pascal = super::hooks::pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+});
+
+/// Code generation templates, embedded defaults with project overrides
+#[derive(Debug)]
+pub struct Templates {
+    registry: Handlebars<'static>,
+    overridden: Vec<String>,
+}
+
+impl Templates {
+    /// The embedded templates only
+    pub fn embedded() -> &'static Templates {
+        static EMBEDDED_TEMPLATES: OnceLock<Templates> = OnceLock::new();
+        EMBEDDED_TEMPLATES.get_or_init(|| {
+            Templates::with_overrides(None).expect("embedded typegen templates are valid")
+        })
+    }
+
+    /// Embedded templates, overridden by the `<name>.hbs` files of `dir` when it exists
+    pub fn load(dir: &Path) -> Result<Self> {
+        Self::with_overrides(dir.is_dir().then_some(dir))
+    }
+
+    fn with_overrides(dir: Option<&Path>) -> Result<Self> {
+        let mut registry = Handlebars::new();
+        registry.register_escape_fn(handlebars::no_escape);
+        registry.register_helper("snake_case", Box::new(snake_case));
+        registry.register_helper("kebab_case", Box::new(kebab_case));
+        registry.register_helper("pascal_case", Box::new(pascal_case));
+        registry.register_helper("camel_case", Box::new(camel_case));
+
+        let mut overridden = Vec::new();
+        for (name, embedded) in EMBEDDED {
+            let path = dir.map(|dir| dir.join(format!("{}.hbs", name)));
+            let source = match &path {
+                Some(path) if path.is_file() => {
+                    overridden.push(name.to_string());
+                    std::fs::read_to_string(path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?
+                }
+                _ => embedded.to_string(),
+            };
+            registry
+                .register_template_string(name, source)
+                .with_context(|| format!("Invalid typegen template '{}'", name))?;
+        }
+
+        if let Some(dir) = dir {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                let Some(stem) = path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .and_then(|s| s.strip_suffix(".hbs"))
+                else {
+                    continue;
+                };
+                if !EMBEDDED.iter().any(|(name, _)| *name == stem) {
+                    anyhow::bail!(
+                        "Unknown typegen template {} (expected one of: {})",
+                        path.display(),
+                        EMBEDDED
+                            .iter()
+                            .map(|(name, _)| format!("{}.hbs", name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            registry,
+            overridden,
+        })
+    }
+
+    /// Names of the templates taken from the project
+    pub fn overridden(&self) -> &[String] {
+        &self.overridden
+    }
+
+    /// Render template `name` for a contract
+    pub fn render(&self, name: &str, data: &JsonValue) -> Result<String> {
+        self.registry
+            .render(name, data)
+            .with_context(|| format!("Failed to render typegen template '{}'", name))
+    }
+
+    /// File name of the basic React hooks of a contract
+    pub fn hooks_file(&self, contract_name: &str) -> Result<String> {
+        let file = self.render("hooks-file", &template_data(contract_name, None))?;
+        Ok(file.trim().to_string())
+    }
+}
+
+/// Data every template is rendered with
+pub(super) fn template_data(contract_name: &str, version: Option<&str>) -> JsonValue {
+    serde_json::json!({
+        "contract_name": contract_name,
+        "version": version.unwrap_or("0.0.0"),
+    })
+}
+
+/// Lowercase words of a `PascalCase`, `snake_case` or `kebab-case` name
+fn to_words(name: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            previous_lower = false;
+            words.push(String::new());
+            continue;
+        }
+        if (c.is_ascii_uppercase() && previous_lower) || words.is_empty() {
+            words.push(String::new());
+        }
+        previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        if let Some(word) = words.last_mut() {
+            word.push(c.to_ascii_lowercase());
+        }
+    }
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_overrides() {
+        let dir = std::env::temp_dir().join(format!("glin-forge-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("hooks-file.hbs"),
+            "{{kebab_case contract_name}}.hooks.ts\n",
+        )
+        .unwrap();
+
+        let templates = Templates::load(&dir).unwrap();
+        assert_eq!(templates.overridden(), ["hooks-file"]);
+        assert_eq!(
+            templates.hooks_file("MyToken").unwrap(),
+            "my-token.hooks.ts"
+        );
+        // Not overridden: embedded
+        let header = templates
+            .render("header", &template_data("MyToken", None))
+            .unwrap();
+        assert!(header.contains("TypeScript definitions for MyToken contract"));
+
+        std::fs::write(dir.join("footer.hbs"), "").unwrap();
+        assert!(Templates::load(&dir).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            Templates::embedded().hooks_file("MyToken").unwrap(),
+            "useMyToken.ts"
+        );
+    }
+}
@@ -13,7 +13,7 @@
 
 use anyhow::{Context, Result};
 use serde_json::Value as JsonValue;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 /// Represents a TypeScript type
 #[derive(Debug, Clone, PartialEq)]
@@ -62,6 +62,9 @@ pub struct UnionVariant {
     pub docs: Vec<String>,
 }
 
+/// Nominal types of `--branded`, matched against type paths like the plain mappings
+const BRANDS: [&str; 3] = ["AccountId", "Balance", "Hash"];
+
 /// Type resolver that converts ink! types to TypeScript
 pub struct TypeResolver {
     /// Type registry from metadata
@@ -75,6 +78,12 @@ pub struct TypeResolver {
 
     /// Named types (structs, enums) that need separate declarations
     named_types: HashMap<String, TypeScriptType>,
+
+    /// Map 64-bit and wider integers to `bigint` only (`--strict-bigint`)
+    strict_bigint: bool,
+
+    /// Reference the branded `AccountId`, `Balance` and `Hash` types (`--branded`)
+    branded: bool,
 }
 
 impl TypeResolver {
@@ -96,9 +105,39 @@ impl TypeResolver {
             resolved_cache: HashMap::new(),
             resolving_stack: HashSet::new(),
             named_types: HashMap::new(),
+            strict_bigint: false,
+            branded: false,
         })
     }
 
+    /// Emit `bigint` for 64-bit and wider integers instead of `string | number | bigint`;
+    /// inputs are formatted as `bigint | string` by `format_input_type`
+    pub fn with_strict_bigint(mut self, strict: bool) -> Self {
+        self.strict_bigint = strict;
+        self
+    }
+
+    /// Resolve account ids, balances and hashes to the nominal types declared
+    /// by the generated module instead of their plain representation
+    pub fn with_branded_types(mut self, branded: bool) -> Self {
+        self.branded = branded;
+        self
+    }
+
+    /// Type of 64-bit and wider integers and balances
+    fn wide_integer(&self) -> TypeScriptType {
+        if self.strict_bigint {
+            TypeScriptType::Primitive("bigint".to_string())
+        } else {
+            // Union type for safety
+            TypeScriptType::Or(vec![
+                TypeScriptType::Primitive("string".to_string()),
+                TypeScriptType::Primitive("number".to_string()),
+                TypeScriptType::Primitive("bigint".to_string()),
+            ])
+        }
+    }
+
     /// Resolve a type ID to a TypeScript type
     pub fn resolve_type(&mut self, type_id: u32) -> Result<TypeScriptType> {
         // Check cache first
@@ -187,6 +226,12 @@ impl TypeResolver {
     ) -> Result<Option<TypeScriptType>> {
         let path_str = path.join("::");
 
+        if self.branded {
+            if let Some(brand) = BRANDS.iter().find(|brand| path_str.contains(*brand)) {
+                return Ok(Some(TypeScriptType::Reference(brand.to_string())));
+            }
+        }
+
         match path_str.as_str() {
             "Option" => {
                 let params = type_def["params"].as_array();
@@ -247,11 +292,7 @@ impl TypeResolver {
                 return Ok(Some(TypeScriptType::Primitive("string".to_string())));
             }
             path if path.contains("Balance") => {
-                return Ok(Some(TypeScriptType::Or(vec![
-                    TypeScriptType::Primitive("string".to_string()),
-                    TypeScriptType::Primitive("number".to_string()),
-                    TypeScriptType::Primitive("bigint".to_string()),
-                ])));
+                return Ok(Some(self.wide_integer()));
             }
             path if path.contains("Hash") => {
                 return Ok(Some(TypeScriptType::Or(vec![
@@ -273,12 +314,7 @@ impl TypeResolver {
             "str" => "string",
             "u8" | "u16" | "u32" | "i8" | "i16" | "i32" => "number",
             "u64" | "u128" | "u256" | "i64" | "i128" | "i256" => {
-                // Return union type for safety
-                return Ok(TypeScriptType::Or(vec![
-                    TypeScriptType::Primitive("string".to_string()),
-                    TypeScriptType::Primitive("number".to_string()),
-                    TypeScriptType::Primitive("bigint".to_string()),
-                ]));
+                return Ok(self.wide_integer());
             }
             _ => "any",
         };
@@ -474,27 +510,45 @@ impl TypeResolver {
 
     /// Format a TypeScript type as a string
     pub fn format_type(&self, ts_type: &TypeScriptType) -> String {
+        self.format(ts_type, false)
+    }
+
+    /// Format the type of an argument; in strict bigint mode wide integers
+    /// also accept decimal strings
+    pub fn format_input_type(&self, ts_type: &TypeScriptType) -> String {
+        self.format(ts_type, self.strict_bigint)
+    }
+
+    fn format(&self, ts_type: &TypeScriptType, input: bool) -> String {
         match ts_type {
+            TypeScriptType::Primitive(name) if input && name == "bigint" => {
+                "bigint | string".to_string()
+            }
             TypeScriptType::Primitive(name) => name.clone(),
             TypeScriptType::Reference(name) => name.clone(),
             TypeScriptType::Array(inner) => {
-                format!("{}[]", self.format_type(inner))
+                let inner = self.format(inner, input);
+                if inner.contains(" | ") {
+                    format!("({})[]", inner)
+                } else {
+                    format!("{}[]", inner)
+                }
             }
             TypeScriptType::Tuple(types) => {
                 let formatted = types
                     .iter()
-                    .map(|t| self.format_type(t))
+                    .map(|t| self.format(t, input))
                     .collect::<Vec<_>>()
                     .join(", ");
                 format!("[{}]", formatted)
             }
             TypeScriptType::Or(types) => types
                 .iter()
-                .map(|t| self.format_type(t))
+                .map(|t| self.format(t, input))
                 .collect::<Vec<_>>()
                 .join(" | "),
             TypeScriptType::Optional(inner) => {
-                format!("{} | null", self.format_type(inner))
+                format!("{} | null", self.format(inner, input))
             }
             TypeScriptType::Interface { name, .. } => name.clone(),
             TypeScriptType::Union { name, .. } => name.clone(),
@@ -503,6 +557,24 @@ impl TypeResolver {
     }
 }
 
+/// Add the declared types (structs and enums) `ts_type` refers to by name
+pub fn collect_names(ts_type: &TypeScriptType, names: &mut BTreeSet<String>) {
+    match ts_type {
+        TypeScriptType::Interface { name, .. } | TypeScriptType::Union { name, .. } => {
+            names.insert(name.clone());
+        }
+        TypeScriptType::Array(inner) | TypeScriptType::Optional(inner) => {
+            collect_names(inner, names)
+        }
+        TypeScriptType::Tuple(types) | TypeScriptType::Or(types) => {
+            for ts_type in types {
+                collect_names(ts_type, names);
+            }
+        }
+        _ => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -514,6 +586,8 @@ mod tests {
             resolved_cache: HashMap::new(),
             resolving_stack: HashSet::new(),
             named_types: HashMap::new(),
+            strict_bigint: false,
+            branded: false,
         };
 
         assert_eq!(
@@ -540,6 +614,8 @@ mod tests {
             resolved_cache: HashMap::new(),
             resolving_stack: HashSet::new(),
             named_types: HashMap::new(),
+            strict_bigint: false,
+            branded: false,
         };
 
         assert_eq!(
@@ -561,4 +637,23 @@ mod tests {
             "string | null"
         );
     }
+
+    #[test]
+    fn test_strict_bigint() {
+        let resolver = TypeResolver::new(&serde_json::json!([]))
+            .unwrap()
+            .with_strict_bigint(true);
+
+        let wide = resolver.resolve_primitive("u128").unwrap();
+        assert_eq!(resolver.format_type(&wide), "bigint");
+        assert_eq!(resolver.format_input_type(&wide), "bigint | string");
+        assert_eq!(
+            resolver.format_input_type(&TypeScriptType::Array(Box::new(wide))),
+            "(bigint | string)[]"
+        );
+        assert_eq!(
+            resolver.format_type(&resolver.resolve_primitive("u32").unwrap()),
+            "number"
+        );
+    }
 }
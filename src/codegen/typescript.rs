@@ -6,23 +6,69 @@
 use anyhow::{Context, Result};
 use serde_json::Value as JsonValue;
 
+use super::openrpc::split_result;
+use super::templates::{template_data, Templates};
 use super::type_resolver::{TypeResolver, TypeScriptType, UnionVariant};
 
+/// Options shared by the generated module and framework bindings
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModuleOptions<'a> {
+    /// `bigint` for 64-bit and wider integers in results, `bigint | string` for inputs
+    pub strict_bigint: bool,
+    /// Nominal `AccountId`, `Balance` and `Hash` types with validating constructors
+    pub branded: bool,
+    /// Templates with project overrides; the embedded ones when unset
+    pub templates: Option<&'a Templates>,
+}
+
+impl<'a> ModuleOptions<'a> {
+    pub(super) fn templates(&self) -> &'a Templates {
+        match self.templates {
+            Some(templates) => templates,
+            None => Templates::embedded(),
+        }
+    }
+
+    /// Type resolver formatting types the way the module declares them
+    pub(super) fn type_resolver(&self, types: &JsonValue) -> Result<TypeResolver> {
+        Ok(TypeResolver::new(types)?
+            .with_strict_bigint(self.strict_bigint)
+            .with_branded_types(self.branded))
+    }
+
+    /// Import of the branded types for modules built on the main one
+    pub(super) fn branded_import(&self, contract_name: &str) -> String {
+        if self.branded {
+            format!(
+                "import type {{ AccountId, Balance, Hash }} from './{}';\n",
+                contract_name
+            )
+        } else {
+            String::new()
+        }
+    }
+}
+
 /// Generate complete TypeScript module from contract metadata
-pub fn generate_typescript_module(contract_name: &str, metadata: &JsonValue) -> Result<String> {
-    let mut generator = TypeScriptGenerator::new(contract_name, metadata)?;
+pub fn generate_typescript_module(
+    contract_name: &str,
+    metadata: &JsonValue,
+    options: ModuleOptions,
+) -> Result<String> {
+    let mut generator = TypeScriptGenerator::new(contract_name, metadata, options)?;
     generator.generate()
 }
 
 /// TypeScript code generator
-struct TypeScriptGenerator {
+struct TypeScriptGenerator<'a> {
     contract_name: String,
     type_resolver: TypeResolver,
     metadata: JsonValue,
+    options: ModuleOptions<'a>,
 }
 
-impl TypeScriptGenerator {
-    fn new(contract_name: &str, metadata: &JsonValue) -> Result<Self> {
+impl<'a> TypeScriptGenerator<'a> {
+    fn new(contract_name: &str, metadata: &JsonValue, options: ModuleOptions<'a>) -> Result<Self> {
         // Get types section (handle both V3 and V4 format)
         let types_section = if let Some(v3) = metadata.get("V3") {
             &v3["types"]
@@ -30,12 +76,13 @@ impl TypeScriptGenerator {
             metadata.get("types").context("Types section not found")?
         };
 
-        let type_resolver = TypeResolver::new(types_section)?;
+        let type_resolver = options.type_resolver(types_section)?;
 
         Ok(Self {
             contract_name: contract_name.to_string(),
             type_resolver,
             metadata: metadata.clone(),
+            options,
         })
     }
 
@@ -44,42 +91,66 @@ impl TypeScriptGenerator {
         let mut output = String::new();
 
         // Header
-        output.push_str(&self.generate_header());
+        output.push_str(&self.generate_header()?);
 
         // Imports
         output.push_str(&self.generate_imports());
 
+        // Branded primitives
+        if self.options.branded {
+            output.push_str(&self.generate_branded_types());
+        }
+
         // Custom type definitions (structs, enums)
         output.push_str(&self.generate_custom_types()?);
 
+        // Error unions and matching helpers
+        output.push_str(&self.generate_error_types()?);
+
+        // bigint codecs
+        if self.options.strict_bigint {
+            output.push_str(&self.generate_bigint_codecs());
+        }
+
         // Constructor interface
         output.push_str(&self.generate_constructor_interface()?);
 
         // Methods interface (separated into queries and transactions)
         output.push_str(&self.generate_methods_interface()?);
 
+        // Selectors and call data encoders
+        let types_section = match self.metadata.get("V3") {
+            Some(v3) => v3["types"].clone(),
+            None => self.metadata["types"].clone(),
+        };
+        output.push_str(&super::calldata::generate_call_data(
+            &self.contract_name,
+            &types_section,
+            &self.get_spec_section(),
+            &mut self.type_resolver,
+        )?);
+
         // Events interface
         output.push_str(&self.generate_events_interface()?);
 
+        // Typed event subscriptions
+        output.push_str(&self.generate_event_subscriptions());
+
         // Main contract interface
         output.push_str(&self.generate_main_interface()?);
 
+        // Deploy factory
+        output.push_str(&self.generate_deploy_factory()?);
+
         Ok(output)
     }
 
     /// Generate file header with metadata
-    fn generate_header(&self) -> String {
-        format!(
-            r#"/**
- * TypeScript definitions for {} contract
- *
- * Generated by glin-forge typegen
- * DO NOT EDIT MANUALLY
- */
-
-"#,
-            self.contract_name
-        )
+    fn generate_header(&self) -> Result<String> {
+        let version = self.metadata["contract"]["version"].as_str();
+        self.options
+            .templates()
+            .render("header", &template_data(&self.contract_name, version))
     }
 
     /// Generate import statements
@@ -154,13 +225,12 @@ impl TypeScriptGenerator {
         let mut custom_types = Vec::new();
         for type_id in type_ids {
             let ts_type = self.type_resolver.resolve_type(type_id)?;
-            match ts_type {
-                TypeScriptType::Interface { .. } | TypeScriptType::Union { .. } => {
-                    if !custom_types.iter().any(|t| self.types_equal(t, &ts_type)) {
-                        custom_types.push(ts_type);
-                    }
-                }
-                _ => {}
+            if matches!(
+                ts_type,
+                TypeScriptType::Interface { .. } | TypeScriptType::Union { .. }
+            ) && !custom_types.iter().any(|t| self.types_equal(t, &ts_type))
+            {
+                custom_types.push(ts_type);
             }
         }
 
@@ -263,6 +333,276 @@ impl TypeScriptGenerator {
         }
     }
 
+    /// Tagged unions of the error enums messages return in `Result<T, E>`, ink!'s
+    /// `LangError`, and helpers matching and decoding both
+    fn generate_error_types(&mut self) -> Result<String> {
+        let spec = self.get_spec_section();
+        let mut lang_error = None;
+        let mut errors: Vec<(String, Vec<UnionVariant>)> = Vec::new();
+        for msg in spec["messages"].as_array().into_iter().flatten() {
+            let Some(type_id) = msg["returnType"]["type"].as_u64() else {
+                continue;
+            };
+            let mut returns = self.type_resolver.resolve_type(type_id as u32)?;
+            if let Some((ok, TypeScriptType::Union { name, variants, .. })) = split_result(&returns)
+            {
+                if name == "LangError" {
+                    lang_error = Some(variants.clone());
+                    returns = ok.clone();
+                }
+            }
+            if let Some((_, TypeScriptType::Union { name, variants, .. })) = split_result(&returns)
+            {
+                let name = if name == "Error" {
+                    format!("{}Error", self.contract_name)
+                } else {
+                    name.clone()
+                };
+                if !errors.iter().any(|(known, _)| *known == name) {
+                    errors.push((name, variants.clone()));
+                }
+            }
+        }
+        if lang_error.is_none() && errors.is_empty() {
+            return Ok(String::new());
+        }
+
+        let mut output = String::new();
+        output.push_str("// ========================================\n");
+        output.push_str("// Errors\n");
+        output.push_str("// ========================================\n\n");
+
+        let mut unions = Vec::new();
+        if let Some(variants) = lang_error {
+            unions.push((
+                "LangError".to_string(),
+                variants,
+                "Error raised by ink! before a message runs, e.g. an unknown selector".to_string(),
+            ));
+        }
+        for (name, variants) in errors {
+            let docs = format!("Error returned by {} messages in `Err`", self.contract_name);
+            unions.push((name, variants, docs));
+        }
+
+        for (name, variants, docs) in &unions {
+            output.push_str(&format!("/** {} */\n", docs));
+            output.push_str(&format!("export type {} =\n", name));
+            for (i, variant) in variants.iter().enumerate() {
+                let fields: String = variant
+                    .fields
+                    .iter()
+                    .map(|(field, field_type)| {
+                        format!(
+                            ", {}: {}",
+                            field.as_deref().unwrap_or("value"),
+                            self.type_resolver.format_type(field_type)
+                        )
+                    })
+                    .collect();
+                output.push_str(&format!(
+                    "  {}{{ type: '{}'{} }}\n",
+                    if i == 0 { "" } else { "| " },
+                    variant.name,
+                    fields
+                ));
+            }
+            output.push_str(";\n\n");
+
+            let names: Vec<String> = variants.iter().map(|v| format!("'{}'", v.name)).collect();
+            output.push_str(&format!(
+                r#"export const {n}Variants = [{names}] as const;
+
+export type {n}Variant = {n}['type'];
+
+/**
+ * Whether `error` is a {n}, optionally of the given variant
+ *
+ * Matches the tagged form; run values from the node through `decode{n}()` first.
+ *
+ * @example
+ * if (is{n}(error, {first})) {{ ... }}
+ */
+export function is{n}<V extends {n}Variant>(
+  error: unknown,
+  variant?: V,
+): error is Extract<{n}, {{ type: V }}> {{
+  const decoded = decode{n}(error);
+  return (
+    decoded !== undefined &&
+    decoded === error &&
+    (variant === undefined || decoded.type === variant)
+  );
+}}
+
+/** Decode a {n} as returned by the node or wrapped in a `Result` */
+export function decode{n}(value: unknown): {n} | undefined {{
+  return decodeEnum<{n}>(value, {n}Variants);
+}}
+
+"#,
+                n = name,
+                names = names.join(", "),
+                first = names.first().map(String::as_str).unwrap_or("''"),
+            ));
+        }
+
+        output.push_str(
+            r#"/**
+ * Normalize an enum value to its `{ type: ... }` form
+ *
+ * Accepts the tagged form, plain variant names and `{ Variant: payload }` objects,
+ * unwrapping `{ Ok: ... }` / `{ Err: ... }` results.
+ */
+function decodeEnum<T extends { type: string }>(
+  value: unknown,
+  variants: readonly string[],
+): T | undefined {
+  if (typeof value === 'string') {
+    return variants.includes(value) ? ({ type: value } as T) : undefined;
+  }
+  if (value === null || typeof value !== 'object') {
+    return undefined;
+  }
+  const record = value as Record<string, unknown>;
+  if (typeof record.type === 'string') {
+    return variants.includes(record.type) ? (record as T) : undefined;
+  }
+  for (const wrapper of ['Ok', 'ok', 'Err', 'err']) {
+    if (wrapper in record) {
+      return decodeEnum<T>(record[wrapper], variants);
+    }
+  }
+  const keys = Object.keys(record);
+  if (keys.length !== 1 || !variants.includes(keys[0])) {
+    return undefined;
+  }
+  const payload = record[keys[0]];
+  let fields: Record<string, unknown> = {};
+  if (Array.isArray(payload)) {
+    fields = Object.fromEntries(payload.map((field, i) => [`field${i}`, field]));
+  } else if (payload !== null && typeof payload === 'object') {
+    fields = payload as Record<string, unknown>;
+  } else if (payload !== null && payload !== undefined) {
+    fields = { value: payload };
+  }
+  return { ...fields, type: keys[0] } as unknown as T;
+}
+
+"#,
+        );
+
+        Ok(output)
+    }
+
+    /// Nominal account id, balance and hash types with their validating constructors
+    fn generate_branded_types(&self) -> String {
+        let balance = if self.options.strict_bigint {
+            "bigint"
+        } else {
+            "string | number | bigint"
+        };
+        format!(
+            r#"// ============================================================================
+// Branded Types
+// ============================================================================
+
+/** `T` that only becomes a `Brand<T, N>` through a validating constructor */
+export type Brand<T, N extends string> = T & {{ readonly __brand: N }};
+
+/** SS58-encoded account address */
+export type AccountId = Brand<string, 'AccountId'>;
+
+/** Amount in the chain's smallest unit */
+export type Balance = Brand<{balance}, 'Balance'>;
+
+/** 32-byte hash as `0x`-prefixed hex */
+export type Hash = Brand<string, 'Hash'>;
+
+export function isAccountId(value: string): value is AccountId {{
+  return /^[1-9A-HJ-NP-Za-km-z]{{46,48}}$/.test(value);
+}}
+
+export function toAccountId(value: string): AccountId {{
+  if (!isAccountId(value)) {{
+    throw new TypeError(`Invalid AccountId: '${{value}}'`);
+  }}
+  return value;
+}}
+
+export function toBalance(value: bigint | string | number): Balance {{
+  const amount = typeof value === 'bigint' ? value : BigInt(value);
+  if (amount < 0n) {{
+    throw new RangeError(`Negative Balance: ${{value}}`);
+  }}
+  return amount as Balance;
+}}
+
+export function isHash(value: string): value is Hash {{
+  return /^0x[0-9a-fA-F]{{64}}$/.test(value);
+}}
+
+export function toHash(value: string | Uint8Array): Hash {{
+  const hex =
+    typeof value === 'string'
+      ? value
+      : '0x' + Array.from(value, (byte) => byte.toString(16).padStart(2, '0')).join('');
+  if (!isHash(hex)) {{
+    throw new TypeError(`Invalid Hash: '${{hex}}'`);
+  }}
+  return hex;
+}}
+
+"#
+        )
+    }
+
+    /// Conversions between `bigint` and the values accepted or returned at runtime
+    fn generate_bigint_codecs(&self) -> String {
+        r#"// ============================================================================
+// bigint Codecs
+// ============================================================================
+
+/**
+ * Normalize a 64-bit+ integer argument; strings may be decimal or `0x` hex
+ */
+export function toBigInt(value: bigint | string): bigint {
+  if (typeof value === 'bigint') {
+    return value;
+  }
+  const trimmed = value.trim();
+  if (!/^-?(\d+|0x[0-9a-fA-F]+)$/.test(trimmed)) {
+    throw new TypeError(`Invalid integer: '${value}'`);
+  }
+  return trimmed.startsWith('-') ? -BigInt(trimmed.slice(1)) : BigInt(trimmed);
+}
+
+/**
+ * Decode a 64-bit+ integer result without losing precision
+ */
+export function decodeBigInt(value: unknown): bigint {
+  if (typeof value === 'bigint') {
+    return value;
+  }
+  if (typeof value === 'number') {
+    if (!Number.isSafeInteger(value)) {
+      throw new RangeError(`Integer ${value} lost precision before decoding`);
+    }
+    return BigInt(value);
+  }
+  if (typeof value === 'string') {
+    return toBigInt(value.replace(/,/g, ''));
+  }
+  if (value !== null && typeof value === 'object' && 'toString' in value) {
+    return toBigInt(String(value));
+  }
+  throw new TypeError(`Cannot decode ${typeof value} as bigint`);
+}
+
+"#
+        .to_string()
+    }
+
     /// Generate constructor interface
     fn generate_constructor_interface(&mut self) -> Result<String> {
         let mut output = String::new();
@@ -283,20 +623,16 @@ impl TypeScriptGenerator {
 
         for ctor in constructors {
             let label = ctor["label"].as_str().unwrap_or("new");
-            let docs = ctor["docs"].as_array();
-
-            // JSDoc
-            if let Some(docs) = docs {
-                if !docs.is_empty() {
-                    output.push_str("  /**\n");
-                    for doc in docs {
-                        if let Some(doc_str) = doc.as_str() {
-                            output.push_str(&format!("   * {}\n", doc_str));
-                        }
-                    }
-                    output.push_str("   */\n");
-                }
+            let mut remarks = Vec::new();
+            if let Some(selector) = ctor["selector"].as_str() {
+                remarks.push(format!("Selector `{}`", selector));
             }
+            if ctor["payable"].as_bool().unwrap_or(false) {
+                remarks.push("payable".to_string());
+            }
+
+            // TSDoc
+            output.push_str(&tsdoc("  ", ctor, &remarks));
 
             // Method signature
             output.push_str(&format!("  {}(\n", label));
@@ -310,16 +646,15 @@ impl TypeScriptGenerator {
                     output.push_str(&format!(
                         "    {}: {},\n",
                         arg_name,
-                        self.type_resolver.format_type(&arg_type)
+                        self.type_resolver.format_input_type(&arg_type)
                     ));
                 }
             }
 
-            output.push_str("    options?: {\n");
-            output.push_str("      value?: number | bigint;\n");
-            output.push_str("      gasLimit?: number | bigint;\n");
-            output.push_str("      salt?: string;\n");
-            output.push_str("    }\n");
+            output.push_str(&format!(
+                "    options?: {}DeployOptions\n",
+                self.contract_name
+            ));
             output.push_str(&format!("  ): Promise<{}>;\n\n", self.contract_name));
         }
 
@@ -386,23 +721,22 @@ impl TypeScriptGenerator {
         let mut output = String::new();
 
         let label = msg["label"].as_str().unwrap_or("method");
-        let docs = msg["docs"].as_array();
-
-        // JSDoc
-        if let Some(docs) = docs {
-            if !docs.is_empty() {
-                output.push_str("  /**\n");
-                for doc in docs {
-                    if let Some(doc_str) = doc.as_str() {
-                        output.push_str(&format!("   * {}\n", doc_str));
-                    }
-                }
-                output.push_str("   */\n");
-            }
+        let mut remarks = Vec::new();
+        if let Some(selector) = msg["selector"].as_str() {
+            remarks.push(format!("Selector `{}`", selector));
+        }
+        if msg["mutates"].as_bool().unwrap_or(false) {
+            remarks.push("mutates state".to_string());
         }
+        if msg["payable"].as_bool().unwrap_or(false) {
+            remarks.push("payable".to_string());
+        }
+
+        // TSDoc
+        output.push_str(&tsdoc("  ", &msg, &remarks));
 
         // Method signature
-        output.push_str(&format!("  {}(", label));
+        output.push_str(&format!("  {}(", property_key(label)));
 
         // Arguments
         if let Some(args) = msg["args"].as_array() {
@@ -415,7 +749,7 @@ impl TypeScriptGenerator {
                     output.push_str(&format!(
                         "    {}: {},\n",
                         arg_name,
-                        self.type_resolver.format_type(&arg_type)
+                        self.type_resolver.format_input_type(&arg_type)
                     ));
                 }
                 output.push_str("  ");
@@ -469,20 +803,18 @@ impl TypeScriptGenerator {
 
             for event in events {
                 let event_name = event["label"].as_str().unwrap_or("Event");
-                let docs = event["docs"].as_array();
-
-                // JSDoc
-                if let Some(docs) = docs {
-                    if !docs.is_empty() {
-                        output.push_str("/**\n");
-                        for doc in docs {
-                            if let Some(doc_str) = doc.as_str() {
-                                output.push_str(&format!(" * {}\n", doc_str));
-                            }
-                        }
-                        output.push_str(" */\n");
-                    }
-                }
+                let remarks: Vec<String> = event["signature_topic"]
+                    .as_str()
+                    .map(|topic| format!("Signature topic `{}`", topic))
+                    .into_iter()
+                    .collect();
+
+                // TSDoc; fields are documented inline below
+                output.push_str(&tsdoc(
+                    "",
+                    &serde_json::json!({ "docs": event["docs"] }),
+                    &remarks,
+                ));
 
                 // Event interface
                 output.push_str(&format!("export interface {}Event {{\n", event_name));
@@ -494,12 +826,25 @@ impl TypeScriptGenerator {
                         let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
                         let arg_type = self.type_resolver.resolve_type(type_id)?;
 
-                        let comment = if indexed { " // indexed" } else { "" };
+                        let mut lines = doc_lines(&arg["docs"]);
+                        if indexed {
+                            lines.push("Indexed: published as a topic".to_string());
+                        }
+                        match lines.as_slice() {
+                            [] => {}
+                            [line] => output.push_str(&format!("  /** {} */\n", line)),
+                            lines => {
+                                output.push_str("  /**\n");
+                                for line in lines {
+                                    output.push_str(&format!("   * {}\n", line));
+                                }
+                                output.push_str("   */\n");
+                            }
+                        }
                         output.push_str(&format!(
-                            "  {}: {};{}\n",
+                            "  {}: {};\n",
                             arg_name,
-                            self.type_resolver.format_type(&arg_type),
-                            comment
+                            self.type_resolver.format_type(&arg_type)
                         ));
                     }
                 }
@@ -527,6 +872,106 @@ impl TypeScriptGenerator {
         Ok(output)
     }
 
+    /// Generate event topic info and `subscribe<Event>` helpers
+    fn generate_event_subscriptions(&self) -> String {
+        let events = super::metadata::extract_events(&serde_json::json!({
+            "spec": self.get_spec_section()
+        }));
+        if events.is_empty() {
+            return String::new();
+        }
+
+        let name = &self.contract_name;
+        let mut output = String::new();
+
+        output.push_str("// ========================================\n");
+        output.push_str("// Event Subscriptions\n");
+        output.push_str("// ========================================\n\n");
+
+        // Topic layout, so subscribers can filter on indexed fields
+        output.push_str("/** Signature topic and indexed fields of each event */\n");
+        output.push_str(&format!("export const {}EventTopics = {{\n", name));
+        for event in &events {
+            let signature = match &event.signature_topic {
+                Some(topic) => format!("'{}'", topic),
+                None => "null".to_string(),
+            };
+            let indexed: Vec<String> = event
+                .args
+                .iter()
+                .filter(|arg| arg.indexed)
+                .map(|arg| format!("'{}'", arg.label))
+                .collect();
+            output.push_str(&format!("  {}: {{\n", event.label));
+            output.push_str(&format!("    signatureTopic: {},\n", signature));
+            output.push_str(&format!("    indexed: [{}],\n", indexed.join(", ")));
+            output.push_str("  },\n");
+        }
+        output.push_str("} as const;\n\n");
+
+        output.push_str(&format!(
+            "export type {}EventName = keyof {}Events;\n\n",
+            name, name
+        ));
+        output.push_str("/** A decoded event tagged with its name */\n");
+        output.push_str(&format!(
+            "export type {n}DecodedEvent = {{\n  [K in {n}EventName]: {{ name: K; args: {n}Events[K] }};\n}}[{n}EventName];\n\n",
+            n = name
+        ));
+        output.push_str("export type Unsubscribe = () => void;\n\n");
+        output.push_str(
+            "/** Anything delivering decoded events, such as a contract from @glin-forge/sdk */\n",
+        );
+        output.push_str(&format!("export interface {}EventSource {{\n", name));
+        output.push_str(
+            "  subscribe(handler: (event: ContractEvent) => void): Unsubscribe | Promise<Unsubscribe>;\n",
+        );
+        output.push_str("}\n\n");
+
+        // Helper layer
+        output.push_str(&format!(
+            "/**\n * Typed subscriptions to {} events\n *\n * @example\n * const events = create{}EventSubscriptions(contract);\n * const unsubscribe = await events.subscribe{}((event) => console.log(event));\n */\n",
+            name, name, events[0].label
+        ));
+        output.push_str(&format!(
+            "export function create{}EventSubscriptions(source: {}EventSource) {{\n",
+            name, name
+        ));
+        output.push_str(&format!(
+            "  const on = <K extends {n}EventName>(name: K, callback: (event: {n}Events[K]) => void) =>\n",
+            n = name
+        ));
+        output.push_str("    source.subscribe((event) => {\n");
+        output.push_str(&format!(
+            "      if (event.name === name) callback(event.args as unknown as {}Events[K]);\n",
+            name
+        ));
+        output.push_str("    });\n\n");
+        output.push_str("  return {\n");
+        for event in &events {
+            if let Some(doc) = event.docs.iter().find(|d| !d.trim().is_empty()) {
+                output.push_str(&format!("    /** {} */\n", doc.trim()));
+            }
+            output.push_str(&format!(
+                "    subscribe{label}: (callback: (event: {label}Event) => void) => on('{label}', callback),\n",
+                label = event.label
+            ));
+        }
+        output.push_str("    /** Every event, tagged with its name */\n");
+        output.push_str(&format!(
+            "    subscribeAll: (callback: (event: {}DecodedEvent) => void) =>\n",
+            name
+        ));
+        output.push_str(&format!(
+            "      source.subscribe((event) => callback(event as unknown as {}DecodedEvent)),\n",
+            name
+        ));
+        output.push_str("  };\n");
+        output.push_str("}\n\n");
+
+        output
+    }
+
     /// Generate main contract interface
     fn generate_main_interface(&self) -> Result<String> {
         let mut output = String::new();
@@ -566,6 +1011,116 @@ export interface {} {{
         Ok(output)
     }
 
+    /// Generate the code hash constant, constructor argument types and `deploy<Contract>()`
+    fn generate_deploy_factory(&mut self) -> Result<String> {
+        let name = self.contract_name.clone();
+        let mut output = String::new();
+
+        output.push_str("// ========================================\n");
+        output.push_str("// Deployment\n");
+        output.push_str("// ========================================\n\n");
+
+        let code_hash = match self.metadata["source"]["hash"].as_str() {
+            Some(hash) => format!("'{}'", hash),
+            None => "null".to_string(),
+        };
+        output.push_str("/** Hash of the code these bindings were generated from */\n");
+        output.push_str(&format!(
+            "export const {}CodeHash: string | null = {};\n\n",
+            name, code_hash
+        ));
+
+        output.push_str(&format!("export interface {}DeployOptions {{\n", name));
+        output.push_str("  /** Balance transferred to the new contract */\n");
+        output.push_str("  value?: number | bigint;\n");
+        output.push_str("  gasLimit?: number | bigint;\n");
+        output.push_str("  storageDepositLimit?: number | bigint;\n");
+        output.push_str("  /** Salt for the contract address; random when omitted */\n");
+        output.push_str("  salt?: string;\n");
+        output.push_str("  /** Code to instantiate, defaults to the generated code hash */\n");
+        output.push_str("  codeHash?: string;\n");
+        output.push_str("}\n\n");
+
+        // Constructor arguments as labeled tuples, plus selectors
+        let spec = self.get_spec_section();
+        let constructors = spec["constructors"]
+            .as_array()
+            .context("Constructors section missing")?;
+
+        let mut selectors = Vec::new();
+        output.push_str(&format!("export interface {}ConstructorArgs {{\n", name));
+        for ctor in constructors {
+            let label = ctor["label"].as_str().unwrap_or("new");
+            let mut args = Vec::new();
+            for arg in ctor["args"].as_array().into_iter().flatten() {
+                let arg_name = arg["label"].as_str().unwrap_or("arg");
+                let type_id = arg["type"]["type"].as_u64().unwrap_or(0) as u32;
+                let arg_type = self.type_resolver.resolve_type(type_id)?;
+                args.push(format!(
+                    "{}: {}",
+                    arg_name,
+                    self.type_resolver.format_input_type(&arg_type)
+                ));
+            }
+            output.push_str(&format!("  {}: [{}];\n", label, args.join(", ")));
+            if let Some(selector) = ctor["selector"].as_str() {
+                selectors.push(format!("  {}: '{}',\n", label, selector));
+            }
+        }
+        output.push_str("}\n\n");
+
+        output.push_str(&format!(
+            "export const {}ConstructorSelectors = {{\n{}}} as const;\n\n",
+            name,
+            selectors.concat()
+        ));
+
+        output.push_str(
+            "/** Instantiates code by hash, such as the deployer from @glin-forge/sdk */\n",
+        );
+        output.push_str(&format!("export interface {}Deployer {{\n", name));
+        output.push_str(&format!(
+            "  instantiate(request: {}DeployOptions & {{\n",
+            name
+        ));
+        output.push_str("    codeHash: string;\n");
+        output.push_str("    constructor: string;\n");
+        output.push_str("    args: unknown[];\n");
+        output.push_str("    metadata: string;\n");
+        output.push_str(&format!("  }}): Promise<{}>;\n", name));
+        output.push_str("}\n\n");
+
+        output.push_str(&format!(
+            r#"/**
+ * Deploy a new {n} contract with type-checked constructor arguments
+ *
+ * @example
+ * const contract = await deploy{n}(deployer, metadata, '{first}', [...args], {{ value: 0n }});
+ */
+export function deploy{n}<K extends keyof {n}ConstructorArgs>(
+  deployer: {n}Deployer,
+  metadata: string,
+  constructor: K,
+  args: {n}ConstructorArgs[K],
+  options: {n}DeployOptions = {{}},
+): Promise<{n}> {{
+  const codeHash = options.codeHash ?? {n}CodeHash;
+  if (!codeHash) {{
+    throw new Error('No code hash in the metadata; pass options.codeHash');
+  }}
+  return deployer.instantiate({{ ...options, codeHash, constructor, args, metadata }});
+}}
+"#,
+            n = name,
+            first = constructors
+                .first()
+                .and_then(|c| c["label"].as_str())
+                .unwrap_or("new"),
+        ));
+
+        Ok(output)
+    }
+
     /// Helper to get spec section (handles both V3 and V4 format)
     fn get_spec_section(&self) -> JsonValue {
         if let Some(v3) = self.metadata.get("V3") {
@@ -593,6 +1148,100 @@ export interface {} {{
     }
 }
 
+/// Object key for a message label; trait messages like `Erc20::total_supply` are quoted
+pub(super) fn property_key(label: &str) -> String {
+    let mut chars = label.chars();
+    let identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        label.to_string()
+    } else {
+        format!("'{}'", label)
+    }
+}
+
+/// Property access for a message label: `.flip` or `['Erc20::total_supply']`
+pub(super) fn member_access(label: &str) -> String {
+    let key = property_key(label);
+    if key == label {
+        format!(".{}", label)
+    } else {
+        format!("[{}]", key)
+    }
+}
+
+/// Doc lines of an ABI `docs` array, trimmed and safe inside a comment
+fn doc_lines(docs: &JsonValue) -> Vec<String> {
+    let lines: Vec<String> = docs
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|doc| doc.as_str())
+        .map(|doc| doc.trim().replace("*/", "*\\/"))
+        .collect();
+
+    // Drop blank lines at either end
+    let start = lines
+        .iter()
+        .position(|l| !l.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(start, |i| i + 1);
+    lines[start..end].to_vec()
+}
+
+/// TSDoc block for a message, constructor or event: its docs, `@param` tags
+/// for documented arguments and a `@remarks` line
+fn tsdoc(indent: &str, item: &JsonValue, remarks: &[String]) -> String {
+    let mut body = doc_lines(&item["docs"]);
+
+    let params: Vec<String> = item["args"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|arg| {
+            let docs = doc_lines(&arg["docs"]);
+            (!docs.is_empty()).then(|| {
+                format!(
+                    "@param {} - {}",
+                    arg["label"].as_str().unwrap_or("arg"),
+                    docs.join(" ")
+                )
+            })
+        })
+        .collect();
+    if !params.is_empty() {
+        if !body.is_empty() {
+            body.push(String::new());
+        }
+        body.extend(params);
+    }
+    if !remarks.is_empty() {
+        if !body.is_empty() {
+            body.push(String::new());
+        }
+        body.push(format!("@remarks {}", remarks.join(" · ")));
+    }
+
+    if body.is_empty() {
+        return String::new();
+    }
+    let mut output = format!("{}/**\n", indent);
+    for line in body {
+        if line.is_empty() {
+            output.push_str(&format!("{} *\n", indent));
+        } else {
+            output.push_str(&format!("{} * {}\n", indent, line));
+        }
+    }
+    output.push_str(&format!("{} */\n", indent));
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -660,18 +1309,224 @@ mod tests {
     #[test]
     fn test_generate_header() {
         let metadata = sample_metadata();
-        let generator = TypeScriptGenerator::new("Flipper", &metadata).unwrap();
-        let header = generator.generate_header();
+        let generator =
+            TypeScriptGenerator::new("Flipper", &metadata, ModuleOptions::default()).unwrap();
+        let header = generator.generate_header().unwrap();
         assert!(header.contains("Flipper"));
         assert!(header.contains("DO NOT EDIT"));
     }
 
+    #[test]
+    fn test_generate_event_subscriptions() {
+        let mut metadata = sample_metadata();
+        metadata["spec"]["events"] = serde_json::json!([
+            {
+                "label": "Flipped",
+                "signature_topic": "0xabcd",
+                "args": [
+                    { "label": "by", "indexed": true, "type": { "type": 1 } },
+                    { "label": "value", "indexed": false, "type": { "type": 1 } }
+                ],
+                "docs": ["Emitted when the value flips."]
+            }
+        ]);
+        let generator =
+            TypeScriptGenerator::new("Flipper", &metadata, ModuleOptions::default()).unwrap();
+        let helpers = generator.generate_event_subscriptions();
+
+        assert!(helpers.contains("signatureTopic: '0xabcd'"));
+        assert!(helpers.contains("indexed: ['by']"));
+        assert!(helpers.contains("export function createFlipperEventSubscriptions"));
+        assert!(helpers.contains(
+            "subscribeFlipped: (callback: (event: FlippedEvent) => void) => on('Flipped', callback)"
+        ));
+
+        // No events, no helper layer
+        let generator =
+            TypeScriptGenerator::new("Flipper", &sample_metadata(), ModuleOptions::default())
+                .unwrap();
+        assert!(generator.generate_event_subscriptions().is_empty());
+    }
+
+    #[test]
+    fn test_generate_deploy_factory() {
+        let mut metadata = sample_metadata();
+        metadata["source"] = serde_json::json!({ "hash": "0x1234" });
+        let mut generator =
+            TypeScriptGenerator::new("Flipper", &metadata, ModuleOptions::default()).unwrap();
+        let factory = generator.generate_deploy_factory().unwrap();
+
+        assert!(factory.contains("export const FlipperCodeHash: string | null = '0x1234';"));
+        assert!(factory.contains("  new: [init_value: boolean];"));
+        assert!(factory.contains("  new: '0x9bae9d5e',"));
+        assert!(factory
+            .contains("export function deployFlipper<K extends keyof FlipperConstructorArgs>("));
+    }
+
+    #[test]
+    fn test_method_tsdoc() {
+        let metadata = sample_metadata();
+        let mut generator =
+            TypeScriptGenerator::new("Flipper", &metadata, ModuleOptions::default()).unwrap();
+        let msg = serde_json::json!({
+            "label": "set",
+            "selector": "0xe8c45eb6",
+            "mutates": true,
+            "payable": true,
+            "args": [
+                { "label": "value", "type": { "type": 1 }, "docs": [" The new value."] }
+            ],
+            "docs": [" Sets the value.", ""]
+        });
+        let signature = generator.generate_method_signature(msg, true).unwrap();
+
+        assert!(signature.starts_with(
+            "  /**\n   * Sets the value.\n   *\n   * @param value - The new value.\n   *\n   * @remarks Selector `0xe8c45eb6` · mutates state · payable\n   */\n"
+        ));
+    }
+
     #[test]
     fn test_generate_imports() {
         let metadata = sample_metadata();
-        let generator = TypeScriptGenerator::new("Flipper", &metadata).unwrap();
+        let generator =
+            TypeScriptGenerator::new("Flipper", &metadata, ModuleOptions::default()).unwrap();
         let imports = generator.generate_imports();
         assert!(imports.contains("@glin-forge/sdk"));
         assert!(imports.contains("Transaction"));
     }
+
+    #[test]
+    fn test_strict_bigint_module() {
+        let metadata = serde_json::json!({
+            "source": { "hash": "0x00" },
+            "spec": {
+                "constructors": [{
+                    "label": "new",
+                    "selector": "0x9bae9d5e",
+                    "args": [{ "label": "supply", "type": { "type": 0 } }],
+                    "docs": []
+                }],
+                "messages": [{
+                    "label": "balance_of",
+                    "selector": "0x0f755a56",
+                    "mutates": false,
+                    "payable": false,
+                    "args": [{ "label": "min", "type": { "type": 0 } }],
+                    "returnType": { "type": 0 },
+                    "docs": []
+                }],
+                "events": []
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u128" } } }
+            ]
+        });
+
+        let loose =
+            generate_typescript_module("Token", &metadata, ModuleOptions::default()).unwrap();
+        assert!(loose.contains("supply: string | number | bigint"));
+        assert!(!loose.contains("decodeBigInt"));
+
+        let strict = generate_typescript_module(
+            "Token",
+            &metadata,
+            ModuleOptions {
+                strict_bigint: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(strict.contains("supply: bigint | string"));
+        assert!(strict.contains("min: bigint | string"));
+        assert!(strict.contains("export function toBigInt(value: bigint | string): bigint"));
+        assert!(strict.contains("export function decodeBigInt(value: unknown): bigint"));
+        assert!(!strict.contains("string | number | bigint"));
+    }
+
+    #[test]
+    fn test_branded_module() {
+        let metadata = serde_json::json!({
+            "spec": {
+                "constructors": [],
+                "messages": [{
+                    "label": "balance_of",
+                    "mutates": false,
+                    "args": [{ "label": "owner", "type": { "type": 0 } }],
+                    "returnType": { "type": 2 }
+                }],
+                "events": []
+            },
+            "types": [
+                { "id": 0, "type": { "path": ["ink_primitives", "types", "AccountId"], "def": { "composite": { "fields": [{ "type": 1 }] } } } },
+                { "id": 1, "type": { "def": { "array": { "len": 32, "type": 3 } } } },
+                { "id": 2, "type": { "path": ["ink_primitives", "types", "Balance"], "def": { "primitive": "u128" } } },
+                { "id": 3, "type": { "def": { "primitive": "u8" } } }
+            ]
+        });
+
+        let plain =
+            generate_typescript_module("Token", &metadata, ModuleOptions::default()).unwrap();
+        assert!(plain.contains("owner: string,"));
+        assert!(!plain.contains("export type AccountId"));
+
+        let branded = generate_typescript_module(
+            "Token",
+            &metadata,
+            ModuleOptions {
+                branded: true,
+                strict_bigint: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(branded.contains("owner: AccountId,"));
+        assert!(branded.contains("): Promise<Balance>;"));
+        assert!(branded.contains("export type AccountId = Brand<string, 'AccountId'>;"));
+        assert!(branded.contains("export type Balance = Brand<bigint, 'Balance'>;"));
+        assert!(branded.contains("export function toAccountId(value: string): AccountId {"));
+    }
+
+    #[test]
+    fn test_generate_error_types() {
+        let metadata = serde_json::json!({
+            "spec": {
+                "constructors": [],
+                "messages": [{
+                    "label": "transfer",
+                    "mutates": true,
+                    "args": [],
+                    "returnType": { "type": 0 }
+                }],
+                "events": []
+            },
+            "types": [
+                { "id": 0, "type": { "path": ["Result"], "params": [{ "type": 1 }, { "type": 4 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 1, "type": { "path": ["Result"], "params": [{ "type": 2 }, { "type": 3 }], "def": { "variant": { "variants": [] } } } },
+                { "id": 2, "type": { "def": { "tuple": [] } } },
+                { "id": 3, "type": { "path": ["token", "Error"], "def": { "variant": { "variants": [
+                    { "name": "InsufficientBalance" },
+                    { "name": "Custom", "fields": [{ "type": 5 }] }
+                ] } } } },
+                { "id": 4, "type": { "path": ["ink_primitives", "LangError"], "def": { "variant": { "variants": [{ "name": "CouldNotReadInput" }] } } } },
+                { "id": 5, "type": { "def": { "primitive": "str" } } }
+            ]
+        });
+
+        let module =
+            generate_typescript_module("Token", &metadata, ModuleOptions::default()).unwrap();
+        assert!(module.contains(
+            "export type TokenError =\n  { type: 'InsufficientBalance' }\n  | { type: 'Custom', value: string }\n;"
+        ));
+        assert!(module.contains(
+            "export const TokenErrorVariants = ['InsufficientBalance', 'Custom'] as const;"
+        ));
+        assert!(module.contains("export function isTokenError<V extends TokenErrorVariant>("));
+        assert!(module.contains(
+            "    decoded !== undefined &&\n    decoded === error &&\n    (variant === undefined || decoded.type === variant)"
+        ));
+        assert!(module.contains("export type LangError =\n  { type: 'CouldNotReadInput' }\n;"));
+        assert!(module
+            .contains("export function decodeLangError(value: unknown): LangError | undefined {"));
+        assert_eq!(module.matches("function decodeEnum<").count(), 1);
+    }
 }
@@ -0,0 +1,110 @@
+// Manifest of contract artifacts
+//
+// `verify package` records the source archive it creates for a contract in
+// `.glin-forge/artifacts.json`, keyed by contract name: where the archive is,
+// its SHA-256 and the files it holds, so an explorer or auditor given the
+// archive can check it is the one the project built and deployed from.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Manifest file, relative to the project root
+pub const ARTIFACTS_FILE: &str = ".glin-forge/artifacts.json";
+
+/// What is recorded about a contract's artifacts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_archive: Option<SourceArchive>,
+}
+
+/// A source archive made for verification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceArchive {
+    pub path: PathBuf,
+    /// `0x`-prefixed SHA-256 of the archive file
+    pub sha256: String,
+    /// Paths inside the archive
+    pub files: Vec<String>,
+}
+
+/// Artifact records keyed by contract name
+pub struct ArtifactManifest {
+    path: PathBuf,
+    contracts: BTreeMap<String, ArtifactRecord>,
+}
+
+impl ArtifactManifest {
+    /// Load a manifest, empty when nothing was recorded yet
+    pub fn open(path: &Path) -> Result<Self> {
+        let contracts = if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid artifacts manifest {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            contracts,
+        })
+    }
+
+    /// Load the manifest of the current project
+    pub fn open_default() -> Result<Self> {
+        Self::open(Path::new(ARTIFACTS_FILE))
+    }
+
+    pub fn get(&self, contract: &str) -> Option<&ArtifactRecord> {
+        self.contracts.get(contract)
+    }
+
+    /// Record a contract's source archive, replacing the file atomically
+    pub fn record_source_archive(
+        &mut self,
+        contract: &str,
+        version: &str,
+        archive: SourceArchive,
+    ) -> Result<()> {
+        let record = self.contracts.entry(contract.to_string()).or_default();
+        record.version = Some(version.to_string());
+        record.source_archive = Some(archive);
+        super::deployments::write_json(&self.path, &self.contracts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_source_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".glin-forge/artifacts.json");
+        let mut manifest = ArtifactManifest::open(&path).unwrap();
+        assert_eq!(manifest.get("flipper"), None);
+
+        let archive = SourceArchive {
+            path: PathBuf::from("target/verify/flipper-0.1.0-source.tar.gz"),
+            sha256: "0x01".to_string(),
+            files: vec!["flipper-0.1.0/Cargo.toml".to_string()],
+        };
+        manifest
+            .record_source_archive("flipper", "0.1.0", archive.clone())
+            .unwrap();
+        manifest
+            .record_source_archive("token", "1.0.0", archive.clone())
+            .unwrap();
+
+        let reopened = ArtifactManifest::open(&path).unwrap();
+        let record = reopened.get("flipper").unwrap();
+        assert_eq!(record.version.as_deref(), Some("0.1.0"));
+        assert_eq!(record.source_archive.as_ref(), Some(&archive));
+        assert!(reopened.get("token").is_some());
+    }
+}
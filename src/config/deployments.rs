@@ -0,0 +1,263 @@
+// Registry of deployed contracts
+//
+// `deploy` and `instantiate` record every contract they instantiate in
+// `.glin-forge/deployments.json`, keyed by network and contract name, so other
+// commands can refer to a contract by name and know where its history starts.
+// Contracts missing from the registry have their instantiation block found on
+// chain, by bisecting the heights at which `Contracts.ContractInfoOf` exists.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::utils::AccountId32;
+
+use glin_client::{GlinClient, GlinConfig};
+
+/// Registry file, relative to the project root
+pub const DEPLOYMENTS_FILE: &str = ".glin-forge/deployments.json";
+
+/// A contract instantiated on a network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    pub address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    /// Block the contract was instantiated in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<String>,
+}
+
+/// Deployments keyed by network, then contract name
+pub struct DeploymentRegistry {
+    path: PathBuf,
+    networks: BTreeMap<String, BTreeMap<String, DeploymentRecord>>,
+}
+
+impl DeploymentRegistry {
+    /// Load a registry, empty when nothing was deployed yet
+    pub fn open(path: &Path) -> Result<Self> {
+        let networks = if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid deployments file {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            networks,
+        })
+    }
+
+    /// Load the registry of the current project
+    pub fn open_default() -> Result<Self> {
+        Self::open(Path::new(DEPLOYMENTS_FILE))
+    }
+
+    /// Deployment of a contract, by name or address
+    pub fn find(&self, network: &str, contract: &str) -> Option<(&str, &DeploymentRecord)> {
+        let deployments = self.networks.get(network)?;
+        deployments
+            .get_key_value(contract)
+            .or_else(|| deployments.iter().find(|(_, d)| d.address == contract))
+            .map(|(name, deployment)| (name.as_str(), deployment))
+    }
+
+    /// Deployments recorded on a network, by name
+    pub fn deployments(&self, network: &str) -> impl Iterator<Item = (&str, &DeploymentRecord)> {
+        self.networks
+            .get(network)
+            .into_iter()
+            .flatten()
+            .map(|(name, deployment)| (name.as_str(), deployment))
+    }
+
+    /// Record a deployment, replacing the file atomically
+    pub fn record(
+        &mut self,
+        network: &str,
+        name: &str,
+        deployment: DeploymentRecord,
+    ) -> Result<()> {
+        self.networks
+            .entry(network.to_string())
+            .or_default()
+            .insert(name.to_string(), deployment);
+        write_json(&self.path, &self.networks)
+    }
+}
+
+/// Write a JSON file atomically, creating its directory
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(value)?)?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Address of a contract given by address or registry name, with its recorded deployment
+pub fn resolve(network: &str, contract: &str) -> Result<(String, Option<DeploymentRecord>)> {
+    let registry = DeploymentRegistry::open_default()?;
+    let deployment = registry.find(network, contract).map(|(_, d)| d.clone());
+    if crate::contract::parse_account_id(contract).is_ok() {
+        return Ok((contract.to_string(), deployment));
+    }
+    match deployment {
+        Some(deployment) => Ok((deployment.address.clone(), Some(deployment))),
+        None => anyhow::bail!(
+            "{} is neither an address nor a contract deployed on {} (see {})",
+            contract,
+            network,
+            DEPLOYMENTS_FILE
+        ),
+    }
+}
+
+/// Block a contract was instantiated in: from the registry, or searched on chain
+pub async fn instantiation_block(
+    client: &GlinClient,
+    rpc: &LegacyRpcMethods<GlinConfig>,
+    network: &str,
+    contract: &AccountId32,
+) -> Result<u64> {
+    let registry = DeploymentRegistry::open_default()?;
+    let recorded = registry
+        .find(network, &contract.to_string())
+        .and_then(|(_, d)| d.block);
+    if let Some(block) = recorded {
+        return Ok(block);
+    }
+
+    let latest = client.blocks().at_latest().await?.number() as u64;
+    let exists_at = |number: u64| async move {
+        let hash = rpc
+            .chain_get_block_hash(Some(number.into()))
+            .await?
+            .with_context(|| format!("Block #{} not found", number))?;
+        let query = subxt::dynamic::storage(
+            "Contracts",
+            "ContractInfoOf",
+            vec![subxt::dynamic::Value::from_bytes(contract.0)],
+        );
+        let info = client
+            .storage()
+            .at(hash)
+            .fetch(&query)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to read the state of block #{}; the node may have pruned it",
+                    number
+                )
+            })?;
+        anyhow::Ok(info.is_some())
+    };
+    first_block(latest, exists_at)
+        .await?
+        .with_context(|| format!("Contract {} does not exist on {}", contract, network))
+}
+
+/// Lowest block up to `latest` from which `exists` holds, assuming it keeps holding
+async fn first_block<F, Fut>(latest: u64, exists: F) -> Result<Option<u64>>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    if !exists(latest).await? {
+        return Ok(None);
+    }
+    let (mut low, mut high) = (0, latest);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if exists(middle).await? {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    Ok(Some(low))
+}
+
+/// Contract name declared in ink! metadata JSON
+pub fn contract_name(metadata_json: &str) -> Option<String> {
+    let metadata: serde_json::Value = serde_json::from_str(metadata_json).ok()?;
+    metadata["contract"]["name"].as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deployment_registry() {
+        let dir =
+            std::env::temp_dir().join(format!("glin-forge-deployments-{}", std::process::id()));
+        let path = dir.join(".glin-forge/deployments.json");
+
+        let mut registry = DeploymentRegistry::open(&path).unwrap();
+        assert_eq!(registry.find("testnet", "flipper"), None);
+        let deployment = DeploymentRecord {
+            address: "5Contract".to_string(),
+            code_hash: Some("0xc0de".to_string()),
+            tx_hash: None,
+            block: Some(1200),
+            block_hash: Some("0xb10c".to_string()),
+        };
+        registry
+            .record("testnet", "flipper", deployment.clone())
+            .unwrap();
+
+        let registry = DeploymentRegistry::open(&path).unwrap();
+        assert_eq!(
+            registry.find("testnet", "flipper"),
+            Some(("flipper", &deployment))
+        );
+        assert_eq!(
+            registry.find("testnet", "5Contract"),
+            Some(("flipper", &deployment))
+        );
+        assert_eq!(registry.find("mainnet", "flipper"), None);
+
+        assert_eq!(
+            contract_name(r#"{"contract": {"name": "flipper", "version": "0.1.0"}}"#).as_deref(),
+            Some("flipper")
+        );
+        assert_eq!(contract_name("{}"), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_first_block() {
+        let probed = std::sync::Mutex::new(0);
+        let deployed_at = |from: u64| {
+            let probed = &probed;
+            move |number: u64| async move {
+                *probed.lock().unwrap() += 1;
+                Ok(number >= from)
+            }
+        };
+
+        assert_eq!(
+            first_block(1000, deployed_at(637)).await.unwrap(),
+            Some(637)
+        );
+        assert!(*probed.lock().unwrap() <= 12);
+        assert_eq!(first_block(1000, deployed_at(0)).await.unwrap(), Some(0));
+        assert_eq!(
+            first_block(1000, deployed_at(1000)).await.unwrap(),
+            Some(1000)
+        );
+        assert_eq!(first_block(1000, deployed_at(1001)).await.unwrap(), None);
+    }
+}
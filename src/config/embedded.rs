@@ -0,0 +1,865 @@
+// Embedded JavaScript engine
+//
+// Loads `.ts`/`.js` config files and runs simple scripts on QuickJS inside
+// glin-forge, so that they work on machines without Node.js, Bun or Deno.
+// TypeScript runs once its type syntax is stripped: annotations of
+// declarations and function parameters, return types, interfaces, type
+// aliases, type-only imports, generic arguments, `as`, `satisfies` and
+// non-null `!`. Enums, namespaces, decorators and typed class members are not
+// supported. Only local modules resolve, plus two built-in ones:
+//
+// - `@glin-forge/sdk`, with `defineConfig` and the `Network` and `Signer`
+//   enums, enough for config files; scripts using the rest of the SDK need an
+//   installed runtime
+// - `glin-forge`, for scripts: `rpc(method, params)` calls the RPC server of
+//   `run` with the server's (snake_case) params, and `deploy`, `instantiate`,
+//   `uploadCode`, `call` and `query` wrap it, failing when it reports a failure
+//
+// Scripts get `console`, `process.env`, `process.argv` and `process.exit`.
+
+use anyhow::Context;
+use regex::Regex;
+use rquickjs::loader::{Loader, Resolver};
+use rquickjs::{Ctx, Function, Module, Object, Persistent, Value};
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+/// Built-in module of scripts run on the embedded engine
+pub const SCRIPT_MODULE: &str = "glin-forge";
+
+const SDK_MODULE: &str = "@glin-forge/sdk";
+
+const SDK_SOURCE: &str = r#"
+export function defineConfig(config) { return config; }
+export const Network = { Testnet: 'testnet', Mainnet: 'mainnet', Local: 'local' };
+export const Signer = { Alice: 'alice', Bob: 'bob', Charlie: 'charlie', Dave: 'dave', Eve: 'eve', Ferdie: 'ferdie' };
+export default { defineConfig, Network, Signer };
+"#;
+
+const SCRIPT_SOURCE: &str = r#"
+export { defineConfig, Network, Signer } from '@glin-forge/sdk';
+export const network = process.env.GLIN_FORGE_NETWORK;
+export function rpc(method, params) {
+  return JSON.parse(globalThis.__glinForgeRpc(method, JSON.stringify(params ?? {})));
+}
+const transaction = (method, failure) => (params) => {
+  const args = params?.args?.map((arg) => (typeof arg === 'string' ? arg : JSON.stringify(arg)));
+  const result = rpc(method, args ? { ...params, args } : params);
+  if (result && result.success === false) {
+    throw new Error(result.error || failure);
+  }
+  return result;
+};
+export const deploy = transaction('deploy', 'Deployment failed');
+export const instantiate = transaction('instantiate', 'Instantiation failed');
+export const uploadCode = transaction('uploadCode', 'Upload failed');
+export const call = transaction('call', 'Call failed');
+export const query = transaction('query', 'Query failed');
+"#;
+
+const PRELUDE: &str = r#"
+const format = (value) => {
+  if (typeof value === 'string') return value;
+  if (value instanceof Error) return value.stack ? `${value}\n${value.stack}` : String(value);
+  if (typeof value === 'function' || typeof value === 'undefined') return String(value);
+  try { return JSON.stringify(value, (_, v) => (typeof v === 'bigint' ? v.toString() : v), 2); } catch { return String(value); }
+};
+const print = (stream) => (...values) => globalThis.__glinForgePrint(stream, values.map(format).join(' '));
+globalThis.console = { log: print(1), info: print(1), debug: print(1), warn: print(2), error: print(2) };
+globalThis.process = {
+  env: JSON.parse(globalThis.__glinForgeEnv),
+  argv: JSON.parse(globalThis.__glinForgeArgv),
+  exit: (code) => globalThis.__glinForgeExit(code ?? 0),
+};
+"#;
+
+/// Default export of a config file, as JSON
+pub fn evaluate_config(path: &Path) -> anyhow::Result<serde_json::Value> {
+    let json = evaluate(path, true)?.unwrap_or_default();
+    serde_json::from_str(&json).context("Config file exports no configuration")
+}
+
+/// Run a script to completion, failing when it throws or leaves a rejected promise unhandled
+pub fn run_script(path: &Path) -> anyhow::Result<()> {
+    evaluate(path, false).map(|_| ())
+}
+
+/// Whether one of the modules imports the engine's built-in script module, so that only the
+/// embedded engine runs them
+pub fn imports_script_module<'a>(sources: impl IntoIterator<Item = &'a PathBuf>) -> bool {
+    static IMPORT: OnceLock<Regex> = OnceLock::new();
+    let import = IMPORT.get_or_init(|| {
+        Regex::new(r#"(?:\bfrom\s*|\bimport\s*\(?\s*)['"]glin-forge['"]"#)
+            .expect("valid import pattern")
+    });
+    sources.into_iter().any(|source| {
+        std::fs::read_to_string(source).is_ok_and(|content| import.is_match(&content))
+    })
+}
+
+/// A rejected promise without a handler yet, and its reason
+type Rejection = (Persistent<Value<'static>>, String);
+
+/// Evaluate a module, returning the JSON of its default export when `export` is set
+fn evaluate(path: &Path, export: bool) -> anyhow::Result<Option<String>> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Module not found: {}", path.display()))?;
+    let runtime = rquickjs::Runtime::new()?;
+    let context = rquickjs::Context::full(&runtime)?;
+    runtime.set_loader(ModuleResolver, ModuleLoader);
+
+    let rejections: Rc<RefCell<Vec<Rejection>>> = Default::default();
+    let tracked = rejections.clone();
+    runtime.set_host_promise_rejection_tracker(Some(Box::new(
+        move |ctx, promise, reason, handled| {
+            let mut tracked = tracked.borrow_mut();
+            if handled {
+                tracked.retain(|(rejected, _)| {
+                    rejected
+                        .clone()
+                        .restore(&ctx)
+                        .is_ok_and(|rejected| rejected != promise)
+                });
+            } else {
+                tracked.push((Persistent::save(&ctx, promise), describe(&reason)));
+            }
+        },
+    )));
+
+    let result = context.with(|ctx| -> anyhow::Result<Option<String>> {
+        install_globals(&ctx).map_err(|e| js_error(&ctx, e))?;
+        let namespace = Module::import(&ctx, path.to_string_lossy().as_bytes())
+            .and_then(|promise| promise.finish::<Object>())
+            .map_err(|e| match e {
+                rquickjs::Error::WouldBlock => anyhow::anyhow!(
+                    "The module awaits something the embedded engine cannot run, e.g. a timer"
+                ),
+                e => js_error(&ctx, e),
+            })?;
+        // Promises the module started without awaiting them, e.g. `main().catch(...)`
+        while ctx.execute_pending_job() {}
+
+        if let Some((_, reason)) = rejections.borrow().first() {
+            anyhow::bail!("Unhandled promise rejection: {}", reason);
+        }
+        if !export {
+            return Ok(None);
+        }
+        let default: Value = namespace.get("default").map_err(|e| js_error(&ctx, e))?;
+        let json = ctx.json_stringify(default).map_err(|e| js_error(&ctx, e))?;
+        json.map(|json| json.to_string())
+            .transpose()
+            .map_err(|e| js_error(&ctx, e))
+    });
+    // Persistent values must not outlive the runtime
+    rejections.borrow_mut().clear();
+    result.with_context(|| format!("Failed to evaluate {}", path.display()))
+}
+
+fn install_globals<'js>(ctx: &Ctx<'js>) -> rquickjs::Result<()> {
+    let globals = ctx.globals();
+    let env: std::collections::HashMap<String, String> = std::env::vars().collect();
+    globals.set(
+        "__glinForgeEnv",
+        serde_json::to_string(&env).unwrap_or_default(),
+    )?;
+    let argv: Vec<String> = std::env::args().collect();
+    globals.set(
+        "__glinForgeArgv",
+        serde_json::to_string(&argv).unwrap_or_default(),
+    )?;
+    globals.set(
+        "__glinForgePrint",
+        Function::new(ctx.clone(), |stream: i32, text: String| {
+            if stream == 2 {
+                eprintln!("{}", text);
+            } else {
+                println!("{}", text);
+            }
+        })?,
+    )?;
+    globals.set(
+        "__glinForgeExit",
+        Function::new(ctx.clone(), |code: i32| -> () {
+            let _ = std::io::stdout().flush();
+            let _ = std::io::stderr().flush();
+            std::process::exit(code)
+        })?,
+    )?;
+    globals.set(
+        "__glinForgeRpc",
+        Function::new(
+            ctx.clone(),
+            |ctx: Ctx<'js>, method: String, params: String| -> rquickjs::Result<String> {
+                serde_json::from_str(&params)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|params| crate::rpc::client::call(&method, params))
+                    .map(|result| result.to_string())
+                    .map_err(|e| rquickjs::Exception::throw_message(&ctx, &format!("{:#}", e)))
+            },
+        )?,
+    )?;
+    ctx.eval::<(), _>(PRELUDE)
+}
+
+/// Error of the engine, with the message and stack of a thrown exception
+fn js_error(ctx: &Ctx<'_>, error: rquickjs::Error) -> anyhow::Error {
+    match error {
+        rquickjs::Error::Exception => anyhow::anyhow!("{}", describe(&ctx.catch())),
+        error => anyhow::anyhow!("{}", error),
+    }
+}
+
+fn describe(value: &Value<'_>) -> String {
+    if let Some(exception) = value.as_exception() {
+        let message = exception.message().unwrap_or_default();
+        return match exception.stack() {
+            Some(stack) if !stack.trim().is_empty() => format!("{}\n{}", message, stack.trim_end()),
+            _ => message,
+        };
+    }
+    match value.as_string().and_then(|s| s.to_string().ok()) {
+        Some(text) => text,
+        None => format!("{:?}", value),
+    }
+}
+
+/// Resolves relative imports to files, and the built-in modules by name
+struct ModuleResolver;
+
+impl Resolver for ModuleResolver {
+    fn resolve(&mut self, _ctx: &Ctx<'_>, base: &str, name: &str) -> rquickjs::Result<String> {
+        if name == SDK_MODULE || name == SCRIPT_MODULE || Path::new(name).is_absolute() {
+            return Ok(name.to_string());
+        }
+        if name.starts_with("./") || name.starts_with("../") {
+            let dir = Path::new(base).parent().unwrap_or(Path::new("."));
+            if let Some(path) = crate::cli::run::resolve_module(dir, name) {
+                return Ok(path.to_string_lossy().to_string());
+            }
+        }
+        Err(rquickjs::Error::new_resolving_message(
+            base,
+            name,
+            "the embedded engine only loads local modules, @glin-forge/sdk's config helpers and glin-forge; install Node.js, Bun or Deno for packages",
+        ))
+    }
+}
+
+/// Loads the built-in modules, and files with their TypeScript types stripped
+struct ModuleLoader;
+
+impl Loader for ModuleLoader {
+    fn load<'js>(
+        &mut self,
+        ctx: &Ctx<'js>,
+        name: &str,
+    ) -> rquickjs::Result<Module<'js, rquickjs::module::Declared>> {
+        match name {
+            SDK_MODULE => return Module::declare(ctx.clone(), name, SDK_SOURCE),
+            SCRIPT_MODULE => return Module::declare(ctx.clone(), name, SCRIPT_SOURCE),
+            _ => {}
+        }
+        let path = Path::new(name);
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| rquickjs::Error::new_loading_message(name, e.to_string()))?;
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let source = match extension {
+            "json" => format!("export default {};", source),
+            "ts" | "mts" | "cts" | "tsx" => strip_types(&source),
+            "cjs" => commonjs(&source),
+            _ if is_commonjs(&source) => commonjs(&source),
+            _ => source,
+        };
+        Module::declare(ctx.clone(), name, source)
+    }
+}
+
+/// Whether a `.js` module is CommonJS: it assigns exports and has no import or export
+fn is_commonjs(source: &str) -> bool {
+    static ESM: OnceLock<Regex> = OnceLock::new();
+    let esm = ESM.get_or_init(|| {
+        Regex::new(r"(?m)^\s*(?:import\s*[\w{*'\x22]|export\s)").expect("valid module pattern")
+    });
+    !esm.is_match(source) && (source.contains("module.exports") || source.contains("exports."))
+}
+
+/// A CommonJS module as an ES module whose default export is `module.exports`
+fn commonjs(source: &str) -> String {
+    format!(
+        "const module = {{ exports: {{}} }};\nconst exports = module.exports;\nconst require = (name) => {{ throw new Error(`require('${{name}}') is not supported by the embedded engine; use import`); }};\n{}\nexport default module.exports;\n",
+        source
+    )
+}
+
+/// JavaScript of a TypeScript module: its type syntax removed, everything else kept as is
+pub fn strip_types(source: &str) -> String {
+    let source = strip_inline_type_imports(source);
+    let chars: Vec<char> = source.chars().collect();
+    let mut out = String::with_capacity(source.len());
+    strip_range(&chars, 0, chars.len(), &mut out);
+    out
+}
+
+/// Remove the `type X` specifiers of import and export lists
+fn strip_inline_type_imports(source: &str) -> String {
+    static LIST: OnceLock<Regex> = OnceLock::new();
+    let list = LIST.get_or_init(|| {
+        Regex::new(r"\b(import|export)(\s*)\{([^}]*)\}").expect("valid import list pattern")
+    });
+    list.replace_all(source, |captures: &regex::Captures| {
+        let specifiers: Vec<&str> = captures[3]
+            .split(',')
+            .filter(|specifier| !specifier.trim_start().starts_with("type "))
+            .collect();
+        format!(
+            "{}{}{{{}}}",
+            &captures[1],
+            &captures[2],
+            specifiers.join(",")
+        )
+    })
+    .into_owned()
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn skip_ws(chars: &[char], mut i: usize, end: usize) -> usize {
+    while i < end && chars[i].is_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+fn ident_end(chars: &[char], mut i: usize, end: usize) -> usize {
+    while i < end && is_ident(chars[i]) {
+        i += 1;
+    }
+    i
+}
+
+/// Index after a string or template literal starting at `i`
+fn skip_string(chars: &[char], i: usize, end: usize) -> usize {
+    let quote = chars[i];
+    let mut j = i + 1;
+    while j < end {
+        match chars[j] {
+            '\\' => j += 2,
+            c if c == quote => return j + 1,
+            _ => j += 1,
+        }
+    }
+    end
+}
+
+/// Index after a comment starting at `i`, if one does
+fn skip_comment(chars: &[char], i: usize, end: usize) -> Option<usize> {
+    match chars.get(i + 1) {
+        Some('/') if chars[i] == '/' => {
+            let mut j = i;
+            while j < end && chars[j] != '\n' {
+                j += 1;
+            }
+            Some(j)
+        }
+        Some('*') if chars[i] == '/' => {
+            let mut j = i + 2;
+            while j + 1 < end && !(chars[j] == '*' && chars[j + 1] == '/') {
+                j += 1;
+            }
+            Some((j + 2).min(end))
+        }
+        _ => None,
+    }
+}
+
+/// Index of the bracket closing the one at `open`; `<` counts angle brackets only
+fn matching(chars: &[char], open: usize, end: usize) -> Option<usize> {
+    let angle = chars[open] == '<';
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < end {
+        let c = chars[i];
+        if c == '\'' || c == '"' || c == '`' {
+            i = skip_string(chars, i, end);
+            continue;
+        }
+        if c == '=' && chars.get(i + 1) == Some(&'>') {
+            i += 2;
+            continue;
+        }
+        match c {
+            '<' if angle => depth += 1,
+            '>' if angle => depth -= 1,
+            '(' | '[' | '{' if !angle => depth += 1,
+            ')' | ']' | '}' if !angle => depth -= 1,
+            // Angle brackets hold types only, so this is a comparison
+            ';' | '=' if angle => return None,
+            _ => {}
+        }
+        if depth == 0 {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index after the type starting at `i` (after a `:`, `as` or `satisfies`)
+fn skip_type(chars: &[char], i: usize, end: usize) -> usize {
+    let mut i = skip_ws(chars, i, end);
+    if i < end && (chars[i] == '|' || chars[i] == '&') {
+        i = skip_ws(chars, i + 1, end);
+    }
+    loop {
+        if i >= end {
+            return end;
+        }
+        match chars[i] {
+            '(' => {
+                let Some(close) = matching(chars, i, end) else {
+                    return i;
+                };
+                i = close + 1;
+                let j = skip_ws(chars, i, end);
+                if chars.get(j) == Some(&'=') && chars.get(j + 1) == Some(&'>') {
+                    // Function type: its return type follows
+                    i = skip_ws(chars, j + 2, end);
+                    continue;
+                }
+            }
+            '{' | '[' => match matching(chars, i, end) {
+                Some(close) => i = close + 1,
+                None => return i,
+            },
+            '\'' | '"' | '`' => i = skip_string(chars, i, end),
+            c if is_ident_start(c) || c.is_ascii_digit() || c == '-' => {
+                let start = i;
+                i += 1;
+                while i < end && (is_ident(chars[i]) || chars[i] == '.') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if matches!(
+                    word.as_str(),
+                    "keyof" | "typeof" | "readonly" | "unique" | "infer" | "new"
+                ) {
+                    i = skip_ws(chars, i, end);
+                    continue;
+                }
+                if chars.get(i) == Some(&'<') {
+                    match matching(chars, i, end) {
+                        Some(close) => i = close + 1,
+                        None => return i,
+                    }
+                }
+            }
+            _ => return i,
+        }
+        // Array and indexed access types
+        while chars.get(i) == Some(&'[') {
+            match matching(chars, i, end) {
+                Some(close) => i = close + 1,
+                None => return i,
+            }
+        }
+        let j = skip_ws(chars, i, end);
+        let operator = chars.get(j).copied();
+        if matches!(operator, Some('|') | Some('&')) && chars.get(j + 1) != operator.as_ref() {
+            i = skip_ws(chars, j + 1, end);
+            continue;
+        }
+        return i;
+    }
+}
+
+/// Index after the statement starting at `i`, for removed type declarations
+fn statement_end(chars: &[char], mut i: usize, end: usize) -> usize {
+    let mut depth = 0usize;
+    let mut last = ' ';
+    while i < end {
+        let c = chars[i];
+        match c {
+            '\'' | '"' | '`' => {
+                i = skip_string(chars, i, end);
+                last = c;
+                continue;
+            }
+            '(' | '[' | '{' | '<' => depth += 1,
+            // The `>` of an arrow closes nothing
+            ')' | ']' | '}' | '>' if depth > 0 && !(c == '>' && i > 0 && chars[i - 1] == '=') => {
+                depth -= 1
+            }
+            ';' if depth == 0 => return i + 1,
+            '\n' if depth == 0 => {
+                let next = skip_ws(chars, i, end);
+                let continued = matches!(last, '=' | '|' | '&' | ',')
+                    || matches!(chars.get(next), Some('|') | Some('&') | Some('='));
+                if !continued {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+        if !c.is_whitespace() {
+            last = c;
+        }
+        i += 1;
+    }
+    end
+}
+
+/// Whether the output ends with an operand, so that `!`, `as` and `<` after it are TypeScript
+fn ends_with_operand(out: &str) -> bool {
+    out.chars()
+        .last()
+        .is_some_and(|c| is_ident(c) || matches!(c, ')' | ']' | '}' | '\'' | '"' | '`'))
+}
+
+/// Parameters of a function, with their types and `?` removed
+fn strip_params(chars: &[char], start: usize, end: usize, out: &mut String) {
+    let mut i = start;
+    while i <= end {
+        // One parameter, up to a comma outside brackets
+        let mut j = i;
+        let mut depth = 0usize;
+        let mut colon = None;
+        let mut default = false;
+        while j < end {
+            let c = chars[j];
+            if c == '\'' || c == '"' || c == '`' {
+                j = skip_string(chars, j, end);
+                continue;
+            }
+            match c {
+                '(' | '[' | '{' | '<' => depth += 1,
+                ')' | ']' | '}' | '>' if depth > 0 => depth -= 1,
+                ',' if depth == 0 => break,
+                ':' if depth == 0 && !default && colon.is_none() => colon = Some(j),
+                '=' if depth == 0 && chars.get(j + 1) != Some(&'>') => default = true,
+                _ => {}
+            }
+            j += 1;
+        }
+        match colon {
+            Some(colon) => {
+                let mut name: String = chars[i..colon].iter().collect();
+                let trimmed = name.trim_end().trim_end_matches('?').len();
+                name.truncate(trimmed);
+                out.push_str(&name);
+                let rest = skip_type(chars, colon + 1, j);
+                strip_range(chars, rest, j, out);
+            }
+            None => strip_range(chars, i, j, out),
+        }
+        if j < end {
+            out.push(',');
+        }
+        i = j + 1;
+    }
+}
+
+/// Parameters and arrow of an arrow function whose parameter list opens at `open`: the index
+/// of the closing parenthesis and of the `=>`
+fn arrow(chars: &[char], open: usize, end: usize) -> Option<(usize, usize)> {
+    let close = matching(chars, open, end)?;
+    let mut j = skip_ws(chars, close + 1, end);
+    if chars.get(j) == Some(&':') {
+        j = skip_ws(chars, skip_type(chars, j + 1, end), end);
+    }
+    (chars.get(j) == Some(&'=') && chars.get(j + 1) == Some(&'>')).then_some((close, j))
+}
+
+fn strip_range(chars: &[char], start: usize, end: usize, out: &mut String) {
+    let mut i = start;
+    while i < end {
+        let c = chars[i];
+        if c == '\'' || c == '"' || c == '`' {
+            let next = skip_string(chars, i, end);
+            out.extend(&chars[i..next]);
+            i = next;
+            continue;
+        }
+        if c == '/' {
+            if let Some(next) = skip_comment(chars, i, end) {
+                out.extend(&chars[i..next]);
+                i = next;
+                continue;
+            }
+        }
+        if c == '(' {
+            if let Some((close, arrow)) = arrow(chars, i, end) {
+                out.push('(');
+                strip_params(chars, i + 1, close, out);
+                out.push_str(") ");
+                i = arrow;
+                continue;
+            }
+        }
+        if c == '!' && ends_with_operand(out) && chars.get(i + 1) != Some(&'=') {
+            // Non-null assertion
+            i += 1;
+            continue;
+        }
+        if !is_ident_start(c) || (i > start && (is_ident(chars[i - 1]) || chars[i - 1] == '.')) {
+            out.push(c);
+            i += 1;
+            continue;
+        }
+
+        let word_end = ident_end(chars, i, end);
+        let word: String = chars[i..word_end].iter().collect();
+        let after = skip_ws(chars, word_end, end);
+        let next_word: String = chars[after..ident_end(chars, after, end)].iter().collect();
+        match word.as_str() {
+            "import" if next_word == "type" => {
+                i = statement_end(chars, i, end);
+            }
+            "export"
+                if matches!(next_word.as_str(), "interface" | "declare")
+                    || (next_word == "type" && {
+                        let name = skip_ws(chars, after + 4, end);
+                        chars
+                            .get(name)
+                            .is_some_and(|&c| is_ident_start(c) || c == '{')
+                    }) =>
+            {
+                // The declaration after it is removed
+                i = after;
+            }
+            "type"
+                if {
+                    let name_end = ident_end(chars, after, end);
+                    let mut j = skip_ws(chars, name_end, end);
+                    if chars.get(j) == Some(&'<') {
+                        j = matching(chars, j, end)
+                            .map_or(j, |close| skip_ws(chars, close + 1, end));
+                    }
+                    name_end > after && chars.get(j) == Some(&'=') && chars.get(j + 1) != Some(&'=')
+                } =>
+            {
+                i = statement_end(chars, i, end);
+            }
+            "type" if chars.get(after) == Some(&'{') => {
+                // `export type { ... }` lists
+                i = statement_end(chars, i, end);
+            }
+            "interface" if !next_word.is_empty() => {
+                let body = (after..end).find(|&j| chars[j] == '{');
+                i = body
+                    .and_then(|body| matching(chars, body, end))
+                    .map_or(end, |close| close + 1);
+            }
+            "declare" if !next_word.is_empty() => {
+                i = statement_end(chars, i, end);
+            }
+            "as" | "satisfies" if ends_with_operand(out.trim_end()) && !next_word.is_empty() => {
+                // The `as` of import and export lists never gets here, those are copied whole
+                while out.ends_with(char::is_whitespace) {
+                    out.pop();
+                }
+                i = skip_type(chars, after, end);
+            }
+            "const" | "let" | "var" => {
+                out.push_str(&word);
+                out.extend(&chars[word_end..after]);
+                // Binding: a name or a destructuring pattern
+                let mut j = match chars.get(after) {
+                    Some('{') | Some('[') => {
+                        matching(chars, after, end).map_or(after, |close| close + 1)
+                    }
+                    _ => ident_end(chars, after, end),
+                };
+                strip_range(chars, after, j, out);
+                if chars.get(j) == Some(&'!') {
+                    j += 1;
+                }
+                let colon = skip_ws(chars, j, end);
+                i = if chars.get(colon) == Some(&':') {
+                    skip_type(chars, colon + 1, end)
+                } else {
+                    j
+                };
+            }
+            "function" => {
+                out.push_str(&word);
+                let mut j = word_end;
+                // Generator star and name
+                while j < end && (chars[j].is_whitespace() || chars[j] == '*' || is_ident(chars[j]))
+                {
+                    out.push(chars[j]);
+                    j += 1;
+                }
+                if chars.get(j) == Some(&'<') {
+                    j = matching(chars, j, end).map_or(j, |close| close + 1);
+                }
+                match (chars.get(j), matching(chars, j, end)) {
+                    (Some('('), Some(close)) => {
+                        out.push('(');
+                        strip_params(chars, j + 1, close, out);
+                        out.push(')');
+                        let colon = skip_ws(chars, close + 1, end);
+                        i = if chars.get(colon) == Some(&':') {
+                            skip_type(chars, colon + 1, end)
+                        } else {
+                            close + 1
+                        };
+                    }
+                    _ => i = j,
+                }
+            }
+            "import" | "export" if matches!(chars.get(after), Some('{') | Some('*')) => {
+                // Lists are copied as they are, their `as` renames included
+                let list_end = match chars[after] {
+                    '{' => matching(chars, after, end).map_or(end, |close| close + 1),
+                    _ => (after..end)
+                        .find(|&j| matches!(chars[j], '\'' | '"'))
+                        .unwrap_or(end),
+                };
+                out.extend(&chars[i..list_end]);
+                i = list_end;
+            }
+            _ => {
+                out.push_str(&word);
+                i = word_end;
+                // Type arguments of a call, e.g. `new Map<string, number>()`
+                if chars.get(i) == Some(&'<') {
+                    if let Some(close) = matching(chars, i, end) {
+                        if chars.get(close + 1) == Some(&'(') {
+                            i = close + 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_types() {
+        let source = r#"import { defineConfig, type GlinForgeConfig } from '@glin-forge/sdk';
+import type { Options } from './types';
+import * as helpers from './helpers';
+
+export interface Extra {
+  name: string;
+  nested: { deep: number };
+}
+type Pair<T> = [T, T];
+export type Mode = 'a' | 'b';
+
+const port: number = Number(process.env.PORT!);
+let names: Array<string> = [];
+const cache = new Map<string, number>();
+const config = { networks: { local: { rpc: `ws://localhost:${port}` } }, mode: 'dev' as const } satisfies GlinForgeConfig;
+
+export async function main(signer: string, retries?: number, opts: { a: number } = { a: 1 }): Promise<void> {
+  const label = (value: number): string => `#${value}`;
+  console.log(signer, retries, opts.a ? 'yes' : 'no', label(1), names, cache, (port as number) > 1);
+}
+
+export default defineConfig(config);
+"#;
+        let stripped = strip_types(source);
+        let expected = r#"import { defineConfig} from '@glin-forge/sdk';
+
+import * as helpers from './helpers';
+
+
+
+
+
+const port = Number(process.env.PORT);
+let names = [];
+const cache = new Map();
+const config = { networks: { local: { rpc: `ws://localhost:${port}` } }, mode: 'dev' };
+
+export async function main(signer, retries, opts = { a: 1 }) {
+  const label = (value) => `#${value}`;
+  console.log(signer, retries, opts.a ? 'yes' : 'no', label(1), names, cache, (port) > 1);
+}
+
+export default defineConfig(config);
+"#;
+        assert_eq!(stripped, expected);
+    }
+
+    #[test]
+    fn test_evaluate_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("networks.ts"),
+            "export const local: { rpc: string } = { rpc: 'ws://localhost:9944' };\n",
+        )
+        .unwrap();
+        let config = dir.path().join("glinforge.config.ts");
+        std::fs::write(
+            &config,
+            "import { defineConfig, Network } from '@glin-forge/sdk';\nimport { local } from './networks';\n\nexport default defineConfig({ defaultNetwork: Network.Local, networks: { local } });\n",
+        )
+        .unwrap();
+        assert_eq!(
+            evaluate_config(&config).unwrap(),
+            serde_json::json!({
+                "defaultNetwork": "local",
+                "networks": { "local": { "rpc": "ws://localhost:9944" } }
+            })
+        );
+
+        let commonjs = dir.path().join("glinforge.config.js");
+        std::fs::write(&commonjs, "module.exports = { defaultNetwork: 'local' };\n").unwrap();
+        assert_eq!(
+            evaluate_config(&commonjs).unwrap(),
+            serde_json::json!({ "defaultNetwork": "local" })
+        );
+
+        // Packages need a real runtime
+        let package = dir.path().join("package.config.ts");
+        std::fs::write(&package, "import { z } from 'zod';\nexport default z;\n").unwrap();
+        let error = evaluate_config(&package).unwrap_err();
+        assert!(
+            format!("{:#}", error).contains("only loads local modules"),
+            "{:#}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_run_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("deploy.ts");
+        std::fs::write(
+            &script,
+            "import { network } from 'glin-forge';\nasync function main(): Promise<void> {\n  if (typeof network !== 'string' && network !== undefined) throw new Error('bad');\n}\nawait main();\n",
+        )
+        .unwrap();
+        run_script(&script).unwrap();
+        assert!(imports_script_module([&script]));
+
+        std::fs::write(
+            &script,
+            "async function main() { throw new Error('boom'); }\nmain();\n",
+        )
+        .unwrap();
+        let error = run_script(&script).unwrap_err();
+        assert!(format!("{:#}", error).contains("boom"), "{:#}", error);
+        assert!(!imports_script_module([&script]));
+    }
+}
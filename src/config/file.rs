@@ -28,8 +28,24 @@ pub struct FileConfig {
     #[serde(default)]
     pub deployments: HashMap<String, HashMap<String, DeploymentConfig>>,
 
+    #[serde(default)]
+    pub watch: WatchConfig,
+
+    #[serde(default)]
+    pub alerts: Vec<AlertConfig>,
+
     #[serde(default)]
     pub vars: HashMap<String, serde_json::Value>,
+
+    #[serde(default)]
+    pub rpc: RpcConfig,
+
+    #[serde(default)]
+    pub hooks: HooksConfig,
+
+    /// Runtime of scripts, hooks and the console; None to use the first one installed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<super::runtime::Runtime>,
 }
 
 fn default_network() -> String {
@@ -213,6 +229,109 @@ fn default_wait_for_finalization() -> bool {
     true
 }
 
+/// Where `glin-forge run` serves the SDK bridge
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct RpcConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    /// Unix socket path, used instead of host and port
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket: Option<PathBuf>,
+}
+
+/// Scripts `deploy` and `run` execute around deployments
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HooksConfig {
+    /// Run before deploying, e.g. to fund the deployer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_deploy: Option<PathBuf>,
+
+    /// Run after a successful deployment, e.g. to verify or register the contract
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_deploy: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WatchConfig {
+    /// Contracts `glin-forge watch --config` multiplexes into one stream
+    #[serde(default)]
+    pub contracts: Vec<WatchContractConfig>,
+
+    /// Endpoints receiving the events `glin-forge watch` displays
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchContractConfig {
+    /// Label of the contract's events (default: the address)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    pub address: String,
+
+    /// Metadata used to decode the contract's events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abi: Option<String>,
+
+    /// Event names or patterns shown (pallet variant or contract event); all when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebhookConfig {
+    pub url: String,
+
+    /// Event names delivered (pallet variant or contract event); all when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    #[serde(default = "default_webhook_retries")]
+    pub retries: u32,
+}
+
+pub fn default_webhook_retries() -> u32 {
+    3
+}
+
+/// Alert evaluated by `glin-forge monitor` over the `watch.contracts` events
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AlertConfig {
+    pub name: String,
+
+    /// e.g. "Transfer.value > 1000 GLIN", "Paused", "no events for 1h"
+    pub rule: String,
+
+    /// Names or addresses of the `watch.contracts` the rule applies to; all when empty
+    #[serde(default)]
+    pub contracts: Vec<String>,
+
+    /// URL the alert is POSTed to as JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+
+    /// Shell command run with GLIN_ALERT, GLIN_ALERT_MESSAGE and GLIN_ALERT_EVENT set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec: Option<String>,
+
+    /// Stop monitoring with this exit code once triggered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit: Option<i32>,
+
+    /// Seconds before the alert can trigger again
+    #[serde(default)]
+    pub cooldown: u64,
+}
+
 /// Load configuration from file
 pub fn load_config_file(path: Option<&Path>) -> Result<FileConfig> {
     let config_path = if let Some(p) = path {
@@ -239,7 +358,7 @@ pub fn load_config_file(path: Option<&Path>) -> Result<FileConfig> {
 }
 
 /// Find config file in current directory
-fn find_config_file() -> Result<PathBuf> {
+pub fn find_config_file() -> Result<PathBuf> {
     let config_files = [
         "glinforge.config.ts",
         "glinforge.config.js",
@@ -261,59 +380,71 @@ fn find_config_file() -> Result<PathBuf> {
 
 /// Load TypeScript config file
 fn load_typescript_config(path: &Path) -> Result<FileConfig> {
-    // Use ts-node to execute TypeScript config
-    let output = Command::new("node")
-        .args([
-            "-e",
-            &format!(
-                r#"
-                const tsNode = require('ts-node');
-                tsNode.register({{ transpileOnly: true, compilerOptions: {{ module: 'commonjs' }} }});
-                const config = require('{}');
-                console.log(JSON.stringify(config.default || config));
-                "#,
-                path.canonicalize()?.display()
-            ),
-        ])
-        .output()
-        .context("Failed to execute Node.js. Ensure Node.js and ts-node are installed.")?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to load TypeScript config: {}", error);
-    }
-
-    let json_str = String::from_utf8(output.stdout)?;
-    let config: FileConfig = serde_json::from_str(&json_str)
-        .context("Failed to parse configuration from TypeScript file")?;
-
-    Ok(config)
+    load_script_config(path, true)
 }
 
 /// Load JavaScript config file
 fn load_javascript_config(path: &Path) -> Result<FileConfig> {
-    let output = Command::new("node")
-        .args([
-            "-e",
-            &format!(
-                r#"
-                const config = require('{}');
-                console.log(JSON.stringify(config.default || config));
-                "#,
-                path.canonicalize()?.display()
+    load_script_config(path, false)
+}
+
+/// Evaluate a TypeScript or JavaScript config file: on the embedded engine unless another
+/// runtime is selected, falling back to an installed runtime (Node.js loads TypeScript
+/// through ts-node) for configs it cannot load
+fn load_script_config(path: &Path, typescript: bool) -> Result<FileConfig> {
+    use super::runtime::Runtime;
+
+    let language = if typescript {
+        "TypeScript"
+    } else {
+        "JavaScript"
+    };
+    let selected = super::runtime::explicit()?;
+    if matches!(selected, None | Some(Runtime::Embedded)) {
+        let embedded = super::embedded::evaluate_config(path);
+        let runtime = super::runtime::current(typescript)?;
+        match embedded {
+            Ok(config) => {
+                return serde_json::from_value(config).with_context(|| {
+                    format!("Failed to parse configuration from {} file", language)
+                })
+            }
+            Err(e) if runtime == Runtime::Embedded => anyhow::bail!(
+                "Failed to load {} config without Node.js, Bun or Deno: {:#}",
+                language,
+                e
             ),
-        ])
+            Err(_) => {}
+        }
+    }
+
+    let runtime = super::runtime::current(typescript)?;
+    let compiled = match (runtime, typescript) {
+        (Runtime::Node, true) => super::transpile::compiled(path),
+        _ => None,
+    };
+    let (program, args) = match &compiled {
+        Some(compiled) => runtime.config_invocation(compiled, false)?,
+        None => runtime.config_invocation(&path.canonicalize()?, typescript)?,
+    };
+    let output = Command::new(&program)
+        .args(args)
         .output()
-        .context("Failed to execute Node.js. Ensure Node.js is installed.")?;
+        .with_context(|| match (runtime, typescript) {
+            (Runtime::Node, true) => {
+                "Failed to execute Node.js. Ensure Node.js and ts-node are installed.".to_string()
+            }
+            _ => format!("Failed to execute {}. Ensure it is installed.", program),
+        })?;
 
     if !output.status.success() {
         let error = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to load JavaScript config: {}", error);
+        anyhow::bail!("Failed to load {} config: {}", language, error);
     }
 
     let json_str = String::from_utf8(output.stdout)?;
     let config: FileConfig = serde_json::from_str(&json_str)
-        .context("Failed to parse configuration from JavaScript file")?;
+        .with_context(|| format!("Failed to parse configuration from {} file", language))?;
 
     Ok(config)
 }
@@ -372,7 +503,12 @@ mod tests {
             typegen: TypeGenConfig::default(),
             test: TestConfig::default(),
             deployments: HashMap::new(),
+            watch: WatchConfig::default(),
+            alerts: Vec::new(),
             vars: HashMap::new(),
+            rpc: RpcConfig::default(),
+            hooks: HooksConfig::default(),
+            runtime: None,
         };
 
         assert_eq!(config.default_network, "testnet");
@@ -390,7 +526,12 @@ mod tests {
             typegen: TypeGenConfig::default(),
             test: TestConfig::default(),
             deployments: HashMap::new(),
+            watch: WatchConfig::default(),
+            alerts: Vec::new(),
             vars: HashMap::new(),
+            rpc: RpcConfig::default(),
+            hooks: HooksConfig::default(),
+            runtime: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -0,0 +1,136 @@
+// Journal of script runs
+//
+// `run` appends every run of a script to `.glin-forge/journal.json`: the hash
+// of its inputs (the script and the local modules it imports), the network,
+// whether it succeeded and the contracts it deployed. A script about to run
+// again on a network with identical inputs after a successful run is then
+// skipped, or run again once confirmed, instead of deploying everything twice.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Journal file, relative to the project root
+pub const JOURNAL_FILE: &str = ".glin-forge/journal.json";
+
+/// A run of a script
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Script path as given to `run`
+    pub script: String,
+    /// Hash of the script and the local modules it imports
+    pub hash: String,
+    pub network: String,
+    /// Unix time the run started at
+    pub started_at: u64,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deployments: Vec<JournalDeployment>,
+}
+
+/// A contract a run deployed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalDeployment {
+    /// Name in the deployments registry, when recorded there
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub network: String,
+    pub address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+}
+
+/// Script runs, oldest first
+pub struct Journal {
+    path: PathBuf,
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Load a journal, empty when nothing ran yet
+    pub fn open(path: &Path) -> Result<Self> {
+        let entries = if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid journal file {}", path.display()))?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// Load the journal of the current project
+    pub fn open_default() -> Result<Self> {
+        Self::open(Path::new(JOURNAL_FILE))
+    }
+
+    /// Latest successful run of a script with the same inputs on a network
+    pub fn executed(&self, script: &str, hash: &str, network: &str) -> Option<&JournalEntry> {
+        self.entries.iter().rev().find(|entry| {
+            entry.success
+                && entry.script == script
+                && entry.hash == hash
+                && entry.network == network
+        })
+    }
+
+    /// Append a run, replacing the file atomically
+    pub fn append(&mut self, entry: JournalEntry) -> Result<()> {
+        self.entries.push(entry);
+        super::deployments::write_json(&self.path, &self.entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(JOURNAL_FILE);
+        let run = |hash: &str, success: bool| JournalEntry {
+            script: "scripts/deploy.ts".to_string(),
+            hash: hash.to_string(),
+            network: "testnet".to_string(),
+            started_at: 1_700_000_000,
+            success,
+            deployments: Vec::new(),
+        };
+
+        let mut journal = Journal::open(&path).unwrap();
+        journal.append(run("0xaa", false)).unwrap();
+        assert_eq!(
+            journal.executed("scripts/deploy.ts", "0xaa", "testnet"),
+            None
+        );
+
+        let mut succeeded = run("0xaa", true);
+        succeeded.deployments.push(JournalDeployment {
+            name: Some("flipper".to_string()),
+            network: "testnet".to_string(),
+            address: "5Contract".to_string(),
+            code_hash: None,
+            tx_hash: None,
+        });
+        journal.append(succeeded.clone()).unwrap();
+
+        let journal = Journal::open(&path).unwrap();
+        assert_eq!(
+            journal.executed("scripts/deploy.ts", "0xaa", "testnet"),
+            Some(&succeeded)
+        );
+        // Changed inputs, or another network, run again
+        assert_eq!(
+            journal.executed("scripts/deploy.ts", "0xbb", "testnet"),
+            None
+        );
+        assert_eq!(journal.executed("scripts/deploy.ts", "0xaa", "local"), None);
+    }
+}
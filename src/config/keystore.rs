@@ -0,0 +1,197 @@
+// Encrypted account keystore
+//
+// Accounts are polkadot-js JSON exports (scrypt and xsalsa20-poly1305), as the
+// polkadot.js extension and most wallets write them, stored as `<name>.json`
+// in `~/.glin-forge/keystore` (or the `GLIN_FORGE_KEYSTORE` directory). Using
+// one needs its password. Accounts can also come from the environment:
+// `GLIN_FORGE_SEED_<NAME>` holds a seed phrase or secret URI.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use subxt_signer::sr25519::Keypair;
+
+/// Environment variable overriding the keystore directory
+pub const KEYSTORE_ENV: &str = "GLIN_FORGE_KEYSTORE";
+
+/// Prefix of the environment variables holding account seeds
+pub const SEED_ENV_PREFIX: &str = "GLIN_FORGE_SEED_";
+
+/// Fields of a keystore file shown without decrypting it
+#[derive(Debug, Deserialize)]
+struct KeystoreJson {
+    address: String,
+}
+
+/// Directory holding the keystore files
+pub fn keystore_dir() -> Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(KEYSTORE_ENV) {
+        return Ok(PathBuf::from(dir));
+    }
+    let home = dirs::home_dir().context("Cannot locate the home directory")?;
+    Ok(home.join(".glin-forge").join("keystore"))
+}
+
+/// Keystore file of an account
+pub fn account_path(dir: &Path, name: &str) -> Result<PathBuf> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!(
+            "Invalid account name '{}': use letters, digits, '-' and '_'",
+            name
+        );
+    }
+    Ok(dir.join(format!("{}.json", name)))
+}
+
+/// Environment variable holding the seed of an account, e.g. GLIN_FORGE_SEED_DEPLOYER
+pub fn seed_env(name: &str) -> String {
+    let suffix: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", SEED_ENV_PREFIX, suffix)
+}
+
+/// Account of a seed environment variable, if set
+pub fn from_env(name: &str) -> Result<Option<Keypair>> {
+    let var = seed_env(name);
+    from_seed(&var, std::env::var(&var).ok().as_deref())
+}
+
+/// Account of the seed read from `var`, if there is one
+fn from_seed(var: &str, seed: Option<&str>) -> Result<Option<Keypair>> {
+    match seed.map(str::trim) {
+        Some(seed) if !seed.is_empty() => glin_client::account_from_seed(seed)
+            .map(Some)
+            .with_context(|| format!("Invalid seed in {}", var)),
+        _ => Ok(None),
+    }
+}
+
+/// Whether the keystore holds an account
+pub fn contains(dir: &Path, name: &str) -> bool {
+    account_path(dir, name)
+        .map(|path| path.exists())
+        .unwrap_or(false)
+}
+
+/// Accounts of the keystore with their addresses, by name
+pub fn list(dir: &Path) -> Result<Vec<(String, String)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut accounts = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let content = std::fs::read_to_string(&path)?;
+        let keystore: KeystoreJson = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid keystore file {}", path.display()))?;
+        accounts.push((name.to_string(), keystore.address));
+    }
+    accounts.sort();
+    Ok(accounts)
+}
+
+/// Decrypt an account of the keystore
+pub fn unlock(dir: &Path, name: &str, password: &str) -> Result<Keypair> {
+    let path = account_path(dir, name)?;
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Account '{}' is not in the keystore", name))?;
+    subxt_signer::polkadot_js_compat::decrypt_json(&content, password)
+        .map_err(|e| anyhow::anyhow!("Failed to unlock '{}': {}", name, e))
+}
+
+/// Copy a polkadot-js JSON export into the keystore; returns its address
+pub fn import_json(dir: &Path, name: &str, json_path: &Path) -> Result<String> {
+    let content = std::fs::read_to_string(json_path)
+        .with_context(|| format!("Failed to read {}", json_path.display()))?;
+    let keystore: KeystoreJson = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a polkadot-js JSON export", json_path.display()))?;
+    let path = account_path(dir, name)?;
+    if path.exists() {
+        anyhow::bail!("Account '{}' already exists in {}", name, dir.display());
+    }
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&path, content)?;
+    Ok(keystore.address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Alice, encrypted with the password "whoisalice"
+    const ALICE_JSON: &str = r#"{
+      "encoded": "DumgApKCTqoCty1OZW/8WS+sgo6RdpHhCwAkA2IoDBMAgAAAAQAAAAgAAAB6IG/q24EeVf0JqWqcBd5m2tKq5BlyY84IQ8oamLn9DZe9Ouhgunr7i36J1XxUnTI801axqL/ym1gil0U8440Qvj0lFVKwGuxq38zuifgoj0B3Yru0CI6QKEvQPU5xxj4MpyxdSxP+2PnTzYao0HDH0fulaGvlAYXfqtU89xrx2/z9z7IjSwS3oDFPXRQ9kAdDebtyCVreZ9Otw9v3",
+      "encoding": {
+        "content": ["pkcs8", "sr25519"],
+        "type": ["scrypt", "xsalsa20-poly1305"],
+        "version": "3"
+      },
+      "address": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+      "meta": { "genesisHash": "", "name": "Alice", "whenCreated": 1718265838755 }
+    }"#;
+
+    #[test]
+    fn test_keystore() {
+        let dir = std::env::temp_dir().join(format!("glin-forge-keystore-{}", std::process::id()));
+        let export =
+            std::env::temp_dir().join(format!("glin-forge-alice-{}.json", std::process::id()));
+        std::fs::write(&export, ALICE_JSON).unwrap();
+
+        assert!(list(&dir).unwrap().is_empty());
+        let address = import_json(&dir, "deployer", &export).unwrap();
+        assert_eq!(address, "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY");
+        assert!(import_json(&dir, "deployer", &export).is_err());
+        assert!(import_json(&dir, "../escape", &export).is_err());
+        assert!(contains(&dir, "deployer"));
+        assert_eq!(
+            list(&dir).unwrap(),
+            vec![("deployer".to_string(), address.clone())]
+        );
+
+        let pair = unlock(&dir, "deployer", "whoisalice").unwrap();
+        assert_eq!(pair.public_key().to_account_id().to_string(), address);
+        assert!(unlock(&dir, "deployer", "wrong").is_err());
+        assert!(unlock(&dir, "missing", "whoisalice").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_file(&export).unwrap();
+    }
+
+    #[test]
+    fn test_seed_env() {
+        assert_eq!(seed_env("deployer"), "GLIN_FORGE_SEED_DEPLOYER");
+        assert_eq!(seed_env("test-net.admin"), "GLIN_FORGE_SEED_TEST_NET_ADMIN");
+
+        let pair = from_seed("GLIN_FORGE_SEED_TEST", Some(" //Alice\n"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            pair.public_key().to_account_id().to_string(),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+        assert!(from_seed("GLIN_FORGE_SEED_TEST", Some(" "))
+            .unwrap()
+            .is_none());
+        assert!(from_seed("GLIN_FORGE_SEED_TEST", None).unwrap().is_none());
+        assert!(from_seed("GLIN_FORGE_SEED_TEST", Some("not a seed")).is_err());
+        assert!(from_env("seed_env_unset").unwrap().is_none());
+    }
+}
@@ -0,0 +1,106 @@
+// Record of applied migrations
+//
+// `migrate` records every migration script it ran successfully in
+// `.glin-forge/migrations.json`, next to the deployments registry, keyed by
+// network and migration name, and skips them on later runs.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Migrations file, relative to the project root
+pub const MIGRATIONS_FILE: &str = ".glin-forge/migrations.json";
+
+/// A migration applied to a network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationRecord {
+    /// Unix time it completed at
+    pub applied_at: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Applied migrations keyed by network, then migration name
+pub struct MigrationRegistry {
+    path: PathBuf,
+    networks: BTreeMap<String, BTreeMap<String, MigrationRecord>>,
+}
+
+impl MigrationRegistry {
+    /// Load a registry, empty when nothing was migrated yet
+    pub fn open(path: &Path) -> Result<Self> {
+        let networks = if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid migrations file {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            networks,
+        })
+    }
+
+    /// Load the registry of the current project
+    pub fn open_default() -> Result<Self> {
+        Self::open(Path::new(MIGRATIONS_FILE))
+    }
+
+    /// Record of a migration applied to a network
+    pub fn applied(&self, network: &str, name: &str) -> Option<&MigrationRecord> {
+        self.networks.get(network)?.get(name)
+    }
+
+    /// Record an applied migration, replacing the file atomically
+    pub fn record(&mut self, network: &str, name: &str, migration: MigrationRecord) -> Result<()> {
+        self.networks
+            .entry(network.to_string())
+            .or_default()
+            .insert(name.to_string(), migration);
+        super::deployments::write_json(&self.path, &self.networks)
+    }
+
+    /// Forget the migrations applied to a network, so that they run again
+    pub fn reset(&mut self, network: &str) -> Result<()> {
+        if self.networks.remove(network).is_some() {
+            super::deployments::write_json(&self.path, &self.networks)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(MIGRATIONS_FILE);
+
+        let mut registry = MigrationRegistry::open(&path).unwrap();
+        assert_eq!(registry.applied("testnet", "001_token"), None);
+        let migration = MigrationRecord {
+            applied_at: 1_700_000_000,
+            tags: vec!["token".to_string()],
+        };
+        registry
+            .record("testnet", "001_token", migration.clone())
+            .unwrap();
+
+        let mut reopened = MigrationRegistry::open(&path).unwrap();
+        assert_eq!(reopened.applied("testnet", "001_token"), Some(&migration));
+        assert_eq!(reopened.applied("local", "001_token"), None);
+
+        reopened.reset("testnet").unwrap();
+        assert_eq!(
+            MigrationRegistry::open(&path)
+                .unwrap()
+                .applied("testnet", "001_token"),
+            None
+        );
+    }
+}
@@ -1,12 +1,61 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
+pub mod artifacts;
+pub mod deployments;
+pub mod embedded;
 pub mod file;
+pub mod journal;
+pub mod keystore;
+pub mod migrations;
+pub mod runtime;
+pub mod transpile;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConfig {
     pub rpc: String,
     pub explorer: Option<String>,
+    /// Faucet of the network; None when it has none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faucet: Option<FaucetConfig>,
+}
+
+/// Faucet the `requestFaucet` RPC method sends tokens from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FaucetConfig {
+    /// Account sending the tokens (dev account, keystore account or seed in the environment)
+    #[serde(default = "default_faucet_account")]
+    pub account: String,
+
+    /// Tokens sent per request, in the smallest unit
+    #[serde(default = "default_faucet_amount")]
+    pub amount: String,
+
+    /// Seconds before an address can request tokens again
+    #[serde(default = "default_faucet_cooldown")]
+    pub cooldown: u64,
+}
+
+fn default_faucet_account() -> String {
+    "alice".to_string()
+}
+fn default_faucet_amount() -> String {
+    // 100 GLIN with 18 decimals
+    "100000000000000000000".to_string()
+}
+fn default_faucet_cooldown() -> u64 {
+    3600
+}
+
+impl Default for FaucetConfig {
+    fn default() -> Self {
+        Self {
+            account: default_faucet_account(),
+            amount: default_faucet_amount(),
+            cooldown: default_faucet_cooldown(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,6 +73,7 @@ impl Default for ForgeConfig {
             NetworkConfig {
                 rpc: "wss://testnet.glin.network".to_string(),
                 explorer: Some("https://explorer-testnet.glin.network".to_string()),
+                faucet: Some(FaucetConfig::default()),
             },
         );
 
@@ -32,6 +82,7 @@ impl Default for ForgeConfig {
             NetworkConfig {
                 rpc: "wss://rpc.glin.network".to_string(),
                 explorer: Some("https://explorer.glin.network".to_string()),
+                faucet: None,
             },
         );
 
@@ -40,6 +91,7 @@ impl Default for ForgeConfig {
             NetworkConfig {
                 rpc: "ws://localhost:9944".to_string(),
                 explorer: None,
+                faucet: Some(FaucetConfig::default()),
             },
         );
 
@@ -50,12 +102,31 @@ impl Default for ForgeConfig {
     }
 }
 
+/// Network by name: a built-in one or one of the project configuration
 pub fn load_network(network_name: &str) -> anyhow::Result<NetworkConfig> {
-    let config = ForgeConfig::default();
-
-    config
-        .networks
+    networks()?
         .get(network_name)
         .cloned()
         .ok_or_else(|| anyhow::anyhow!("Network '{}' not found in configuration", network_name))
 }
+
+/// Names of the networks, sorted
+pub fn network_names() -> anyhow::Result<Vec<String>> {
+    let mut names: Vec<String> = networks()?.keys().cloned().collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Built-in networks, with those of the project configuration added or replacing them;
+/// loaded once per process
+fn networks() -> anyhow::Result<&'static HashMap<String, NetworkConfig>> {
+    static NETWORKS: OnceLock<HashMap<String, NetworkConfig>> = OnceLock::new();
+    if let Some(networks) = NETWORKS.get() {
+        return Ok(networks);
+    }
+    let config = match file::find_config_file() {
+        Ok(path) => file::merge_with_defaults(file::load_config_file(Some(&path))?),
+        Err(_) => ForgeConfig::default(),
+    };
+    Ok(NETWORKS.get_or_init(|| config.networks))
+}
@@ -0,0 +1,260 @@
+// JavaScript runtimes
+//
+// Scripts, hooks, migrations and .ts/.js config files run on
+// Node.js (TypeScript through tsx or ts-node, or compiled by esbuild or swc
+// into a cache, see `transpile`), Bun or Deno. `--runtime`, the
+// GLIN_FORGE_RUNTIME variable or the config's `runtime` picks one; otherwise
+// the first one installed of node, bun and deno is used. Bun and Deno run
+// TypeScript themselves and resolve packages from the project's package.json
+// and node_modules, so `npm install` (or `bun install`) is all a project needs.
+//
+// Without any of them, the engine embedded in glin-forge runs simple scripts
+// (see `embedded`); it also runs the scripts importing its `glin-forge`
+// module, and loads config files first, falling back to an installed runtime
+// for the configs it cannot load.
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+/// Variable naming the runtime, also set for the scripts so that nested commands use it
+pub const RUNTIME_ENV: &str = "GLIN_FORGE_RUNTIME";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    Node,
+    Bun,
+    Deno,
+    Embedded,
+}
+
+impl Runtime {
+    /// Runtimes in the order they are detected
+    pub const ALL: [Runtime; 4] = [
+        Runtime::Node,
+        Runtime::Bun,
+        Runtime::Deno,
+        Runtime::Embedded,
+    ];
+
+    /// Name of the runtime, and of its program
+    pub fn name(self) -> &'static str {
+        match self {
+            Runtime::Node => "node",
+            Runtime::Bun => "bun",
+            Runtime::Deno => "deno",
+            Runtime::Embedded => "embedded",
+        }
+    }
+
+    /// Whether the runtime can run scripts here; Node.js needs tsx, ts-node, or esbuild or swc
+    /// to compile TypeScript
+    fn installed(self, typescript: bool) -> bool {
+        let found = |program: &str| which::which(program).is_ok();
+        match self {
+            Runtime::Node if typescript => {
+                found("node")
+                    && (found("tsx")
+                        || found("ts-node")
+                        || super::transpile::Transpiler::detect().is_some())
+            }
+            Runtime::Embedded => true,
+            runtime => found(runtime.name()),
+        }
+    }
+
+    /// Program and the arguments before the script's path that run a script
+    pub fn script_invocation(
+        self,
+        typescript: bool,
+    ) -> anyhow::Result<(String, Vec<&'static str>)> {
+        let (program, args) = match self {
+            Runtime::Node if typescript => {
+                // Try to use tsx (faster) or ts-node (fallback)
+                if which::which("tsx").is_ok() {
+                    ("tsx", Vec::new())
+                } else if which::which("ts-node").is_ok() {
+                    ("ts-node", Vec::new())
+                } else {
+                    anyhow::bail!(
+                        "TypeScript runtime not found. Please install tsx or ts-node, or use Bun or Deno:\n  npm install -g tsx\n  # or\n  glin-forge run --runtime bun <script>"
+                    );
+                }
+            }
+            Runtime::Node => ("node", Vec::new()),
+            Runtime::Bun => ("bun", vec!["run"]),
+            // Deno resolves npm packages from package.json and node_modules like Node.js
+            Runtime::Deno => ("deno", vec!["run", "--allow-all"]),
+            Runtime::Embedded => return Ok((current_exe()?, vec!["embedded-run"])),
+        };
+        Ok((program.to_string(), args))
+    }
+
+    /// Program and arguments printing the JSON of a config file's (default) export
+    pub fn config_invocation(
+        self,
+        path: &Path,
+        typescript: bool,
+    ) -> anyhow::Result<(String, Vec<String>)> {
+        let file = path.to_string_lossy().to_string();
+        let path = serde_json::to_string(&file).unwrap_or_default();
+        let (program, args) = match self {
+            Runtime::Node if typescript => (
+                "node",
+                vec![
+                    "-e".to_string(),
+                    format!(
+                        r#"
+                        const tsNode = require('ts-node');
+                        tsNode.register({{ transpileOnly: true, compilerOptions: {{ module: 'commonjs' }} }});
+                        const config = require({});
+                        console.log(JSON.stringify(config.default || config));
+                        "#,
+                        path
+                    ),
+                ],
+            ),
+            Runtime::Node | Runtime::Bun => (
+                self.name(),
+                vec![
+                    "-e".to_string(),
+                    format!(
+                        "const config = require({}); console.log(JSON.stringify(config.default || config));",
+                        path
+                    ),
+                ],
+            ),
+            Runtime::Deno => (
+                "deno",
+                vec![
+                    "eval".to_string(),
+                    format!(
+                        "const {{ pathToFileURL }} = await import('node:url'); const config = await import(pathToFileURL({}).href); console.log(JSON.stringify(config.default ?? config));",
+                        path
+                    ),
+                ],
+            ),
+            Runtime::Embedded => {
+                let args = ["embedded-run", "--config", &file].map(str::to_string);
+                return Ok((current_exe()?, args.to_vec()));
+            }
+        };
+        Ok((program.to_string(), args))
+    }
+}
+
+/// Path of glin-forge, which runs scripts on the embedded engine
+fn current_exe() -> anyhow::Result<String> {
+    let exe = std::env::current_exe().context("Failed to locate the glin-forge executable")?;
+    Ok(exe.to_string_lossy().to_string())
+}
+
+impl std::str::FromStr for Runtime {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Runtime::ALL
+            .into_iter()
+            .find(|runtime| runtime.name() == s)
+            .ok_or_else(|| {
+                anyhow::anyhow!("Unknown runtime '{}'. Use node, bun, deno or embedded", s)
+            })
+    }
+}
+
+fn selected() -> &'static Mutex<Option<Runtime>> {
+    static SELECTED: OnceLock<Mutex<Option<Runtime>>> = OnceLock::new();
+    SELECTED.get_or_init(Default::default)
+}
+
+/// Runtime given by `--runtime`, or else GLIN_FORGE_RUNTIME
+pub fn explicit() -> anyhow::Result<Option<Runtime>> {
+    if let Some(runtime) = *selected().lock().unwrap() {
+        return Ok(Some(runtime));
+    }
+    match std::env::var(RUNTIME_ENV) {
+        Ok(name) if !name.is_empty() => name
+            .parse()
+            .map(Some)
+            .with_context(|| format!("Invalid {}", RUNTIME_ENV)),
+        _ => Ok(None),
+    }
+}
+
+/// Select the runtime of the command's scripts: `--runtime`, GLIN_FORGE_RUNTIME, or the
+/// config's `runtime`
+pub fn configure(flag: Option<&str>) -> anyhow::Result<()> {
+    let runtime = match flag {
+        Some(name) => Some(name.parse()?),
+        None => match explicit()? {
+            Some(runtime) => Some(runtime),
+            None => match super::file::find_config_file() {
+                Ok(path) => super::file::load_config_file(Some(&path))?.runtime,
+                Err(_) => None,
+            },
+        },
+    };
+    *selected().lock().unwrap() = runtime;
+    Ok(())
+}
+
+/// Runtime to run a script or config file on: the selected one, or else the first installed,
+/// the embedded engine when there is none
+pub fn current(typescript: bool) -> anyhow::Result<Runtime> {
+    if let Some(runtime) = explicit()? {
+        return Ok(runtime);
+    }
+    Ok(Runtime::ALL
+        .into_iter()
+        .find(|runtime| runtime.installed(typescript))
+        .unwrap_or(Runtime::Embedded))
+}
+
+/// Runtime to run a script on: the embedded engine for scripts importing its built-in
+/// module, unless another runtime is selected
+pub fn for_script(script: &Path, typescript: bool) -> anyhow::Result<Runtime> {
+    if let Some(runtime) = explicit()? {
+        return Ok(runtime);
+    }
+    if super::embedded::imports_script_module(&crate::cli::run::script_sources(script)?) {
+        return Ok(Runtime::Embedded);
+    }
+    current(typescript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runtime_invocations() {
+        assert_eq!("bun".parse::<Runtime>().unwrap(), Runtime::Bun);
+        assert!("python".parse::<Runtime>().is_err());
+        let config: Runtime = serde_json::from_str(r#""deno""#).unwrap();
+        assert_eq!(config, Runtime::Deno);
+
+        let invocation = |runtime: Runtime| runtime.script_invocation(false).unwrap();
+        assert_eq!(invocation(Runtime::Bun), ("bun".to_string(), vec!["run"]));
+        assert_eq!(
+            invocation(Runtime::Deno),
+            ("deno".to_string(), vec!["run", "--allow-all"])
+        );
+        assert_eq!(invocation(Runtime::Node), ("node".to_string(), Vec::new()));
+        // The embedded engine runs in glin-forge itself
+        let (program, args) = invocation(Runtime::Embedded);
+        assert_eq!(program, std::env::current_exe().unwrap().to_string_lossy());
+        assert_eq!(args, ["embedded-run"]);
+
+        // Paths are quoted as JavaScript strings, whatever they contain
+        let path = Path::new("/work/it's/glinforge.config.ts");
+        let (program, args) = Runtime::Deno.config_invocation(path, true).unwrap();
+        assert_eq!(program, "deno");
+        assert_eq!(args[0], "eval");
+        assert!(args[1].contains(r#"pathToFileURL("/work/it's/glinforge.config.ts")"#));
+        let (program, args) = Runtime::Bun.config_invocation(path, true).unwrap();
+        assert_eq!(program, "bun");
+        assert!(args[1].contains(r#"require("/work/it's/glinforge.config.ts")"#));
+    }
+}
@@ -0,0 +1,252 @@
+// Cached TypeScript transpilation
+//
+// Node.js runs TypeScript scripts through tsx or ts-node, and loads
+// TypeScript configs through ts-node, which takes seconds per invocation. When
+// esbuild or swc is installed (on the PATH or in node_modules/.bin), a script
+// or config and the local modules it imports are compiled to CommonJS instead,
+// into `.cache/ts/<hash of their paths and contents>/` mirroring the project
+// tree, and plain Node.js runs the compiled entry. Unchanged inputs reuse the
+// compiled files. `__filename` and `__dirname` keep naming the sources, and
+// anything that fails to compile falls back to tsx or ts-node.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Cache directory, relative to the project root
+pub const CACHE_DIR: &str = ".cache/ts";
+
+/// Extensions of the sources compiled to `.js`; other files are copied as they are
+const COMPILED_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "mjs"];
+
+/// Compiler turning one TypeScript or JavaScript file into CommonJS
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transpiler {
+    Esbuild(PathBuf),
+    Swc(PathBuf),
+}
+
+impl Transpiler {
+    /// First installed of esbuild and swc
+    pub fn detect() -> Option<Self> {
+        let find = |program: &str| {
+            let local = Path::new("node_modules/.bin").join(program);
+            which::which(program)
+                .ok()
+                .or_else(|| local.is_file().then_some(local))
+        };
+        find("esbuild")
+            .map(Transpiler::Esbuild)
+            .or_else(|| find("swc").map(Transpiler::Swc))
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Transpiler::Esbuild(_) => "esbuild",
+            Transpiler::Swc(_) => "swc",
+        }
+    }
+
+    fn compile(&self, source: &Path, output: &Path) -> Result<()> {
+        let extension = source.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let mut command = match self {
+            Transpiler::Esbuild(program) => {
+                let mut command = Command::new(program);
+                command
+                    .arg(source)
+                    .args(["--format=cjs", "--platform=node", "--log-level=error"])
+                    .arg(format!("--outfile={}", output.display()));
+                command
+            }
+            Transpiler::Swc(program) => {
+                let syntax = if extension.starts_with('j') || extension == "mjs" {
+                    "ecmascript"
+                } else {
+                    "typescript"
+                };
+                let mut command = Command::new(program);
+                command
+                    .arg(source)
+                    .arg("-o")
+                    .arg(output)
+                    .args(["-C", "module.type=commonjs", "-C"])
+                    .arg(format!("jsc.parser.syntax={}", syntax));
+                if extension == "tsx" {
+                    command.args(["-C", "jsc.parser.tsx=true"]);
+                }
+                command
+            }
+        };
+        let result = command
+            .output()
+            .with_context(|| format!("Failed to run {}", self.name()))?;
+        if !result.status.success() {
+            anyhow::bail!(
+                "{} failed to compile {}: {}",
+                self.name(),
+                source.display(),
+                String::from_utf8_lossy(&result.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Compiled entry of a TypeScript script or config, from the cache or compiled now; None
+/// without a transpiler or when it cannot be compiled
+pub fn compiled(entry: &Path) -> Option<PathBuf> {
+    let transpiler = Transpiler::detect()?;
+    let root = std::env::current_dir().ok()?.canonicalize().ok()?;
+    let sources = crate::cli::run::script_sources(entry).ok()?;
+    compile_sources(
+        entry,
+        &sources,
+        &root,
+        &root.join(CACHE_DIR),
+        |source, output| transpiler.compile(source, output),
+    )
+    .ok()
+}
+
+/// Compile `sources` under `root` into a directory of `cache` named after their hash, and
+/// return where `entry` compiled to
+fn compile_sources(
+    entry: &Path,
+    sources: &HashSet<PathBuf>,
+    root: &Path,
+    cache: &Path,
+    compile: impl Fn(&Path, &Path) -> Result<()>,
+) -> Result<PathBuf> {
+    let hash = crate::cli::run::inputs_hash(sources)?;
+    let dir = cache.join(&hash[2..18]);
+    let compiled = |source: &Path| {
+        source
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| COMPILED_EXTENSIONS.contains(&e))
+    };
+    let output_of = |source: &Path| -> Result<PathBuf> {
+        let relative = source
+            .strip_prefix(root)
+            .with_context(|| format!("{} is outside of {}", source.display(), root.display()))?;
+        Ok(match compiled(source) {
+            true => dir.join(relative).with_extension("js"),
+            false => dir.join(relative),
+        })
+    };
+    let entry_output = output_of(&entry.canonicalize()?)?;
+    if entry_output.is_file() {
+        return Ok(entry_output);
+    }
+
+    // Compiled files are CommonJS, whatever the project's package.json says
+    std::fs::create_dir_all(cache)?;
+    std::fs::write(cache.join("package.json"), "{ \"type\": \"commonjs\" }\n")?;
+    // Compile into a directory of its own, renamed once complete
+    let staging = cache.join(format!("{}.tmp-{}", &hash[2..18], std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging);
+    let result = (|| -> Result<()> {
+        for source in sources {
+            let output = staging.join(output_of(source)?.strip_prefix(&dir)?);
+            std::fs::create_dir_all(output.parent().unwrap_or(&staging))?;
+            if compiled(source) {
+                compile(source, &output)?;
+                let code = std::fs::read_to_string(&output)?;
+                std::fs::write(&output, with_source_paths(&code, source))?;
+            } else {
+                std::fs::copy(source, &output)?;
+            }
+        }
+        Ok(())
+    })();
+    if let Err(e) = result {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+    if std::fs::rename(&staging, &dir).is_err() {
+        // Compiled meanwhile by another process
+        let _ = std::fs::remove_dir_all(&staging);
+    }
+    Ok(entry_output)
+}
+
+/// Compiled code setting `__filename` and `__dirname` to its source's, after a leading
+/// "use strict" so that it stays in effect
+fn with_source_paths(code: &str, source: &Path) -> String {
+    let quote = |path: &Path| serde_json::to_string(&path.to_string_lossy()).unwrap_or_default();
+    let paths = format!(
+        "__filename = {}; __dirname = {};",
+        quote(source),
+        quote(source.parent().unwrap_or(Path::new("/")))
+    );
+    for directive in ["\"use strict\";", "'use strict';"] {
+        if let Some(rest) = code.strip_prefix(directive) {
+            return format!("{} {}{}", directive, paths, rest);
+        }
+    }
+    format!("{} {}", paths, code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("scripts/lib")).unwrap();
+        let entry = root.join("scripts/deploy.ts");
+        std::fs::write(&entry, "import { x } from './lib/util';\nconsole.log(x);\n").unwrap();
+        std::fs::write(
+            root.join("scripts/lib/util.ts"),
+            "export const x: number = 1;\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("scripts/data.json"), "{}").unwrap();
+        let sources: HashSet<PathBuf> = [
+            entry.clone(),
+            root.join("scripts/lib/util.ts"),
+            root.join("scripts/data.json"),
+        ]
+        .into_iter()
+        .collect();
+        let cache = root.join(CACHE_DIR);
+
+        let compiles = std::cell::Cell::new(0);
+        let compile = |source: &Path, output: &Path| {
+            compiles.set(compiles.get() + 1);
+            let code = std::fs::read_to_string(source)?;
+            std::fs::write(output, format!("\"use strict\";\n{}", code))?;
+            Ok(())
+        };
+
+        let output = compile_sources(&entry, &sources, &root, &cache, compile).unwrap();
+        assert_eq!(output.file_name().unwrap(), "deploy.js");
+        assert!(output.starts_with(&cache));
+        assert_eq!(compiles.get(), 2);
+        let code = std::fs::read_to_string(&output).unwrap();
+        assert!(code.starts_with(&format!(
+            "\"use strict\"; __filename = {:?};",
+            entry.to_string_lossy()
+        )));
+        let compiled_dir = output.parent().unwrap();
+        assert!(compiled_dir.join("lib/util.js").is_file());
+        assert!(compiled_dir.join("data.json").is_file());
+
+        // Unchanged sources are not compiled again; changed ones are
+        compile_sources(&entry, &sources, &root, &cache, compile).unwrap();
+        assert_eq!(compiles.get(), 2);
+        std::fs::write(root.join("scripts/lib/util.ts"), "export const x = 2;\n").unwrap();
+        let changed = compile_sources(&entry, &sources, &root, &cache, compile).unwrap();
+        assert_ne!(changed, output);
+        assert_eq!(compiles.get(), 4);
+
+        // Failures leave nothing behind for the next run to pick up
+        std::fs::write(&entry, "broken").unwrap();
+        let failing = |_: &Path, _: &Path| anyhow::bail!("syntax error");
+        assert!(compile_sources(&entry, &sources, &root, &cache, failing).is_err());
+        assert_eq!(std::fs::read_dir(&cache).unwrap().count(), 3);
+    }
+}
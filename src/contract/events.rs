@@ -0,0 +1,498 @@
+// Decoding of `ContractEmitted` payloads with contract metadata
+//
+// Message call data decodes alike: the selector picks the message of the spec,
+// its arguments follow.
+//
+// The event spec of the metadata identifies the event (ink! 5 by the signature
+// topic of the record, earlier versions by an index byte prefixing the data),
+// then the type registry drives a generic SCALE decoder producing JSON. Account
+// ids are rendered in SS58, byte arrays and vectors as hex, 128-bit and wider
+// integers as strings.
+
+use anyhow::{Context, Result};
+use scale::{Compact, Decode};
+use serde_json::{json, Map, Value as JsonValue};
+use std::collections::HashMap;
+use std::fmt;
+use subxt::utils::AccountId32;
+
+/// A contract event decoded with its metadata
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent {
+    pub name: String,
+    /// Fields in declaration order
+    pub fields: Vec<(String, JsonValue)>,
+}
+
+impl DecodedEvent {
+    /// `{ "event": "Transfer", "fields": { ... } }`
+    pub fn to_json(&self) -> JsonValue {
+        let fields: Map<String, JsonValue> = self.fields.iter().cloned().collect();
+        json!({ "event": self.name, "fields": fields })
+    }
+}
+
+impl fmt::Display for DecodedEvent {
+    /// `Transfer { from: 5Grw..., to: 5FHn..., value: 1000 }`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| match value {
+                JsonValue::String(s) => format!("{}: {}", name, s),
+                other => format!("{}: {}", name, other),
+            })
+            .collect();
+        write!(f, " {{ {} }}", fields.join(", "))
+    }
+}
+
+/// A `ContractEmitted` event of an extrinsic, before decoding
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmittedEvent {
+    pub contract: AccountId32,
+    pub topics: Vec<[u8; 32]>,
+    pub data: Vec<u8>,
+}
+
+/// Split the fields of a `Contracts::ContractEmitted` event into emitter and payload
+pub fn contract_emitted(field_bytes: &[u8]) -> Result<(AccountId32, Vec<u8>)> {
+    <(AccountId32, Vec<u8>)>::decode(&mut &field_bytes[..])
+        .context("Malformed ContractEmitted event")
+}
+
+/// Decodes the events of one contract from its metadata
+#[derive(Debug, Clone)]
+pub struct EventDecoder {
+    types: HashMap<u64, JsonValue>,
+    events: Vec<JsonValue>,
+    messages: Vec<JsonValue>,
+}
+
+impl EventDecoder {
+    /// Decoder for the contract described by `metadata` (ink! 4/5 or V3 layout)
+    pub fn new(metadata: &JsonValue) -> Result<Self> {
+        let (types, spec) = match metadata.get("V3") {
+            Some(v3) => (&v3["types"], &v3["spec"]),
+            None => (&metadata["types"], &metadata["spec"]),
+        };
+        let types = types
+            .as_array()
+            .context("Types section not found in metadata")?
+            .iter()
+            .filter_map(|entry| Some((entry["id"].as_u64()?, entry["type"].clone())))
+            .collect();
+        let events = spec["events"].as_array().cloned().unwrap_or_default();
+        let messages = spec["messages"].as_array().cloned().unwrap_or_default();
+
+        Ok(Self {
+            types,
+            events,
+            messages,
+        })
+    }
+
+    /// Decoder for a metadata file
+    pub fn load(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read metadata {}", path))?;
+        let metadata: JsonValue = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid metadata JSON in {}", path))?;
+        Self::new(&metadata)
+    }
+
+    /// Decode an event payload given the topics of its event record
+    pub fn decode(&self, topics: &[[u8; 32]], data: &[u8]) -> Result<DecodedEvent> {
+        let topic = topics.first().map(|t| format!("0x{}", hex::encode(t)));
+        let by_topic = self.events.iter().find(|event| {
+            event["signature_topic"].as_str().map(str::to_lowercase) == topic && topic.is_some()
+        });
+
+        let (event, mut payload) = match by_topic {
+            Some(event) => (event, data),
+            None if self
+                .events
+                .iter()
+                .any(|e| e.get("signature_topic").is_some()) =>
+            {
+                // ink! 5: only anonymous events lack a signature topic
+                let mut anonymous = self
+                    .events
+                    .iter()
+                    .filter(|e| e["signature_topic"].is_null());
+                match (anonymous.next(), anonymous.next()) {
+                    (Some(event), None) => (event, data),
+                    _ => anyhow::bail!("No event of the metadata matches topic {:?}", topic),
+                }
+            }
+            None => {
+                let (&index, rest) = data.split_first().context("Empty event payload")?;
+                let event = self
+                    .events
+                    .get(index as usize)
+                    .with_context(|| format!("Unknown event index {}", index))?;
+                (event, rest)
+            }
+        };
+
+        self.decode_args(event, &mut payload)
+    }
+
+    /// Decode the input of a message call: the selected message with its arguments
+    pub fn decode_call(&self, data: &[u8]) -> Result<DecodedEvent> {
+        if data.len() < 4 {
+            anyhow::bail!("Call data shorter than a selector");
+        }
+        let (selector, mut payload) = data.split_at(4);
+        let selector = format!("0x{}", hex::encode(selector));
+        let message = self
+            .messages
+            .iter()
+            .find(|m| m["selector"].as_str().map(str::to_lowercase) == Some(selector.clone()))
+            .with_context(|| format!("No message of the metadata has selector {}", selector))?;
+        self.decode_args(message, &mut payload)
+    }
+
+    /// Arguments of an event or message spec, which must consume the whole payload
+    fn decode_args(&self, spec: &JsonValue, payload: &mut &[u8]) -> Result<DecodedEvent> {
+        let mut fields = Vec::new();
+        for arg in spec["args"].as_array().into_iter().flatten() {
+            let label = arg["label"].as_str().unwrap_or("value").to_string();
+            let type_id = arg["type"]["type"]
+                .as_u64()
+                .with_context(|| format!("Argument {} has no type", label))?;
+            fields.push((label, self.decode_value(type_id, payload)?));
+        }
+        if !payload.is_empty() {
+            anyhow::bail!("{} trailing bytes after {}", payload.len(), spec["label"]);
+        }
+
+        Ok(DecodedEvent {
+            name: spec["label"].as_str().unwrap_or("Unknown").to_string(),
+            fields,
+        })
+    }
+
+    /// What each record topic of event `name` holds: the event signature (`None`)
+    /// unless the event is anonymous, then the labels of its indexed fields
+    pub fn topic_labels(&self, name: &str) -> Vec<Option<String>> {
+        let Some(event) = self.events.iter().find(|e| e["label"] == name) else {
+            return Vec::new();
+        };
+        let anonymous = event.get("signature_topic").is_some_and(JsonValue::is_null);
+        let indexed = event["args"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|arg| arg["indexed"].as_bool().unwrap_or(false))
+            .map(|arg| arg["label"].as_str().map(str::to_string));
+        (!anonymous)
+            .then_some(None)
+            .into_iter()
+            .chain(indexed)
+            .collect()
+    }
+
+    /// Display name of each field of event `name` (e.g. `Balance`), in declaration order
+    pub fn field_types(&self, name: &str) -> Vec<Option<String>> {
+        let Some(event) = self.events.iter().find(|e| e["label"] == name) else {
+            return Vec::new();
+        };
+        event["args"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|arg| {
+                arg["type"]["displayName"]
+                    .as_array()
+                    .and_then(|path| path.last())
+                    .and_then(|name| name.as_str())
+                    .map(str::to_string)
+            })
+            .collect()
+    }
+
+    /// Decode a value of registry type `type_id`, advancing `input`
+    pub fn decode_value(&self, type_id: u64, input: &mut &[u8]) -> Result<JsonValue> {
+        let ty = self
+            .types
+            .get(&type_id)
+            .with_context(|| format!("Type {} not found in metadata", type_id))?;
+        let def = &ty["def"];
+        let path: Vec<&str> = ty["path"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|s| s.as_str())
+            .collect();
+
+        if matches!(path.last(), Some(&"AccountId") | Some(&"AccountId32")) {
+            return Ok(JsonValue::String(AccountId32::decode(input)?.to_string()));
+        }
+
+        if let Some(primitive) = def["primitive"].as_str() {
+            return decode_primitive(primitive, input);
+        }
+        if let Some(composite) = def.get("composite") {
+            return self.decode_fields(&composite["fields"], input);
+        }
+        if let Some(variant) = def.get("variant") {
+            let index = u8::decode(input)?;
+            let variant = variant["variants"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .find(|(i, v)| v["index"].as_u64().unwrap_or(*i as u64) == index as u64)
+                .map(|(_, v)| v)
+                .with_context(|| format!("Invalid variant index {} for type {}", index, type_id))?;
+            let name = variant["name"].as_str().unwrap_or("Unknown");
+            let fields = &variant["fields"];
+            if fields.as_array().is_none_or(|f| f.is_empty()) {
+                return Ok(if path == ["Option"] {
+                    JsonValue::Null
+                } else {
+                    JsonValue::String(name.to_string())
+                });
+            }
+            let value = self.decode_fields(fields, input)?;
+            return Ok(if path == ["Option"] {
+                value
+            } else {
+                json!({ name: value })
+            });
+        }
+        if let Some(sequence) = def.get("sequence") {
+            let len = Compact::<u32>::decode(input)?.0 as usize;
+            return self.decode_elements(sequence["type"].as_u64(), len, input);
+        }
+        if let Some(array) = def.get("array") {
+            let len = array["len"].as_u64().unwrap_or(0) as usize;
+            return self.decode_elements(array["type"].as_u64(), len, input);
+        }
+        if let Some(tuple) = def.get("tuple") {
+            let ids: Vec<u64> = tuple
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|id| id.as_u64())
+                .collect();
+            if ids.is_empty() {
+                return Ok(JsonValue::Null);
+            }
+            let values = ids
+                .into_iter()
+                .map(|id| self.decode_value(id, input))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(JsonValue::Array(values));
+        }
+        if def.get("compact").is_some() {
+            return Ok(JsonValue::String(
+                Compact::<u128>::decode(input)?.0.to_string(),
+            ));
+        }
+
+        anyhow::bail!("Unsupported definition of type {}: {}", type_id, def)
+    }
+
+    /// Named fields as an object, a single unnamed field as its value, several as an array
+    fn decode_fields(&self, fields: &JsonValue, input: &mut &[u8]) -> Result<JsonValue> {
+        let fields = fields.as_array().cloned().unwrap_or_default();
+        let mut named = Map::new();
+        let mut unnamed = Vec::new();
+        for field in &fields {
+            let type_id = field["type"].as_u64().context("Field without type")?;
+            let value = self.decode_value(type_id, input)?;
+            match field["name"].as_str() {
+                Some(name) => {
+                    named.insert(name.to_string(), value);
+                }
+                None => unnamed.push(value),
+            }
+        }
+        Ok(match (named.is_empty(), unnamed.len()) {
+            (false, _) => JsonValue::Object(named),
+            (true, 1) => unnamed.remove(0),
+            _ => JsonValue::Array(unnamed),
+        })
+    }
+
+    /// Elements of a sequence or array; bytes as hex
+    fn decode_elements(
+        &self,
+        element: Option<u64>,
+        len: usize,
+        input: &mut &[u8],
+    ) -> Result<JsonValue> {
+        let element = element.context("Collection without element type")?;
+        let is_byte = self
+            .types
+            .get(&element)
+            .and_then(|t| t["def"]["primitive"].as_str())
+            == Some("u8");
+        if is_byte {
+            if input.len() < len {
+                anyhow::bail!("Not enough data for {} bytes", len);
+            }
+            let (bytes, rest) = input.split_at(len);
+            *input = rest;
+            return Ok(JsonValue::String(format!("0x{}", hex::encode(bytes))));
+        }
+        let values = (0..len)
+            .map(|_| self.decode_value(element, input))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(JsonValue::Array(values))
+    }
+}
+
+fn decode_primitive(primitive: &str, input: &mut &[u8]) -> Result<JsonValue> {
+    Ok(match primitive {
+        "bool" => json!(bool::decode(input)?),
+        "char" => {
+            let code = u32::decode(input)?;
+            json!(char::from_u32(code).context("Invalid char")?.to_string())
+        }
+        "str" => json!(String::decode(input)?),
+        "u8" => json!(u8::decode(input)?),
+        "u16" => json!(u16::decode(input)?),
+        "u32" => json!(u32::decode(input)?),
+        "u64" => json!(u64::decode(input)?),
+        "u128" => json!(u128::decode(input)?.to_string()),
+        "i8" => json!(i8::decode(input)?),
+        "i16" => json!(i16::decode(input)?),
+        "i32" => json!(i32::decode(input)?),
+        "i64" => json!(i64::decode(input)?),
+        "i128" => json!(i128::decode(input)?.to_string()),
+        "u256" | "i256" => {
+            let bytes = <[u8; 32]>::decode(input)?;
+            json!(format!("0x{}", hex::encode(bytes)))
+        }
+        other => anyhow::bail!("Unsupported primitive {}", other),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Encode;
+
+    fn metadata(signature_topic: JsonValue) -> JsonValue {
+        json!({
+            "version": 5,
+            "spec": {
+                "events": [{
+                    "label": "Transfer",
+                    "signature_topic": signature_topic,
+                    "args": [
+                        { "label": "from", "indexed": true, "type": { "type": 2 } },
+                        { "label": "to", "indexed": true, "type": { "type": 2 } },
+                        { "label": "value", "indexed": false, "type": { "type": 3, "displayName": ["Balance"] } }
+                    ]
+                }],
+                "messages": [{
+                    "label": "transfer",
+                    "selector": "0x84A15DA1",
+                    "args": [
+                        { "label": "to", "type": { "type": 4 } },
+                        { "label": "value", "type": { "type": 3 } }
+                    ]
+                }]
+            },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u8" } } },
+                { "id": 1, "type": { "def": { "array": { "len": 32, "type": 0 } } } },
+                { "id": 2, "type": {
+                    "path": ["Option"],
+                    "params": [{ "name": "T", "type": 4 }],
+                    "def": { "variant": { "variants": [
+                        { "name": "None", "index": 0 },
+                        { "name": "Some", "index": 1, "fields": [{ "type": 4 }] }
+                    ] } }
+                } },
+                { "id": 3, "type": { "def": { "primitive": "u128" } } },
+                { "id": 4, "type": {
+                    "path": ["ink_primitives", "types", "AccountId"],
+                    "def": { "composite": { "fields": [{ "type": 1, "typeName": "[u8; 32]" }] } }
+                } }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_decode_transfer_event() {
+        let topic = [0xb5u8; 32];
+        let decoder =
+            EventDecoder::new(&metadata(json!(format!("0x{}", hex::encode(topic))))).unwrap();
+
+        let alice = AccountId32([0xd4; 32]);
+        let mut data = Vec::new();
+        None::<[u8; 32]>.encode_to(&mut data);
+        Some(alice.0).encode_to(&mut data);
+        1_000u128.encode_to(&mut data);
+
+        let event = decoder.decode(&[topic], &data).unwrap();
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(event.fields[0], ("from".to_string(), JsonValue::Null));
+        assert_eq!(event.fields[1].1, json!(alice.to_string()));
+        assert_eq!(
+            event.to_string(),
+            format!("Transfer {{ from: null, to: {}, value: 1000 }}", alice)
+        );
+        assert_eq!(event.to_json()["fields"]["value"], "1000");
+
+        assert!(decoder.decode(&[[0u8; 32]], &data).is_err());
+        assert_eq!(
+            decoder.topic_labels("Transfer"),
+            [None, Some("from".to_string()), Some("to".to_string())]
+        );
+        assert_eq!(
+            decoder.field_types("Transfer"),
+            [None, None, Some("Balance".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_decode_indexed_event() {
+        // ink! 4: no signature topics, data prefixed with the event index
+        let mut metadata = metadata(JsonValue::Null);
+        metadata["spec"]["events"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("signature_topic");
+        let decoder = EventDecoder::new(&metadata).unwrap();
+
+        let mut data = vec![0u8];
+        None::<[u8; 32]>.encode_to(&mut data);
+        None::<[u8; 32]>.encode_to(&mut data);
+        5u128.encode_to(&mut data);
+
+        let event = decoder.decode(&[], &data).unwrap();
+        assert_eq!(event.fields[2], ("value".to_string(), json!("5")));
+
+        let (contract, payload) =
+            contract_emitted(&(AccountId32([1; 32]), data.clone()).encode()).unwrap();
+        assert_eq!(contract, AccountId32([1; 32]));
+        assert_eq!(payload, data);
+    }
+
+    #[test]
+    fn test_decode_call() {
+        let decoder = EventDecoder::new(&metadata(JsonValue::Null)).unwrap();
+        let alice = AccountId32([0xd4; 32]);
+        let mut data = vec![0x84, 0xa1, 0x5d, 0xa1];
+        alice.0.encode_to(&mut data);
+        7u128.encode_to(&mut data);
+
+        let call = decoder.decode_call(&data).unwrap();
+        assert_eq!(
+            call.to_string(),
+            format!("transfer {{ to: {}, value: 7 }}", alice)
+        );
+        assert!(decoder.decode_call(&[0, 0, 0, 0]).is_err());
+        assert!(decoder.decode_call(&data[..3]).is_err());
+    }
+}
@@ -8,6 +8,8 @@ use subxt_signer::sr25519::Keypair;
 // Re-export SDK modules for convenience
 pub use glin_contracts::{chain_info, encoding, metadata, metadata_fetcher};
 
+pub mod events;
+
 #[derive(Debug)]
 pub struct DeployResult {
     pub success: bool,
@@ -15,6 +17,8 @@ pub struct DeployResult {
     pub code_hash: Option<String>,
     pub tx_hash: Option<String>,
     pub block_hash: Option<String>,
+    /// Number of the block the contract was instantiated in
+    pub block_number: Option<u64>,
     pub error: Option<String>,
 }
 
@@ -24,6 +28,10 @@ pub struct TxResult {
     pub tx_hash: Option<String>,
     pub block_hash: Option<String>,
     pub error: Option<String>,
+    /// Events emitted by the extrinsic, as `Pallet.Variant` names
+    pub events: Vec<String>,
+    /// Payloads of its `ContractEmitted` events, in emission order
+    pub contract_events: Vec<events::EmittedEvent>,
 }
 
 #[derive(Debug)]
@@ -42,7 +50,50 @@ pub struct UploadResult {
     pub error: Option<String>,
 }
 
+/// Progress of a submitted transaction
+pub type TxProgress = subxt::tx::TxProgress<glin_client::GlinConfig, GlinClient>;
+
+/// A transaction in a block
+pub type TxInBlock = subxt::tx::TxInBlock<glin_client::GlinConfig, GlinClient>;
+
+/// Wait for a transaction to be finalized, reporting each stage it reaches to an RPC client
+/// listening for progress
+pub async fn wait_for_finalized(mut progress: TxProgress) -> Result<TxInBlock, subxt::Error> {
+    use crate::rpc::progress::{report, Stage};
+    use subxt::error::{RpcError, TransactionError};
+    use subxt::tx::TxStatus;
+
+    let tx_hash = format!("0x{}", hex::encode(progress.extrinsic_hash()));
+    report(Stage::Submitted {
+        tx_hash: tx_hash.clone(),
+    });
+    while let Some(status) = progress.next().await {
+        match status? {
+            TxStatus::InBestBlock(in_block) => report(Stage::InBlock {
+                tx_hash: tx_hash.clone(),
+                block_hash: format!("{:?}", in_block.block_hash()),
+            }),
+            TxStatus::NoLongerInBestBlock => report(Stage::Retracted {
+                tx_hash: tx_hash.clone(),
+            }),
+            TxStatus::InFinalizedBlock(in_block) => {
+                report(Stage::Finalized {
+                    tx_hash,
+                    block_hash: format!("{:?}", in_block.block_hash()),
+                });
+                return Ok(in_block);
+            }
+            TxStatus::Error { message } => return Err(TransactionError::Error(message).into()),
+            TxStatus::Invalid { message } => return Err(TransactionError::Invalid(message).into()),
+            TxStatus::Dropped { message } => return Err(TransactionError::Dropped(message).into()),
+            TxStatus::Validated | TxStatus::Broadcasted => {}
+        }
+    }
+    Err(RpcError::SubscriptionDropped.into())
+}
+
 /// Deploy a contract (upload + instantiate)
+#[allow(clippy::too_many_arguments)]
 pub async fn deploy_contract(
     client: &GlinClient,
     wasm_code: Vec<u8>,
@@ -50,6 +101,7 @@ pub async fn deploy_contract(
     constructor_args: Vec<String>,
     constructor_name: Option<&str>,
     value: u128,
+    salt: Option<&str>,
     signer: &Keypair,
 ) -> Result<DeployResult> {
     println!(
@@ -59,8 +111,35 @@ pub async fn deploy_contract(
     println!("Constructor args: {:?}", constructor_args);
     println!("Value: {}", value);
 
+    let tx = deploy_tx(
+        &wasm_code,
+        metadata,
+        &constructor_args,
+        constructor_name,
+        value,
+        salt,
+    )?;
+
+    // Submit and watch transaction
+    let progress = client
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .context("Failed to submit transaction")?;
+    deploy_outcome(client, progress, None, "Transaction failed").await
+}
+
+/// `Contracts::instantiate_with_code` extrinsic uploading and instantiating a contract
+pub fn deploy_tx(
+    wasm_code: &[u8],
+    metadata: &InkProject,
+    constructor_args: &[String],
+    constructor_name: Option<&str>,
+    value: u128,
+    salt: Option<&str>,
+) -> Result<subxt::tx::DynamicPayload> {
     // Encode constructor selector and args
-    let data = encode_constructor_call(&constructor_args, metadata, constructor_name)?;
+    let data = encode_constructor_call(constructor_args, metadata, constructor_name)?;
 
     // Build dynamic transaction for instantiate_with_code
     let gas_limit_value = subxt::dynamic::Value::unnamed_composite(vec![
@@ -68,36 +147,38 @@ pub async fn deploy_contract(
         subxt::dynamic::Value::u128(2_000_000),     // proof_size
     ]);
 
-    let tx = subxt::dynamic::tx(
+    Ok(subxt::dynamic::tx(
         "Contracts",
         "instantiate_with_code",
         vec![
             subxt::dynamic::Value::u128(value),
             gas_limit_value,
             subxt::dynamic::Value::unnamed_variant("None", vec![]), // storage_deposit_limit
-            subxt::dynamic::Value::from_bytes(&wasm_code),
+            subxt::dynamic::Value::from_bytes(wasm_code),
             subxt::dynamic::Value::from_bytes(&data),
-            subxt::dynamic::Value::from_bytes(vec![0u8; 32]), // salt
+            subxt::dynamic::Value::from_bytes(salt_bytes(salt)?),
         ],
-    );
+    ))
+}
 
-    // Submit and watch transaction
-    let events = client
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, signer)
-        .await
-        .context("Failed to submit transaction")?
-        .wait_for_finalized_success()
-        .await
-        .context("Transaction failed")?;
+/// Wait for a deploy or instantiate transaction and collect the new contract from its events;
+/// `code_hash` is reported when no code was stored
+pub async fn deploy_outcome(
+    client: &GlinClient,
+    progress: TxProgress,
+    code_hash: Option<&str>,
+    failure: &'static str,
+) -> Result<DeployResult> {
+    let in_block = wait_for_finalized(progress).await.context(failure)?;
+    let block_hash = in_block.block_hash();
+    let events = in_block.wait_for_success().await.context(failure)?;
+    let block_number = client.blocks().at(block_hash).await?.number() as u64;
 
     let tx_hash = format!("0x{}", hex::encode(events.extrinsic_hash()));
-    // Note: Block hash not directly available in ExtrinsicEvents in subxt 0.44
-    let block_hash = String::from("N/A");
 
     // Find events
     let mut contract_address = None;
-    let mut code_hash = None;
+    let mut stored_code_hash = None;
 
     for event in events.iter() {
         let event = event.context("Failed to decode event")?;
@@ -118,7 +199,7 @@ pub async fn deploy_contract(
                     let field_values = event.field_values()?;
                     if let Ok(json) = serde_json::to_value(&field_values) {
                         if let Some(hash) = json.get("code_hash") {
-                            code_hash = Some(hash.to_string().trim_matches('"').to_string());
+                            stored_code_hash = Some(hash.to_string().trim_matches('"').to_string());
                         }
                     }
                 }
@@ -130,13 +211,25 @@ pub async fn deploy_contract(
     Ok(DeployResult {
         success: true,
         contract_address,
-        code_hash,
+        code_hash: stored_code_hash.or_else(|| code_hash.map(str::to_string)),
         tx_hash: Some(tx_hash),
-        block_hash: Some(block_hash),
+        block_hash: Some(format!("{:?}", block_hash)),
+        block_number: Some(block_number),
         error: None,
     })
 }
 
+/// Instantiation salt: hex when `0x`-prefixed, the UTF-8 bytes otherwise, 32 zero bytes by default
+pub fn salt_bytes(salt: Option<&str>) -> Result<Vec<u8>> {
+    match salt {
+        None => Ok(vec![0u8; 32]),
+        Some(hex_salt) if hex_salt.starts_with("0x") => {
+            hex::decode(&hex_salt[2..]).context("Invalid salt hex")
+        }
+        Some(salt) => Ok(salt.as_bytes().to_vec()),
+    }
+}
+
 /// Upload contract code without instantiation
 pub async fn upload_code(
     client: &GlinClient,
@@ -145,27 +238,40 @@ pub async fn upload_code(
 ) -> Result<UploadResult> {
     println!("Uploading {} bytes of WASM code", wasm_code.len());
 
-    let tx = subxt::dynamic::tx(
+    let tx = upload_tx(&wasm_code);
+    let progress = client
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .context("Failed to submit upload transaction")?;
+    upload_outcome(progress).await
+}
+
+/// `Contracts::upload_code` extrinsic storing contract code
+pub fn upload_tx(wasm_code: &[u8]) -> subxt::tx::DynamicPayload {
+    subxt::dynamic::tx(
         "Contracts",
         "upload_code",
         vec![
-            subxt::dynamic::Value::from_bytes(&wasm_code),
+            subxt::dynamic::Value::from_bytes(wasm_code),
             subxt::dynamic::Value::unnamed_variant("None", vec![]), // storage_deposit_limit
             subxt::dynamic::Value::unnamed_variant("Enforced", vec![]), // Determinism::Enforced
         ],
-    );
+    )
+}
 
-    let events = client
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, signer)
+/// Wait for an upload transaction and find the stored code hash in its events
+pub async fn upload_outcome(progress: TxProgress) -> Result<UploadResult> {
+    let in_block = wait_for_finalized(progress)
         .await
-        .context("Failed to submit upload transaction")?
-        .wait_for_finalized_success()
+        .context("Upload transaction failed")?;
+    let block_hash = format!("{:?}", in_block.block_hash());
+    let events = in_block
+        .wait_for_success()
         .await
         .context("Upload transaction failed")?;
 
     let tx_hash = format!("0x{}", hex::encode(events.extrinsic_hash()));
-    let block_hash = String::from("N/A"); // Block hash not in ExtrinsicEvents
 
     // Find CodeStored event
     let mut code_hash = None;
@@ -194,6 +300,7 @@ pub async fn upload_code(
 }
 
 /// Instantiate contract from uploaded code hash
+#[allow(clippy::too_many_arguments)]
 pub async fn instantiate_contract(
     client: &GlinClient,
     code_hash: &str,
@@ -201,14 +308,47 @@ pub async fn instantiate_contract(
     constructor_args: Vec<String>,
     constructor_name: Option<&str>,
     value: u128,
+    salt: Option<&str>,
     signer: &Keypair,
 ) -> Result<DeployResult> {
     println!("Instantiating contract from code hash: {}", code_hash);
     println!("Constructor args: {:?}", constructor_args);
     println!("Value: {}", value);
 
+    let tx = instantiate_tx(
+        code_hash,
+        metadata,
+        &constructor_args,
+        constructor_name,
+        value,
+        salt,
+    )?;
+
+    let progress = client
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .context("Failed to submit instantiate transaction")?;
+    deploy_outcome(
+        client,
+        progress,
+        Some(code_hash),
+        "Instantiate transaction failed",
+    )
+    .await
+}
+
+/// `Contracts::instantiate` extrinsic instantiating uploaded code
+pub fn instantiate_tx(
+    code_hash: &str,
+    metadata: &InkProject,
+    constructor_args: &[String],
+    constructor_name: Option<&str>,
+    value: u128,
+    salt: Option<&str>,
+) -> Result<subxt::tx::DynamicPayload> {
     // Encode constructor data
-    let data = encode_constructor_call(&constructor_args, metadata, constructor_name)?;
+    let data = encode_constructor_call(constructor_args, metadata, constructor_name)?;
 
     // Decode code hash
     let code_hash_bytes =
@@ -222,7 +362,7 @@ pub async fn instantiate_contract(
         subxt::dynamic::Value::u128(2_000_000),
     ]);
 
-    let tx = subxt::dynamic::tx(
+    Ok(subxt::dynamic::tx(
         "Contracts",
         "instantiate",
         vec![
@@ -231,47 +371,9 @@ pub async fn instantiate_contract(
             subxt::dynamic::Value::unnamed_variant("None", vec![]),
             subxt::dynamic::Value::from_bytes(code_hash_array),
             subxt::dynamic::Value::from_bytes(&data),
-            subxt::dynamic::Value::from_bytes(vec![0u8; 32]), // salt
+            subxt::dynamic::Value::from_bytes(salt_bytes(salt)?),
         ],
-    );
-
-    let events = client
-        .tx()
-        .sign_and_submit_then_watch_default(&tx, signer)
-        .await
-        .context("Failed to submit instantiate transaction")?
-        .wait_for_finalized_success()
-        .await
-        .context("Instantiate transaction failed")?;
-
-    let tx_hash = format!("0x{}", hex::encode(events.extrinsic_hash()));
-    let block_hash = String::from("N/A"); // Block hash not in ExtrinsicEvents
-
-    // Find Instantiated event
-    let mut contract_address = None;
-
-    for event in events.iter() {
-        let event = event.context("Failed to decode event")?;
-
-        if event.pallet_name() == "Contracts" && event.variant_name() == "Instantiated" {
-            let field_values = event.field_values()?;
-            if let Ok(json) = serde_json::to_value(&field_values) {
-                if let Some(contract) = json.get("contract") {
-                    contract_address = Some(contract.to_string().trim_matches('"').to_string());
-                    break;
-                }
-            }
-        }
-    }
-
-    Ok(DeployResult {
-        success: true,
-        contract_address,
-        code_hash: Some(code_hash.to_string()),
-        tx_hash: Some(tx_hash),
-        block_hash: Some(block_hash),
-        error: None,
-    })
+    ))
 }
 
 /// Call a contract method (transaction)
@@ -288,49 +390,128 @@ pub async fn call_contract(
     println!("Args: {:?}", args);
     println!("Value: {}", value);
 
+    let tx = contract_call_tx(contract_address, metadata, method, &args, value)?;
+
+    let progress = client
+        .tx()
+        .sign_and_submit_then_watch_default(&tx, signer)
+        .await
+        .context("Failed to submit call transaction")?;
+    call_outcome(progress).await
+}
+
+/// `Contracts::call` extrinsic calling a contract method with the default gas limit
+pub fn contract_call_tx(
+    contract_address: &str,
+    metadata: &InkProject,
+    method: &str,
+    args: &[String],
+    value: u128,
+) -> Result<subxt::tx::DynamicPayload> {
     // Encode method call
-    let data = encode_method_call(method, &args, metadata)?;
+    let data = encode_method_call(method, args, metadata)?;
 
     // Parse contract address
     let dest = parse_account_id(contract_address)?;
 
+    let gas_limit = Weight {
+        ref_time: 3_000_000_000,
+        proof_size: 1_000_000,
+    };
+    Ok(call_tx(&dest, value, gas_limit, &data))
+}
+
+/// Wait for a call transaction and list the events it emitted
+pub async fn call_outcome(progress: TxProgress) -> Result<TxResult> {
+    let in_block = wait_for_finalized(progress)
+        .await
+        .context("Call transaction failed")?;
+    let block_hash = format!("{:?}", in_block.block_hash());
+    let events = in_block
+        .wait_for_success()
+        .await
+        .context("Call transaction failed")?;
+
+    let tx_hash = format!("0x{}", hex::encode(events.extrinsic_hash()));
+
+    let mut event_names = Vec::new();
+    let mut contract_events = Vec::new();
+    for event in events.iter() {
+        let event = event.context("Failed to decode event")?;
+        event_names.push(format!("{}.{}", event.pallet_name(), event.variant_name()));
+        if event.pallet_name() == "Contracts" && event.variant_name() == "ContractEmitted" {
+            let (contract, data) = events::contract_emitted(event.field_bytes())?;
+            contract_events.push(events::EmittedEvent {
+                contract,
+                topics: event.topics().iter().map(|topic| topic.0).collect(),
+                data,
+            });
+        }
+    }
+
+    Ok(TxResult {
+        success: true,
+        tx_hash: Some(tx_hash),
+        block_hash: Some(block_hash),
+        error: None,
+        events: event_names,
+        contract_events,
+    })
+}
+
+/// `Contracts::call` extrinsic sending `data` to a contract
+fn call_tx(
+    dest: &AccountId32,
+    value: u128,
+    gas_limit: Weight,
+    data: &[u8],
+) -> subxt::tx::DynamicPayload {
     let gas_limit_value = subxt::dynamic::Value::unnamed_composite(vec![
-        subxt::dynamic::Value::u128(3_000_000_000),
-        subxt::dynamic::Value::u128(1_000_000),
+        subxt::dynamic::Value::u128(gas_limit.ref_time as u128),
+        subxt::dynamic::Value::u128(gas_limit.proof_size as u128),
     ]);
 
-    let tx = subxt::dynamic::tx(
+    subxt::dynamic::tx(
         "Contracts",
         "call",
         vec![
-            subxt::dynamic::Value::unnamed_composite(vec![subxt::dynamic::Value::from_bytes(
-                dest.0,
-            )]),
+            subxt::dynamic::Value::unnamed_variant(
+                "Id",
+                vec![subxt::dynamic::Value::from_bytes(dest.0)],
+            ),
             subxt::dynamic::Value::u128(value),
             gas_limit_value,
             subxt::dynamic::Value::unnamed_variant("None", vec![]),
-            subxt::dynamic::Value::from_bytes(&data),
+            subxt::dynamic::Value::from_bytes(data),
         ],
-    );
+    )
+}
+
+/// Transaction fee of calling a contract method with the given gas limit
+#[allow(clippy::too_many_arguments)]
+pub async fn estimate_call_fee(
+    client: &GlinClient,
+    contract_address: &str,
+    metadata: &InkProject,
+    method: &str,
+    args: &[String],
+    value: u128,
+    gas_limit: Weight,
+    signer: &Keypair,
+) -> Result<u128> {
+    let data = encode_method_call(method, args, metadata)?;
+    let dest = parse_account_id(contract_address)?;
+    let tx = call_tx(&dest, value, gas_limit, &data);
 
-    let events = client
+    let signed = client
         .tx()
-        .sign_and_submit_then_watch_default(&tx, signer)
+        .create_signed(&tx, signer, Default::default())
         .await
-        .context("Failed to submit call transaction")?
-        .wait_for_finalized_success()
+        .context("Failed to sign call transaction")?;
+    signed
+        .partial_fee_estimate()
         .await
-        .context("Call transaction failed")?;
-
-    let tx_hash = format!("0x{}", hex::encode(events.extrinsic_hash()));
-    let block_hash = String::from("N/A"); // Block hash not in ExtrinsicEvents
-
-    Ok(TxResult {
-        success: true,
-        tx_hash: Some(tx_hash),
-        block_hash: Some(block_hash),
-        error: None,
-    })
+        .context("Failed to query the transaction fee")
 }
 
 /// Query contract state (read-only)
@@ -345,20 +526,87 @@ pub async fn query_contract(
     println!("Querying contract {} method {}", contract_address, method);
     println!("Args: {:?}", args);
 
+    // Create origin (dummy account for dry-run)
+    let origin = AccountId32([0u8; 32]);
+
+    let exec_result = dry_run_call(
+        rpc_url,
+        &origin,
+        contract_address,
+        metadata,
+        method,
+        args,
+        0,
+    )
+    .await?;
+
+    // Get the message spec to find return type
+    let message = metadata::get_message_spec(metadata, method)?;
+    let return_type_spec = metadata::get_message_return_type(message);
+
+    // Decode the return data
+    let decoded_data = if let Some(data) = exec_result.data {
+        encoding::decode_result(&data, Some(return_type_spec), metadata)?
+    } else {
+        serde_json::Value::Null
+    };
+
+    Ok(QueryResult {
+        success: exec_result.success,
+        data: Some(serde_json::to_string(&decoded_data)?),
+        error: exec_result.error,
+    })
+}
+
+/// Two-dimensional weight (refTime, proofSize)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Weight {
+    pub ref_time: u64,
+    pub proof_size: u64,
+}
+
+/// Storage deposit reported by a dry-run: positive values are charged, negative refunded
+pub type StorageDeposit = i128;
+
+/// Result of dry-running a contract message via `ContractsApi_call`
+#[derive(Debug, Clone)]
+pub struct DryRunResult {
+    /// Execution succeeded and the contract did not revert
+    pub success: bool,
+    /// Contract returned with the REVERT flag set
+    pub reverted: bool,
+    pub gas_consumed: Weight,
+    pub gas_required: Weight,
+    pub storage_deposit: StorageDeposit,
+    /// Raw SCALE-encoded return data
+    pub data: Option<Vec<u8>>,
+    pub debug_message: String,
+    /// SCALE-encoded `DispatchError` when execution failed
+    pub dispatch_error: Option<Vec<u8>>,
+    pub error: Option<String>,
+}
+
+/// Dry-run a contract message without submitting a transaction
+pub async fn dry_run_call(
+    rpc_url: &str,
+    origin: &AccountId32,
+    contract_address: &str,
+    metadata: &InkProject,
+    method: &str,
+    args: Vec<String>,
+    value: u128,
+) -> Result<DryRunResult> {
     // Encode method call
     let data = encode_method_call(method, &args, metadata)?;
 
     // Parse contract address
     let dest = parse_account_id(contract_address)?;
 
-    // Create origin (dummy account for dry-run)
-    let origin = AccountId32([0u8; 32]);
-
     // Prepare RPC call parameters
     let call_params = (
         origin.0.to_vec(),
         dest.0.to_vec(),
-        0u128,        // value
+        value,
         None::<u64>,  // gas_limit (None = estimate)
         None::<u128>, // storage_deposit_limit
         data,
@@ -376,66 +624,41 @@ pub async fn query_contract(
         .await
         .context("Contract query RPC call failed")?;
 
-    // Decode ContractExecResult
-    // The result contains a ContractExecResult structure
-    // We need to decode it to extract the actual return data
-    let exec_result = decode_contract_exec_result(&result_bytes)?;
-
-    // Get the message spec to find return type
-    let message = metadata::get_message_spec(metadata, method)?;
-    let return_type_spec = metadata::get_message_return_type(message);
-
-    // Decode the return data
-    let decoded_data = if let Some(data) = exec_result.data {
-        encoding::decode_result(&data, Some(return_type_spec), metadata)?
-    } else {
-        serde_json::Value::Null
-    };
-
-    Ok(QueryResult {
-        success: exec_result.success,
-        data: Some(serde_json::to_string(&decoded_data)?),
-        error: exec_result.error,
-    })
-}
-
-/// Simplified ContractExecResult for decoding RPC response
-struct ContractExecResultDecoded {
-    success: bool,
-    data: Option<Vec<u8>>,
-    error: Option<String>,
+    decode_contract_exec_result(&result_bytes)
 }
 
 /// Decode ContractExecResult from RPC response
-fn decode_contract_exec_result(bytes: &[u8]) -> Result<ContractExecResultDecoded> {
-    use scale::Decode;
+fn decode_contract_exec_result(bytes: &[u8]) -> Result<DryRunResult> {
+    use scale::{Compact, Decode};
 
-    // ContractExecResult structure (simplified):
-    // - gas_consumed: Weight
+    // ContractExecResult structure:
+    // - gas_consumed: Weight { ref_time: Compact<u64>, proof_size: Compact<u64> }
     // - gas_required: Weight
-    // - storage_deposit: StorageDeposit
+    // - storage_deposit: StorageDeposit (Refund(u128) | Charge(u128))
     // - debug_message: Vec<u8>
     // - result: Result<ExecReturnValue, DispatchError>
 
     let mut input = bytes;
 
-    // Skip gas_consumed (WeightV2 - 2x u64)
-    let _ref_time = u64::decode(&mut input)?;
-    let _proof_size = u64::decode(&mut input)?;
+    let decode_weight = |input: &mut &[u8]| -> Result<Weight> {
+        Ok(Weight {
+            ref_time: Compact::<u64>::decode(input)?.0,
+            proof_size: Compact::<u64>::decode(input)?.0,
+        })
+    };
 
-    // Skip gas_required (WeightV2 - 2x u64)
-    let _ref_time_required = u64::decode(&mut input)?;
-    let _proof_size_required = u64::decode(&mut input)?;
+    let gas_consumed = decode_weight(&mut input)?;
+    let gas_required = decode_weight(&mut input)?;
 
-    // Skip storage_deposit (enum variant index + optional value)
-    let _storage_deposit_variant = u8::decode(&mut input)?;
-    // Storage deposit value if variant != 0
-    if _storage_deposit_variant != 0 {
-        let _deposit_value = u128::decode(&mut input)?;
-    }
+    let storage_deposit_variant = u8::decode(&mut input)?;
+    let deposit_value = u128::decode(&mut input)? as i128;
+    let storage_deposit = if storage_deposit_variant == 0 {
+        -deposit_value
+    } else {
+        deposit_value
+    };
 
-    // Skip debug_message
-    let _debug_msg = Vec::<u8>::decode(&mut input)?;
+    let debug_message = String::from_utf8_lossy(&Vec::<u8>::decode(&mut input)?).to_string();
 
     // Decode result: Result<ExecReturnValue, DispatchError>
     let result_variant = u8::decode(&mut input)?;
@@ -443,24 +666,110 @@ fn decode_contract_exec_result(bytes: &[u8]) -> Result<ContractExecResultDecoded
     if result_variant == 0 {
         // Ok variant - contains ExecReturnValue
         // ExecReturnValue { flags: u32, data: Vec<u8> }
-        let _flags = u32::decode(&mut input)?;
+        let flags = u32::decode(&mut input)?;
         let data = Vec::<u8>::decode(&mut input)?;
-
-        Ok(ContractExecResultDecoded {
-            success: true,
+        let reverted = flags & 1 != 0;
+
+        Ok(DryRunResult {
+            success: !reverted,
+            reverted,
+            gas_consumed,
+            gas_required,
+            storage_deposit,
             data: Some(data),
-            error: None,
+            debug_message,
+            dispatch_error: None,
+            error: reverted.then(|| "Contract reverted".to_string()),
         })
     } else {
         // Err variant - contains DispatchError
-        Ok(ContractExecResultDecoded {
+        Ok(DryRunResult {
             success: false,
+            reverted: false,
+            gas_consumed,
+            gas_required,
+            storage_deposit,
             data: None,
+            debug_message,
+            dispatch_error: Some(input.to_vec()),
             error: Some("Contract execution failed".to_string()),
         })
     }
 }
 
+/// Readable form of a SCALE-encoded `DispatchError`, naming module errors from the runtime metadata
+pub fn describe_dispatch_error(bytes: &[u8], runtime: Option<&subxt::Metadata>) -> String {
+    const VARIANTS: [&str; 14] = [
+        "Other",
+        "CannotLookup",
+        "BadOrigin",
+        "Module",
+        "ConsumerRemaining",
+        "NoProviders",
+        "TooManyConsumers",
+        "Token",
+        "Arithmetic",
+        "Transactional",
+        "Exhausted",
+        "Corruption",
+        "Unavailable",
+        "RootNotAllowed",
+    ];
+    const TOKEN_ERRORS: [&str; 10] = [
+        "FundsUnavailable",
+        "OnlyProvider",
+        "BelowMinimum",
+        "CannotCreate",
+        "UnknownAsset",
+        "Frozen",
+        "Unsupported",
+        "CannotCreateHold",
+        "NotExpendable",
+        "Blocked",
+    ];
+    const ARITHMETIC_ERRORS: [&str; 3] = ["Underflow", "Overflow", "DivisionByZero"];
+    const TRANSACTIONAL_ERRORS: [&str; 2] = ["LimitReached", "NoLayer"];
+
+    let named = |names: &[&str], index: Option<&u8>| {
+        index
+            .and_then(|i| names.get(*i as usize))
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| "Unknown".to_string())
+    };
+
+    match bytes.first() {
+        Some(3) => {
+            let (Some(&pallet), Some(&error)) = (bytes.get(1), bytes.get(2)) else {
+                return "Module error".to_string();
+            };
+            let variant = runtime.and_then(|runtime| {
+                let pallet = runtime.pallet_by_index(pallet)?;
+                let variant = pallet.error_variant_by_index(error)?;
+                Some((pallet.name().to_string(), variant.clone()))
+            });
+            match variant {
+                Some((pallet, variant)) if variant.docs.is_empty() => {
+                    format!("{}::{}", pallet, variant.name)
+                }
+                Some((pallet, variant)) => format!(
+                    "{}::{}: {}",
+                    pallet,
+                    variant.name,
+                    variant.docs.join(" ").trim()
+                ),
+                None => format!("Module error {} of pallet {}", error, pallet),
+            }
+        }
+        Some(7) => format!("Token::{}", named(&TOKEN_ERRORS, bytes.get(1))),
+        Some(8) => format!("Arithmetic::{}", named(&ARITHMETIC_ERRORS, bytes.get(1))),
+        Some(9) => format!(
+            "Transactional::{}",
+            named(&TRANSACTIONAL_ERRORS, bytes.get(1))
+        ),
+        index => named(&VARIANTS, index),
+    }
+}
+
 /// Encode constructor call with selector and arguments
 fn encode_constructor_call(
     args: &[String],
@@ -508,7 +817,7 @@ fn encode_method_call(method: &str, args: &[String], metadata: &InkProject) -> R
 }
 
 /// Parse account ID from various formats
-fn parse_account_id(address: &str) -> Result<AccountId32> {
+pub fn parse_account_id(address: &str) -> Result<AccountId32> {
     use std::str::FromStr;
 
     // If it's hex, decode it
@@ -527,3 +836,70 @@ fn parse_account_id(address: &str) -> Result<AccountId32> {
 
     anyhow::bail!("Invalid address format: {}", address)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Compact;
+
+    #[test]
+    fn test_decode_contract_exec_result() {
+        let mut bytes = Vec::new();
+        // gas_consumed, gas_required
+        Compact(1_000_000u64).encode_to(&mut bytes);
+        Compact(2_048u64).encode_to(&mut bytes);
+        Compact(1_500_000u64).encode_to(&mut bytes);
+        Compact(4_096u64).encode_to(&mut bytes);
+        // StorageDeposit::Charge(500)
+        1u8.encode_to(&mut bytes);
+        500u128.encode_to(&mut bytes);
+        // debug_message
+        b"hello".to_vec().encode_to(&mut bytes);
+        // Ok(ExecReturnValue { flags: REVERT, data })
+        0u8.encode_to(&mut bytes);
+        1u32.encode_to(&mut bytes);
+        vec![0x01u8, 0x02].encode_to(&mut bytes);
+
+        let result = decode_contract_exec_result(&bytes).unwrap();
+        assert_eq!(
+            result.gas_consumed,
+            Weight {
+                ref_time: 1_000_000,
+                proof_size: 2_048
+            }
+        );
+        assert_eq!(result.gas_required.proof_size, 4_096);
+        assert_eq!(result.storage_deposit, 500);
+        assert_eq!(result.debug_message, "hello");
+        assert!(result.reverted);
+        assert!(!result.success);
+        assert_eq!(result.data, Some(vec![0x01, 0x02]));
+        assert_eq!(result.dispatch_error, None);
+    }
+
+    #[test]
+    fn test_describe_dispatch_error() {
+        assert_eq!(describe_dispatch_error(&[2], None), "BadOrigin");
+        assert_eq!(
+            describe_dispatch_error(&[7, 0], None),
+            "Token::FundsUnavailable"
+        );
+        assert_eq!(
+            describe_dispatch_error(&[8, 1], None),
+            "Arithmetic::Overflow"
+        );
+        assert_eq!(
+            describe_dispatch_error(&[3, 8, 11, 0, 0, 0], None),
+            "Module error 11 of pallet 8"
+        );
+        assert_eq!(describe_dispatch_error(&[42], None), "Unknown");
+    }
+
+    #[test]
+    fn test_salt_bytes() {
+        assert_eq!(salt_bytes(None).unwrap(), vec![0u8; 32]);
+        assert_eq!(salt_bytes(Some("0x0102")).unwrap(), vec![1, 2]);
+        assert_eq!(salt_bytes(Some("v2")).unwrap(), b"v2".to_vec());
+        assert!(salt_bytes(Some("0xzz")).is_err());
+    }
+}
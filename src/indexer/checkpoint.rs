@@ -0,0 +1,177 @@
+// Resumable processing and reorg detection for `watch` and `index`
+//
+// Both commands remember the last block they fully processed per (network,
+// contract): `index` in its database, `watch` in `.glin-forge/checkpoints.json`.
+// Following non-finalized blocks, a block whose hash or parent disagrees with
+// the recorded hashes means the chain reorganized; processing rewinds to the
+// newest recorded block still on the canonical chain.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use subxt::backend::legacy::LegacyRpcMethods;
+
+use glin_client::GlinConfig;
+
+/// Checkpoint file of `watch`
+pub const CHECKPOINT_FILE: &str = ".glin-forge/checkpoints.json";
+
+/// Deepest reorganization handled; also how many block hashes are remembered
+pub const MAX_REORG_DEPTH: usize = 256;
+
+/// Last fully processed block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub block: u64,
+    pub hash: String,
+}
+
+/// Checkpoints keyed by `<network>/<contract>`
+pub struct CheckpointFile {
+    path: PathBuf,
+    entries: BTreeMap<String, Checkpoint>,
+}
+
+impl CheckpointFile {
+    /// Load a checkpoint file, empty when it does not exist yet
+    pub fn open(path: &Path) -> Result<Self> {
+        let entries = if path.exists() {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Invalid checkpoint file {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    pub fn get(&self, network: &str, contract: &str) -> Option<&Checkpoint> {
+        self.entries.get(&format!("{}/{}", network, contract))
+    }
+
+    /// Record a checkpoint, replacing the file atomically
+    pub fn set(&mut self, network: &str, contract: &str, checkpoint: Checkpoint) -> Result<()> {
+        self.entries
+            .insert(format!("{}/{}", network, contract), checkpoint);
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&self.entries)?)?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+/// Whether a block conflicts with the recorded hash of its height or of its parent
+pub fn is_reorg(
+    number: u64,
+    hash: &str,
+    parent: &str,
+    known: impl Fn(u64) -> Option<String>,
+) -> bool {
+    let conflicts = |n: u64, expected: &str| known(n).is_some_and(|h| h != expected);
+    conflicts(number, hash) || (number > 0 && conflicts(number - 1, parent))
+}
+
+/// Newest of the recorded blocks (newest first) still on the canonical chain
+pub async fn common_ancestor(
+    rpc: &LegacyRpcMethods<GlinConfig>,
+    known: &[(u64, String)],
+) -> Result<u64> {
+    for (number, hash) in known {
+        let canonical = rpc.chain_get_block_hash(Some((*number).into())).await?;
+        if canonical.is_some_and(|h| format!("{:?}", h) == *hash) {
+            return Ok(*number);
+        }
+    }
+    anyhow::bail!(
+        "Chain reorganization deeper than the {} recorded blocks; restart with --from-block",
+        known.len()
+    )
+}
+
+/// Hashes of the latest processed blocks, for processes without a database
+#[derive(Debug, Default)]
+pub struct RecentBlocks {
+    blocks: VecDeque<(u64, String)>,
+}
+
+impl RecentBlocks {
+    pub fn get(&self, number: u64) -> Option<String> {
+        self.blocks
+            .iter()
+            .find(|(n, _)| *n == number)
+            .map(|(_, hash)| hash.clone())
+    }
+
+    /// Record a processed block, forgetting any block at or above its height
+    pub fn push(&mut self, number: u64, hash: String) {
+        self.rollback(number.saturating_sub(1));
+        self.blocks.push_back((number, hash));
+        if self.blocks.len() > MAX_REORG_DEPTH {
+            self.blocks.pop_front();
+        }
+    }
+
+    /// Forget the blocks above `ancestor`
+    pub fn rollback(&mut self, ancestor: u64) {
+        self.blocks.retain(|(n, _)| *n <= ancestor);
+    }
+
+    /// Recorded blocks, newest first
+    pub fn newest_first(&self) -> Vec<(u64, String)> {
+        self.blocks.iter().rev().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorg_detection() {
+        let mut recent = RecentBlocks::default();
+        recent.push(10, "0xa10".to_string());
+        recent.push(11, "0xa11".to_string());
+
+        // Extending the chain, or seeing a processed block again
+        assert!(!is_reorg(12, "0xa12", "0xa11", |n| recent.get(n)));
+        assert!(!is_reorg(11, "0xa11", "0xa10", |n| recent.get(n)));
+        // A sibling of a processed block, or a child of an unknown one
+        assert!(is_reorg(11, "0xb11", "0xa10", |n| recent.get(n)));
+        assert!(is_reorg(12, "0xb12", "0xb11", |n| recent.get(n)));
+
+        recent.push(11, "0xb11".to_string());
+        assert_eq!(
+            recent.newest_first(),
+            [(11, "0xb11".to_string()), (10, "0xa10".to_string())]
+        );
+        recent.rollback(10);
+        assert_eq!(recent.get(11), None);
+    }
+
+    #[test]
+    fn test_checkpoint_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".glin-forge/checkpoints.json");
+
+        let mut file = CheckpointFile::open(&path).unwrap();
+        assert_eq!(file.get("testnet", "5Contract"), None);
+        let checkpoint = Checkpoint {
+            block: 42,
+            hash: "0x42".to_string(),
+        };
+        file.set("testnet", "5Contract", checkpoint.clone())
+            .unwrap();
+
+        let file = CheckpointFile::open(&path).unwrap();
+        assert_eq!(file.get("testnet", "5Contract"), Some(&checkpoint));
+        assert_eq!(file.get("mainnet", "5Contract"), None);
+    }
+}
@@ -0,0 +1,428 @@
+// Minimal GraphQL over the event database
+//
+// Supports the query subset dApp backends need: one (optionally named) query
+// operation with variables, aliases, arguments and nested selections. No
+// fragments, directives or mutations. The schema:
+//
+//   type Query {
+//     events(name, contract, account, fromBlock, toBlock, order, limit, offset): EventPage
+//     stats: [EventStats]
+//     aggregate(field!, op!, name, contract, account, fromBlock, toBlock): Float
+//   }
+//   type EventPage { total, limit, offset, nextOffset, events: [Event] }
+//   type Event { id, block, eventIndex, contract, name, data }
+//   type EventStats { name, count, firstBlock, lastBlock }
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value as JsonValue};
+use std::collections::HashMap;
+
+use super::query::{Aggregate, EventFilter};
+use super::EventStore;
+
+/// Execute a GraphQL request, producing the `{ data }` or `{ errors }` response body
+pub fn execute(store: &EventStore, query: &str, variables: &JsonValue) -> JsonValue {
+    match run(store, query, variables) {
+        Ok(data) => json!({ "data": data }),
+        Err(e) => json!({ "errors": [{ "message": format!("{:#}", e) }] }),
+    }
+}
+
+fn run(store: &EventStore, query: &str, variables: &JsonValue) -> Result<JsonValue> {
+    let selection = Parser::new(query)?.document()?;
+    let mut data = Map::new();
+    for field in &selection {
+        let args = field.arguments(variables)?;
+        let value = match field.name.as_str() {
+            "events" => serde_json::to_value(store.events(&filter(&args)?)?)?,
+            "stats" => serde_json::to_value(store.stats()?)?,
+            "aggregate" => {
+                let field_name = args
+                    .get("field")
+                    .and_then(|v| v.as_str())
+                    .context("aggregate requires a field argument")?;
+                let op: Aggregate = args
+                    .get("op")
+                    .and_then(|v| v.as_str())
+                    .context("aggregate requires an op argument")?
+                    .parse()?;
+                store.aggregate(&filter(&args)?, field_name, op)?
+            }
+            "__typename" => json!("Query"),
+            other => anyhow::bail!("Cannot query field '{}' on type 'Query'", other),
+        };
+        data.insert(field.key().to_string(), project(&value, &field.selection)?);
+    }
+    Ok(JsonValue::Object(data))
+}
+
+/// Event filter from field arguments
+fn filter(args: &HashMap<String, JsonValue>) -> Result<EventFilter> {
+    let text = |key: &str| args.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let number = |key: &str| args.get(key).and_then(|v| v.as_u64());
+    let ascending = match text("order").as_deref() {
+        None | Some("desc") | Some("DESC") => false,
+        Some("asc") | Some("ASC") => true,
+        Some(other) => anyhow::bail!("Invalid order '{}' (asc, desc)", other),
+    };
+    Ok(EventFilter {
+        name: text("name"),
+        contract: text("contract"),
+        account: text("account"),
+        from_block: number("fromBlock"),
+        to_block: number("toBlock"),
+        ascending,
+        limit: number("limit").map(|n| n as u32),
+        offset: number("offset").map(|n| n as u32),
+    })
+}
+
+/// Keep the selected fields of a result, camelCase names mapping to its snake_case keys
+fn project(value: &JsonValue, selection: &[Field]) -> Result<JsonValue> {
+    if selection.is_empty() {
+        return Ok(value.clone());
+    }
+    match value {
+        JsonValue::Array(items) => items
+            .iter()
+            .map(|item| project(item, selection))
+            .collect::<Result<Vec<_>>>()
+            .map(JsonValue::Array),
+        JsonValue::Object(object) => {
+            let mut projected = Map::new();
+            for field in selection {
+                let key = snake_case(&field.name);
+                let value = object
+                    .get(&key)
+                    .with_context(|| format!("Unknown field '{}'", field.name))?;
+                projected.insert(field.key().to_string(), project(value, &field.selection)?);
+            }
+            Ok(JsonValue::Object(projected))
+        }
+        JsonValue::Null => Ok(JsonValue::Null),
+        _ => anyhow::bail!("Scalar fields take no selection"),
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// A selected field
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    alias: Option<String>,
+    name: String,
+    arguments: Vec<(String, Value)>,
+    selection: Vec<Field>,
+}
+
+impl Field {
+    /// Response key
+    fn key(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+
+    fn arguments(&self, variables: &JsonValue) -> Result<HashMap<String, JsonValue>> {
+        self.arguments
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), value.resolve(variables)?)))
+            .filter(|arg| !matches!(arg, Ok((_, JsonValue::Null))))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Variable(String),
+    Literal(JsonValue),
+}
+
+impl Value {
+    fn resolve(&self, variables: &JsonValue) -> Result<JsonValue> {
+        match self {
+            Value::Literal(value) => Ok(value.clone()),
+            Value::Variable(name) => Ok(variables.get(name).cloned().unwrap_or(JsonValue::Null)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Punct(char),
+    Name(String),
+    Number(JsonValue),
+    Str(String),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self> {
+        Ok(Self {
+            tokens: tokenize(source)?,
+            pos: 0,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .context("Unexpected end of query")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(&Token::Punct(c)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        match self.next()? {
+            Token::Punct(p) if p == c => Ok(()),
+            other => anyhow::bail!("Expected '{}', found {:?}", c, other),
+        }
+    }
+
+    fn name(&mut self) -> Result<String> {
+        match self.next()? {
+            Token::Name(name) => Ok(name),
+            other => anyhow::bail!("Expected a name, found {:?}", other),
+        }
+    }
+
+    /// `{ ... }` or `query Name($var: Type = default) { ... }`
+    fn document(&mut self) -> Result<Vec<Field>> {
+        if let Some(Token::Name(keyword)) = self.peek() {
+            match keyword.as_str() {
+                "query" => {
+                    self.pos += 1;
+                    if matches!(self.peek(), Some(Token::Name(_))) {
+                        self.pos += 1;
+                    }
+                    if self.eat('(') {
+                        self.skip_variable_definitions()?;
+                    }
+                }
+                other => anyhow::bail!("Only query operations are supported, found '{}'", other),
+            }
+        }
+        let selection = self.selection_set()?;
+        if let Some(token) = self.peek() {
+            anyhow::bail!("Unexpected {:?} after the operation", token);
+        }
+        Ok(selection)
+    }
+
+    /// Variable types are not checked; values come from the request as they are
+    fn skip_variable_definitions(&mut self) -> Result<()> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next()? {
+                Token::Punct('(') => depth += 1,
+                Token::Punct(')') => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn selection_set(&mut self) -> Result<Vec<Field>> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        while !self.eat('}') {
+            fields.push(self.field()?);
+        }
+        if fields.is_empty() {
+            anyhow::bail!("Empty selection set");
+        }
+        Ok(fields)
+    }
+
+    fn field(&mut self) -> Result<Field> {
+        let mut name = self.name()?;
+        let mut alias = None;
+        if self.eat(':') {
+            alias = Some(name);
+            name = self.name()?;
+        }
+
+        let mut arguments = Vec::new();
+        if self.eat('(') {
+            while !self.eat(')') {
+                let arg = self.name()?;
+                self.expect(':')?;
+                arguments.push((arg, self.value()?));
+            }
+        }
+
+        let selection = if self.peek() == Some(&Token::Punct('{')) {
+            self.selection_set()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Field {
+            alias,
+            name,
+            arguments,
+            selection,
+        })
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        Ok(match self.next()? {
+            Token::Punct('$') => Value::Variable(self.name()?),
+            Token::Number(n) => Value::Literal(n),
+            Token::Str(s) => Value::Literal(JsonValue::String(s)),
+            Token::Name(name) => Value::Literal(match name.as_str() {
+                "true" => JsonValue::Bool(true),
+                "false" => JsonValue::Bool(false),
+                "null" => JsonValue::Null,
+                // Enum values
+                _ => JsonValue::String(name),
+            }),
+            other => anyhow::bail!("Expected a value, found {:?}", other),
+        })
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            // Commas are insignificant in GraphQL
+            c if c.is_whitespace() || c == ',' => {
+                chars.next();
+            }
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '{' | '}' | '(' | ')' | ':' | '$' | '!' | '=' | '[' | ']' => {
+                tokens.push(Token::Punct(c));
+                chars.next();
+            }
+            '"' => {
+                chars.next();
+                let mut s = String::new();
+                loop {
+                    match chars.next().context("Unterminated string")? {
+                        '"' => break,
+                        '\\' => match chars.next().context("Unterminated string")? {
+                            'n' => s.push('\n'),
+                            't' => s.push('\t'),
+                            other => s.push(other),
+                        },
+                        other => s.push(other),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut number = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || ".-+eE".contains(*c)) {
+                    number.push(c);
+                }
+                let value: JsonValue = serde_json::from_str(&number)
+                    .with_context(|| format!("Invalid number '{}'", number))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                tokens.push(Token::Name(name));
+            }
+            other => anyhow::bail!("Unexpected character '{}' in query", other),
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::IndexedEvent;
+
+    #[test]
+    fn test_parse_query() {
+        let fields = Parser::new(
+            r#"query Recent($to: String!, $limit: Int = 10) {
+                 latest: events(account: $to, limit: 2, order: asc) { total events { block name } }
+                 stats # per name
+               }"#,
+        )
+        .unwrap()
+        .document()
+        .unwrap();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].key(), "latest");
+        assert_eq!(
+            fields[0].arguments(&json!({ "to": "5Alice" })).unwrap(),
+            HashMap::from([
+                ("account".to_string(), json!("5Alice")),
+                ("limit".to_string(), json!(2)),
+                ("order".to_string(), json!("asc")),
+            ])
+        );
+        assert_eq!(fields[0].selection[1].selection.len(), 2);
+        assert!(Parser::new("mutation { x }").unwrap().document().is_err());
+    }
+
+    #[test]
+    fn test_execute() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EventStore::open(&dir.path().join("events.sqlite")).unwrap();
+        let event = IndexedEvent {
+            block: 4,
+            event_index: 1,
+            contract: "5Contract".to_string(),
+            name: Some("Transfer".to_string()),
+            data: json!({ "to": "5Alice", "value": "7" }),
+            topics: Vec::new(),
+        };
+        store.insert_block(4, "0x04", &[event]).unwrap();
+
+        let response = execute(
+            &store,
+            "{ events(name: \"Transfer\") { total nextOffset events { eventIndex data } } sum: aggregate(field: \"value\", op: sum) }",
+            &JsonValue::Null,
+        );
+        assert_eq!(
+            response,
+            json!({ "data": {
+                "events": { "total": 1, "nextOffset": null, "events": [{ "eventIndex": 1, "data": { "to": "5Alice", "value": "7" } }] },
+                "sum": 7.0
+            } })
+        );
+
+        let error = execute(&store, "{ blocks }", &JsonValue::Null);
+        assert_eq!(
+            error["errors"][0]["message"],
+            "Cannot query field 'blocks' on type 'Query'"
+        );
+    }
+}
@@ -0,0 +1,360 @@
+// SQLite storage of indexed contract events
+//
+// `glin-forge index` ingests the `ContractEmitted` events of a contract into a
+// small normalized schema:
+//
+//   blocks(number, hash)                         every ingested block
+//   events(id, block, event_index, contract,     one row per contract event,
+//          name, data)                           `data` holding the JSON payload
+//   topics(event_id, position, field, topic)     the record topics, labelled with
+//                                                the indexed field they carry
+//   checkpoints(network, contract, block, hash)  last block fully ingested
+//
+// Re-ingesting a block is a no-op, events being unique per (block, event_index).
+
+pub mod checkpoint;
+pub mod graphql;
+pub mod query;
+pub mod server;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, types::ValueRef, Connection, OpenFlags, OptionalExtension};
+use serde_json::Value as JsonValue;
+use std::path::Path;
+
+use checkpoint::Checkpoint;
+
+const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS blocks (
+    number INTEGER PRIMARY KEY,
+    hash TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    block INTEGER NOT NULL REFERENCES blocks(number),
+    event_index INTEGER NOT NULL,
+    contract TEXT NOT NULL,
+    name TEXT,
+    data TEXT NOT NULL,
+    UNIQUE (block, event_index)
+);
+CREATE TABLE IF NOT EXISTS topics (
+    event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    field TEXT,
+    topic TEXT NOT NULL,
+    PRIMARY KEY (event_id, position)
+);
+CREATE TABLE IF NOT EXISTS checkpoints (
+    network TEXT NOT NULL,
+    contract TEXT NOT NULL,
+    block INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    PRIMARY KEY (network, contract)
+);
+CREATE INDEX IF NOT EXISTS events_name ON events(name);
+CREATE INDEX IF NOT EXISTS events_contract ON events(contract, block);
+CREATE INDEX IF NOT EXISTS topics_topic ON topics(topic);
+"#;
+
+/// A contract event ready for storage
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedEvent {
+    pub block: u64,
+    /// Position of the event record in its block
+    pub event_index: u32,
+    pub contract: String,
+    /// Event name, `None` when the payload could not be decoded
+    pub name: Option<String>,
+    /// Decoded fields, or `{ "raw": "0x..." }`
+    pub data: JsonValue,
+    /// Record topics with the indexed field each one carries
+    pub topics: Vec<(Option<String>, [u8; 32])>,
+}
+
+/// Rows of an ad-hoc query
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryRows {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<JsonValue>>,
+}
+
+/// Event database
+pub struct EventStore {
+    conn: Connection,
+}
+
+impl EventStore {
+    /// Open or create a database with the indexer schema
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open database {}", path.display()))?;
+        conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL;")?;
+        conn.execute_batch(SCHEMA)
+            .context("Failed to create the indexer schema")?;
+        Ok(Self { conn })
+    }
+
+    /// Open an existing database without write access, for queries
+    pub fn open_read_only(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            anyhow::bail!(
+                "Database {} not found; run glin-forge index first",
+                path.display()
+            );
+        }
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("Failed to open database {}", path.display()))?;
+        Ok(Self { conn })
+    }
+
+    /// Store a block and its events atomically; returns how many events were new
+    pub fn insert_block(
+        &mut self,
+        number: u64,
+        hash: &str,
+        events: &[IndexedEvent],
+    ) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO blocks (number, hash) VALUES (?1, ?2)
+             ON CONFLICT (number) DO UPDATE SET hash = excluded.hash",
+            params![number as i64, hash],
+        )?;
+
+        let mut inserted = 0;
+        for event in events {
+            let changed = tx.execute(
+                "INSERT OR IGNORE INTO events (block, event_index, contract, name, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    event.block as i64,
+                    event.event_index,
+                    event.contract,
+                    event.name,
+                    event.data.to_string()
+                ],
+            )?;
+            if changed == 0 {
+                continue;
+            }
+            let id = tx.last_insert_rowid();
+            for (position, (field, topic)) in event.topics.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO topics (event_id, position, field, topic) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        id,
+                        position as i64,
+                        field,
+                        format!("0x{}", hex::encode(topic))
+                    ],
+                )?;
+            }
+            inserted += 1;
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Highest ingested block
+    pub fn last_block(&self) -> Result<Option<u64>> {
+        let number: Option<i64> =
+            self.conn
+                .query_row("SELECT MAX(number) FROM blocks", [], |row| row.get(0))?;
+        Ok(number.map(|n| n as u64))
+    }
+
+    /// Hash of an ingested block
+    pub fn block_hash(&self, number: u64) -> Result<Option<String>> {
+        let hash = self
+            .conn
+            .query_row(
+                "SELECT hash FROM blocks WHERE number = ?1",
+                params![number as i64],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(hash)
+    }
+
+    /// Latest ingested blocks, newest first
+    pub fn recent_blocks(&self, limit: usize) -> Result<Vec<(u64, String)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT number, hash FROM blocks ORDER BY number DESC LIMIT ?1")?;
+        let blocks = stmt
+            .query_map(params![limit as i64], |row| {
+                Ok((row.get::<_, i64>(0)? as u64, row.get(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(blocks)
+    }
+
+    /// Last block fully ingested for a contract
+    pub fn checkpoint(&self, network: &str, contract: &str) -> Result<Option<Checkpoint>> {
+        let checkpoint = self
+            .conn
+            .query_row(
+                "SELECT block, hash FROM checkpoints WHERE network = ?1 AND contract = ?2",
+                params![network, contract],
+                |row| {
+                    Ok(Checkpoint {
+                        block: row.get::<_, i64>(0)? as u64,
+                        hash: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(checkpoint)
+    }
+
+    pub fn save_checkpoint(
+        &self,
+        network: &str,
+        contract: &str,
+        checkpoint: &Checkpoint,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO checkpoints (network, contract, block, hash) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (network, contract) DO UPDATE SET block = excluded.block, hash = excluded.hash",
+            params![network, contract, checkpoint.block as i64, checkpoint.hash],
+        )?;
+        Ok(())
+    }
+
+    /// Drop everything above `ancestor` after a reorganization; returns the removed events
+    pub fn rollback(&mut self, ancestor: u64) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let removed = tx.execute(
+            "DELETE FROM events WHERE block > ?1",
+            params![ancestor as i64],
+        )?;
+        tx.execute(
+            "DELETE FROM blocks WHERE number > ?1",
+            params![ancestor as i64],
+        )?;
+        tx.execute(
+            "UPDATE checkpoints SET block = ?1,
+                 hash = (SELECT hash FROM blocks WHERE number = ?1)
+             WHERE block > ?1",
+            params![ancestor as i64],
+        )?;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    /// Number of stored events
+    pub fn event_count(&self) -> Result<u64> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM events", [], |row| row.get(0))?;
+        Ok(count as u64)
+    }
+
+    /// Run an SQL statement, returning its rows as JSON values
+    pub fn query(&self, sql: &str) -> Result<QueryRows> {
+        let mut stmt = self.conn.prepare(sql).context("Invalid SQL")?;
+        let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+        let mut rows = Vec::new();
+        let mut result = stmt.query([])?;
+        while let Some(row) = result.next()? {
+            let mut values = Vec::with_capacity(columns.len());
+            for i in 0..columns.len() {
+                values.push(match row.get_ref(i)? {
+                    ValueRef::Null => JsonValue::Null,
+                    ValueRef::Integer(n) => JsonValue::from(n),
+                    ValueRef::Real(f) => JsonValue::from(f),
+                    ValueRef::Text(text) => {
+                        JsonValue::String(String::from_utf8_lossy(text).to_string())
+                    }
+                    ValueRef::Blob(blob) => JsonValue::String(format!("0x{}", hex::encode(blob))),
+                });
+            }
+            rows.push(values);
+        }
+
+        Ok(QueryRows { columns, rows })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.sqlite");
+
+        let event = IndexedEvent {
+            block: 12,
+            event_index: 3,
+            contract: "5Contract".to_string(),
+            name: Some("Transfer".to_string()),
+            data: json!({ "from": null, "to": "5Grw", "value": "1000" }),
+            topics: vec![(None, [0xb5; 32]), (Some("to".to_string()), [0xd4; 32])],
+        };
+
+        let mut store = EventStore::open(&path).unwrap();
+        assert_eq!(store.last_block().unwrap(), None);
+        assert_eq!(
+            store
+                .insert_block(12, "0xabc", std::slice::from_ref(&event))
+                .unwrap(),
+            1
+        );
+        // Re-ingesting is idempotent
+        assert_eq!(
+            store
+                .insert_block(12, "0xabc", std::slice::from_ref(&event))
+                .unwrap(),
+            0
+        );
+        store.insert_block(13, "0xdef", &[]).unwrap();
+        assert_eq!(store.last_block().unwrap(), Some(13));
+        assert_eq!(store.event_count().unwrap(), 1);
+
+        // A reorganization past block 11 retracts block 12 and pulls checkpoints back
+        let checkpoint = Checkpoint {
+            block: 13,
+            hash: "0xdef".to_string(),
+        };
+        store
+            .save_checkpoint("testnet", "5Contract", &checkpoint)
+            .unwrap();
+        store.insert_block(11, "0x123", &[]).unwrap();
+        assert_eq!(store.rollback(11).unwrap(), 1);
+        assert_eq!(store.block_hash(12).unwrap(), None);
+        assert_eq!(
+            store.recent_blocks(10).unwrap(),
+            [(11, "0x123".to_string())]
+        );
+        assert_eq!(
+            store.checkpoint("testnet", "5Contract").unwrap(),
+            Some(Checkpoint {
+                block: 11,
+                hash: "0x123".to_string()
+            })
+        );
+        store.insert_block(12, "0xabc", &[event]).unwrap();
+        drop(store);
+
+        let store = EventStore::open_read_only(&path).unwrap();
+        let rows = store
+            .query(
+                "SELECT e.name, json_extract(e.data, '$.value') AS value, t.field
+                 FROM events e JOIN topics t ON t.event_id = e.id
+                 WHERE t.position = 1",
+            )
+            .unwrap();
+        assert_eq!(rows.columns, ["name", "value", "field"]);
+        assert_eq!(
+            rows.rows,
+            [vec![json!("Transfer"), json!("1000"), json!("to")]]
+        );
+        assert!(store.query("DELETE FROM events").is_err());
+    }
+}
@@ -0,0 +1,293 @@
+// Typed reads over the event database, shared by the REST and GraphQL APIs
+
+use anyhow::Result;
+use rusqlite::{params_from_iter, types::Value as SqlValue};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use super::EventStore;
+
+/// Page size when none is requested
+pub const DEFAULT_LIMIT: u32 = 50;
+
+/// Largest page served
+pub const MAX_LIMIT: u32 = 1000;
+
+/// Which events to read, newest first unless `ascending`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    pub name: Option<String>,
+    pub contract: Option<String>,
+    /// Events with a top-level field equal to this account
+    pub account: Option<String>,
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>,
+    pub ascending: bool,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+impl EventFilter {
+    fn limit(&self) -> u32 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT)
+    }
+
+    /// `WHERE` clause and its parameters
+    fn conditions(&self) -> (String, Vec<SqlValue>) {
+        let mut clauses = Vec::new();
+        let mut values = Vec::new();
+        if let Some(name) = &self.name {
+            clauses.push("e.name = ?");
+            values.push(SqlValue::Text(name.clone()));
+        }
+        if let Some(contract) = &self.contract {
+            clauses.push("e.contract = ?");
+            values.push(SqlValue::Text(contract.clone()));
+        }
+        if let Some(account) = &self.account {
+            clauses.push("EXISTS (SELECT 1 FROM json_each(e.data) WHERE json_each.value = ?)");
+            values.push(SqlValue::Text(account.clone()));
+        }
+        if let Some(from) = self.from_block {
+            clauses.push("e.block >= ?");
+            values.push(SqlValue::Integer(from as i64));
+        }
+        if let Some(to) = self.to_block {
+            clauses.push("e.block <= ?");
+            values.push(SqlValue::Integer(to as i64));
+        }
+
+        let clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", clauses.join(" AND "))
+        };
+        (clause, values)
+    }
+}
+
+/// A stored event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StoredEvent {
+    pub id: i64,
+    pub block: u64,
+    pub event_index: u32,
+    pub contract: String,
+    pub name: Option<String>,
+    pub data: JsonValue,
+}
+
+/// One page of events with the number of matches
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventPage {
+    pub events: Vec<StoredEvent>,
+    pub total: u64,
+    pub limit: u32,
+    pub offset: u32,
+    /// Offset of the following page, `None` on the last one
+    pub next_offset: Option<u32>,
+}
+
+/// Event counts per name
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventStats {
+    pub name: Option<String>,
+    pub count: u64,
+    pub first_block: u64,
+    pub last_block: u64,
+}
+
+/// Aggregate functions over a numeric event field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+impl std::str::FromStr for Aggregate {
+    type Err = anyhow::Error;
+
+    fn from_str(op: &str) -> Result<Self> {
+        Ok(match op {
+            "count" => Aggregate::Count,
+            "sum" => Aggregate::Sum,
+            "min" => Aggregate::Min,
+            "max" => Aggregate::Max,
+            "avg" => Aggregate::Avg,
+            other => anyhow::bail!("Unknown aggregate '{}' (count, sum, min, max, avg)", other),
+        })
+    }
+}
+
+impl EventStore {
+    /// Events matching a filter
+    pub fn events(&self, filter: &EventFilter) -> Result<EventPage> {
+        let (clause, values) = filter.conditions();
+        let total: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM events e{}", clause),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )?;
+
+        let order = if filter.ascending { "ASC" } else { "DESC" };
+        let (limit, offset) = (filter.limit(), filter.offset.unwrap_or(0));
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT e.id, e.block, e.event_index, e.contract, e.name, e.data FROM events e{}
+             ORDER BY e.block {order}, e.event_index {order} LIMIT {limit} OFFSET {offset}",
+            clause,
+        ))?;
+        let events = stmt
+            .query_map(params_from_iter(values.iter()), |row| {
+                let data: String = row.get(5)?;
+                Ok(StoredEvent {
+                    id: row.get(0)?,
+                    block: row.get::<_, i64>(1)? as u64,
+                    event_index: row.get(2)?,
+                    contract: row.get(3)?,
+                    name: row.get(4)?,
+                    data: serde_json::from_str(&data).unwrap_or(JsonValue::String(data)),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let end = offset as u64 + events.len() as u64;
+        Ok(EventPage {
+            events,
+            total: total as u64,
+            limit,
+            offset,
+            next_offset: (end < total as u64).then_some(end as u32),
+        })
+    }
+
+    /// Counts and block range of every event name
+    pub fn stats(&self) -> Result<Vec<EventStats>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, COUNT(*), MIN(block), MAX(block) FROM events
+             GROUP BY name ORDER BY COUNT(*) DESC, name",
+        )?;
+        let stats = stmt
+            .query_map([], |row| {
+                Ok(EventStats {
+                    name: row.get(0)?,
+                    count: row.get::<_, i64>(1)? as u64,
+                    first_block: row.get::<_, i64>(2)? as u64,
+                    last_block: row.get::<_, i64>(3)? as u64,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(stats)
+    }
+
+    /// Aggregate a top-level field of the events matching a filter
+    ///
+    /// Values are summed as floating point numbers, so sums of 128-bit
+    /// balances are approximate past 2^53.
+    pub fn aggregate(&self, filter: &EventFilter, field: &str, op: Aggregate) -> Result<JsonValue> {
+        if !field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            anyhow::bail!("Invalid field name '{}'", field);
+        }
+        let value = format!("CAST(json_extract(e.data, '$.{}') AS REAL)", field);
+        let expression = match op {
+            Aggregate::Count => format!("COUNT(json_extract(e.data, '$.{}'))", field),
+            Aggregate::Sum => format!("TOTAL({})", value),
+            Aggregate::Min => format!("MIN({})", value),
+            Aggregate::Max => format!("MAX({})", value),
+            Aggregate::Avg => format!("AVG({})", value),
+        };
+
+        let (clause, values) = filter.conditions();
+        let result: Option<f64> = self.conn.query_row(
+            &format!("SELECT {} FROM events e{}", expression, clause),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )?;
+        Ok(match result {
+            Some(n) if op == Aggregate::Count => JsonValue::from(n as u64),
+            Some(n) => JsonValue::from(n),
+            None => JsonValue::Null,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::IndexedEvent;
+    use serde_json::json;
+
+    fn transfer(block: u64, to: &str, value: u64) -> IndexedEvent {
+        IndexedEvent {
+            block,
+            event_index: 0,
+            contract: "5Contract".to_string(),
+            name: Some("Transfer".to_string()),
+            data: json!({ "from": null, "to": to, "value": value.to_string() }),
+            topics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_event_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EventStore::open(&dir.path().join("events.sqlite")).unwrap();
+        store
+            .insert_block(1, "0x01", &[transfer(1, "5Alice", 10)])
+            .unwrap();
+        store
+            .insert_block(2, "0x02", &[transfer(2, "5Bob", 20)])
+            .unwrap();
+        let mut approval = transfer(3, "5Alice", 5);
+        approval.name = Some("Approval".to_string());
+        store.insert_block(3, "0x03", &[approval]).unwrap();
+
+        let page = store
+            .events(&EventFilter {
+                account: Some("5Alice".to_string()),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.events[0].block, 3);
+        assert_eq!(page.next_offset, Some(1));
+
+        let transfers = EventFilter {
+            name: Some("Transfer".to_string()),
+            ascending: true,
+            ..Default::default()
+        };
+        let page = store.events(&transfers).unwrap();
+        assert_eq!(
+            page.events.iter().map(|e| e.block).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert_eq!(page.next_offset, None);
+
+        assert_eq!(
+            store
+                .aggregate(&transfers, "value", Aggregate::Sum)
+                .unwrap(),
+            json!(30.0)
+        );
+        assert_eq!(
+            store
+                .aggregate(&transfers, "value", Aggregate::Count)
+                .unwrap(),
+            json!(2)
+        );
+        assert!(store
+            .aggregate(&transfers, "value') --", Aggregate::Sum)
+            .is_err());
+
+        let stats = store.stats().unwrap();
+        assert_eq!(stats[0].name.as_deref(), Some("Transfer"));
+        assert_eq!(
+            (stats[0].count, stats[0].first_block, stats[0].last_block),
+            (2, 1, 2)
+        );
+    }
+}
@@ -0,0 +1,256 @@
+// HTTP API over the event database
+//
+//   GET  /events                     ?name&contract&account&from_block&to_block&order&limit&offset
+//   GET  /events/{name}              same filters, one event name
+//   GET  /accounts/{account}/events  same filters, events carrying the account
+//   GET  /stats                      counts per event name
+//   GET  /aggregate                  ?field&op plus the event filters
+//   POST /graphql                    { "query", "variables" }
+//   GET  /health                     last indexed block and event count
+
+use anyhow::{Context, Result};
+use jsonrpc_http_server::hyper::{
+    self,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, StatusCode,
+};
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use super::query::{Aggregate, EventFilter};
+use super::{graphql, EventStore};
+
+/// Serve the API until the process is stopped
+pub async fn serve(store: EventStore, addr: SocketAddr) -> Result<()> {
+    let store = Arc::new(Mutex::new(store));
+    let make_service = make_service_fn(move |_| {
+        let store = store.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let store = store.clone();
+                async move { Ok::<_, Infallible>(handle(&store, request).await) }
+            }))
+        }
+    });
+
+    hyper::Server::try_bind(&addr)
+        .with_context(|| format!("Failed to bind {}", addr))?
+        .serve(make_service)
+        .await
+        .context("Server error")
+}
+
+async fn handle(store: &Mutex<EventStore>, request: Request<Body>) -> Response<Body> {
+    let method = request.method().clone();
+    let uri = request.uri().to_string();
+    let (status, body) = match hyper::body::to_bytes(request.into_body()).await {
+        Ok(body) => {
+            let store = store.lock().unwrap_or_else(|e| e.into_inner());
+            route(&store, &method, &uri, &body)
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, error(e)),
+    };
+
+    let mut response = Response::builder()
+        .status(status)
+        .header("access-control-allow-origin", "*")
+        .header("access-control-allow-methods", "GET, POST, OPTIONS")
+        .header("access-control-allow-headers", "content-type");
+    if body.is_null() {
+        return response.body(Body::empty()).expect("valid response");
+    }
+    response = response.header("content-type", "application/json");
+    response
+        .body(Body::from(body.to_string()))
+        .expect("valid response")
+}
+
+/// Status and JSON body of a request; a null body means an empty response
+fn route(store: &EventStore, method: &Method, uri: &str, body: &[u8]) -> (StatusCode, JsonValue) {
+    let url = match reqwest::Url::parse(&format!("http://localhost{}", uri)) {
+        Ok(url) => url,
+        Err(e) => return (StatusCode::BAD_REQUEST, error(e)),
+    };
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let segments: Vec<&str> = url.path().split('/').filter(|s| !s.is_empty()).collect();
+
+    let result = match (method, segments.as_slice()) {
+        (&Method::OPTIONS, _) => return (StatusCode::NO_CONTENT, JsonValue::Null),
+        (&Method::GET, ["health"]) => health(store),
+        (&Method::GET, ["events"]) => {
+            filter(&params).and_then(|f| Ok(serde_json::to_value(store.events(&f)?)?))
+        }
+        (&Method::GET, ["events", name]) => filter(&params).and_then(|mut f| {
+            f.name = Some(decode(name));
+            Ok(serde_json::to_value(store.events(&f)?)?)
+        }),
+        (&Method::GET, ["accounts", account, "events"]) => filter(&params).and_then(|mut f| {
+            f.account = Some(decode(account));
+            Ok(serde_json::to_value(store.events(&f)?)?)
+        }),
+        (&Method::GET, ["stats"]) => store.stats().map(|stats| json!({ "events": stats })),
+        (&Method::GET, ["aggregate"]) => aggregate(store, &params),
+        (&Method::POST, ["graphql"]) => {
+            return match serde_json::from_slice::<JsonValue>(body) {
+                Ok(request) => match request.get("query").and_then(|q| q.as_str()) {
+                    Some(query) => {
+                        let variables = request.get("variables").cloned().unwrap_or_default();
+                        (StatusCode::OK, graphql::execute(store, query, &variables))
+                    }
+                    None => (
+                        StatusCode::BAD_REQUEST,
+                        json!({ "errors": [{ "message": "Missing query" }] }),
+                    ),
+                },
+                Err(e) => (
+                    StatusCode::BAD_REQUEST,
+                    json!({ "errors": [{ "message": format!("Invalid JSON: {}", e) }] }),
+                ),
+            };
+        }
+        _ => {
+            return (
+                StatusCode::NOT_FOUND,
+                json!({ "error": format!("No route for {} {}", method, url.path()) }),
+            )
+        }
+    };
+
+    match result {
+        Ok(body) => (StatusCode::OK, body),
+        Err(e) => (StatusCode::BAD_REQUEST, error(e)),
+    }
+}
+
+fn health(store: &EventStore) -> Result<JsonValue> {
+    Ok(json!({
+        "status": "ok",
+        "last_block": store.last_block()?,
+        "events": store.event_count()?,
+    }))
+}
+
+fn aggregate(store: &EventStore, params: &HashMap<String, String>) -> Result<JsonValue> {
+    let field = params.get("field").context("Missing field parameter")?;
+    let op: Aggregate = params
+        .get("op")
+        .context("Missing op parameter (count, sum, min, max, avg)")?
+        .parse()?;
+    let value = store.aggregate(&filter(params)?, field, op)?;
+    Ok(json!({ "field": field, "op": params["op"], "value": value }))
+}
+
+/// Event filter from query parameters
+fn filter(params: &HashMap<String, String>) -> Result<EventFilter> {
+    fn number<T: std::str::FromStr>(
+        params: &HashMap<String, String>,
+        key: &str,
+    ) -> Result<Option<T>> {
+        params
+            .get(key)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid {} '{}'", key, v))
+            })
+            .transpose()
+    }
+
+    let ascending = match params.get("order").map(String::as_str) {
+        None | Some("desc") => false,
+        Some("asc") => true,
+        Some(other) => anyhow::bail!("Invalid order '{}' (asc, desc)", other),
+    };
+    Ok(EventFilter {
+        name: params.get("name").cloned(),
+        contract: params.get("contract").cloned(),
+        account: params.get("account").cloned(),
+        from_block: number(params, "from_block")?,
+        to_block: number(params, "to_block")?,
+        ascending,
+        limit: number(params, "limit")?,
+        offset: number(params, "offset")?,
+    })
+}
+
+/// Percent-decoded path segment
+fn decode(segment: &str) -> String {
+    reqwest::Url::parse(&format!("http://localhost/?v={}", segment))
+        .ok()
+        .and_then(|url| url.query_pairs().next().map(|(_, v)| v.into_owned()))
+        .unwrap_or_else(|| segment.to_string())
+}
+
+fn error(e: impl std::fmt::Display) -> JsonValue {
+    json!({ "error": e.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::IndexedEvent;
+
+    #[test]
+    fn test_routes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EventStore::open(&dir.path().join("events.sqlite")).unwrap();
+        for (block, name) in [(1, "Transfer"), (2, "Approval"), (3, "Transfer")] {
+            let event = IndexedEvent {
+                block,
+                event_index: 0,
+                contract: "5Contract".to_string(),
+                name: Some(name.to_string()),
+                data: json!({ "owner": "5Alice", "value": block.to_string() }),
+                topics: Vec::new(),
+            };
+            store.insert_block(block, "0x00", &[event]).unwrap();
+        }
+
+        let (status, body) = route(
+            &store,
+            &Method::GET,
+            "/events/Transfer?order=asc&limit=1",
+            b"",
+        );
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            (body["total"].clone(), body["next_offset"].clone()),
+            (json!(2), json!(1))
+        );
+        assert_eq!(body["events"][0]["block"], 1);
+
+        let (_, body) = route(
+            &store,
+            &Method::GET,
+            "/accounts/5Alice/events?from_block=2",
+            b"",
+        );
+        assert_eq!(body["total"], 2);
+
+        let (_, body) = route(
+            &store,
+            &Method::GET,
+            "/aggregate?name=Transfer&field=value&op=max",
+            b"",
+        );
+        assert_eq!(body["value"], 3.0);
+
+        let (status, body) = route(&store, &Method::GET, "/events?limit=many", b"");
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Invalid limit 'many'");
+
+        let (status, body) = route(
+            &store,
+            &Method::POST,
+            "/graphql",
+            br#"{"query":"query($n: String) { events(name: $n) { total } }","variables":{"n":"Approval"}}"#,
+        );
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "data": { "events": { "total": 1 } } }));
+
+        let (status, _) = route(&store, &Method::GET, "/blocks", b"");
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
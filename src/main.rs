@@ -11,11 +11,14 @@
 use clap::{Parser, Subcommand};
 use colored::Colorize;
 
+mod analyzer;
 mod cli;
 mod codegen;
 mod config;
 mod contract;
+mod indexer;
 mod rpc;
+mod testing;
 
 #[derive(Parser)]
 #[command(name = "glin-forge")]
@@ -61,6 +64,12 @@ enum Commands {
     /// Watch contract events
     Watch(cli::watch::WatchArgs),
 
+    /// Index contract events into SQLite and query them
+    Index(cli::index::IndexArgs),
+
+    /// Evaluate alert rules over contract events and trigger their actions
+    Monitor(cli::monitor::MonitorArgs),
+
     /// Verify contract on explorer
     Verify(cli::verify::VerifyArgs),
 
@@ -79,14 +88,40 @@ enum Commands {
     /// Run a TypeScript deployment script
     Run(cli::run::RunArgs),
 
+    /// Run the deployment scripts of scripts/deploy not yet applied to a network
+    Migrate(cli::migrate::MigrateArgs),
+
+    /// Run the RPC server in the background for scripts, the console and frontends to share
+    Daemon(cli::daemon::DaemonArgs),
+
+    /// Benchmark contract message weights
+    Bench(cli::bench::BenchArgs),
+
     /// Analyze contract code for security and optimization
-    Analyze(cli::analyze::AnalyzeArgs),
+    Analyze(Box<cli::analyze::AnalyzeArgs>),
 
     /// Start an interactive console (REPL)
     Console(cli::console::ConsoleArgs),
 
+    /// Open a terminal dashboard of the network, its contracts, events and balances
+    Ui(cli::ui::UiArgs),
+
     /// Clean build artifacts
     Clean(cli::clean::CleanArgs),
+
+    /// Check the toolchain, Node.js and network connectivity
+    Doctor(cli::doctor::DoctorArgs),
+
+    /// Generate shell completions (bash, zsh or fish)
+    Completions(cli::completions::CompletionsArgs),
+
+    /// Print the completions of a command line, for the shell completion scripts
+    #[command(name = "__complete", hide = true)]
+    Complete(cli::completions::CompleteArgs),
+
+    /// Run a script on the embedded JavaScript engine
+    #[command(hide = true)]
+    EmbeddedRun(cli::run::EmbeddedArgs),
 }
 
 #[tokio::main]
@@ -105,18 +140,25 @@ async fn main() -> anyhow::Result<()> {
         Commands::Instantiate(args) => cli::instantiate::execute(args).await,
         Commands::Typegen(args) => cli::typegen::execute(args).await,
         Commands::Watch(args) => cli::watch::execute(args).await,
+        Commands::Index(args) => cli::index::execute(args).await,
+        Commands::Monitor(args) => cli::monitor::execute(args).await,
         Commands::Verify(args) => cli::verify::execute(args).await,
         Commands::Config(args) => cli::config::execute(args).await,
         Commands::Account(args) => cli::account::execute(args).await,
         Commands::Balance(args) => cli::balance::execute(args).await,
         Commands::Network(args) => cli::network::execute(args).await,
         Commands::Run(args) => cli::run::execute(args).await,
-        Commands::Analyze(args) => {
-            cli::analyze::run(args)?;
-            Ok(())
-        }
+        Commands::Migrate(args) => cli::migrate::execute(args).await,
+        Commands::Daemon(args) => cli::daemon::execute(args).await,
+        Commands::Bench(args) => cli::bench::execute(args).await,
+        Commands::Analyze(args) => cli::analyze::execute(*args).await,
         Commands::Console(args) => cli::console::execute(args).await,
+        Commands::Ui(args) => cli::ui::execute(args).await,
         Commands::Clean(args) => cli::clean::execute(args).await,
+        Commands::Doctor(args) => cli::doctor::execute(args).await,
+        Commands::Completions(args) => cli::completions::execute(args).await,
+        Commands::Complete(args) => cli::completions::complete(args).await,
+        Commands::EmbeddedRun(args) => cli::run::execute_embedded(args).await,
     };
 
     if let Err(e) = result {
@@ -0,0 +1,116 @@
+// Accounts RPC requests sign with
+//
+// A request's `account` names a dev account (alice, bob, ...), a keystore
+// account unlocked in this session with `unlockAccount`, or an account whose
+// seed is in `GLIN_FORGE_SEED_<NAME>`. Unlocked keys stay in memory until
+// `lockAccount` or the end of the session.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use subxt_signer::sr25519::Keypair;
+
+use crate::config::keystore;
+use crate::rpc::errors::{tagged, ErrorKind};
+
+fn unlocked() -> &'static Mutex<HashMap<String, Keypair>> {
+    static UNLOCKED: OnceLock<Mutex<HashMap<String, Keypair>>> = OnceLock::new();
+    UNLOCKED.get_or_init(Default::default)
+}
+
+/// Keypair of an account
+pub fn signer(account: &str) -> Result<Keypair> {
+    signer_with(account, keystore::from_env, keystore::keystore_dir)
+}
+
+/// Keypair of an account, with the seed variables and keystore directory given
+fn signer_with(
+    account: &str,
+    from_env: impl FnOnce(&str) -> Result<Option<Keypair>>,
+    keystore_dir: impl FnOnce() -> Result<PathBuf>,
+) -> Result<Keypair> {
+    if let Ok(pair) = glin_client::get_dev_account(account) {
+        return Ok(pair);
+    }
+    if let Some(pair) = unlocked().lock().unwrap().get(account) {
+        return Ok(pair.clone());
+    }
+    if let Some(pair) = from_env(account)? {
+        return Ok(pair);
+    }
+    let dir = keystore_dir()?;
+    if keystore::contains(&dir, account) {
+        return Err(tagged(
+            ErrorKind::Account,
+            format!(
+                "Account '{}' is locked; unlock it with the unlockAccount method first",
+                account
+            ),
+        ));
+    }
+    Err(tagged(
+        ErrorKind::Account,
+        format!(
+            "Unknown account '{}': not a dev account, in {}, or set in {}",
+            account,
+            dir.display(),
+            keystore::seed_env(account)
+        ),
+    ))
+}
+
+/// Decrypt a keystore account for the rest of the session; returns its address
+pub fn unlock(account: &str, password: &str) -> Result<String> {
+    let pair = keystore::unlock(&keystore::keystore_dir()?, account, password)?;
+    let address = pair.public_key().to_account_id().to_string();
+    unlocked().lock().unwrap().insert(account.to_string(), pair);
+    Ok(address)
+}
+
+/// Forget an unlocked account; false when it was not unlocked
+pub fn lock(account: &str) -> bool {
+    unlocked().lock().unwrap().remove(account).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signer_resolution() {
+        let keystore = tempfile::tempdir().unwrap();
+        let signer = |account: &str| {
+            signer_with(
+                account,
+                |account| {
+                    Ok((account == "accounts_test_env")
+                        .then(|| glin_client::get_dev_account("alice").unwrap()))
+                },
+                || Ok(keystore.path().to_path_buf()),
+            )
+        };
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let address =
+            |account: &str| signer(account).map(|p| p.public_key().to_account_id().to_string());
+
+        assert_eq!(address("alice").unwrap(), alice);
+        let error = signer("accounts_test_unknown").unwrap_err().to_string();
+        assert!(
+            error.contains(&keystore.path().display().to_string()),
+            "{}",
+            error
+        );
+
+        assert_eq!(address("accounts_test_env").unwrap(), alice);
+
+        unlocked().lock().unwrap().insert(
+            "accounts_test_unlocked".to_string(),
+            glin_client::get_dev_account("bob").unwrap(),
+        );
+        assert_ne!(address("accounts_test_unlocked").unwrap(), alice);
+        assert!(lock("accounts_test_unlocked"));
+        assert!(!lock("accounts_test_unlocked"));
+        assert!(signer("accounts_test_unlocked").is_err());
+    }
+}
@@ -0,0 +1,172 @@
+// Access control of the RPC server
+//
+// The server signs transactions on behalf of its caller, so only the script it
+// was started for may use it. A bearer token generated for each session is
+// handed to the script via `GLIN_FORGE_RPC_TOKEN` and required on every
+// request, as an `Authorization: Bearer` header or, for WebSocket clients that
+// cannot set headers, a `token` query parameter. Browsers send an `Origin`
+// header, which must then be in the allowed origins; requests without one come
+// from local processes and need the token only.
+
+use jsonrpc_http_server::hyper::header::{AUTHORIZATION, ORIGIN, WWW_AUTHENTICATE};
+use jsonrpc_http_server::hyper::{Body, Method, Request, Response, StatusCode};
+use rand::Rng;
+
+/// Environment variable the session token is passed in
+pub const TOKEN_ENV: &str = "GLIN_FORGE_RPC_TOKEN";
+
+/// Token and allowed origins of a server session
+#[derive(Debug, Clone)]
+pub struct Access {
+    token: String,
+    origins: Vec<String>,
+}
+
+impl Access {
+    /// Generate a session token, allowing browser requests from `origins`
+    pub fn new(origins: &[String]) -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill(&mut bytes);
+        Self::with_token(hex::encode(bytes), origins)
+    }
+
+    /// Use a token chosen by the caller, e.g. shared with a remote client
+    pub fn with_token(token: String, origins: &[String]) -> Self {
+        Self {
+            token,
+            origins: origins
+                .iter()
+                .map(|origin| origin.trim_end_matches('/').to_string())
+                .collect(),
+        }
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Response rejecting a request, or None when it may proceed
+    pub fn check(&self, request: &Request<Body>) -> Option<Response<Body>> {
+        if let Some(origin) = request.headers().get(ORIGIN) {
+            let allowed = origin
+                .to_str()
+                .map(|origin| self.origins.iter().any(|o| o == origin))
+                .unwrap_or(false);
+            if !allowed {
+                return Some(reject(StatusCode::FORBIDDEN, "Origin not allowed"));
+            }
+            // CORS preflights carry no credentials; the server answers them
+            if request.method() == Method::OPTIONS {
+                return None;
+            }
+        }
+
+        let token = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or_else(|| {
+                request
+                    .uri()
+                    .query()?
+                    .split('&')
+                    .find_map(|pair| pair.strip_prefix("token="))
+            });
+        match token {
+            Some(token) if constant_time_eq(token.as_bytes(), self.token.as_bytes()) => None,
+            _ => {
+                let mut response = reject(StatusCode::UNAUTHORIZED, "Missing or invalid token");
+                response
+                    .headers_mut()
+                    .insert(WWW_AUTHENTICATE, "Bearer".parse().expect("valid header"));
+                Some(response)
+            }
+        }
+    }
+}
+
+fn reject(status: StatusCode, message: &str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::from(message.to_string()))
+        .expect("valid response")
+}
+
+/// Compare without revealing how long a prefix matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_check() {
+        let access = Access::with_token(
+            "secret".to_string(),
+            &["http://localhost:5173/".to_string()],
+        );
+        let request = |method: Method, headers: &[(&str, &str)]| {
+            let mut builder = Request::builder().method(method).uri("/");
+            for (name, value) in headers {
+                builder = builder.header(*name, *value);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+        let status = |request: Request<Body>| access.check(&request).map(|r| r.status());
+        let get = |uri: &str| Request::get(uri).body(Body::empty()).unwrap();
+
+        assert_eq!(
+            status(request(Method::POST, &[("authorization", "Bearer secret")])),
+            None
+        );
+        assert_eq!(
+            status(request(Method::POST, &[])),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(
+            status(request(Method::POST, &[("authorization", "Bearer secreT")])),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(
+            status(request(Method::POST, &[("authorization", "secret")])),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(status(get("/ws?token=secret")), None);
+        assert_eq!(status(get("/ws?a=1&token=secret")), None);
+        assert_eq!(
+            status(get("/ws?token=secre")),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(status(get("/ws")), Some(StatusCode::UNAUTHORIZED));
+
+        let allowed = ("origin", "http://localhost:5173");
+        assert_eq!(
+            status(request(
+                Method::POST,
+                &[allowed, ("authorization", "Bearer secret")]
+            )),
+            None
+        );
+        assert_eq!(
+            status(request(Method::POST, &[allowed])),
+            Some(StatusCode::UNAUTHORIZED)
+        );
+        assert_eq!(status(request(Method::OPTIONS, &[allowed])), None);
+        assert_eq!(
+            status(request(
+                Method::POST,
+                &[
+                    ("origin", "http://evil.example"),
+                    ("authorization", "Bearer secret")
+                ]
+            )),
+            Some(StatusCode::FORBIDDEN)
+        );
+
+        assert_eq!(Access::new(&[]).token().len(), 64);
+        assert_ne!(Access::new(&[]).token(), Access::new(&[]).token());
+    }
+}
@@ -0,0 +1,212 @@
+// JSON-RPC batches
+//
+// jsonrpc-core answers a batch by polling its calls together on one task.
+// Batches are split here instead and each call spawned, so slow calls (dry
+// runs, transactions) run in parallel on the runtime. Responses keep the order
+// of the calls, and notifications get none, as the specification requires.
+// Every call runs under the server's timeout and can be cancelled (see
+// `requests`), reports its progress when made over a WebSocket (see
+// `progress`), and is recorded in the server's metrics.
+
+use jsonrpc_core::IoHandler;
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+use crate::rpc::metrics::{Metrics, UNKNOWN_METHOD};
+use crate::rpc::progress;
+use crate::rpc::requests::{take_timeout, Requests};
+
+/// Calls accepted in one batch
+pub const MAX_BATCH: usize = 1000;
+
+/// Answer a request or batch; None when nothing is to be answered. Progress of the calls
+/// goes to `notifications` when given
+pub async fn handle(
+    io: &Arc<IoHandler>,
+    metrics: &Arc<Metrics>,
+    requests: &Arc<Requests>,
+    notifications: Option<&mpsc::Sender<String>>,
+    request: &str,
+) -> Option<String> {
+    let calls = match serde_json::from_str::<JsonValue>(request) {
+        Ok(JsonValue::Array(calls)) if !calls.is_empty() => calls,
+        Ok(call @ JsonValue::Object(_)) => {
+            return handle_call(io, metrics, requests, notifications, call).await
+        }
+        // Single calls, and malformed or empty batches, which jsonrpc-core rejects
+        _ => return io.handle_request(request).await,
+    };
+    if calls.len() > MAX_BATCH {
+        let message = format!("Batch of {} calls exceeds {}", calls.len(), MAX_BATCH);
+        return Some(invalid_request(&message));
+    }
+
+    let tasks: Vec<_> = calls
+        .into_iter()
+        .map(|call| {
+            let (io, metrics, requests) = (io.clone(), metrics.clone(), requests.clone());
+            let notifications = notifications.cloned();
+            tokio::spawn(async move {
+                if !call.is_object() {
+                    return Some(invalid_request("Batch entries must be request objects"));
+                }
+                handle_call(&io, &metrics, &requests, notifications.as_ref(), call).await
+            })
+        })
+        .collect();
+    let mut responses = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        // A panicking call loses its response rather than the whole batch
+        if let Ok(Some(response)) = task.await {
+            responses.push(response);
+        }
+    }
+    if responses.is_empty() {
+        None
+    } else {
+        Some(format!("[{}]", responses.join(",")))
+    }
+}
+
+/// Answer one call within its timeout, recording its method, duration and outcome
+async fn handle_call(
+    io: &IoHandler,
+    metrics: &Metrics,
+    requests: &Requests,
+    notifications: Option<&mpsc::Sender<String>>,
+    mut call: JsonValue,
+) -> Option<String> {
+    let method = call["method"]
+        .as_str()
+        .filter(|method| io.iter().any(|(name, _)| name == method))
+        .unwrap_or(UNKNOWN_METHOD)
+        .to_string();
+    let timeout = take_timeout(&mut call);
+    let request = call.to_string();
+    let started = Instant::now();
+    let id = call.get("id");
+    let response = requests
+        .run(
+            id,
+            timeout,
+            progress::scope(notifications, id, io.handle_request(&request)),
+        )
+        .await;
+    metrics.record(
+        &method,
+        started.elapsed(),
+        response.as_deref().and_then(error_code),
+    );
+    response
+}
+
+/// Code of the error a response carries
+fn error_code(response: &str) -> Option<i64> {
+    if !response.contains("\"error\"") {
+        return None;
+    }
+    let response: JsonValue = serde_json::from_str(response).ok()?;
+    response["error"]["code"].as_i64()
+}
+
+fn invalid_request(message: &str) -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {
+            "code": jsonrpc_core::ErrorCode::InvalidRequest.code(),
+            "message": message,
+        },
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_core::{Params, Value};
+    use std::time::{Duration, Instant};
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_batch() {
+        let mut io = IoHandler::new();
+        io.add_sync_method("echo", |params: Params| params.parse::<Value>());
+        // Blocks its thread, so only parallel calls finish in time
+        io.add_sync_method("slow", |_| {
+            std::thread::sleep(Duration::from_millis(300));
+            Ok(Value::from("done"))
+        });
+        let io = Arc::new(io);
+        let metrics = Arc::new(Metrics::default());
+        let requests = Arc::new(Requests::default());
+
+        let started = Instant::now();
+        let batch = r#"[
+            {"jsonrpc":"2.0","id":1,"method":"slow","params":[]},
+            {"jsonrpc":"2.0","id":2,"method":"slow","params":[]},
+            {"jsonrpc":"2.0","id":3,"method":"slow","params":[]},
+            {"jsonrpc":"2.0","method":"echo","params":["notification"]},
+            {"jsonrpc":"2.0","id":4,"method":"echo","params":[4]},
+            {"jsonrpc":"2.0","id":5,"method":"missing","params":[]},
+            42
+        ]"#;
+        let response = handle(&io, &metrics, &requests, None, batch).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(800));
+
+        let responses: Vec<JsonValue> = serde_json::from_str(&response).unwrap();
+        assert_eq!(responses.len(), 6);
+        let ids: Vec<_> = responses.iter().map(|r| r["id"].clone()).collect();
+        assert_eq!(
+            ids,
+            serde_json::json!([1, 2, 3, 4, 5, null]).as_array().unwrap()[..]
+        );
+        assert_eq!(responses[0]["result"], "done");
+        assert_eq!(responses[3]["result"], serde_json::json!([4]));
+        assert_eq!(responses[4]["error"]["code"], -32601);
+        assert_eq!(responses[5]["error"]["code"], -32600);
+
+        // Single calls and notification-only batches
+        let single = handle(
+            &io,
+            &metrics,
+            &requests,
+            None,
+            r#"{"jsonrpc":"2.0","id":9,"method":"echo","params":[]}"#,
+        )
+        .await
+        .unwrap();
+        assert!(single.starts_with('{'));
+        assert_eq!(
+            handle(
+                &io,
+                &metrics,
+                &requests,
+                None,
+                r#"[{"jsonrpc":"2.0","method":"echo","params":[]}]"#
+            )
+            .await,
+            None
+        );
+
+        let oversized = format!(
+            "[{}]",
+            vec![r#"{"jsonrpc":"2.0","id":1,"method":"echo","params":[]}"#; MAX_BATCH + 1]
+                .join(",")
+        );
+        let response: JsonValue = serde_json::from_str(
+            &handle(&io, &metrics, &requests, None, &oversized)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], -32600);
+
+        let text = metrics.render();
+        assert!(text.contains("glin_forge_rpc_requests_total{method=\"slow\",status=\"ok\"} 3"));
+        assert!(
+            text.contains("glin_forge_rpc_requests_total{method=\"unknown\",status=\"error\"} 1")
+        );
+    }
+}
@@ -0,0 +1,62 @@
+// Blocking client of the RPC server
+//
+// Scripts glin-forge runs itself, on its embedded JavaScript engine or as
+// Rhai, reach the RPC server of `run` like SDK scripts do: over HTTP, with the
+// session's bearer token, from GLIN_FORGE_RPC_URL and GLIN_FORGE_RPC_TOKEN.
+
+use anyhow::Context;
+
+/// Call a method of the RPC server, blocking until it answers
+pub fn call(method: &str, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    let Ok(url) = std::env::var("GLIN_FORGE_RPC_URL") else {
+        if std::env::var_os("GLIN_FORGE_RPC_SOCKET").is_some() {
+            anyhow::bail!(
+                "Embedded scripts reach the RPC server over TCP; run without --rpc-socket"
+            );
+        }
+        anyhow::bail!("No RPC server; run the script with glin-forge run");
+    };
+    let token = std::env::var(super::auth::TOKEN_ENV).unwrap_or_default();
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": [params],
+    });
+
+    // A runtime of its own, as scripts run inside the command's runtime
+    let response = std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?;
+                runtime.block_on(async {
+                    let response = reqwest::Client::new()
+                        .post(&url)
+                        .bearer_auth(&token)
+                        .json(&body)
+                        .send()
+                        .await
+                        .with_context(|| {
+                            format!("RPC call failed. Is the RPC server running on {}?", url)
+                        })?;
+                    anyhow::Ok(response.json::<serde_json::Value>().await?)
+                })
+            })
+            .join()
+            .map_err(|_| anyhow::anyhow!("RPC call panicked"))?
+    })?;
+
+    if let Some(error) = response.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|message| message.as_str())
+            .unwrap_or("RPC call failed");
+        match error.pointer("/data/kind").and_then(|kind| kind.as_str()) {
+            Some(kind) => anyhow::bail!("{} ({})", message, kind),
+            None => anyhow::bail!("{}", message),
+        }
+    }
+    Ok(response.get("result").cloned().unwrap_or_default())
+}
@@ -0,0 +1,41 @@
+// Contracts instantiated through the server
+//
+// `deploy`, `instantiate` and `submitSigned` note every contract they
+// instantiate, so that `glin-forge run` can journal what its script deployed.
+// Only a server in the same process is seen, so journaled runs never use a
+// daemon.
+
+use std::sync::{Mutex, OnceLock};
+
+/// A contract the server instantiated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployed {
+    /// None for transactions signed outside the server
+    pub network: Option<String>,
+    pub address: String,
+    pub code_hash: Option<String>,
+    pub tx_hash: Option<String>,
+}
+
+fn deployed() -> &'static Mutex<Vec<Deployed>> {
+    static DEPLOYED: OnceLock<Mutex<Vec<Deployed>>> = OnceLock::new();
+    DEPLOYED.get_or_init(Default::default)
+}
+
+/// Note the contract of a successful deploy or instantiation
+pub fn note(network: Option<&str>, result: &crate::contract::DeployResult) {
+    let Some(address) = result.contract_address.clone().filter(|_| result.success) else {
+        return;
+    };
+    deployed().lock().unwrap().push(Deployed {
+        network: network.map(str::to_string),
+        address,
+        code_hash: result.code_hash.clone(),
+        tx_hash: result.tx_hash.clone(),
+    });
+}
+
+/// Contracts instantiated since the last call
+pub fn take() -> Vec<Deployed> {
+    std::mem::take(&mut *deployed().lock().unwrap())
+}
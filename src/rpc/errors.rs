@@ -0,0 +1,258 @@
+// Error responses of the RPC server
+//
+// Failed requests answer with a stable code from the JSON-RPC server error
+// range and a `data` object clients can branch on:
+//
+//   -32001  network               the node is unreachable or the connection dropped
+//   -32002  decode                metadata, arguments or chain data failed to (de)code
+//   -32003  insufficient_balance  the account cannot pay the fees or the transfer
+//   -32004  module                a pallet error; `pallet` and `error` name it
+//   -32005  transaction           the transaction was invalid, dropped or failed
+//   -32006  account               the account is unknown or locked, or a signature bad
+//   -32007  not_found             a file or prepared transaction does not exist
+//   -32008  rate_limited          the request must wait, as for the faucet cooldown
+//   -32009  timeout               the request ran out of time
+//   -32010  cancelled             the request was cancelled with `cancel`
+//   -32603  internal              anything else
+//
+// `data.kind` holds the name, and `data.details` the whole error chain. The
+// kind is found by looking through the chain for subxt and I/O errors, or an
+// error tagged with `Tagged` where the handler knows better.
+
+use jsonrpc_core::{Error as RpcError, ErrorCode};
+use serde_json::{json, Value as JsonValue};
+use subxt::error::{DispatchError, RpcError as SubxtRpcError, TokenError, TransactionError};
+use subxt::ext::subxt_rpcs;
+
+/// Kind of a failed request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Network,
+    Decode,
+    InsufficientBalance,
+    Module,
+    Transaction,
+    Account,
+    NotFound,
+    RateLimited,
+    Timeout,
+    Cancelled,
+    Internal,
+}
+
+impl ErrorKind {
+    pub fn code(self) -> i64 {
+        match self {
+            ErrorKind::Network => -32001,
+            ErrorKind::Decode => -32002,
+            ErrorKind::InsufficientBalance => -32003,
+            ErrorKind::Module => -32004,
+            ErrorKind::Transaction => -32005,
+            ErrorKind::Account => -32006,
+            ErrorKind::NotFound => -32007,
+            ErrorKind::RateLimited => -32008,
+            ErrorKind::Timeout => -32009,
+            ErrorKind::Cancelled => -32010,
+            ErrorKind::Internal => ErrorCode::InternalError.code(),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Network => "network",
+            ErrorKind::Decode => "decode",
+            ErrorKind::InsufficientBalance => "insufficient_balance",
+            ErrorKind::Module => "module",
+            ErrorKind::Transaction => "transaction",
+            ErrorKind::Account => "account",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::RateLimited => "rate_limited",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::Cancelled => "cancelled",
+            ErrorKind::Internal => "internal",
+        }
+    }
+}
+
+/// Error whose kind is known where it is raised
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct Tagged {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+/// An error of a known kind
+pub fn tagged(kind: ErrorKind, message: impl Into<String>) -> anyhow::Error {
+    Tagged {
+        kind,
+        message: message.into(),
+    }
+    .into()
+}
+
+/// JSON-RPC error answering a failed request
+pub fn to_rpc_error(error: &anyhow::Error) -> RpcError {
+    let (kind, mut data) = classify(error);
+    data["kind"] = json!(kind.name());
+    data["details"] = json!(error.chain().map(|e| e.to_string()).collect::<Vec<_>>());
+    let code = match kind {
+        ErrorKind::Internal => ErrorCode::InternalError,
+        kind => ErrorCode::ServerError(kind.code()),
+    };
+    RpcError {
+        code,
+        message: error.to_string(),
+        data: Some(data),
+    }
+}
+
+/// Kind of an error, with the fields `data` carries for it
+fn classify(error: &anyhow::Error) -> (ErrorKind, JsonValue) {
+    for cause in error.chain() {
+        if let Some(tagged) = cause.downcast_ref::<Tagged>() {
+            return (tagged.kind, json!({}));
+        }
+        if let Some(error) = cause.downcast_ref::<subxt::Error>() {
+            return classify_subxt(error);
+        }
+        if let Some(error) = cause.downcast_ref::<std::io::Error>() {
+            if error.kind() == std::io::ErrorKind::NotFound {
+                return (ErrorKind::NotFound, json!({}));
+            }
+        }
+        if cause.is::<serde_json::Error>() || cause.is::<scale::Error>() {
+            return (ErrorKind::Decode, json!({}));
+        }
+    }
+    (ErrorKind::Internal, json!({}))
+}
+
+fn classify_subxt(error: &subxt::Error) -> (ErrorKind, JsonValue) {
+    match error {
+        subxt::Error::Runtime(DispatchError::Module(module)) => match module.details() {
+            Ok(details) => {
+                let (pallet, variant) = (details.pallet.name(), details.variant.name.as_str());
+                let data = json!({
+                    "pallet": pallet,
+                    "error": variant,
+                    "docs": details.variant.docs.join(" "),
+                });
+                let kind = if is_insufficient_balance(pallet, variant) {
+                    ErrorKind::InsufficientBalance
+                } else {
+                    ErrorKind::Module
+                };
+                (kind, data)
+            }
+            Err(_) => (ErrorKind::Module, json!({})),
+        },
+        subxt::Error::Runtime(DispatchError::Token(TokenError::FundsUnavailable)) => {
+            (ErrorKind::InsufficientBalance, json!({}))
+        }
+        subxt::Error::Runtime(_) => (ErrorKind::Transaction, json!({})),
+        subxt::Error::Transaction(TransactionError::Invalid(reason))
+        | subxt::Error::Transaction(TransactionError::Dropped(reason))
+        | subxt::Error::Transaction(TransactionError::Error(reason)) => {
+            (transaction_kind(reason), json!({ "reason": reason }))
+        }
+        subxt::Error::Transaction(_) => (ErrorKind::Transaction, json!({})),
+        subxt::Error::Rpc(SubxtRpcError::ClientError(subxt_rpcs::Error::User(user))) => (
+            transaction_kind(&user.message),
+            json!({ "node_code": user.code, "reason": user.message }),
+        ),
+        subxt::Error::Rpc(SubxtRpcError::ClientError(
+            subxt_rpcs::Error::Deserialization(_) | subxt_rpcs::Error::Decode(_),
+        )) => (ErrorKind::Decode, json!({})),
+        subxt::Error::Rpc(_) | subxt::Error::Io(_) => (ErrorKind::Network, json!({})),
+        subxt::Error::Codec(_)
+        | subxt::Error::Serialization(_)
+        | subxt::Error::Metadata(_)
+        | subxt::Error::MetadataDecoding(_)
+        | subxt::Error::Decode(_)
+        | subxt::Error::Encode(_)
+        | subxt::Error::Unknown(_) => (ErrorKind::Decode, json!({})),
+        _ => (ErrorKind::Internal, json!({})),
+    }
+}
+
+/// Pallet errors meaning the account lacks funds
+fn is_insufficient_balance(pallet: &str, error: &str) -> bool {
+    matches!(
+        (pallet, error),
+        ("Balances", "InsufficientBalance")
+            | ("Balances", "ExistentialDeposit")
+            | ("Contracts", "TransferFailed")
+            | ("Contracts", "StorageDepositNotEnoughFunds")
+    )
+}
+
+/// Kind of a transaction the node refused, from its reason
+fn transaction_kind(reason: &str) -> ErrorKind {
+    // "Inability to pay some fees (e.g. account balance too low)"
+    if reason.contains("Inability to pay some fees") {
+        ErrorKind::InsufficientBalance
+    } else {
+        ErrorKind::Transaction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_to_rpc_error() {
+        let error = to_rpc_error(&anyhow::anyhow!("boom"));
+        assert_eq!(error.code, ErrorCode::InternalError);
+        let data = error.data.unwrap();
+        assert_eq!(data["kind"], "internal");
+        assert_eq!(data["details"], json!(["boom"]));
+
+        let locked: anyhow::Result<()> = Err(tagged(ErrorKind::Account, "Account is locked"));
+        let error = to_rpc_error(&locked.context("Failed to deploy").unwrap_err());
+        assert_eq!(error.code, ErrorCode::ServerError(-32006));
+        assert_eq!(error.message, "Failed to deploy");
+        let data = error.data.unwrap();
+        assert_eq!(data["kind"], "account");
+        assert_eq!(
+            data["details"],
+            json!(["Failed to deploy", "Account is locked"])
+        );
+
+        let missing = std::fs::read("/nonexistent/contract.wasm")
+            .context("Failed to read WASM file")
+            .unwrap_err();
+        assert_eq!(to_rpc_error(&missing).code, ErrorCode::ServerError(-32007));
+
+        let malformed = serde_json::from_str::<JsonValue>("{")
+            .context("Failed to parse metadata")
+            .unwrap_err();
+        assert_eq!(to_rpc_error(&malformed).data.unwrap()["kind"], "decode");
+    }
+
+    #[test]
+    fn test_classify_subxt() {
+        let refused = subxt::Error::Transaction(TransactionError::Invalid(
+            "Inability to pay some fees (e.g. account balance too low)".to_string(),
+        ));
+        let error = to_rpc_error(&anyhow::Error::from(refused));
+        assert_eq!(error.code, ErrorCode::ServerError(-32003));
+        assert_eq!(error.data.unwrap()["kind"], "insufficient_balance");
+
+        let dropped = subxt::Error::Transaction(TransactionError::Dropped("pool full".into()));
+        let data = to_rpc_error(&anyhow::Error::from(dropped)).data.unwrap();
+        assert_eq!(data["kind"], "transaction");
+        assert_eq!(data["reason"], "pool full");
+
+        let disconnected = subxt::Error::Rpc(SubxtRpcError::SubscriptionDropped);
+        assert_eq!(
+            to_rpc_error(&anyhow::Error::from(disconnected)).code,
+            ErrorCode::ServerError(-32001)
+        );
+
+        assert!(is_insufficient_balance("Balances", "InsufficientBalance"));
+        assert!(!is_insufficient_balance("Contracts", "ContractTrapped"));
+    }
+}
@@ -0,0 +1,126 @@
+// Faucet of the requestFaucet method
+//
+// A network has a faucet when its configuration has a `faucet` entry; the
+// built-in testnet and local networks send 100 GLIN from alice. An address
+// gets tokens once per `cooldown`: its next request is refused until then, and
+// a request that fails to transfer frees its slot again.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::config::FaucetConfig;
+use crate::rpc::errors::{tagged, ErrorKind};
+
+/// Faucet of a network, from the project configuration or else the built-in networks
+pub fn config(network: &str) -> Result<Option<FaucetConfig>> {
+    if let Ok(path) = crate::config::file::find_config_file() {
+        let file = crate::config::file::load_config_file(Some(&path))?;
+        if let Some(faucet) = file.networks.get(network).and_then(|n| n.faucet.clone()) {
+            return Ok(Some(faucet));
+        }
+    }
+    Ok(crate::config::ForgeConfig::default()
+        .networks
+        .remove(network)
+        .and_then(|network| network.faucet))
+}
+
+/// Tokens a faucet sends per request
+pub fn amount(faucet: &FaucetConfig) -> Result<u128> {
+    faucet
+        .amount
+        .parse()
+        .with_context(|| format!("Invalid faucet amount '{}'", faucet.amount))
+}
+
+/// Time until which each (network, address) waits for its next request
+fn cooldowns() -> &'static Mutex<HashMap<(String, String), Instant>> {
+    static COOLDOWNS: OnceLock<Mutex<HashMap<(String, String), Instant>>> = OnceLock::new();
+    COOLDOWNS.get_or_init(Default::default)
+}
+
+/// Claim the faucet for `address`, or fail if it is still cooling down
+pub fn reserve(network: &str, address: &str, cooldown: Duration) -> Result<()> {
+    let now = Instant::now();
+    let mut cooldowns = cooldowns().lock().unwrap();
+    cooldowns.retain(|_, until| *until > now);
+
+    let key = (network.to_string(), address.to_string());
+    if let Some(until) = cooldowns.get(&key) {
+        return Err(tagged(
+            ErrorKind::RateLimited,
+            format!(
+                "{} already received tokens from the {} faucet; try again in {}",
+                address,
+                network,
+                format_wait(*until - now)
+            ),
+        ));
+    }
+    cooldowns.insert(key, now + cooldown);
+    Ok(())
+}
+
+/// Free the claim of a request that sent nothing
+pub fn release(network: &str, address: &str) {
+    cooldowns()
+        .lock()
+        .unwrap()
+        .remove(&(network.to_string(), address.to_string()));
+}
+
+fn format_wait(wait: Duration) -> String {
+    match wait.as_secs() {
+        seconds @ 0..=119 => format!("{}s", seconds.max(1)),
+        seconds => format!("{} minutes", seconds.div_ceil(60)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve() {
+        let hour = Duration::from_secs(3600);
+        reserve("test-faucet", "5Alice", hour).unwrap();
+        let error = reserve("test-faucet", "5Alice", hour).unwrap_err();
+        assert!(error.to_string().contains("try again in 60 minutes"));
+        assert_eq!(
+            crate::rpc::errors::to_rpc_error(&error).code,
+            jsonrpc_core::ErrorCode::ServerError(-32008)
+        );
+
+        // Other addresses and networks have their own cooldown
+        reserve("test-faucet", "5Bob", hour).unwrap();
+        reserve("other-faucet", "5Alice", hour).unwrap();
+
+        release("test-faucet", "5Alice");
+        reserve("test-faucet", "5Alice", hour).unwrap();
+
+        reserve("test-faucet", "5Charlie", Duration::ZERO).unwrap();
+        reserve("test-faucet", "5Charlie", Duration::ZERO).unwrap();
+    }
+
+    #[test]
+    fn test_faucet_config() {
+        let faucet: FaucetConfig = serde_json::from_str(r#"{"cooldown": 60}"#).unwrap();
+        assert_eq!(faucet.account, "alice");
+        assert_eq!(faucet.cooldown, 60);
+        assert_eq!(amount(&faucet).unwrap(), 100_000_000_000_000_000_000);
+
+        let network: crate::config::NetworkConfig =
+            serde_json::from_str(r#"{"rpc": "ws://localhost:9944"}"#).unwrap();
+        assert_eq!(network.faucet, None);
+        assert!(crate::config::load_network("mainnet")
+            .unwrap()
+            .faucet
+            .is_none());
+        assert_eq!(
+            crate::config::load_network("local").unwrap().faucet,
+            Some(FaucetConfig::default())
+        );
+    }
+}
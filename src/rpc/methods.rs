@@ -1,8 +1,13 @@
 use crate::rpc::types::{
     CallParams, CallResult, ContractEvent, DeployParams, DeployResult, EstimateGasParams,
-    EstimateGasResult, GetBalanceParams, GetBalanceResult, GetBlockNumberParams,
-    GetBlockNumberResult, GetNetworkInfoParams, GetNetworkInfoResult, QueryParams, QueryResult,
-    RequestFaucetParams, RequestFaucetResult, WatchParams, WatchResult,
+    EstimateGasResult, GeneratedFile, GetBalanceParams, GetBalanceResult, GetBlockNumberParams,
+    GetBlockNumberResult, GetDeploymentParams, GetDeploymentResult, GetNetworkInfoParams,
+    GetNetworkInfoResult, GetStorageItemsParams, GetStorageItemsResult, GetStorageParams,
+    GetStorageResult, HealthParams, HealthResult, InstantiateParams, InstantiateResult,
+    QueryParams, QueryResult, RequestFaucetParams, RequestFaucetResult, SaveDeploymentParams,
+    SaveDeploymentResult, SignRawParams, SignRawResult, SubmitSignedParams, SubmitSignedResult,
+    TypegenParams, TypegenResult, UnlockAccountParams, UnlockAccountResult, UploadCodeParams,
+    UploadCodeResult, WatchParams, WatchResult,
 };
 use anyhow::{Context, Result};
 use futures::StreamExt;
@@ -26,16 +31,10 @@ pub async fn handle_deploy(params: DeployParams) -> Result<DeployResult> {
     ))?;
 
     // Connect to network
-    let client = glin_client::create_client(&network_config.rpc)
-        .await
-        .context(format!(
-            "Failed to connect to network: {}",
-            network_config.rpc
-        ))?;
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
 
     // Get signer account
-    let signer = glin_client::get_dev_account(&params.account)
-        .context(format!("Failed to get account: {}", params.account))?;
+    let signer = crate::rpc::accounts::signer(&params.account)?;
 
     // Deploy contract using existing logic
     let result = crate::contract::deploy_contract(
@@ -45,10 +44,12 @@ pub async fn handle_deploy(params: DeployParams) -> Result<DeployResult> {
         params.args,
         None,
         params.value,
+        params.salt.as_deref(),
         &signer,
     )
     .await
     .context("Failed to deploy contract")?;
+    crate::rpc::deployed::note(Some(&params.network), &result);
 
     // Convert to RPC result type
     Ok(DeployResult {
@@ -61,6 +62,82 @@ pub async fn handle_deploy(params: DeployParams) -> Result<DeployResult> {
     })
 }
 
+/// Handle uploadCode RPC method
+pub async fn handle_upload_code(params: UploadCodeParams) -> Result<UploadCodeResult> {
+    // Load WASM
+    let wasm_bytes = std::fs::read(&params.wasm)
+        .context(format!("Failed to read WASM file: {}", params.wasm))?;
+
+    // Get network configuration
+    let network_config = crate::config::load_network(&params.network).context(format!(
+        "Failed to load network config for: {}",
+        params.network
+    ))?;
+
+    // Connect to network
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
+
+    // Get signer account
+    let signer = crate::rpc::accounts::signer(&params.account)?;
+
+    let result = crate::contract::upload_code(&client, wasm_bytes, &signer)
+        .await
+        .context("Failed to upload code")?;
+
+    Ok(UploadCodeResult {
+        success: result.success,
+        code_hash: result.code_hash,
+        tx_hash: result.tx_hash,
+        block_hash: result.block_hash,
+        error: result.error,
+    })
+}
+
+/// Handle instantiate RPC method
+pub async fn handle_instantiate(params: InstantiateParams) -> Result<InstantiateResult> {
+    // Load metadata
+    let metadata_json = std::fs::read_to_string(&params.metadata)
+        .context(format!("Failed to read metadata file: {}", params.metadata))?;
+
+    let metadata = crate::contract::metadata::parse_metadata(&metadata_json)
+        .context("Failed to parse metadata")?;
+
+    // Get network configuration
+    let network_config = crate::config::load_network(&params.network).context(format!(
+        "Failed to load network config for: {}",
+        params.network
+    ))?;
+
+    // Connect to network
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
+
+    // Get signer account
+    let signer = crate::rpc::accounts::signer(&params.account)?;
+
+    let result = crate::contract::instantiate_contract(
+        &client,
+        &params.code_hash,
+        &metadata,
+        params.args,
+        params.constructor.as_deref(),
+        params.value,
+        params.salt.as_deref(),
+        &signer,
+    )
+    .await
+    .context("Failed to instantiate contract")?;
+    crate::rpc::deployed::note(Some(&params.network), &result);
+
+    Ok(InstantiateResult {
+        success: result.success,
+        address: result.contract_address,
+        code_hash: result.code_hash,
+        tx_hash: result.tx_hash,
+        block_hash: result.block_hash,
+        error: result.error,
+    })
+}
+
 /// Handle call RPC method
 pub async fn handle_call(params: CallParams) -> Result<CallResult> {
     // Load metadata
@@ -77,16 +154,10 @@ pub async fn handle_call(params: CallParams) -> Result<CallResult> {
     ))?;
 
     // Connect to network
-    let client = glin_client::create_client(&network_config.rpc)
-        .await
-        .context(format!(
-            "Failed to connect to network: {}",
-            network_config.rpc
-        ))?;
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
 
     // Get signer account
-    let signer = glin_client::get_dev_account(&params.account)
-        .context(format!("Failed to get account: {}", params.account))?;
+    let signer = crate::rpc::accounts::signer(&params.account)?;
 
     // Call contract using existing logic
     let result = crate::contract::call_contract(
@@ -126,12 +197,7 @@ pub async fn handle_query(params: QueryParams) -> Result<QueryResult> {
     ))?;
 
     // Connect to network for query
-    let client = glin_client::create_client(&network_config.rpc)
-        .await
-        .context(format!(
-            "Failed to connect to network: {}",
-            network_config.rpc
-        ))?;
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
 
     // Query contract using existing logic
     let result = crate::contract::query_contract(
@@ -162,12 +228,7 @@ pub async fn handle_watch(params: WatchParams) -> Result<WatchResult> {
     ))?;
 
     // Connect to network
-    let client = glin_client::create_client(&network_config.rpc)
-        .await
-        .context(format!(
-            "Failed to connect to network: {}",
-            network_config.rpc
-        ))?;
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
 
     let mut events = Vec::new();
     let limit = params.limit.unwrap_or(10);
@@ -240,9 +301,7 @@ pub async fn handle_watch(params: WatchParams) -> Result<WatchResult> {
             }
 
             // Get block hash for this number using RPC
-            let rpc = glin_client::create_rpc_client(&network_config.rpc)
-                .await
-                .context("Failed to create RPC client")?;
+            let rpc = crate::rpc::pool::rpc_methods(&network_config.rpc).await?;
 
             let block_hash_opt: Option<subxt::utils::H256> = rpc
                 .chain_get_block_hash(Some(block_num.into()))
@@ -309,16 +368,25 @@ pub async fn handle_get_balance(params: GetBalanceParams) -> Result<GetBalanceRe
     ))?;
 
     // Connect to network
-    let client = glin_client::create_client(&network_config.rpc)
-        .await
-        .context(format!(
-            "Failed to connect to network: {}",
-            network_config.rpc
-        ))?;
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
 
     // Parse account ID
     let account_id = AccountId32::from_str(&params.address).context("Failed to parse address")?;
 
+    let free = free_balance(&client, &account_id).await?;
+
+    Ok(GetBalanceResult {
+        success: true,
+        balance: Some(free.to_string()),
+        error: None,
+    })
+}
+
+/// Free balance of an account, 0 when it does not exist
+pub(crate) async fn free_balance(
+    client: &glin_client::GlinClient,
+    account_id: &subxt::utils::AccountId32,
+) -> Result<u128> {
     // Query account info using dynamic storage
     let account_query = subxt::dynamic::storage(
         "System",
@@ -337,26 +405,16 @@ pub async fn handle_get_balance(params: GetBalanceParams) -> Result<GetBalanceRe
         let value = info.to_value()?;
 
         if let Ok(json) = serde_json::to_value(&value) {
-            let free = json
+            return Ok(json
                 .get("data")
                 .and_then(|d| d.get("free"))
                 .and_then(|f| f.as_str())
                 .and_then(|s| s.parse::<u128>().ok())
-                .unwrap_or(0);
-
-            return Ok(GetBalanceResult {
-                success: true,
-                balance: Some(free.to_string()),
-                error: None,
-            });
+                .unwrap_or(0));
         }
     }
 
-    Ok(GetBalanceResult {
-        success: true,
-        balance: Some("0".to_string()),
-        error: None,
-    })
+    Ok(0)
 }
 
 /// Handle requestFaucet RPC method
@@ -364,39 +422,76 @@ pub async fn handle_request_faucet(params: RequestFaucetParams) -> Result<Reques
     use std::str::FromStr;
     use subxt::utils::AccountId32;
 
-    // Only allow faucet on testnet/local
-    if params.network != "testnet" && params.network != "local" {
+    let Some(faucet) = crate::rpc::faucet::config(&params.network)? else {
         return Ok(RequestFaucetResult {
             success: false,
             amount: None,
             tx_hash: None,
-            error: Some("Faucet only available on testnet and local networks".to_string()),
+            error: Some(format!(
+                "No faucet on network '{}'; configure one with its `faucet` entry",
+                params.network
+            )),
         });
-    }
+    };
+    let amount = crate::rpc::faucet::amount(&faucet)?;
 
-    // Get network configuration
-    let network_config = crate::config::load_network(&params.network).context(format!(
-        "Failed to load network config for: {}",
-        params.network
-    ))?;
+    // Parse recipient address
+    let dest = AccountId32::from_str(&params.address).context("Failed to parse address")?;
+    let address = dest.to_string();
+
+    crate::rpc::faucet::reserve(
+        &params.network,
+        &address,
+        std::time::Duration::from_secs(faucet.cooldown),
+    )?;
+    let result = send_faucet_tokens(&params.network, &faucet, dest, amount).await;
+    if result.is_err() {
+        crate::rpc::faucet::release(&params.network, &address);
+    }
+    let tx_hash = result?;
 
-    // Connect to network
-    let client = glin_client::create_client(&network_config.rpc)
-        .await
-        .context(format!(
-            "Failed to connect to network: {}",
-            network_config.rpc
-        ))?;
+    Ok(RequestFaucetResult {
+        success: true,
+        amount: Some(amount.to_string()),
+        tx_hash: Some(tx_hash),
+        error: None,
+    })
+}
 
-    // Use Alice as faucet account
-    let faucet_signer =
-        glin_client::get_dev_account("alice").context("Failed to get faucet account")?;
+/// Transfer `amount` from the faucet account to `dest`; returns the transaction hash
+async fn send_faucet_tokens(
+    network: &str,
+    faucet: &crate::config::FaucetConfig,
+    dest: subxt::utils::AccountId32,
+    amount: u128,
+) -> Result<String> {
+    use crate::rpc::errors::{tagged, ErrorKind};
 
-    // Send tokens (100 GLIN)
-    let amount = 100_000_000_000_000_000_000u128; // 100 GLIN with 18 decimals
+    // Get network configuration
+    let network_config = crate::config::load_network(network)
+        .context(format!("Failed to load network config for: {}", network))?;
 
-    // Parse recipient address
-    let dest = AccountId32::from_str(&params.address).context("Failed to parse address")?;
+    // Connect to network
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
+
+    let faucet_signer = crate::rpc::accounts::signer(&faucet.account)
+        .with_context(|| format!("Failed to get faucet account '{}'", faucet.account))?;
+    let faucet_id = faucet_signer.public_key().to_account_id();
+
+    // Refuse up front rather than with an opaque fee payment error
+    let balance = free_balance(&client, &faucet_id).await?;
+    if balance <= amount {
+        return Err(tagged(
+            ErrorKind::InsufficientBalance,
+            format!(
+                "Faucet account '{}' ({}) has {} GLIN, not enough to send {} GLIN; fund it or lower the faucet amount",
+                faucet.account,
+                faucet_id,
+                crate::cli::balance::format_balance(balance),
+                crate::cli::balance::format_balance(amount)
+            ),
+        ));
+    }
 
     // Create transfer extrinsic
     let transfer_tx = subxt::dynamic::tx(
@@ -409,39 +504,477 @@ pub async fn handle_request_faucet(params: RequestFaucetParams) -> Result<Reques
     );
 
     // Submit and wait for transaction
-    let events = client
+    let progress = client
         .tx()
         .sign_and_submit_then_watch_default(&transfer_tx, &faucet_signer)
+        .await?;
+    let events = crate::contract::wait_for_finalized(progress)
         .await?
-        .wait_for_finalized_success()
+        .wait_for_success()
         .await?;
 
-    let tx_hash = format!("0x{}", hex::encode(events.extrinsic_hash()));
+    Ok(format!("0x{}", hex::encode(events.extrinsic_hash())))
+}
 
-    Ok(RequestFaucetResult {
+/// Handle estimateGas RPC method
+pub async fn handle_estimate_gas(params: EstimateGasParams) -> Result<EstimateGasResult> {
+    // Load metadata
+    let metadata_json = std::fs::read_to_string(&params.metadata)
+        .context(format!("Failed to read metadata file: {}", params.metadata))?;
+
+    let metadata = crate::contract::metadata::parse_metadata(&metadata_json)
+        .context("Failed to parse metadata")?;
+
+    // Get network configuration
+    let network_config = crate::config::load_network(&params.network).context(format!(
+        "Failed to load network config for: {}",
+        params.network
+    ))?;
+
+    // Connect to network
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
+
+    // Dry-run from a signing account, or from any address (without a fee estimate)
+    let signer = crate::rpc::accounts::signer(&params.from).ok();
+    let origin = match &signer {
+        Some(signer) => signer.public_key().to_account_id(),
+        None => crate::contract::parse_account_id(&params.from)
+            .context(format!("Failed to get account: {}", params.from))?,
+    };
+
+    let dry_run = crate::contract::dry_run_call(
+        &network_config.rpc,
+        &origin,
+        &params.address,
+        &metadata,
+        &params.method,
+        params.args.clone(),
+        params.value,
+    )
+    .await
+    .context("Failed to dry-run contract call")?;
+
+    let error = if dry_run.success {
+        None
+    } else {
+        Some(dry_run_failure(
+            &dry_run,
+            &metadata,
+            &params.method,
+            &client,
+        ))
+    };
+
+    let estimated_cost = match &signer {
+        Some(signer) if dry_run.success => Some(
+            crate::contract::estimate_call_fee(
+                &client,
+                &params.address,
+                &metadata,
+                &params.method,
+                &params.args,
+                params.value,
+                dry_run.gas_required,
+                signer,
+            )
+            .await?
+            .to_string(),
+        ),
+        _ => None,
+    };
+
+    Ok(EstimateGasResult {
+        success: dry_run.success,
+        gas_limit: Some(dry_run.gas_required.ref_time),
+        ref_time: Some(dry_run.gas_required.ref_time),
+        proof_size: Some(dry_run.gas_required.proof_size),
+        storage_deposit: Some(dry_run.storage_deposit.to_string()),
+        estimated_cost,
+        error,
+    })
+}
+
+/// Why a dry-run failed: the decoded revert value or dispatch error, with the debug message
+fn dry_run_failure(
+    dry_run: &crate::contract::DryRunResult,
+    metadata: &ink_metadata::InkProject,
+    method: &str,
+    client: &glin_client::GlinClient,
+) -> String {
+    let mut reason = match (&dry_run.dispatch_error, &dry_run.data) {
+        (Some(dispatch_error), _) => {
+            crate::contract::describe_dispatch_error(dispatch_error, Some(&client.metadata()))
+        }
+        (None, Some(data)) => {
+            let decoded = crate::contract::metadata::get_message_spec(metadata, method)
+                .map(crate::contract::metadata::get_message_return_type)
+                .and_then(|return_type| {
+                    crate::contract::encoding::decode_result(data, Some(return_type), metadata)
+                });
+            match decoded {
+                Ok(value) => format!("Contract reverted: {}", value),
+                Err(_) => format!("Contract reverted: 0x{}", hex::encode(data)),
+            }
+        }
+        (None, None) => dry_run
+            .error
+            .clone()
+            .unwrap_or_else(|| "Unknown error".to_string()),
+    };
+    if !dry_run.debug_message.is_empty() {
+        reason.push_str(&format!(" ({})", dry_run.debug_message.trim()));
+    }
+    reason
+}
+
+/// Handle typegen RPC method
+pub async fn handle_typegen(params: TypegenParams) -> Result<TypegenResult> {
+    use clap::Parser;
+
+    // Reuse the CLI arguments, their defaults and their validation
+    let mut argv = vec!["typegen".to_string()];
+    if let Some(metadata) = &params.metadata {
+        argv.extend(["--abi".to_string(), metadata.clone()]);
+    }
+    if let Some(output) = &params.output {
+        argv.extend(["--output".to_string(), output.clone()]);
+    }
+    if let Some(framework) = &params.framework {
+        argv.extend(["--framework".to_string(), framework.clone()]);
+    }
+    for (enabled, flag) in [
+        (params.react_query, "--react-query"),
+        (params.mock, "--mock"),
+        (params.strict_bigint, "--strict-bigint"),
+        (params.branded, "--branded"),
+    ] {
+        if enabled {
+            argv.push(flag.to_string());
+        }
+    }
+    let args = crate::cli::typegen::TypegenArgs::try_parse_from(&argv)
+        .map_err(|e| anyhow::anyhow!("{}", e.render().to_string().trim()))?;
+
+    let (contract_name, paths) =
+        tokio::task::spawn_blocking(move || crate::cli::typegen::generate_typescript(&args))
+            .await??;
+
+    let files = paths
+        .into_iter()
+        .map(|path| {
+            let content = if params.include_contents {
+                Some(
+                    std::fs::read_to_string(&path)
+                        .context(format!("Failed to read {}", path.display()))?,
+                )
+            } else {
+                None
+            };
+            Ok(GeneratedFile {
+                path: path.display().to_string(),
+                content,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(TypegenResult {
         success: true,
-        amount: Some(amount.to_string()),
-        tx_hash: Some(tx_hash),
+        contract_name: Some(contract_name),
+        files,
         error: None,
     })
 }
 
-/// Handle estimateGas RPC method
-pub async fn handle_estimate_gas(_params: EstimateGasParams) -> Result<EstimateGasResult> {
-    // Estimate gas (simplified - returns a default estimate)
-    // TODO: Implement proper gas estimation using contract metadata and dry-run
-    let gas_limit = 100_000_000_000u64; // Default 100 billion gas units
-    let gas_price = 1u128; // 1 unit per gas
-    let estimated_cost = (gas_limit as u128 * gas_price).to_string();
+/// Handle unlockAccount RPC method
+pub async fn handle_unlock_account(params: UnlockAccountParams) -> Result<UnlockAccountResult> {
+    // Key derivation takes a while; keep it off the request threads
+    let unlocked = tokio::task::spawn_blocking(move || {
+        crate::rpc::accounts::unlock(&params.account, &params.password)
+    })
+    .await?;
+
+    Ok(match unlocked {
+        Ok(address) => UnlockAccountResult {
+            success: true,
+            address: Some(address),
+            error: None,
+        },
+        Err(e) => UnlockAccountResult {
+            success: false,
+            address: None,
+            error: Some(e.to_string()),
+        },
+    })
+}
 
-    Ok(EstimateGasResult {
+/// Handle getDeployment RPC method
+pub async fn handle_get_deployment(params: GetDeploymentParams) -> Result<GetDeploymentResult> {
+    get_deployment(
+        std::path::Path::new(crate::config::deployments::DEPLOYMENTS_FILE),
+        params,
+    )
+}
+
+fn get_deployment(
+    registry: &std::path::Path,
+    params: GetDeploymentParams,
+) -> Result<GetDeploymentResult> {
+    let registry = crate::config::deployments::DeploymentRegistry::open(registry)?;
+    let found = registry.find(&params.network, &params.name);
+
+    Ok(GetDeploymentResult {
+        success: true,
+        name: found.map(|(name, _)| name.to_string()),
+        deployment: found.map(|(_, deployment)| deployment.clone()),
+        error: None,
+    })
+}
+
+/// Handle saveDeployment RPC method
+pub async fn handle_save_deployment(params: SaveDeploymentParams) -> Result<SaveDeploymentResult> {
+    save_deployment(
+        std::path::Path::new(crate::config::deployments::DEPLOYMENTS_FILE),
+        params,
+    )
+}
+
+fn save_deployment(
+    registry: &std::path::Path,
+    params: SaveDeploymentParams,
+) -> Result<SaveDeploymentResult> {
+    // Saves of one batch run in parallel; each must see the others' records
+    static REGISTRY: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    crate::contract::parse_account_id(&params.deployment.address).with_context(|| {
+        format!(
+            "Invalid address of deployment '{}': {}",
+            params.name, params.deployment.address
+        )
+    })?;
+
+    let _guard = REGISTRY.lock().unwrap();
+    let mut registry = crate::config::deployments::DeploymentRegistry::open(registry)?;
+    let previous = registry
+        .find(&params.network, &params.name)
+        .filter(|(name, _)| *name == params.name)
+        .map(|(_, deployment)| deployment.clone());
+    registry.record(&params.network, &params.name, params.deployment)?;
+
+    Ok(SaveDeploymentResult {
+        success: true,
+        previous,
+        error: None,
+    })
+}
+
+/// Handle getStorage RPC method
+pub async fn handle_get_storage(params: GetStorageParams) -> Result<GetStorageResult> {
+    let network_config = crate::config::load_network(&params.network).context(format!(
+        "Failed to load network config for: {}",
+        params.network
+    ))?;
+    let rpc = crate::rpc::pool::rpc_client(&network_config.rpc).await?;
+
+    let contract = crate::contract::parse_account_id(&params.address)?;
+    let key =
+        hex::decode(params.key.trim_start_matches("0x")).context("Invalid storage key hex")?;
+    let value = crate::rpc::storage::read(&rpc, &contract, &key).await?;
+
+    Ok(GetStorageResult {
+        success: true,
+        value: value.map(|value| format!("0x{}", hex::encode(value))),
+        error: None,
+    })
+}
+
+/// Handle getStorageItems RPC method
+pub async fn handle_get_storage_items(
+    params: GetStorageItemsParams,
+) -> Result<GetStorageItemsResult> {
+    let network_config = crate::config::load_network(&params.network).context(format!(
+        "Failed to load network config for: {}",
+        params.network
+    ))?;
+    let rpc = crate::rpc::pool::rpc_client(&network_config.rpc).await?;
+
+    let contract = crate::contract::parse_account_id(&params.address)?;
+    let prefix = hex::decode(params.prefix.trim_start_matches("0x"))
+        .context("Invalid storage prefix hex")?;
+    let start = params
+        .start_key
+        .map(|key| hex::decode(key.trim_start_matches("0x")))
+        .transpose()
+        .context("Invalid start key hex")?;
+    let (items, next) =
+        crate::rpc::storage::items(&rpc, &contract, &prefix, params.count, start).await?;
+
+    let hex = |bytes: &[u8]| format!("0x{}", hex::encode(bytes));
+    Ok(GetStorageItemsResult {
+        success: true,
+        items: items
+            .iter()
+            .map(|item| crate::rpc::types::StorageItem {
+                key: item.key.as_deref().map(hex),
+                trie_key: hex(&item.trie_key),
+                value: hex(&item.value),
+            })
+            .collect(),
+        next_key: next.as_deref().map(hex),
+        error: None,
+    })
+}
+
+/// Handle signRaw RPC method
+pub async fn handle_sign_raw(params: SignRawParams) -> Result<SignRawResult> {
+    use crate::rpc::signing::{Kind, Pending};
+
+    let (network, account) = match &params {
+        SignRawParams::Deploy(p) => (&p.network, &p.account),
+        SignRawParams::UploadCode(p) => (&p.network, &p.account),
+        SignRawParams::Instantiate(p) => (&p.network, &p.account),
+        SignRawParams::Call(p) => (&p.network, &p.account),
+    };
+    let signer = crate::contract::parse_account_id(account).map_err(|e| {
+        crate::rpc::errors::tagged(
+            crate::rpc::errors::ErrorKind::Account,
+            format!("signRaw needs the signer's address as account: {}", e),
+        )
+    })?;
+
+    // Get network configuration
+    let network_config = crate::config::load_network(network)
+        .context(format!("Failed to load network config for: {}", network))?;
+
+    // Connect to network
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
+
+    let (tx, kind) = match &params {
+        SignRawParams::Deploy(p) => {
+            let wasm_bytes =
+                std::fs::read(&p.wasm).context(format!("Failed to read WASM file: {}", p.wasm))?;
+            let metadata = load_metadata(&p.metadata)?;
+            let tx = crate::contract::deploy_tx(
+                &wasm_bytes,
+                &metadata,
+                &p.args,
+                None,
+                p.value,
+                p.salt.as_deref(),
+            )?;
+            (tx, Kind::Deploy)
+        }
+        SignRawParams::UploadCode(p) => {
+            let wasm_bytes =
+                std::fs::read(&p.wasm).context(format!("Failed to read WASM file: {}", p.wasm))?;
+            (crate::contract::upload_tx(&wasm_bytes), Kind::UploadCode)
+        }
+        SignRawParams::Instantiate(p) => {
+            let metadata = load_metadata(&p.metadata)?;
+            let tx = crate::contract::instantiate_tx(
+                &p.code_hash,
+                &metadata,
+                &p.args,
+                p.constructor.as_deref(),
+                p.value,
+                p.salt.as_deref(),
+            )?;
+            (tx, Kind::Instantiate(p.code_hash.clone()))
+        }
+        SignRawParams::Call(p) => {
+            let metadata = load_metadata(&p.metadata)?;
+            let tx = crate::contract::contract_call_tx(
+                &p.address, &metadata, &p.method, &p.args, p.value,
+            )?;
+            (tx, Kind::Call)
+        }
+    };
+
+    let transaction = client
+        .tx()
+        .create_partial(&tx, &signer, Default::default())
+        .await
+        .context("Failed to prepare transaction")?;
+    let payload = format!("0x{}", hex::encode(transaction.signer_payload()));
+    let address = signer.to_string();
+    let id = crate::rpc::signing::insert(Pending::new(client, transaction, signer, kind));
+
+    Ok(SignRawResult {
         success: true,
-        gas_limit: Some(gas_limit),
-        estimated_cost: Some(estimated_cost),
+        id: Some(id),
+        payload: Some(payload),
+        address: Some(address),
         error: None,
     })
 }
 
+/// Handle submitSigned RPC method
+pub async fn handle_submit_signed(params: SubmitSignedParams) -> Result<SubmitSignedResult> {
+    use crate::rpc::signing::Kind;
+
+    let signature =
+        crate::rpc::signing::parse_signature(&params.signature, params.signature_type.as_deref())?;
+    let mut pending = crate::rpc::signing::take(&params.id)?;
+    let progress = pending
+        .transaction
+        .sign_with_account_and_signature(&pending.signer, &signature)
+        .submit_and_watch()
+        .await
+        .context("Failed to submit signed transaction")?;
+
+    Ok(match pending.kind {
+        Kind::Deploy | Kind::Instantiate(_) => {
+            let code_hash = match &pending.kind {
+                Kind::Instantiate(code_hash) => Some(code_hash.as_str()),
+                _ => None,
+            };
+            let result = crate::contract::deploy_outcome(
+                &pending.client,
+                progress,
+                code_hash,
+                "Transaction failed",
+            )
+            .await?;
+            crate::rpc::deployed::note(None, &result);
+            SubmitSignedResult {
+                success: result.success,
+                tx_hash: result.tx_hash,
+                block_hash: result.block_hash,
+                address: result.contract_address,
+                code_hash: result.code_hash,
+                error: result.error,
+            }
+        }
+        Kind::UploadCode => {
+            let result = crate::contract::upload_outcome(progress).await?;
+            SubmitSignedResult {
+                success: result.success,
+                tx_hash: result.tx_hash,
+                block_hash: result.block_hash,
+                address: None,
+                code_hash: result.code_hash,
+                error: result.error,
+            }
+        }
+        Kind::Call => {
+            let result = crate::contract::call_outcome(progress).await?;
+            SubmitSignedResult {
+                success: result.success,
+                tx_hash: result.tx_hash,
+                block_hash: result.block_hash,
+                address: None,
+                code_hash: None,
+                error: result.error,
+            }
+        }
+    })
+}
+
+fn load_metadata(path: &str) -> Result<ink_metadata::InkProject> {
+    let metadata_json =
+        std::fs::read_to_string(path).context(format!("Failed to read metadata file: {}", path))?;
+    crate::contract::metadata::parse_metadata(&metadata_json).context("Failed to parse metadata")
+}
+
 /// Handle getBlockNumber RPC method
 pub async fn handle_get_block_number(params: GetBlockNumberParams) -> Result<GetBlockNumberResult> {
     // Get network configuration
@@ -451,12 +984,7 @@ pub async fn handle_get_block_number(params: GetBlockNumberParams) -> Result<Get
     ))?;
 
     // Connect to network
-    let client = glin_client::create_client(&network_config.rpc)
-        .await
-        .context(format!(
-            "Failed to connect to network: {}",
-            network_config.rpc
-        ))?;
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
 
     // Get latest block number
     let latest_block = client
@@ -482,12 +1010,7 @@ pub async fn handle_get_network_info(params: GetNetworkInfoParams) -> Result<Get
     ))?;
 
     // Connect to network
-    let client = glin_client::create_client(&network_config.rpc)
-        .await
-        .context(format!(
-            "Failed to connect to network: {}",
-            network_config.rpc
-        ))?;
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
 
     // Get latest block number
     let latest_block = client
@@ -505,3 +1028,171 @@ pub async fn handle_get_network_info(params: GetNetworkInfoParams) -> Result<Get
         error: None,
     })
 }
+
+/// Time the health check waits for the node
+const HEALTH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Handle health RPC method; `methods` are those the server supports
+pub async fn handle_health(params: HealthParams, methods: &[String]) -> Result<HealthResult> {
+    let network_config = crate::config::load_network(&params.network).context(format!(
+        "Failed to load network config for: {}",
+        params.network
+    ))?;
+
+    // An unreachable node makes the server unhealthy, not the call fail
+    let head = async {
+        let client = crate::rpc::pool::client(&network_config.rpc).await?;
+        let block = client
+            .blocks()
+            .at_latest()
+            .await
+            .context("Failed to get latest block")?;
+        anyhow::Ok((block.number() as u64, format!("{:?}", block.hash())))
+    };
+    let head = match tokio::time::timeout(HEALTH_TIMEOUT, head).await {
+        Ok(head) => head,
+        Err(_) => Err(anyhow::anyhow!(
+            "No answer from {} within {}s",
+            network_config.rpc,
+            HEALTH_TIMEOUT.as_secs()
+        )),
+    };
+
+    let (block_number, block_hash, error) = match head {
+        Ok((number, hash)) => (Some(number), Some(hash), None),
+        Err(e) => (None, None, Some(format!("{:#}", e))),
+    };
+    Ok(HealthResult {
+        success: error.is_none(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        methods: methods.to_vec(),
+        network: params.network,
+        rpc: network_config.rpc,
+        block_number,
+        block_hash,
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_handle_typegen() {
+        let dir =
+            std::env::temp_dir().join(format!("glin-forge-rpc-typegen-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let metadata = dir.join("flipper.json");
+        let abi = json!({
+            "contract": { "name": "flipper", "version": "0.1.0" },
+            "spec": {
+                "constructors": [
+                    { "label": "new", "selector": "0x9bae9d5e", "args": [{ "label": "init", "type": { "type": 0 } }] }
+                ],
+                "messages": [
+                    { "label": "get", "selector": "0x2f865bd9", "mutates": false, "args": [], "returnType": { "type": 0 } },
+                    { "label": "flip", "selector": "0x633aa551", "mutates": true, "args": [], "returnType": null }
+                ],
+                "events": []
+            },
+            "types": [{ "id": 0, "type": { "def": { "primitive": "bool" } } }]
+        });
+        std::fs::write(&metadata, abi.to_string()).unwrap();
+
+        let result = handle_typegen(TypegenParams {
+            metadata: Some(metadata.display().to_string()),
+            output: Some(dir.join("types").display().to_string()),
+            mock: true,
+            include_contents: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert!(result.success);
+        let name = result.contract_name.unwrap();
+        assert_eq!(result.files.len(), 2);
+        assert!(result.files[0].path.ends_with(&format!("{}.ts", name)));
+        assert!(result.files[0].content.as_ref().unwrap().contains("flip"));
+        assert!(result.files[1].path.ends_with(".mock.ts"));
+
+        // CLI validation applies
+        let error = handle_typegen(TypegenParams {
+            metadata: Some(metadata.display().to_string()),
+            react_query: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains("--react-query"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_deployment_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = dir.path().join(".glin-forge/deployments.json");
+        let lookup = |name: &str| GetDeploymentParams {
+            name: name.to_string(),
+            network: "local".to_string(),
+        };
+
+        let missing = get_deployment(&registry, lookup("flipper")).unwrap();
+        assert!(missing.success);
+        assert!(missing.deployment.is_none());
+
+        let save: SaveDeploymentParams = serde_json::from_value(json!({
+            "name": "flipper",
+            "address": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+            "code_hash": "0xc0de",
+            "block": 12,
+            "network": "local",
+        }))
+        .unwrap();
+        let saved = save_deployment(&registry, save.clone()).unwrap();
+        assert!(saved.previous.is_none());
+        assert!(save_deployment(&registry, save).unwrap().previous.is_some());
+
+        let found = get_deployment(&registry, lookup("flipper")).unwrap();
+        assert_eq!(found.name.as_deref(), Some("flipper"));
+        let deployment = found.deployment.unwrap();
+        assert_eq!(deployment.code_hash.as_deref(), Some("0xc0de"));
+        assert_eq!(deployment.block, Some(12));
+        // By address too
+        let by_address = get_deployment(&registry, lookup(&deployment.address)).unwrap();
+        assert_eq!(by_address.name.as_deref(), Some("flipper"));
+
+        let invalid: SaveDeploymentParams = serde_json::from_value(json!({
+            "name": "broken",
+            "address": "not-an-address",
+        }))
+        .unwrap();
+        assert!(save_deployment(&registry, invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_health() {
+        // Reports whether the node answers, but never fails because of it
+        let methods = vec!["deploy".to_string(), "health".to_string()];
+        let health = handle_health(
+            HealthParams {
+                network: "local".to_string(),
+            },
+            &methods,
+        )
+        .await
+        .unwrap();
+        assert_eq!(health.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(health.methods, methods);
+        assert_eq!(health.rpc, "ws://localhost:9944");
+        assert_eq!(health.success, health.error.is_none());
+        assert_eq!(health.success, health.block_hash.is_some());
+
+        let unknown = HealthParams {
+            network: "nowhere".to_string(),
+        };
+        assert!(handle_health(unknown, &methods).await.is_err());
+    }
+}
@@ -0,0 +1,183 @@
+// Request metrics of the RPC server
+//
+// Every JSON-RPC call is counted by method and outcome, and its duration added
+// to a histogram. With `--metrics` they are served in the Prometheus text
+// format on `GET /metrics`; with `--log-requests` each call is also logged to
+// stderr as one JSON line:
+//
+//   {"time":1760437000123,"method":"deploy","duration_ms":1520.4,"success":true}
+//
+// Calls of methods the server does not have are counted as `unknown`, so the
+// number of series stays bounded.
+
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Upper bounds of the duration histogram buckets, in seconds
+const BUCKETS: [f64; 10] = [0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0];
+
+/// Method label of calls to methods the server does not have
+pub const UNKNOWN_METHOD: &str = "unknown";
+
+/// Counters of the calls the server answered
+pub struct Metrics {
+    expose: bool,
+    log_requests: bool,
+    started: Instant,
+    methods: Mutex<BTreeMap<String, MethodStats>>,
+}
+
+#[derive(Default)]
+struct MethodStats {
+    calls: u64,
+    errors: u64,
+    seconds: f64,
+    /// Calls at most as long as each bucket bound
+    buckets: [u64; BUCKETS.len()],
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new(false, false)
+    }
+}
+
+impl Metrics {
+    /// Metrics served on /metrics when `expose`, logging each call when `log_requests`
+    pub fn new(expose: bool, log_requests: bool) -> Self {
+        Self {
+            expose,
+            log_requests,
+            started: Instant::now(),
+            methods: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Whether /metrics is served
+    pub fn exposed(&self) -> bool {
+        self.expose
+    }
+
+    /// Count a call, with the code of the error it answered with
+    pub fn record(&self, method: &str, elapsed: Duration, error: Option<i64>) {
+        let seconds = elapsed.as_secs_f64();
+        {
+            let mut methods = self.methods.lock().unwrap();
+            let stats = methods.entry(method.to_string()).or_default();
+            stats.calls += 1;
+            stats.errors += u64::from(error.is_some());
+            stats.seconds += seconds;
+            for (count, bound) in stats.buckets.iter_mut().zip(BUCKETS) {
+                *count += u64::from(seconds <= bound);
+            }
+        }
+
+        if self.log_requests {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64;
+            let mut line = json!({
+                "time": time,
+                "method": method,
+                "duration_ms": (seconds * 10_000.0).round() / 10.0,
+                "success": error.is_none(),
+            });
+            if let Some(code) = error {
+                line["error_code"] = json!(code);
+            }
+            eprintln!("{}", line);
+        }
+    }
+
+    /// Metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let methods = self.methods.lock().unwrap();
+        let mut out = String::new();
+
+        out.push_str("# HELP glin_forge_rpc_requests_total JSON-RPC calls answered\n");
+        out.push_str("# TYPE glin_forge_rpc_requests_total counter\n");
+        for (method, stats) in methods.iter() {
+            for (status, count) in [("ok", stats.calls - stats.errors), ("error", stats.errors)] {
+                let _ = writeln!(
+                    out,
+                    "glin_forge_rpc_requests_total{{method=\"{}\",status=\"{}\"}} {}",
+                    method, status, count
+                );
+            }
+        }
+
+        out.push_str(
+            "# HELP glin_forge_rpc_request_duration_seconds Time to answer JSON-RPC calls\n",
+        );
+        out.push_str("# TYPE glin_forge_rpc_request_duration_seconds histogram\n");
+        for (method, stats) in methods.iter() {
+            for (count, bound) in stats.buckets.iter().zip(BUCKETS) {
+                let _ = writeln!(
+                    out,
+                    "glin_forge_rpc_request_duration_seconds_bucket{{method=\"{}\",le=\"{}\"}} {}",
+                    method, bound, count
+                );
+            }
+            let _ = writeln!(
+                out,
+                "glin_forge_rpc_request_duration_seconds_bucket{{method=\"{}\",le=\"+Inf\"}} {}",
+                method, stats.calls
+            );
+            let _ = writeln!(
+                out,
+                "glin_forge_rpc_request_duration_seconds_sum{{method=\"{}\"}} {}",
+                method, stats.seconds
+            );
+            let _ = writeln!(
+                out,
+                "glin_forge_rpc_request_duration_seconds_count{{method=\"{}\"}} {}",
+                method, stats.calls
+            );
+        }
+
+        out.push_str("# HELP glin_forge_rpc_uptime_seconds Time since the server started\n");
+        out.push_str("# TYPE glin_forge_rpc_uptime_seconds gauge\n");
+        let _ = writeln!(
+            out,
+            "glin_forge_rpc_uptime_seconds {}",
+            self.started.elapsed().as_secs()
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_render() {
+        let metrics = Metrics::default();
+        metrics.record("deploy", Duration::from_millis(700), None);
+        metrics.record("deploy", Duration::from_millis(20), Some(-32603));
+        metrics.record("getBalance", Duration::from_millis(3), None);
+
+        let text = metrics.render();
+        assert!(text.contains("glin_forge_rpc_requests_total{method=\"deploy\",status=\"ok\"} 1"));
+        assert!(
+            text.contains("glin_forge_rpc_requests_total{method=\"deploy\",status=\"error\"} 1")
+        );
+        assert!(text.contains(
+            "glin_forge_rpc_request_duration_seconds_bucket{method=\"deploy\",le=\"0.05\"} 1"
+        ));
+        assert!(text.contains(
+            "glin_forge_rpc_request_duration_seconds_bucket{method=\"deploy\",le=\"1\"} 2"
+        ));
+        assert!(text.contains(
+            "glin_forge_rpc_request_duration_seconds_bucket{method=\"deploy\",le=\"+Inf\"} 2"
+        ));
+        assert!(
+            text.contains("glin_forge_rpc_request_duration_seconds_count{method=\"getBalance\"} 1")
+        );
+        assert!(text.contains("# TYPE glin_forge_rpc_uptime_seconds gauge"));
+    }
+}
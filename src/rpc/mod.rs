@@ -1,5 +1,22 @@
+pub mod accounts;
+pub mod auth;
+pub mod batch;
+pub mod client;
+pub mod deployed;
+pub mod errors;
+pub mod faucet;
 pub mod methods;
+pub mod metrics;
+pub mod network;
+pub mod pool;
+pub mod progress;
+pub mod requests;
 pub mod server;
+pub mod signing;
+pub mod storage;
+pub mod subscriptions;
+pub mod transport;
 pub mod types;
+pub mod websocket;
 
 pub use server::RpcServer;
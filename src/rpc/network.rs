@@ -0,0 +1,199 @@
+// Networks the RPC server acts on
+//
+// Scripts run against one default network. A request may name another one in
+// its `network` parameter, which must then be in the server's allowlist; a
+// request without a network uses the default. Every network is resolved through
+// `config::load_network` when the server starts, so unknown names fail early.
+//
+// A sandbox adds opt-in limits for scripts that should not be trusted with
+// everything the server's accounts can do: networks no request may act on, such
+// as mainnet and any network sharing its RPC endpoint, and the most value a
+// transaction may transfer, faucet transfers included. They bind requests to
+// the server only; a script connecting to a node itself is beyond them.
+
+use anyhow::Result;
+use jsonrpc_core::{Error as RpcError, Params};
+use serde::de::DeserializeOwned;
+
+use crate::rpc::types::NetworkParams;
+
+/// Default and allowed networks of a server
+#[derive(Debug, Clone)]
+pub struct NetworkPolicy {
+    default: String,
+    allowed: Vec<String>,
+    max_value: Option<u128>,
+}
+
+/// Opt-in limits on the requests of a server
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sandbox {
+    /// Refuse mainnet, and the networks with its RPC endpoint
+    pub deny_mainnet: bool,
+    /// Most value a transaction may transfer, in the smallest unit
+    pub max_value: Option<u128>,
+}
+
+impl NetworkPolicy {
+    /// Allow the default network and the `extra` ones
+    pub fn new(default: &str, extra: &[String]) -> Result<Self> {
+        let mut allowed = vec![default.to_string()];
+        for network in extra {
+            if !allowed.contains(network) {
+                allowed.push(network.clone());
+            }
+        }
+        for network in &allowed {
+            crate::config::load_network(network)?;
+        }
+        Ok(Self {
+            default: default.to_string(),
+            allowed,
+            max_value: None,
+        })
+    }
+
+    /// Apply a sandbox; fails when it denies a network the server is to act on
+    pub fn sandboxed(mut self, sandbox: &Sandbox) -> Result<Self> {
+        if sandbox.deny_mainnet {
+            let mainnet = crate::config::load_network("mainnet")?.rpc;
+            for network in &self.allowed {
+                if network == "mainnet" || crate::config::load_network(network)?.rpc == mainnet {
+                    anyhow::bail!("Network '{}' is mainnet, which the sandbox denies", network);
+                }
+            }
+        }
+        self.max_value = sandbox.max_value;
+        Ok(self)
+    }
+
+    #[cfg(test)]
+    pub fn allowed(&self) -> &[String] {
+        &self.allowed
+    }
+
+    /// Network a request acts on: the one it names, or the default
+    pub fn resolve(&self, requested: &str) -> Result<String, RpcError> {
+        if requested.is_empty() {
+            return Ok(self.default.clone());
+        }
+        if self.allowed.iter().any(|network| network == requested) {
+            Ok(requested.to_string())
+        } else {
+            Err(RpcError::invalid_params(format!(
+                "Network '{}' is not allowed by this server (allowed: {})",
+                requested,
+                self.allowed.join(", ")
+            )))
+        }
+    }
+
+    /// Parse request parameters, resolving their network
+    pub fn parse<P: DeserializeOwned + NetworkParams>(
+        &self,
+        params: Params,
+    ) -> Result<P, RpcError> {
+        let mut params: P = parse_params(params)?;
+        let network = self.resolve(params.network_mut())?;
+        *params.network_mut() = network;
+        if let Some(max_value) = self.max_value.filter(|max| params.value() > *max) {
+            return Err(RpcError::invalid_params(format!(
+                "Value of {} GLIN exceeds the sandbox's limit of {} GLIN",
+                crate::cli::balance::format_balance(params.value()),
+                crate::cli::balance::format_balance(max_value)
+            )));
+        }
+        Ok(params)
+    }
+}
+
+/// Parse request parameters given by name, or as the single object of a positional array as
+/// the SDK sends them
+pub fn parse_params<P: DeserializeOwned>(params: Params) -> Result<P, RpcError> {
+    let params = match params {
+        Params::Array(mut values) if values.len() == 1 && values[0].is_object() => {
+            match values.pop() {
+                Some(serde_json::Value::Object(map)) => Params::Map(map),
+                _ => unreachable!("checked to be an object"),
+            }
+        }
+        params => params,
+    };
+    params
+        .parse()
+        .map_err(|e| RpcError::invalid_params(format!("{}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::types::GetBlockNumberParams;
+    use serde_json::json;
+
+    #[test]
+    fn test_network_policy() {
+        let policy = NetworkPolicy::new("testnet", &["local".to_string()]).unwrap();
+        assert_eq!(policy.allowed(), ["testnet", "local"]);
+        assert_eq!(policy.resolve("").unwrap(), "testnet");
+        assert_eq!(policy.resolve("local").unwrap(), "local");
+        let error = policy.resolve("mainnet").unwrap_err();
+        assert!(error.message.contains("not allowed"));
+
+        let params = Params::Array(vec![json!({})]);
+        let parsed: GetBlockNumberParams = policy.parse(params).unwrap();
+        assert_eq!(parsed.network, "testnet");
+        let params = Params::Map(json!({ "network": "local" }).as_object().unwrap().clone());
+        let parsed: GetBlockNumberParams = policy.parse(params).unwrap();
+        assert_eq!(parsed.network, "local");
+        let params = Params::Array(vec![json!({ "network": "mainnet" })]);
+        assert!(policy.parse::<GetBlockNumberParams>(params).is_err());
+
+        assert!(NetworkPolicy::new("testnet", &["nowhere".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_sandbox() {
+        use crate::rpc::types::{CallParams, RequestFaucetParams, UploadCodeParams};
+
+        let sandbox = Sandbox {
+            deny_mainnet: true,
+            max_value: Some(10),
+        };
+        let error = NetworkPolicy::new("testnet", &["mainnet".to_string()])
+            .and_then(|policy| policy.sandboxed(&sandbox))
+            .unwrap_err();
+        assert!(error.to_string().contains("mainnet"), "{}", error);
+
+        let policy = NetworkPolicy::new("testnet", &["local".to_string()])
+            .and_then(|policy| policy.sandboxed(&sandbox))
+            .unwrap();
+        let call = |value: u64| {
+            Params::Array(vec![json!({
+                "address": "5Grw", "metadata": "m.json", "method": "flip",
+                "value": value, "account": "alice",
+            })])
+        };
+        assert!(policy.parse::<CallParams>(call(10)).is_ok());
+        let error = policy.parse::<CallParams>(call(11)).unwrap_err();
+        assert!(error.message.contains("sandbox"), "{}", error.message);
+        // Requests transferring nothing pass whatever the limit
+        let upload = Params::Array(vec![json!({ "wasm": "a.wasm", "account": "alice" })]);
+        assert!(policy.parse::<UploadCodeParams>(upload).is_ok());
+        // The local faucet sends 100 GLIN from alice
+        let faucet =
+            |network: &str| Params::Array(vec![json!({ "address": "5Grw", "network": network })]);
+        let error = policy
+            .parse::<RequestFaucetParams>(faucet("local"))
+            .unwrap_err();
+        assert!(error.message.contains("sandbox"), "{}", error.message);
+        let policy = NetworkPolicy::new("local", &[])
+            .and_then(|policy| {
+                policy.sandboxed(&Sandbox {
+                    deny_mainnet: false,
+                    max_value: Some(100_000_000_000_000_000_000),
+                })
+            })
+            .unwrap();
+        assert!(policy.parse::<RequestFaucetParams>(faucet("")).is_ok());
+    }
+}
@@ -0,0 +1,166 @@
+// Connections of the RPC server to the networks
+//
+// Connecting a subxt client fetches the runtime metadata, which takes seconds,
+// so every request reuses one live client per node URL. A client unused for
+// `CHECK_AFTER` is probed before it is handed out again and replaced when the
+// node no longer answers. Requests for a URL wait for its first connection
+// instead of each opening their own.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::RpcClient;
+
+use glin_client::{GlinClient, GlinConfig};
+
+/// Idle time after which a client is probed before reuse
+const CHECK_AFTER: Duration = Duration::from_secs(15);
+
+/// Time a probed node has to answer
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Live client of a node, connecting on first use
+pub async fn client(rpc_url: &str) -> Result<GlinClient> {
+    static CLIENTS: OnceLock<Pool<GlinClient>> = OnceLock::new();
+    CLIENTS
+        .get_or_init(|| Pool::new(CHECK_AFTER))
+        .get(
+            rpc_url,
+            || async {
+                glin_client::create_client(rpc_url)
+                    .await
+                    .with_context(|| format!("Failed to connect to network: {}", rpc_url))
+            },
+            |client| {
+                let client = client.clone();
+                async move {
+                    let probe = client.backend().latest_finalized_block_ref();
+                    matches!(tokio::time::timeout(PROBE_TIMEOUT, probe).await, Ok(Ok(_)))
+                }
+            },
+        )
+        .await
+}
+
+/// Live legacy RPC methods of a node, connecting on first use
+pub async fn rpc_methods(rpc_url: &str) -> Result<LegacyRpcMethods<GlinConfig>> {
+    Ok(LegacyRpcMethods::new(rpc_client(rpc_url).await?))
+}
+
+/// Live raw RPC client of a node, for methods subxt has no wrapper of
+pub async fn rpc_client(rpc_url: &str) -> Result<RpcClient> {
+    static RPC_CLIENTS: OnceLock<Pool<RpcClient>> = OnceLock::new();
+    RPC_CLIENTS
+        .get_or_init(|| Pool::new(CHECK_AFTER))
+        .get(
+            rpc_url,
+            || async {
+                RpcClient::from_url(rpc_url)
+                    .await
+                    .with_context(|| format!("Failed to connect to network: {}", rpc_url))
+            },
+            |rpc| {
+                let rpc = LegacyRpcMethods::<GlinConfig>::new(rpc.clone());
+                async move {
+                    let probe = rpc.system_health();
+                    matches!(tokio::time::timeout(PROBE_TIMEOUT, probe).await, Ok(Ok(_)))
+                }
+            },
+        )
+        .await
+}
+
+/// Pooled client of a key, locked while it connects
+type Slot<C> = Arc<tokio::sync::Mutex<Option<Entry<C>>>>;
+
+/// Clients by key, each probed when idle for `check_after`
+pub struct Pool<C> {
+    entries: Mutex<HashMap<String, Slot<C>>>,
+    check_after: Duration,
+}
+
+struct Entry<C> {
+    client: C,
+    used: Instant,
+}
+
+impl<C: Clone> Pool<C> {
+    pub fn new(check_after: Duration) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            check_after,
+        }
+    }
+
+    /// Client of `key`: the pooled one when healthy, otherwise a new connection
+    pub async fn get<Connect, Connecting, Healthy, Probing>(
+        &self,
+        key: &str,
+        connect: Connect,
+        healthy: Healthy,
+    ) -> Result<C>
+    where
+        Connect: FnOnce() -> Connecting,
+        Connecting: Future<Output = Result<C>>,
+        Healthy: FnOnce(&C) -> Probing,
+        Probing: Future<Output = bool>,
+    {
+        let slot = self
+            .entries
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+        let mut entry = slot.lock().await;
+
+        if let Some(pooled) = entry.as_mut() {
+            if pooled.used.elapsed() < self.check_after || healthy(&pooled.client).await {
+                pooled.used = Instant::now();
+                return Ok(pooled.client.clone());
+            }
+        }
+        // Leave nothing behind when the connection fails, so the next request retries
+        *entry = None;
+        let client = connect().await?;
+        *entry = Some(Entry {
+            client: client.clone(),
+            used: Instant::now(),
+        });
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_pool_reuse_and_health() {
+        let connections = AtomicU32::new(0);
+        let connect = || async { Ok(connections.fetch_add(1, Ordering::SeqCst) + 1) };
+        let healthy = |up: bool| move |_: &u32| async move { up };
+
+        // Within the idle time no probe runs
+        let pool = Pool::new(Duration::from_secs(60));
+        assert_eq!(pool.get("a", connect, healthy(false)).await.unwrap(), 1);
+        assert_eq!(pool.get("a", connect, healthy(false)).await.unwrap(), 1);
+        assert_eq!(pool.get("b", connect, healthy(true)).await.unwrap(), 2);
+
+        // Idle clients are probed, and replaced when unhealthy
+        let pool = Pool::new(Duration::ZERO);
+        assert_eq!(pool.get("a", connect, healthy(true)).await.unwrap(), 3);
+        assert_eq!(pool.get("a", connect, healthy(true)).await.unwrap(), 3);
+        assert_eq!(pool.get("a", connect, healthy(false)).await.unwrap(), 4);
+
+        // A failed connection is retried by the next request
+        let failing = || async { anyhow::bail!("node down") };
+        assert!(pool.get("c", failing, healthy(true)).await.is_err());
+        assert_eq!(pool.get("c", connect, healthy(true)).await.unwrap(), 5);
+    }
+}
@@ -0,0 +1,114 @@
+// Progress notifications of transactions
+//
+// Calls made over a WebSocket report the transactions they submit as they go,
+// since a deploy can wait long for finalization:
+//
+//   {"jsonrpc":"2.0","method":"progress","params":{"request":7,"stage":"in_block","tx_hash":"0x..","block_hash":"0x.."}}
+//
+// `request` is the id of the call, and `stage` one of submitted, in_block,
+// retracted (the block left the best chain) and finalized. Notifications are
+// best effort: they are dropped when the client reads too slowly, and calls
+// over HTTP report nothing.
+
+use serde::Serialize;
+use serde_json::{json, Value as JsonValue};
+use std::future::Future;
+use tokio::sync::mpsc;
+
+/// Method of the progress notifications
+pub const NOTIFICATION_METHOD: &str = "progress";
+
+/// Stage a submitted transaction reached
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum Stage {
+    Submitted { tx_hash: String },
+    InBlock { tx_hash: String, block_hash: String },
+    Retracted { tx_hash: String },
+    Finalized { tx_hash: String, block_hash: String },
+}
+
+#[derive(Clone)]
+struct Reporter {
+    outgoing: mpsc::Sender<String>,
+    request: JsonValue,
+}
+
+tokio::task_local! {
+    static REPORTER: Reporter;
+}
+
+/// Run a call, sending the progress it reports to `outgoing` as notifications about `request`
+pub async fn scope<F: Future>(
+    outgoing: Option<&mpsc::Sender<String>>,
+    request: Option<&JsonValue>,
+    call: F,
+) -> F::Output {
+    match (outgoing, request) {
+        (Some(outgoing), Some(request)) => {
+            let reporter = Reporter {
+                outgoing: outgoing.clone(),
+                request: request.clone(),
+            };
+            REPORTER.scope(reporter, call).await
+        }
+        _ => call.await,
+    }
+}
+
+/// Report a stage to the client of the current call, if it listens
+pub fn report(stage: Stage) {
+    let _ = REPORTER.try_with(|reporter| {
+        let _ = reporter
+            .outgoing
+            .try_send(notification(&reporter.request, &stage));
+    });
+}
+
+fn notification(request: &JsonValue, stage: &Stage) -> String {
+    let mut params = serde_json::to_value(stage).unwrap_or_default();
+    params["request"] = request.clone();
+    json!({
+        "jsonrpc": "2.0",
+        "method": NOTIFICATION_METHOD,
+        "params": params,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_progress_scope() {
+        let (outgoing, mut received) = mpsc::channel(8);
+        let submitted = || Stage::Submitted {
+            tx_hash: "0xabc".to_string(),
+        };
+
+        scope(Some(&outgoing), Some(&json!(7)), async {
+            report(submitted());
+            report(Stage::Finalized {
+                tx_hash: "0xabc".to_string(),
+                block_hash: "0xb10c".to_string(),
+            });
+        })
+        .await;
+        let first: JsonValue = serde_json::from_str(&received.recv().await.unwrap()).unwrap();
+        assert_eq!(first["method"], "progress");
+        assert_eq!(
+            first["params"],
+            json!({"request": 7, "stage": "submitted", "tx_hash": "0xabc"})
+        );
+        let second: JsonValue = serde_json::from_str(&received.recv().await.unwrap()).unwrap();
+        assert_eq!(second["params"]["stage"], "finalized");
+        assert_eq!(second["params"]["block_hash"], "0xb10c");
+
+        // Outside a scope, and for calls without an id, nothing is sent
+        report(submitted());
+        scope(Some(&outgoing), None, async { report(submitted()) }).await;
+        drop(outgoing);
+        assert!(received.recv().await.is_none());
+    }
+}
@@ -0,0 +1,199 @@
+// Running RPC calls: timeouts and cancellation
+//
+// Every call runs under a deadline, the server's `--request-timeout` or the
+// call's own `timeout` member (seconds, next to `method` and `params`). A call
+// with an id can also be cancelled by the `cancel` method until it answers.
+// Either way its handler is dropped at its next await point, which ends block
+// subscriptions and dry runs in flight, and the call answers with a `timeout`
+// or `cancelled` error. Ids are compared as JSON, so `1` and `"1"` differ;
+// every SDK client, over HTTP or WebSocket, prefixes its ids with a random
+// string so that clients do not cancel each other's calls.
+
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::rpc::errors::{tagged, to_rpc_error, ErrorKind};
+
+/// Timeout of calls when the server sets none
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Calls in flight, by id
+pub struct Requests {
+    timeout: Option<Duration>,
+    running: Mutex<HashMap<String, Vec<(u64, CancellationToken)>>>,
+    next: AtomicU64,
+}
+
+impl Default for Requests {
+    fn default() -> Self {
+        Self::new(Some(DEFAULT_TIMEOUT))
+    }
+}
+
+impl Requests {
+    /// Calls timing out after `timeout`, or never when None
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            running: Mutex::new(HashMap::new()),
+            next: AtomicU64::new(1),
+        }
+    }
+
+    /// Run a call, answering with an error when it times out or is cancelled first
+    pub async fn run<F>(
+        &self,
+        id: Option<&JsonValue>,
+        timeout: Option<Duration>,
+        call: F,
+    ) -> Option<String>
+    where
+        F: Future<Output = Option<String>>,
+    {
+        let timeout = timeout.or(self.timeout);
+        let token = CancellationToken::new();
+        let _running = id.map(|id| self.register(id, token.clone()));
+
+        let deadline = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+        let error = tokio::select! {
+            response = call => return response,
+            _ = deadline => tagged(
+                ErrorKind::Timeout,
+                format!("Request timed out after {}s", timeout.unwrap_or_default().as_secs_f64()),
+            ),
+            _ = token.cancelled() => tagged(ErrorKind::Cancelled, "Request cancelled"),
+        };
+        // Notifications get no answer, not even an error
+        let id = id?;
+        Some(
+            json!({
+                "jsonrpc": "2.0",
+                "error": to_rpc_error(&error),
+                "id": id,
+            })
+            .to_string(),
+        )
+    }
+
+    /// Cancel the running calls with `id`; false when none is running
+    pub fn cancel(&self, id: &JsonValue) -> bool {
+        let running = self.running.lock().unwrap().remove(&id.to_string());
+        let cancelled = running.unwrap_or_default();
+        for (_, token) in &cancelled {
+            token.cancel();
+        }
+        !cancelled.is_empty()
+    }
+
+    fn register(&self, id: &JsonValue, token: CancellationToken) -> Running<'_> {
+        let key = id.to_string();
+        let serial = self.next.fetch_add(1, Ordering::Relaxed);
+        self.running
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default()
+            .push((serial, token));
+        Running {
+            requests: self,
+            key,
+            serial,
+        }
+    }
+}
+
+/// Registration of a running call, removed when it ends or is dropped
+struct Running<'a> {
+    requests: &'a Requests,
+    key: String,
+    serial: u64,
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        let mut running = self.requests.running.lock().unwrap();
+        if let Some(calls) = running.get_mut(&self.key) {
+            calls.retain(|(serial, _)| *serial != self.serial);
+            if calls.is_empty() {
+                running.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Per-call timeout from a call's `timeout` member, removing it from the call
+pub fn take_timeout(call: &mut JsonValue) -> Option<Duration> {
+    let timeout = call.as_object_mut()?.remove("timeout")?;
+    timeout
+        .as_f64()
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_timeout_and_cancel() {
+        let requests = Arc::new(Requests::new(Some(Duration::from_millis(50))));
+        let forever = || async {
+            std::future::pending::<()>().await;
+            None
+        };
+
+        let answered = requests
+            .run(Some(&json!(1)), None, async { Some("done".to_string()) })
+            .await;
+        assert_eq!(answered.as_deref(), Some("done"));
+
+        let timed_out = requests
+            .run(Some(&json!(2)), None, forever())
+            .await
+            .unwrap();
+        let timed_out: JsonValue = serde_json::from_str(&timed_out).unwrap();
+        assert_eq!(timed_out["id"], 2);
+        assert_eq!(timed_out["error"]["code"], -32009);
+        assert_eq!(timed_out["error"]["data"]["kind"], "timeout");
+
+        // A call's own timeout takes precedence
+        let mut call = json!({"jsonrpc": "2.0", "id": 3, "method": "watch", "timeout": 10});
+        let timeout = take_timeout(&mut call);
+        assert_eq!(timeout, Some(Duration::from_secs(10)));
+        assert!(call.get("timeout").is_none());
+
+        let running = {
+            let requests = requests.clone();
+            tokio::spawn(async move {
+                requests
+                    .run(Some(&json!("abc")), timeout, forever())
+                    .await
+                    .unwrap()
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!requests.cancel(&json!("other")));
+        assert!(requests.cancel(&json!("abc")));
+        let cancelled: JsonValue = serde_json::from_str(&running.await.unwrap()).unwrap();
+        assert_eq!(cancelled["id"], "abc");
+        assert_eq!(cancelled["error"]["data"]["kind"], "cancelled");
+
+        // Finished calls are forgotten
+        assert!(!requests.cancel(&json!("abc")));
+        assert!(requests.running.lock().unwrap().is_empty());
+
+        // Notifications time out silently
+        assert_eq!(requests.run(None, None, forever()).await, None);
+    }
+}
@@ -1,44 +1,124 @@
 use anyhow::{Context, Result};
 use jsonrpc_core::{Error as RpcError, ErrorCode, IoHandler, Params};
-use jsonrpc_http_server::{Server, ServerBuilder};
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
 
+use crate::rpc::auth::Access;
+use crate::rpc::errors;
 use crate::rpc::methods;
+use crate::rpc::metrics::Metrics;
+use crate::rpc::network::{parse_params, NetworkPolicy};
+use crate::rpc::requests::Requests;
+use crate::rpc::subscriptions::Subscriptions;
+use crate::rpc::transport::Transport;
 use crate::rpc::types::*;
 
+/// Address the RPC server listens on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    /// TCP address; port 0 picks a random available port
+    Tcp(SocketAddr),
+    /// Unix domain socket path
+    Unix(PathBuf),
+}
+
+impl std::fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Endpoint::Tcp(address) => write!(f, "http://{}", address),
+            Endpoint::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+struct Listener {
+    shutdown: oneshot::Sender<()>,
+    /// Removed on shutdown
+    socket: Option<PathBuf>,
+}
+
+impl Listener {
+    fn close(self) {
+        let _ = self.shutdown.send(());
+        if let Some(socket) = self.socket {
+            let _ = std::fs::remove_file(socket);
+        }
+    }
+}
+
 /// JSON-RPC server for SDK communication
 pub struct RpcServer {
-    server: Arc<Mutex<Option<Server>>>,
-    port: u16,
+    server: Arc<Mutex<Option<Listener>>>,
+    endpoint: Endpoint,
 }
 
 impl RpcServer {
-    /// Start the RPC server on a random available port
-    pub async fn start(network: String) -> Result<Self> {
+    /// Start the RPC server on `endpoint`, serving only requests `access` allows
+    pub async fn start(
+        networks: NetworkPolicy,
+        access: Access,
+        metrics: Metrics,
+        requests: Requests,
+        endpoint: &Endpoint,
+    ) -> Result<Self> {
         let mut io = IoHandler::new();
-
-        // Clone network for each closure
-        let network_deploy = network.clone();
-        let network_call = network.clone();
-        let network_query = network.clone();
-        let network_watch = network.clone();
+        let networks = Arc::new(networks);
+        let requests = Arc::new(requests);
 
         // Register deploy method
+        let networks_deploy = networks.clone();
         io.add_method("deploy", move |params: Params| {
-            let _network = network_deploy.clone();
+            let networks = networks_deploy.clone();
             async move {
-                let deploy_params: DeployParams = params
-                    .parse()
-                    .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+                let deploy_params: DeployParams = networks.parse(params)?;
 
                 let result = methods::handle_deploy(deploy_params)
                     .await
-                    .map_err(|e| RpcError {
-                        code: ErrorCode::InternalError,
-                        message: e.to_string(),
-                        data: None,
-                    })?;
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        // Register uploadCode method
+        let networks_upload = networks.clone();
+        io.add_method("uploadCode", move |params: Params| {
+            let networks = networks_upload.clone();
+            async move {
+                let upload_params: UploadCodeParams = networks.parse(params)?;
+
+                let result = methods::handle_upload_code(upload_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        // Register instantiate method
+        let networks_instantiate = networks.clone();
+        io.add_method("instantiate", move |params: Params| {
+            let networks = networks_instantiate.clone();
+            async move {
+                let instantiate_params: InstantiateParams = networks.parse(params)?;
+
+                let result = methods::handle_instantiate(instantiate_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
 
                 let json = serde_json::to_value(&result).map_err(|e| RpcError {
                     code: ErrorCode::InternalError,
@@ -51,20 +131,15 @@ impl RpcServer {
         });
 
         // Register call method
+        let networks_call = networks.clone();
         io.add_method("call", move |params: Params| {
-            let _network = network_call.clone();
+            let networks = networks_call.clone();
             async move {
-                let call_params: CallParams = params
-                    .parse()
-                    .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+                let call_params: CallParams = networks.parse(params)?;
 
                 let result = methods::handle_call(call_params)
                     .await
-                    .map_err(|e| RpcError {
-                        code: ErrorCode::InternalError,
-                        message: e.to_string(),
-                        data: None,
-                    })?;
+                    .map_err(|e| errors::to_rpc_error(&e))?;
 
                 let json = serde_json::to_value(&result).map_err(|e| RpcError {
                     code: ErrorCode::InternalError,
@@ -77,20 +152,15 @@ impl RpcServer {
         });
 
         // Register query method
+        let networks_query = networks.clone();
         io.add_method("query", move |params: Params| {
-            let _network = network_query.clone();
+            let networks = networks_query.clone();
             async move {
-                let query_params: QueryParams = params
-                    .parse()
-                    .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+                let query_params: QueryParams = networks.parse(params)?;
 
                 let result = methods::handle_query(query_params)
                     .await
-                    .map_err(|e| RpcError {
-                        code: ErrorCode::InternalError,
-                        message: e.to_string(),
-                        data: None,
-                    })?;
+                    .map_err(|e| errors::to_rpc_error(&e))?;
 
                 let json = serde_json::to_value(&result).map_err(|e| RpcError {
                     code: ErrorCode::InternalError,
@@ -103,20 +173,88 @@ impl RpcServer {
         });
 
         // Register watch method
+        let networks_watch = networks.clone();
         io.add_method("watch", move |params: Params| {
-            let _network = network_watch.clone();
+            let networks = networks_watch.clone();
             async move {
-                let watch_params: WatchParams = params
-                    .parse()
-                    .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+                let watch_params: WatchParams = networks.parse(params)?;
 
                 let result = methods::handle_watch(watch_params)
                     .await
-                    .map_err(|e| RpcError {
-                        code: ErrorCode::InternalError,
-                        message: e.to_string(),
-                        data: None,
-                    })?;
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        // Register event subscription methods; events stream over SSE
+        let subscriptions = Arc::new(Subscriptions::default());
+        let subscriptions_subscribe = subscriptions.clone();
+        let networks_subscribe = networks.clone();
+        io.add_method("subscribeEvents", move |params: Params| {
+            let subscriptions = subscriptions_subscribe.clone();
+            let networks = networks_subscribe.clone();
+            async move {
+                let subscribe_params: SubscribeEventsParams = networks.parse(params)?;
+
+                let result = match subscriptions.subscribe(subscribe_params) {
+                    Ok(id) => SubscribeEventsResult {
+                        success: true,
+                        subscription_id: Some(id),
+                        error: None,
+                    },
+                    Err(e) => SubscribeEventsResult {
+                        success: false,
+                        subscription_id: None,
+                        error: Some(format!("{:#}", e)),
+                    },
+                };
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        let subscriptions_unsubscribe = subscriptions.clone();
+        io.add_method("unsubscribeEvents", move |params: Params| {
+            let subscriptions = subscriptions_unsubscribe.clone();
+            async move {
+                let unsubscribe_params: UnsubscribeEventsParams = parse_params(params)?;
+
+                let result = UnsubscribeEventsResult {
+                    success: subscriptions.unsubscribe(&unsubscribe_params.subscription_id),
+                };
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        let requests_cancel = requests.clone();
+        io.add_method("cancel", move |params: Params| {
+            let requests = requests_cancel.clone();
+            async move {
+                let cancel_params: CancelParams = parse_params(params)?;
+
+                let result = CancelResult {
+                    success: requests.cancel(&cancel_params.request_id),
+                };
 
                 let json = serde_json::to_value(&result).map_err(|e| RpcError {
                     code: ErrorCode::InternalError,
@@ -129,19 +267,76 @@ impl RpcServer {
         });
 
         // Register getBalance method
-        io.add_method("getBalance", move |params: Params| async move {
-            let balance_params: GetBalanceParams = params
-                .parse()
-                .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+        let networks_balance = networks.clone();
+        io.add_method("getBalance", move |params: Params| {
+            let networks = networks_balance.clone();
+            async move {
+                let balance_params: GetBalanceParams = networks.parse(params)?;
 
-            let result = methods::handle_get_balance(balance_params)
-                .await
-                .map_err(|e| RpcError {
+                let result = methods::handle_get_balance(balance_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
                     code: ErrorCode::InternalError,
-                    message: e.to_string(),
+                    message: format!("Serialization error: {}", e),
                     data: None,
                 })?;
 
+                Ok(json)
+            }
+        });
+
+        // Register requestFaucet method
+        let networks_faucet = networks.clone();
+        io.add_method("requestFaucet", move |params: Params| {
+            let networks = networks_faucet.clone();
+            async move {
+                let faucet_params: RequestFaucetParams = networks.parse(params)?;
+
+                let result = methods::handle_request_faucet(faucet_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        // Register estimateGas method
+        let networks_gas = networks.clone();
+        io.add_method("estimateGas", move |params: Params| {
+            let networks = networks_gas.clone();
+            async move {
+                let gas_params: EstimateGasParams = networks.parse(params)?;
+
+                let result = methods::handle_estimate_gas(gas_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        // Register typegen method
+        io.add_method("typegen", move |params: Params| async move {
+            let typegen_params: TypegenParams = parse_params(params)?;
+
+            let result = methods::handle_typegen(typegen_params)
+                .await
+                .map_err(|e| errors::to_rpc_error(&e))?;
+
             let json = serde_json::to_value(&result).map_err(|e| RpcError {
                 code: ErrorCode::InternalError,
                 message: format!("Serialization error: {}", e),
@@ -151,20 +346,34 @@ impl RpcServer {
             Ok(json)
         });
 
-        // Register requestFaucet method
-        io.add_method("requestFaucet", move |params: Params| async move {
-            let faucet_params: RequestFaucetParams = params
-                .parse()
-                .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+        // Register external signing methods
+        let networks_sign_raw = networks.clone();
+        io.add_method("signRaw", move |params: Params| {
+            let networks = networks_sign_raw.clone();
+            async move {
+                let sign_params: SignRawParams = networks.parse(params)?;
 
-            let result = methods::handle_request_faucet(faucet_params)
-                .await
-                .map_err(|e| RpcError {
+                let result = methods::handle_sign_raw(sign_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
                     code: ErrorCode::InternalError,
-                    message: e.to_string(),
+                    message: format!("Serialization error: {}", e),
                     data: None,
                 })?;
 
+                Ok(json)
+            }
+        });
+
+        io.add_method("submitSigned", move |params: Params| async move {
+            let submit_params: SubmitSignedParams = parse_params(params)?;
+
+            let result = methods::handle_submit_signed(submit_params)
+                .await
+                .map_err(|e| errors::to_rpc_error(&e))?;
+
             let json = serde_json::to_value(&result).map_err(|e| RpcError {
                 code: ErrorCode::InternalError,
                 message: format!("Serialization error: {}", e),
@@ -174,19 +383,13 @@ impl RpcServer {
             Ok(json)
         });
 
-        // Register estimateGas method
-        io.add_method("estimateGas", move |params: Params| async move {
-            let gas_params: EstimateGasParams = params
-                .parse()
-                .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+        // Register account unlocking methods
+        io.add_method("unlockAccount", move |params: Params| async move {
+            let unlock_params: UnlockAccountParams = parse_params(params)?;
 
-            let result = methods::handle_estimate_gas(gas_params)
+            let result = methods::handle_unlock_account(unlock_params)
                 .await
-                .map_err(|e| RpcError {
-                    code: ErrorCode::InternalError,
-                    message: e.to_string(),
-                    data: None,
-                })?;
+                .map_err(|e| errors::to_rpc_error(&e))?;
 
             let json = serde_json::to_value(&result).map_err(|e| RpcError {
                 code: ErrorCode::InternalError,
@@ -197,19 +400,12 @@ impl RpcServer {
             Ok(json)
         });
 
-        // Register getBlockNumber method
-        io.add_method("getBlockNumber", move |params: Params| async move {
-            let block_params: GetBlockNumberParams = params
-                .parse()
-                .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+        io.add_method("lockAccount", move |params: Params| async move {
+            let lock_params: LockAccountParams = parse_params(params)?;
 
-            let result = methods::handle_get_block_number(block_params)
-                .await
-                .map_err(|e| RpcError {
-                    code: ErrorCode::InternalError,
-                    message: e.to_string(),
-                    data: None,
-                })?;
+            let result = LockAccountResult {
+                success: crate::rpc::accounts::lock(&lock_params.account),
+            };
 
             let json = serde_json::to_value(&result).map_err(|e| RpcError {
                 code: ErrorCode::InternalError,
@@ -220,52 +416,208 @@ impl RpcServer {
             Ok(json)
         });
 
+        // Register getBlockNumber method
+        let networks_block_number = networks.clone();
+        io.add_method("getBlockNumber", move |params: Params| {
+            let networks = networks_block_number.clone();
+            async move {
+                let block_params: GetBlockNumberParams = networks.parse(params)?;
+
+                let result = methods::handle_get_block_number(block_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
         // Register getNetworkInfo method
-        io.add_method("getNetworkInfo", move |params: Params| async move {
-            let info_params: GetNetworkInfoParams = params
-                .parse()
-                .map_err(|e| RpcError::invalid_params(format!("{}", e)))?;
+        let networks_info = networks.clone();
+        io.add_method("getNetworkInfo", move |params: Params| {
+            let networks = networks_info.clone();
+            async move {
+                let info_params: GetNetworkInfoParams = networks.parse(params)?;
 
-            let result = methods::handle_get_network_info(info_params)
-                .await
-                .map_err(|e| RpcError {
+                let result = methods::handle_get_network_info(info_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
                     code: ErrorCode::InternalError,
-                    message: e.to_string(),
+                    message: format!("Serialization error: {}", e),
                     data: None,
                 })?;
 
-            let json = serde_json::to_value(&result).map_err(|e| RpcError {
-                code: ErrorCode::InternalError,
-                message: format!("Serialization error: {}", e),
-                data: None,
-            })?;
+                Ok(json)
+            }
+        });
 
-            Ok(json)
+        // Register getDeployment method
+        let networks_get_deployment = networks.clone();
+        io.add_method("getDeployment", move |params: Params| {
+            let networks = networks_get_deployment.clone();
+            async move {
+                let get_deployment_params: GetDeploymentParams = networks.parse(params)?;
+
+                let result = methods::handle_get_deployment(get_deployment_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        // Register saveDeployment method
+        let networks_save_deployment = networks.clone();
+        io.add_method("saveDeployment", move |params: Params| {
+            let networks = networks_save_deployment.clone();
+            async move {
+                let save_deployment_params: SaveDeploymentParams = networks.parse(params)?;
+
+                let result = methods::handle_save_deployment(save_deployment_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
         });
 
-        // Start server on random port
-        let server = ServerBuilder::new(io)
-            .start_http(&"127.0.0.1:0".parse()?)
-            .context("Unable to start RPC server")?;
+        // Register getStorage method
+        let networks_storage = networks.clone();
+        io.add_method("getStorage", move |params: Params| {
+            let networks = networks_storage.clone();
+            async move {
+                let storage_params: GetStorageParams = networks.parse(params)?;
 
-        let port = server.address().port();
+                let result = methods::handle_get_storage(storage_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        // Register getStorageItems method
+        let networks_storage_items = networks.clone();
+        io.add_method("getStorageItems", move |params: Params| {
+            let networks = networks_storage_items.clone();
+            async move {
+                let storage_items_params: GetStorageItemsParams = networks.parse(params)?;
+
+                let result = methods::handle_get_storage_items(storage_items_params)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        // Register health method, last so that it knows every other method
+        let mut supported: Vec<String> = io.iter().map(|(name, _)| name.clone()).collect();
+        supported.push("health".to_string());
+        supported.sort();
+        let supported = Arc::new(supported);
+        let networks_health = networks.clone();
+        io.add_method("health", move |params: Params| {
+            let networks = networks_health.clone();
+            let supported = supported.clone();
+            async move {
+                let health_params: HealthParams = networks.parse(params)?;
+
+                let result = methods::handle_health(health_params, &supported)
+                    .await
+                    .map_err(|e| errors::to_rpc_error(&e))?;
+
+                let json = serde_json::to_value(&result).map_err(|e| RpcError {
+                    code: ErrorCode::InternalError,
+                    message: format!("Serialization error: {}", e),
+                    data: None,
+                })?;
+
+                Ok(json)
+            }
+        });
+
+        let transport = Transport::new(io, access, subscriptions, metrics, requests);
+        let (listener, endpoint) = match endpoint {
+            Endpoint::Tcp(address) => {
+                let socket = tokio::net::TcpListener::bind(address)
+                    .await
+                    .with_context(|| format!("Unable to start RPC server on {}", address))?;
+                let endpoint = Endpoint::Tcp(socket.local_addr()?);
+                let incoming = futures::stream::unfold(socket, |socket| async move {
+                    let connection = socket.accept().await.map(|(stream, _)| stream);
+                    Some((connection, socket))
+                });
+                let listener = Listener {
+                    shutdown: transport.serve(incoming),
+                    socket: None,
+                };
+                (listener, endpoint)
+            }
+            Endpoint::Unix(path) => {
+                let listener = Listener {
+                    shutdown: serve_unix(transport, path)?,
+                    socket: Some(path.clone()),
+                };
+                (listener, endpoint.clone())
+            }
+        };
 
         Ok(RpcServer {
-            server: Arc::new(Mutex::new(Some(server))),
-            port,
+            server: Arc::new(Mutex::new(Some(listener))),
+            endpoint,
         })
     }
 
-    /// Get the port the server is listening on
+    /// Address the server is listening on
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Get the port the server is listening on, 0 on a unix socket
     pub fn port(&self) -> u16 {
-        self.port
+        match &self.endpoint {
+            Endpoint::Tcp(address) => address.port(),
+            Endpoint::Unix(_) => 0,
+        }
     }
 
     /// Shutdown the RPC server
     pub async fn shutdown(&self) -> Result<()> {
         let mut server_lock = self.server.lock().await;
-        if let Some(server) = server_lock.take() {
-            server.close();
+        if let Some(listener) = server_lock.take() {
+            listener.close();
         }
         Ok(())
     }
@@ -276,23 +628,191 @@ impl Drop for RpcServer {
         // Best effort cleanup - try to shut down the server
         if let Some(server) = Arc::get_mut(&mut self.server) {
             if let Ok(mut lock) = server.try_lock() {
-                if let Some(s) = lock.take() {
-                    s.close();
+                if let Some(listener) = lock.take() {
+                    listener.close();
                 }
             }
         }
     }
 }
 
+/// Serve on a unix socket until the returned sender fires
+#[cfg(unix)]
+fn serve_unix(transport: Arc<Transport>, path: &std::path::Path) -> Result<oneshot::Sender<()>> {
+    if path.exists() {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            anyhow::bail!("{} is used by another server", path.display());
+        }
+        // Left over by a server that did not shut down
+        std::fs::remove_file(path)?;
+    }
+    let socket = tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("Unable to start RPC server on {}", path.display()))?;
+    let incoming = futures::stream::unfold(socket, |socket| async move {
+        let connection = socket.accept().await.map(|(stream, _)| stream);
+        Some((connection, socket))
+    });
+    Ok(transport.serve(incoming))
+}
+
+#[cfg(not(unix))]
+fn serve_unix(_transport: Arc<Transport>, _path: &std::path::Path) -> Result<oneshot::Sender<()>> {
+    anyhow::bail!("Unix sockets are not supported on this platform")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[tokio::test]
-    #[ignore] // Skip: Runtime drop issue in nested async context
     async fn test_server_start_and_shutdown() {
-        let server = RpcServer::start("testnet".to_string()).await.unwrap();
+        let networks = NetworkPolicy::new("testnet", &[]).unwrap();
+        let endpoint = Endpoint::Tcp("127.0.0.1:0".parse().unwrap());
+        let server = RpcServer::start(
+            networks,
+            Access::new(&[]),
+            Metrics::default(),
+            Requests::default(),
+            &endpoint,
+        )
+        .await
+        .unwrap();
         assert!(server.port() > 0);
         server.shutdown().await.unwrap();
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_unix_socket() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let path = std::env::temp_dir().join(format!("glin-forge-rpc-{}.sock", std::process::id()));
+        let networks = NetworkPolicy::new("testnet", &[]).unwrap();
+        let access = Access::with_token("secret".to_string(), &[]);
+        let server = RpcServer::start(
+            networks,
+            access,
+            Metrics::default(),
+            Requests::default(),
+            &Endpoint::Unix(path.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(server.port(), 0);
+
+        let post = |authorization: &'static str| {
+            let path = path.clone();
+            async move {
+                let body = r#"{"jsonrpc":"2.0","id":1,"method":"nothing","params":[]}"#;
+                let request = format!(
+                    "POST / HTTP/1.1\r\nhost: localhost\r\n{}content-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    authorization,
+                    body.len(),
+                    body
+                );
+                let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+                stream.write_all(request.as_bytes()).await.unwrap();
+                let mut response = String::new();
+                stream.read_to_string(&mut response).await.unwrap();
+                response
+            }
+        };
+        assert!(post("").await.starts_with("HTTP/1.1 401"));
+        let response = post("authorization: Bearer secret\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("Method not found"));
+
+        server.shutdown().await.unwrap();
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_websocket() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio_util::compat::TokioAsyncReadCompatExt;
+
+        let networks = NetworkPolicy::new("testnet", &[]).unwrap();
+        let access = Access::with_token("secret".to_string(), &[]);
+        let endpoint = Endpoint::Tcp("127.0.0.1:0".parse().unwrap());
+        let server = RpcServer::start(
+            networks,
+            access,
+            Metrics::default(),
+            Requests::default(),
+            &endpoint,
+        )
+        .await
+        .unwrap();
+
+        let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", server.port()))
+            .await
+            .unwrap();
+        let handshake = "GET /?token=secret HTTP/1.1\r\nhost: localhost\r\nupgrade: websocket\r\nconnection: Upgrade\r\nsec-websocket-key: dGhlIHNhbXBsZSBub25jZQ==\r\nsec-websocket-version: 13\r\n\r\n";
+        stream.write_all(handshake.as_bytes()).await.unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap();
+        assert!(head.starts_with("HTTP/1.1 101"), "{}", head);
+        assert!(head.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        let (mut sender, mut receiver) =
+            soketto::connection::Builder::new(stream.compat(), soketto::connection::Mode::Client)
+                .finish();
+        sender
+            .send_text(r#"{"jsonrpc":"2.0","id":1,"method":"nothing","params":[]}"#)
+            .await
+            .unwrap();
+        sender.flush().await.unwrap();
+        let mut message = Vec::new();
+        receiver.receive_data(&mut message).await.unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&message).unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["error"]["message"], "Method not found");
+
+        server.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let send = |port: u16, request: String| async move {
+            let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port))
+                .await
+                .unwrap();
+            stream.write_all(request.as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+        let get_metrics = "GET /metrics HTTP/1.1\r\nhost: localhost\r\nauthorization: Bearer secret\r\nconnection: close\r\n\r\n";
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"nothing","params":[]}"#;
+        let post = format!(
+            "POST / HTTP/1.1\r\nhost: localhost\r\nauthorization: Bearer secret\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let endpoint = Endpoint::Tcp("127.0.0.1:0".parse().unwrap());
+        let start = |metrics| {
+            let networks = NetworkPolicy::new("testnet", &[]).unwrap();
+            let access = Access::with_token("secret".to_string(), &[]);
+            RpcServer::start(networks, access, metrics, Requests::default(), &endpoint)
+        };
+
+        let server = start(Metrics::new(true, false)).await.unwrap();
+        send(server.port(), post).await;
+        let response = send(server.port(), get_metrics.to_string()).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response
+            .contains("glin_forge_rpc_requests_total{method=\"unknown\",status=\"error\"} 1"));
+        server.shutdown().await.unwrap();
+
+        // Not served unless enabled
+        let server = start(Metrics::default()).await.unwrap();
+        let response = send(server.port(), get_metrics.to_string()).await;
+        assert!(response.starts_with("HTTP/1.1 405"), "{}", response);
+        server.shutdown().await.unwrap();
+    }
 }
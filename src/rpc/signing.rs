@@ -0,0 +1,174 @@
+// Transactions signed outside the server
+//
+// `signRaw` builds a deploy, upload, instantiate or call transaction for an
+// address the server holds no key for and returns the exact bytes to sign
+// (already hashed when longer than 256 bytes, as the chain expects). A wallet
+// (browser extension, hardware wallet) signs them, and `submitSigned` attaches
+// the signature, submits the transaction and reports its outcome. Prepared
+// transactions wait `PENDING_TTL` for their signature.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use subxt::tx::PartialTransaction;
+use subxt::utils::{AccountId32, MultiSignature};
+
+use glin_client::{GlinClient, GlinConfig};
+
+use crate::rpc::errors::{tagged, ErrorKind};
+
+/// Time a prepared transaction waits for its signature
+pub const PENDING_TTL: Duration = Duration::from_secs(600);
+
+/// What a prepared transaction does, deciding how its outcome is read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Kind {
+    Deploy,
+    UploadCode,
+    /// Instantiation of the code with this hash
+    Instantiate(String),
+    Call,
+}
+
+/// Transaction waiting for an external signature
+pub struct Pending {
+    pub client: GlinClient,
+    pub transaction: PartialTransaction<GlinConfig, GlinClient>,
+    pub signer: AccountId32,
+    pub kind: Kind,
+    prepared: Instant,
+}
+
+impl Pending {
+    pub fn new(
+        client: GlinClient,
+        transaction: PartialTransaction<GlinConfig, GlinClient>,
+        signer: AccountId32,
+        kind: Kind,
+    ) -> Self {
+        Self {
+            client,
+            transaction,
+            signer,
+            kind,
+            prepared: Instant::now(),
+        }
+    }
+}
+
+fn pending() -> &'static Mutex<HashMap<String, Pending>> {
+    static PENDING: OnceLock<Mutex<HashMap<String, Pending>>> = OnceLock::new();
+    PENDING.get_or_init(Default::default)
+}
+
+/// Keep a prepared transaction until its signature arrives; returns its id
+pub fn insert(transaction: Pending) -> String {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    let id = format!("tx-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let mut pending = pending().lock().unwrap();
+    pending.retain(|_, p| p.prepared.elapsed() < PENDING_TTL);
+    pending.insert(id.clone(), transaction);
+    id
+}
+
+/// Take a prepared transaction to submit it
+pub fn take(id: &str) -> Result<Pending> {
+    let transaction = pending().lock().unwrap().remove(id).ok_or_else(|| {
+        tagged(
+            ErrorKind::NotFound,
+            format!("No prepared transaction '{}'", id),
+        )
+    })?;
+    if transaction.prepared.elapsed() >= PENDING_TTL {
+        return Err(tagged(
+            ErrorKind::NotFound,
+            format!(
+                "Prepared transaction '{}' expired; prepare it again with signRaw",
+                id
+            ),
+        ));
+    }
+    Ok(transaction)
+}
+
+/// Signature from hex: a raw 64 byte sr25519 or ed25519 signature (per `scheme`, sr25519 by
+/// default), a raw 65 byte ecdsa one, or a SCALE-encoded `MultiSignature` as polkadot-js
+/// `signPayload` returns
+pub fn parse_signature(signature: &str, scheme: Option<&str>) -> Result<MultiSignature> {
+    decode_signature(signature, scheme).map_err(|e| tagged(ErrorKind::Account, e.to_string()))
+}
+
+fn decode_signature(signature: &str, scheme: Option<&str>) -> Result<MultiSignature> {
+    let bytes = hex::decode(signature.trim_start_matches("0x")).context("Invalid signature hex")?;
+    let array = |bytes: &[u8]| -> Result<[u8; 64]> {
+        bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Signature must be 64 bytes"))
+    };
+
+    match (bytes.len(), scheme.unwrap_or("sr25519")) {
+        (64, "sr25519") => Ok(MultiSignature::Sr25519(array(&bytes)?)),
+        (64, "ed25519") => Ok(MultiSignature::Ed25519(array(&bytes)?)),
+        (65, "ecdsa") => Ok(MultiSignature::Ecdsa(bytes.try_into().expect("65 bytes"))),
+        (65, _) if bytes[0] == 0 => Ok(MultiSignature::Ed25519(array(&bytes[1..])?)),
+        (65, _) if bytes[0] == 1 => Ok(MultiSignature::Sr25519(array(&bytes[1..])?)),
+        (66, _) if bytes[0] == 2 => Ok(MultiSignature::Ecdsa(
+            bytes[1..].try_into().expect("65 bytes"),
+        )),
+        (_, scheme @ ("sr25519" | "ed25519" | "ecdsa")) => {
+            anyhow::bail!("Unexpected {} byte signature for {}", bytes.len(), scheme)
+        }
+        (_, scheme) => anyhow::bail!(
+            "Unknown signature scheme '{}': use sr25519, ed25519 or ecdsa",
+            scheme
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signature() {
+        let raw = format!("0x{}", "ab".repeat(64));
+        assert!(matches!(
+            parse_signature(&raw, None).unwrap(),
+            MultiSignature::Sr25519(bytes) if bytes == [0xab; 64]
+        ));
+        assert!(matches!(
+            parse_signature(&raw, Some("ed25519")).unwrap(),
+            MultiSignature::Ed25519(_)
+        ));
+        assert!(matches!(
+            parse_signature(&"cd".repeat(65), Some("ecdsa")).unwrap(),
+            MultiSignature::Ecdsa(bytes) if bytes == [0xcd; 65]
+        ));
+
+        // SCALE-encoded MultiSignature, as polkadot-js signPayload returns
+        assert!(matches!(
+            parse_signature(&format!("0x01{}", "ab".repeat(64)), None).unwrap(),
+            MultiSignature::Sr25519(bytes) if bytes == [0xab; 64]
+        ));
+        assert!(matches!(
+            parse_signature(&format!("0x00{}", "ab".repeat(64)), None).unwrap(),
+            MultiSignature::Ed25519(_)
+        ));
+        assert!(matches!(
+            parse_signature(&format!("0x02{}", "cd".repeat(65)), None).unwrap(),
+            MultiSignature::Ecdsa(_)
+        ));
+
+        assert!(parse_signature("0x1234", None).is_err());
+        assert!(parse_signature(&raw, Some("rsa")).is_err());
+        assert!(parse_signature("zz", None).is_err());
+    }
+
+    #[test]
+    fn test_take_unknown() {
+        let error = take("tx-unknown").err().unwrap();
+        assert!(error.to_string().contains("No prepared transaction"));
+    }
+}
@@ -0,0 +1,223 @@
+// Raw storage of contracts
+//
+// A contract keeps its storage in a child trie of its own. `read` asks the
+// runtime for one value through `ContractsApi_get_storage`, with the key as the
+// contract uses it (an ink! contract's root is at `0x00000000`); the runtime
+// hashes it. `items` walks the child trie, whose keys are
+// `blake2_128(key) ++ key`, and matches the prefix against the unhashed key.
+
+use anyhow::{Context, Result};
+use scale::{Decode, Encode};
+use sp_core_hashing::{blake2_128, twox_128, twox_64};
+use subxt::backend::legacy::LegacyRpcMethods;
+use subxt::backend::rpc::RpcClient;
+use subxt::ext::subxt_rpcs::rpc_params;
+use subxt::utils::AccountId32;
+
+use glin_client::GlinConfig;
+
+use crate::rpc::errors::{tagged, ErrorKind};
+
+/// Items returned by one `items` call at most
+pub const MAX_ITEMS: u32 = 1000;
+
+/// Child trie keys fetched per node request
+const PAGE: u32 = 1000;
+
+/// A value in a contract's child trie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageItem {
+    /// Key as the contract uses it; None when the trie key does not carry it
+    pub key: Option<Vec<u8>>,
+    pub trie_key: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+/// Value under a contract storage key, None when nothing is stored there
+pub async fn read(rpc: &RpcClient, contract: &AccountId32, key: &[u8]) -> Result<Option<Vec<u8>>> {
+    let methods = LegacyRpcMethods::<GlinConfig>::new(rpc.clone());
+    let bytes = methods
+        .state_call(
+            "ContractsApi_get_storage",
+            Some(&(contract.0, key.to_vec()).encode()),
+            None,
+        )
+        .await
+        .context("Contract storage RPC call failed")?;
+    decode_get_storage(&bytes, contract)
+}
+
+/// Decode `Result<Option<Vec<u8>>, ContractAccessError>` as `ContractsApi_get_storage` returns
+fn decode_get_storage(bytes: &[u8], contract: &AccountId32) -> Result<Option<Vec<u8>>> {
+    let result = Result::<Option<Vec<u8>>, u8>::decode(&mut &bytes[..])
+        .context("Failed to decode ContractsApi_get_storage result")?;
+    match result {
+        Ok(value) => Ok(value),
+        Err(0) => Err(tagged(
+            ErrorKind::NotFound,
+            format!("Contract {} does not exist", contract),
+        )),
+        Err(1) => Err(tagged(
+            ErrorKind::Decode,
+            "The runtime could not decode the storage key",
+        )),
+        Err(2) => anyhow::bail!("A storage migration is in progress; try again later"),
+        Err(code) => anyhow::bail!("Contract storage access failed with error {}", code),
+    }
+}
+
+/// Up to `count` items whose unhashed key starts with `prefix`, after the trie key `start`;
+/// also returns the trie key to continue from when more may follow
+pub async fn items(
+    rpc: &RpcClient,
+    contract: &AccountId32,
+    prefix: &[u8],
+    count: u32,
+    start: Option<Vec<u8>>,
+) -> Result<(Vec<StorageItem>, Option<Vec<u8>>)> {
+    if count == 0 || count > MAX_ITEMS {
+        anyhow::bail!("count must be between 1 and {}", MAX_ITEMS);
+    }
+    let child = child_storage_key(&trie_id(rpc, contract).await?);
+
+    let mut keys = Vec::new();
+    let mut next = start;
+    'pages: loop {
+        let page: Vec<String> = rpc
+            .request(
+                "childstate_getKeysPaged",
+                rpc_params![
+                    to_hex(&child),
+                    Option::<String>::None,
+                    PAGE,
+                    next.as_deref().map(to_hex),
+                    Option::<String>::None
+                ],
+            )
+            .await
+            .context("Failed to list contract storage keys")?;
+        let full = page.len() as u32 == PAGE;
+
+        for key in page {
+            let key = from_hex(&key)?;
+            next = Some(key.clone());
+            if prefix.is_empty() || unhashed_key(&key).is_some_and(|k| k.starts_with(prefix)) {
+                keys.push(key);
+                if keys.len() as u32 == count {
+                    break 'pages;
+                }
+            }
+        }
+        if !full {
+            next = None;
+            break;
+        }
+    }
+    if keys.is_empty() {
+        return Ok((Vec::new(), next));
+    }
+
+    let hex_keys: Vec<String> = keys.iter().map(|key| to_hex(key)).collect();
+    let values: Vec<Option<String>> = rpc
+        .request(
+            "childstate_getStorageEntries",
+            rpc_params![to_hex(&child), hex_keys, Option::<String>::None],
+        )
+        .await
+        .context("Failed to read contract storage")?;
+
+    let mut items = Vec::with_capacity(keys.len());
+    for (trie_key, value) in keys.into_iter().zip(values) {
+        // Keys removed between the two requests are left out
+        if let Some(value) = value {
+            items.push(StorageItem {
+                key: unhashed_key(&trie_key).map(<[u8]>::to_vec),
+                value: from_hex(&value)?,
+                trie_key,
+            });
+        }
+    }
+    Ok((items, next))
+}
+
+/// Id of a contract's child trie, the first field of its `Contracts.ContractInfoOf`
+async fn trie_id(rpc: &RpcClient, contract: &AccountId32) -> Result<Vec<u8>> {
+    let key = [
+        &twox_128(b"Contracts")[..],
+        &twox_128(b"ContractInfoOf"),
+        &twox_64(&contract.0),
+        &contract.0,
+    ]
+    .concat();
+    let info = LegacyRpcMethods::<GlinConfig>::new(rpc.clone())
+        .state_get_storage(&key, None)
+        .await
+        .context("Failed to read the contract info")?
+        .ok_or_else(|| {
+            tagged(
+                ErrorKind::NotFound,
+                format!("Contract {} does not exist", contract),
+            )
+        })?;
+    Vec::<u8>::decode(&mut &info[..]).context("Failed to decode the contract info")
+}
+
+fn child_storage_key(trie_id: &[u8]) -> Vec<u8> {
+    [b":child_storage:default:".as_slice(), trie_id].concat()
+}
+
+/// Key a `blake2_128(key) ++ key` trie key was made from
+fn unhashed_key(trie_key: &[u8]) -> Option<&[u8]> {
+    let (hash, key) = trie_key.split_at_checked(16)?;
+    (!key.is_empty() && blake2_128(key) == hash).then_some(key)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn from_hex(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x")).context("Invalid hex from the node")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_get_storage() {
+        let contract = AccountId32([7; 32]);
+        let stored = Ok::<_, u8>(Some(vec![1u8, 0, 0, 0])).encode();
+        assert_eq!(
+            decode_get_storage(&stored, &contract).unwrap(),
+            Some(vec![1, 0, 0, 0])
+        );
+        let empty = Ok::<Option<Vec<u8>>, u8>(None).encode();
+        assert_eq!(decode_get_storage(&empty, &contract).unwrap(), None);
+
+        let missing = Err::<Option<Vec<u8>>, u8>(0).encode();
+        let error = decode_get_storage(&missing, &contract).unwrap_err();
+        assert!(error.to_string().contains("does not exist"));
+        assert_eq!(
+            crate::rpc::errors::to_rpc_error(&error).code,
+            jsonrpc_core::ErrorCode::ServerError(-32007)
+        );
+        assert!(decode_get_storage(&[0x02], &contract).is_err());
+    }
+
+    #[test]
+    fn test_unhashed_key() {
+        let root = [0u8, 0, 0, 0];
+        let trie_key = [&blake2_128(&root)[..], &root].concat();
+        assert_eq!(unhashed_key(&trie_key), Some(&root[..]));
+
+        // Fixed 32 byte keys are hashed whole
+        assert_eq!(unhashed_key(&[9; 32]), None);
+        assert_eq!(unhashed_key(&blake2_128(&[])), None);
+
+        assert_eq!(
+            child_storage_key(&[0xab]),
+            b":child_storage:default:\xab".to_vec()
+        );
+    }
+}
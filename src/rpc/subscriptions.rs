@@ -0,0 +1,271 @@
+// Live event subscriptions streamed as Server-Sent Events
+//
+// `subscribeEvents` starts following finalized blocks for a contract and returns
+// a subscription id. `GET /subscriptions/<id>` on the RPC port then streams each
+// matching event as an SSE `data:` line until the client disconnects or calls
+// `unsubscribeEvents`. WebSocket clients get the events as notifications on
+// their connection instead.
+
+use anyhow::{Context, Result};
+use jsonrpc_http_server::hyper::{Body, Method, Request, Response, StatusCode};
+use jsonrpc_http_server::RequestMiddlewareAction;
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::cli::watch::{decode_emitted, event_contract};
+use crate::contract::events::EventDecoder;
+use crate::rpc::types::SubscribeEventsParams;
+
+/// Events buffered per subscription before the follower waits for the client
+const BUFFER: usize = 256;
+
+/// Path prefix of the event streams
+const STREAM_PATH: &str = "/subscriptions/";
+
+/// Open event subscriptions
+#[derive(Default)]
+pub struct Subscriptions {
+    active: Mutex<HashMap<String, Subscription>>,
+    next_id: AtomicU64,
+}
+
+/// What a subscription delivers
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamItem {
+    /// Event as the SDK's `ContractEvent`
+    Event(JsonValue),
+    /// The subscription failed and ends
+    Error(String),
+}
+
+impl StreamItem {
+    /// Server-Sent Event carrying the item
+    fn to_sse(&self) -> String {
+        match self {
+            StreamItem::Event(event) => format!("data: {}\n\n", event),
+            StreamItem::Error(message) => {
+                format!("event: error\ndata: {}\n\n", json!({ "message": message }))
+            }
+        }
+    }
+}
+
+struct Subscription {
+    /// Taken by the stream request
+    events: Option<mpsc::Receiver<StreamItem>>,
+    follower: JoinHandle<()>,
+}
+
+impl Subscriptions {
+    /// Start following a contract's events; returns the subscription id
+    pub fn subscribe(&self, params: SubscribeEventsParams) -> Result<String> {
+        let decoder = params
+            .metadata
+            .as_deref()
+            .map(EventDecoder::load)
+            .transpose()?;
+        let contract = crate::contract::parse_account_id(&params.address)?;
+
+        let id = format!("sub-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let (sender, receiver) = mpsc::channel(BUFFER);
+        let follower = tokio::spawn(async move {
+            if let Err(e) = follow(&params, &contract, decoder.as_ref(), &sender).await {
+                let _ = sender.send(StreamItem::Error(format!("{:#}", e))).await;
+            }
+        });
+
+        self.active.lock().unwrap().insert(
+            id.clone(),
+            Subscription {
+                events: Some(receiver),
+                follower,
+            },
+        );
+        Ok(id)
+    }
+
+    /// Stop a subscription, ending its stream; false when it does not exist
+    pub fn unsubscribe(&self, id: &str) -> bool {
+        match self.active.lock().unwrap().remove(id) {
+            Some(subscription) => {
+                subscription.follower.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take the events of a subscription, once, to deliver them elsewhere than its stream
+    pub fn take_events(&self, id: &str) -> Option<mpsc::Receiver<StreamItem>> {
+        self.active
+            .lock()
+            .unwrap()
+            .get_mut(id)
+            .and_then(|subscription| subscription.events.take())
+    }
+
+    /// Serve `GET /subscriptions/<id>`, passing every other request to JSON-RPC
+    pub fn handle(self: &Arc<Self>, request: Request<Body>) -> RequestMiddlewareAction {
+        let id = match request.uri().path().strip_prefix(STREAM_PATH) {
+            Some(id) if request.method() == Method::GET => id.to_string(),
+            _ => {
+                return RequestMiddlewareAction::Proceed {
+                    should_continue_on_invalid_cors: false,
+                    request,
+                }
+            }
+        };
+
+        let Some(events) = self.take_events(&id) else {
+            return Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::from(format!(
+                    "Unknown or already streamed subscription: {}",
+                    id
+                )))
+                .expect("valid response")
+                .into();
+        };
+
+        // The stream ends when the follower stops; a disconnect stops the follower
+        let subscriptions = self.clone();
+        let stream = futures::stream::unfold(events, move |mut events| {
+            let subscriptions = subscriptions.clone();
+            let id = id.clone();
+            async move {
+                match events.recv().await {
+                    Some(item) => Some((Ok::<_, std::convert::Infallible>(item.to_sse()), events)),
+                    None => {
+                        subscriptions.unsubscribe(&id);
+                        None
+                    }
+                }
+            }
+        });
+
+        Response::builder()
+            .header("content-type", "text/event-stream")
+            .header("cache-control", "no-cache")
+            .header("access-control-allow-origin", "*")
+            .body(Body::wrap_stream(stream))
+            .expect("valid response")
+            .into()
+    }
+}
+
+/// Forward the contract's finalized events until the stream is dropped
+async fn follow(
+    params: &SubscribeEventsParams,
+    contract: &subxt::utils::AccountId32,
+    decoder: Option<&EventDecoder>,
+    sender: &mpsc::Sender<StreamItem>,
+) -> Result<()> {
+    let network_config = crate::config::load_network(&params.network)?;
+    let client = crate::rpc::pool::client(&network_config.rpc).await?;
+    let mut blocks_sub = client.blocks().subscribe_finalized().await?;
+
+    while let Some(block_result) = blocks_sub.next().await {
+        let block = block_result?;
+        let block_number = block.number() as u64;
+
+        for event in block.events().await?.iter() {
+            let event = event?;
+            if event.pallet_name() != "Contracts"
+                || event_contract(&event).as_ref() != Some(contract)
+            {
+                continue;
+            }
+            let decoded = decode_emitted(&event, decoder, Some(contract));
+            let message = stream_event(block_number, &event, decoded.as_ref())?;
+            if let Some(filter) = &params.event {
+                if message["eventName"] != *filter && event.variant_name() != filter {
+                    continue;
+                }
+            }
+            if sender.send(StreamItem::Event(message)).await.is_err() {
+                // Client gone
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Event as the SDK's `ContractEvent`, decoded when metadata was given
+fn stream_event<T: subxt::Config>(
+    block_number: u64,
+    event: &subxt::events::EventDetails<T>,
+    decoded: Option<&Result<crate::contract::events::DecodedEvent>>,
+) -> Result<JsonValue> {
+    Ok(match decoded {
+        Some(Ok(decoded)) => json!({
+            "blockNumber": block_number,
+            "eventName": decoded.name,
+            "data": decoded.to_json()["fields"].take(),
+        }),
+        _ => json!({
+            "blockNumber": block_number,
+            "eventName": event.variant_name(),
+            "data": serde_json::to_value(event.field_values()?).unwrap_or(JsonValue::Null),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stream_routing() {
+        let subscriptions = Arc::new(Subscriptions::default());
+        let request = |method: Method, path: &str| {
+            Request::builder()
+                .method(method)
+                .uri(path)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // JSON-RPC requests pass through
+        assert!(matches!(
+            subscriptions.handle(request(Method::POST, "/")),
+            RequestMiddlewareAction::Proceed { .. }
+        ));
+
+        let (sender, receiver) = mpsc::channel(1);
+        subscriptions.active.lock().unwrap().insert(
+            "sub-1".to_string(),
+            Subscription {
+                events: Some(receiver),
+                follower: tokio::spawn(async {}),
+            },
+        );
+        sender.send(StreamItem::Event(json!({}))).await.unwrap();
+        drop(sender);
+
+        let RequestMiddlewareAction::Respond { response, .. } =
+            subscriptions.handle(request(Method::GET, "/subscriptions/sub-1"))
+        else {
+            panic!("stream not served");
+        };
+        let response = response.await.unwrap();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        let body = jsonrpc_http_server::hyper::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        assert_eq!(body, "data: {}\n\n");
+        // The ended stream removed its subscription
+        assert!(!subscriptions.unsubscribe("sub-1"));
+
+        let RequestMiddlewareAction::Respond { response, .. } =
+            subscriptions.handle(request(Method::GET, "/subscriptions/sub-9"))
+        else {
+            panic!("unknown subscription not answered");
+        };
+        assert_eq!(response.await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+}
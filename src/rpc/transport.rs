@@ -0,0 +1,256 @@
+// HTTP and WebSocket transport of the RPC server
+//
+// One hyper server answers on a TCP address or a unix socket: JSON-RPC over
+// `POST /`, event streams on `GET /subscriptions/<id>`, and JSON-RPC over a
+// WebSocket when a GET asks to upgrade, and metrics on `GET /metrics` when
+// enabled. Every request first goes through the session's `Access`; responses
+// to allowed browser origins carry CORS headers.
+
+use anyhow::Result;
+use futures::Stream;
+use jsonrpc_core::IoHandler;
+use jsonrpc_http_server::hyper::header::{self, HeaderValue};
+use jsonrpc_http_server::hyper::service::{make_service_fn, service_fn};
+use jsonrpc_http_server::hyper::{self, Body, Method, Request, Response, StatusCode};
+use jsonrpc_http_server::RequestMiddlewareAction;
+use sha1::{Digest, Sha1};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::oneshot;
+
+use crate::rpc::auth::Access;
+use crate::rpc::batch;
+use crate::rpc::metrics::Metrics;
+use crate::rpc::requests::Requests;
+use crate::rpc::subscriptions::Subscriptions;
+use crate::rpc::websocket;
+
+/// GUID appended to a WebSocket key to accept it (RFC 6455)
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Handlers shared by every connection
+pub struct Transport {
+    io: Arc<IoHandler>,
+    access: Access,
+    subscriptions: Arc<Subscriptions>,
+    metrics: Arc<Metrics>,
+    requests: Arc<Requests>,
+}
+
+impl Transport {
+    pub fn new(
+        io: IoHandler,
+        access: Access,
+        subscriptions: Arc<Subscriptions>,
+        metrics: Metrics,
+        requests: Arc<Requests>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            io: Arc::new(io),
+            access,
+            subscriptions,
+            metrics: Arc::new(metrics),
+            requests,
+        })
+    }
+
+    /// Serve the accepted connections until the returned sender fires
+    pub fn serve<I, S>(self: Arc<Self>, incoming: I) -> oneshot::Sender<()>
+    where
+        I: Stream<Item = std::io::Result<S>> + Send + 'static,
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let service = make_service_fn(move |_| {
+            let transport = self.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| transport.clone().handle(request)))
+            }
+        });
+
+        let (shutdown, stop) = oneshot::channel::<()>();
+        let server = hyper::Server::builder(hyper::server::accept::from_stream(incoming))
+            .serve(service)
+            .with_graceful_shutdown(async {
+                let _ = stop.await;
+            });
+        tokio::spawn(server);
+        shutdown
+    }
+
+    async fn handle(
+        self: Arc<Self>,
+        request: Request<Body>,
+    ) -> Result<Response<Body>, hyper::Error> {
+        if let Some(rejection) = self.access.check(&request) {
+            return Ok(rejection);
+        }
+        let origin = request.headers().get(header::ORIGIN).cloned();
+        let mut response = self.route(request).await?;
+        if let Some(origin) = origin {
+            let headers = response.headers_mut();
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+            headers.insert(header::VARY, HeaderValue::from_static("origin"));
+        }
+        Ok(response)
+    }
+
+    async fn route(&self, request: Request<Body>) -> Result<Response<Body>, hyper::Error> {
+        if request.method() == Method::OPTIONS {
+            return Ok(Response::builder()
+                .status(StatusCode::NO_CONTENT)
+                .header(header::ACCESS_CONTROL_ALLOW_METHODS, "POST, GET, OPTIONS")
+                .header(
+                    header::ACCESS_CONTROL_ALLOW_HEADERS,
+                    "content-type, authorization",
+                )
+                .header(header::ACCESS_CONTROL_MAX_AGE, "3600")
+                .body(Body::empty())
+                .expect("valid response"));
+        }
+        if is_websocket_upgrade(&request) {
+            return Ok(self.upgrade(request));
+        }
+        if self.metrics.exposed()
+            && request.method() == Method::GET
+            && request.uri().path() == "/metrics"
+        {
+            return Ok(Response::builder()
+                .header(header::CONTENT_TYPE, "text/plain; version=0.0.4")
+                .body(Body::from(self.metrics.render()))
+                .expect("valid response"));
+        }
+
+        let request = match self.subscriptions.handle(request) {
+            RequestMiddlewareAction::Respond { response, .. } => return response.await,
+            RequestMiddlewareAction::Proceed { request, .. } => request,
+        };
+        if request.method() != Method::POST {
+            return Ok(plain(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "JSON-RPC requests must be POSTed",
+            ));
+        }
+
+        let body = hyper::body::to_bytes(request.into_body()).await?;
+        let request = String::from_utf8_lossy(&body);
+        let response = batch::handle(&self.io, &self.metrics, &self.requests, None, &request)
+            .await
+            .unwrap_or_default();
+        Ok(Response::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(response))
+            .expect("valid response"))
+    }
+
+    /// Accept a WebSocket handshake and serve JSON-RPC on the upgraded connection
+    fn upgrade(&self, mut request: Request<Body>) -> Response<Body> {
+        let version = request.headers().get(header::SEC_WEBSOCKET_VERSION);
+        if version.map(|v| v.as_bytes()) != Some(b"13") {
+            return plain(StatusCode::BAD_REQUEST, "Unsupported WebSocket version");
+        }
+        let Some(key) = request.headers().get(header::SEC_WEBSOCKET_KEY) else {
+            return plain(StatusCode::BAD_REQUEST, "Missing Sec-WebSocket-Key");
+        };
+        let accept = accept_key(key.as_bytes());
+
+        let upgrading = hyper::upgrade::on(&mut request);
+        let (io, subscriptions, metrics, requests) = (
+            self.io.clone(),
+            self.subscriptions.clone(),
+            self.metrics.clone(),
+            self.requests.clone(),
+        );
+        tokio::spawn(async move {
+            if let Ok(upgraded) = upgrading.await {
+                websocket::serve(upgraded, io, subscriptions, metrics, requests).await;
+            }
+        });
+
+        Response::builder()
+            .status(StatusCode::SWITCHING_PROTOCOLS)
+            .header(header::UPGRADE, "websocket")
+            .header(header::CONNECTION, "upgrade")
+            .header(header::SEC_WEBSOCKET_ACCEPT, accept)
+            .body(Body::empty())
+            .expect("valid response")
+    }
+}
+
+fn is_websocket_upgrade(request: &Request<Body>) -> bool {
+    let header_has = |name: header::HeaderName, token: &str| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                value
+                    .split(',')
+                    .any(|part| part.trim().eq_ignore_ascii_case(token))
+            })
+            .unwrap_or(false)
+    };
+    request.method() == Method::GET
+        && header_has(header::UPGRADE, "websocket")
+        && header_has(header::CONNECTION, "upgrade")
+}
+
+/// `Sec-WebSocket-Accept` answering a `Sec-WebSocket-Key`
+fn accept_key(key: &[u8]) -> String {
+    use base64::Engine;
+    let mut sha1 = Sha1::new();
+    sha1.update(key);
+    sha1.update(WEBSOCKET_GUID.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(sha1.finalize())
+}
+
+fn plain(status: StatusCode, message: &str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::from(message.to_string()))
+        .expect("valid response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_websocket_handshake() {
+        // Example of RFC 6455, section 1.3
+        assert_eq!(
+            accept_key(b"dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+
+        let request = |method: Method, upgrade: &str, connection: &str| {
+            Request::builder()
+                .method(method)
+                .uri("/")
+                .header("upgrade", upgrade)
+                .header("connection", connection)
+                .body(Body::empty())
+                .unwrap()
+        };
+        assert!(is_websocket_upgrade(&request(
+            Method::GET,
+            "websocket",
+            "Upgrade"
+        )));
+        assert!(is_websocket_upgrade(&request(
+            Method::GET,
+            "WebSocket",
+            "keep-alive, Upgrade"
+        )));
+        assert!(!is_websocket_upgrade(&request(
+            Method::POST,
+            "websocket",
+            "upgrade"
+        )));
+        assert!(!is_websocket_upgrade(&request(
+            Method::GET,
+            "h2c",
+            "upgrade"
+        )));
+    }
+}
@@ -17,7 +17,8 @@ pub struct DeployParams {
     #[serde(default)]
     pub value: u128,
 
-    /// Network to deploy to (testnet, mainnet, local)
+    /// Network to deploy to (testnet, mainnet, local); the server default when empty
+    #[serde(default)]
     pub network: String,
 
     /// Account to deploy from (alice, bob, or custom)
@@ -43,6 +44,74 @@ pub struct DeployResult {
     pub error: Option<String>,
 }
 
+/// Parameters for uploading contract code
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UploadCodeParams {
+    /// Path to WASM file
+    pub wasm: String,
+
+    /// Network to upload to; the server default when empty
+    #[serde(default)]
+    pub network: String,
+
+    /// Account to upload from
+    pub account: String,
+}
+
+/// Result of uploading contract code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadCodeResult {
+    pub success: bool,
+    pub code_hash: Option<String>,
+    pub tx_hash: Option<String>,
+    pub block_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Parameters for instantiating uploaded code
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InstantiateParams {
+    /// Code hash returned by `uploadCode`
+    pub code_hash: String,
+
+    /// Path to metadata JSON file
+    pub metadata: String,
+
+    /// Constructor to call [default: `new`, or the first constructor]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constructor: Option<String>,
+
+    /// Constructor arguments
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Value to send with instantiation (in GLIN)
+    #[serde(default)]
+    pub value: u128,
+
+    /// Network to instantiate on; the server default when empty
+    #[serde(default)]
+    pub network: String,
+
+    /// Account to instantiate from
+    pub account: String,
+
+    /// Salt making the address unique: `0x`-prefixed hex or text [default: 32 zero bytes]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+}
+
+/// Result of instantiating uploaded code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateResult {
+    pub success: bool,
+    pub address: Option<String>,
+    pub code_hash: Option<String>,
+    pub tx_hash: Option<String>,
+    pub block_hash: Option<String>,
+    pub error: Option<String>,
+}
+
 /// Parameters for calling a contract method
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CallParams {
@@ -63,7 +132,8 @@ pub struct CallParams {
     #[serde(default)]
     pub value: u128,
 
-    /// Network
+    /// Network; the server default when empty
+    #[serde(default)]
     pub network: String,
 
     /// Calling account
@@ -99,7 +169,8 @@ pub struct QueryParams {
     #[serde(default)]
     pub args: Vec<String>,
 
-    /// Network
+    /// Network; the server default when empty
+    #[serde(default)]
     pub network: String,
 }
 
@@ -121,7 +192,8 @@ pub struct WatchParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub event: Option<String>,
 
-    /// Network
+    /// Network; the server default when empty
+    #[serde(default)]
     pub network: String,
 
     /// Follow mode (keep watching for new events)
@@ -153,10 +225,51 @@ pub struct ContractEvent {
     pub data: serde_json::Value,
 }
 
+/// Parameters for subscribing to live contract events
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscribeEventsParams {
+    /// Contract address to watch
+    pub address: String,
+
+    /// Event name filter (pallet variant or decoded event name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+
+    /// Network; the server default when empty
+    #[serde(default)]
+    pub network: String,
+
+    /// Contract metadata used to decode emitted events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+}
+
+/// Result of subscribing; events stream from `GET /subscriptions/<subscription_id>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribeEventsResult {
+    pub success: bool,
+    pub subscription_id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Parameters for ending a subscription
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UnsubscribeEventsParams {
+    pub subscription_id: String,
+}
+
+/// Result of ending a subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsubscribeEventsResult {
+    /// Whether the subscription was open
+    pub success: bool,
+}
+
 /// Parameters for getting balance
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GetBalanceParams {
     pub address: String,
+    #[serde(default)]
     pub network: String,
 }
 
@@ -172,6 +285,7 @@ pub struct GetBalanceResult {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RequestFaucetParams {
     pub address: String,
+    #[serde(default)]
     pub network: String,
 }
 
@@ -188,12 +302,15 @@ pub struct RequestFaucetResult {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct EstimateGasParams {
     pub address: String,
+    /// Path to metadata JSON file
+    pub metadata: String,
     pub method: String,
     #[serde(default)]
     pub args: Vec<String>,
     #[serde(default)]
     pub value: u128,
     pub from: String,
+    #[serde(default)]
     pub network: String,
 }
 
@@ -201,7 +318,13 @@ pub struct EstimateGasParams {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EstimateGasResult {
     pub success: bool,
+    /// Gas required, as refTime
     pub gas_limit: Option<u64>,
+    pub ref_time: Option<u64>,
+    pub proof_size: Option<u64>,
+    /// Storage deposit charged (positive) or refunded (negative), in plancks
+    pub storage_deposit: Option<String>,
+    /// Transaction fee at the required gas, when `from` is a dev account
     pub estimated_cost: Option<String>,
     pub error: Option<String>,
 }
@@ -209,6 +332,7 @@ pub struct EstimateGasResult {
 /// Parameters for getting block number
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GetBlockNumberParams {
+    #[serde(default)]
     pub network: String,
 }
 
@@ -223,6 +347,7 @@ pub struct GetBlockNumberResult {
 /// Parameters for getting network info
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GetNetworkInfoParams {
+    #[serde(default)]
     pub network: String,
 }
 
@@ -235,3 +360,371 @@ pub struct GetNetworkInfoResult {
     pub block_number: Option<u64>,
     pub error: Option<String>,
 }
+
+/// Parameters for checking the server's health
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct HealthParams {
+    #[serde(default)]
+    pub network: String,
+}
+
+/// Result of checking the server's health
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthResult {
+    /// Whether the server reached the network's node
+    pub success: bool,
+    /// glin-forge version of the server
+    pub version: String,
+    /// Methods the server supports, sorted
+    pub methods: Vec<String>,
+    pub network: String,
+    pub rpc: String,
+    /// Chain head, when the node was reached
+    pub block_number: Option<u64>,
+    pub block_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Parameters for generating TypeScript types
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TypegenParams {
+    /// Path to metadata JSON file [default: found in artifacts/ or target/ink]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+
+    /// Output directory [default: ./types]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    /// Framework bindings to generate alongside the types (react, angular)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework: Option<String>,
+
+    /// Build React hooks on TanStack Query
+    #[serde(default)]
+    pub react_query: bool,
+
+    /// Also write an in-memory mock client
+    #[serde(default)]
+    pub mock: bool,
+
+    /// Emit `bigint` for 64-bit and wider integers
+    #[serde(default)]
+    pub strict_bigint: bool,
+
+    /// Nominal `AccountId`, `Balance` and `Hash` types
+    #[serde(default)]
+    pub branded: bool,
+
+    /// Return the content of every generated file along with its path
+    #[serde(default)]
+    pub include_contents: bool,
+}
+
+/// A file written by type generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedFile {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// Result of generating TypeScript types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypegenResult {
+    pub success: bool,
+    pub contract_name: Option<String>,
+    /// Written files, the types module first
+    #[serde(default)]
+    pub files: Vec<GeneratedFile>,
+    pub error: Option<String>,
+}
+
+/// Parameters for unlocking a keystore account
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UnlockAccountParams {
+    pub account: String,
+    pub password: String,
+}
+
+/// Result of unlocking a keystore account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockAccountResult {
+    pub success: bool,
+    pub address: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Parameters for locking an unlocked account
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LockAccountParams {
+    pub account: String,
+}
+
+/// Result of locking an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockAccountResult {
+    /// Whether the account was unlocked
+    pub success: bool,
+}
+
+/// Parameters for preparing a transaction for an external signer: a method and its
+/// parameters, with the signer's address as `account`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "method", content = "params")]
+pub enum SignRawParams {
+    #[serde(rename = "deploy")]
+    Deploy(DeployParams),
+    #[serde(rename = "uploadCode")]
+    UploadCode(UploadCodeParams),
+    #[serde(rename = "instantiate")]
+    Instantiate(InstantiateParams),
+    #[serde(rename = "call")]
+    Call(CallParams),
+}
+
+/// Result of preparing a transaction for an external signer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignRawResult {
+    pub success: bool,
+    /// Id to submit the signature with
+    pub id: Option<String>,
+    /// Hex bytes to sign
+    pub payload: Option<String>,
+    /// Address that must sign
+    pub address: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Parameters for submitting an externally signed transaction
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubmitSignedParams {
+    /// Id `signRaw` returned
+    pub id: String,
+
+    /// Hex signature of the payload
+    pub signature: String,
+
+    /// Scheme of a raw signature: sr25519, ed25519 or ecdsa [default: sr25519]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_type: Option<String>,
+}
+
+/// Result of submitting an externally signed transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitSignedResult {
+    pub success: bool,
+    pub tx_hash: Option<String>,
+    pub block_hash: Option<String>,
+    /// Contract instantiated by a deploy or instantiate
+    pub address: Option<String>,
+    /// Code stored by a deploy or upload
+    pub code_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Parameters for looking up a recorded deployment
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GetDeploymentParams {
+    /// Contract name in the registry, or its address
+    pub name: String,
+    #[serde(default)]
+    pub network: String,
+}
+
+/// Result of looking up a recorded deployment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetDeploymentResult {
+    pub success: bool,
+    /// Name the deployment is recorded under
+    pub name: Option<String>,
+    /// None when nothing is recorded under the name on the network
+    pub deployment: Option<crate::config::deployments::DeploymentRecord>,
+    pub error: Option<String>,
+}
+
+/// Parameters for recording a deployment
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SaveDeploymentParams {
+    /// Name to record the contract under
+    pub name: String,
+    #[serde(flatten)]
+    pub deployment: crate::config::deployments::DeploymentRecord,
+    #[serde(default)]
+    pub network: String,
+}
+
+/// Result of recording a deployment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveDeploymentResult {
+    pub success: bool,
+    /// Deployment the name had before, now replaced
+    pub previous: Option<crate::config::deployments::DeploymentRecord>,
+    pub error: Option<String>,
+}
+
+/// Parameters for reading one value of a contract's storage
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GetStorageParams {
+    /// Contract address
+    pub address: String,
+    /// Hex storage key as the contract uses it, e.g. 0x00000000 for an ink! root
+    pub key: String,
+    #[serde(default)]
+    pub network: String,
+}
+
+/// Result of reading one value of a contract's storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetStorageResult {
+    pub success: bool,
+    /// Hex SCALE-encoded value; None when nothing is stored under the key
+    pub value: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Parameters for listing values of a contract's storage
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GetStorageItemsParams {
+    /// Contract address
+    pub address: String,
+    /// Hex prefix of the storage keys to list [default: all]
+    #[serde(default)]
+    pub prefix: String,
+    /// Items to return at most [default: 100]
+    #[serde(default = "default_storage_count")]
+    pub count: u32,
+    /// Trie key to continue after, the `next_key` of the previous page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_key: Option<String>,
+    #[serde(default)]
+    pub network: String,
+}
+
+fn default_storage_count() -> u32 {
+    100
+}
+
+/// A value of a contract's storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageItem {
+    /// Hex storage key as the contract uses it, when known
+    pub key: Option<String>,
+    /// Hex key in the contract's child trie
+    pub trie_key: String,
+    /// Hex SCALE-encoded value
+    pub value: String,
+}
+
+/// Result of listing values of a contract's storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetStorageItemsResult {
+    pub success: bool,
+    pub items: Vec<StorageItem>,
+    /// Trie key to pass as `start_key` for the next page; None after the last one
+    pub next_key: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Parameters for cancelling a running request
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CancelParams {
+    /// JSON-RPC id of the request
+    pub request_id: serde_json::Value,
+}
+
+/// Result of cancelling a running request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelResult {
+    /// Whether a request with the id was running
+    pub success: bool,
+}
+
+/// Parameters naming the network a request acts on
+pub trait NetworkParams {
+    fn network_mut(&mut self) -> &mut String;
+
+    /// Value the request's transaction transfers
+    fn value(&self) -> u128 {
+        0
+    }
+}
+
+macro_rules! network_params {
+    ($($params:ty),* $(,)?) => {
+        $(impl NetworkParams for $params {
+            fn network_mut(&mut self) -> &mut String {
+                &mut self.network
+            }
+        })*
+    };
+}
+
+/// Parameters of a transaction transferring `value`
+macro_rules! transfer_params {
+    ($($params:ty),* $(,)?) => {
+        $(impl NetworkParams for $params {
+            fn network_mut(&mut self) -> &mut String {
+                &mut self.network
+            }
+
+            fn value(&self) -> u128 {
+                self.value
+            }
+        })*
+    };
+}
+
+transfer_params!(DeployParams, InstantiateParams, CallParams);
+
+network_params!(
+    UploadCodeParams,
+    QueryParams,
+    WatchParams,
+    SubscribeEventsParams,
+    GetBalanceParams,
+    EstimateGasParams,
+    GetBlockNumberParams,
+    GetNetworkInfoParams,
+    HealthParams,
+    GetDeploymentParams,
+    SaveDeploymentParams,
+    GetStorageParams,
+    GetStorageItemsParams,
+);
+
+impl NetworkParams for RequestFaucetParams {
+    fn network_mut(&mut self) -> &mut String {
+        &mut self.network
+    }
+
+    /// What the network's faucet sends; 0 without a valid faucet, which the request
+    /// then fails on anyway
+    fn value(&self) -> u128 {
+        crate::rpc::faucet::config(&self.network)
+            .ok()
+            .flatten()
+            .and_then(|faucet| crate::rpc::faucet::amount(&faucet).ok())
+            .unwrap_or(0)
+    }
+}
+
+impl NetworkParams for SignRawParams {
+    fn network_mut(&mut self) -> &mut String {
+        match self {
+            SignRawParams::Deploy(params) => params.network_mut(),
+            SignRawParams::UploadCode(params) => params.network_mut(),
+            SignRawParams::Instantiate(params) => params.network_mut(),
+            SignRawParams::Call(params) => params.network_mut(),
+        }
+    }
+
+    fn value(&self) -> u128 {
+        match self {
+            SignRawParams::Deploy(params) => params.value(),
+            SignRawParams::UploadCode(params) => params.value(),
+            SignRawParams::Instantiate(params) => params.value(),
+            SignRawParams::Call(params) => params.value(),
+        }
+    }
+}
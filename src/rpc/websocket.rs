@@ -0,0 +1,207 @@
+// JSON-RPC over WebSocket
+//
+// Each text message is a JSON-RPC request, answered on the same connection.
+// Requests run concurrently, so responses may arrive out of order and are
+// matched by id. A `subscribeEvents` made over the connection delivers its
+// events as notifications instead of a Server-Sent Events stream:
+//
+//   {"jsonrpc":"2.0","method":"subscription","params":{"subscription":"sub-1","result":{...}}}
+//
+// with `error` in place of `result` when the subscription fails. Subscriptions
+// end with the connection. Calls submitting transactions also send `progress`
+// notifications as they go (see `progress`).
+
+use jsonrpc_core::IoHandler;
+use serde_json::{json, Value as JsonValue};
+use soketto::connection::{Builder, Mode};
+use soketto::Data;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+use tokio_util::compat::TokioAsyncReadCompatExt;
+
+use crate::rpc::batch;
+use crate::rpc::metrics::Metrics;
+use crate::rpc::requests::Requests;
+use crate::rpc::subscriptions::{StreamItem, Subscriptions};
+
+/// Messages queued for a slow client before requests wait for it
+const BUFFER: usize = 256;
+
+/// Method of the notifications sent to subscribers
+pub const NOTIFICATION_METHOD: &str = "subscription";
+
+/// Serve JSON-RPC on an upgraded connection until the client goes away
+pub async fn serve<S>(
+    socket: S,
+    io: Arc<IoHandler>,
+    subscriptions: Arc<Subscriptions>,
+    metrics: Arc<Metrics>,
+    requests: Arc<Requests>,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (mut sender, mut receiver) = Builder::new(socket.compat(), Mode::Server).finish();
+
+    // One writer, so responses and notifications never interleave
+    let (outgoing, mut queued) = mpsc::channel::<String>(BUFFER);
+    let writer = tokio::spawn(async move {
+        while let Some(message) = queued.recv().await {
+            if sender.send_text_owned(message).await.is_err() || sender.flush().await.is_err() {
+                break;
+            }
+        }
+        let _ = sender.close().await;
+    });
+
+    let owned = Arc::new(Mutex::new(Vec::<String>::new()));
+    let mut message = Vec::new();
+    loop {
+        message.clear();
+        match receiver.receive_data(&mut message).await {
+            Ok(Data::Text(_)) => {}
+            Ok(Data::Binary(_)) => continue,
+            Err(_) => break,
+        }
+        let request = String::from_utf8_lossy(&message).into_owned();
+        let (io, subscriptions, metrics, requests, outgoing, owned) = (
+            io.clone(),
+            subscriptions.clone(),
+            metrics.clone(),
+            requests.clone(),
+            outgoing.clone(),
+            owned.clone(),
+        );
+        tokio::spawn(async move {
+            let Some(response) =
+                batch::handle(&io, &metrics, &requests, Some(&outgoing), &request).await
+            else {
+                return;
+            };
+            let events = subscription_id(&request, &response).and_then(|id| {
+                let events = subscriptions.take_events(&id)?;
+                owned.lock().unwrap().push(id.clone());
+                Some((id, events))
+            });
+            if outgoing.send(response).await.is_err() {
+                return;
+            }
+            if let Some((id, events)) = events {
+                forward(&id, events, &outgoing).await;
+            }
+        });
+    }
+
+    for id in owned.lock().unwrap().drain(..) {
+        subscriptions.unsubscribe(&id);
+    }
+    writer.abort();
+}
+
+/// Subscription a successful `subscribeEvents` request opened
+fn subscription_id(request: &str, response: &str) -> Option<String> {
+    let request: JsonValue = serde_json::from_str(request).ok()?;
+    if request["method"] != "subscribeEvents" {
+        return None;
+    }
+    let response: JsonValue = serde_json::from_str(response).ok()?;
+    response["result"]["subscription_id"]
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Send a subscription's events as notifications until it ends
+async fn forward(
+    id: &str,
+    mut events: mpsc::Receiver<StreamItem>,
+    outgoing: &mpsc::Sender<String>,
+) {
+    while let Some(item) = events.recv().await {
+        if outgoing.send(notification(id, &item)).await.is_err() {
+            break;
+        }
+    }
+}
+
+fn notification(id: &str, item: &StreamItem) -> String {
+    let params = match item {
+        StreamItem::Event(event) => json!({ "subscription": id, "result": event }),
+        StreamItem::Error(message) => {
+            json!({ "subscription": id, "error": { "message": message } })
+        }
+    };
+    json!({ "jsonrpc": "2.0", "method": NOTIFICATION_METHOD, "params": params }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc_core::Value;
+
+    #[tokio::test]
+    async fn test_websocket_requests() {
+        let mut io = IoHandler::new();
+        io.add_sync_method("echo", |params: jsonrpc_core::Params| {
+            params.parse::<Value>()
+        });
+        let (server, client) = tokio::io::duplex(4096);
+        tokio::spawn(serve(
+            server,
+            Arc::new(io),
+            Arc::new(Subscriptions::default()),
+            Arc::new(Metrics::default()),
+            Arc::new(Requests::default()),
+        ));
+
+        let (mut sender, mut receiver) = Builder::new(client.compat(), Mode::Client).finish();
+        sender
+            .send_text(r#"{"jsonrpc":"2.0","id":7,"method":"echo","params":["hi"]}"#)
+            .await
+            .unwrap();
+        sender.flush().await.unwrap();
+        let mut message = Vec::new();
+        receiver.receive_data(&mut message).await.unwrap();
+        let response: JsonValue = serde_json::from_slice(&message).unwrap();
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["result"], json!(["hi"]));
+    }
+
+    #[tokio::test]
+    async fn test_subscription_notifications() {
+        assert_eq!(
+            subscription_id(
+                r#"{"jsonrpc":"2.0","id":1,"method":"subscribeEvents","params":[{}]}"#,
+                r#"{"jsonrpc":"2.0","id":1,"result":{"success":true,"subscription_id":"sub-3"}}"#
+            )
+            .as_deref(),
+            Some("sub-3")
+        );
+        assert_eq!(
+            subscription_id(
+                r#"{"jsonrpc":"2.0","id":1,"method":"getBalance","params":[{}]}"#,
+                r#"{"jsonrpc":"2.0","id":1,"result":{"subscription_id":"sub-3"}}"#
+            ),
+            None
+        );
+
+        let (events, receiver) = mpsc::channel(4);
+        let (outgoing, mut sent) = mpsc::channel(4);
+        events
+            .send(StreamItem::Event(json!({ "eventName": "Flipped" })))
+            .await
+            .unwrap();
+        events
+            .send(StreamItem::Error("connection lost".to_string()))
+            .await
+            .unwrap();
+        drop(events);
+        forward("sub-3", receiver, &outgoing).await;
+
+        let first: JsonValue = serde_json::from_str(&sent.recv().await.unwrap()).unwrap();
+        assert_eq!(first["method"], "subscription");
+        assert_eq!(first["params"]["subscription"], "sub-3");
+        assert_eq!(first["params"]["result"]["eventName"], "Flipped");
+        let second: JsonValue = serde_json::from_str(&sent.recv().await.unwrap()).unwrap();
+        assert_eq!(second["params"]["error"]["message"], "connection lost");
+    }
+}
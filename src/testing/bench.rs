@@ -0,0 +1,350 @@
+// Message weight benchmarks
+//
+// Each message is dry-run against a deployed contract and the reported
+// `gas_consumed` is collected. Argument sets come from a bench file:
+//
+//   iterations: 10
+//   from: alice
+//   messages:
+//     transfer:
+//       - ["$bob", "100"]
+//       - ["$charlie", "1000000"]
+//     total_supply: [[]]
+//
+// Messages without arguments are benchmarked automatically when they are not
+// listed. Arguments starting with `$` are replaced by a dev account address.
+
+use anyhow::{Context, Result};
+use ink_metadata::InkProject;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use subxt::utils::AccountId32;
+
+use crate::contract::{self, Weight};
+
+/// Bench file describing which messages to run and with which arguments
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BenchFile {
+    #[serde(default)]
+    pub iterations: Option<u32>,
+
+    /// Dev account used as the caller
+    #[serde(default)]
+    pub from: Option<String>,
+
+    /// Argument sets per message
+    #[serde(default)]
+    pub messages: BTreeMap<String, Vec<Vec<String>>>,
+}
+
+impl BenchFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read bench file: {}", path.display()))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse bench file: {}", path.display()))
+    }
+}
+
+/// A single message/argument combination to benchmark
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchCase {
+    pub message: String,
+    pub args: Vec<String>,
+}
+
+impl BenchCase {
+    /// Identifier used to match results against a baseline
+    pub fn key(&self) -> String {
+        format!("{}({})", self.message, self.args.join(", "))
+    }
+}
+
+/// Min/max/mean of a series of samples
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub min: u64,
+    pub max: u64,
+    pub mean: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[u64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let sum: u128 = samples.iter().map(|s| *s as u128).sum();
+        Self {
+            min: *samples.iter().min().unwrap(),
+            max: *samples.iter().max().unwrap(),
+            mean: (sum / samples.len() as u128) as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub message: String,
+    pub args: Vec<String>,
+    pub iterations: u32,
+    pub ref_time: Stats,
+    pub proof_size: Stats,
+    pub storage_deposit: i128,
+    pub reverted: bool,
+}
+
+impl BenchResult {
+    pub fn key(&self) -> String {
+        format!("{}({})", self.message, self.args.join(", "))
+    }
+}
+
+/// Full benchmark report, also used as the on-disk baseline format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub contract: String,
+    pub network: String,
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchReport {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse baseline: {}", path.display()))
+    }
+
+    /// Render the report as a markdown table, with deltas against a baseline if given
+    pub fn to_markdown(&self, baseline: Option<&BenchReport>) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("# Benchmark: {}\n\n", self.contract));
+        out.push_str(&format!("Network: `{}`\n\n", self.network));
+
+        if baseline.is_some() {
+            out.push_str("| Message | Iterations | refTime (mean) | Δ refTime | proofSize (mean) | Δ proofSize | Storage deposit |\n");
+            out.push_str("|---|---|---|---|---|---|---|\n");
+        } else {
+            out.push_str("| Message | Iterations | refTime (min / mean / max) | proofSize (min / mean / max) | Storage deposit |\n");
+            out.push_str("|---|---|---|---|---|\n");
+        }
+
+        for result in &self.results {
+            let mut name = result.key();
+            if result.reverted {
+                name.push_str(" (reverted)");
+            }
+
+            match baseline {
+                Some(baseline) => {
+                    let previous = baseline.results.iter().find(|r| r.key() == result.key());
+                    out.push_str(&format!(
+                        "| `{}` | {} | {} | {} | {} | {} | {} |\n",
+                        name,
+                        result.iterations,
+                        result.ref_time.mean,
+                        delta(result.ref_time.mean, previous.map(|p| p.ref_time.mean)),
+                        result.proof_size.mean,
+                        delta(result.proof_size.mean, previous.map(|p| p.proof_size.mean)),
+                        result.storage_deposit,
+                    ));
+                }
+                None => {
+                    out.push_str(&format!(
+                        "| `{}` | {} | {} / {} / {} | {} / {} / {} | {} |\n",
+                        name,
+                        result.iterations,
+                        result.ref_time.min,
+                        result.ref_time.mean,
+                        result.ref_time.max,
+                        result.proof_size.min,
+                        result.proof_size.mean,
+                        result.proof_size.max,
+                        result.storage_deposit,
+                    ));
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// Format the relative change between a value and its baseline
+fn delta(current: u64, baseline: Option<u64>) -> String {
+    match baseline {
+        None => "new".to_string(),
+        Some(0) if current == 0 => "0.0%".to_string(),
+        Some(0) => "+∞".to_string(),
+        Some(base) => {
+            let change = (current as f64 - base as f64) / base as f64 * 100.0;
+            format!("{:+.1}%", change)
+        }
+    }
+}
+
+/// Build the list of benchmark cases from the bench file and contract metadata
+pub fn plan_cases(metadata: &InkProject, file: &BenchFile) -> Result<Vec<BenchCase>> {
+    let mut cases = Vec::new();
+
+    for (message, arg_sets) in &file.messages {
+        // Fail early on typos in the bench file
+        contract::metadata::get_message_spec(metadata, message)?;
+
+        for args in arg_sets {
+            cases.push(BenchCase {
+                message: message.clone(),
+                args: args.clone(),
+            });
+        }
+    }
+
+    for message in metadata.spec().messages() {
+        let label = message.label().to_string();
+        if message.args().is_empty() && !file.messages.contains_key(&label) {
+            cases.push(BenchCase {
+                message: label,
+                args: Vec::new(),
+            });
+        }
+    }
+
+    Ok(cases)
+}
+
+/// Dry-run a case `iterations` times and collect weight statistics
+pub async fn run_case(
+    rpc_url: &str,
+    origin: &AccountId32,
+    contract_address: &str,
+    metadata: &InkProject,
+    case: &BenchCase,
+    iterations: u32,
+) -> Result<BenchResult> {
+    let args = substitute_args(&case.args)?;
+
+    let mut ref_times = Vec::with_capacity(iterations as usize);
+    let mut proof_sizes = Vec::with_capacity(iterations as usize);
+    let mut storage_deposit = 0;
+    let mut reverted = false;
+
+    for _ in 0..iterations {
+        let result = contract::dry_run_call(
+            rpc_url,
+            origin,
+            contract_address,
+            metadata,
+            &case.message,
+            args.clone(),
+            0,
+        )
+        .await?;
+
+        if !result.success && !result.reverted {
+            anyhow::bail!(
+                "{} failed: {}",
+                case.key(),
+                result.error.unwrap_or_else(|| "Unknown error".to_string())
+            );
+        }
+
+        let Weight {
+            ref_time,
+            proof_size,
+        } = result.gas_consumed;
+        ref_times.push(ref_time);
+        proof_sizes.push(proof_size);
+        storage_deposit = result.storage_deposit;
+        reverted = result.reverted;
+    }
+
+    Ok(BenchResult {
+        message: case.message.clone(),
+        args: case.args.clone(),
+        iterations,
+        ref_time: Stats::from_samples(&ref_times),
+        proof_size: Stats::from_samples(&proof_sizes),
+        storage_deposit,
+        reverted,
+    })
+}
+
+/// Replace `$account` arguments with dev account addresses
+fn substitute_args(args: &[String]) -> Result<Vec<String>> {
+    args.iter()
+        .map(|arg| match arg.strip_prefix('$') {
+            Some(name) => {
+                let pair = glin_client::get_dev_account(name)
+                    .with_context(|| format!("Unknown variable: {}", arg))?;
+                Ok(glin_client::get_address(&pair))
+            }
+            None => Ok(arg.clone()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(message: &str, ref_time: u64, proof_size: u64) -> BenchResult {
+        let stats = |v| Stats {
+            min: v,
+            max: v,
+            mean: v,
+        };
+        BenchResult {
+            message: message.to_string(),
+            args: vec![],
+            iterations: 1,
+            ref_time: stats(ref_time),
+            proof_size: stats(proof_size),
+            storage_deposit: 0,
+            reverted: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_bench_file() {
+        let yaml = r#"
+iterations: 5
+messages:
+  transfer:
+    - ["$bob", "100"]
+  total_supply: [[]]
+"#;
+        let file: BenchFile = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(file.iterations, Some(5));
+        assert_eq!(file.messages["transfer"][0], vec!["$bob", "100"]);
+        assert!(file.messages["total_supply"][0].is_empty());
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(&[10, 20, 30]);
+        assert_eq!(stats.min, 10);
+        assert_eq!(stats.max, 30);
+        assert_eq!(stats.mean, 20);
+        assert_eq!(Stats::from_samples(&[]), Stats::default());
+    }
+
+    #[test]
+    fn test_markdown_baseline_delta() {
+        let baseline = BenchReport {
+            contract: "5Grw".to_string(),
+            network: "local".to_string(),
+            results: vec![result("get", 1000, 100)],
+        };
+        let current = BenchReport {
+            contract: "5Grw".to_string(),
+            network: "local".to_string(),
+            results: vec![result("get", 1100, 100), result("flip", 500, 50)],
+        };
+
+        let markdown = current.to_markdown(Some(&baseline));
+        assert!(markdown.contains("| `get()` | 1 | 1100 | +10.0% | 100 | +0.0% | 0 |"));
+        assert!(markdown.contains("| `flip()` | 1 | 500 | new | 50 | new | 0 |"));
+    }
+}
@@ -0,0 +1,6 @@
+// Test runners that go beyond `cargo test`
+
+pub mod bench;
+pub mod scenario;
+
+pub use scenario::{load_scenarios, Scenario, ScenarioRunner, StepOutcome};
@@ -0,0 +1,667 @@
+// Declarative YAML scenario tests
+//
+// A scenario is a sequence of deploy/call/query steps with expectations, e.g.:
+//
+//   name: Token transfer
+//   steps:
+//     - deploy:
+//         name: token
+//         wasm: artifacts/token/token.wasm
+//         metadata: artifacts/token/token.json
+//         args: ["1000000"]
+//     - call:
+//         contract: token
+//         method: transfer
+//         args: ["$bob", "100"]
+//         expect_events:
+//           - Transfer: { from: "$alice", to: "$bob", value: 100 }
+//     - query:
+//         contract: token
+//         method: balance_of
+//         args: ["$bob"]
+//         expect: "100"
+//
+// Arguments starting with `$` are substituted with the address of a dev account
+// (`$alice`) or of a contract deployed earlier in the same scenario (`$token`).
+// Expected events name a chain event (`ContractEmitted`, `Contracts.Called`) or
+// a contract event decoded with the metadata of its emitter, optionally with
+// the values of some of its fields.
+
+use crate::contract::events::{DecodedEvent, EmittedEvent, EventDecoder};
+use anyhow::{Context, Result};
+use glin_client::GlinClient;
+use ink_metadata::InkProject;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use subxt::utils::AccountId32;
+
+/// A single scenario file
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Network override for this scenario
+    #[serde(default)]
+    pub network: Option<String>,
+
+    #[serde(with = "serde_yaml::with::singleton_map_recursive")]
+    pub steps: Vec<Step>,
+}
+
+/// One step of a scenario
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    Deploy(DeployStep),
+    Call(CallStep),
+    Query(QueryStep),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeployStep {
+    /// Alias used to reference the contract in later steps
+    pub name: String,
+    pub wasm: PathBuf,
+    pub metadata: PathBuf,
+    #[serde(default)]
+    pub constructor: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_account")]
+    pub from: String,
+    #[serde(default)]
+    pub value: u128,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CallStep {
+    pub contract: String,
+    pub method: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_account")]
+    pub from: String,
+    #[serde(default)]
+    pub value: u128,
+    /// Events that must be emitted by the call
+    #[serde(default)]
+    pub expect_events: Vec<ExpectedEvent>,
+    /// Expect the call to fail instead of succeed
+    #[serde(default)]
+    pub expect_error: bool,
+}
+
+/// An event a call must emit: a chain event by variant or `Pallet.Variant`, or a
+/// decoded contract event by label, with the values of the fields listed
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "RawExpectedEvent")]
+pub struct ExpectedEvent {
+    pub name: String,
+    pub fields: Vec<(String, JsonValue)>,
+}
+
+/// `Transfer` or `Transfer: { to: "$bob", value: 100 }`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawExpectedEvent {
+    Name(String),
+    Fields(BTreeMap<String, BTreeMap<String, serde_yaml::Value>>),
+}
+
+impl TryFrom<RawExpectedEvent> for ExpectedEvent {
+    type Error = String;
+
+    fn try_from(raw: RawExpectedEvent) -> std::result::Result<Self, Self::Error> {
+        match raw {
+            RawExpectedEvent::Name(name) => Ok(Self {
+                name,
+                fields: Vec::new(),
+            }),
+            RawExpectedEvent::Fields(map) if map.len() == 1 => {
+                let (name, fields) = map.into_iter().next().unwrap_or_default();
+                let fields = fields
+                    .into_iter()
+                    .map(|(field, value)| Ok((field, serde_json::to_value(value)?)))
+                    .collect::<std::result::Result<_, serde_json::Error>>()
+                    .map_err(|e| e.to_string())?;
+                Ok(Self { name, fields })
+            }
+            RawExpectedEvent::Fields(_) => {
+                Err("an expected event with fields names exactly one event".to_string())
+            }
+        }
+    }
+}
+
+impl fmt::Display for ExpectedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect();
+        write!(f, " {{ {} }}", fields.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct QueryStep {
+    pub contract: String,
+    pub method: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Expected decoded return value
+    #[serde(default)]
+    pub expect: Option<serde_yaml::Value>,
+}
+
+fn default_account() -> String {
+    "alice".to_string()
+}
+
+impl Step {
+    /// Short human-readable label for reports
+    pub fn label(&self) -> String {
+        match self {
+            Step::Deploy(step) => format!("deploy {}", step.name),
+            Step::Call(step) => format!("call {}.{}", step.contract, step.method),
+            Step::Query(step) => format!("query {}.{}", step.contract, step.method),
+        }
+    }
+}
+
+/// Outcome of executing a single step
+#[derive(Debug, Clone)]
+pub struct StepOutcome {
+    pub label: String,
+    /// Failure description (including diffs), `None` if the step passed
+    pub failure: Option<String>,
+}
+
+impl StepOutcome {
+    pub fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Load all `*.yaml`/`*.yml` scenario files from a directory, sorted by file name
+pub fn load_scenarios(dir: &Path) -> Result<Vec<(PathBuf, Scenario)>> {
+    if !dir.exists() {
+        anyhow::bail!("Scenario directory not found: {}", dir.display());
+    }
+
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if path.is_file() && (ext == "yaml" || ext == "yml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read scenario: {}", path.display()))?;
+            let scenario: Scenario = serde_yaml::from_str(&content)
+                .with_context(|| format!("Invalid scenario file: {}", path.display()))?;
+            Ok((path, scenario))
+        })
+        .collect()
+}
+
+struct DeployedContract {
+    address: String,
+    account: Option<AccountId32>,
+    metadata: InkProject,
+    events: EventDecoder,
+}
+
+/// Executes scenarios against a live node
+pub struct ScenarioRunner {
+    client: GlinClient,
+    rpc_url: String,
+    /// Base directory that artifact paths are resolved against
+    base_dir: PathBuf,
+    deployed: HashMap<String, DeployedContract>,
+}
+
+impl ScenarioRunner {
+    pub async fn connect(rpc_url: &str, base_dir: &Path) -> Result<Self> {
+        let client = glin_client::create_client(rpc_url)
+            .await
+            .with_context(|| format!("Failed to connect to network: {}", rpc_url))?;
+
+        Ok(Self {
+            client,
+            rpc_url: rpc_url.to_string(),
+            base_dir: base_dir.to_path_buf(),
+            deployed: HashMap::new(),
+        })
+    }
+
+    /// Run every step of a scenario, stopping at the first failure
+    pub async fn run(&mut self, scenario: &Scenario) -> Vec<StepOutcome> {
+        self.deployed.clear();
+
+        let mut outcomes = Vec::new();
+        for step in &scenario.steps {
+            let failure = match self.run_step(step).await {
+                Ok(failure) => failure,
+                Err(e) => Some(format!("{:#}", e)),
+            };
+            let stop = failure.is_some();
+
+            outcomes.push(StepOutcome {
+                label: step.label(),
+                failure,
+            });
+
+            if stop {
+                break;
+            }
+        }
+
+        outcomes
+    }
+
+    /// Execute a step, returning `Some(reason)` when an expectation is not met
+    async fn run_step(&mut self, step: &Step) -> Result<Option<String>> {
+        match step {
+            Step::Deploy(step) => {
+                let wasm = std::fs::read(self.base_dir.join(&step.wasm))
+                    .with_context(|| format!("Failed to read WASM: {}", step.wasm.display()))?;
+                let metadata_json = std::fs::read_to_string(self.base_dir.join(&step.metadata))
+                    .with_context(|| {
+                        format!("Failed to read metadata: {}", step.metadata.display())
+                    })?;
+                let metadata = crate::contract::metadata::parse_metadata(&metadata_json)?;
+                let events = EventDecoder::new(&serde_json::from_str(&metadata_json)?)?;
+                let signer = glin_client::get_dev_account(&step.from)?;
+                let args = self.substitute_args(&step.args)?;
+
+                let result = crate::contract::deploy_contract(
+                    &self.client,
+                    wasm,
+                    &metadata,
+                    args,
+                    step.constructor.as_deref(),
+                    step.value,
+                    None,
+                    &signer,
+                )
+                .await?;
+
+                let address = result
+                    .contract_address
+                    .context("Deployment did not emit an Instantiated event")?;
+
+                let account = crate::contract::parse_account_id(&address).ok();
+                self.deployed.insert(
+                    step.name.clone(),
+                    DeployedContract {
+                        address,
+                        account,
+                        metadata,
+                        events,
+                    },
+                );
+
+                Ok(None)
+            }
+            Step::Call(step) => {
+                let contract = self.contract(&step.contract)?;
+                let signer = glin_client::get_dev_account(&step.from)?;
+                let args = self.substitute_args(&step.args)?;
+
+                let result = crate::contract::call_contract(
+                    &self.client,
+                    &contract.address,
+                    &contract.metadata,
+                    &step.method,
+                    args,
+                    step.value,
+                    &signer,
+                )
+                .await;
+
+                match (result, step.expect_error) {
+                    (Ok(_), true) => Ok(Some("Expected call to fail, but it succeeded".into())),
+                    (Err(_), true) => Ok(None),
+                    (Err(e), false) => Err(e),
+                    (Ok(tx), false) => {
+                        let decoded = decode_contract_events(&tx.contract_events, |emitter| {
+                            self.deployed
+                                .values()
+                                .find(|c| c.account.as_ref() == Some(emitter))
+                                .map(|c| &c.events)
+                        });
+
+                        let mut missing = Vec::new();
+                        for expected in &step.expect_events {
+                            let expected = self.substitute_event(expected)?;
+                            if !event_emitted(&expected, &tx.events, &decoded) {
+                                missing.push(expected.to_string());
+                            }
+                        }
+
+                        if missing.is_empty() {
+                            Ok(None)
+                        } else {
+                            let mut emitted = tx.events.clone();
+                            emitted.extend(decoded.iter().map(DecodedEvent::to_string));
+                            Ok(Some(format!(
+                                "Missing expected events: {:?}\n  emitted: {:?}",
+                                missing, emitted
+                            )))
+                        }
+                    }
+                }
+            }
+            Step::Query(step) => {
+                let contract = self.contract(&step.contract)?;
+                let args = self.substitute_args(&step.args)?;
+
+                let result = crate::contract::query_contract(
+                    &self.client,
+                    &self.rpc_url,
+                    &contract.address,
+                    &contract.metadata,
+                    &step.method,
+                    args,
+                )
+                .await?;
+
+                if !result.success {
+                    return Ok(Some(format!(
+                        "Query failed: {}",
+                        result.error.unwrap_or_else(|| "Unknown error".to_string())
+                    )));
+                }
+
+                let Some(expected) = &step.expect else {
+                    return Ok(None);
+                };
+
+                let expected = serde_json::to_value(expected)?;
+                let actual: JsonValue = result
+                    .data
+                    .as_deref()
+                    .map(serde_json::from_str)
+                    .transpose()?
+                    .unwrap_or(JsonValue::Null);
+
+                if values_match(&expected, &actual) {
+                    Ok(None)
+                } else {
+                    Ok(Some(format!(
+                        "Unexpected query result:\n{}",
+                        diff_values(&expected, &actual)
+                    )))
+                }
+            }
+        }
+    }
+
+    fn contract(&self, name: &str) -> Result<&DeployedContract> {
+        self.deployed
+            .get(name)
+            .with_context(|| format!("Contract '{}' has not been deployed in this scenario", name))
+    }
+
+    /// Replace `$name` arguments with dev account or deployed contract addresses
+    fn substitute_args(&self, args: &[String]) -> Result<Vec<String>> {
+        args.iter().map(|arg| self.substitute(arg)).collect()
+    }
+
+    /// Substitute the `$name` field values of an expected event
+    fn substitute_event(&self, expected: &ExpectedEvent) -> Result<ExpectedEvent> {
+        let fields = expected
+            .fields
+            .iter()
+            .map(|(name, value)| match value {
+                JsonValue::String(s) => Ok((name.clone(), JsonValue::String(self.substitute(s)?))),
+                other => Ok((name.clone(), other.clone())),
+            })
+            .collect::<Result<_>>()?;
+        Ok(ExpectedEvent {
+            name: expected.name.clone(),
+            fields,
+        })
+    }
+
+    fn substitute(&self, arg: &str) -> Result<String> {
+        match arg.strip_prefix('$') {
+            Some(name) => {
+                if let Some(contract) = self.deployed.get(name) {
+                    Ok(contract.address.clone())
+                } else {
+                    let pair = glin_client::get_dev_account(name)
+                        .with_context(|| format!("Unknown variable: {}", arg))?;
+                    Ok(glin_client::get_address(&pair))
+                }
+            }
+            None => Ok(arg.to_string()),
+        }
+    }
+}
+
+/// Decode the `ContractEmitted` payloads of contracts with a known decoder
+fn decode_contract_events<'a>(
+    emitted: &[EmittedEvent],
+    decoder: impl Fn(&AccountId32) -> Option<&'a EventDecoder>,
+) -> Vec<DecodedEvent> {
+    emitted
+        .iter()
+        .filter_map(|event| {
+            decoder(&event.contract)?
+                .decode(&event.topics, &event.data)
+                .ok()
+        })
+        .collect()
+}
+
+/// Whether an expectation is met by a decoded contract event with the expected field
+/// values, or, when it lists no fields, by a chain event
+fn event_emitted(expected: &ExpectedEvent, emitted: &[String], decoded: &[DecodedEvent]) -> bool {
+    let contract_event = decoded.iter().any(|event| {
+        event.name == expected.name
+            && expected.fields.iter().all(|(name, value)| {
+                event
+                    .fields
+                    .iter()
+                    .any(|(field, actual)| field == name && values_match(value, actual))
+            })
+    });
+    contract_event
+        || (expected.fields.is_empty() && emitted.iter().any(|e| event_matches(e, &expected.name)))
+}
+
+/// Check whether an emitted `Pallet.Variant` event satisfies an expectation
+fn event_matches(emitted: &str, expected: &str) -> bool {
+    emitted == expected || emitted.rsplit('.').next() == Some(expected)
+}
+
+/// Compare expected and actual values, treating numbers and numeric strings as equal
+fn values_match(expected: &JsonValue, actual: &JsonValue) -> bool {
+    match (expected, actual) {
+        (JsonValue::Number(n), JsonValue::String(s))
+        | (JsonValue::String(s), JsonValue::Number(n)) => n.to_string() == *s,
+        _ => expected == actual,
+    }
+}
+
+/// Render a line-based diff between expected and actual values
+fn diff_values(expected: &JsonValue, actual: &JsonValue) -> String {
+    let expected = serde_json::to_string_pretty(expected).unwrap_or_default();
+    let actual = serde_json::to_string_pretty(actual).unwrap_or_default();
+
+    let mut output = String::new();
+    for line in expected.lines() {
+        output.push_str(&format!("  - {}\n", line));
+    }
+    for line in actual.lines() {
+        output.push_str(&format!("  + {}\n", line));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scenario() {
+        let yaml = r#"
+name: Token transfer
+steps:
+  - deploy:
+      name: token
+      wasm: token.wasm
+      metadata: token.json
+      args: ["1000"]
+  - call:
+      contract: token
+      method: transfer
+      args: ["$bob", "100"]
+      expect_events:
+        - ContractEmitted
+        - Transfer: { to: "$bob", value: 100 }
+  - query:
+      contract: token
+      method: balance_of
+      args: ["$bob"]
+      expect: 100
+"#;
+        let scenario: Scenario = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(scenario.name, "Token transfer");
+        assert_eq!(scenario.steps.len(), 3);
+        assert_eq!(scenario.steps[1].label(), "call token.transfer");
+
+        match &scenario.steps[0] {
+            Step::Deploy(step) => assert_eq!(step.from, "alice"),
+            _ => panic!("Expected deploy step"),
+        }
+        match &scenario.steps[1] {
+            Step::Call(step) => {
+                assert_eq!(step.expect_events[0].name, "ContractEmitted");
+                assert_eq!(
+                    step.expect_events[1].fields,
+                    vec![
+                        ("to".to_string(), serde_json::json!("$bob")),
+                        ("value".to_string(), serde_json::json!(100)),
+                    ]
+                );
+            }
+            _ => panic!("Expected call step"),
+        }
+    }
+
+    #[test]
+    fn test_values_match_numeric_strings() {
+        assert!(values_match(
+            &serde_json::json!(100),
+            &serde_json::json!("100")
+        ));
+        assert!(!values_match(
+            &serde_json::json!(100),
+            &serde_json::json!("101")
+        ));
+        assert!(values_match(
+            &serde_json::json!(true),
+            &serde_json::json!(true)
+        ));
+    }
+
+    #[test]
+    fn test_event_matches() {
+        assert!(event_matches(
+            "Contracts.ContractEmitted",
+            "ContractEmitted"
+        ));
+        assert!(event_matches("Contracts.Called", "Contracts.Called"));
+        assert!(!event_matches("Contracts.Called", "ContractEmitted"));
+    }
+
+    #[test]
+    fn test_decoded_contract_event() {
+        use scale::Encode;
+
+        // ink! 4 layout: the event index prefixes the data
+        let decoder = EventDecoder::new(&serde_json::json!({
+            "spec": { "events": [{
+                "label": "Transfer",
+                "args": [
+                    { "label": "to", "indexed": true, "type": { "type": 1 } },
+                    { "label": "value", "indexed": false, "type": { "type": 2 } }
+                ]
+            }] },
+            "types": [
+                { "id": 0, "type": { "def": { "primitive": "u8" } } },
+                { "id": 1, "type": {
+                    "path": ["ink_primitives", "types", "AccountId"],
+                    "def": { "composite": { "fields": [{ "type": 3, "typeName": "[u8; 32]" }] } }
+                } },
+                { "id": 2, "type": { "def": { "primitive": "u128" } } },
+                { "id": 3, "type": { "def": { "array": { "len": 32, "type": 0 } } } }
+            ]
+        }))
+        .unwrap();
+
+        let token = AccountId32([1; 32]);
+        let bob = AccountId32([0x8e; 32]);
+        let mut data = vec![0u8];
+        bob.0.encode_to(&mut data);
+        100u128.encode_to(&mut data);
+        let emitted = [
+            EmittedEvent {
+                contract: token.clone(),
+                topics: Vec::new(),
+                data,
+            },
+            EmittedEvent {
+                contract: AccountId32([2; 32]),
+                topics: Vec::new(),
+                data: vec![0],
+            },
+        ];
+
+        let decoded =
+            decode_contract_events(&emitted, |emitter| (*emitter == token).then_some(&decoder));
+        assert_eq!(decoded.len(), 1);
+        let chain = ["Contracts.ContractEmitted".to_string()];
+
+        let expect = |yaml: &str| serde_yaml::from_str::<ExpectedEvent>(yaml).unwrap();
+        let mut transfer = expect("Transfer: { value: 100 }");
+        transfer
+            .fields
+            .push(("to".to_string(), serde_json::json!(bob.to_string())));
+        assert!(event_emitted(&transfer, &chain, &decoded));
+        assert!(event_emitted(&expect("Transfer"), &chain, &decoded));
+        assert!(event_emitted(&expect("ContractEmitted"), &chain, &decoded));
+        assert!(!event_emitted(
+            &expect("Transfer: { value: 99 }"),
+            &chain,
+            &decoded
+        ));
+        assert!(!event_emitted(&expect("Approval"), &chain, &decoded));
+        assert!(!event_emitted(
+            &expect("ContractEmitted: { value: 100 }"),
+            &chain,
+            &decoded
+        ));
+        assert!(serde_yaml::from_str::<ExpectedEvent>("{ A: {}, B: {} }").is_err());
+    }
+}